@@ -0,0 +1,89 @@
+//! GitHub Actions workflow command output.
+//!
+//! When the CLI runs inside a GitHub Actions job, diagnostics are printed as
+//! [workflow commands] so that they appear as annotations in the Actions UI.
+//!
+//! | [`DiagnosticSeverity`] | Command |
+//! |------------------------|---------|
+//! | `Blocking` | `::error` |
+//! | `Warning` | `::warning` |
+//! | `Informational` | `::notice` |
+//!
+//! [workflow commands]: https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions
+
+use pipeline::{Diagnostic, DiagnosticSeverity};
+
+/// Environment variable GitHub Actions sets to `"true"` on every runner.
+pub const GITHUB_ACTIONS_ENV: &str = "GITHUB_ACTIONS";
+
+/// Returns `true` if the process is running inside a GitHub Actions job.
+#[must_use]
+pub fn running_in_github_actions() -> bool {
+    is_github_actions_value(std::env::var(GITHUB_ACTIONS_ENV).ok().as_deref())
+}
+
+/// Returns `true` if `value` (the value of [`GITHUB_ACTIONS_ENV`]) indicates
+/// a GitHub Actions runner.
+#[must_use]
+pub fn is_github_actions_value(value: Option<&str>) -> bool {
+    value == Some("true")
+}
+
+/// Formats a [`Diagnostic`] as a single workflow command line.
+///
+/// The artefact path becomes the `file` property and the category (plus the
+/// location, when present) becomes the `title`.
+#[must_use]
+pub fn diagnostic_command(diagnostic: &Diagnostic) -> String {
+    let command = match diagnostic.severity {
+        DiagnosticSeverity::Blocking => "error",
+        DiagnosticSeverity::Warning => "warning",
+        DiagnosticSeverity::Informational => "notice",
+    };
+
+    let title = match &diagnostic.location {
+        Some(location) => format!("{} ({location})", diagnostic.category),
+        None => diagnostic.category.to_string(),
+    };
+
+    let mut properties = Vec::with_capacity(2);
+    if let Some(artifact) = &diagnostic.artifact {
+        properties.push(format!("file={}", escape_property(artifact.as_str())));
+    }
+    properties.push(format!("title={}", escape_property(&title)));
+
+    format!(
+        "::{command} {}::{}",
+        properties.join(","),
+        escape_data(&diagnostic.message)
+    )
+}
+
+/// Returns the command that opens a collapsible log group.
+#[must_use]
+pub fn group_start(title: &str) -> String {
+    format!("::group::{}", escape_data(title))
+}
+
+/// Returns the command that closes the innermost log group.
+#[must_use]
+pub fn group_end() -> &'static str {
+    "::endgroup::"
+}
+
+/// Escapes the message part of a workflow command.
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a `key=value` property of a workflow command.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+#[path = "actions_tests.rs"]
+mod tests;
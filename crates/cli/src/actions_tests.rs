@@ -0,0 +1,71 @@
+use pipeline::{ArtifactPath, DiagnosticCategory};
+
+use super::*;
+
+fn diagnostic(severity: DiagnosticSeverity) -> Diagnostic {
+    Diagnostic {
+        artifact: Some(ArtifactPath::new("src/lib.rs").unwrap()),
+        location: Some("line 3".to_string()),
+        severity,
+        category: DiagnosticCategory::new("type_error").unwrap(),
+        message: "mismatched types".to_string(),
+    }
+}
+
+#[test]
+fn test_is_github_actions_value_true_returns_true() {
+    assert!(is_github_actions_value(Some("true")));
+}
+
+#[test]
+fn test_is_github_actions_value_other_or_unset_returns_false() {
+    assert!(!is_github_actions_value(Some("false")));
+    assert!(!is_github_actions_value(Some("1")));
+    assert!(!is_github_actions_value(None));
+}
+
+#[test]
+fn test_diagnostic_command_blocking_emits_error_with_file_and_title() {
+    assert_eq!(
+        diagnostic_command(&diagnostic(DiagnosticSeverity::Blocking)),
+        "::error file=src/lib.rs,title=type_error (line 3)::mismatched types"
+    );
+}
+
+#[test]
+fn test_diagnostic_command_severity_maps_to_command() {
+    assert!(diagnostic_command(&diagnostic(DiagnosticSeverity::Warning)).starts_with("::warning "));
+    assert!(
+        diagnostic_command(&diagnostic(DiagnosticSeverity::Informational)).starts_with("::notice ")
+    );
+}
+
+#[test]
+fn test_diagnostic_command_no_artifact_or_location_emits_title_only() {
+    let mut diagnostic = diagnostic(DiagnosticSeverity::Warning);
+    diagnostic.artifact = None;
+    diagnostic.location = None;
+
+    assert_eq!(
+        diagnostic_command(&diagnostic),
+        "::warning title=type_error::mismatched types"
+    );
+}
+
+#[test]
+fn test_diagnostic_command_special_characters_escaped() {
+    let mut diagnostic = diagnostic(DiagnosticSeverity::Blocking);
+    diagnostic.location = Some("a:b, c".to_string());
+    diagnostic.message = "100% done\r\nnext".to_string();
+
+    assert_eq!(
+        diagnostic_command(&diagnostic),
+        "::error file=src/lib.rs,title=type_error (a%3Ab%2C c)::100%25 done%0D%0Anext"
+    );
+}
+
+#[test]
+fn test_group_start_title_escaped() {
+    assert_eq!(group_start("step\n1"), "::group::step%0A1");
+    assert_eq!(group_end(), "::endgroup::");
+}
@@ -0,0 +1,142 @@
+//! Command-line argument parsing.
+//!
+//! The flag set is small, so arguments are parsed by hand rather than through
+//! an argument-parsing framework.
+//!
+//! ```text
+//! cogworks [--issue-url <url>] [--pipeline <name>]
+//! cogworks run-node --node <name> --issue-url <url> [--pipeline <name>]
+//! cogworks validate [--pipeline <name>]
+//! ```
+//!
+//! | Flag | Value | Default |
+//! |------|-------|---------|
+//! | `--issue-url` | GitHub issue URL (single-shot mode); required by `run-node` | none |
+//! | `--pipeline` | Name of the pipeline in `.cogworks/pipeline.toml` | `"default"` |
+//! | `--node` | Node to run (`run-node` only) | none |
+
+use anyhow::{anyhow, bail, Context};
+
+use pipeline::{NodeId, PipelineName, DEFAULT_PIPELINE_NAME};
+
+/// Subcommand name for [`Command::RunNode`].
+const RUN_NODE: &str = "run-node";
+
+/// Subcommand name for [`Command::Validate`].
+const VALIDATE: &str = "validate";
+
+/// What the CLI was asked to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Run the pipeline step function (no subcommand given).
+    Run,
+    /// Run one node in isolation, bypassing edge evaluation, and report its
+    /// outcome. The graph does not advance.
+    RunNode {
+        /// The node to run.
+        node: NodeId,
+    },
+    /// Validate the configuration against the repository without running
+    /// anything (see [`crate::validate`]).
+    Validate,
+}
+
+/// Parsed command-line arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliArgs {
+    /// The selected subcommand.
+    pub command: Command,
+    /// Issue to process in single-shot mode.
+    pub issue_url: Option<String>,
+    /// Pipeline selected from `.cogworks/pipeline.toml`.
+    pub pipeline: PipelineName,
+}
+
+impl CliArgs {
+    /// Parses arguments, excluding the program name.
+    ///
+    /// An optional subcommand comes first. Both `--flag value` and
+    /// `--flag=value` forms are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown subcommands or flags, flags missing a
+    /// value, repeated flags, empty names, `--node` outside `run-node`,
+    /// `run-node` without `--node` or `--issue-url`, and `validate` with
+    /// `--issue-url`.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut args = args.into_iter().peekable();
+
+        let subcommand = match args.peek().map(String::as_str) {
+            Some(name @ (RUN_NODE | VALIDATE)) => {
+                let name = name.to_string();
+                args.next();
+                Some(name)
+            }
+            Some(other) if !other.starts_with("--") => bail!("unknown subcommand '{other}'"),
+            _ => None,
+        };
+        let run_node = subcommand.as_deref() == Some(RUN_NODE);
+
+        let mut issue_url = None;
+        let mut pipeline = None;
+        let mut node = None;
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+
+            let slot = match flag.as_str() {
+                "--issue-url" => &mut issue_url,
+                "--pipeline" => &mut pipeline,
+                "--node" if run_node => &mut node,
+                "--node" => bail!("'--node' is only valid with '{RUN_NODE}'"),
+                other => bail!("unknown argument '{other}'"),
+            };
+            if slot.is_some() {
+                bail!("'{flag}' given more than once");
+            }
+
+            let value = match inline_value {
+                Some(value) => value,
+                None => args
+                    .next()
+                    .ok_or_else(|| anyhow!("'{flag}' requires a value"))?,
+            };
+            *slot = Some(value);
+        }
+
+        let pipeline =
+            PipelineName::new(pipeline.unwrap_or_else(|| DEFAULT_PIPELINE_NAME.to_string()))
+                .context("'--pipeline' must not be empty")?;
+
+        let command = if run_node {
+            let node = node
+                .and_then(NodeId::new)
+                .with_context(|| format!("'{RUN_NODE}' requires a non-empty '--node'"))?;
+            if issue_url.is_none() {
+                bail!("'{RUN_NODE}' requires '--issue-url'");
+            }
+            Command::RunNode { node }
+        } else if subcommand.as_deref() == Some(VALIDATE) {
+            if issue_url.is_some() {
+                bail!("'--issue-url' is not valid with '{VALIDATE}'");
+            }
+            Command::Validate
+        } else {
+            Command::Run
+        };
+
+        Ok(Self {
+            command,
+            issue_url,
+            pipeline,
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "args_tests.rs"]
+mod tests;
@@ -0,0 +1,62 @@
+use super::*;
+
+fn parse(args: &[&str]) -> anyhow::Result<CliArgs> {
+    CliArgs::parse(args.iter().map(|arg| (*arg).to_string()))
+}
+
+#[test]
+fn test_parse_no_arguments_selects_default_pipeline() {
+    let args = parse(&[]).unwrap();
+
+    assert_eq!(args.pipeline.as_str(), DEFAULT_PIPELINE_NAME);
+    assert_eq!(args.issue_url, None);
+}
+
+#[test]
+fn test_parse_pipeline_separate_value_selects_named_pipeline() {
+    let args = parse(&["--pipeline", "hotfix"]).unwrap();
+
+    assert_eq!(args.pipeline.as_str(), "hotfix");
+}
+
+#[test]
+fn test_parse_pipeline_inline_value_selects_named_pipeline() {
+    let args = parse(&[
+        "--pipeline=release",
+        "--issue-url",
+        "https://github.com/o/r/issues/1",
+    ])
+    .unwrap();
+
+    assert_eq!(args.pipeline.as_str(), "release");
+    assert_eq!(
+        args.issue_url.as_deref(),
+        Some("https://github.com/o/r/issues/1")
+    );
+}
+
+#[test]
+fn test_parse_empty_pipeline_returns_error() {
+    assert!(parse(&["--pipeline="]).is_err());
+}
+
+#[test]
+fn test_parse_pipeline_without_value_returns_error() {
+    let error = parse(&["--pipeline"]).unwrap_err();
+
+    assert!(error.to_string().contains("requires a value"));
+}
+
+#[test]
+fn test_parse_repeated_pipeline_returns_error() {
+    let error = parse(&["--pipeline", "a", "--pipeline", "b"]).unwrap_err();
+
+    assert!(error.to_string().contains("more than once"));
+}
+
+#[test]
+fn test_parse_unknown_flag_returns_error() {
+    let error = parse(&["--verbose"]).unwrap_err();
+
+    assert!(error.to_string().contains("unknown argument '--verbose'"));
+}
@@ -0,0 +1,62 @@
+//! HTTP delivery of audit events to an external collector.
+//!
+//! [`HttpCollectorTransport`] is the production
+//! [`nodes::audit_collector::CollectorTransport`]: it POSTs each event as a
+//! JSON body. Queueing, retries, and the guarantee that collector failures
+//! never fail a run live in `nodes::audit_collector`.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/operations.md` §Audit Trail on GitHub.
+
+use async_trait::async_trait;
+
+use nodes::audit_collector::{CollectedAuditEvent, CollectorError, CollectorTransport};
+
+/// [`CollectorTransport`] over a shared `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct HttpCollectorTransport {
+    client: reqwest::Client,
+}
+
+impl HttpCollectorTransport {
+    /// Creates a transport with a default `reqwest::Client`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a transport over an existing client (e.g. one with a timeout
+    /// or proxy settings).
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl CollectorTransport for HttpCollectorTransport {
+    async fn post(
+        &self,
+        endpoint: &str,
+        event: &CollectedAuditEvent,
+    ) -> Result<(), CollectorError> {
+        let body = serde_json::to_vec(event).map_err(|e| CollectorError::Unreachable {
+            message: format!("failed to serialise audit event: {e}"),
+        })?;
+        let response = self
+            .client
+            .post(endpoint)
+            .header("content-type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| CollectorError::Unreachable {
+                message: e.to_string(),
+            })?;
+        let status = response.status().as_u16();
+        if (200..300).contains(&status) {
+            Ok(())
+        } else {
+            Err(CollectorError::Rejected { status })
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! HTTP delivery of escalation notifications.
+//!
+//! [`HttpWebhookTransport`] is the production
+//! [`nodes::escalation::WebhookTransport`]: it POSTs each
+//! [`EscalationPayload`] as a JSON body. Deciding when to escalate, and the
+//! guarantee that a failed notification never fails a run, live in
+//! `nodes::escalation`.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Escalation.
+
+use async_trait::async_trait;
+
+use nodes::escalation::{EscalationPayload, WebhookTransport};
+
+/// [`WebhookTransport`] over a shared `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct HttpWebhookTransport {
+    client: reqwest::Client,
+}
+
+impl HttpWebhookTransport {
+    /// Creates a transport with a default `reqwest::Client`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a transport over an existing client (e.g. one with a timeout
+    /// or proxy settings).
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl WebhookTransport for HttpWebhookTransport {
+    async fn post_json(&self, url: &str, payload: &EscalationPayload) -> Result<u16, String> {
+        let body = serde_json::to_vec(payload)
+            .map_err(|e| format!("failed to serialise escalation payload: {e}"))?;
+        let response = self
+            .client
+            .post(url)
+            .header("content-type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(response.status().as_u16())
+    }
+}
@@ -0,0 +1,470 @@
+//! Destinations for structured events.
+//!
+//! Not every deployment runs an OpenTelemetry collector. The `[[events.sinks]]`
+//! entries in `.cogworks/config.toml` choose where the observability layer
+//! sends structured events: an OTLP endpoint, a local file, standard output,
+//! or any combination. [`build_sinks`] turns that configuration into one
+//! [`FanOutSink`], which forwards every [`StructuredEvent`] to each chosen
+//! sink.
+//!
+//! The file and stdout sinks write one JSON object per line (JSONL), so the
+//! output can be tailed, grepped, or loaded with `jq -s`.
+//!
+//! The OTLP sink buffers events as OTLP log records and exports them in
+//! batches of up to [`OTLP_BATCH_SIZE`] as OTLP/HTTP JSON
+//! (`POST {endpoint}/v1/logs`), through an [`OtlpExporter`]. The production
+//! [`HttpOtlpExporter`] sends each batch on the ambient tokio runtime without
+//! waiting for the collector, so a slow or absent collector never blocks the
+//! code emitting events.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/operations.md` §Event Sink Configuration.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    mem,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value as JsonValue};
+
+use pipeline::Timestamp;
+
+/// Default OTLP collector endpoint (the collector's OTLP/HTTP port).
+pub const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4318";
+
+/// Path of the OTLP/HTTP logs export, appended to the endpoint.
+pub const OTLP_LOGS_PATH: &str = "/v1/logs";
+
+/// Events [`OtlpSink`] buffers before exporting them without waiting for a
+/// flush.
+pub const OTLP_BATCH_SIZE: usize = 512;
+
+/// `service.name` resource attribute of exported log records.
+pub const OTLP_SERVICE_NAME: &str = "cogworks";
+
+// ─── Events ─────────────────────────────────────────────────────────────────
+
+/// One structured event, as written to a sink.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredEvent {
+    /// When the event was emitted.
+    pub timestamp: Timestamp,
+    /// Level name (`"ERROR"`, `"WARN"`, `"INFO"`, `"DEBUG"`, `"TRACE"`).
+    pub level: String,
+    /// Module path of the code that emitted the event.
+    pub target: String,
+    /// The event message.
+    pub message: String,
+    /// Remaining structured fields of the event.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, JsonValue>,
+}
+
+// ─── Configuration ──────────────────────────────────────────────────────────
+
+/// One configured sink (`[[events.sinks]]`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EventSinkConfig {
+    /// Export to an OpenTelemetry collector.
+    Otlp {
+        /// Collector endpoint.
+        #[serde(default = "default_otlp_endpoint")]
+        endpoint: String,
+    },
+    /// Append JSONL to a file, creating it if needed.
+    File {
+        /// Path of the file.
+        path: PathBuf,
+    },
+    /// Write JSONL to standard output.
+    Stdout,
+}
+
+fn default_otlp_endpoint() -> String {
+    DEFAULT_OTLP_ENDPOINT.to_string()
+}
+
+/// The `[events]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventsConfig {
+    /// Sinks every event is sent to.
+    #[serde(default = "default_sinks")]
+    pub sinks: Vec<EventSinkConfig>,
+}
+
+fn default_sinks() -> Vec<EventSinkConfig> {
+    vec![EventSinkConfig::Otlp {
+        endpoint: default_otlp_endpoint(),
+    }]
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            sinks: default_sinks(),
+        }
+    }
+}
+
+// ─── Sinks ──────────────────────────────────────────────────────────────────
+
+/// A destination for structured events.
+pub trait EventSink: Send + Sync {
+    /// Writes one event.
+    ///
+    /// # Errors
+    ///
+    /// The I/O or export error that prevented the write.
+    fn emit(&self, event: &StructuredEvent) -> io::Result<()>;
+
+    /// Flushes buffered events.
+    ///
+    /// # Errors
+    ///
+    /// The I/O or export error that prevented the flush.
+    fn flush(&self) -> io::Result<()>;
+}
+
+/// Writes each event as one JSON line to `W`.
+pub struct JsonlSink<W> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonlSink<W> {
+    /// Creates a sink writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// Consumes the sink and returns its writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl JsonlSink<File> {
+    /// Opens `path` for appending, creating it if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// The error from opening the file.
+    pub fn open(path: &std::path::Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(file))
+    }
+}
+
+impl JsonlSink<io::Stdout> {
+    /// Creates a sink writing to standard output.
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl<W: Write + Send> EventSink for JsonlSink<W> {
+    fn emit(&self, event: &StructuredEvent) -> io::Result<()> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        self.writer
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .write_all(&line)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.writer
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .flush()
+    }
+}
+
+/// Sends one OTLP/HTTP JSON export request for [`OtlpSink`].
+pub trait OtlpExporter: Send + Sync {
+    /// POSTs `request` (an `ExportLogsServiceRequest`) to `url`.
+    ///
+    /// # Errors
+    ///
+    /// The error that prevented the request from being sent.
+    fn export(&self, url: &str, request: JsonValue) -> io::Result<()>;
+}
+
+/// [`OtlpExporter`] over a shared `reqwest::Client`.
+///
+/// The request is spawned on the current tokio runtime and not awaited:
+/// `export` returns once the request is queued, and a collector that rejects
+/// or never answers it is not reported back, since reporting it would emit
+/// more events into the sink being exported.
+#[derive(Debug, Clone, Default)]
+pub struct HttpOtlpExporter {
+    client: reqwest::Client,
+}
+
+impl HttpOtlpExporter {
+    /// Creates an exporter with a default `reqwest::Client`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an exporter over an existing client (e.g. one with a timeout
+    /// or proxy settings).
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl OtlpExporter for HttpOtlpExporter {
+    fn export(&self, url: &str, request: JsonValue) -> io::Result<()> {
+        let runtime = tokio::runtime::Handle::try_current()
+            .map_err(|e| io::Error::other(format!("OTLP export needs a tokio runtime: {e}")))?;
+        let request = self
+            .client
+            .post(url)
+            .header("content-type", "application/json")
+            .body(serde_json::to_vec(&request)?);
+        runtime.spawn(async move {
+            // Best effort; see the type documentation.
+            let _ = request.send().await;
+        });
+        Ok(())
+    }
+}
+
+/// Exports events to an OpenTelemetry collector as OTLP log records.
+///
+/// Events are buffered and exported when [`OTLP_BATCH_SIZE`] are pending or
+/// on [`EventSink::flush`].
+pub struct OtlpSink {
+    endpoint: String,
+    exporter: Arc<dyn OtlpExporter>,
+    pending: Mutex<Vec<JsonValue>>,
+}
+
+impl OtlpSink {
+    /// Creates a sink exporting to `endpoint` through [`HttpOtlpExporter`].
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            exporter: Arc::new(HttpOtlpExporter::new()),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Uses `exporter` instead of [`HttpOtlpExporter`].
+    #[must_use]
+    pub fn with_exporter(mut self, exporter: Arc<dyn OtlpExporter>) -> Self {
+        self.exporter = exporter;
+        self
+    }
+
+    /// Returns the collector endpoint.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Returns the URL batches are exported to: the endpoint followed by
+    /// [`OTLP_LOGS_PATH`].
+    pub fn logs_url(&self) -> String {
+        format!("{}{OTLP_LOGS_PATH}", self.endpoint.trim_end_matches('/'))
+    }
+
+    /// Returns the number of events waiting to be exported.
+    pub fn pending(&self) -> usize {
+        self.lock_pending().len()
+    }
+
+    fn lock_pending(&self) -> std::sync::MutexGuard<'_, Vec<JsonValue>> {
+        self.pending
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn export(&self, records: Vec<JsonValue>) -> io::Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        self.exporter
+            .export(&self.logs_url(), otlp_export_request(records))
+    }
+}
+
+impl std::fmt::Debug for OtlpSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OtlpSink")
+            .field("endpoint", &self.endpoint)
+            .field("pending", &self.pending())
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventSink for OtlpSink {
+    fn emit(&self, event: &StructuredEvent) -> io::Result<()> {
+        let batch = {
+            let mut pending = self.lock_pending();
+            pending.push(otlp_log_record(event));
+            if pending.len() < OTLP_BATCH_SIZE {
+                return Ok(());
+            }
+            mem::take(&mut *pending)
+        };
+        self.export(batch)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        let batch = mem::take(&mut *self.lock_pending());
+        self.export(batch)
+    }
+}
+
+/// OTLP `severityNumber` for a level name; unknown levels map to
+/// `0` (unspecified).
+pub fn otlp_severity_number(level: &str) -> u8 {
+    match level.to_ascii_uppercase().as_str() {
+        "TRACE" => 1,
+        "DEBUG" => 5,
+        "INFO" => 9,
+        "WARN" => 13,
+        "ERROR" => 17,
+        _ => 0,
+    }
+}
+
+/// Converts `event` to an OTLP JSON `LogRecord`.
+///
+/// The target becomes the `code.namespace` attribute and each structured
+/// field an attribute of the same name.
+pub fn otlp_log_record(event: &StructuredEvent) -> JsonValue {
+    let mut attributes = vec![otlp_attribute(
+        "code.namespace",
+        &JsonValue::String(event.target.clone()),
+    )];
+    attributes.extend(
+        event
+            .fields
+            .iter()
+            .map(|(key, value)| otlp_attribute(key, value)),
+    );
+    let time_unix_nano = event
+        .timestamp
+        .as_datetime()
+        .timestamp_nanos_opt()
+        .unwrap_or_default();
+    json!({
+        "timeUnixNano": time_unix_nano.to_string(),
+        "severityNumber": otlp_severity_number(&event.level),
+        "severityText": event.level,
+        "body": { "stringValue": event.message },
+        "attributes": attributes,
+    })
+}
+
+fn otlp_attribute(key: &str, value: &JsonValue) -> JsonValue {
+    let value = match value {
+        JsonValue::Bool(b) => json!({ "boolValue": b }),
+        // OTLP JSON encodes 64-bit integers as strings.
+        JsonValue::Number(n) if n.is_i64() || n.is_u64() => json!({ "intValue": n.to_string() }),
+        JsonValue::Number(n) => json!({ "doubleValue": n }),
+        JsonValue::String(s) => json!({ "stringValue": s }),
+        other => json!({ "stringValue": other.to_string() }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+/// Wraps `records` in an OTLP `ExportLogsServiceRequest` with the
+/// [`OTLP_SERVICE_NAME`] resource.
+pub fn otlp_export_request(records: Vec<JsonValue>) -> JsonValue {
+    json!({
+        "resourceLogs": [{
+            "resource": {
+                "attributes": [otlp_attribute(
+                    "service.name",
+                    &JsonValue::String(OTLP_SERVICE_NAME.to_string()),
+                )],
+            },
+            "scopeLogs": [{
+                "scope": { "name": OTLP_SERVICE_NAME },
+                "logRecords": records,
+            }],
+        }],
+    })
+}
+
+/// Forwards every event to each of its sinks.
+///
+/// A failing sink does not stop delivery to the others; the first error is
+/// returned after all sinks have been tried.
+#[derive(Default)]
+pub struct FanOutSink {
+    sinks: Vec<Box<dyn EventSink>>,
+}
+
+impl FanOutSink {
+    /// Creates a fan-out over `sinks`.
+    pub fn new(sinks: Vec<Box<dyn EventSink>>) -> Self {
+        Self { sinks }
+    }
+
+    /// Returns the number of sinks.
+    pub fn len(&self) -> usize {
+        self.sinks.len()
+    }
+
+    /// Returns `true` if there are no sinks.
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    fn each(&self, mut op: impl FnMut(&dyn EventSink) -> io::Result<()>) -> io::Result<()> {
+        let mut first_error = None;
+        for sink in &self.sinks {
+            if let Err(e) = op(sink.as_ref()) {
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+impl EventSink for FanOutSink {
+    fn emit(&self, event: &StructuredEvent) -> io::Result<()> {
+        self.each(|sink| sink.emit(event))
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.each(|sink| sink.flush())
+    }
+}
+
+/// Builds the sinks named in `config`.
+///
+/// # Errors
+///
+/// The error from opening a file sink.
+pub fn build_sinks(config: &EventsConfig) -> io::Result<FanOutSink> {
+    let sinks = config
+        .sinks
+        .iter()
+        .map(|sink| -> io::Result<Box<dyn EventSink>> {
+            Ok(match sink {
+                EventSinkConfig::Otlp { endpoint } => Box::new(OtlpSink::new(endpoint.clone())),
+                EventSinkConfig::File { path } => Box::new(JsonlSink::open(path)?),
+                EventSinkConfig::Stdout => Box::new(JsonlSink::stdout()),
+            })
+        })
+        .collect::<io::Result<_>>()?;
+    Ok(FanOutSink::new(sinks))
+}
+
+#[cfg(test)]
+#[path = "event_sink_tests.rs"]
+mod tests;
@@ -0,0 +1,335 @@
+use super::*;
+
+/// [`OtlpExporter`] recording every export and optionally failing.
+#[derive(Default)]
+struct RecordingExporter {
+    fail: bool,
+    exports: Mutex<Vec<(String, JsonValue)>>,
+}
+
+impl RecordingExporter {
+    fn failing() -> Self {
+        Self {
+            fail: true,
+            ..Self::default()
+        }
+    }
+
+    fn exports(&self) -> Vec<(String, JsonValue)> {
+        self.exports.lock().unwrap().clone()
+    }
+
+    /// Log records of export `index`.
+    fn records(&self, index: usize) -> Vec<JsonValue> {
+        self.exports()[index].1["resourceLogs"][0]["scopeLogs"][0]["logRecords"]
+            .as_array()
+            .unwrap()
+            .clone()
+    }
+}
+
+impl OtlpExporter for RecordingExporter {
+    fn export(&self, url: &str, request: JsonValue) -> io::Result<()> {
+        self.exports
+            .lock()
+            .unwrap()
+            .push((url.to_string(), request));
+        if self.fail {
+            Err(io::Error::other("collector unreachable"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// [`EventSink`] counting calls and optionally failing.
+#[derive(Default)]
+struct CountingSink {
+    fail: bool,
+    emits: Arc<Mutex<u32>>,
+    flushes: Arc<Mutex<u32>>,
+}
+
+impl EventSink for CountingSink {
+    fn emit(&self, _event: &StructuredEvent) -> io::Result<()> {
+        *self.emits.lock().unwrap() += 1;
+        if self.fail {
+            Err(io::Error::other("emit failed"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        *self.flushes.lock().unwrap() += 1;
+        Ok(())
+    }
+}
+
+fn event(message: &str) -> StructuredEvent {
+    let mut fields = Map::new();
+    fields.insert("node".to_string(), JsonValue::String("plan".to_string()));
+    fields.insert("attempt".to_string(), JsonValue::from(2u64));
+    fields.insert("dry_run".to_string(), JsonValue::Bool(true));
+    StructuredEvent {
+        timestamp: Timestamp::now(),
+        level: "WARN".to_string(),
+        target: "nodes::executor".to_string(),
+        message: message.to_string(),
+        fields,
+    }
+}
+
+fn otlp_sink(exporter: &Arc<RecordingExporter>) -> OtlpSink {
+    OtlpSink::new("http://collector:4318/").with_exporter(Arc::clone(exporter) as _)
+}
+
+// ─── JsonlSink ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_jsonl_sink_emit_writes_one_json_line_per_event() {
+    let sink = JsonlSink::new(Vec::new());
+
+    sink.emit(&event("first")).unwrap();
+    sink.emit(&event("second")).unwrap();
+
+    let output = String::from_utf8(sink.into_inner()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(output.ends_with('\n'));
+    let first: StructuredEvent = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(first.message, "first");
+}
+
+#[test]
+fn test_jsonl_sink_open_appends_to_existing_file() {
+    let path =
+        std::env::temp_dir().join(format!("cogworks-event-sink-{}.jsonl", std::process::id()));
+    std::fs::write(&path, "existing\n").unwrap();
+
+    let sink = JsonlSink::open(&path).unwrap();
+    sink.emit(&event("appended")).unwrap();
+    sink.flush().unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(contents.starts_with("existing\n"));
+    assert_eq!(contents.lines().count(), 2);
+}
+
+// ─── OtlpSink ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_otlp_sink_logs_url_appends_logs_path_once() {
+    let exporter = Arc::new(RecordingExporter::default());
+
+    assert_eq!(
+        otlp_sink(&exporter).logs_url(),
+        "http://collector:4318/v1/logs"
+    );
+}
+
+#[test]
+fn test_otlp_sink_emit_buffers_until_flush() {
+    let exporter = Arc::new(RecordingExporter::default());
+    let sink = otlp_sink(&exporter);
+
+    sink.emit(&event("one")).unwrap();
+    sink.emit(&event("two")).unwrap();
+
+    assert!(exporter.exports().is_empty());
+    assert_eq!(sink.pending(), 2);
+}
+
+#[test]
+fn test_otlp_sink_flush_exports_pending_records_in_order() {
+    let exporter = Arc::new(RecordingExporter::default());
+    let sink = otlp_sink(&exporter);
+    sink.emit(&event("one")).unwrap();
+    sink.emit(&event("two")).unwrap();
+
+    sink.flush().unwrap();
+
+    let exports = exporter.exports();
+    assert_eq!(exports.len(), 1);
+    assert_eq!(exports[0].0, "http://collector:4318/v1/logs");
+    let bodies: Vec<JsonValue> = exporter
+        .records(0)
+        .iter()
+        .map(|record| record["body"]["stringValue"].clone())
+        .collect();
+    assert_eq!(bodies, vec![json!("one"), json!("two")]);
+    assert_eq!(sink.pending(), 0);
+}
+
+#[test]
+fn test_otlp_sink_flush_nothing_pending_does_not_export() {
+    let exporter = Arc::new(RecordingExporter::default());
+    let sink = otlp_sink(&exporter);
+
+    sink.flush().unwrap();
+
+    assert!(exporter.exports().is_empty());
+}
+
+#[test]
+fn test_otlp_sink_emit_full_batch_exports_without_flush() {
+    let exporter = Arc::new(RecordingExporter::default());
+    let sink = otlp_sink(&exporter);
+
+    for i in 0..OTLP_BATCH_SIZE {
+        sink.emit(&event(&format!("event {i}"))).unwrap();
+    }
+
+    assert_eq!(exporter.exports().len(), 1);
+    assert_eq!(exporter.records(0).len(), OTLP_BATCH_SIZE);
+    assert_eq!(sink.pending(), 0);
+}
+
+#[test]
+fn test_otlp_sink_flush_exporter_error_returns_error_and_drops_batch() {
+    let exporter = Arc::new(RecordingExporter::failing());
+    let sink = otlp_sink(&exporter);
+    sink.emit(&event("lost")).unwrap();
+
+    let result = sink.flush();
+
+    assert!(result.is_err());
+    assert_eq!(sink.pending(), 0);
+}
+
+#[test]
+fn test_http_otlp_exporter_export_outside_runtime_returns_error() {
+    let result = HttpOtlpExporter::new().export("http://collector:4318/v1/logs", json!({}));
+
+    assert!(result.is_err());
+}
+
+// ─── OTLP encoding ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_otlp_severity_number_levels_map_to_otlp_ranges() {
+    assert_eq!(otlp_severity_number("TRACE"), 1);
+    assert_eq!(otlp_severity_number("DEBUG"), 5);
+    assert_eq!(otlp_severity_number("INFO"), 9);
+    assert_eq!(otlp_severity_number("warn"), 13);
+    assert_eq!(otlp_severity_number("ERROR"), 17);
+    assert_eq!(otlp_severity_number("FATAL-ISH"), 0);
+}
+
+#[test]
+fn test_otlp_log_record_event_maps_level_body_and_attributes() {
+    let record = otlp_log_record(&event("node retried"));
+
+    assert_eq!(record["severityText"], json!("WARN"));
+    assert_eq!(record["severityNumber"], json!(13));
+    assert_eq!(record["body"], json!({ "stringValue": "node retried" }));
+    let attributes = record["attributes"].as_array().unwrap();
+    assert!(attributes.contains(&json!({
+        "key": "code.namespace",
+        "value": { "stringValue": "nodes::executor" },
+    })));
+    assert!(attributes.contains(&json!({
+        "key": "node",
+        "value": { "stringValue": "plan" },
+    })));
+    assert!(attributes.contains(&json!({
+        "key": "attempt",
+        "value": { "intValue": "2" },
+    })));
+    assert!(attributes.contains(&json!({
+        "key": "dry_run",
+        "value": { "boolValue": true },
+    })));
+}
+
+#[test]
+fn test_otlp_export_request_wraps_records_with_service_resource() {
+    let request = otlp_export_request(vec![json!({ "body": { "stringValue": "x" } })]);
+
+    let resource_logs = &request["resourceLogs"][0];
+    assert_eq!(
+        resource_logs["resource"]["attributes"][0],
+        json!({ "key": "service.name", "value": { "stringValue": "cogworks" } })
+    );
+    assert_eq!(
+        resource_logs["scopeLogs"][0]["logRecords"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+// ─── FanOutSink ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_fan_out_sink_failing_sink_still_delivers_to_others() {
+    let failing = CountingSink {
+        fail: true,
+        ..CountingSink::default()
+    };
+    let healthy = CountingSink::default();
+    let healthy_emits = Arc::clone(&healthy.emits);
+    let sink = FanOutSink::new(vec![Box::new(failing), Box::new(healthy)]);
+
+    let result = sink.emit(&event("fan out"));
+
+    assert!(result.is_err());
+    assert_eq!(*healthy_emits.lock().unwrap(), 1);
+}
+
+#[test]
+fn test_fan_out_sink_flush_flushes_every_sink() {
+    let first = CountingSink::default();
+    let second = CountingSink::default();
+    let flushes = [Arc::clone(&first.flushes), Arc::clone(&second.flushes)];
+    let sink = FanOutSink::new(vec![Box::new(first), Box::new(second)]);
+
+    sink.flush().unwrap();
+
+    assert!(flushes.iter().all(|count| *count.lock().unwrap() == 1));
+}
+
+// ─── Configuration ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_events_config_default_is_single_otlp_sink_at_default_endpoint() {
+    assert_eq!(
+        EventsConfig::default().sinks,
+        vec![EventSinkConfig::Otlp {
+            endpoint: DEFAULT_OTLP_ENDPOINT.to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_build_sinks_every_kind_builds_one_sink_each() {
+    let path =
+        std::env::temp_dir().join(format!("cogworks-build-sinks-{}.jsonl", std::process::id()));
+    let config = EventsConfig {
+        sinks: vec![
+            EventSinkConfig::Otlp {
+                endpoint: DEFAULT_OTLP_ENDPOINT.to_string(),
+            },
+            EventSinkConfig::File { path: path.clone() },
+            EventSinkConfig::Stdout,
+        ],
+    };
+
+    let sinks = build_sinks(&config).unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(sinks.len(), 3);
+}
+
+#[test]
+fn test_build_sinks_unopenable_file_returns_error() {
+    let config = EventsConfig {
+        sinks: vec![EventSinkConfig::File {
+            path: PathBuf::from("/nonexistent-directory/cogworks/events.jsonl"),
+        }],
+    };
+
+    assert!(build_sinks(&config).is_err());
+}
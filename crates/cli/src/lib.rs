@@ -0,0 +1,25 @@
+//! CogWorks CLI support library.
+//!
+//! Argument parsing and output formatting used by the `cogworks` binary
+//! (`src/main.rs`), which is the composition root. Kept in a library target so
+//! the pieces are usable without going through `main`.
+//!
+//! ## Module Overview
+//!
+//! | Module | Contents |
+//! |--------|----------|
+//! | [`args`] | Command-line argument parsing |
+//! | [`actions`] | GitHub Actions workflow command output |
+//! | [`audit_collector`] | HTTP transport streaming audit events to an external collector |
+//! | [`escalation_webhook`] | HTTP transport POSTing escalation notifications to a webhook |
+//! | [`event_sink`] | OTLP, JSONL file, and stdout destinations for structured events |
+//! | [`run_node`] | Single-node runs for `cogworks run-node` |
+//! | [`validate`] | Repository checks run by `cogworks validate` |
+
+pub mod actions;
+pub mod args;
+pub mod audit_collector;
+pub mod escalation_webhook;
+pub mod event_sink;
+pub mod run_node;
+pub mod validate;
@@ -2,25 +2,76 @@
 //!
 //! This binary is the composition root for the entire system. Responsibilities:
 //!
-//! 1. **Parse configuration** — load `.cogworks/config.toml` and validate it.
+//! 1. **Parse configuration** — load `.cogworks/config.toml` and validate it,
+//!    then select the pipeline named by `--pipeline` (default `"default"`) from
+//!    `.cogworks/pipeline.toml` via [`pipeline::PipelineConfiguration::select`],
+//!    which validates the selected graph before any node runs.
 //! 2. **Wire observability** — configure `tracing-subscriber` with a JSON layer
-//!    and an OpenTelemetry OTLP exporter. All `tracing` spans and structured
+//!    that forwards to the sinks configured under `[events]` (OTLP, a JSONL
+//!    file, stdout; see `cli::event_sink`). All `tracing` spans and structured
 //!    events emitted by every crate in the workspace flow through this layer.
+//!    When `GITHUB_ACTIONS=true`, diagnostics are additionally printed as
+//!    GitHub Actions workflow commands (see `cli::actions`).
 //! 3. **Construct infrastructure** — create concrete instances of all
-//!    infrastructure types (`GithubClient`, `AnthropicProvider`,
-//!    `ExtensionApiClient`, event source) and inject them into `PipelineExecutor`.
+//!    infrastructure types (`GithubClient`, the `LlmProvider` selected by
+//!    `[llm] provider` — `AnthropicProvider`, `OpenAiProvider`, or
+//!    `EchoProvider` — `ExtensionApiClient`, event source) and inject them into
+//!    `PipelineExecutor`.
 //! 4. **Select trigger mode** — based on `CliConfig.trigger_mode`:
 //!    - `SingleShot` — synthesise one [`pipeline::GitHubEvent`] from `--issue-url`
 //!      and call `run_step` once (Phase 1 CLI).
 //!    - `Webhook` — construct a `GitHubWebhookEventSource` and run the event loop.
 //!    - `Queue` — construct a `QueueEventSource` and run the event loop.
 //!
+//!    In both event-loop modes the executor reads from a bounded
+//!    `listener::EventBuffer`, so a slow executor pushes back on the source
+//!    instead of queueing events without limit. An
+//!    `extension_api::ServicesReloader` is spawned alongside the event loop so
+//!    edits to `.cogworks/services.toml` take effect without a restart.
+//!
+//! ## Subcommands
+//!
+//! `cogworks run-node --node <name> --issue-url <url>` reconstructs the run
+//! state for the issue and runs only that node through `cli::run_node`,
+//! bypassing edge evaluation, then prints the resulting `NodeOutcome`. The
+//! graph does not advance. Until the infrastructure above is constructed
+//! here, no node implementations are registered, so the subcommand reports
+//! that and exits with status 2 rather than running anything.
+//!
+//! `cogworks validate` loads and validates the configuration, then checks it
+//! against the repository (`cli::validate`), e.g. warning about protected-path
+//! patterns that name no existing path. Warnings are printed and do not fail
+//! the command. Until the configuration loader and `GithubClient` are
+//! constructed here, there is nothing to check against, so the subcommand
+//! reports that and exits with status 2.
+//!
 //! ## Specification
 //!
 //! See `docs/spec/interfaces/infrastructure.md` §cli for the full contract.
 //!
 //! *This binary is a skeleton. Implementation is added in PR 10.*
 
+use cli::args::{CliArgs, Command};
+
 fn main() {
-    todo!("CLI entry point — see docs/spec/interfaces/infrastructure.md §cli")
+    let args = match CliArgs::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("cogworks: {e:#}");
+            std::process::exit(2);
+        }
+    };
+    tracing::debug!(pipeline = %args.pipeline, "parsed command-line arguments");
+
+    match args.command {
+        Command::Run => todo!("CLI entry point — see docs/spec/interfaces/infrastructure.md §cli"),
+        Command::RunNode { node } => {
+            eprintln!("cogworks: cannot run node '{node}': no node implementations are registered");
+            std::process::exit(2);
+        }
+        Command::Validate => {
+            eprintln!("cogworks: cannot validate: no repository client is configured");
+            std::process::exit(2);
+        }
+    }
 }
@@ -0,0 +1,80 @@
+//! `cogworks run-node`: runs one node in isolation and reports its outcome.
+//!
+//! The node runs through [`PipelineExecutor::run_node`], so upstream
+//! completion, gates, and outgoing edges are not evaluated and the run state
+//! is not modified. [`run_node`] returns the report printed by the command and
+//! the process exit code: `0` when the node completed or is awaiting human
+//! review, `1` when it failed.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/infrastructure.md` §cli.
+
+use anyhow::Context;
+
+use nodes::{NodeOutcome, PipelineExecutor};
+use pipeline::{NodeId, PipelineState};
+
+/// Exit code when the node failed.
+pub const NODE_FAILED_EXIT_CODE: i32 = 1;
+
+/// Report of one `run-node` invocation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunNodeReport {
+    /// The node that ran.
+    pub node: NodeId,
+    /// Its outcome.
+    pub outcome: NodeOutcome,
+}
+
+impl RunNodeReport {
+    /// Process exit code for this outcome.
+    pub fn exit_code(&self) -> i32 {
+        match self.outcome {
+            NodeOutcome::Failed { .. } => NODE_FAILED_EXIT_CODE,
+            NodeOutcome::Completed { .. } | NodeOutcome::AwaitingHumanReview { .. } => 0,
+        }
+    }
+
+    /// One-line human-readable summary, e.g.
+    /// `node 'plan' completed (cost $0.120000)`.
+    pub fn render(&self) -> String {
+        let node = &self.node;
+        let cost = self.outcome.cost();
+        match &self.outcome {
+            NodeOutcome::Completed { .. } => format!("node '{node}' completed (cost {cost})"),
+            NodeOutcome::AwaitingHumanReview { .. } => {
+                format!("node '{node}' is awaiting human review (cost {cost})")
+            }
+            NodeOutcome::Failed { error, .. } => {
+                format!("node '{node}' failed: {error} (cost {cost})")
+            }
+        }
+    }
+}
+
+/// Runs `node` once against `state` without advancing the graph.
+///
+/// # Errors
+///
+/// Returns an error if `node` is not in the executor's graph or has no
+/// implementation registered. A node that runs and fails is not an error;
+/// it is reported through [`RunNodeReport::outcome`].
+pub async fn run_node(
+    executor: &PipelineExecutor,
+    state: &PipelineState,
+    node: &NodeId,
+) -> anyhow::Result<RunNodeReport> {
+    let outcome = executor
+        .run_node(state, node)
+        .await
+        .with_context(|| format!("cannot run node '{node}'"))?;
+    Ok(RunNodeReport {
+        node: node.clone(),
+        outcome,
+    })
+}
+
+#[cfg(test)]
+#[path = "run_node_tests.rs"]
+mod tests;
@@ -0,0 +1,129 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use nodes::Node;
+use pipeline::{
+    NodeDefinition, NodeGate, NodeType, PipelineGraph, PipelineRunId, PipelineSettings,
+    PipelineToolProfileConfig, ProfileName, TokenCost, ValidationKind,
+};
+
+use super::*;
+
+struct FixedNode(NodeOutcome);
+
+#[async_trait]
+impl Node for FixedNode {
+    async fn execute(&self, _state: &PipelineState) -> NodeOutcome {
+        self.0.clone()
+    }
+}
+
+fn node_id(id: &str) -> NodeId {
+    NodeId::new(id).unwrap()
+}
+
+fn cost(usd: f64) -> TokenCost {
+    TokenCost::new(usd).unwrap()
+}
+
+fn executor(outcome: NodeOutcome) -> PipelineExecutor {
+    let graph = PipelineGraph {
+        nodes: vec![NodeDefinition {
+            id: node_id("plan"),
+            node_type: NodeType::Llm,
+            declared_inputs: Vec::new(),
+            declared_outputs: Vec::new(),
+            timeout: None,
+            cost_budget: None,
+            gate: NodeGate::AutoProceed,
+            validation_kind: ValidationKind::None,
+            abort_siblings_on_failure: false,
+            priority: 0,
+            model: None,
+        }],
+        edges: Vec::new(),
+        evaluation_modes: HashMap::new(),
+        explicit_edge_lists: HashMap::new(),
+        settings: PipelineSettings {
+            default_timeout: None,
+            default_cost_budget: None,
+            max_node_retries: 3,
+            default_model: None,
+        },
+        tool_profiles: PipelineToolProfileConfig {
+            default_profile: ProfileName::new("default").unwrap(),
+            node_overrides: HashMap::new(),
+        },
+    };
+    let node: Arc<dyn Node> = Arc::new(FixedNode(outcome));
+    PipelineExecutor::new(graph, HashMap::from([(node_id("plan"), node)]))
+}
+
+fn state() -> PipelineState {
+    PipelineState {
+        run_id: PipelineRunId::new_random(),
+        node_states: HashMap::new(),
+        active_parallel_branches: Vec::new(),
+        cost_accumulator: TokenCost::zero(),
+        last_processed_event: None,
+        expected_labels: Default::default(),
+        sub_work_items_created: 0,
+        read_only: false,
+    }
+}
+
+#[tokio::test]
+async fn test_run_node_completed_node_reports_success() {
+    let executor = executor(NodeOutcome::Completed { cost: cost(0.12) });
+
+    let report = run_node(&executor, &state(), &node_id("plan"))
+        .await
+        .unwrap();
+
+    assert_eq!(report.render(), "node 'plan' completed (cost $0.120000)");
+    assert_eq!(report.exit_code(), 0);
+}
+
+#[tokio::test]
+async fn test_run_node_failed_node_reports_error_and_exit_code() {
+    let executor = executor(NodeOutcome::Failed {
+        error: "model refused".to_string(),
+        cost: TokenCost::zero(),
+    });
+
+    let report = run_node(&executor, &state(), &node_id("plan"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report.render(),
+        "node 'plan' failed: model refused (cost $0.000000)"
+    );
+    assert_eq!(report.exit_code(), NODE_FAILED_EXIT_CODE);
+}
+
+#[tokio::test]
+async fn test_run_node_awaiting_review_reports_success() {
+    let executor = executor(NodeOutcome::AwaitingHumanReview { cost: cost(2.0) });
+
+    let report = run_node(&executor, &state(), &node_id("plan"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report.render(),
+        "node 'plan' is awaiting human review (cost $2.000000)"
+    );
+    assert_eq!(report.exit_code(), 0);
+}
+
+#[tokio::test]
+async fn test_run_node_unknown_node_returns_error() {
+    let executor = executor(NodeOutcome::Completed { cost: cost(0.1) });
+
+    let error = run_node(&executor, &state(), &node_id("deploy"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.to_string(), "cannot run node 'deploy'");
+}
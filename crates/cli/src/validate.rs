@@ -0,0 +1,138 @@
+//! Repository checks run by `cogworks validate`.
+//!
+//! A protected-path pattern with a typo (`.github/workflow/**` for
+//! `.github/workflows/**`) matches nothing, so the files it was meant to
+//! protect are silently left writable. [`check_protected_paths`] looks up the
+//! literal directory part of each configured pattern in the repository and
+//! reports a warning for every one that does not exist. A missing path is not
+//! an error: a pattern may protect a location that does not exist yet, so
+//! [`ValidateReport::exit_code`] is `0` whatever the warnings.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/operations.md` §Budget Configuration (`[constitutional]`).
+
+use anyhow::Context;
+
+use pipeline::{
+    CodeRepository, Diagnostic, DiagnosticCategory, DiagnosticSeverity, GitHubOperationError,
+    GlobPattern, RepositoryId,
+};
+
+/// Category of the warnings reported by [`check_protected_paths`].
+pub const PROTECTED_PATH_MISSING_CATEGORY: &str = "protected_path_missing";
+
+/// Report of one `validate` invocation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidateReport {
+    /// Warnings found, in pattern order.
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl ValidateReport {
+    /// Process exit code for this report; warnings do not fail the command.
+    pub fn exit_code(&self) -> i32 {
+        0
+    }
+
+    /// Human-readable summary: one `warning: ...` line per warning, or
+    /// `configuration is valid` when there are none.
+    pub fn render(&self) -> String {
+        if self.warnings.is_empty() {
+            return "configuration is valid".to_string();
+        }
+        self.warnings
+            .iter()
+            .map(|warning| format!("warning: {}", warning.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Characters that start a glob wildcard in a path segment.
+const GLOB_METACHARACTERS: &[char] = &['*', '?', '['];
+
+/// Returns the leading part of `pattern` that contains no wildcard.
+///
+/// `.github/workflows/**` gives `.github/workflows`; `src/*.rs` gives `src`.
+/// Returns `None` if the first segment already contains a wildcard (e.g.
+/// `**/*.pem`), since such a pattern names no fixed location.
+pub fn literal_prefix(pattern: &GlobPattern) -> Option<String> {
+    let literal: Vec<&str> = pattern
+        .as_str()
+        .split('/')
+        .take_while(|segment| !segment.contains(GLOB_METACHARACTERS))
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    if literal.is_empty() {
+        None
+    } else {
+        Some(literal.join("/"))
+    }
+}
+
+/// Checks that each protected-path pattern names a location that exists in
+/// `repository` at `git_ref`.
+///
+/// Patterns without a literal prefix (see [`literal_prefix`]) are not
+/// checked. Returns one [`DiagnosticSeverity::Warning`] per pattern whose
+/// prefix does not exist, in pattern order; an empty list means every
+/// pattern points at an existing path.
+///
+/// # Errors
+///
+/// Returns the first [`GitHubOperationError`] from the existence checks.
+pub async fn check_protected_paths(
+    code: &dyn CodeRepository,
+    repository: &RepositoryId,
+    git_ref: &str,
+    patterns: &[GlobPattern],
+) -> Result<Vec<Diagnostic>, GitHubOperationError> {
+    let mut warnings = Vec::new();
+    for pattern in patterns {
+        let Some(prefix) = literal_prefix(pattern) else {
+            continue;
+        };
+        if code.file_exists(repository, &prefix, git_ref).await? {
+            continue;
+        }
+        tracing::warn!(%pattern, path = %prefix, "protected path does not exist");
+        warnings.extend(missing_path_warning(pattern, &prefix, git_ref));
+    }
+    Ok(warnings)
+}
+
+/// Runs the `validate` repository checks against `repository` at `git_ref`.
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be queried; see
+/// [`check_protected_paths`].
+pub async fn validate(
+    code: &dyn CodeRepository,
+    repository: &RepositoryId,
+    git_ref: &str,
+    patterns: &[GlobPattern],
+) -> anyhow::Result<ValidateReport> {
+    let warnings = check_protected_paths(code, repository, git_ref, patterns)
+        .await
+        .with_context(|| format!("cannot check protected paths in '{repository}'"))?;
+    Ok(ValidateReport { warnings })
+}
+
+fn missing_path_warning(pattern: &GlobPattern, prefix: &str, git_ref: &str) -> Option<Diagnostic> {
+    Some(Diagnostic {
+        artifact: None,
+        location: None,
+        severity: DiagnosticSeverity::Warning,
+        category: DiagnosticCategory::new(PROTECTED_PATH_MISSING_CATEGORY)?,
+        message: format!(
+            "protected path pattern '{pattern}' matches nothing: '{prefix}' does not exist at \
+             '{git_ref}'; check it for typos"
+        ),
+    })
+}
+
+#[cfg(test)]
+#[path = "validate_tests.rs"]
+mod tests;
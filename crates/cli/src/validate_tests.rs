@@ -0,0 +1,264 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use pipeline::{DirectoryEntry, FileContent};
+
+use super::*;
+
+/// [`CodeRepository`] in which only `existing` paths exist. Fails every
+/// existence check when `unavailable` is set.
+#[derive(Default)]
+struct FakeRepository {
+    existing: Vec<&'static str>,
+    unavailable: bool,
+    checked: Mutex<Vec<(String, String)>>,
+}
+
+impl FakeRepository {
+    fn with_paths(existing: Vec<&'static str>) -> Self {
+        Self {
+            existing,
+            ..Self::default()
+        }
+    }
+
+    /// `(path, git_ref)` pairs passed to `file_exists`, in call order.
+    fn checked(&self) -> Vec<(String, String)> {
+        self.checked.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl CodeRepository for FakeRepository {
+    async fn read_file(
+        &self,
+        _repository: &RepositoryId,
+        path: &str,
+        _git_ref: &str,
+    ) -> Result<FileContent, GitHubOperationError> {
+        Err(GitHubOperationError::NotFound {
+            resource: path.to_string(),
+        })
+    }
+
+    async fn list_directory(
+        &self,
+        _repository: &RepositoryId,
+        path: &str,
+        _git_ref: &str,
+    ) -> Result<Vec<DirectoryEntry>, GitHubOperationError> {
+        Err(GitHubOperationError::NotFound {
+            resource: path.to_string(),
+        })
+    }
+
+    async fn file_exists(
+        &self,
+        _repository: &RepositoryId,
+        path: &str,
+        git_ref: &str,
+    ) -> Result<bool, GitHubOperationError> {
+        self.checked
+            .lock()
+            .unwrap()
+            .push((path.to_string(), git_ref.to_string()));
+        if self.unavailable {
+            return Err(GitHubOperationError::SdkCapabilityMissing {
+                capability: "contents_api_file_exists".to_string(),
+            });
+        }
+        Ok(self.existing.contains(&path))
+    }
+
+    async fn read_tree(
+        &self,
+        _repository: &RepositoryId,
+        _git_ref: &str,
+    ) -> Result<Vec<DirectoryEntry>, GitHubOperationError> {
+        Ok(Vec::new())
+    }
+}
+
+fn repository() -> RepositoryId {
+    RepositoryId::new("octo/widgets").unwrap()
+}
+
+fn patterns(patterns: &[&str]) -> Vec<GlobPattern> {
+    patterns
+        .iter()
+        .map(|pattern| GlobPattern::new(*pattern).unwrap())
+        .collect()
+}
+
+// ─── literal_prefix ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_literal_prefix_recursive_wildcard_returns_directory() {
+    let pattern = GlobPattern::new(".github/workflows/**").unwrap();
+
+    assert_eq!(
+        literal_prefix(&pattern).as_deref(),
+        Some(".github/workflows")
+    );
+}
+
+#[test]
+fn test_literal_prefix_file_wildcard_returns_parent_directory() {
+    let pattern = GlobPattern::new("src/*.rs").unwrap();
+
+    assert_eq!(literal_prefix(&pattern).as_deref(), Some("src"));
+}
+
+#[test]
+fn test_literal_prefix_plain_path_returns_whole_path() {
+    let pattern = GlobPattern::new("Cargo.lock").unwrap();
+
+    assert_eq!(literal_prefix(&pattern).as_deref(), Some("Cargo.lock"));
+}
+
+#[test]
+fn test_literal_prefix_leading_wildcard_returns_none() {
+    let pattern = GlobPattern::new("**/*.pem").unwrap();
+
+    assert_eq!(literal_prefix(&pattern), None);
+}
+
+#[test]
+fn test_literal_prefix_character_class_stops_prefix() {
+    let pattern = GlobPattern::new("docs/[ab]/notes.md").unwrap();
+
+    assert_eq!(literal_prefix(&pattern).as_deref(), Some("docs"));
+}
+
+// ─── check_protected_paths ───────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_check_protected_paths_existing_path_returns_no_warnings() {
+    let code = FakeRepository::with_paths(vec![".github/workflows"]);
+
+    let warnings = check_protected_paths(
+        &code,
+        &repository(),
+        "main",
+        &patterns(&[".github/workflows/**"]),
+    )
+    .await
+    .unwrap();
+
+    assert!(warnings.is_empty());
+    assert_eq!(
+        code.checked(),
+        vec![(".github/workflows".to_string(), "main".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn test_check_protected_paths_missing_path_returns_warning() {
+    let code = FakeRepository::with_paths(vec![".github/workflows"]);
+
+    let warnings = check_protected_paths(
+        &code,
+        &repository(),
+        "main",
+        &patterns(&[".github/workflow/**"]),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(warnings.len(), 1);
+    let warning = &warnings[0];
+    assert_eq!(warning.severity, DiagnosticSeverity::Warning);
+    assert_eq!(warning.category.as_str(), PROTECTED_PATH_MISSING_CATEGORY);
+    assert!(warning.message.contains(".github/workflow/**"));
+    assert!(warning.message.contains("'main'"));
+}
+
+#[tokio::test]
+async fn test_check_protected_paths_leading_wildcard_is_not_checked() {
+    let code = FakeRepository::default();
+
+    let warnings = check_protected_paths(&code, &repository(), "main", &patterns(&["**/*.pem"]))
+        .await
+        .unwrap();
+
+    assert!(warnings.is_empty());
+    assert!(code.checked().is_empty());
+}
+
+#[tokio::test]
+async fn test_check_protected_paths_mixed_patterns_warns_in_pattern_order() {
+    let code = FakeRepository::with_paths(vec!["src"]);
+
+    let warnings = check_protected_paths(
+        &code,
+        &repository(),
+        "main",
+        &patterns(&["secrets/**", "src/*.rs", "deploy/prod.toml"]),
+    )
+    .await
+    .unwrap();
+
+    let messages: Vec<&str> = warnings.iter().map(|w| w.message.as_str()).collect();
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].contains("'secrets/**'"));
+    assert!(messages[1].contains("'deploy/prod.toml'"));
+}
+
+#[tokio::test]
+async fn test_check_protected_paths_lookup_failure_returns_error() {
+    let code = FakeRepository {
+        unavailable: true,
+        ..FakeRepository::default()
+    };
+
+    let result = check_protected_paths(&code, &repository(), "main", &patterns(&["src/**"])).await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::SdkCapabilityMissing { .. })
+    ));
+}
+
+// ─── validate ────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_validate_all_paths_exist_renders_valid_and_exits_zero() {
+    let code = FakeRepository::with_paths(vec!["src"]);
+
+    let report = validate(&code, &repository(), "main", &patterns(&["src/**"]))
+        .await
+        .unwrap();
+
+    assert!(report.warnings.is_empty());
+    assert_eq!(report.render(), "configuration is valid");
+    assert_eq!(report.exit_code(), 0);
+}
+
+#[tokio::test]
+async fn test_validate_missing_path_renders_warning_and_exits_zero() {
+    let code = FakeRepository::default();
+
+    let report = validate(&code, &repository(), "main", &patterns(&["src/**"]))
+        .await
+        .unwrap();
+
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report
+        .render()
+        .starts_with("warning: protected path pattern 'src/**'"));
+    assert_eq!(report.exit_code(), 0);
+}
+
+#[tokio::test]
+async fn test_validate_lookup_failure_names_repository() {
+    let code = FakeRepository {
+        unavailable: true,
+        ..FakeRepository::default()
+    };
+
+    let error = validate(&code, &repository(), "main", &patterns(&["src/**"]))
+        .await
+        .unwrap_err();
+
+    assert!(format!("{error}").contains("octo/widgets"));
+}
@@ -19,9 +19,27 @@
 //! - `transport = "http"` — HTTP/1.1 (configurable; authentication mechanism
 //!   is to be determined).
 //!
+//! ## Service Registrations
+//!
+//! [`services::ServicesConfig`] parses `.cogworks/services.toml` and
+//! [`services::ServicePool`] holds one handle per registered service. In
+//! long-running modes [`reload::ServicesReloader`] watches the file and
+//! applies changes to the pool without a restart: new services are opened and
+//! removed ones closed, while requests already in flight on a removed service
+//! finish.
+//!
 //! ## Specification
 //!
 //! See `docs/spec/interfaces/domain-traits.md` and
 //! `docs/spec/interfaces/infrastructure.md` §extension-api for the full contract.
 //!
 //! *This crate is a skeleton. Method bodies are added in PR 10.*
+
+pub mod reload;
+pub mod services;
+
+pub use reload::{ServicesReloader, DEFAULT_RELOAD_POLL_INTERVAL};
+pub use services::{
+    PoolChanges, ServiceHandle, ServicePool, ServiceRegistration, ServiceTransport, ServicesConfig,
+    ServicesConfigError, DEFAULT_HEALTH_CHECK_TIMEOUT_MS,
+};
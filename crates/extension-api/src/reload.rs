@@ -0,0 +1,108 @@
+//! Hot reload of `.cogworks/services.toml` in long-running modes.
+//!
+//! In webhook and queue modes the process runs indefinitely, so adding a
+//! domain service should not need a restart. [`ServicesReloader`] watches the
+//! registration file by polling its modification time and, when it changes,
+//! re-reads it and applies it to the shared [`ServicePool`].
+//!
+//! A file that fails to load leaves the pool unchanged; the error is reported
+//! once and the file is not re-read until it changes again.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/operations.md` §Domain Service Hot Reload.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use tracing::instrument;
+
+use crate::services::{PoolChanges, ServicePool, ServicesConfig, ServicesConfigError};
+
+/// Default interval between checks of the registration file.
+pub const DEFAULT_RELOAD_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Reloads a [`ServicePool`] when its registration file changes.
+#[derive(Debug)]
+pub struct ServicesReloader {
+    path: PathBuf,
+    pool: Arc<ServicePool>,
+    /// Modification time of the file when it was last read.
+    last_modified: Option<SystemTime>,
+}
+
+impl ServicesReloader {
+    /// Creates a reloader for the file at `path` feeding `pool`.
+    ///
+    /// The current modification time is recorded, so the file is only
+    /// re-read after it next changes; `pool` is assumed to have been built
+    /// from its current content.
+    pub fn new(path: impl Into<PathBuf>, pool: Arc<ServicePool>) -> Self {
+        let path = path.into();
+        let last_modified = modified(&path);
+        Self {
+            path,
+            pool,
+            last_modified,
+        }
+    }
+
+    /// Returns the watched path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Re-reads the file if it changed since the last check and applies it.
+    ///
+    /// Returns `Ok(None)` if the file is unchanged, otherwise the changes
+    /// made to the pool.
+    ///
+    /// # Errors
+    ///
+    /// Any [`ServicesConfigError`] from loading the changed file. The pool is
+    /// left as it was.
+    pub fn check(&mut self) -> Result<Option<PoolChanges>, ServicesConfigError> {
+        let current = modified(&self.path);
+        if current == self.last_modified {
+            return Ok(None);
+        }
+        self.last_modified = current;
+        let config = ServicesConfig::load(&self.path)?;
+        Ok(Some(self.pool.apply(&config)))
+    }
+
+    /// Checks the file every `interval` until the returned future is dropped.
+    ///
+    /// Intended to be spawned next to the event loop.
+    #[instrument(skip(self), fields(path = %self.path.display()))]
+    pub async fn watch(mut self, interval: Duration) {
+        loop {
+            tokio::time::sleep(interval).await;
+            match self.check() {
+                Ok(Some(changes)) if !changes.is_empty() => tracing::info!(
+                    added = ?changes.added,
+                    removed = ?changes.removed,
+                    replaced = ?changes.replaced,
+                    "domain service registrations reloaded"
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::warn!(
+                    error = %e,
+                    "services configuration changed but could not be loaded; keeping current services"
+                ),
+            }
+        }
+    }
+}
+
+/// Returns the modification time of `path`, or `None` if it cannot be read.
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+#[path = "reload_tests.rs"]
+mod tests;
@@ -0,0 +1,123 @@
+use pipeline::DomainServiceName;
+
+use super::*;
+
+fn name(value: &str) -> DomainServiceName {
+    DomainServiceName::new(value).unwrap()
+}
+
+fn services_toml(names: &[&str]) -> String {
+    names
+        .iter()
+        .map(|name| format!("[[services]]\nname = \"{name}\"\npath = \"/run/{name}.sock\"\n"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A scratch registration file, removed when dropped.
+struct ServicesFile {
+    path: PathBuf,
+}
+
+impl ServicesFile {
+    fn new(names: &[&str]) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "cogworks-reload-{}-{}",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = Self {
+            path: dir.join("services.toml"),
+        };
+        file.write(&services_toml(names), 1);
+        file
+    }
+
+    /// Writes `text` and sets the modification time to `seconds` after the
+    /// epoch, so changes are visible regardless of timestamp resolution.
+    fn write(&self, text: &str, seconds: u64) {
+        std::fs::write(&self.path, text).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&self.path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+            .unwrap();
+    }
+}
+
+impl Drop for ServicesFile {
+    fn drop(&mut self) {
+        if let Some(dir) = self.path.parent() {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
+
+fn reloader(file: &ServicesFile) -> (ServicesReloader, Arc<ServicePool>) {
+    let pool = Arc::new(ServicePool::new(&ServicesConfig::load(&file.path).unwrap()));
+    (ServicesReloader::new(&file.path, Arc::clone(&pool)), pool)
+}
+
+#[test]
+fn test_check_unchanged_file_returns_none() {
+    let file = ServicesFile::new(&["interfaces"]);
+    let (mut reloader, pool) = reloader(&file);
+
+    assert!(reloader.check().unwrap().is_none());
+    assert_eq!(pool.names(), vec![name("interfaces")]);
+    assert_eq!(reloader.path(), file.path.as_path());
+}
+
+#[test]
+fn test_check_service_added_and_removed_updates_pool() {
+    let file = ServicesFile::new(&["interfaces", "legacy"]);
+    let (mut reloader, pool) = reloader(&file);
+    let legacy = pool.get(&name("legacy")).unwrap();
+
+    file.write(&services_toml(&["interfaces", "requirements"]), 2);
+    let changes = reloader.check().unwrap().unwrap();
+
+    assert_eq!(changes.added, vec![name("requirements")]);
+    assert_eq!(changes.removed, vec![name("legacy")]);
+    assert_eq!(pool.names(), vec![name("interfaces"), name("requirements")]);
+    assert!(legacy.is_closed());
+    assert!(reloader.check().unwrap().is_none());
+}
+
+#[test]
+fn test_check_invalid_file_keeps_pool_and_waits_for_next_change() {
+    let file = ServicesFile::new(&["interfaces"]);
+    let (mut reloader, pool) = reloader(&file);
+
+    file.write("[[services]]\nname = \"broken\"\n", 2);
+    let result = reloader.check();
+
+    assert!(matches!(
+        result,
+        Err(ServicesConfigError::MissingEndpoint { .. })
+    ));
+    assert_eq!(pool.names(), vec![name("interfaces")]);
+    assert!(reloader.check().unwrap().is_none());
+
+    file.write(&services_toml(&["requirements"]), 3);
+    let changes = reloader.check().unwrap().unwrap();
+    assert_eq!(changes.added, vec![name("requirements")]);
+    assert_eq!(changes.removed, vec![name("interfaces")]);
+}
+
+#[test]
+fn test_check_deleted_file_returns_read_error_and_keeps_pool() {
+    let file = ServicesFile::new(&["interfaces"]);
+    let (mut reloader, pool) = reloader(&file);
+
+    std::fs::remove_file(&file.path).unwrap();
+    let result = reloader.check();
+
+    assert!(matches!(result, Err(ServicesConfigError::Read { .. })));
+    assert_eq!(pool.names(), vec![name("interfaces")]);
+}
@@ -0,0 +1,316 @@
+//! Domain service registrations and the pool of service clients built from
+//! them.
+//!
+//! `.cogworks/services.toml` lists each domain service by name with its
+//! transport and endpoint. [`ServicesConfig::parse`] reads and validates the
+//! file; [`ServicePool`] holds one [`ServiceHandle`] per registered service.
+//!
+//! [`ServicePool::apply`] replaces the registrations of a running pool: new
+//! services get a handle, removed services have theirs closed, and services
+//! whose endpoint changed are closed and reopened. Closing a handle only stops
+//! new requests from starting; a request that already holds the handle keeps
+//! it (handles are [`Arc`]s) and runs to completion.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/operations.md` §Environment Variables (registration file
+//! format) and §Domain Service Hot Reload.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use pipeline::DomainServiceName;
+
+/// Handshake timeout applied when a registration does not set one.
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT_MS: u64 = 5000;
+
+// ─── Registrations ──────────────────────────────────────────────────────────
+
+/// Transport used to reach a domain service.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceTransport {
+    /// Unix domain socket at `path`.
+    #[default]
+    Unix,
+    /// HTTP/1.1 at `url`.
+    Http,
+}
+
+/// One `[[services]]` entry of `.cogworks/services.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceRegistration {
+    /// Service name, unique within the file.
+    pub name: DomainServiceName,
+    /// Transport; defaults to [`ServiceTransport::Unix`].
+    #[serde(default)]
+    pub transport: ServiceTransport,
+    /// Socket path, required for the Unix transport.
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// Base URL, required for the HTTP transport.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Handshake timeout in milliseconds.
+    #[serde(default = "default_health_check_timeout_ms")]
+    pub health_check_timeout_ms: u64,
+}
+
+fn default_health_check_timeout_ms() -> u64 {
+    DEFAULT_HEALTH_CHECK_TIMEOUT_MS
+}
+
+/// Errors returned when loading `.cogworks/services.toml`.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ServicesConfigError {
+    /// The file could not be read.
+    #[error("failed to read '{}': {message}", path.display())]
+    Read {
+        /// Path of the registration file.
+        path: PathBuf,
+        /// The underlying I/O error.
+        message: String,
+    },
+
+    /// The file is not valid TOML or does not match the schema.
+    #[error("invalid services configuration: {message}")]
+    Parse {
+        /// Parser error message.
+        message: String,
+    },
+
+    /// Two entries share a name.
+    #[error("domain service '{name}' is registered more than once")]
+    DuplicateService {
+        /// The repeated name.
+        name: DomainServiceName,
+    },
+
+    /// An entry lacks the endpoint its transport requires.
+    #[error("domain service '{name}' has no {field} for its {transport:?} transport")]
+    MissingEndpoint {
+        /// The incomplete entry.
+        name: DomainServiceName,
+        /// The transport that needs the endpoint.
+        transport: ServiceTransport,
+        /// The missing field (`path` or `url`).
+        field: &'static str,
+    },
+}
+
+/// The parsed content of `.cogworks/services.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServicesConfig {
+    /// Registered services, in file order.
+    #[serde(default)]
+    pub services: Vec<ServiceRegistration>,
+}
+
+impl ServicesConfig {
+    /// Parses and validates the TOML text of a registration file.
+    ///
+    /// # Errors
+    ///
+    /// - [`ServicesConfigError::Parse`] — the text is not a valid
+    ///   registration file.
+    /// - [`ServicesConfigError::DuplicateService`] — a name is used twice.
+    /// - [`ServicesConfigError::MissingEndpoint`] — an entry has no `path`
+    ///   (Unix) or `url` (HTTP).
+    pub fn parse(text: &str) -> Result<Self, ServicesConfigError> {
+        let config: Self = toml::from_str(text).map_err(|e| ServicesConfigError::Parse {
+            message: e.to_string(),
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reads and parses the registration file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// [`ServicesConfigError::Read`] if the file cannot be read, otherwise as
+    /// for [`ServicesConfig::parse`].
+    pub fn load(path: &Path) -> Result<Self, ServicesConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|e| ServicesConfigError::Read {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        Self::parse(&text)
+    }
+
+    fn validate(&self) -> Result<(), ServicesConfigError> {
+        let mut seen = BTreeSet::new();
+        for service in &self.services {
+            if !seen.insert(service.name.as_str()) {
+                return Err(ServicesConfigError::DuplicateService {
+                    name: service.name.clone(),
+                });
+            }
+            let missing = match service.transport {
+                ServiceTransport::Unix => service.path.is_none().then_some("path"),
+                ServiceTransport::Http => service.url.is_none().then_some("url"),
+            };
+            if let Some(field) = missing {
+                return Err(ServicesConfigError::MissingEndpoint {
+                    name: service.name.clone(),
+                    transport: service.transport,
+                    field,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+// ─── Pool ───────────────────────────────────────────────────────────────────
+
+/// Client-side handle for one registered domain service.
+#[derive(Debug)]
+pub struct ServiceHandle {
+    registration: ServiceRegistration,
+    closed: AtomicBool,
+}
+
+impl ServiceHandle {
+    fn open(registration: ServiceRegistration) -> Arc<Self> {
+        Arc::new(Self {
+            registration,
+            closed: AtomicBool::new(false),
+        })
+    }
+
+    /// The registration this handle was opened from.
+    pub fn registration(&self) -> &ServiceRegistration {
+        &self.registration
+    }
+
+    /// Returns `true` once the service has been removed or re-registered.
+    ///
+    /// New requests must not be started on a closed handle; requests already
+    /// in flight may finish.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+    }
+}
+
+/// Services added, removed, and re-registered by [`ServicePool::apply`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolChanges {
+    /// Services that were not registered before.
+    pub added: Vec<DomainServiceName>,
+    /// Services no longer registered; their handles are closed.
+    pub removed: Vec<DomainServiceName>,
+    /// Services whose registration changed; the old handle is closed and a
+    /// new one opened.
+    pub replaced: Vec<DomainServiceName>,
+}
+
+impl PoolChanges {
+    /// Returns `true` if the pool was left unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.replaced.is_empty()
+    }
+}
+
+/// The open domain service handles, keyed by service name.
+#[derive(Debug, Default)]
+pub struct ServicePool {
+    handles: RwLock<HashMap<DomainServiceName, Arc<ServiceHandle>>>,
+}
+
+impl ServicePool {
+    /// Creates a pool with a handle for every service in `config`.
+    pub fn new(config: &ServicesConfig) -> Self {
+        let pool = Self::default();
+        pool.apply(config);
+        pool
+    }
+
+    /// Returns the open handle for `name`, if the service is registered.
+    pub fn get(&self, name: &DomainServiceName) -> Option<Arc<ServiceHandle>> {
+        self.read().get(name).cloned()
+    }
+
+    /// Returns the registered service names, sorted.
+    pub fn names(&self) -> Vec<DomainServiceName> {
+        let mut names: Vec<_> = self.read().keys().cloned().collect();
+        names.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        names
+    }
+
+    /// Brings the pool in line with `config`.
+    ///
+    /// Handles of unchanged services are kept. Each list in the returned
+    /// [`PoolChanges`] is sorted by name.
+    pub fn apply(&self, config: &ServicesConfig) -> PoolChanges {
+        let mut handles = self
+            .handles
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut changes = PoolChanges::default();
+
+        let wanted: HashMap<_, _> = config
+            .services
+            .iter()
+            .map(|service| (service.name.clone(), service))
+            .collect();
+        handles.retain(|name, handle| {
+            let keep = wanted.contains_key(name);
+            if !keep {
+                handle.close();
+                changes.removed.push(name.clone());
+            }
+            keep
+        });
+
+        for (name, registration) in wanted {
+            match handles.get(&name) {
+                Some(current) if current.registration() == registration => {}
+                Some(current) => {
+                    current.close();
+                    handles.insert(name.clone(), ServiceHandle::open(registration.clone()));
+                    changes.replaced.push(name);
+                }
+                None => {
+                    handles.insert(name.clone(), ServiceHandle::open(registration.clone()));
+                    changes.added.push(name);
+                }
+            }
+        }
+
+        for list in [
+            &mut changes.added,
+            &mut changes.removed,
+            &mut changes.replaced,
+        ] {
+            list.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        }
+        changes
+    }
+
+    fn read(
+        &self,
+    ) -> std::sync::RwLockReadGuard<'_, HashMap<DomainServiceName, Arc<ServiceHandle>>> {
+        self.handles
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+#[path = "services_tests.rs"]
+mod tests;
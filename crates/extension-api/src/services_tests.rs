@@ -0,0 +1,217 @@
+use super::*;
+
+fn name(value: &str) -> DomainServiceName {
+    DomainServiceName::new(value).unwrap()
+}
+
+fn unix(service: &str, path: &str) -> ServiceRegistration {
+    ServiceRegistration {
+        name: name(service),
+        transport: ServiceTransport::Unix,
+        path: Some(PathBuf::from(path)),
+        url: None,
+        health_check_timeout_ms: DEFAULT_HEALTH_CHECK_TIMEOUT_MS,
+    }
+}
+
+fn config(services: Vec<ServiceRegistration>) -> ServicesConfig {
+    ServicesConfig { services }
+}
+
+// ─── ServicesConfig ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_unix_and_http_entries_applies_defaults() {
+    let text = r#"
+[[services]]
+name = "interfaces"
+path = "/run/cogworks/interfaces.sock"
+
+[[services]]
+name = "requirements"
+transport = "http"
+url = "http://localhost:8080"
+health_check_timeout_ms = 250
+"#;
+
+    let config = ServicesConfig::parse(text).unwrap();
+
+    assert_eq!(
+        config.services,
+        vec![
+            unix("interfaces", "/run/cogworks/interfaces.sock"),
+            ServiceRegistration {
+                name: name("requirements"),
+                transport: ServiceTransport::Http,
+                path: None,
+                url: Some("http://localhost:8080".to_string()),
+                health_check_timeout_ms: 250,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_empty_file_returns_no_services() {
+    assert_eq!(
+        ServicesConfig::parse("").unwrap(),
+        ServicesConfig::default()
+    );
+}
+
+#[test]
+fn test_parse_duplicate_name_returns_duplicate_service() {
+    let text = r#"
+[[services]]
+name = "interfaces"
+path = "/run/a.sock"
+
+[[services]]
+name = "interfaces"
+path = "/run/b.sock"
+"#;
+
+    let result = ServicesConfig::parse(text);
+
+    assert!(matches!(
+        result,
+        Err(ServicesConfigError::DuplicateService { name: n }) if n == name("interfaces")
+    ));
+}
+
+#[test]
+fn test_parse_unix_entry_without_path_returns_missing_endpoint() {
+    let text = "[[services]]\nname = \"interfaces\"\n";
+
+    let result = ServicesConfig::parse(text);
+
+    assert!(matches!(
+        result,
+        Err(ServicesConfigError::MissingEndpoint {
+            field: "path",
+            transport: ServiceTransport::Unix,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_parse_http_entry_without_url_returns_missing_endpoint() {
+    let text = "[[services]]\nname = \"interfaces\"\ntransport = \"http\"\n";
+
+    let result = ServicesConfig::parse(text);
+
+    assert!(matches!(
+        result,
+        Err(ServicesConfigError::MissingEndpoint {
+            field: "url",
+            transport: ServiceTransport::Http,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_parse_invalid_toml_returns_parse_error() {
+    let result = ServicesConfig::parse("[[services]\nname =");
+
+    assert!(matches!(result, Err(ServicesConfigError::Parse { .. })));
+}
+
+#[test]
+fn test_load_missing_file_returns_read_error() {
+    let path = std::env::temp_dir()
+        .join("cogworks-missing-services")
+        .join("services.toml");
+
+    let result = ServicesConfig::load(&path);
+
+    assert!(matches!(result, Err(ServicesConfigError::Read { path: p, .. }) if p == path));
+}
+
+// ─── ServicePool ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_new_opens_handle_per_service_and_lists_names_sorted() {
+    let pool = ServicePool::new(&config(vec![
+        unix("requirements", "/run/r.sock"),
+        unix("interfaces", "/run/i.sock"),
+    ]));
+
+    assert_eq!(pool.names(), vec![name("interfaces"), name("requirements")]);
+    let handle = pool.get(&name("interfaces")).unwrap();
+    assert!(!handle.is_closed());
+    assert_eq!(handle.registration(), &unix("interfaces", "/run/i.sock"));
+    assert!(pool.get(&name("unknown")).is_none());
+}
+
+#[test]
+fn test_apply_added_and_removed_services_updates_pool() {
+    let pool = ServicePool::new(&config(vec![
+        unix("interfaces", "/run/i.sock"),
+        unix("legacy", "/run/l.sock"),
+    ]));
+    let legacy = pool.get(&name("legacy")).unwrap();
+
+    let changes = pool.apply(&config(vec![
+        unix("interfaces", "/run/i.sock"),
+        unix("requirements", "/run/r.sock"),
+    ]));
+
+    assert_eq!(
+        changes,
+        PoolChanges {
+            added: vec![name("requirements")],
+            removed: vec![name("legacy")],
+            replaced: Vec::new(),
+        }
+    );
+    assert_eq!(pool.names(), vec![name("interfaces"), name("requirements")]);
+    assert!(legacy.is_closed());
+}
+
+#[test]
+fn test_apply_removed_service_in_flight_handle_stays_usable() {
+    let pool = ServicePool::new(&config(vec![unix("legacy", "/run/l.sock")]));
+    let in_flight = pool.get(&name("legacy")).unwrap();
+
+    pool.apply(&ServicesConfig::default());
+
+    assert!(pool.get(&name("legacy")).is_none());
+    assert!(in_flight.is_closed());
+    assert_eq!(in_flight.registration(), &unix("legacy", "/run/l.sock"));
+}
+
+#[test]
+fn test_apply_changed_endpoint_replaces_handle() {
+    let pool = ServicePool::new(&config(vec![unix("interfaces", "/run/old.sock")]));
+    let old = pool.get(&name("interfaces")).unwrap();
+
+    let changes = pool.apply(&config(vec![unix("interfaces", "/run/new.sock")]));
+
+    assert_eq!(changes.replaced, vec![name("interfaces")]);
+    assert!(changes.added.is_empty() && changes.removed.is_empty());
+    assert!(old.is_closed());
+    let new = pool.get(&name("interfaces")).unwrap();
+    assert!(!new.is_closed());
+    assert_eq!(
+        new.registration().path,
+        Some(PathBuf::from("/run/new.sock"))
+    );
+}
+
+#[test]
+fn test_apply_unchanged_config_keeps_existing_handles() {
+    let services = config(vec![unix("interfaces", "/run/i.sock")]);
+    let pool = ServicePool::new(&services);
+    let before = pool.get(&name("interfaces")).unwrap();
+
+    let changes = pool.apply(&services);
+
+    assert!(changes.is_empty());
+    assert!(Arc::ptr_eq(
+        &before,
+        &pool.get(&name("interfaces")).unwrap()
+    ));
+    assert!(!before.is_closed());
+}
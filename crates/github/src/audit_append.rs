@@ -0,0 +1,145 @@
+//! Idempotent appends to the audit trail.
+//!
+//! Audit events are stored as issue comments (see
+//! [`crate::audit_replay::render_audit_comment`]). When posting a comment
+//! fails transiently the caller cannot tell whether GitHub stored it, and a
+//! plain retry may log the event twice. [`GithubClient::append_event`] takes
+//! an idempotency key, embeds it in the comment as an HTML comment marker
+//! (invisible in the GitHub UI), and skips the write when a recent comment
+//! already carries the same key.
+//!
+//! [`AuditStore::record_event`](pipeline::AuditStore::record_event) goes through
+//! the same path with the key from [`event_idempotency_key`], which is derived
+//! from the run and the event's content, so recording the same event again
+//! after a failure does not duplicate it.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Idempotent audit append.
+
+use tracing::instrument;
+
+use pipeline::{
+    audit::{AuditEvent, AuditStoreError},
+    github::{GitHubOperationError, IssueComment, IssueTracker},
+    PipelineRunId, WorkItemId,
+};
+
+use crate::{audit_replay::render_audit_comment, GithubClient};
+
+/// Number of most recent comments searched for an existing idempotency key.
+///
+/// A retry follows the failed write closely, so the duplicate, if any, is
+/// among the newest comments.
+pub const IDEMPOTENCY_SCAN_DEPTH: usize = 100;
+
+/// Opening of the hidden marker that carries an idempotency key.
+const KEY_MARKER_OPEN: &str = "<!-- cogworks:audit-key:";
+
+/// Closing of the hidden marker that carries an idempotency key.
+const KEY_MARKER_CLOSE: &str = " -->";
+
+/// FNV-1a 64-bit offset basis.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64-bit prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Returns the hidden marker embedding `key`.
+///
+/// # Errors
+///
+/// - [`AuditStoreError::SerialisationError`] — `key` is empty, or contains
+///   `--` or a line break and so cannot be placed inside an HTML comment.
+pub fn idempotency_marker(key: &str) -> Result<String, AuditStoreError> {
+    if key.is_empty() || key.contains("--") || key.contains(['\n', '\r']) {
+        return Err(AuditStoreError::SerialisationError {
+            message: format!("idempotency key {key:?} cannot be embedded in an HTML comment"),
+        });
+    }
+    Ok(format!("{KEY_MARKER_OPEN}{key}{KEY_MARKER_CLOSE}"))
+}
+
+/// Returns the idempotency key [`AuditStore::record_event`](pipeline::AuditStore::record_event)
+/// uses for `event`: the run ID and a 64-bit FNV-1a hash of the rendered
+/// audit comment.
+///
+/// Every event carries its own timestamp, so two distinct events of a run get
+/// different keys while a retry of the same event gets the same one.
+///
+/// # Errors
+///
+/// - [`AuditStoreError::SerialisationError`] — the event could not be
+///   serialised.
+pub fn event_idempotency_key(
+    run_id: PipelineRunId,
+    event: &AuditEvent,
+) -> Result<String, AuditStoreError> {
+    let rendered = render_audit_comment(run_id, event)?;
+    let hash = rendered.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    Ok(format!("{run_id}:{hash:016x}"))
+}
+
+/// Returns `true` if any of the newest [`IDEMPOTENCY_SCAN_DEPTH`] comments in
+/// `comments` (oldest first) contains `marker`.
+pub fn contains_marker(comments: &[IssueComment], marker: &str) -> bool {
+    comments
+        .iter()
+        .rev()
+        .take(IDEMPOTENCY_SCAN_DEPTH)
+        .any(|c| c.body.contains(marker))
+}
+
+impl GithubClient {
+    /// Append `event` to the audit trail on `work_item_id` unless an event with
+    /// `idempotency_key` has already been recorded there.
+    ///
+    /// Retrying with the same key after a transient failure therefore writes
+    /// the comment at most once. Keys only need to be unique per work item;
+    /// deriving them from the run ID and a per-run sequence number is enough.
+    ///
+    /// # Errors
+    ///
+    /// - [`AuditStoreError::SerialisationError`] — the event could not be
+    ///   serialised, or the key cannot be embedded (see
+    ///   [`idempotency_marker`]).
+    /// - [`AuditStoreError::Unavailable`] — listing or posting comments failed,
+    ///   including when the client has no repository or transport.
+    #[instrument(skip(self, event), fields(work_item = %work_item_id))]
+    pub async fn append_event(
+        &self,
+        work_item_id: WorkItemId,
+        run_id: PipelineRunId,
+        event: AuditEvent,
+        idempotency_key: &str,
+    ) -> Result<(), AuditStoreError> {
+        let marker = idempotency_marker(idempotency_key)?;
+        let comments = self
+            .list_comments(work_item_id)
+            .await
+            .map_err(unavailable)?;
+        if contains_marker(&comments, &marker) {
+            tracing::debug!(
+                key = idempotency_key,
+                "audit event already recorded; skipping"
+            );
+            return Ok(());
+        }
+        let body = format!("{marker}\n{}", render_audit_comment(run_id, &event)?);
+        self.post_comment(work_item_id, &body)
+            .await
+            .map_err(unavailable)
+    }
+}
+
+fn unavailable(e: GitHubOperationError) -> AuditStoreError {
+    AuditStoreError::Unavailable {
+        message: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "audit_append_tests.rs"]
+mod tests;
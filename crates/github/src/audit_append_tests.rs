@@ -0,0 +1,262 @@
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use pipeline::{audit::StateTransitionRecord, AuditStore, CommentId, NodeId, NodeStatus};
+use serde_json::{json, Value as JsonValue};
+
+use crate::{
+    test_support::client,
+    transport::{RestMethod, ScriptedTransport},
+};
+
+use super::*;
+
+fn transition(to_status: NodeStatus) -> AuditEvent {
+    AuditEvent::StateTransition(StateTransitionRecord {
+        node_id: NodeId::new("plan").unwrap(),
+        from_status: NodeStatus::Pending,
+        to_status,
+        reason: None,
+        timestamp: Utc.with_ymd_and_hms(2026, 10, 15, 9, 30, 0).unwrap(),
+    })
+}
+
+/// A comments listing holding the bodies of every comment posted so far.
+fn listing_of_posts(transport: &ScriptedTransport) -> JsonValue {
+    JsonValue::Array(
+        transport
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == RestMethod::Post)
+            .enumerate()
+            .map(|(i, r)| {
+                json!({
+                    "id": i + 1,
+                    "body": r.body.unwrap()["body"],
+                    "user": { "login": "cogworks[bot]" },
+                    "created_at": "2026-10-15T09:30:00Z"
+                })
+            })
+            .collect(),
+    )
+}
+
+fn posts(transport: &ScriptedTransport) -> usize {
+    transport
+        .requests()
+        .iter()
+        .filter(|r| r.method == RestMethod::Post)
+        .count()
+}
+
+fn comment(id: u64, body: &str) -> IssueComment {
+    IssueComment {
+        id: CommentId::new(id),
+        author: "cogworks[bot]".to_string(),
+        body: body.to_string(),
+        created_at: Utc.with_ymd_and_hms(2026, 10, 15, 9, 30, 0).unwrap(),
+    }
+}
+
+/// What a successful first write leaves on the issue: the marker on the
+/// first line of the audit comment.
+fn written(id: u64, key: &str) -> IssueComment {
+    let marker = idempotency_marker(key).unwrap();
+    comment(id, &format!("{marker}\n<details>audit event</details>"))
+}
+
+// ─── idempotency_marker ─────────────────────────────────────────────────────
+
+#[test]
+fn test_idempotency_marker_valid_key_returns_hidden_html_comment() {
+    assert_eq!(
+        idempotency_marker("run-7:12").unwrap(),
+        "<!-- cogworks:audit-key:run-7:12 -->"
+    );
+}
+
+#[test]
+fn test_idempotency_marker_unembeddable_key_returns_serialisation_error() {
+    for key in ["", "a--b", "line\nbreak", "carriage\rreturn"] {
+        assert!(
+            matches!(
+                idempotency_marker(key),
+                Err(AuditStoreError::SerialisationError { .. })
+            ),
+            "key {key:?} was accepted"
+        );
+    }
+}
+
+// ─── contains_marker ────────────────────────────────────────────────────────
+
+#[test]
+fn test_contains_marker_retry_after_stored_write_returns_true() {
+    let mut comments = vec![comment(1, "Looks good to me")];
+    let marker = idempotency_marker("run-7:12").unwrap();
+    assert!(!contains_marker(&comments, &marker));
+
+    comments.push(written(2, "run-7:12"));
+
+    assert!(contains_marker(&comments, &marker));
+}
+
+#[test]
+fn test_contains_marker_different_key_returns_false() {
+    let comments = vec![written(1, "run-7:12")];
+
+    assert!(!contains_marker(
+        &comments,
+        &idempotency_marker("run-7:13").unwrap()
+    ));
+}
+
+#[test]
+fn test_contains_marker_key_prefix_of_other_key_returns_false() {
+    let comments = vec![written(1, "run-7:120")];
+
+    assert!(!contains_marker(
+        &comments,
+        &idempotency_marker("run-7:12").unwrap()
+    ));
+}
+
+#[test]
+fn test_contains_marker_only_beyond_scan_depth_returns_false() {
+    let depth = u64::try_from(IDEMPOTENCY_SCAN_DEPTH).unwrap();
+    let mut comments = vec![written(0, "run-7:12")];
+    comments.extend((1..=depth).map(|id| comment(id, "unrelated")));
+
+    assert!(!contains_marker(
+        &comments,
+        &idempotency_marker("run-7:12").unwrap()
+    ));
+}
+
+// ─── event_idempotency_key ──────────────────────────────────────────────────
+
+#[test]
+fn test_event_idempotency_key_same_event_returns_same_embeddable_key() {
+    let run_id = PipelineRunId::new_random();
+
+    let first = event_idempotency_key(run_id, &transition(NodeStatus::Active)).unwrap();
+    let second = event_idempotency_key(run_id, &transition(NodeStatus::Active)).unwrap();
+
+    assert_eq!(first, second);
+    assert!(first.starts_with(&run_id.to_string()));
+    assert!(idempotency_marker(&first).is_ok());
+}
+
+#[test]
+fn test_event_idempotency_key_other_run_returns_different_key() {
+    let event = transition(NodeStatus::Active);
+
+    assert_ne!(
+        event_idempotency_key(PipelineRunId::new_random(), &event).unwrap(),
+        event_idempotency_key(PipelineRunId::new_random(), &event).unwrap()
+    );
+}
+
+// ─── append_event / record_event ────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_append_event_same_key_twice_posts_once() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let client = client(&transport);
+    let run_id = PipelineRunId::new_random();
+    transport.push_json(200, json!([]));
+    transport.push_json(201, json!({ "id": 1 }));
+
+    client
+        .append_event(
+            WorkItemId::new(42),
+            run_id,
+            transition(NodeStatus::Active),
+            "run-7:12",
+        )
+        .await
+        .unwrap();
+    transport.push_json(200, listing_of_posts(&transport));
+    client
+        .append_event(
+            WorkItemId::new(42),
+            run_id,
+            transition(NodeStatus::Active),
+            "run-7:12",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(posts(&transport), 1);
+    let post = transport
+        .requests()
+        .into_iter()
+        .find(|r| r.method == RestMethod::Post)
+        .unwrap();
+    assert_eq!(post.path, "/repos/octo/widgets/issues/42/comments");
+}
+
+#[tokio::test]
+async fn test_record_event_same_event_twice_posts_once() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let client = client(&transport);
+    let run_id = PipelineRunId::new_random();
+    transport.push_json(200, json!([]));
+    transport.push_json(201, json!({ "id": 1 }));
+
+    client
+        .record_event(run_id, WorkItemId::new(42), transition(NodeStatus::Active))
+        .await
+        .unwrap();
+    transport.push_json(200, listing_of_posts(&transport));
+    client
+        .record_event(run_id, WorkItemId::new(42), transition(NodeStatus::Active))
+        .await
+        .unwrap();
+
+    assert_eq!(posts(&transport), 1);
+}
+
+#[tokio::test]
+async fn test_record_event_distinct_events_post_twice() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let client = client(&transport);
+    let run_id = PipelineRunId::new_random();
+    transport.push_json(200, json!([]));
+    transport.push_json(201, json!({ "id": 1 }));
+
+    client
+        .record_event(run_id, WorkItemId::new(42), transition(NodeStatus::Active))
+        .await
+        .unwrap();
+    transport.push_json(200, listing_of_posts(&transport));
+    transport.push_json(201, json!({ "id": 2 }));
+    client
+        .record_event(
+            run_id,
+            WorkItemId::new(42),
+            transition(NodeStatus::Completed),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(posts(&transport), 2);
+}
+
+#[tokio::test]
+async fn test_append_event_post_fails_returns_unavailable() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, json!([]));
+    transport.push_json(502, json!({ "message": "Bad Gateway" }));
+
+    let result = client(&transport)
+        .append_event(
+            WorkItemId::new(42),
+            PipelineRunId::new_random(),
+            transition(NodeStatus::Active),
+            "run-7:12",
+        )
+        .await;
+
+    assert!(matches!(result, Err(AuditStoreError::Unavailable { .. })));
+}
@@ -0,0 +1,279 @@
+//! Filtered replay of audit events stored as issue comments.
+//!
+//! [`GithubClient`] persists each [`AuditEvent`] as a comment on the work-item
+//! issue (see [`render_audit_comment`]). For analysis it is often enough to
+//! look at one kind of event — only LLM calls, say — for one run.
+//! [`GithubClient::read_events_filtered`] returns an [`AuditEventStream`] that
+//! fetches comments one page at a time and yields only the events of the
+//! requested run that match a predicate, so memory use is bounded by one page
+//! regardless of how long the audit trail is.
+//!
+//! Comments that are not audit records, or whose JSON no longer parses, are
+//! skipped.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Audit replay.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tracing::instrument;
+
+use pipeline::{
+    audit::{AuditEvent, AuditStoreError},
+    github::{GitHubOperationError, IssueComment},
+    CommentId, PipelineRunId, WorkItemId,
+};
+
+use crate::{rate_limited::status_error, transport::RestRequest, GithubClient};
+
+/// Number of comments requested per page while replaying.
+pub const AUDIT_REPLAY_PAGE_SIZE: u32 = 100;
+
+/// Login reported for comments whose author account has been deleted.
+const GHOST_LOGIN: &str = "ghost";
+
+/// Opening fence of the JSON block inside an audit comment.
+const JSON_FENCE: &str = "```json";
+
+/// Closing fence of the JSON block inside an audit comment.
+const FENCE: &str = "```";
+
+/// The JSON document embedded in every audit comment.
+#[derive(Debug, Serialize, Deserialize)]
+struct AuditRecord {
+    run_id: PipelineRunId,
+    event: AuditEvent,
+}
+
+/// Formats `event` as the body of an audit comment.
+///
+/// The event is wrapped in a collapsible `<details>` block whose summary names
+/// the event kind, with the run ID and event serialised as a fenced JSON
+/// block.
+///
+/// # Errors
+///
+/// - [`AuditStoreError::SerialisationError`] — the event could not be
+///   serialised.
+pub fn render_audit_comment(
+    run_id: PipelineRunId,
+    event: &AuditEvent,
+) -> Result<String, AuditStoreError> {
+    let record = AuditRecord {
+        run_id,
+        event: event.clone(),
+    };
+    let json =
+        serde_json::to_string_pretty(&record).map_err(|e| AuditStoreError::SerialisationError {
+            message: e.to_string(),
+        })?;
+    let kind = serde_json::to_value(event.kind())
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+    Ok(format!(
+        "<details>\n<summary>Audit: {kind}</summary>\n\n{JSON_FENCE}\n{json}\n{FENCE}\n\n</details>"
+    ))
+}
+
+/// Extracts the run ID and event from an audit comment body.
+///
+/// Returns `None` for comments that are not audit records.
+pub fn parse_audit_comment(body: &str) -> Option<(PipelineRunId, AuditEvent)> {
+    let start = body.find(JSON_FENCE)? + JSON_FENCE.len();
+    let rest = &body[start..];
+    let end = rest.find(FENCE)?;
+    let record: AuditRecord = serde_json::from_str(rest[..end].trim()).ok()?;
+    Some((record.run_id, record.event))
+}
+
+// ─── Paging ──────────────────────────────────────────────────────────────────
+
+/// A source of issue comments delivered one page at a time, oldest first.
+#[async_trait]
+pub trait CommentPages: Send {
+    /// Fetches the next page, or `Ok(None)` once every page has been read.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — the issue does not exist.
+    /// - [`GitHubOperationError::Transient`] — transient network failure.
+    async fn next_page(&mut self) -> Result<Option<Vec<IssueComment>>, GitHubOperationError>;
+}
+
+/// Pages through the comments of one work-item issue via the REST API.
+struct IssueCommentPages<'a> {
+    client: &'a GithubClient,
+    work_item_id: WorkItemId,
+    /// 1-based number of the next page to fetch; `None` once exhausted.
+    next_page: Option<u32>,
+}
+
+#[async_trait]
+impl CommentPages for IssueCommentPages<'_> {
+    /// Fetches `GET /repos/{owner}/{repo}/issues/{number}/comments`, one page
+    /// of [`AUDIT_REPLAY_PAGE_SIZE`] comments at a time. A short page is the
+    /// last one.
+    #[instrument(skip(self), fields(work_item = %self.work_item_id))]
+    async fn next_page(&mut self) -> Result<Option<Vec<IssueComment>>, GitHubOperationError> {
+        let Some(page) = self.next_page else {
+            return Ok(None);
+        };
+        let path = format!(
+            "{}/comments?per_page={AUDIT_REPLAY_PAGE_SIZE}&page={page}",
+            self.client.issue_path(self.work_item_id)?
+        );
+        let response = self.client.send(RestRequest::get(path)).await?;
+        if let Some(error) = status_error(&response, &format!("issue #{}", self.work_item_id)) {
+            return Err(error);
+        }
+        let comments = parse_comments_page(&response.body)?;
+        self.next_page = (comments.len() >= AUDIT_REPLAY_PAGE_SIZE as usize).then(|| page + 1);
+        if comments.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(comments))
+    }
+}
+
+#[derive(Deserialize)]
+struct WireComment {
+    id: u64,
+    body: Option<String>,
+    created_at: DateTime<Utc>,
+    user: Option<WireUser>,
+}
+
+#[derive(Deserialize)]
+struct WireUser {
+    login: String,
+}
+
+/// Maps one page of the issue comments listing onto [`IssueComment`]s.
+///
+/// # Errors
+///
+/// [`GitHubOperationError::ParseFailure`] — the page is not an array of
+/// comments.
+pub fn parse_comments_page(body: &JsonValue) -> Result<Vec<IssueComment>, GitHubOperationError> {
+    let comments: Vec<WireComment> =
+        serde_json::from_value(body.clone()).map_err(|e| GitHubOperationError::ParseFailure {
+            message: format!("issue comments: {e}"),
+        })?;
+    Ok(comments
+        .into_iter()
+        .map(|comment| IssueComment {
+            id: CommentId::new(comment.id),
+            author: comment
+                .user
+                .map_or_else(|| GHOST_LOGIN.to_string(), |user| user.login),
+            body: comment.body.unwrap_or_default(),
+            created_at: comment.created_at,
+        })
+        .collect())
+}
+
+// ─── Stream ──────────────────────────────────────────────────────────────────
+
+/// Audit events of one run that match a predicate, read lazily page by page.
+///
+/// Follows the `next_*` convention of [`crate::streaming`]: `Ok(Some(_))` for
+/// an event, `Ok(None)` at the end of the audit trail.
+pub struct AuditEventStream<P, F> {
+    pages: P,
+    run_id: PipelineRunId,
+    predicate: F,
+    current: std::vec::IntoIter<IssueComment>,
+    finished: bool,
+}
+
+impl<P, F> AuditEventStream<P, F>
+where
+    P: CommentPages,
+    F: Fn(&AuditEvent) -> bool + Send,
+{
+    /// Creates a stream over `pages` yielding events of `run_id` for which
+    /// `predicate` returns `true`.
+    pub fn new(pages: P, run_id: PipelineRunId, predicate: F) -> Self {
+        Self {
+            pages,
+            run_id,
+            predicate,
+            current: Vec::new().into_iter(),
+            finished: false,
+        }
+    }
+
+    /// Returns the next matching event, in the order it was recorded.
+    ///
+    /// # Errors
+    ///
+    /// - [`AuditStoreError::Unavailable`] — a page of comments could not be
+    ///   fetched. The stream may be polled again to retry the same page.
+    pub async fn next_event(&mut self) -> Result<Option<AuditEvent>, AuditStoreError> {
+        loop {
+            for comment in self.current.by_ref() {
+                match parse_audit_comment(&comment.body) {
+                    Some((run_id, event)) if run_id == self.run_id && (self.predicate)(&event) => {
+                        return Ok(Some(event));
+                    }
+                    _ => {}
+                }
+            }
+            if self.finished {
+                return Ok(None);
+            }
+            match self.pages.next_page().await {
+                Ok(Some(page)) => self.current = page.into_iter(),
+                Ok(None) => self.finished = true,
+                Err(e) => {
+                    return Err(AuditStoreError::Unavailable {
+                        message: e.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+// ─── GithubClient entry point ────────────────────────────────────────────────
+
+impl GithubClient {
+    /// Replay the audit events of `run_id` recorded on `work_item_id` for which
+    /// `predicate` returns `true`.
+    ///
+    /// No request is made until the stream is first polled; comments are then
+    /// fetched [`AUDIT_REPLAY_PAGE_SIZE`] at a time from the repository set
+    /// with [`GithubClient::with_repository`]. Without one, polling fails with
+    /// [`AuditStoreError::Unavailable`].
+    ///
+    /// ```rust,ignore
+    /// let mut calls = client.read_events_filtered(work_item, run, |e| {
+    ///     e.kind() == AuditEventKind::LlmCall
+    /// });
+    /// while let Some(event) = calls.next_event().await? { /* ... */ }
+    /// ```
+    pub fn read_events_filtered<F>(
+        &self,
+        work_item_id: WorkItemId,
+        run_id: PipelineRunId,
+        predicate: F,
+    ) -> AuditEventStream<impl CommentPages + '_, F>
+    where
+        F: Fn(&AuditEvent) -> bool + Send,
+    {
+        let pages = IssueCommentPages {
+            client: self,
+            work_item_id,
+            next_page: Some(1),
+        };
+        AuditEventStream::new(pages, run_id, predicate)
+    }
+}
+
+#[cfg(test)]
+#[path = "audit_replay_tests.rs"]
+mod tests;
@@ -0,0 +1,295 @@
+use std::sync::Arc;
+
+use chrono::TimeZone;
+use pipeline::{
+    audit::{AuditEventKind, LlmCallRecord, StateTransitionRecord},
+    NodeId, NodeStatus, TokenCost, TokenCount,
+};
+use serde_json::json;
+
+use crate::{
+    test_support::client,
+    transport::{ScriptedTransport, REPOSITORY_CAPABILITY},
+};
+
+use super::*;
+
+fn timestamp() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 15, 9, 30, 0).unwrap()
+}
+
+fn node() -> NodeId {
+    NodeId::new("plan").unwrap()
+}
+
+fn llm_call(model: &str) -> AuditEvent {
+    AuditEvent::LlmCall(LlmCallRecord {
+        node_id: node(),
+        model_id: model.to_string(),
+        prompt_tokens: TokenCount::new(100),
+        completion_tokens: TokenCount::new(20),
+        cost: TokenCost::zero(),
+        latency: std::time::Duration::from_millis(250),
+        schema_validated: true,
+        request_id: None,
+        provider_request_id: None,
+        timestamp: timestamp(),
+    })
+}
+
+fn transition() -> AuditEvent {
+    AuditEvent::StateTransition(StateTransitionRecord {
+        node_id: node(),
+        from_status: NodeStatus::Pending,
+        to_status: NodeStatus::Active,
+        reason: None,
+        timestamp: timestamp(),
+    })
+}
+
+fn comment(id: u64, body: String) -> IssueComment {
+    IssueComment {
+        id: CommentId::new(id),
+        author: "cogworks[bot]".to_string(),
+        body,
+        created_at: timestamp(),
+    }
+}
+
+fn audit_comment(id: u64, run_id: PipelineRunId, event: &AuditEvent) -> IssueComment {
+    comment(id, render_audit_comment(run_id, event).unwrap())
+}
+
+fn model_of(event: &AuditEvent) -> &str {
+    match event {
+        AuditEvent::LlmCall(record) => &record.model_id,
+        other => panic!("expected an LLM call, got {other:?}"),
+    }
+}
+
+fn is_llm_call(event: &AuditEvent) -> bool {
+    event.kind() == AuditEventKind::LlmCall
+}
+
+/// Serves fixed pages and counts how many were requested.
+struct FixedPages {
+    pages: std::vec::IntoIter<Result<Vec<IssueComment>, GitHubOperationError>>,
+    fetched: usize,
+}
+
+impl FixedPages {
+    fn new(pages: Vec<Result<Vec<IssueComment>, GitHubOperationError>>) -> Self {
+        Self {
+            pages: pages.into_iter(),
+            fetched: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl CommentPages for FixedPages {
+    async fn next_page(&mut self) -> Result<Option<Vec<IssueComment>>, GitHubOperationError> {
+        self.fetched += 1;
+        self.pages.next().transpose()
+    }
+}
+
+// ─── Comment format ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_render_audit_comment_names_kind_in_summary() {
+    let body = render_audit_comment(PipelineRunId::new_random(), &llm_call("model-a")).unwrap();
+
+    assert!(body.starts_with("<details>\n<summary>Audit: llm_call</summary>"));
+    assert!(body.ends_with("</details>"));
+}
+
+#[test]
+fn test_parse_audit_comment_rendered_comment_returns_run_and_event() {
+    let run_id = PipelineRunId::new_random();
+
+    let (parsed_run, event) =
+        parse_audit_comment(&render_audit_comment(run_id, &llm_call("model-a")).unwrap()).unwrap();
+
+    assert_eq!(parsed_run, run_id);
+    assert_eq!(model_of(&event), "model-a");
+}
+
+#[test]
+fn test_parse_audit_comment_plain_comment_returns_none() {
+    assert!(parse_audit_comment("Looks good to me").is_none());
+    assert!(parse_audit_comment("```json\n{ \"not\": \"audit\" }\n```").is_none());
+}
+
+#[test]
+fn test_parse_comments_page_missing_user_returns_ghost_author() {
+    let body = json!([
+        { "id": 1, "body": "first", "created_at": "2026-10-15T09:30:00Z", "user": { "login": "octocat" } },
+        { "id": 2, "body": null, "created_at": "2026-10-15T09:30:00Z", "user": null }
+    ]);
+
+    let comments = parse_comments_page(&body).unwrap();
+
+    assert_eq!(comments[0].id, CommentId::new(1));
+    assert_eq!(comments[0].author, "octocat");
+    assert_eq!(comments[0].body, "first");
+    assert_eq!(comments[1].author, GHOST_LOGIN);
+    assert_eq!(comments[1].body, "");
+}
+
+#[test]
+fn test_parse_comments_page_not_an_array_returns_parse_failure() {
+    let error = parse_comments_page(&json!({ "message": "nope" })).unwrap_err();
+
+    assert!(matches!(error, GitHubOperationError::ParseFailure { .. }));
+}
+
+// ─── Stream ──────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_next_event_kind_predicate_returns_only_matching_events() {
+    let run_id = PipelineRunId::new_random();
+    let pages = FixedPages::new(vec![
+        Ok(vec![
+            audit_comment(1, run_id, &transition()),
+            audit_comment(2, run_id, &llm_call("model-a")),
+            comment(3, "a human comment".to_string()),
+        ]),
+        Ok(vec![
+            audit_comment(4, run_id, &transition()),
+            audit_comment(5, run_id, &llm_call("model-b")),
+        ]),
+    ]);
+    let mut stream = AuditEventStream::new(pages, run_id, is_llm_call);
+
+    let first = stream.next_event().await.unwrap().unwrap();
+    let second = stream.next_event().await.unwrap().unwrap();
+
+    assert_eq!(model_of(&first), "model-a");
+    assert_eq!(model_of(&second), "model-b");
+    assert!(stream.next_event().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_next_event_other_run_events_are_skipped() {
+    let run_id = PipelineRunId::new_random();
+    let other_run = PipelineRunId::new_random();
+    let pages = FixedPages::new(vec![Ok(vec![
+        audit_comment(1, other_run, &llm_call("other")),
+        audit_comment(2, run_id, &llm_call("mine")),
+    ])]);
+    let mut stream = AuditEventStream::new(pages, run_id, |_: &AuditEvent| true);
+
+    assert_eq!(
+        model_of(&stream.next_event().await.unwrap().unwrap()),
+        "mine"
+    );
+    assert!(stream.next_event().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_next_event_reads_second_page_only_when_first_is_exhausted() {
+    let run_id = PipelineRunId::new_random();
+    let pages = FixedPages::new(vec![
+        Ok(vec![audit_comment(1, run_id, &llm_call("model-a"))]),
+        Ok(vec![audit_comment(2, run_id, &llm_call("model-b"))]),
+    ]);
+    let mut stream = AuditEventStream::new(pages, run_id, is_llm_call);
+
+    stream.next_event().await.unwrap();
+
+    assert_eq!(stream.pages.fetched, 1);
+}
+
+#[tokio::test]
+async fn test_next_event_page_error_returns_unavailable() {
+    let run_id = PipelineRunId::new_random();
+    let pages = FixedPages::new(vec![Err(GitHubOperationError::Transient {
+        message: "connection reset".to_string(),
+    })]);
+    let mut stream = AuditEventStream::new(pages, run_id, is_llm_call);
+
+    let error = stream.next_event().await.unwrap_err();
+
+    assert!(matches!(error, AuditStoreError::Unavailable { .. }));
+}
+
+// ─── GithubClient ────────────────────────────────────────────────────────────
+
+fn wire_comment(id: u64, body: &str) -> JsonValue {
+    json!({
+        "id": id,
+        "body": body,
+        "created_at": "2026-10-15T09:30:00Z",
+        "user": { "login": "cogworks[bot]" }
+    })
+}
+
+#[tokio::test]
+async fn test_read_events_filtered_requests_comment_pages_until_short_page() {
+    let run_id = PipelineRunId::new_random();
+    let full_page: Vec<JsonValue> = (0..u64::from(AUDIT_REPLAY_PAGE_SIZE))
+        .map(|id| wire_comment(id, "not audit"))
+        .collect();
+    let audit_body = render_audit_comment(run_id, &llm_call("model-a")).unwrap();
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, JsonValue::Array(full_page));
+    transport.push_json(200, json!([wire_comment(500, &audit_body)]));
+    let client = client(&transport);
+
+    let mut stream = client.read_events_filtered(WorkItemId::new(42), run_id, is_llm_call);
+
+    assert_eq!(
+        model_of(&stream.next_event().await.unwrap().unwrap()),
+        "model-a"
+    );
+    assert!(stream.next_event().await.unwrap().is_none());
+    let paths: Vec<String> = transport
+        .requests()
+        .into_iter()
+        .map(|request| request.path)
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/repos/octo/widgets/issues/42/comments?per_page=100&page=1".to_string(),
+            "/repos/octo/widgets/issues/42/comments?per_page=100&page=2".to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_read_events_filtered_missing_issue_returns_unavailable() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+    let client = client(&transport);
+
+    let mut stream = client.read_events_filtered(
+        WorkItemId::new(42),
+        PipelineRunId::new_random(),
+        is_llm_call,
+    );
+
+    assert!(matches!(
+        stream.next_event().await.unwrap_err(),
+        AuditStoreError::Unavailable { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_read_events_filtered_without_repository_returns_unavailable() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let client = GithubClient::new(Arc::new(())).with_transport(Arc::clone(&transport) as _);
+
+    let mut stream = client.read_events_filtered(
+        WorkItemId::new(42),
+        PipelineRunId::new_random(),
+        is_llm_call,
+    );
+
+    let AuditStoreError::Unavailable { message } = stream.next_event().await.unwrap_err() else {
+        panic!("expected Unavailable");
+    };
+    assert!(message.contains(REPOSITORY_CAPABILITY), "{message}");
+    assert!(transport.requests().is_empty());
+}
@@ -0,0 +1,286 @@
+//! Enabling auto-merge on pull requests.
+//!
+//! In fully automated flows the integration node opens a pull request and
+//! leaves it to GitHub to merge once the required checks and reviews pass.
+//! Auto-merge is only reachable through the GraphQL
+//! `enablePullRequestAutoMerge` mutation, which addresses the pull request by
+//! node ID, so enabling it takes two requests:
+//!
+//! 1. [`auto_merge_target_request`] reads the pull request's node ID and
+//!    whether the repository allows auto-merge at all;
+//! 2. [`enable_auto_merge_request`] enables it with the chosen
+//!    [`AutoMergeMethod`].
+//!
+//! A repository with "Allow auto-merge" turned off in its settings yields
+//! [`GitHubOperationError::AutoMergeDisabled`], from the first response's
+//! `autoMergeAllowed` or, if the setting changed in between, from the
+//! mutation's error. Retrying does not help; a repository admin has to turn
+//! the setting on.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Auto-merge.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use tracing::instrument;
+
+use pipeline::{
+    github::{GitHubOperationError, PullRequest},
+    PullRequestId, RepositoryId,
+};
+
+use crate::{
+    graphql::{classify_error, parse_errors, GraphQlNodeId, RATE_LIMIT_SELECTION},
+    GithubClient,
+};
+
+/// Fragment of GitHub's error message when the repository disallows
+/// auto-merge.
+const AUTO_MERGE_NOT_ALLOWED: &str = "auto merge is not allowed";
+
+/// How GitHub merges the pull request once it is ready.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoMergeMethod {
+    /// A merge commit.
+    Merge,
+    /// All commits squashed into one.
+    #[default]
+    Squash,
+    /// Commits rebased onto the base branch.
+    Rebase,
+}
+
+impl AutoMergeMethod {
+    /// The `PullRequestMergeMethod` enum value GraphQL expects.
+    pub fn as_graphql(self) -> &'static str {
+        match self {
+            Self::Merge => "MERGE",
+            Self::Squash => "SQUASH",
+            Self::Rebase => "REBASE",
+        }
+    }
+}
+
+/// What [`auto_merge_target_request`] reads about a pull request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoMergeTarget {
+    /// GraphQL node ID of the pull request.
+    pub node_id: GraphQlNodeId,
+    /// Whether auto-merge is already enabled on the pull request.
+    pub already_enabled: bool,
+}
+
+// ─── GraphQL documents ───────────────────────────────────────────────────────
+
+const TARGET_QUERY_FIELDS: &str = "\
+  repository(owner: $owner, name: $name) {
+    autoMergeAllowed
+    pullRequest(number: $number) { id autoMergeRequest { enabledAt } }
+  }";
+
+const ENABLE_MUTATION: &str = "\
+mutation($pullRequestId: ID!, $mergeMethod: PullRequestMergeMethod!) {
+  enablePullRequestAutoMerge(input: { pullRequestId: $pullRequestId, mergeMethod: $mergeMethod }) {
+    pullRequest { autoMergeRequest { enabledAt } }
+  }
+}";
+
+/// Builds the GraphQL request reading the auto-merge target for pull request
+/// `number` in `repository`.
+pub fn auto_merge_target_request(repository: &RepositoryId, number: PullRequestId) -> JsonValue {
+    let query = format!(
+        "query($owner: String!, $name: String!, $number: Int!) {{\n{TARGET_QUERY_FIELDS}\n  \
+         {RATE_LIMIT_SELECTION}\n}}"
+    );
+    json!({
+        "query": query,
+        "variables": {
+            "owner": repository.owner(),
+            "name": repository.repo(),
+            "number": number.as_u64(),
+        },
+    })
+}
+
+/// Builds the GraphQL request enabling auto-merge on `pull_request` with
+/// `method`.
+pub fn enable_auto_merge_request(
+    pull_request: &GraphQlNodeId,
+    method: AutoMergeMethod,
+) -> JsonValue {
+    json!({
+        "query": ENABLE_MUTATION,
+        "variables": {
+            "pullRequestId": pull_request.as_str(),
+            "mergeMethod": method.as_graphql(),
+        },
+    })
+}
+
+// ─── Response mapping ────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WireRepository {
+    auto_merge_allowed: bool,
+    pull_request: Option<WirePullRequest>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WirePullRequest {
+    id: GraphQlNodeId,
+    auto_merge_request: Option<JsonValue>,
+}
+
+/// Returns [`GitHubOperationError::AutoMergeDisabled`] if any error in
+/// `response` reports that the repository disallows auto-merge.
+fn auto_merge_disabled(
+    repository: &RepositoryId,
+    response: &JsonValue,
+) -> Option<GitHubOperationError> {
+    parse_errors(response)
+        .iter()
+        .any(|error| {
+            error
+                .message
+                .to_ascii_lowercase()
+                .contains(AUTO_MERGE_NOT_ALLOWED)
+        })
+        .then(|| GitHubOperationError::AutoMergeDisabled {
+            repository: repository.clone(),
+        })
+}
+
+/// Maps a response to [`auto_merge_target_request`].
+///
+/// # Errors
+///
+/// - [`GitHubOperationError::AutoMergeDisabled`] — the repository does not
+///   allow auto-merge.
+/// - [`GitHubOperationError::NotFound`] — the repository or pull request
+///   does not exist.
+/// - [`GitHubOperationError::ParseFailure`] — the response has an unexpected
+///   shape.
+/// - Any other reported error, mapped by [`classify_error`].
+pub fn parse_auto_merge_target(
+    repository: &RepositoryId,
+    number: PullRequestId,
+    response: &JsonValue,
+    rate_limit_reset: DateTime<Utc>,
+) -> Result<AutoMergeTarget, GitHubOperationError> {
+    let resource = format!("pull request #{number} in {repository}");
+    if let Some(error) = parse_errors(response).first() {
+        return Err(classify_error(&resource, error, rate_limit_reset));
+    }
+    let Some(wire) = response.get("data").and_then(|data| data.get("repository")) else {
+        return Err(GitHubOperationError::ParseFailure {
+            message: format!("{resource}: response has no repository data"),
+        });
+    };
+    if wire.is_null() {
+        return Err(GitHubOperationError::NotFound { resource });
+    }
+    let wire: WireRepository =
+        serde_json::from_value(wire.clone()).map_err(|e| GitHubOperationError::ParseFailure {
+            message: format!("{resource}: {e}"),
+        })?;
+    if !wire.auto_merge_allowed {
+        return Err(GitHubOperationError::AutoMergeDisabled {
+            repository: repository.clone(),
+        });
+    }
+    let pull_request = wire
+        .pull_request
+        .ok_or(GitHubOperationError::NotFound { resource })?;
+    Ok(AutoMergeTarget {
+        node_id: pull_request.id,
+        already_enabled: pull_request
+            .auto_merge_request
+            .is_some_and(|request| !request.is_null()),
+    })
+}
+
+/// Checks a response to [`enable_auto_merge_request`].
+///
+/// # Errors
+///
+/// - [`GitHubOperationError::AutoMergeDisabled`] — the repository does not
+///   allow auto-merge.
+/// - Any other reported error, mapped by [`classify_error`].
+pub fn parse_enable_auto_merge(
+    repository: &RepositoryId,
+    number: PullRequestId,
+    response: &JsonValue,
+    rate_limit_reset: DateTime<Utc>,
+) -> Result<(), GitHubOperationError> {
+    if let Some(disabled) = auto_merge_disabled(repository, response) {
+        return Err(disabled);
+    }
+    match parse_errors(response).first() {
+        Some(error) => Err(classify_error(
+            &format!("auto-merge for pull request #{number} in {repository}"),
+            error,
+            rate_limit_reset,
+        )),
+        None => Ok(()),
+    }
+}
+
+// ─── GithubClient entry point ────────────────────────────────────────────────
+
+impl GithubClient {
+    /// Enable auto-merge on `pr` so GitHub merges it with `method` once its
+    /// required checks and reviews pass.
+    ///
+    /// Enabling auto-merge that is already enabled is not an error; the
+    /// mutation is not sent again.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::AutoMergeDisabled`] — the repository does
+    ///   not allow auto-merge.
+    /// - [`GitHubOperationError::NotFound`] — the pull request does not exist.
+    /// - As for [`parse_auto_merge_target`], [`parse_enable_auto_merge`], and
+    ///   [`GithubClient::send_graphql`].
+    #[instrument(skip(self, pr), fields(pr = %pr.id, repository = %pr.repository))]
+    pub async fn enable_auto_merge(
+        &self,
+        pr: &PullRequest,
+        method: AutoMergeMethod,
+    ) -> Result<(), GitHubOperationError> {
+        let resource = format!("pull request #{} in {}", pr.id, pr.repository);
+        let response = self
+            .send_graphql(auto_merge_target_request(&pr.repository, pr.id), &resource)
+            .await?;
+        let target = parse_auto_merge_target(
+            &pr.repository,
+            pr.id,
+            &response,
+            self.graphql_rate_limit_reset(),
+        )?;
+        if target.already_enabled {
+            tracing::debug!("auto-merge already enabled");
+            return Ok(());
+        }
+        let response = self
+            .send_graphql(
+                enable_auto_merge_request(&target.node_id, method),
+                &format!("auto-merge for {resource}"),
+            )
+            .await?;
+        parse_enable_auto_merge(
+            &pr.repository,
+            pr.id,
+            &response,
+            self.graphql_rate_limit_reset(),
+        )
+    }
+}
+
+#[cfg(test)]
+#[path = "auto_merge_tests.rs"]
+mod tests;
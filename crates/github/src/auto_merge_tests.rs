@@ -0,0 +1,337 @@
+use std::sync::Arc;
+
+use chrono::TimeZone;
+use pipeline::{github::ReviewStatus, BranchName, CommitSha, RetryPolicy};
+
+use crate::{
+    test_support::{client_without_repository, repository},
+    transport::{RestMethod, ScriptedTransport, GRAPHQL_PATH},
+};
+
+use super::*;
+
+fn number() -> PullRequestId {
+    PullRequestId::new(12)
+}
+
+fn node_id() -> GraphQlNodeId {
+    GraphQlNodeId::new("PR_kwDOAbc123").unwrap()
+}
+
+fn reset() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 15, 10, 0, 0).unwrap()
+}
+
+fn pull_request() -> PullRequest {
+    PullRequest {
+        id: number(),
+        repository: repository(),
+        title: "Fix the loader".to_string(),
+        body: String::new(),
+        author: "cogworks[bot]".to_string(),
+        head_branch: BranchName::new("cogworks/fix-loader").unwrap(),
+        base_branch: BranchName::new("main").unwrap(),
+        head_sha: CommitSha::parse("0123456789abcdef0123456789abcdef01234567").unwrap(),
+        is_open: true,
+        is_merged: false,
+        review_status: ReviewStatus {
+            approvals: 0,
+            changes_requested: false,
+            approved: false,
+        },
+        created_at: Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap(),
+    }
+}
+
+/// A recorded response to [`auto_merge_target_request`].
+fn target_response(auto_merge_allowed: bool, auto_merge_request: JsonValue) -> JsonValue {
+    json!({
+        "data": {
+            "repository": {
+                "autoMergeAllowed": auto_merge_allowed,
+                "pullRequest": {
+                    "id": "PR_kwDOAbc123",
+                    "autoMergeRequest": auto_merge_request
+                }
+            },
+            "rateLimit": { "cost": 1, "remaining": 4999, "resetAt": "2026-10-15T10:00:00Z" }
+        }
+    })
+}
+
+/// A recorded response to [`enable_auto_merge_request`].
+fn enabled_response() -> JsonValue {
+    json!({
+        "data": {
+            "enablePullRequestAutoMerge": {
+                "pullRequest": { "autoMergeRequest": { "enabledAt": "2026-10-15T09:30:00Z" } }
+            }
+        }
+    })
+}
+
+fn not_allowed_response() -> JsonValue {
+    json!({
+        "data": { "enablePullRequestAutoMerge": null },
+        "errors": [{
+            "type": "UNPROCESSABLE",
+            "message": "Pull request Auto merge is not allowed for this repository"
+        }]
+    })
+}
+
+fn is_disabled(result: &Result<impl std::fmt::Debug, GitHubOperationError>) -> bool {
+    matches!(
+        result,
+        Err(GitHubOperationError::AutoMergeDisabled { repository })
+            if *repository == self::repository()
+    )
+}
+
+// ─── Documents ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_as_graphql_each_method_returns_enum_value() {
+    assert_eq!(AutoMergeMethod::Merge.as_graphql(), "MERGE");
+    assert_eq!(AutoMergeMethod::Squash.as_graphql(), "SQUASH");
+    assert_eq!(AutoMergeMethod::Rebase.as_graphql(), "REBASE");
+    assert_eq!(AutoMergeMethod::default(), AutoMergeMethod::Squash);
+}
+
+#[test]
+fn test_auto_merge_target_request_variables_name_repository_and_number() {
+    let request = auto_merge_target_request(&repository(), number());
+
+    assert_eq!(
+        request["variables"],
+        json!({ "owner": "octo", "name": "widgets", "number": 12 })
+    );
+    let query = request["query"].as_str().unwrap();
+    assert!(query.contains("autoMergeAllowed"));
+    assert!(query.contains("rateLimit"));
+}
+
+#[test]
+fn test_enable_auto_merge_request_variables_name_node_and_method() {
+    let request = enable_auto_merge_request(&node_id(), AutoMergeMethod::Rebase);
+
+    assert_eq!(
+        request["variables"],
+        json!({ "pullRequestId": "PR_kwDOAbc123", "mergeMethod": "REBASE" })
+    );
+    assert!(request["query"]
+        .as_str()
+        .unwrap()
+        .contains("enablePullRequestAutoMerge"));
+}
+
+// ─── parse_auto_merge_target ────────────────────────────────────────────────
+
+#[test]
+fn test_parse_auto_merge_target_allowed_returns_node_id() {
+    let target = parse_auto_merge_target(
+        &repository(),
+        number(),
+        &target_response(true, JsonValue::Null),
+        reset(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        target,
+        AutoMergeTarget {
+            node_id: node_id(),
+            already_enabled: false,
+        }
+    );
+}
+
+#[test]
+fn test_parse_auto_merge_target_request_present_returns_already_enabled() {
+    let response = target_response(true, json!({ "enabledAt": "2026-10-15T09:00:00Z" }));
+
+    let target = parse_auto_merge_target(&repository(), number(), &response, reset()).unwrap();
+
+    assert!(target.already_enabled);
+}
+
+#[test]
+fn test_parse_auto_merge_target_repository_disallows_returns_auto_merge_disabled() {
+    let result = parse_auto_merge_target(
+        &repository(),
+        number(),
+        &target_response(false, JsonValue::Null),
+        reset(),
+    );
+
+    assert!(is_disabled(&result));
+    assert_eq!(
+        result.unwrap_err().retry_policy(),
+        RetryPolicy::NonRetryable
+    );
+}
+
+#[test]
+fn test_parse_auto_merge_target_missing_pull_request_returns_not_found() {
+    let response = json!({
+        "data": { "repository": { "autoMergeAllowed": true, "pullRequest": null } }
+    });
+
+    let result = parse_auto_merge_target(&repository(), number(), &response, reset());
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::NotFound { resource }) if resource.contains("#12")
+    ));
+}
+
+#[test]
+fn test_parse_auto_merge_target_null_repository_returns_not_found() {
+    let response = json!({ "data": { "repository": null } });
+
+    let result = parse_auto_merge_target(&repository(), number(), &response, reset());
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[test]
+fn test_parse_auto_merge_target_no_data_returns_parse_failure() {
+    let result = parse_auto_merge_target(&repository(), number(), &json!({}), reset());
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_parse_auto_merge_target_rate_limited_returns_exhausted_until_reset() {
+    let response = json!({
+        "data": null,
+        "errors": [{ "type": "RATE_LIMITED", "message": "API rate limit exceeded" }]
+    });
+
+    let result = parse_auto_merge_target(&repository(), number(), &response, reset());
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::RateLimitExhausted { reset_at }) if reset_at == reset()
+    ));
+}
+
+// ─── parse_enable_auto_merge ────────────────────────────────────────────────
+
+#[test]
+fn test_parse_enable_auto_merge_success_returns_ok() {
+    let result = parse_enable_auto_merge(&repository(), number(), &enabled_response(), reset());
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_parse_enable_auto_merge_not_allowed_error_returns_auto_merge_disabled() {
+    let result = parse_enable_auto_merge(&repository(), number(), &not_allowed_response(), reset());
+
+    assert!(is_disabled(&result));
+}
+
+#[test]
+fn test_parse_enable_auto_merge_forbidden_returns_permission_denied() {
+    let response = json!({
+        "data": { "enablePullRequestAutoMerge": null },
+        "errors": [{ "type": "FORBIDDEN", "message": "Resource not accessible by integration" }]
+    });
+
+    let result = parse_enable_auto_merge(&repository(), number(), &response, reset());
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::PermissionDenied { .. })
+    ));
+}
+
+// ─── GithubClient ───────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_enable_auto_merge_allowed_sends_query_then_mutation() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, target_response(true, JsonValue::Null));
+    transport.push_json(200, enabled_response());
+
+    client_without_repository(&transport)
+        .enable_auto_merge(&pull_request(), AutoMergeMethod::Squash)
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests
+        .iter()
+        .all(|r| r.method == RestMethod::Post && r.path == GRAPHQL_PATH));
+    assert_eq!(
+        requests[0].body,
+        Some(auto_merge_target_request(&repository(), number()))
+    );
+    assert_eq!(
+        requests[1].body,
+        Some(enable_auto_merge_request(
+            &node_id(),
+            AutoMergeMethod::Squash
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_enable_auto_merge_already_enabled_skips_mutation() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(
+        200,
+        target_response(true, json!({ "enabledAt": "2026-10-15T09:00:00Z" })),
+    );
+
+    client_without_repository(&transport)
+        .enable_auto_merge(&pull_request(), AutoMergeMethod::Merge)
+        .await
+        .unwrap();
+
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_enable_auto_merge_repository_disallows_returns_disabled_without_mutation() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, target_response(false, JsonValue::Null));
+
+    let result = client_without_repository(&transport)
+        .enable_auto_merge(&pull_request(), AutoMergeMethod::Squash)
+        .await;
+
+    assert!(is_disabled(&result));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_enable_auto_merge_setting_turned_off_before_mutation_returns_disabled() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, target_response(true, JsonValue::Null));
+    transport.push_json(200, not_allowed_response());
+
+    let result = client_without_repository(&transport)
+        .enable_auto_merge(&pull_request(), AutoMergeMethod::Squash)
+        .await;
+
+    assert!(is_disabled(&result));
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_enable_auto_merge_without_transport_returns_sdk_capability_missing() {
+    let result = GithubClient::new(Arc::new(()))
+        .enable_auto_merge(&pull_request(), AutoMergeMethod::Squash)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::SdkCapabilityMissing { .. })
+    ));
+}
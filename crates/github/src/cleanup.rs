@@ -0,0 +1,265 @@
+//! Finding open pull requests and branches left behind by CogWorks.
+//!
+//! Abandoned or superseded runs can leave open PRs (and their branches)
+//! behind. A cleanup command needs the list of PRs CogWorks opened, without
+//! touching PRs opened by people. A PR counts as CogWorks' when it is open and
+//! either was opened by the bot account or has a head branch under the
+//! CogWorks branch prefix.
+//!
+//! Work branches follow `cogworks/<work-item-number>/<node-slug>`. Once the
+//! work item is closed and no open PR is using a branch, the branch is
+//! orphaned; [`GithubClient::find_orphaned_branches`] lists those.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §CogWorks PR enumeration and
+//! §Orphaned branches.
+
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+use serde_json::Value as JsonValue;
+use tracing::instrument;
+
+use pipeline::{
+    github::{
+        GitHubOperationError, IssueState, PullRequest, PullRequestFilter, PullRequestManager,
+        PullRequestStateFilter,
+    },
+    BranchName, PullRequestId, RepositoryId, WorkItemId,
+};
+
+use crate::{
+    default_branch::repository_path, rate_limited::status_error, transport::RestRequest,
+    GithubClient,
+};
+
+/// Default prefix of branches CogWorks creates.
+pub const DEFAULT_BRANCH_PREFIX: &str = "cogworks/";
+
+/// Prefix of branch refs in git ref names.
+const HEADS_REF_PREFIX: &str = "refs/heads/";
+
+/// Decides which pull requests were opened by CogWorks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CogWorksPrSelector {
+    /// Login of the bot account, if known (e.g. `"cogworks[bot]"`).
+    pub bot_login: Option<String>,
+    /// Head-branch prefix used for CogWorks branches.
+    pub branch_prefix: String,
+}
+
+impl Default for CogWorksPrSelector {
+    fn default() -> Self {
+        Self {
+            bot_login: None,
+            branch_prefix: DEFAULT_BRANCH_PREFIX.to_string(),
+        }
+    }
+}
+
+impl CogWorksPrSelector {
+    /// Creates a selector matching PRs opened by `bot_login` or from branches
+    /// under [`DEFAULT_BRANCH_PREFIX`].
+    pub fn for_bot(bot_login: impl Into<String>) -> Self {
+        Self {
+            bot_login: Some(bot_login.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Returns `true` if `pr` is open and was opened by CogWorks.
+    ///
+    /// Login comparison is case-insensitive, as GitHub logins are.
+    pub fn matches(&self, pr: &PullRequest) -> bool {
+        if !pr.is_open {
+            return false;
+        }
+        let by_bot = self
+            .bot_login
+            .as_deref()
+            .is_some_and(|login| pr.author.eq_ignore_ascii_case(login));
+        let on_bot_branch = !self.branch_prefix.is_empty()
+            && pr.head_branch.as_str().starts_with(&self.branch_prefix);
+        by_bot || on_bot_branch
+    }
+
+    /// Returns the IDs of the pull requests in `prs` that [`matches`](Self::matches)
+    /// selects, in input order.
+    pub fn select(&self, prs: &[PullRequest]) -> Vec<PullRequestId> {
+        prs.iter()
+            .filter(|pr| self.matches(pr))
+            .map(|pr| pr.id)
+            .collect()
+    }
+}
+
+/// Returns the work item a `<prefix><work-item-number>/<node-slug>` branch
+/// belongs to, or `None` if `branch` does not follow that convention.
+pub fn work_item_for_branch(branch: &BranchName, prefix: &str) -> Option<WorkItemId> {
+    if prefix.is_empty() {
+        return None;
+    }
+    let (number, _slug) = branch.as_str().strip_prefix(prefix)?.split_once('/')?;
+    number.parse().ok().map(WorkItemId::new)
+}
+
+/// Returns the branches in `branches` that are orphaned, in input order.
+///
+/// A branch is orphaned when it follows the `<prefix><work-item-number>/...`
+/// convention, its work item is [`IssueState::Closed`] in `work_items`, and
+/// it is not in `open_pr_heads` (the head branches of open PRs). Work items
+/// missing from `work_items` are treated as open, so a branch is never
+/// reported on incomplete information.
+pub fn select_orphaned_branches(
+    branches: &[BranchName],
+    prefix: &str,
+    work_items: &HashMap<WorkItemId, IssueState>,
+    open_pr_heads: &HashSet<BranchName>,
+) -> Vec<BranchName> {
+    branches
+        .iter()
+        .filter(|branch| {
+            work_item_for_branch(branch, prefix)
+                .is_some_and(|id| work_items.get(&id) == Some(&IssueState::Closed))
+                && !open_pr_heads.contains(*branch)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Returns the path listing the branches of `repository` whose names start
+/// with `prefix`.
+///
+/// The matching-refs endpoint returns every match in one response, so no
+/// pagination is needed.
+pub fn matching_branches_path(repository: &RepositoryId, prefix: &str) -> String {
+    format!(
+        "{}/git/matching-refs/heads/{prefix}",
+        repository_path(repository)
+    )
+}
+
+/// Maps a matching-refs response onto branch names, dropping `refs/heads/`.
+///
+/// # Errors
+///
+/// [`GitHubOperationError::ParseFailure`] — the body is not an array, or an
+/// entry has no `ref` under `refs/heads/` naming a valid branch.
+pub fn parse_matching_refs(body: &JsonValue) -> Result<Vec<BranchName>, GitHubOperationError> {
+    let parse_failure = |message: String| GitHubOperationError::ParseFailure {
+        message: format!("branches: {message}"),
+    };
+    body.as_array()
+        .ok_or_else(|| parse_failure("expected an array".to_string()))?
+        .iter()
+        .map(|entry| {
+            let name = entry
+                .get("ref")
+                .and_then(JsonValue::as_str)
+                .ok_or_else(|| parse_failure("entry without a ref".to_string()))?;
+            name.strip_prefix(HEADS_REF_PREFIX)
+                .and_then(BranchName::new)
+                .ok_or_else(|| parse_failure(format!("'{name}' is not a branch ref")))
+        })
+        .collect()
+}
+
+impl GithubClient {
+    /// List the CogWorks work branches in `repository` whose work item is
+    /// closed and that no open pull request is using.
+    ///
+    /// Reads the branches under `branch_prefix`, the state of each work item
+    /// they name, and the open pull requests, then applies
+    /// [`select_orphaned_branches`]. Nothing is deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from listing branches, reading a work item's
+    /// state, or [`PullRequestManager::find_pull_requests`].
+    #[instrument(skip(self))]
+    pub async fn find_orphaned_branches(
+        &self,
+        repository: &RepositoryId,
+        branch_prefix: &str,
+    ) -> Result<Vec<BranchName>, GitHubOperationError> {
+        let branches = self.list_branches(repository, branch_prefix).await?;
+        let mut work_items = HashMap::new();
+        for id in branches
+            .iter()
+            .filter_map(|branch| work_item_for_branch(branch, branch_prefix))
+        {
+            if let Entry::Vacant(entry) = work_items.entry(id) {
+                entry.insert(self.read_issue(id).await?.state);
+            }
+        }
+        let filter = PullRequestFilter {
+            state: Some(PullRequestStateFilter::Open),
+            ..PullRequestFilter::default()
+        };
+        let open_pr_heads = self
+            .find_pull_requests(repository, &filter)
+            .await?
+            .into_iter()
+            .map(|pr| pr.head_branch)
+            .collect();
+        let orphaned =
+            select_orphaned_branches(&branches, branch_prefix, &work_items, &open_pr_heads);
+        tracing::debug!(
+            branches = branches.len(),
+            orphaned = orphaned.len(),
+            "found orphaned CogWorks branches"
+        );
+        Ok(orphaned)
+    }
+
+    /// Lists the branches of `repository` whose names start with `prefix`.
+    ///
+    /// # Errors
+    ///
+    /// - The [`status_error`] of a non-success response.
+    /// - [`GitHubOperationError::ParseFailure`] — see [`parse_matching_refs`].
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   [transport](crate::transport).
+    pub(crate) async fn list_branches(
+        &self,
+        repository: &RepositoryId,
+        prefix: &str,
+    ) -> Result<Vec<BranchName>, GitHubOperationError> {
+        let response = self
+            .send(RestRequest::get(matching_branches_path(repository, prefix)))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("branches of {repository}")) {
+            return Err(error);
+        }
+        parse_matching_refs(&response.body)
+    }
+
+    /// List the open pull requests in `repository` that CogWorks opened.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from [`PullRequestManager::find_pull_requests`].
+    #[instrument(skip(self))]
+    pub async fn list_cogworks_prs(
+        &self,
+        repository: &RepositoryId,
+        selector: &CogWorksPrSelector,
+    ) -> Result<Vec<PullRequestId>, GitHubOperationError> {
+        let filter = PullRequestFilter {
+            state: Some(PullRequestStateFilter::Open),
+            ..PullRequestFilter::default()
+        };
+        let prs = self.find_pull_requests(repository, &filter).await?;
+        let selected = selector.select(&prs);
+        tracing::debug!(
+            open = prs.len(),
+            cogworks = selected.len(),
+            "enumerated CogWorks pull requests"
+        );
+        Ok(selected)
+    }
+}
+
+#[cfg(test)]
+#[path = "cleanup_tests.rs"]
+mod tests;
@@ -0,0 +1,319 @@
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use pipeline::{github::ReviewStatus, CommitSha};
+use serde_json::json;
+
+use crate::{
+    test_support::{client, repository},
+    transport::{RestMethod, ScriptedTransport},
+};
+
+use super::*;
+
+fn branch(name: &str) -> BranchName {
+    BranchName::new(name).unwrap()
+}
+
+fn pr(number: u64, author: &str, head: &str, is_open: bool) -> PullRequest {
+    PullRequest {
+        id: PullRequestId::new(number),
+        repository: repository(),
+        title: format!("PR {number}"),
+        body: String::new(),
+        author: author.to_string(),
+        head_branch: branch(head),
+        base_branch: branch("main"),
+        head_sha: CommitSha::parse("0123456789abcdef0123456789abcdef01234567").unwrap(),
+        is_open,
+        is_merged: false,
+        review_status: ReviewStatus {
+            approvals: 0,
+            changes_requested: false,
+            approved: false,
+        },
+        created_at: Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap(),
+    }
+}
+
+#[test]
+fn test_matches_bot_author_on_other_branch_returns_true() {
+    let selector = CogWorksPrSelector::for_bot("cogworks[bot]");
+
+    assert!(selector.matches(&pr(1, "CogWorks[bot]", "fix-typo", true)));
+}
+
+#[test]
+fn test_matches_human_author_on_prefixed_branch_returns_true() {
+    let selector = CogWorksPrSelector::default();
+
+    assert!(selector.matches(&pr(1, "octocat", "cogworks/42/plan", true)));
+}
+
+#[test]
+fn test_matches_closed_bot_pr_returns_false() {
+    let selector = CogWorksPrSelector::for_bot("cogworks[bot]");
+
+    assert!(!selector.matches(&pr(1, "cogworks[bot]", "cogworks/42/plan", false)));
+}
+
+#[test]
+fn test_matches_empty_prefix_without_bot_returns_false() {
+    let selector = CogWorksPrSelector {
+        bot_login: None,
+        branch_prefix: String::new(),
+    };
+
+    assert!(!selector.matches(&pr(1, "octocat", "cogworks/42/plan", true)));
+}
+
+#[test]
+fn test_select_mixed_prs_returns_bot_prs_in_input_order() {
+    let prs = vec![
+        pr(1, "octocat", "feature/login", true),
+        pr(2, "cogworks[bot]", "cogworks/42/plan", true),
+        pr(3, "hubot", "cogworks/43/review", true),
+        pr(4, "cogworks[bot]", "cogworks/44/plan", false),
+        pr(5, "cogworks[bot]", "hotfix", true),
+    ];
+
+    let selected = CogWorksPrSelector::for_bot("cogworks[bot]").select(&prs);
+
+    assert_eq!(
+        selected,
+        vec![
+            PullRequestId::new(2),
+            PullRequestId::new(3),
+            PullRequestId::new(5)
+        ]
+    );
+}
+
+// ─── work_item_for_branch ───────────────────────────────────────────────────
+
+#[test]
+fn test_work_item_for_branch_conventional_branch_returns_work_item() {
+    assert_eq!(
+        work_item_for_branch(&branch("cogworks/42/plan"), DEFAULT_BRANCH_PREFIX),
+        Some(WorkItemId::new(42))
+    );
+}
+
+#[test]
+fn test_work_item_for_branch_unconventional_branch_returns_none() {
+    for name in ["feature/42/plan", "cogworks/42", "cogworks/abc/plan"] {
+        assert_eq!(
+            work_item_for_branch(&branch(name), DEFAULT_BRANCH_PREFIX),
+            None,
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_work_item_for_branch_empty_prefix_returns_none() {
+    assert_eq!(work_item_for_branch(&branch("42/plan"), ""), None);
+}
+
+// ─── select_orphaned_branches ───────────────────────────────────────────────
+
+#[test]
+fn test_select_orphaned_branches_closed_vs_open_work_items_returns_closed_only() {
+    let branches = vec![
+        branch("cogworks/42/plan"),
+        branch("cogworks/43/plan"),
+        branch("cogworks/42/review"),
+    ];
+    let work_items = HashMap::from([
+        (WorkItemId::new(42), IssueState::Closed),
+        (WorkItemId::new(43), IssueState::Open),
+    ]);
+
+    let orphaned = select_orphaned_branches(
+        &branches,
+        DEFAULT_BRANCH_PREFIX,
+        &work_items,
+        &HashSet::new(),
+    );
+
+    assert_eq!(
+        orphaned,
+        vec![branch("cogworks/42/plan"), branch("cogworks/42/review")]
+    );
+}
+
+#[test]
+fn test_select_orphaned_branches_open_pr_head_is_kept() {
+    let branches = vec![branch("cogworks/42/plan"), branch("cogworks/42/review")];
+    let work_items = HashMap::from([(WorkItemId::new(42), IssueState::Closed)]);
+    let open_pr_heads = HashSet::from([branch("cogworks/42/review")]);
+
+    let orphaned = select_orphaned_branches(
+        &branches,
+        DEFAULT_BRANCH_PREFIX,
+        &work_items,
+        &open_pr_heads,
+    );
+
+    assert_eq!(orphaned, vec![branch("cogworks/42/plan")]);
+}
+
+#[test]
+fn test_select_orphaned_branches_unknown_work_item_is_treated_as_open() {
+    let branches = vec![branch("cogworks/44/plan"), branch("hotfix/44/plan")];
+
+    let orphaned = select_orphaned_branches(
+        &branches,
+        DEFAULT_BRANCH_PREFIX,
+        &HashMap::new(),
+        &HashSet::new(),
+    );
+
+    assert!(orphaned.is_empty());
+}
+
+// ─── Branch listing ─────────────────────────────────────────────────────────
+
+/// A recorded `GET /repos/octo/widgets/git/matching-refs/heads/cogworks/`
+/// response.
+fn matching_refs() -> JsonValue {
+    json!([
+        {
+            "ref": "refs/heads/cogworks/42/plan",
+            "node_id": "MDM6UmVmcmVmcy9oZWFkcy9mZWF0dXJlQQ==",
+            "object": { "type": "commit", "sha": "aa218f56b14c9653891f9e74264a383fa43fefbd" }
+        },
+        {
+            "ref": "refs/heads/cogworks/43/review",
+            "node_id": "MDM6UmVmcmVmcy9oZWFkcy9mZWF0dXJlQg==",
+            "object": { "type": "commit", "sha": "612077ae6dffb4d2fbd8ce0cccaa58893b07b5ac" }
+        }
+    ])
+}
+
+#[test]
+fn test_matching_branches_path_prefix_appended_to_heads() {
+    assert_eq!(
+        matching_branches_path(&repository(), DEFAULT_BRANCH_PREFIX),
+        "/repos/octo/widgets/git/matching-refs/heads/cogworks/"
+    );
+}
+
+#[test]
+fn test_parse_matching_refs_recorded_response_strips_heads_prefix() {
+    let branches = parse_matching_refs(&matching_refs()).unwrap();
+
+    assert_eq!(
+        branches,
+        vec![branch("cogworks/42/plan"), branch("cogworks/43/review")]
+    );
+}
+
+#[test]
+fn test_parse_matching_refs_tag_ref_returns_parse_failure() {
+    let body = json!([{ "ref": "refs/tags/v1.0" }]);
+
+    assert!(matches!(
+        parse_matching_refs(&body),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_parse_matching_refs_not_an_array_returns_parse_failure() {
+    assert!(matches!(
+        parse_matching_refs(&json!({ "ref": "refs/heads/main" })),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_list_branches_recorded_response_reads_matching_refs() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, matching_refs());
+
+    let branches = client(&transport)
+        .list_branches(&repository(), DEFAULT_BRANCH_PREFIX)
+        .await
+        .unwrap();
+
+    assert_eq!(branches.len(), 2);
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, RestMethod::Get);
+    assert_eq!(
+        requests[0].path,
+        "/repos/octo/widgets/git/matching-refs/heads/cogworks/"
+    );
+}
+
+#[tokio::test]
+async fn test_list_branches_error_status_returns_error() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client(&transport)
+        .list_branches(&repository(), DEFAULT_BRANCH_PREFIX)
+        .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+// ─── find_orphaned_branches ─────────────────────────────────────────────────
+
+fn issue_response(number: u64, state: &str) -> JsonValue {
+    json!({
+        "number": number,
+        "repository_url": "https://api.github.com/repos/octo/widgets",
+        "title": format!("Issue {number}"),
+        "body": null,
+        "state": state,
+        "labels": [],
+        "milestone": null,
+        "created_at": "2026-03-01T10:00:00Z",
+        "updated_at": "2026-03-02T12:30:00Z"
+    })
+}
+
+fn open_pull_request(number: u64, head: &str) -> JsonValue {
+    json!({
+        "number": number,
+        "state": "open",
+        "title": format!("PR {number}"),
+        "body": null,
+        "user": { "login": "cogworks[bot]" },
+        "created_at": "2026-10-15T09:00:00Z",
+        "merged": false,
+        "head": { "ref": head, "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e" },
+        "base": { "ref": "main", "repo": { "full_name": "octo/widgets" } }
+    })
+}
+
+#[tokio::test]
+async fn test_find_orphaned_branches_closed_items_without_open_pr_returns_branch() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, matching_refs());
+    transport.push_json(200, issue_response(42, "closed"));
+    transport.push_json(200, issue_response(43, "closed"));
+    transport.push_json(200, json!([open_pull_request(9, "cogworks/43/review")]));
+
+    let orphaned = client(&transport)
+        .find_orphaned_branches(&repository(), DEFAULT_BRANCH_PREFIX)
+        .await
+        .unwrap();
+
+    assert_eq!(orphaned, vec![branch("cogworks/42/plan")]);
+    let paths: Vec<_> = transport
+        .requests()
+        .into_iter()
+        .map(|request| request.path)
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/repos/octo/widgets/git/matching-refs/heads/cogworks/",
+            "/repos/octo/widgets/issues/42",
+            "/repos/octo/widgets/issues/43",
+            "/repos/octo/widgets/pulls?state=open&per_page=100&page=1",
+        ]
+    );
+}
@@ -0,0 +1,271 @@
+//! Coalescing frequent edits to marker comments.
+//!
+//! A step that reports progress through [`IssueTracker::upsert_comment`] can
+//! rewrite the same comment many times a minute, spending API budget and
+//! flooding watchers with edit notifications. [`CommentThrottle`] allows at
+//! most one write per marker comment per window. An update that arrives
+//! inside the window is held as pending, replacing any earlier pending
+//! update, so only the latest content is written when the window elapses.
+//!
+//! Pending content is written by [`GithubClient::flush_due_comments`] once
+//! its window has elapsed, and unconditionally by
+//! [`GithubClient::flush_comments`] when the run completes, so the final
+//! state always reaches the issue.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Comment throttling.
+
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use tracing::instrument;
+
+use pipeline::{
+    github::{GitHubOperationError, IssueTracker},
+    WorkItemId,
+};
+
+use crate::GithubClient;
+
+/// Default minimum interval between writes to the same marker comment.
+pub const DEFAULT_COMMENT_THROTTLE_WINDOW: Duration = Duration::from_secs(10);
+
+/// An update held back by the throttle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingComment {
+    /// Issue the comment belongs to.
+    pub work_item_id: WorkItemId,
+    /// Marker identifying the comment.
+    pub marker: String,
+    /// Latest body offered for the comment.
+    pub body: String,
+}
+
+#[derive(Debug, Default)]
+struct Slot {
+    /// When the comment was last written.
+    last_write: Option<DateTime<Utc>>,
+    /// Latest body offered since that write.
+    pending: Option<String>,
+}
+
+// ─── Throttle ────────────────────────────────────────────────────────────────
+
+/// Per-comment write throttle.
+///
+/// Like [`crate::rate_limit::RateLimitTracker`], the throttle never sleeps:
+/// callers pass the current time and perform the writes it hands back.
+#[derive(Debug)]
+pub struct CommentThrottle {
+    window: TimeDelta,
+    slots: Mutex<HashMap<(WorkItemId, String), Slot>>,
+}
+
+impl Default for CommentThrottle {
+    fn default() -> Self {
+        Self::new(DEFAULT_COMMENT_THROTTLE_WINDOW)
+    }
+}
+
+impl CommentThrottle {
+    /// Creates a throttle allowing one write per comment every `window`.
+    ///
+    /// A zero window disables coalescing.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window: TimeDelta::from_std(window).unwrap_or(TimeDelta::MAX),
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the configured window.
+    pub fn window(&self) -> Duration {
+        self.window.to_std().unwrap_or_default()
+    }
+
+    /// Offers `body` for the comment identified by `marker` at `now`.
+    ///
+    /// Returns `Some(body)` if it should be written now, in which case the
+    /// write is recorded at `now`. Returns `None` if the comment was written
+    /// within the window; `body` is then held as pending, replacing any
+    /// earlier pending body.
+    pub fn offer(
+        &self,
+        work_item_id: WorkItemId,
+        marker: &str,
+        body: &str,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        let mut slots = self.slots();
+        let slot = slots.entry((work_item_id, marker.to_string())).or_default();
+        if slot.last_write.is_some_and(|last| !self.elapsed(last, now)) {
+            slot.pending = Some(body.to_string());
+            return None;
+        }
+        slot.last_write = Some(now);
+        slot.pending = None;
+        Some(body.to_string())
+    }
+
+    /// Puts back `comment` after its write failed, unless a newer body has
+    /// been offered since.
+    pub fn restore(&self, comment: PendingComment) {
+        let mut slots = self.slots();
+        let slot = slots
+            .entry((comment.work_item_id, comment.marker))
+            .or_default();
+        if slot.pending.is_none() {
+            slot.pending = Some(comment.body);
+        }
+    }
+
+    /// Takes the pending updates whose window has elapsed at `now`, recording
+    /// each as written at `now`.
+    pub fn take_due(&self, now: DateTime<Utc>) -> Vec<PendingComment> {
+        let mut slots = self.slots();
+        let mut due = Vec::new();
+        for ((work_item_id, marker), slot) in slots.iter_mut() {
+            if slot.last_write.is_some_and(|last| !self.elapsed(last, now)) {
+                continue;
+            }
+            if let Some(body) = slot.pending.take() {
+                slot.last_write = Some(now);
+                due.push(PendingComment {
+                    work_item_id: *work_item_id,
+                    marker: marker.clone(),
+                    body,
+                });
+            }
+        }
+        due
+    }
+
+    /// Takes every pending update for `work_item_id`, regardless of the
+    /// window, and forgets that work item's comments.
+    pub fn take_all(&self, work_item_id: WorkItemId) -> Vec<PendingComment> {
+        let mut slots = self.slots();
+        let keys: Vec<_> = slots
+            .keys()
+            .filter(|(id, _)| *id == work_item_id)
+            .cloned()
+            .collect();
+        keys.into_iter()
+            .filter_map(|key| {
+                let slot = slots.remove(&key)?;
+                Some(PendingComment {
+                    work_item_id: key.0,
+                    marker: key.1,
+                    body: slot.pending?,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the number of comments with a pending update.
+    pub fn pending_count(&self) -> usize {
+        self.slots()
+            .values()
+            .filter(|slot| slot.pending.is_some())
+            .count()
+    }
+
+    fn elapsed(&self, last: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        last.checked_add_signed(self.window)
+            .is_none_or(|next| next <= now)
+    }
+
+    fn slots(&self) -> std::sync::MutexGuard<'_, HashMap<(WorkItemId, String), Slot>> {
+        self.slots
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+// ─── GithubClient entry points ───────────────────────────────────────────────
+
+impl GithubClient {
+    /// [`IssueTracker::upsert_comment`] through the client's
+    /// [`CommentThrottle`].
+    ///
+    /// Returns `true` if the comment was written, `false` if the update was
+    /// held as pending.
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`IssueTracker::upsert_comment`]. The update stays
+    /// pending and is retried by the next flush.
+    #[instrument(skip(self, body))]
+    pub async fn upsert_comment_throttled(
+        &self,
+        id: WorkItemId,
+        marker: &str,
+        body: &str,
+    ) -> Result<bool, GitHubOperationError> {
+        let Some(body) = self.comment_throttle.offer(id, marker, body, Utc::now()) else {
+            tracing::debug!("comment update coalesced");
+            return Ok(false);
+        };
+        self.write_pending(vec![PendingComment {
+            work_item_id: id,
+            marker: marker.to_string(),
+            body,
+        }])
+        .await?;
+        Ok(true)
+    }
+
+    /// Writes every pending update whose throttle window has elapsed.
+    ///
+    /// Returns the number of comments written.
+    ///
+    /// # Errors
+    ///
+    /// The first error from [`IssueTracker::upsert_comment`]; unwritten
+    /// updates stay pending.
+    #[instrument(skip(self))]
+    pub async fn flush_due_comments(&self) -> Result<usize, GitHubOperationError> {
+        self.write_pending(self.comment_throttle.take_due(Utc::now()))
+            .await
+    }
+
+    /// Writes every pending update for `id`, ignoring the throttle window.
+    ///
+    /// Call when the run on `id` completes so the final content is never
+    /// left unwritten. Returns the number of comments written.
+    ///
+    /// # Errors
+    ///
+    /// The first error from [`IssueTracker::upsert_comment`]; unwritten
+    /// updates stay pending.
+    #[instrument(skip(self))]
+    pub async fn flush_comments(&self, id: WorkItemId) -> Result<usize, GitHubOperationError> {
+        self.write_pending(self.comment_throttle.take_all(id)).await
+    }
+
+    /// Writes `pending` in order, restoring the unwritten remainder to the
+    /// throttle on the first failure.
+    async fn write_pending(
+        &self,
+        pending: Vec<PendingComment>,
+    ) -> Result<usize, GitHubOperationError> {
+        let mut written = 0;
+        let mut pending = pending.into_iter();
+        while let Some(comment) = pending.next() {
+            if let Err(e) = self
+                .upsert_comment(comment.work_item_id, &comment.marker, &comment.body)
+                .await
+            {
+                self.comment_throttle.restore(comment);
+                pending.for_each(|rest| self.comment_throttle.restore(rest));
+                return Err(e);
+            }
+            written += 1;
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+#[path = "comment_throttle_tests.rs"]
+mod tests;
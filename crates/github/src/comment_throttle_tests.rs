@@ -0,0 +1,144 @@
+use chrono::TimeZone;
+
+use super::*;
+
+const MARKER: &str = "<!-- cogworks:run-summary -->";
+
+fn at(seconds: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap() + TimeDelta::seconds(seconds)
+}
+
+fn throttle() -> CommentThrottle {
+    CommentThrottle::new(Duration::from_secs(10))
+}
+
+fn item() -> WorkItemId {
+    WorkItemId::new(42)
+}
+
+#[test]
+fn test_offer_first_update_returns_body_to_write() {
+    assert_eq!(
+        throttle().offer(item(), MARKER, "v1", at(0)),
+        Some("v1".to_string())
+    );
+}
+
+#[test]
+fn test_offer_rapid_updates_coalesce_to_latest_pending() {
+    let throttle = throttle();
+    throttle.offer(item(), MARKER, "v1", at(0));
+
+    assert_eq!(throttle.offer(item(), MARKER, "v2", at(2)), None);
+    assert_eq!(throttle.offer(item(), MARKER, "v3", at(4)), None);
+
+    assert_eq!(throttle.pending_count(), 1);
+    assert!(throttle.take_due(at(5)).is_empty());
+    assert_eq!(
+        throttle.take_due(at(10)),
+        vec![PendingComment {
+            work_item_id: item(),
+            marker: MARKER.to_string(),
+            body: "v3".to_string(),
+        }]
+    );
+    assert_eq!(throttle.pending_count(), 0);
+}
+
+#[test]
+fn test_offer_after_window_returns_body_and_clears_pending() {
+    let throttle = throttle();
+    throttle.offer(item(), MARKER, "v1", at(0));
+    throttle.offer(item(), MARKER, "v2", at(3));
+
+    assert_eq!(
+        throttle.offer(item(), MARKER, "v3", at(10)),
+        Some("v3".to_string())
+    );
+    assert_eq!(throttle.pending_count(), 0);
+}
+
+#[test]
+fn test_offer_other_marker_is_throttled_separately() {
+    let throttle = throttle();
+    throttle.offer(item(), MARKER, "summary", at(0));
+
+    assert_eq!(
+        throttle.offer(item(), "<!-- cogworks:state -->", "state", at(1)),
+        Some("state".to_string())
+    );
+}
+
+#[test]
+fn test_offer_zero_window_never_coalesces() {
+    let throttle = CommentThrottle::new(Duration::ZERO);
+    throttle.offer(item(), MARKER, "v1", at(0));
+
+    assert_eq!(
+        throttle.offer(item(), MARKER, "v2", at(0)),
+        Some("v2".to_string())
+    );
+}
+
+#[test]
+fn test_take_due_written_update_restarts_window() {
+    let throttle = throttle();
+    throttle.offer(item(), MARKER, "v1", at(0));
+    throttle.offer(item(), MARKER, "v2", at(1));
+    throttle.take_due(at(10));
+
+    assert_eq!(throttle.offer(item(), MARKER, "v3", at(15)), None);
+}
+
+#[test]
+fn test_take_all_flushes_pending_inside_window() {
+    let throttle = throttle();
+    throttle.offer(item(), MARKER, "v1", at(0));
+    throttle.offer(item(), MARKER, "final", at(1));
+    throttle.offer(WorkItemId::new(7), MARKER, "other", at(0));
+    throttle.offer(WorkItemId::new(7), MARKER, "other-pending", at(1));
+
+    let flushed = throttle.take_all(item());
+
+    assert_eq!(flushed.len(), 1);
+    assert_eq!(flushed[0].body, "final");
+    assert_eq!(
+        throttle.pending_count(),
+        1,
+        "other work items keep pending updates"
+    );
+    assert_eq!(
+        throttle.offer(item(), MARKER, "next run", at(2)),
+        Some("next run".to_string()),
+        "the flushed work item's window is forgotten"
+    );
+}
+
+#[test]
+fn test_restore_keeps_newer_pending_body() {
+    let throttle = throttle();
+    throttle.offer(item(), MARKER, "v1", at(0));
+    throttle.offer(item(), MARKER, "v2", at(1));
+
+    throttle.restore(PendingComment {
+        work_item_id: item(),
+        marker: MARKER.to_string(),
+        body: "v1".to_string(),
+    });
+
+    assert_eq!(throttle.take_all(item())[0].body, "v2");
+}
+
+#[test]
+fn test_restore_without_pending_requeues_failed_write() {
+    let throttle = throttle();
+    let body = throttle.offer(item(), MARKER, "v1", at(0)).unwrap();
+
+    throttle.restore(PendingComment {
+        work_item_id: item(),
+        marker: MARKER.to_string(),
+        body,
+    });
+
+    assert_eq!(throttle.pending_count(), 1);
+}
@@ -0,0 +1,174 @@
+//! Issue comment reads and writes for
+//! [`IssueTracker::list_comments`](pipeline::IssueTracker::list_comments),
+//! [`IssueTracker::post_comment`](pipeline::IssueTracker::post_comment), and
+//! [`IssueTracker::update_comment`](pipeline::IssueTracker::update_comment).
+//!
+//! `list_comments` reads `GET /repos/{owner}/{repo}/issues/{number}/comments`
+//! in pages of [`COMMENTS_PER_PAGE`]. [`collect_comments`] follows the `Link`
+//! header's `rel="next"` entry until it is absent, failing with
+//! [`GitHubOperationError::PaginationLimitExceeded`] rather than reading past
+//! [`DEFAULT_MAX_COMMENT_PAGES`]. Pages are parsed by
+//! [`parse_comments_page`].
+//!
+//! `post_comment` is a `POST` to the same path; `update_comment` is a `PATCH`
+//! of `/repos/{owner}/{repo}/issues/comments/{id}`, since comment IDs are
+//! repository-wide. Both send [`comment_body`].
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §IssueTracker.
+
+use std::future::Future;
+
+use serde_json::{json, Value as JsonValue};
+use tracing::instrument;
+
+use pipeline::{
+    github::{GitHubOperationError, IssueComment},
+    CommentId, WorkItemId,
+};
+
+use crate::{
+    audit_replay::parse_comments_page,
+    default_branch::repository_path,
+    pr_files::{has_next_page, LINK_HEADER},
+    rate_limited::status_error,
+    transport::RestRequest,
+    GithubClient,
+};
+
+/// Comments requested per page (the API maximum).
+pub const COMMENTS_PER_PAGE: u32 = 100;
+
+/// Pages [`GithubClient::list_comments`](pipeline::IssueTracker::list_comments)
+/// reads before failing with
+/// [`GitHubOperationError::PaginationLimitExceeded`] (10 000 comments).
+pub const DEFAULT_MAX_COMMENT_PAGES: u32 = 100;
+
+/// One page of [`collect_comments`] input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommentsPage {
+    /// The page's JSON array of comment objects.
+    pub body: JsonValue,
+    /// The response's `Link` header, if any.
+    pub link: Option<String>,
+}
+
+/// Builds the `POST`/`PATCH` body that writes a comment.
+pub fn comment_body(body: &str) -> JsonValue {
+    json!({ "body": body })
+}
+
+/// Reads pages from `fetch_page` (called with 1, 2, ...) until one has no
+/// next link, and returns all their comments in order (oldest first).
+///
+/// # Errors
+///
+/// - [`GitHubOperationError::PaginationLimitExceeded`] — page `max_pages`
+///   still links a next page.
+/// - The first error from `fetch_page` or [`parse_comments_page`].
+pub async fn collect_comments<F, Fut>(
+    mut fetch_page: F,
+    max_pages: u32,
+) -> Result<Vec<IssueComment>, GitHubOperationError>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<CommentsPage, GitHubOperationError>>,
+{
+    let mut comments = Vec::new();
+    let mut page = 1;
+    loop {
+        let response = fetch_page(page).await?;
+        comments.extend(parse_comments_page(&response.body)?);
+        if !has_next_page(response.link.as_deref()) {
+            tracing::debug!(pages = page, comments = comments.len(), "listed comments");
+            return Ok(comments);
+        }
+        if page >= max_pages {
+            return Err(GitHubOperationError::PaginationLimitExceeded { max_pages });
+        }
+        page += 1;
+    }
+}
+
+impl GithubClient {
+    /// Reads one page of the comments on `work_item`.
+    pub(crate) async fn read_comments_page(
+        &self,
+        work_item: WorkItemId,
+        page: u32,
+    ) -> Result<CommentsPage, GitHubOperationError> {
+        let path = format!(
+            "{}/comments?per_page={COMMENTS_PER_PAGE}&page={page}",
+            self.issue_path(work_item)?
+        );
+        let response = self.send(RestRequest::get(path)).await?;
+        if let Some(error) = status_error(&response, &format!("comments on issue #{work_item}")) {
+            return Err(error);
+        }
+        Ok(CommentsPage {
+            link: response.header(LINK_HEADER).map(str::to_string),
+            body: response.body,
+        })
+    }
+
+    /// Posts a new comment with `body` on `work_item`.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — the issue does not exist.
+    /// - [`GitHubOperationError::PermissionDenied`] — the installation may
+    ///   not comment on issues.
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   repository or transport.
+    #[instrument(skip(self, body))]
+    pub(crate) async fn create_comment(
+        &self,
+        work_item: WorkItemId,
+        body: &str,
+    ) -> Result<(), GitHubOperationError> {
+        let path = format!("{}/comments", self.issue_path(work_item)?);
+        let response = self
+            .send(RestRequest::post(path, comment_body(body)))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("issue #{work_item}")) {
+            return Err(error);
+        }
+        tracing::debug!("comment posted");
+        Ok(())
+    }
+
+    /// Replaces the body of `comment` with `body`.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — the comment does not exist.
+    /// - [`GitHubOperationError::PermissionDenied`] — the installation may
+    ///   not edit the comment.
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   repository or transport.
+    #[instrument(skip(self, body))]
+    pub(crate) async fn patch_comment(
+        &self,
+        comment: CommentId,
+        body: &str,
+    ) -> Result<(), GitHubOperationError> {
+        let path = format!(
+            "{}/issues/comments/{}",
+            repository_path(self.repository()?),
+            comment.as_u64()
+        );
+        let response = self
+            .send(RestRequest::patch(path, comment_body(body)))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("comment {}", comment.as_u64())) {
+            return Err(error);
+        }
+        tracing::debug!("comment updated");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "comments_tests.rs"]
+mod tests;
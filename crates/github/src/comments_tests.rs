@@ -0,0 +1,222 @@
+use std::{cell::RefCell, sync::Arc};
+
+use pipeline::IssueTracker;
+
+use crate::{
+    rate_limited::RestResponse,
+    test_support::{client, repository},
+    transport::{RestMethod, ScriptedTransport},
+};
+
+use super::*;
+
+fn comment(id: u64, body: &str) -> JsonValue {
+    json!({
+        "id": id,
+        "body": body,
+        "user": { "login": "cogworks[bot]" },
+        "created_at": "2026-03-02T12:00:00Z"
+    })
+}
+
+fn next_link(page: u32) -> Option<String> {
+    Some(format!(
+        "<https://api.github.com/repositories/1/issues/42/comments?per_page=100&page={page}>; rel=\"next\""
+    ))
+}
+
+/// Two pages of a listing: the first linked onward, the last with no next link.
+fn two_pages() -> Vec<CommentsPage> {
+    vec![
+        CommentsPage {
+            body: json!([comment(1, "first"), comment(2, "second")]),
+            link: next_link(2),
+        },
+        CommentsPage {
+            body: json!([comment(3, "third")]),
+            link: None,
+        },
+    ]
+}
+
+// ─── collect_comments ───────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_collect_comments_two_pages_returns_every_comment_in_order() {
+    let pages = RefCell::new(two_pages().into_iter());
+    let requested = RefCell::new(Vec::new());
+
+    let comments = collect_comments(
+        |page| {
+            requested.borrow_mut().push(page);
+            let next = pages.borrow_mut().next();
+            async move { Ok(next.unwrap()) }
+        },
+        DEFAULT_MAX_COMMENT_PAGES,
+    )
+    .await
+    .unwrap();
+
+    let ids: Vec<u64> = comments.iter().map(|c| c.id.as_u64()).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+    assert_eq!(*requested.borrow(), vec![1, 2]);
+}
+
+#[tokio::test]
+async fn test_collect_comments_cap_reached_with_next_link_returns_pagination_limit() {
+    let pages = RefCell::new(two_pages().into_iter());
+
+    let result = collect_comments(
+        |_| {
+            let next = pages.borrow_mut().next();
+            async move { Ok(next.unwrap()) }
+        },
+        1,
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::PaginationLimitExceeded { max_pages: 1 })
+    ));
+}
+
+// ─── list_comments ──────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_list_comments_follows_link_header_across_pages() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push(Ok(RestResponse {
+        status: 200,
+        headers: vec![("Link".to_string(), next_link(2).unwrap())],
+        body: json!([comment(1, "first")]),
+    }));
+    transport.push_json(200, json!([comment(2, "second")]));
+
+    let comments = client(&transport)
+        .list_comments(WorkItemId::new(42))
+        .await
+        .unwrap();
+
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[1].body, "second");
+    assert_eq!(comments[1].author, "cogworks[bot]");
+    let paths: Vec<String> = transport.requests().into_iter().map(|r| r.path).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/repos/octo/widgets/issues/42/comments?per_page=100&page=1",
+            "/repos/octo/widgets/issues/42/comments?per_page=100&page=2",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_list_comments_missing_issue_returns_not_found() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client(&transport).list_comments(WorkItemId::new(42)).await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+// ─── post_comment ───────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_post_comment_posts_body_to_issue_comments() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(201, comment(7, "hello"));
+
+    client(&transport)
+        .post_comment(WorkItemId::new(42), "hello")
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Post);
+    assert_eq!(requests[0].path, "/repos/octo/widgets/issues/42/comments");
+    assert_eq!(requests[0].body, Some(json!({ "body": "hello" })));
+}
+
+#[tokio::test]
+async fn test_post_comment_forbidden_returns_permission_denied() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(403, json!({ "message": "Forbidden" }));
+
+    let result = client(&transport)
+        .post_comment(WorkItemId::new(42), "hello")
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::PermissionDenied { .. })
+    ));
+}
+
+// ─── update_comment ─────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_update_comment_patches_repository_comment() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, comment(7, "edited"));
+
+    client(&transport)
+        .update_comment(CommentId::new(7), "edited")
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Patch);
+    assert_eq!(requests[0].path, "/repos/octo/widgets/issues/comments/7");
+    assert_eq!(requests[0].body, Some(json!({ "body": "edited" })));
+}
+
+#[tokio::test]
+async fn test_update_comment_missing_comment_returns_not_found() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client(&transport)
+        .update_comment(CommentId::new(7), "edited")
+        .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_update_comment_without_transport_returns_sdk_capability_missing() {
+    let result = GithubClient::new(Arc::new(()))
+        .with_repository(repository())
+        .update_comment(CommentId::new(7), "edited")
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::SdkCapabilityMissing { .. })
+    ));
+}
+
+// ─── upsert_comment ─────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_upsert_comment_marker_present_patches_existing_comment() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(
+        200,
+        json!([comment(5, "<!-- cogworks:run-summary -->\nold")]),
+    );
+    transport.push_json(200, comment(5, "new"));
+
+    client(&transport)
+        .upsert_comment(WorkItemId::new(42), "<!-- cogworks:run-summary -->", "new")
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, RestMethod::Patch);
+    assert_eq!(requests[1].path, "/repos/octo/widgets/issues/comments/5");
+}
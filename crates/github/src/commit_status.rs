@@ -0,0 +1,113 @@
+//! Combined CI status of a commit.
+//!
+//! After CogWorks pushes commits, the executor gates on CI before merging.
+//! [`GithubClient::get_commit_status`] reads
+//! `GET /repos/{owner}/{repo}/commits/{ref}/status`, which reports GitHub's
+//! combined state and the latest status of each context, and
+//! [`parse_status_summary`] maps it onto [`StatusSummary`].
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Commit status.
+
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::instrument;
+
+use pipeline::{
+    github::{CommitState, ContextStatus, GitHubOperationError, StatusSummary},
+    CommitSha, RepositoryId,
+};
+
+use crate::{rate_limited::status_error, GithubClient};
+
+#[derive(Deserialize)]
+struct WireCombinedStatus {
+    sha: String,
+    state: CommitState,
+    #[serde(default)]
+    statuses: Vec<WireStatus>,
+}
+
+#[derive(Deserialize)]
+struct WireStatus {
+    context: String,
+    state: CommitState,
+    description: Option<String>,
+    target_url: Option<String>,
+}
+
+/// Returns the request path of the combined status of `sha`.
+pub fn commit_status_path(repository: &RepositoryId, sha: &CommitSha) -> String {
+    format!(
+        "/repos/{}/{}/commits/{sha}/status",
+        repository.owner(),
+        repository.repo()
+    )
+}
+
+/// Maps a combined-status response onto a [`StatusSummary`].
+///
+/// A commit no context has reported on is `pending` with no contexts.
+///
+/// # Errors
+///
+/// [`GitHubOperationError::ParseFailure`] — the response does not have the
+/// expected shape, a `state` is unrecognised, or `sha` is empty.
+pub fn parse_status_summary(response: &JsonValue) -> Result<StatusSummary, GitHubOperationError> {
+    let parse_failure = |message: String| GitHubOperationError::ParseFailure { message };
+    let status: WireCombinedStatus = serde_json::from_value(response.clone())
+        .map_err(|e| parse_failure(format!("commit status: {e}")))?;
+    Ok(StatusSummary {
+        sha: CommitSha::new(status.sha)
+            .ok_or_else(|| parse_failure("commit status: empty sha".to_string()))?,
+        state: status.state,
+        contexts: status
+            .statuses
+            .into_iter()
+            .map(|status| ContextStatus {
+                context: status.context,
+                state: status.state,
+                description: status.description,
+                target_url: status.target_url,
+            })
+            .collect(),
+    })
+}
+
+impl GithubClient {
+    /// Read the combined and per-context CI status of `sha`.
+    ///
+    /// Goes through the conditional-GET path, so polling an unchanged status
+    /// with the ETag cache enabled does not spend primary rate limit.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — the repository or commit does
+    ///   not exist.
+    /// - [`GitHubOperationError::ParseFailure`] — see [`parse_status_summary`].
+    #[instrument(skip(self))]
+    pub async fn get_commit_status(
+        &self,
+        repository: &RepositoryId,
+        sha: &CommitSha,
+    ) -> Result<StatusSummary, GitHubOperationError> {
+        let response = self
+            .conditional_get(&commit_status_path(repository, sha))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("commit {sha} in {repository}")) {
+            return Err(error);
+        }
+        let summary = parse_status_summary(&response.body)?;
+        tracing::debug!(
+            state = ?summary.state,
+            contexts = summary.contexts.len(),
+            "read commit status"
+        );
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+#[path = "commit_status_tests.rs"]
+mod tests;
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::{
+    test_support::{client, repository},
+    transport::{RestMethod, ScriptedTransport},
+};
+
+use super::*;
+
+const SHA: &str = "6dcb09b5b57875f334f61aebed695e2e4193db5e";
+
+fn sha() -> CommitSha {
+    CommitSha::parse(SHA).unwrap()
+}
+
+/// A recorded `GET /repos/octo/widgets/commits/{sha}/status` response, with
+/// `ci/test` and the combined state set to `state`.
+fn combined_status(state: &str) -> JsonValue {
+    json!({
+        "state": state,
+        "sha": SHA,
+        "total_count": 2,
+        "statuses": [
+            {
+                "context": "ci/build",
+                "state": "success",
+                "description": "Build passed",
+                "target_url": "https://ci.example/builds/1"
+            },
+            {
+                "context": "ci/test",
+                "state": state,
+                "description": null,
+                "target_url": null
+            }
+        ]
+    })
+}
+
+// ─── parse_status_summary ───────────────────────────────────────────────────
+
+#[test]
+fn test_commit_status_path_sha_in_commits_path() {
+    assert_eq!(
+        commit_status_path(&repository(), &sha()),
+        format!("/repos/octo/widgets/commits/{SHA}/status")
+    );
+}
+
+#[test]
+fn test_parse_status_summary_pending_returns_combined_and_context_states() {
+    let summary = parse_status_summary(&combined_status("pending")).unwrap();
+
+    assert_eq!(summary.sha, sha());
+    assert_eq!(summary.state, CommitState::Pending);
+    assert_eq!(
+        summary.contexts,
+        vec![
+            ContextStatus {
+                context: "ci/build".to_string(),
+                state: CommitState::Success,
+                description: Some("Build passed".to_string()),
+                target_url: Some("https://ci.example/builds/1".to_string()),
+            },
+            ContextStatus {
+                context: "ci/test".to_string(),
+                state: CommitState::Pending,
+                description: None,
+                target_url: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_status_summary_success_returns_success() {
+    let summary = parse_status_summary(&combined_status("success")).unwrap();
+
+    assert!(summary.is_success());
+    assert_eq!(summary.failed_contexts().count(), 0);
+}
+
+#[test]
+fn test_parse_status_summary_no_statuses_returns_pending_without_contexts() {
+    let response = json!({ "state": "pending", "sha": SHA, "total_count": 0 });
+
+    let summary = parse_status_summary(&response).unwrap();
+
+    assert_eq!(summary.state, CommitState::Pending);
+    assert!(summary.contexts.is_empty());
+}
+
+#[test]
+fn test_parse_status_summary_unknown_state_returns_parse_failure() {
+    assert!(matches!(
+        parse_status_summary(&combined_status("neutral")),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+// ─── get_commit_status ──────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_get_commit_status_success_reads_status_path() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, combined_status("pending"));
+
+    let summary = client(&transport)
+        .get_commit_status(&repository(), &sha())
+        .await
+        .unwrap();
+
+    assert_eq!(summary.state, CommitState::Pending);
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, RestMethod::Get);
+    assert_eq!(
+        requests[0].path,
+        format!("/repos/octo/widgets/commits/{SHA}/status")
+    );
+}
+
+#[tokio::test]
+async fn test_get_commit_status_unknown_commit_returns_not_found() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client(&transport)
+        .get_commit_status(&repository(), &sha())
+        .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
@@ -0,0 +1,415 @@
+//! Writing commits through the Git Data API, optionally signed.
+//!
+//! Branches whose protection requires signed commits reject unsigned pushes,
+//! so [`GithubClient::commit_files`] and [`GithubClient::amend_last_commit`]
+//! take a [`CommitSigning`] option:
+//!
+//! | Option | How the commit is signed |
+//! |--------|--------------------------|
+//! | [`CommitSigning::Unsigned`] | Not signed. Refused up front if the branch requires signatures |
+//! | [`CommitSigning::AppKey`] | Created without an explicit author or committer; GitHub signs it with the App's key |
+//! | [`CommitSigning::Signer`] | Signed locally with a GPG or SSH key; the signature is sent in the commit's `signature` field |
+//!
+//! A commit is written in four steps: read the branch head, create a tree on
+//! top of the head's tree, create the commit object, and move the branch to
+//! it. For the signed options the new commit must come back with
+//! `verification.verified: true`; otherwise the branch is not moved and the
+//! call fails with [`GitHubOperationError::CommitSigningUnavailable`], so an
+//! unsigned commit never reaches a protected branch.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Commit signing.
+
+use std::{fmt, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value as JsonValue};
+use thiserror::Error;
+use tracing::instrument;
+
+use pipeline::{github::GitHubOperationError, BranchName, CommitSha, RepositoryId};
+
+use crate::{
+    default_branch::repository_path, rate_limited::status_error, transport::RestRequest,
+    GithubClient,
+};
+
+/// Git file mode of a regular, non-executable file.
+const REGULAR_FILE_MODE: &str = "100644";
+
+// ─── Options ─────────────────────────────────────────────────────────────────
+
+/// A file written by a commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitFile {
+    /// Repository-root-relative path.
+    pub path: String,
+    /// Full UTF-8 content of the file.
+    pub content: String,
+}
+
+impl CommitFile {
+    /// Creates a file at `path` with `content`.
+    pub fn new(path: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            content: content.into(),
+        }
+    }
+}
+
+/// Author and committer of a locally signed commit.
+///
+/// Must match a verified email of the account that owns the signing key for
+/// GitHub to report the commit as verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitIdentity {
+    /// Display name.
+    pub name: String,
+    /// Email address.
+    pub email: String,
+}
+
+/// Returned by a [`CommitSigner`] that cannot sign.
+#[derive(Debug, Error)]
+#[error("{message}")]
+pub struct SignerError {
+    /// Why the payload was not signed (e.g. the key is unavailable).
+    pub message: String,
+}
+
+/// Signs commit objects with a GPG or SSH key.
+pub trait CommitSigner: Send + Sync {
+    /// Returns an ASCII-armoured detached signature over `payload`, the
+    /// commit object as git hashes it (see [`commit_payload`]).
+    ///
+    /// # Errors
+    ///
+    /// [`SignerError`] — the payload could not be signed.
+    fn sign(&self, payload: &str) -> Result<String, SignerError>;
+}
+
+/// How commits written by [`GithubClient::commit_files`] and
+/// [`GithubClient::amend_last_commit`] are signed.
+#[derive(Clone, Default)]
+pub enum CommitSigning {
+    /// No signature. The commit is refused with
+    /// [`GitHubOperationError::CommitSigningUnavailable`] if the branch
+    /// requires signed commits.
+    #[default]
+    Unsigned,
+    /// GitHub signs the commit with the App's key. Author and committer are
+    /// the App.
+    AppKey,
+    /// The commit is signed by `signer` as `identity`.
+    Signer {
+        /// Author and committer recorded in the commit.
+        identity: CommitIdentity,
+        /// Produces the signature.
+        signer: Arc<dyn CommitSigner>,
+    },
+}
+
+impl fmt::Debug for CommitSigning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsigned => f.write_str("Unsigned"),
+            Self::AppKey => f.write_str("AppKey"),
+            Self::Signer { identity, .. } => f
+                .debug_struct("Signer")
+                .field("identity", identity)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+impl CommitSigning {
+    /// Returns `true` unless this is [`CommitSigning::Unsigned`].
+    pub fn is_signed(&self) -> bool {
+        !matches!(self, Self::Unsigned)
+    }
+}
+
+/// Returns the commit object text git hashes and signs.
+///
+/// `timestamp` is recorded, in UTC, as both the author and committer date.
+pub fn commit_payload(
+    tree: &str,
+    parents: &[CommitSha],
+    identity: &CommitIdentity,
+    timestamp: DateTime<Utc>,
+    message: &str,
+) -> String {
+    let mut payload = format!("tree {tree}\n");
+    for parent in parents {
+        payload.push_str(&format!("parent {}\n", parent.as_str()));
+    }
+    let signature = format!(
+        "{} <{}> {} +0000",
+        identity.name,
+        identity.email,
+        timestamp.timestamp()
+    );
+    payload.push_str(&format!(
+        "author {signature}\ncommitter {signature}\n\n{message}"
+    ));
+    payload
+}
+
+// ─── GithubClient ────────────────────────────────────────────────────────────
+
+/// The branch head a new commit is built on.
+struct Head {
+    sha: CommitSha,
+    tree: String,
+    parents: Vec<CommitSha>,
+}
+
+impl GithubClient {
+    /// Commit `files` on top of `branch` in `repository` and move the branch
+    /// to the new commit.
+    ///
+    /// Files not listed are unchanged. Returns the new commit's SHA.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::CommitSigningUnavailable`] — `signing` is
+    ///   [`CommitSigning::Unsigned`] and the branch requires signed commits,
+    ///   or a signed commit could not be produced or was not verified. The
+    ///   branch is unchanged.
+    /// - [`GitHubOperationError::NotFound`] — the branch does not exist.
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   [transport](crate::transport).
+    #[instrument(skip(self, message, files, signing), fields(files = files.len()))]
+    pub async fn commit_files(
+        &self,
+        repository: &RepositoryId,
+        branch: &BranchName,
+        message: &str,
+        files: &[CommitFile],
+        signing: &CommitSigning,
+    ) -> Result<CommitSha, GitHubOperationError> {
+        self.write_commit(repository, branch, message, files, signing, false)
+            .await
+    }
+
+    /// Replace the head commit of `branch` with one that also writes `files`
+    /// and carries `message`, then force-move the branch to it.
+    ///
+    /// The replacement has the same parents as the commit it replaces.
+    ///
+    /// # Errors
+    ///
+    /// As for [`GithubClient::commit_files`].
+    #[instrument(skip(self, message, files, signing), fields(files = files.len()))]
+    pub async fn amend_last_commit(
+        &self,
+        repository: &RepositoryId,
+        branch: &BranchName,
+        message: &str,
+        files: &[CommitFile],
+        signing: &CommitSigning,
+    ) -> Result<CommitSha, GitHubOperationError> {
+        self.write_commit(repository, branch, message, files, signing, true)
+            .await
+    }
+
+    /// Returns `true` if `branch`'s protection requires signed commits.
+    ///
+    /// # Errors
+    ///
+    /// Any error other than `404` from the protection endpoint.
+    pub async fn requires_signed_commits(
+        &self,
+        repository: &RepositoryId,
+        branch: &BranchName,
+    ) -> Result<bool, GitHubOperationError> {
+        let path = format!(
+            "{}/branches/{}/protection/required_signatures",
+            repository_path(repository),
+            branch.as_str()
+        );
+        let response = self.send(RestRequest::get(path)).await?;
+        if response.status == 404 {
+            return Ok(false);
+        }
+        if let Some(error) = status_error(&response, &format!("branch {branch} protection")) {
+            return Err(error);
+        }
+        Ok(response
+            .body
+            .get("enabled")
+            .and_then(JsonValue::as_bool)
+            .unwrap_or(false))
+    }
+
+    async fn read_head(
+        &self,
+        repository: &RepositoryId,
+        branch: &BranchName,
+    ) -> Result<Head, GitHubOperationError> {
+        let repo = repository_path(repository);
+        let response = self
+            .send(RestRequest::get(format!(
+                "{repo}/git/ref/heads/{}",
+                branch.as_str()
+            )))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("branch {branch}")) {
+            return Err(error);
+        }
+        let sha = parse_sha(response.body.pointer("/object/sha"), "ref object.sha")?;
+
+        let response = self
+            .send(RestRequest::get(format!(
+                "{repo}/git/commits/{}",
+                sha.as_str()
+            )))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("commit {}", sha.as_str())) {
+            return Err(error);
+        }
+        let tree = string_field(&response.body, "/tree/sha")?;
+        let parents = response
+            .body
+            .get("parents")
+            .and_then(JsonValue::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|parent| parse_sha(parent.get("sha"), "commit parents[].sha"))
+            .collect::<Result<_, _>>()?;
+        Ok(Head { sha, tree, parents })
+    }
+
+    /// Writes a commit on `branch` and moves the branch to it. With `amend`,
+    /// the commit replaces the head (same parents, forced ref update);
+    /// otherwise it is a child of the head.
+    async fn write_commit(
+        &self,
+        repository: &RepositoryId,
+        branch: &BranchName,
+        message: &str,
+        files: &[CommitFile],
+        signing: &CommitSigning,
+        amend: bool,
+    ) -> Result<CommitSha, GitHubOperationError> {
+        let unavailable = |reason: String| GitHubOperationError::CommitSigningUnavailable {
+            branch: branch.clone(),
+            reason,
+        };
+        if !signing.is_signed() && self.requires_signed_commits(repository, branch).await? {
+            return Err(unavailable(
+                "the branch requires signed commits and no signing method is configured"
+                    .to_string(),
+            ));
+        }
+        let head = self.read_head(repository, branch).await?;
+        let parents = if amend { head.parents } else { vec![head.sha] };
+
+        let repo = repository_path(repository);
+        let entries: Vec<JsonValue> = files
+            .iter()
+            .map(|file| {
+                json!({
+                    "path": file.path,
+                    "mode": REGULAR_FILE_MODE,
+                    "type": "blob",
+                    "content": file.content,
+                })
+            })
+            .collect();
+        let response = self
+            .send(RestRequest::post(
+                format!("{repo}/git/trees"),
+                json!({ "base_tree": head.tree, "tree": entries }),
+            ))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("tree for branch {branch}")) {
+            return Err(error);
+        }
+        let tree = string_field(&response.body, "/sha")?;
+
+        let parent_shas: Vec<&str> = parents.iter().map(CommitSha::as_str).collect();
+        let mut commit = json!({ "message": message, "tree": tree, "parents": parent_shas });
+        if let CommitSigning::Signer { identity, signer } = signing {
+            let timestamp = Utc::now();
+            let payload = commit_payload(&tree, &parents, identity, timestamp, message);
+            let signature = signer
+                .sign(&payload)
+                .map_err(|e| unavailable(format!("signing failed: {e}")))?;
+            let person = json!({
+                "name": identity.name,
+                "email": identity.email,
+                "date": timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            });
+            commit["author"] = person.clone();
+            commit["committer"] = person;
+            commit["signature"] = JsonValue::String(signature);
+        }
+        let response = self
+            .send(RestRequest::post(format!("{repo}/git/commits"), commit))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("commit on branch {branch}")) {
+            return Err(error);
+        }
+        let sha = parse_sha(response.body.get("sha"), "commit sha")?;
+        if signing.is_signed() {
+            let verified = response
+                .body
+                .pointer("/verification/verified")
+                .and_then(JsonValue::as_bool)
+                .unwrap_or(false);
+            if !verified {
+                let reason = response
+                    .body
+                    .pointer("/verification/reason")
+                    .and_then(JsonValue::as_str)
+                    .unwrap_or("unknown");
+                return Err(unavailable(format!(
+                    "GitHub did not verify the commit signature ({reason})"
+                )));
+            }
+        }
+
+        let response = self
+            .send(RestRequest::patch(
+                format!("{repo}/git/refs/heads/{}", branch.as_str()),
+                json!({ "sha": sha.as_str(), "force": amend }),
+            ))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("branch {branch}")) {
+            return Err(error);
+        }
+        tracing::info!(
+            sha = sha.as_str(),
+            signed = signing.is_signed(),
+            amended = amend,
+            "commit written"
+        );
+        Ok(sha)
+    }
+}
+
+fn string_field(body: &JsonValue, pointer: &str) -> Result<String, GitHubOperationError> {
+    body.pointer(pointer)
+        .and_then(JsonValue::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| GitHubOperationError::ParseFailure {
+            message: format!("git data: missing {pointer}"),
+        })
+}
+
+fn parse_sha(value: Option<&JsonValue>, field: &str) -> Result<CommitSha, GitHubOperationError> {
+    value
+        .and_then(JsonValue::as_str)
+        .and_then(|sha| CommitSha::parse(sha).ok())
+        .ok_or_else(|| GitHubOperationError::ParseFailure {
+            message: format!("git data: missing or invalid {field}"),
+        })
+}
+
+#[cfg(test)]
+#[path = "commits_tests.rs"]
+mod tests;
@@ -0,0 +1,306 @@
+use std::sync::Mutex;
+
+use chrono::TimeZone;
+use serde_json::json;
+
+use crate::{
+    test_support::{client_without_repository, repository},
+    transport::{RestMethod, ScriptedTransport},
+};
+
+use super::*;
+
+const HEAD: &str = "1111111111111111111111111111111111111111";
+const PARENT: &str = "2222222222222222222222222222222222222222";
+const NEW_COMMIT: &str = "3333333333333333333333333333333333333333";
+const BASE_TREE: &str = "4444444444444444444444444444444444444444";
+const NEW_TREE: &str = "5555555555555555555555555555555555555555";
+
+fn branch() -> BranchName {
+    BranchName::new("cogworks/42/plan").unwrap()
+}
+
+fn sha(value: &str) -> CommitSha {
+    CommitSha::parse(value).unwrap()
+}
+
+fn files() -> Vec<CommitFile> {
+    vec![CommitFile::new("docs/plan.md", "# Plan\n")]
+}
+
+fn identity() -> CommitIdentity {
+    CommitIdentity {
+        name: "CogWorks".to_string(),
+        email: "cogworks@example.com".to_string(),
+    }
+}
+
+/// Signer returning a fixed signature and recording what it signed.
+#[derive(Default)]
+struct FixedSigner {
+    payloads: Mutex<Vec<String>>,
+    fail: bool,
+}
+
+impl CommitSigner for FixedSigner {
+    fn sign(&self, payload: &str) -> Result<String, SignerError> {
+        self.payloads.lock().unwrap().push(payload.to_string());
+        if self.fail {
+            return Err(SignerError {
+                message: "key not loaded".to_string(),
+            });
+        }
+        Ok("-----BEGIN SSH SIGNATURE-----\nabc\n-----END SSH SIGNATURE-----".to_string())
+    }
+}
+
+/// Queues the head ref and head commit reads.
+fn push_head(transport: &ScriptedTransport) {
+    transport.push_json(200, json!({ "object": { "sha": HEAD } }));
+    transport.push_json(
+        200,
+        json!({ "sha": HEAD, "tree": { "sha": BASE_TREE }, "parents": [{ "sha": PARENT }] }),
+    );
+}
+
+/// Queues the tree, commit, and ref update responses.
+fn push_writes(transport: &ScriptedTransport, verified: bool) {
+    transport.push_json(201, json!({ "sha": NEW_TREE }));
+    transport.push_json(
+        201,
+        json!({ "sha": NEW_COMMIT, "verification": { "verified": verified, "reason": if verified { "valid" } else { "unsigned" } } }),
+    );
+    transport.push_json(200, json!({ "object": { "sha": NEW_COMMIT } }));
+}
+
+fn request_to(transport: &ScriptedTransport, method: RestMethod, suffix: &str) -> RestRequest {
+    transport
+        .requests()
+        .into_iter()
+        .find(|request| request.method == method && request.path.ends_with(suffix))
+        .unwrap_or_else(|| panic!("no {method:?} request to ...{suffix}"))
+}
+
+fn no_post_to(transport: &ScriptedTransport, suffix: &str) -> bool {
+    transport
+        .requests()
+        .iter()
+        .all(|request| !(request.method == RestMethod::Post && request.path.ends_with(suffix)))
+}
+
+#[test]
+fn test_commit_payload_lists_tree_parents_and_identity() {
+    let timestamp = Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap();
+
+    let payload = commit_payload(NEW_TREE, &[sha(HEAD)], &identity(), timestamp, "Add plan");
+
+    assert_eq!(
+        payload,
+        format!(
+            "tree {NEW_TREE}\nparent {HEAD}\n\
+             author CogWorks <cogworks@example.com> {ts} +0000\n\
+             committer CogWorks <cogworks@example.com> {ts} +0000\n\nAdd plan",
+            ts = timestamp.timestamp()
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_commit_files_app_key_creates_commit_without_author_and_moves_branch() {
+    let transport = Arc::new(ScriptedTransport::new());
+    push_head(&transport);
+    push_writes(&transport, true);
+
+    let commit = client_without_repository(&transport)
+        .commit_files(
+            &repository(),
+            &branch(),
+            "Add plan",
+            &files(),
+            &CommitSigning::AppKey,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(commit, sha(NEW_COMMIT));
+    let tree = request_to(&transport, RestMethod::Post, "/git/trees")
+        .body
+        .unwrap();
+    assert_eq!(tree["base_tree"], BASE_TREE);
+    assert_eq!(tree["tree"][0]["path"], "docs/plan.md");
+    assert_eq!(tree["tree"][0]["mode"], "100644");
+    let body = request_to(&transport, RestMethod::Post, "/git/commits")
+        .body
+        .unwrap();
+    assert_eq!(body["tree"], NEW_TREE);
+    assert_eq!(body["parents"], json!([HEAD]));
+    assert!(body.get("author").is_none());
+    assert!(body.get("signature").is_none());
+    let update = request_to(
+        &transport,
+        RestMethod::Patch,
+        "/git/refs/heads/cogworks/42/plan",
+    );
+    assert_eq!(
+        update.body.unwrap(),
+        json!({ "sha": NEW_COMMIT, "force": false })
+    );
+}
+
+#[tokio::test]
+async fn test_commit_files_signer_sets_signature_field() {
+    let transport = Arc::new(ScriptedTransport::new());
+    push_head(&transport);
+    push_writes(&transport, true);
+    let signer = Arc::new(FixedSigner::default());
+    let signing = CommitSigning::Signer {
+        identity: identity(),
+        signer: Arc::clone(&signer) as _,
+    };
+
+    client_without_repository(&transport)
+        .commit_files(&repository(), &branch(), "Add plan", &files(), &signing)
+        .await
+        .unwrap();
+
+    let body = request_to(&transport, RestMethod::Post, "/git/commits")
+        .body
+        .unwrap();
+    assert!(body["signature"]
+        .as_str()
+        .unwrap()
+        .starts_with("-----BEGIN SSH SIGNATURE-----"));
+    assert_eq!(body["author"]["email"], "cogworks@example.com");
+    assert_eq!(body["committer"], body["author"]);
+    let payloads = signer.payloads.lock().unwrap();
+    assert!(payloads[0].starts_with(&format!("tree {NEW_TREE}\nparent {HEAD}\n")));
+    assert!(payloads[0].ends_with("\n\nAdd plan"));
+}
+
+#[tokio::test]
+async fn test_commit_files_unverified_signed_commit_leaves_branch_unchanged() {
+    let transport = Arc::new(ScriptedTransport::new());
+    push_head(&transport);
+    push_writes(&transport, false);
+
+    let error = client_without_repository(&transport)
+        .commit_files(
+            &repository(),
+            &branch(),
+            "Add plan",
+            &files(),
+            &CommitSigning::AppKey,
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::CommitSigningUnavailable { ref reason, .. } if reason.contains("unsigned")
+    ));
+    assert!(transport
+        .requests()
+        .iter()
+        .all(|request| request.method != RestMethod::Patch));
+}
+
+#[tokio::test]
+async fn test_commit_files_signer_failure_returns_signing_unavailable() {
+    let transport = Arc::new(ScriptedTransport::new());
+    push_head(&transport);
+    transport.push_json(201, json!({ "sha": NEW_TREE }));
+    let signing = CommitSigning::Signer {
+        identity: identity(),
+        signer: Arc::new(FixedSigner {
+            fail: true,
+            ..FixedSigner::default()
+        }),
+    };
+
+    let error = client_without_repository(&transport)
+        .commit_files(&repository(), &branch(), "Add plan", &files(), &signing)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::CommitSigningUnavailable { ref reason, .. } if reason.contains("key not loaded")
+    ));
+    assert!(no_post_to(&transport, "/git/commits"));
+}
+
+#[tokio::test]
+async fn test_commit_files_unsigned_on_branch_requiring_signatures_returns_error_before_writing() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, json!({ "enabled": true }));
+
+    let error = client_without_repository(&transport)
+        .commit_files(
+            &repository(),
+            &branch(),
+            "Add plan",
+            &files(),
+            &CommitSigning::Unsigned,
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::CommitSigningUnavailable { .. }
+    ));
+    assert_eq!(transport.requests().len(), 1);
+    assert!(transport.requests()[0]
+        .path
+        .ends_with("/branches/cogworks/42/plan/protection/required_signatures"));
+}
+
+#[tokio::test]
+async fn test_commit_files_unsigned_without_protection_commits_unsigned() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Branch not protected" }));
+    push_head(&transport);
+    push_writes(&transport, false);
+
+    let commit = client_without_repository(&transport)
+        .commit_files(
+            &repository(),
+            &branch(),
+            "Add plan",
+            &files(),
+            &CommitSigning::Unsigned,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(commit, sha(NEW_COMMIT));
+}
+
+#[tokio::test]
+async fn test_amend_last_commit_reuses_head_parents_and_forces_update() {
+    let transport = Arc::new(ScriptedTransport::new());
+    push_head(&transport);
+    push_writes(&transport, true);
+
+    client_without_repository(&transport)
+        .amend_last_commit(
+            &repository(),
+            &branch(),
+            "Add plan",
+            &files(),
+            &CommitSigning::AppKey,
+        )
+        .await
+        .unwrap();
+
+    let body = request_to(&transport, RestMethod::Post, "/git/commits")
+        .body
+        .unwrap();
+    assert_eq!(body["parents"], json!([PARENT]));
+    let update = request_to(
+        &transport,
+        RestMethod::Patch,
+        "/git/refs/heads/cogworks/42/plan",
+    );
+    assert_eq!(update.body.unwrap()["force"], true);
+}
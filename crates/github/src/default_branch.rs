@@ -0,0 +1,114 @@
+//! Per-repository default branch lookup with run-scoped caching.
+//!
+//! Branch creation, tree reads, and PR creation all need the repository's
+//! default branch. It is fetched from GitHub once per repository and reused
+//! for the lifetime of the [`GithubClient`] (one run). No name is assumed:
+//! repositories whose default is neither `main` nor `master` work unchanged.
+//!
+//! The value is read from `default_branch` in `GET /repos/{owner}/{repo}`.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Default branch.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use serde_json::Value as JsonValue;
+use tokio::sync::OnceCell;
+use tracing::instrument;
+
+use pipeline::{github::GitHubOperationError, BranchName, RepositoryId};
+
+use crate::{rate_limited::status_error, transport::RestRequest, GithubClient};
+
+/// Cache of default branches keyed by repository.
+///
+/// Each repository gets its own [`OnceCell`], so concurrent first lookups for
+/// the same repository share one fetch while lookups for different
+/// repositories proceed independently. A failed fetch leaves the cell empty
+/// and the next lookup retries.
+#[derive(Default)]
+pub(crate) struct DefaultBranchCache {
+    entries: Mutex<HashMap<RepositoryId, Arc<OnceCell<BranchName>>>>,
+}
+
+impl DefaultBranchCache {
+    /// Returns the cell for `repository`, creating an empty one if needed.
+    fn cell(&self, repository: &RepositoryId) -> Arc<OnceCell<BranchName>> {
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        Arc::clone(entries.entry(repository.clone()).or_default())
+    }
+}
+
+impl GithubClient {
+    /// Return the default branch of `repository`.
+    ///
+    /// The first call per repository fetches the value from GitHub; later
+    /// calls return the cached value without an API request.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — repository does not exist or is
+    ///   not visible to the installation.
+    /// - [`GitHubOperationError::Transient`] — transient network failure. The
+    ///   failure is not cached.
+    /// - [`GitHubOperationError::ParseFailure`] — the response has no
+    ///   `default_branch`.
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   [transport](crate::transport).
+    #[instrument(skip(self))]
+    pub async fn default_branch(
+        &self,
+        repository: &RepositoryId,
+    ) -> Result<BranchName, GitHubOperationError> {
+        let cell = self.default_branches.cell(repository);
+        let branch = cell
+            .get_or_try_init(|| self.fetch_default_branch(repository))
+            .await?;
+        Ok(branch.clone())
+    }
+
+    /// Fetch the default branch from `GET /repos/{owner}/{repo}`.
+    async fn fetch_default_branch(
+        &self,
+        repository: &RepositoryId,
+    ) -> Result<BranchName, GitHubOperationError> {
+        let response = self
+            .send(RestRequest::get(repository_path(repository)))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("repository {repository}")) {
+            return Err(error);
+        }
+        parse_default_branch(&response.body)
+    }
+}
+
+/// Returns the `GET` path of `repository`.
+pub(crate) fn repository_path(repository: &RepositoryId) -> String {
+    format!("/repos/{}/{}", repository.owner(), repository.repo())
+}
+
+/// Reads `default_branch` from a repository response.
+///
+/// # Errors
+///
+/// [`GitHubOperationError::ParseFailure`] — the field is missing, empty, or
+/// not a string.
+pub(crate) fn parse_default_branch(body: &JsonValue) -> Result<BranchName, GitHubOperationError> {
+    body.get("default_branch")
+        .and_then(JsonValue::as_str)
+        .and_then(BranchName::new)
+        .ok_or_else(|| GitHubOperationError::ParseFailure {
+            message: "repository: missing default_branch".to_string(),
+        })
+}
+
+#[cfg(test)]
+#[path = "default_branch_tests.rs"]
+mod tests;
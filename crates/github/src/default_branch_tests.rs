@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::{
+    test_support::{client_without_repository, repository},
+    transport::{RestMethod, ScriptedTransport, REST_TRANSPORT_CAPABILITY},
+};
+
+use super::*;
+
+#[test]
+fn test_parse_default_branch_present_returns_branch() {
+    let branch = parse_default_branch(&json!({ "default_branch": "trunk" })).unwrap();
+    assert_eq!(branch.as_str(), "trunk");
+}
+
+#[test]
+fn test_parse_default_branch_missing_returns_parse_failure() {
+    let error = parse_default_branch(&json!({ "name": "widgets" })).unwrap_err();
+    assert!(matches!(error, GitHubOperationError::ParseFailure { .. }));
+}
+
+#[test]
+fn test_parse_default_branch_empty_returns_parse_failure() {
+    let error = parse_default_branch(&json!({ "default_branch": "" })).unwrap_err();
+    assert!(matches!(error, GitHubOperationError::ParseFailure { .. }));
+}
+
+#[tokio::test]
+async fn test_default_branch_first_call_reads_repository() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, json!({ "default_branch": "develop" }));
+
+    let branch = client_without_repository(&transport)
+        .default_branch(&repository())
+        .await
+        .unwrap();
+
+    assert_eq!(branch.as_str(), "develop");
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Get);
+    assert_eq!(requests[0].path, "/repos/octo/widgets");
+}
+
+#[tokio::test]
+async fn test_default_branch_second_call_uses_cache() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, json!({ "default_branch": "develop" }));
+    let client = client_without_repository(&transport);
+
+    client.default_branch(&repository()).await.unwrap();
+    let branch = client.default_branch(&repository()).await.unwrap();
+
+    assert_eq!(branch.as_str(), "develop");
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_default_branch_failure_is_not_cached() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(503, json!({ "message": "unavailable" }));
+    transport.push_json(200, json!({ "default_branch": "main" }));
+    let client = client_without_repository(&transport);
+
+    let error = client.default_branch(&repository()).await.unwrap_err();
+    let branch = client.default_branch(&repository()).await.unwrap();
+
+    assert!(matches!(error, GitHubOperationError::Transient { .. }));
+    assert_eq!(branch.as_str(), "main");
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_default_branch_missing_repository_returns_not_found() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let error = client_without_repository(&transport)
+        .default_branch(&repository())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, GitHubOperationError::NotFound { .. }));
+}
+
+#[tokio::test]
+async fn test_default_branch_without_transport_returns_capability_missing() {
+    let error = GithubClient::new(Arc::new(()))
+        .default_branch(&repository())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::SdkCapabilityMissing { capability } if capability == REST_TRANSPORT_CAPABILITY
+    ));
+}
@@ -0,0 +1,326 @@
+//! GitHub Discussions as an alternative work source.
+//!
+//! Some teams track work in Discussions rather than Issues. Discussions are
+//! only reachable through the GraphQL API, so this module builds the GraphQL
+//! documents for the read path (discussion body, labels, and comments) and
+//! for posting a comment, and maps the responses onto the existing domain
+//! types: a discussion becomes an [`Issue`] and each discussion comment an
+//! [`IssueComment`]. Discussion numbers share the repository's issue number
+//! space, so they are carried as [`WorkItemId`]s.
+//!
+//! Only top-level discussion comments are read; threaded replies are not.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Discussions.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+use tracing::instrument;
+
+use pipeline::{
+    github::{GitHubOperationError, Issue, IssueComment, IssueState, Label},
+    CommentId, RepositoryId, WorkItemId,
+};
+
+use crate::{graphql::GraphQlNodeId, GithubClient};
+
+/// Maximum number of labels and comments read with a discussion.
+pub const DISCUSSION_PAGE_SIZE: u32 = 100;
+
+/// Login reported for comments whose author account has been deleted.
+const GHOST_LOGIN: &str = "ghost";
+
+const DISCUSSION_QUERY: &str = "\
+query($owner: String!, $name: String!, $number: Int!, $first: Int!) {
+  repository(owner: $owner, name: $name) {
+    discussion(number: $number) {
+      id number title body closed createdAt updatedAt
+      labels(first: $first) { nodes { name color } }
+      comments(first: $first) {
+        nodes { databaseId body createdAt author { login } }
+      }
+    }
+  }
+  rateLimit { cost remaining resetAt }
+}";
+
+const ADD_COMMENT_MUTATION: &str = "\
+mutation($discussionId: ID!, $body: String!) {
+  addDiscussionComment(input: { discussionId: $discussionId, body: $body }) {
+    comment { databaseId body createdAt author { login } }
+  }
+}";
+
+/// A discussion read as a work item, with its comments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscussionThread {
+    /// GraphQL node ID of the discussion, needed to post comments.
+    pub node_id: GraphQlNodeId,
+    /// The discussion mapped onto an issue.
+    pub issue: Issue,
+    /// Top-level comments, oldest first.
+    pub comments: Vec<IssueComment>,
+}
+
+// ─── GraphQL documents ───────────────────────────────────────────────────────
+
+/// Builds the GraphQL request reading discussion `number` in `repository`.
+pub fn discussion_request(repository: &RepositoryId, number: WorkItemId) -> JsonValue {
+    json!({
+        "query": DISCUSSION_QUERY,
+        "variables": {
+            "owner": repository.owner(),
+            "name": repository.repo(),
+            "number": number.as_u64(),
+            "first": DISCUSSION_PAGE_SIZE,
+        },
+    })
+}
+
+/// Builds the GraphQL request posting `body` as a comment on the discussion
+/// with node ID `discussion`.
+pub fn add_comment_request(discussion: &GraphQlNodeId, body: &str) -> JsonValue {
+    json!({
+        "query": ADD_COMMENT_MUTATION,
+        "variables": { "discussionId": discussion.as_str(), "body": body },
+    })
+}
+
+// ─── Response mapping ────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    #[serde(default, rename = "type")]
+    kind: Option<String>,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RepositoryData {
+    repository: Option<DiscussionData>,
+}
+
+#[derive(Deserialize)]
+struct DiscussionData {
+    discussion: Option<WireDiscussion>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WireDiscussion {
+    id: GraphQlNodeId,
+    number: u64,
+    title: String,
+    body: String,
+    closed: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    labels: Nodes<WireLabel>,
+    comments: Nodes<WireComment>,
+}
+
+#[derive(Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct WireLabel {
+    name: String,
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WireComment {
+    database_id: u64,
+    body: String,
+    created_at: DateTime<Utc>,
+    author: Option<WireActor>,
+}
+
+#[derive(Deserialize)]
+struct WireActor {
+    login: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AddCommentData {
+    add_discussion_comment: AddCommentPayload,
+}
+
+#[derive(Deserialize)]
+struct AddCommentPayload {
+    comment: WireComment,
+}
+
+impl From<WireComment> for IssueComment {
+    fn from(comment: WireComment) -> Self {
+        IssueComment {
+            id: CommentId::new(comment.database_id),
+            author: comment
+                .author
+                .map_or_else(|| GHOST_LOGIN.to_string(), |actor| actor.login),
+            body: comment.body,
+            created_at: comment.created_at,
+        }
+    }
+}
+
+/// Decodes a GraphQL response envelope, mapping reported errors.
+///
+/// `NOT_FOUND` errors become [`GitHubOperationError::NotFound`] and
+/// `FORBIDDEN` errors [`GitHubOperationError::PermissionDenied`]; any other
+/// reported error is treated as transient.
+fn decode<T: for<'de> Deserialize<'de>>(
+    response: &JsonValue,
+    resource: &str,
+) -> Result<T, GitHubOperationError> {
+    let envelope: GraphQlResponse<T> = serde_json::from_value(response.clone()).map_err(|e| {
+        GitHubOperationError::ParseFailure {
+            message: format!("{resource}: {e}"),
+        }
+    })?;
+    if let Some(error) = envelope.errors.into_iter().next() {
+        return Err(match error.kind.as_deref() {
+            Some("NOT_FOUND") => GitHubOperationError::NotFound {
+                resource: resource.to_string(),
+            },
+            Some("FORBIDDEN") => GitHubOperationError::PermissionDenied {
+                action: format!("{resource}: {}", error.message),
+            },
+            _ => GitHubOperationError::Transient {
+                message: format!("{resource}: {}", error.message),
+            },
+        });
+    }
+    envelope
+        .data
+        .ok_or_else(|| GitHubOperationError::ParseFailure {
+            message: format!("{resource}: response has neither data nor errors"),
+        })
+}
+
+/// Maps a response to [`discussion_request`] onto a [`DiscussionThread`].
+///
+/// # Errors
+///
+/// - [`GitHubOperationError::NotFound`] — the repository or discussion does
+///   not exist.
+/// - [`GitHubOperationError::PermissionDenied`] — the installation cannot
+///   read discussions.
+/// - [`GitHubOperationError::ParseFailure`] — the response has an unexpected
+///   shape.
+pub fn parse_discussion(
+    repository: &RepositoryId,
+    response: &JsonValue,
+) -> Result<DiscussionThread, GitHubOperationError> {
+    let resource = format!("discussion in {repository}");
+    let data: RepositoryData = decode(response, &resource)?;
+    let discussion = data
+        .repository
+        .and_then(|repo| repo.discussion)
+        .ok_or_else(|| GitHubOperationError::NotFound {
+            resource: resource.clone(),
+        })?;
+
+    Ok(DiscussionThread {
+        node_id: discussion.id,
+        issue: Issue {
+            id: WorkItemId::new(discussion.number),
+            repository: repository.clone(),
+            title: discussion.title,
+            body: discussion.body,
+            state: if discussion.closed {
+                IssueState::Closed
+            } else {
+                IssueState::Open
+            },
+            labels: discussion
+                .labels
+                .nodes
+                .into_iter()
+                .map(|label| Label {
+                    name: label.name,
+                    color: label.color,
+                })
+                .collect(),
+            milestone: None,
+            created_at: discussion.created_at,
+            updated_at: discussion.updated_at,
+        },
+        comments: discussion
+            .comments
+            .nodes
+            .into_iter()
+            .map(IssueComment::from)
+            .collect(),
+    })
+}
+
+/// Maps a response to [`add_comment_request`] onto the created comment.
+///
+/// # Errors
+///
+/// As for [`parse_discussion`].
+pub fn parse_added_comment(response: &JsonValue) -> Result<IssueComment, GitHubOperationError> {
+    let data: AddCommentData = decode(response, "discussion comment")?;
+    Ok(data.add_discussion_comment.comment.into())
+}
+
+// ─── GithubClient entry points ───────────────────────────────────────────────
+
+impl GithubClient {
+    /// Read discussion `number` in `repository` with its labels and comments.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — the discussion does not exist.
+    /// - As for [`parse_discussion`] and [`GithubClient::send_graphql`].
+    #[instrument(skip(self))]
+    pub async fn get_discussion(
+        &self,
+        repository: &RepositoryId,
+        number: WorkItemId,
+    ) -> Result<DiscussionThread, GitHubOperationError> {
+        let resource = format!("discussion #{number} in {repository}");
+        let response = self
+            .send_graphql(discussion_request(repository, number), &resource)
+            .await?;
+        parse_discussion(repository, &response)
+    }
+
+    /// Post `body` as a top-level comment on `discussion`.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::PermissionDenied`] — the installation cannot
+    ///   write discussions.
+    /// - As for [`parse_added_comment`] and [`GithubClient::send_graphql`].
+    #[instrument(skip(self, discussion, body), fields(discussion = %discussion.issue.id))]
+    pub async fn post_discussion_comment(
+        &self,
+        discussion: &DiscussionThread,
+        body: &str,
+    ) -> Result<IssueComment, GitHubOperationError> {
+        let resource = format!("comment on discussion #{}", discussion.issue.id);
+        let response = self
+            .send_graphql(add_comment_request(&discussion.node_id, body), &resource)
+            .await?;
+        parse_added_comment(&response)
+    }
+}
+
+#[cfg(test)]
+#[path = "discussions_tests.rs"]
+mod tests;
@@ -0,0 +1,322 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::{
+    test_support::{client_without_repository, repository},
+    transport::{RestMethod, ScriptedTransport, GRAPHQL_PATH},
+};
+
+use super::*;
+
+/// A recorded response to [`discussion_request`] for discussion #7.
+fn discussion_response() -> JsonValue {
+    json!({
+        "data": {
+            "repository": {
+                "discussion": {
+                    "id": "D_kwDOAbc123",
+                    "number": 7,
+                    "title": "Support dark mode",
+                    "body": "The settings page should offer a dark theme.",
+                    "closed": false,
+                    "createdAt": "2026-03-01T10:00:00Z",
+                    "updatedAt": "2026-03-02T12:30:00Z",
+                    "labels": { "nodes": [
+                        { "name": "cogworks:run", "color": "0e8a16" },
+                        { "name": "ui", "color": null }
+                    ] },
+                    "comments": { "nodes": [
+                        {
+                            "databaseId": 9001,
+                            "body": "Please include the editor too.",
+                            "createdAt": "2026-03-01T11:00:00Z",
+                            "author": { "login": "alice" }
+                        },
+                        {
+                            "databaseId": 9002,
+                            "body": "+1",
+                            "createdAt": "2026-03-01T12:00:00Z",
+                            "author": null
+                        }
+                    ] }
+                }
+            },
+            "rateLimit": { "cost": 1, "remaining": 4999, "resetAt": "2026-03-01T11:00:00Z" }
+        }
+    })
+}
+
+/// A recorded response to [`add_comment_request`].
+fn added_comment_response() -> JsonValue {
+    json!({
+        "data": {
+            "addDiscussionComment": {
+                "comment": {
+                    "databaseId": 9100,
+                    "body": "Plan posted.",
+                    "createdAt": "2026-03-03T09:00:00Z",
+                    "author": { "login": "cogworks[bot]" }
+                }
+            }
+        }
+    })
+}
+
+// ─── Documents ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_discussion_request_variables_name_repository_and_number() {
+    let request = discussion_request(&repository(), WorkItemId::new(7));
+
+    assert_eq!(
+        request["variables"],
+        json!({ "owner": "octo", "name": "widgets", "number": 7, "first": DISCUSSION_PAGE_SIZE })
+    );
+    assert!(request["query"]
+        .as_str()
+        .unwrap()
+        .contains("discussion(number: $number)"));
+}
+
+#[test]
+fn test_add_comment_request_variables_carry_node_id_and_body() {
+    let node_id = GraphQlNodeId::new("D_kwDOAbc123").unwrap();
+
+    let request = add_comment_request(&node_id, "Plan posted.");
+
+    assert_eq!(
+        request["variables"],
+        json!({ "discussionId": "D_kwDOAbc123", "body": "Plan posted." })
+    );
+    assert!(request["query"]
+        .as_str()
+        .unwrap()
+        .contains("addDiscussionComment"));
+}
+
+// ─── parse_discussion ───────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_discussion_recorded_response_maps_onto_issue() {
+    let thread = parse_discussion(&repository(), &discussion_response()).unwrap();
+
+    assert_eq!(thread.node_id.as_str(), "D_kwDOAbc123");
+    assert_eq!(thread.issue.id, WorkItemId::new(7));
+    assert_eq!(thread.issue.repository, repository());
+    assert_eq!(thread.issue.title, "Support dark mode");
+    assert_eq!(thread.issue.state, IssueState::Open);
+    assert_eq!(thread.issue.milestone, None);
+    assert_eq!(
+        thread.issue.labels,
+        vec![
+            Label {
+                name: "cogworks:run".to_string(),
+                color: Some("0e8a16".to_string())
+            },
+            Label {
+                name: "ui".to_string(),
+                color: None
+            },
+        ]
+    );
+    assert_eq!(
+        thread.issue.updated_at.to_rfc3339(),
+        "2026-03-02T12:30:00+00:00"
+    );
+}
+
+#[test]
+fn test_parse_discussion_comments_map_ids_and_deleted_author_to_ghost() {
+    let thread = parse_discussion(&repository(), &discussion_response()).unwrap();
+
+    let summary: Vec<(u64, &str, &str)> = thread
+        .comments
+        .iter()
+        .map(|c| (c.id.as_u64(), c.author.as_str(), c.body.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (9001, "alice", "Please include the editor too."),
+            (9002, "ghost", "+1"),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_discussion_closed_discussion_is_closed_issue() {
+    let mut response = discussion_response();
+    response["data"]["repository"]["discussion"]["closed"] = json!(true);
+
+    let thread = parse_discussion(&repository(), &response).unwrap();
+
+    assert_eq!(thread.issue.state, IssueState::Closed);
+}
+
+#[test]
+fn test_parse_discussion_null_discussion_returns_not_found() {
+    let response = json!({ "data": { "repository": { "discussion": null } } });
+
+    let result = parse_discussion(&repository(), &response);
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[test]
+fn test_parse_discussion_error_types_map_to_operation_errors() {
+    let error =
+        |kind: &str| json!({ "data": null, "errors": [{ "type": kind, "message": "nope" }] });
+
+    assert!(matches!(
+        parse_discussion(&repository(), &error("NOT_FOUND")),
+        Err(GitHubOperationError::NotFound { .. })
+    ));
+    assert!(matches!(
+        parse_discussion(&repository(), &error("FORBIDDEN")),
+        Err(GitHubOperationError::PermissionDenied { .. })
+    ));
+    assert!(matches!(
+        parse_discussion(&repository(), &error("INTERNAL")),
+        Err(GitHubOperationError::Transient { .. })
+    ));
+}
+
+#[test]
+fn test_parse_discussion_missing_fields_returns_parse_failure() {
+    let response = json!({ "data": { "repository": { "discussion": { "id": "D_1" } } } });
+
+    let result = parse_discussion(&repository(), &response);
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_parse_added_comment_recorded_response_returns_comment() {
+    let comment = parse_added_comment(&added_comment_response()).unwrap();
+
+    assert_eq!(comment.id, CommentId::new(9100));
+    assert_eq!(comment.author, "cogworks[bot]");
+    assert_eq!(comment.body, "Plan posted.");
+}
+
+// ─── GithubClient ───────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_get_discussion_posts_query_to_graphql_and_returns_thread() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, discussion_response());
+
+    let thread = client_without_repository(&transport)
+        .get_discussion(&repository(), WorkItemId::new(7))
+        .await
+        .unwrap();
+
+    assert_eq!(thread.issue.id, WorkItemId::new(7));
+    assert_eq!(thread.comments.len(), 2);
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Post);
+    assert_eq!(requests[0].path, GRAPHQL_PATH);
+    assert_eq!(
+        requests[0].body,
+        Some(discussion_request(&repository(), WorkItemId::new(7)))
+    );
+}
+
+#[tokio::test]
+async fn test_get_discussion_records_graphql_rate_limit() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, discussion_response());
+    let client = client_without_repository(&transport);
+
+    client
+        .get_discussion(&repository(), WorkItemId::new(7))
+        .await
+        .unwrap();
+
+    let budget = client.rate_limits().graphql_budget().unwrap();
+    assert_eq!(budget.remaining, 4999);
+}
+
+#[tokio::test]
+async fn test_get_discussion_http_unauthorized_returns_permission_denied() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(401, json!({ "message": "Bad credentials" }));
+
+    let result = client_without_repository(&transport)
+        .get_discussion(&repository(), WorkItemId::new(7))
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::PermissionDenied { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_post_discussion_comment_sends_mutation_and_returns_comment() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, discussion_response());
+    transport.push_json(200, added_comment_response());
+    let client = client_without_repository(&transport);
+    let thread = client
+        .get_discussion(&repository(), WorkItemId::new(7))
+        .await
+        .unwrap();
+
+    let comment = client
+        .post_discussion_comment(&thread, "Plan posted.")
+        .await
+        .unwrap();
+
+    assert_eq!(comment.id, CommentId::new(9100));
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].path, GRAPHQL_PATH);
+    assert_eq!(
+        requests[1].body,
+        Some(add_comment_request(&thread.node_id, "Plan posted."))
+    );
+}
+
+#[tokio::test]
+async fn test_post_discussion_comment_forbidden_returns_permission_denied() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, discussion_response());
+    transport.push_json(
+        200,
+        json!({ "data": null, "errors": [{ "type": "FORBIDDEN", "message": "locked" }] }),
+    );
+    let client = client_without_repository(&transport);
+    let thread = client
+        .get_discussion(&repository(), WorkItemId::new(7))
+        .await
+        .unwrap();
+
+    let result = client
+        .post_discussion_comment(&thread, "Plan posted.")
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::PermissionDenied { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_get_discussion_without_transport_returns_capability_missing() {
+    let client = GithubClient::new(Arc::new(()));
+
+    let result = client
+        .get_discussion(&repository(), WorkItemId::new(7))
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::SdkCapabilityMissing { .. })
+    ));
+}
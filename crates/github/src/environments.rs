@@ -0,0 +1,120 @@
+//! Deployment environment protection lookup.
+//!
+//! Merging into a branch that deploys to a protected environment starts a
+//! deployment that waits on required reviewers or a wait timer. The
+//! Integration node reads the protection rules first so it can hold the work
+//! item rather than merge into a gated flow.
+//!
+//! The rules are read from `protection_rules` in
+//! `GET /repos/{owner}/{repo}/environments/{name}`: a `required_reviewers`
+//! rule lists users (by login) and teams (by slug), and a `wait_timer` rule
+//! gives the delay in minutes. Other rule types (e.g. `branch_policy`) do not
+//! make a deployment wait and are ignored.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Environment protection.
+
+use std::time::Duration;
+
+use serde_json::Value as JsonValue;
+use tracing::instrument;
+
+use pipeline::{
+    github::{EnvironmentProtection, GitHubOperationError},
+    RepositoryId,
+};
+
+use crate::{
+    default_branch::repository_path, issues::encode_query_value, rate_limited::status_error,
+    transport::RestRequest, GithubClient,
+};
+
+impl GithubClient {
+    /// Return the deployment protection rules of `environment` in `repository`.
+    ///
+    /// An environment that does not exist has no protection rules and yields
+    /// [`EnvironmentProtection::default`], which is unprotected.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::PermissionDenied`] — the installation cannot
+    ///   read environments.
+    /// - [`GitHubOperationError::ParseFailure`] — a protection rule is
+    ///   malformed.
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   [transport](crate::transport).
+    #[instrument(skip(self))]
+    pub async fn get_environment_protection(
+        &self,
+        repository: &RepositoryId,
+        environment: &str,
+    ) -> Result<EnvironmentProtection, GitHubOperationError> {
+        let path = format!(
+            "{}/environments/{}",
+            repository_path(repository),
+            encode_query_value(environment)
+        );
+        let response = self.send(RestRequest::get(path)).await?;
+        if response.status == 404 {
+            return Ok(EnvironmentProtection::default());
+        }
+        if let Some(error) = status_error(&response, &format!("environment {environment}")) {
+            return Err(error);
+        }
+        parse_environment_protection(&response.body)
+    }
+}
+
+/// Reads the protection rules of an environment response.
+///
+/// # Errors
+///
+/// [`GitHubOperationError::ParseFailure`] — a `wait_timer` rule has no
+/// numeric timer, or a reviewer has neither a `login` nor a `slug`.
+pub(crate) fn parse_environment_protection(
+    body: &JsonValue,
+) -> Result<EnvironmentProtection, GitHubOperationError> {
+    let parse_failure = |message: &str| GitHubOperationError::ParseFailure {
+        message: format!("environment: {message}"),
+    };
+
+    let mut protection = EnvironmentProtection::default();
+    let rules = body
+        .get("protection_rules")
+        .and_then(JsonValue::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for rule in rules {
+        match rule.get("type").and_then(JsonValue::as_str) {
+            Some("required_reviewers") => {
+                let reviewers = rule
+                    .get("reviewers")
+                    .and_then(JsonValue::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                for reviewer in reviewers {
+                    let name = reviewer
+                        .get("reviewer")
+                        .and_then(|r| r.get("login").or_else(|| r.get("slug")))
+                        .and_then(JsonValue::as_str)
+                        .ok_or_else(|| parse_failure("reviewer without login or slug"))?;
+                    protection.required_reviewers.push(name.to_string());
+                }
+            }
+            Some("wait_timer") => {
+                let minutes = rule
+                    .get("wait_timer")
+                    .and_then(JsonValue::as_u64)
+                    .ok_or_else(|| parse_failure("wait_timer rule without a timer"))?;
+                protection.wait_timer = Some(Duration::from_secs(minutes.saturating_mul(60)));
+            }
+            _ => {}
+        }
+    }
+    Ok(protection)
+}
+
+#[cfg(test)]
+#[path = "environments_tests.rs"]
+mod tests;
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::{
+    test_support::{client_without_repository, repository},
+    transport::{RestMethod, ScriptedTransport},
+};
+
+use super::*;
+
+#[test]
+fn test_parse_environment_protection_no_rules_is_unprotected() {
+    let protection =
+        parse_environment_protection(&json!({ "name": "staging", "protection_rules": [] }))
+            .unwrap();
+
+    assert_eq!(protection, EnvironmentProtection::default());
+    assert!(!protection.is_protected());
+}
+
+#[test]
+fn test_parse_environment_protection_reviewers_and_timer_is_protected() {
+    let body = json!({
+        "name": "production",
+        "protection_rules": [
+            { "type": "wait_timer", "wait_timer": 30 },
+            {
+                "type": "required_reviewers",
+                "reviewers": [
+                    { "type": "User", "reviewer": { "login": "octocat" } },
+                    { "type": "Team", "reviewer": { "slug": "release-managers" } }
+                ]
+            },
+            { "type": "branch_policy" }
+        ]
+    });
+
+    let protection = parse_environment_protection(&body).unwrap();
+
+    assert_eq!(
+        protection.required_reviewers,
+        vec!["octocat".to_string(), "release-managers".to_string()]
+    );
+    assert_eq!(protection.wait_timer, Some(Duration::from_secs(30 * 60)));
+    assert!(protection.is_protected());
+}
+
+#[test]
+fn test_parse_environment_protection_zero_wait_timer_is_unprotected() {
+    let body = json!({ "protection_rules": [{ "type": "wait_timer", "wait_timer": 0 }] });
+
+    assert!(!parse_environment_protection(&body).unwrap().is_protected());
+}
+
+#[test]
+fn test_parse_environment_protection_reviewer_without_name_returns_parse_failure() {
+    let body = json!({
+        "protection_rules": [
+            { "type": "required_reviewers", "reviewers": [{ "type": "User", "reviewer": {} }] }
+        ]
+    });
+
+    let error = parse_environment_protection(&body).unwrap_err();
+
+    assert!(matches!(error, GitHubOperationError::ParseFailure { .. }));
+}
+
+#[tokio::test]
+async fn test_get_environment_protection_protected_environment_holds() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(
+        200,
+        json!({
+            "protection_rules": [{
+                "type": "required_reviewers",
+                "reviewers": [{ "type": "User", "reviewer": { "login": "octocat" } }]
+            }]
+        }),
+    );
+
+    let protection = client_without_repository(&transport)
+        .get_environment_protection(&repository(), "production")
+        .await
+        .unwrap();
+
+    assert!(protection.is_protected());
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, RestMethod::Get);
+    assert_eq!(
+        requests[0].path,
+        "/repos/octo/widgets/environments/production"
+    );
+}
+
+#[tokio::test]
+async fn test_get_environment_protection_unprotected_environment_proceeds() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, json!({ "name": "staging", "protection_rules": [] }));
+
+    let protection = client_without_repository(&transport)
+        .get_environment_protection(&repository(), "staging")
+        .await
+        .unwrap();
+
+    assert!(!protection.is_protected());
+}
+
+#[tokio::test]
+async fn test_get_environment_protection_missing_environment_is_unprotected() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let protection = client_without_repository(&transport)
+        .get_environment_protection(&repository(), "preview")
+        .await
+        .unwrap();
+
+    assert_eq!(protection, EnvironmentProtection::default());
+}
+
+#[tokio::test]
+async fn test_get_environment_protection_name_with_space_is_encoded() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, json!({ "protection_rules": [] }));
+
+    client_without_repository(&transport)
+        .get_environment_protection(&repository(), "prod eu")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        transport.requests()[0].path,
+        "/repos/octo/widgets/environments/prod%20eu"
+    );
+}
+
+#[tokio::test]
+async fn test_get_environment_protection_server_error_returns_transient() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(502, json!({}));
+
+    let error = client_without_repository(&transport)
+        .get_environment_protection(&repository(), "production")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, GitHubOperationError::Transient { .. }));
+}
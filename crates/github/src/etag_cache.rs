@@ -0,0 +1,202 @@
+//! ETag-based conditional GET caching.
+//!
+//! Repeated reads of the same issue or pull request cost primary rate-limit
+//! budget even when nothing changed. With the cache enabled
+//! ([`GithubClient::with_etag_cache`]), every GET records the response's
+//! `ETag` and body under its request URL, and the next GET of that URL sends
+//! `If-None-Match`. GitHub answers an unchanged resource with `304 Not
+//! Modified`, which does not count against the primary rate limit, and the
+//! cached body is returned in its place.
+//!
+//! The cache holds at most `capacity` URLs and evicts the least recently
+//! used one when full.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Conditional requests.
+
+use std::{collections::HashMap, num::NonZeroUsize, sync::Mutex};
+
+use serde_json::Value as JsonValue;
+
+use pipeline::github::GitHubOperationError;
+
+use crate::{
+    rate_limit::EndpointClass, rate_limited::RestResponse, transport::RestRequest, GithubClient,
+};
+
+/// Response header carrying the entity tag.
+const ETAG_HEADER: &str = "etag";
+
+/// Request header carrying the cached entity tag.
+pub const IF_NONE_MATCH_HEADER: &str = "If-None-Match";
+
+/// A cached response body and the tag it was served with.
+#[derive(Debug, Clone)]
+struct Entry {
+    etag: String,
+    body: JsonValue,
+    /// Value of [`Entries::clock`] when the entry was last used.
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    by_url: HashMap<String, Entry>,
+    /// Incremented on every use; orders entries by recency.
+    clock: u64,
+}
+
+/// LRU cache of GET response bodies keyed by request URL.
+#[derive(Debug)]
+pub struct EtagCache {
+    capacity: NonZeroUsize,
+    entries: Mutex<Entries>,
+}
+
+impl EtagCache {
+    /// Creates an empty cache holding at most `capacity` URLs.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// The most URLs the cache holds.
+    pub fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
+    /// Number of URLs currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().by_url.len()
+    }
+
+    /// Returns `true` if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the `If-None-Match` value to send for `url`, if it is cached,
+    /// and marks it as recently used.
+    pub fn if_none_match(&self, url: &str) -> Option<String> {
+        let mut entries = self.lock();
+        entries.clock += 1;
+        let clock = entries.clock;
+        let entry = entries.by_url.get_mut(url)?;
+        entry.last_used = clock;
+        Some(entry.etag.clone())
+    }
+
+    /// Applies `response` to a GET of `url` and returns the response to use.
+    ///
+    /// - `304` — the cached body is returned with status `200`. A 304 for a
+    ///   URL that is no longer cached is returned unchanged.
+    /// - `2xx` with an `ETag` — the body is cached, evicting the least
+    ///   recently used URL if the cache is full.
+    /// - Anything else is returned unchanged and the cache is not touched.
+    pub fn resolve(&self, url: &str, mut response: RestResponse) -> RestResponse {
+        let mut entries = self.lock();
+        entries.clock += 1;
+        let clock = entries.clock;
+        if response.status == 304 {
+            if let Some(entry) = entries.by_url.get_mut(url) {
+                entry.last_used = clock;
+                tracing::debug!(url, "GitHub resource not modified; using cached body");
+                response.status = 200;
+                response.body = entry.body.clone();
+            }
+            return response;
+        }
+        if !(200..300).contains(&response.status) {
+            return response;
+        }
+        let Some(etag) = response.header(ETAG_HEADER).map(str::to_string) else {
+            return response;
+        };
+        if !entries.by_url.contains_key(url) && entries.by_url.len() >= self.capacity.get() {
+            let oldest = entries
+                .by_url
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(url, _)| url.clone());
+            if let Some(oldest) = oldest {
+                entries.by_url.remove(&oldest);
+            }
+        }
+        entries.by_url.insert(
+            url.to_string(),
+            Entry {
+                etag,
+                body: response.body.clone(),
+                last_used: clock,
+            },
+        );
+        response
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl GithubClient {
+    /// Sends `GET url` through the rate limiter, making it conditional when
+    /// the ETag cache is enabled and holds `url`.
+    ///
+    /// A `304` is returned as a `200` carrying the cached body, so callers
+    /// never see the conditional exchange.
+    ///
+    /// # Errors
+    ///
+    /// As for [`RateLimitedClient::execute`](crate::rate_limited::RateLimitedClient::execute)
+    /// and [`GithubClient::conditional_get_unmetered`].
+    pub(crate) async fn conditional_get(
+        &self,
+        url: &str,
+    ) -> Result<RestResponse, GitHubOperationError> {
+        self.rate_limited()
+            .execute(EndpointClass::for_path(url), || {
+                self.conditional_get_unmetered(url)
+            })
+            .await
+    }
+
+    /// As [`GithubClient::conditional_get`], without rate limiting; for
+    /// callers that consult and update the rate-limit tracker themselves.
+    ///
+    /// # Errors
+    ///
+    /// As for [`GithubClient::send_unmetered`].
+    pub(crate) async fn conditional_get_unmetered(
+        &self,
+        url: &str,
+    ) -> Result<RestResponse, GitHubOperationError> {
+        let Some(cache) = &self.etag_cache else {
+            return self.send_get(url, None).await;
+        };
+        let etag = cache.if_none_match(url);
+        let response = self.send_get(url, etag.as_deref()).await?;
+        Ok(cache.resolve(url, response))
+    }
+
+    /// Sends `GET url`, with `If-None-Match: if_none_match` when given.
+    async fn send_get(
+        &self,
+        url: &str,
+        if_none_match: Option<&str>,
+    ) -> Result<RestResponse, GitHubOperationError> {
+        let mut request = RestRequest::get(url);
+        if let Some(etag) = if_none_match {
+            request = request.with_header(IF_NONE_MATCH_HEADER, etag);
+        }
+        self.send_unmetered(request).await
+    }
+}
+
+#[cfg(test)]
+#[path = "etag_cache_tests.rs"]
+mod tests;
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::{
+    test_support::client_without_repository,
+    transport::{RestMethod, ScriptedTransport},
+};
+
+use super::*;
+
+fn cache(capacity: usize) -> EtagCache {
+    EtagCache::new(NonZeroUsize::new(capacity).unwrap())
+}
+
+fn response(status: u16, etag: Option<&str>, body: JsonValue) -> RestResponse {
+    RestResponse {
+        status,
+        headers: etag
+            .map(|etag| vec![("ETag".to_string(), etag.to_string())])
+            .unwrap_or_default(),
+        body,
+    }
+}
+
+fn not_modified() -> RestResponse {
+    response(304, Some(r#""v1""#), JsonValue::Null)
+}
+
+// ─── EtagCache ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_resolve_success_with_etag_caches_body() {
+    let cache = cache(4);
+
+    let resolved = cache.resolve("/a", response(200, Some(r#""v1""#), json!({ "n": 1 })));
+
+    assert_eq!(resolved.status, 200);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.if_none_match("/a").as_deref(), Some(r#""v1""#));
+}
+
+#[test]
+fn test_resolve_not_modified_cached_url_returns_cached_body_as_ok() {
+    let cache = cache(4);
+    let body = json!({ "n": 1 });
+    cache.resolve("/a", response(200, Some(r#""v1""#), body.clone()));
+
+    let resolved = cache.resolve("/a", not_modified());
+
+    assert_eq!(resolved.status, 200);
+    assert_eq!(resolved.body, body);
+}
+
+#[test]
+fn test_resolve_not_modified_uncached_url_returned_unchanged() {
+    let cache = cache(4);
+
+    let resolved = cache.resolve("/a", not_modified());
+
+    assert_eq!(resolved.status, 304);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_resolve_without_etag_or_error_status_not_cached() {
+    let cache = cache(4);
+
+    cache.resolve("/a", response(200, None, json!({})));
+    cache.resolve("/b", response(404, Some(r#""v1""#), json!({})));
+
+    assert!(cache.is_empty());
+    assert_eq!(cache.if_none_match("/a"), None);
+}
+
+#[test]
+fn test_resolve_full_cache_evicts_least_recently_used_url() {
+    let cache = cache(2);
+    cache.resolve("/a", response(200, Some(r#""a""#), json!({})));
+    cache.resolve("/b", response(200, Some(r#""b""#), json!({})));
+    cache.if_none_match("/a");
+
+    cache.resolve("/c", response(200, Some(r#""c""#), json!({})));
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.if_none_match("/a").is_some());
+    assert_eq!(cache.if_none_match("/b"), None);
+    assert!(cache.if_none_match("/c").is_some());
+}
+
+#[test]
+fn test_resolve_cached_url_new_etag_replaces_entry_without_eviction() {
+    let cache = cache(1);
+    cache.resolve("/a", response(200, Some(r#""v1""#), json!({})));
+
+    cache.resolve("/a", response(200, Some(r#""v2""#), json!({})));
+
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.if_none_match("/a").as_deref(), Some(r#""v2""#));
+}
+
+// ─── conditional_get ────────────────────────────────────────────────────────
+
+fn client(transport: &Arc<ScriptedTransport>, capacity: usize) -> GithubClient {
+    client_without_repository(transport).with_etag_cache(capacity)
+}
+
+#[tokio::test]
+async fn test_conditional_get_second_read_sends_if_none_match_and_returns_cached_body() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let body = json!({ "state": "success" });
+    transport.push(Ok(response(200, Some(r#""v1""#), body.clone())));
+    transport.push(Ok(not_modified()));
+    let client = client(&transport, 8);
+
+    client
+        .conditional_get("/repos/octo/widgets/x")
+        .await
+        .unwrap();
+    let second = client
+        .conditional_get("/repos/octo/widgets/x")
+        .await
+        .unwrap();
+
+    assert_eq!(second.status, 200);
+    assert_eq!(second.body, body);
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, RestMethod::Get);
+    assert_eq!(requests[0].header(IF_NONE_MATCH_HEADER), None);
+    assert_eq!(requests[1].header(IF_NONE_MATCH_HEADER), Some(r#""v1""#));
+}
+
+#[tokio::test]
+async fn test_conditional_get_cache_disabled_sends_plain_get() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push(Ok(response(200, Some(r#""v1""#), json!({}))));
+    transport.push(Ok(response(200, Some(r#""v1""#), json!({}))));
+    let client = client(&transport, 0);
+
+    client
+        .conditional_get("/repos/octo/widgets/x")
+        .await
+        .unwrap();
+    client
+        .conditional_get("/repos/octo/widgets/x")
+        .await
+        .unwrap();
+
+    assert!(client.etag_cache().is_none());
+    assert!(transport
+        .requests()
+        .iter()
+        .all(|request| request.header(IF_NONE_MATCH_HEADER).is_none()));
+}
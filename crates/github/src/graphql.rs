@@ -0,0 +1,222 @@
+//! Types shared by the GraphQL code paths.
+//!
+//! GraphQL addresses objects by global node ID: an opaque, base64-like string
+//! (e.g. `D_kwDOABCD1M4AXyz`) that is unrelated to the issue, pull request, or
+//! comment numbers used everywhere else. [`GraphQlNodeId`] keeps those IDs
+//! out of the domain identifier types: GraphQL documents take and return
+//! node IDs, and the response mappers translate to [`pipeline`] identifiers
+//! (e.g. [`pipeline::WorkItemId`]) at the edge. Node IDs never appear in
+//! [`pipeline`] types.
+//!
+//! GraphQL is also metered differently from REST: each query costs points
+//! from an hourly budget. Queries select [`RATE_LIMIT_SELECTION`] at their
+//! root, and [`parse_rate_limit`] reads the result so the client's
+//! [`RateLimitTracker`](crate::rate_limit::RateLimitTracker) can follow the
+//! remaining points.
+//!
+//! Documents are posted to [`GRAPHQL_PATH`] through the REST transport by
+//! [`GithubClient::send_graphql`], which also records the reported point
+//! budget.
+//!
+//! GraphQL answers HTTP 200 even when part or all of a request failed; the
+//! failures are listed in the response's `errors` array. [`parse_errors`]
+//! reads it and [`classify_error`] maps each entry onto the
+//! [`GitHubOperationError`] a REST call would have produced, so GraphQL
+//! failures follow the same [`pipeline::RetryPolicy`] rules.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §GraphQL node IDs and
+//! §Rate limiting.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use pipeline::github::GitHubOperationError;
+
+use crate::{
+    rate_limited::status_error,
+    transport::{RestRequest, GRAPHQL_PATH},
+    GithubClient,
+};
+
+/// Root selection added to every GraphQL query to report its point cost.
+///
+/// Mutations cannot select it; their cost shows up in the next query's report.
+pub const RATE_LIMIT_SELECTION: &str = "rateLimit { cost remaining resetAt }";
+
+/// A GitHub GraphQL global node ID.
+///
+/// Opaque: compare and pass it back to GitHub, never parse it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GraphQlNodeId(String);
+
+impl GraphQlNodeId {
+    /// Creates a node ID, returning `None` if the value is empty.
+    #[must_use]
+    pub fn new(value: impl Into<String>) -> Option<Self> {
+        let v = value.into();
+        if v.is_empty() {
+            None
+        } else {
+            Some(Self(v))
+        }
+    }
+
+    /// Returns the node ID as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for GraphQlNodeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// ─── Point budget ────────────────────────────────────────────────────────────
+
+/// The `rateLimit` object of a GraphQL query response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlRateLimit {
+    /// Points charged for this query.
+    pub cost: u32,
+    /// Points left in the current window.
+    pub remaining: u32,
+    /// When the window resets.
+    pub reset_at: DateTime<Utc>,
+}
+
+/// Reads `data.rateLimit` from a GraphQL response.
+///
+/// Returns `None` if the query did not select [`RATE_LIMIT_SELECTION`] or the
+/// response carries no data (e.g. it failed outright).
+pub fn parse_rate_limit(response: &JsonValue) -> Option<GraphQlRateLimit> {
+    let rate_limit = response.get("data")?.get("rateLimit")?;
+    serde_json::from_value(rate_limit.clone()).ok()
+}
+
+// ─── Errors ──────────────────────────────────────────────────────────────────
+
+/// One entry of a GraphQL response's `errors` array.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GraphQlError {
+    /// GitHub's error type (e.g. `NOT_FOUND`, `RATE_LIMITED`), if given.
+    #[serde(default, rename = "type")]
+    pub kind: Option<String>,
+    /// Human-readable description.
+    pub message: String,
+    /// Path of the response field the error belongs to, e.g.
+    /// `["repository", "i3"]`; empty for request-level errors.
+    #[serde(default)]
+    pub path: Vec<JsonValue>,
+    /// Extra detail; `extensions.code` carries the error type on some
+    /// endpoints instead of `type`.
+    #[serde(default)]
+    pub extensions: Option<JsonValue>,
+}
+
+impl GraphQlError {
+    /// Returns the error type from `type`, falling back to `extensions.code`.
+    pub fn code(&self) -> Option<&str> {
+        self.kind.as_deref().or_else(|| {
+            self.extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code"))
+                .and_then(JsonValue::as_str)
+        })
+    }
+
+    /// Returns `true` if `path` contains the field or alias `name`.
+    pub fn concerns(&self, name: &str) -> bool {
+        self.path
+            .iter()
+            .any(|segment| segment.as_str() == Some(name))
+    }
+}
+
+/// Reads the `errors` array of a GraphQL response.
+///
+/// Returns an empty list when there is none or it cannot be read.
+pub fn parse_errors(response: &JsonValue) -> Vec<GraphQlError> {
+    response
+        .get("errors")
+        .and_then(|errors| serde_json::from_value(errors.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Maps a GraphQL error about `resource` onto a [`GitHubOperationError`].
+///
+/// `NOT_FOUND` becomes [`GitHubOperationError::NotFound`], `FORBIDDEN`
+/// [`GitHubOperationError::PermissionDenied`], and `RATE_LIMITED`
+/// [`GitHubOperationError::RateLimitExhausted`] until `rate_limit_reset`.
+/// Any other error is treated as transient.
+pub fn classify_error(
+    resource: &str,
+    error: &GraphQlError,
+    rate_limit_reset: DateTime<Utc>,
+) -> GitHubOperationError {
+    match error.code() {
+        Some("NOT_FOUND") => GitHubOperationError::NotFound {
+            resource: resource.to_string(),
+        },
+        Some("FORBIDDEN") => GitHubOperationError::PermissionDenied {
+            action: format!("{resource}: {}", error.message),
+        },
+        Some("RATE_LIMITED") => GitHubOperationError::RateLimitExhausted {
+            reset_at: rate_limit_reset,
+        },
+        _ => GitHubOperationError::Transient {
+            message: format!("{resource}: {}", error.message),
+        },
+    }
+}
+
+// ─── Sending ─────────────────────────────────────────────────────────────────
+
+impl GithubClient {
+    /// POSTs a GraphQL document and returns the response body, feeding the
+    /// reported `rateLimit` into the GraphQL budget.
+    ///
+    /// Entries of the body's `errors` array are left to the caller, since a
+    /// partly failed query still carries data.
+    ///
+    /// # Errors
+    ///
+    /// - The [`status_error`] of a non-success response, for `resource`.
+    /// - As for [`GithubClient::send`].
+    pub(crate) async fn send_graphql(
+        &self,
+        document: JsonValue,
+        resource: &str,
+    ) -> Result<JsonValue, GitHubOperationError> {
+        let response = self.send(RestRequest::post(GRAPHQL_PATH, document)).await?;
+        if let Some(error) = status_error(&response, resource) {
+            return Err(error);
+        }
+        if let Some(rate_limit) = parse_rate_limit(&response.body) {
+            self.rate_limits().observe_graphql(rate_limit);
+        }
+        Ok(response.body)
+    }
+
+    /// Returns when the GraphQL point budget resets, for mapping a
+    /// `RATE_LIMITED` error with [`classify_error`].
+    ///
+    /// Uses the latest reported budget; before any query has reported one,
+    /// assumes a full hourly window from now.
+    pub(crate) fn graphql_rate_limit_reset(&self) -> DateTime<Utc> {
+        self.rate_limits().graphql_budget().map_or_else(
+            || Utc::now() + chrono::Duration::hours(1),
+            |budget| budget.reset_at,
+        )
+    }
+}
+
+#[cfg(test)]
+#[path = "graphql_tests.rs"]
+mod tests;
@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use pipeline::WorkItemId;
+
+use crate::{
+    discussions::{add_comment_request, parse_discussion},
+    test_support::{client_without_repository, repository},
+    transport::{RestMethod, ScriptedTransport, GRAPHQL_PATH},
+};
+
+use super::*;
+
+// ─── GraphQlNodeId ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_graphql_node_id_new_non_empty_value_is_kept_verbatim() {
+    let id = GraphQlNodeId::new("PR_kwDOABCD1M5a1b2c").unwrap();
+
+    assert_eq!(id.as_str(), "PR_kwDOABCD1M5a1b2c");
+    assert_eq!(id.to_string(), "PR_kwDOABCD1M5a1b2c");
+}
+
+#[test]
+fn test_graphql_node_id_new_empty_value_returns_none() {
+    assert_eq!(GraphQlNodeId::new(""), None);
+}
+
+#[test]
+fn test_graphql_node_id_serde_round_trips_as_plain_string() {
+    let id = GraphQlNodeId::new("D_kwDOAbc123").unwrap();
+
+    let json = serde_json::to_value(&id).unwrap();
+    let back: GraphQlNodeId = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(json, json!("D_kwDOAbc123"));
+    assert_eq!(back, id);
+}
+
+#[test]
+fn test_graphql_mapping_node_id_stays_separate_from_work_item_id() {
+    let repository = repository();
+    let response = json!({
+        "data": {
+            "repository": {
+                "discussion": {
+                    "id": "D_kwDOAbc123",
+                    "number": 7,
+                    "title": "t",
+                    "body": "b",
+                    "closed": false,
+                    "createdAt": "2026-03-01T10:00:00Z",
+                    "updatedAt": "2026-03-01T10:00:00Z",
+                    "labels": { "nodes": [] },
+                    "comments": { "nodes": [] }
+                }
+            }
+        }
+    });
+
+    let thread = parse_discussion(&repository, &response).unwrap();
+
+    assert_eq!(thread.node_id, GraphQlNodeId::new("D_kwDOAbc123").unwrap());
+    assert_eq!(thread.issue.id, WorkItemId::new(7));
+    let domain = serde_json::to_string(&thread.issue).unwrap();
+    assert!(!domain.contains("D_kwDOAbc123"), "node ID leaked: {domain}");
+}
+
+#[test]
+fn test_graphql_mutation_variables_carry_node_id_not_number() {
+    let node_id = GraphQlNodeId::new("D_kwDOAbc123").unwrap();
+
+    let request = add_comment_request(&node_id, "hello");
+
+    assert_eq!(request["variables"]["discussionId"], json!("D_kwDOAbc123"));
+}
+
+// ─── Point budget ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_rate_limit_selected_rate_limit_returns_points() {
+    let response = json!({
+        "data": {
+            "repository": {},
+            "rateLimit": { "cost": 3, "remaining": 4_990, "resetAt": "2026-03-01T13:00:00Z" }
+        }
+    });
+
+    let rate_limit = parse_rate_limit(&response).unwrap();
+
+    assert_eq!(rate_limit.cost, 3);
+    assert_eq!(rate_limit.remaining, 4_990);
+    assert_eq!(
+        rate_limit.reset_at.to_rfc3339(),
+        "2026-03-01T13:00:00+00:00"
+    );
+}
+
+#[test]
+fn test_parse_rate_limit_without_selection_returns_none() {
+    let response = json!({ "data": { "repository": {} } });
+
+    assert_eq!(parse_rate_limit(&response), None);
+}
+
+#[test]
+fn test_parse_rate_limit_failed_query_without_data_returns_none() {
+    let response = json!({ "errors": [{ "message": "Bad credentials" }] });
+
+    assert_eq!(parse_rate_limit(&response), None);
+}
+
+#[test]
+fn test_rate_limit_selection_names_fields_parse_rate_limit_reads() {
+    for field in ["cost", "remaining", "resetAt"] {
+        assert!(RATE_LIMIT_SELECTION.contains(field), "missing {field}");
+    }
+}
+
+// ─── GithubClient::send_graphql ─────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_send_graphql_success_posts_document_and_records_points() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(
+        200,
+        json!({
+            "data": {
+                "viewer": { "login": "cogworks[bot]" },
+                "rateLimit": { "cost": 1, "remaining": 4999, "resetAt": "2026-10-15T10:00:00Z" }
+            }
+        }),
+    );
+    let client = client_without_repository(&transport);
+    let document = json!({ "query": "query { viewer { login } }", "variables": {} });
+
+    client
+        .send_graphql(document.clone(), "viewer")
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, RestMethod::Post);
+    assert_eq!(requests[0].path, GRAPHQL_PATH);
+    assert_eq!(requests[0].body, Some(document));
+    assert_eq!(
+        client
+            .rate_limits()
+            .graphql_budget()
+            .map(|budget| budget.remaining),
+        Some(4999)
+    );
+}
+
+#[tokio::test]
+async fn test_send_graphql_unauthorised_returns_permission_denied() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(401, json!({ "message": "Bad credentials" }));
+
+    let result = client_without_repository(&transport)
+        .send_graphql(json!({ "query": "{}" }), "viewer")
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::PermissionDenied { .. })
+    ));
+}
@@ -0,0 +1,326 @@
+//! Reading many issues in one GraphQL request.
+//!
+//! Reconstructing several work items one
+//! [`GithubClient::get_issue_snapshot`] call at a time costs one round trip
+//! each. [`GithubClient::get_issues`] instead asks for up to
+//! [`ISSUE_BATCH_SIZE`] issues per GraphQL query, one aliased `issue(number:)`
+//! field per work item (`i0`, `i1`, ...), and maps each alias back onto its
+//! [`WorkItemId`]. GraphQL reports a missing issue as a `null` alias plus an
+//! error whose `path` names the alias, while the other aliases still resolve,
+//! so results are returned per work item and one bad number does not fail the
+//! rest.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Batch issue read.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+use tracing::instrument;
+
+use pipeline::{
+    github::{GitHubOperationError, IssueSnapshot, IssueState, Label},
+    MilestoneId, RepositoryId, WorkItemId,
+};
+
+use crate::{graphql::RATE_LIMIT_SELECTION, GithubClient};
+
+/// Maximum number of issues requested in one query.
+///
+/// Each aliased issue also selects its labels and assignees, so the query's
+/// point cost grows with the batch; 50 keeps it well under GitHub's node
+/// limit.
+pub const ISSUE_BATCH_SIZE: usize = 50;
+
+/// Maximum number of labels and assignees read per issue.
+const CONNECTION_PAGE_SIZE: u32 = 100;
+
+/// One result per requested work item, in request order.
+pub type IssueBatchResults = Vec<(WorkItemId, Result<IssueSnapshot, GitHubOperationError>)>;
+
+/// Fields selected for each aliased issue.
+const ISSUE_SELECTION: &str = "number title body state updatedAt \
+    milestone { number } \
+    labels(first: $first) { nodes { name color } } \
+    assignees(first: $first) { nodes { login } }";
+
+/// Returns the alias used for the issue at `index` in a batch.
+fn alias(index: usize) -> String {
+    format!("i{index}")
+}
+
+/// Builds the GraphQL request reading `ids` in `repository`.
+///
+/// Callers split larger sets into chunks of [`ISSUE_BATCH_SIZE`];
+/// [`GithubClient::get_issues`] does this.
+pub fn issue_batch_request(repository: &RepositoryId, ids: &[WorkItemId]) -> JsonValue {
+    let fields: String = ids
+        .iter()
+        .enumerate()
+        .map(|(index, id)| {
+            format!(
+                "    {}: issue(number: {}) {{ {ISSUE_SELECTION} }}\n",
+                alias(index),
+                id.as_u64()
+            )
+        })
+        .collect();
+    let query = format!(
+        "query($owner: String!, $name: String!, $first: Int!) {{\n  \
+         repository(owner: $owner, name: $name) {{\n{fields}  }}\n  {RATE_LIMIT_SELECTION}\n}}"
+    );
+    json!({
+        "query": query,
+        "variables": {
+            "owner": repository.owner(),
+            "name": repository.repo(),
+            "first": CONNECTION_PAGE_SIZE,
+        },
+    })
+}
+
+// ─── Response mapping ────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WireIssue {
+    number: u64,
+    title: String,
+    body: String,
+    state: String,
+    updated_at: DateTime<Utc>,
+    milestone: Option<WireMilestone>,
+    labels: Nodes<WireLabel>,
+    assignees: Nodes<WireUser>,
+}
+
+#[derive(Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct WireMilestone {
+    number: u64,
+}
+
+#[derive(Deserialize)]
+struct WireLabel {
+    name: String,
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WireUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct WireError {
+    #[serde(default, rename = "type")]
+    kind: Option<String>,
+    message: String,
+    #[serde(default)]
+    path: Vec<JsonValue>,
+}
+
+/// Maps a GraphQL error reported for one issue onto a
+/// [`GitHubOperationError`].
+fn issue_error(resource: String, error: &WireError) -> GitHubOperationError {
+    match error.kind.as_deref() {
+        Some("NOT_FOUND") => GitHubOperationError::NotFound { resource },
+        Some("FORBIDDEN") => GitHubOperationError::PermissionDenied {
+            action: format!("{resource}: {}", error.message),
+        },
+        _ => GitHubOperationError::Transient {
+            message: format!("{resource}: {}", error.message),
+        },
+    }
+}
+
+fn parse_issue(
+    repository: &RepositoryId,
+    value: JsonValue,
+) -> Result<IssueSnapshot, GitHubOperationError> {
+    let parse_failure = |message: String| GitHubOperationError::ParseFailure { message };
+    let issue: WireIssue =
+        serde_json::from_value(value).map_err(|e| parse_failure(format!("issue: {e}")))?;
+    let state = match issue.state.as_str() {
+        "OPEN" => IssueState::Open,
+        "CLOSED" => IssueState::Closed,
+        other => return Err(parse_failure(format!("issue: unknown state '{other}'"))),
+    };
+    Ok(IssueSnapshot {
+        id: WorkItemId::new(issue.number),
+        repository: repository.clone(),
+        title: issue.title,
+        body: issue.body,
+        state,
+        labels: issue
+            .labels
+            .nodes
+            .into_iter()
+            .map(|label| Label {
+                name: label.name,
+                color: label.color,
+            })
+            .collect(),
+        milestone: issue
+            .milestone
+            .map(|milestone| MilestoneId::new(milestone.number)),
+        assignees: issue
+            .assignees
+            .nodes
+            .into_iter()
+            .map(|user| user.login)
+            .collect(),
+        updated_at: issue.updated_at,
+    })
+}
+
+/// Maps a response to [`issue_batch_request`] for `ids` onto one result per
+/// work item, in the order of `ids`.
+///
+/// An alias that resolved is parsed into an [`IssueSnapshot`]. A `null` alias
+/// takes the error whose `path` names it (`NOT_FOUND` becomes
+/// [`GitHubOperationError::NotFound`], `FORBIDDEN`
+/// [`GitHubOperationError::PermissionDenied`], anything else
+/// [`GitHubOperationError::Transient`]), or `NotFound` if no error names it.
+///
+/// # Errors
+///
+/// Returns `Err` only when the response as a whole is unusable: it has no
+/// `data.repository` object. The error then applies to every id.
+pub fn parse_issue_batch(
+    repository: &RepositoryId,
+    ids: &[WorkItemId],
+    response: &JsonValue,
+) -> Result<IssueBatchResults, GitHubOperationError> {
+    let errors: Vec<WireError> = response
+        .get("errors")
+        .map(|errors| serde_json::from_value(errors.clone()))
+        .transpose()
+        .map_err(|e| GitHubOperationError::ParseFailure {
+            message: format!("issue batch errors: {e}"),
+        })?
+        .unwrap_or_default();
+    let Some(data) = response
+        .get("data")
+        .and_then(|data| data.get("repository"))
+        .and_then(JsonValue::as_object)
+    else {
+        let resource = format!("repository {repository}");
+        return Err(match errors.first() {
+            Some(error) => issue_error(resource, error),
+            None => GitHubOperationError::ParseFailure {
+                message: "issue batch: response has no repository data".to_string(),
+            },
+        });
+    };
+
+    Ok(ids
+        .iter()
+        .enumerate()
+        .map(|(index, id)| {
+            let alias = alias(index);
+            let resource = format!("issue #{id} in {repository}");
+            let result = match data.get(&alias) {
+                Some(value) if !value.is_null() => parse_issue(repository, value.clone()),
+                _ => Err(errors
+                    .iter()
+                    .find(|error| {
+                        error
+                            .path
+                            .iter()
+                            .any(|p| p.as_str() == Some(alias.as_str()))
+                    })
+                    .map_or_else(
+                        || GitHubOperationError::NotFound {
+                            resource: resource.clone(),
+                        },
+                        |error| issue_error(resource.clone(), error),
+                    )),
+            };
+            (*id, result)
+        })
+        .collect())
+}
+
+/// Rebuilds `error` for each work item of a chunk whose request failed as a
+/// whole ([`GitHubOperationError`] is not `Clone`).
+fn chunk_error(error: &GitHubOperationError) -> GitHubOperationError {
+    match error {
+        GitHubOperationError::NotFound { resource } => GitHubOperationError::NotFound {
+            resource: resource.clone(),
+        },
+        GitHubOperationError::PermissionDenied { action } => {
+            GitHubOperationError::PermissionDenied {
+                action: action.clone(),
+            }
+        }
+        GitHubOperationError::MissingAppPermission { action, permission } => {
+            GitHubOperationError::MissingAppPermission {
+                action: action.clone(),
+                permission: permission.clone(),
+            }
+        }
+        GitHubOperationError::RateLimitExhausted { reset_at } => {
+            GitHubOperationError::RateLimitExhausted {
+                reset_at: *reset_at,
+            }
+        }
+        GitHubOperationError::SdkCapabilityMissing { capability } => {
+            GitHubOperationError::SdkCapabilityMissing {
+                capability: capability.clone(),
+            }
+        }
+        GitHubOperationError::ParseFailure { message } => GitHubOperationError::ParseFailure {
+            message: message.clone(),
+        },
+        other => GitHubOperationError::Transient {
+            message: other.to_string(),
+        },
+    }
+}
+
+// ─── GithubClient entry point ────────────────────────────────────────────────
+
+impl GithubClient {
+    /// Read `ids` in `repository`, [`ISSUE_BATCH_SIZE`] issues per request.
+    ///
+    /// Each chunk is posted through [`GithubClient::send_graphql`]. Returns one
+    /// result per id, in the order given. A failure reading one issue (e.g.
+    /// it does not exist or is a pull request) affects only that id; a
+    /// failed request affects only the ids of its chunk.
+    #[instrument(skip(self, ids), fields(count = ids.len()))]
+    pub async fn get_issues(
+        &self,
+        repository: &RepositoryId,
+        ids: &[WorkItemId],
+    ) -> IssueBatchResults {
+        let mut results = Vec::with_capacity(ids.len());
+        for chunk in ids.chunks(ISSUE_BATCH_SIZE) {
+            let request = issue_batch_request(repository, chunk);
+            let parsed = match self
+                .send_graphql(request, &format!("issues of {repository}"))
+                .await
+            {
+                Ok(response) => parse_issue_batch(repository, chunk, &response),
+                Err(e) => Err(e),
+            };
+            match parsed {
+                Ok(chunk_results) => results.extend(chunk_results),
+                Err(e) => {
+                    tracing::warn!(error = %e, "issue batch request failed");
+                    results.extend(chunk.iter().map(|id| (*id, Err(chunk_error(&e)))));
+                }
+            }
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+#[path = "issue_batch_tests.rs"]
+mod tests;
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use pipeline::github::IssueState;
+
+use crate::{
+    rate_limited::RestResponse,
+    test_support::{client_without_repository, repository},
+    transport::{RestMethod, ScriptedTransport, GRAPHQL_PATH},
+};
+
+use super::*;
+
+fn ids(numbers: &[u64]) -> Vec<WorkItemId> {
+    numbers.iter().copied().map(WorkItemId::new).collect()
+}
+
+fn issue(number: u64, state: &str) -> JsonValue {
+    json!({
+        "number": number,
+        "title": format!("Issue {number}"),
+        "body": "",
+        "state": state,
+        "updatedAt": "2026-10-15T09:00:00Z",
+        "milestone": { "number": 3 },
+        "labels": { "nodes": [{ "name": "cogworks:run", "color": "0e8a16" }] },
+        "assignees": { "nodes": [{ "login": "octocat" }] }
+    })
+}
+
+/// A recorded response for issues 12, 13, and 14 where 13 does not exist.
+fn one_missing() -> JsonValue {
+    json!({
+        "data": {
+            "repository": {
+                "i0": issue(12, "OPEN"),
+                "i1": null,
+                "i2": issue(14, "CLOSED")
+            },
+            "rateLimit": { "cost": 1, "remaining": 4990, "resetAt": "2026-10-15T10:00:00Z" }
+        },
+        "errors": [{
+            "type": "NOT_FOUND",
+            "path": ["repository", "i1"],
+            "message": "Could not resolve to an issue or pull request with the number of 13."
+        }]
+    })
+}
+
+fn assert_one_missing(results: &IssueBatchResults) {
+    let numbers: Vec<_> = results.iter().map(|(id, _)| id.as_u64()).collect();
+    assert_eq!(numbers, vec![12, 13, 14]);
+    let first = results[0].1.as_ref().unwrap();
+    assert_eq!(first.state, IssueState::Open);
+    assert_eq!(first.milestone, Some(MilestoneId::new(3)));
+    assert_eq!(first.assignees, vec!["octocat".to_string()]);
+    assert!(matches!(
+        results[1].1,
+        Err(GitHubOperationError::NotFound { .. })
+    ));
+    assert_eq!(results[2].1.as_ref().unwrap().state, IssueState::Closed);
+}
+
+// ─── issue_batch_request ────────────────────────────────────────────────────
+
+#[test]
+fn test_issue_batch_request_one_alias_per_id_in_order() {
+    let request = issue_batch_request(&repository(), &ids(&[12, 13]));
+
+    let query = request["query"].as_str().unwrap();
+    assert!(query.contains("i0: issue(number: 12)"));
+    assert!(query.contains("i1: issue(number: 13)"));
+    assert!(query.contains(RATE_LIMIT_SELECTION));
+    assert_eq!(request["variables"]["owner"], json!("octo"));
+    assert_eq!(request["variables"]["name"], json!("widgets"));
+}
+
+// ─── parse_issue_batch ──────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_issue_batch_one_missing_issue_fails_only_that_id() {
+    let results = parse_issue_batch(&repository(), &ids(&[12, 13, 14]), &one_missing()).unwrap();
+
+    assert_one_missing(&results);
+}
+
+#[test]
+fn test_parse_issue_batch_null_alias_without_error_returns_not_found() {
+    let response = json!({ "data": { "repository": { "i0": null } } });
+
+    let results = parse_issue_batch(&repository(), &ids(&[12]), &response).unwrap();
+
+    assert!(matches!(
+        results[0].1,
+        Err(GitHubOperationError::NotFound { .. })
+    ));
+}
+
+#[test]
+fn test_parse_issue_batch_forbidden_alias_returns_permission_denied() {
+    let response = json!({
+        "data": { "repository": { "i0": null } },
+        "errors": [{ "type": "FORBIDDEN", "path": ["repository", "i0"], "message": "no" }]
+    });
+
+    let results = parse_issue_batch(&repository(), &ids(&[12]), &response).unwrap();
+
+    assert!(matches!(
+        results[0].1,
+        Err(GitHubOperationError::PermissionDenied { .. })
+    ));
+}
+
+#[test]
+fn test_parse_issue_batch_missing_repository_returns_error() {
+    let response = json!({
+        "data": { "repository": null },
+        "errors": [{ "type": "NOT_FOUND", "path": ["repository"], "message": "gone" }]
+    });
+
+    let result = parse_issue_batch(&repository(), &ids(&[12]), &response);
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+// ─── get_issues ─────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_get_issues_three_issues_one_missing_returns_per_id_results() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, one_missing());
+
+    let results = client_without_repository(&transport)
+        .get_issues(&repository(), &ids(&[12, 13, 14]))
+        .await;
+
+    assert_one_missing(&results);
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Post);
+    assert_eq!(requests[0].path, GRAPHQL_PATH);
+}
+
+#[tokio::test]
+async fn test_get_issues_failed_chunk_fails_only_its_ids() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let all: Vec<u64> = (1..=u64::try_from(ISSUE_BATCH_SIZE).unwrap() + 1).collect();
+    let first_chunk: serde_json::Map<String, JsonValue> = all[..ISSUE_BATCH_SIZE]
+        .iter()
+        .enumerate()
+        .map(|(index, number)| (alias(index), issue(*number, "OPEN")))
+        .collect();
+    transport.push_json(200, json!({ "data": { "repository": first_chunk } }));
+    transport.push(Ok(RestResponse {
+        status: 502,
+        headers: Vec::new(),
+        body: JsonValue::Null,
+    }));
+
+    let results = client_without_repository(&transport)
+        .get_issues(&repository(), &ids(&all))
+        .await;
+
+    assert_eq!(results.len(), all.len());
+    assert!(results[..ISSUE_BATCH_SIZE]
+        .iter()
+        .all(|(_, result)| result.is_ok()));
+    assert!(matches!(
+        results[ISSUE_BATCH_SIZE].1,
+        Err(GitHubOperationError::Transient { .. })
+    ));
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_get_issues_without_transport_fails_every_id() {
+    let client = GithubClient::new(Arc::new(()));
+
+    let results = client.get_issues(&repository(), &ids(&[12, 13])).await;
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|(_, result)| matches!(
+        result,
+        Err(GitHubOperationError::SdkCapabilityMissing { .. })
+    )));
+}
@@ -0,0 +1,148 @@
+//! One-request read of a work item for state reconstruction.
+//!
+//! State reconstruction needs an issue's title, body, labels, milestone, and
+//! assignees together. [`GithubClient::get_issue_snapshot`] reads them with a
+//! single `GET /repos/{owner}/{repo}/issues/{number}` request and
+//! [`parse_issue_snapshot`] maps the response onto [`IssueSnapshot`].
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Issue snapshot.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::instrument;
+
+use pipeline::{
+    github::{GitHubOperationError, IssueSnapshot, IssueState, Label},
+    MilestoneId, RepositoryId, WorkItemId,
+};
+
+use crate::{rate_limited::status_error, transport::RestRequest, GithubClient};
+
+/// Path prefix of `repository_url` in REST issue responses.
+const REPOS_PATH: &str = "/repos/";
+
+#[derive(Deserialize)]
+struct WireIssue {
+    number: u64,
+    repository_url: String,
+    title: String,
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    labels: Vec<WireLabel>,
+    milestone: Option<WireMilestone>,
+    #[serde(default)]
+    assignees: Vec<WireUser>,
+    updated_at: DateTime<Utc>,
+    /// Present only when the "issue" is a pull request.
+    pull_request: Option<JsonValue>,
+}
+
+#[derive(Deserialize)]
+struct WireLabel {
+    name: String,
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WireMilestone {
+    number: u64,
+}
+
+#[derive(Deserialize)]
+struct WireUser {
+    login: String,
+}
+
+/// Maps a REST issue response onto an [`IssueSnapshot`].
+///
+/// The repository is taken from the response's `repository_url`. The
+/// milestone is identified by its repository-scoped `number`, the value the
+/// milestone endpoints accept.
+///
+/// # Errors
+///
+/// - [`GitHubOperationError::NotFound`] — the number belongs to a pull
+///   request, not an issue.
+/// - [`GitHubOperationError::ParseFailure`] — the response does not have the
+///   expected shape, or `state` or `repository_url` is unrecognised.
+pub fn parse_issue_snapshot(response: &JsonValue) -> Result<IssueSnapshot, GitHubOperationError> {
+    let parse_failure = |message: String| GitHubOperationError::ParseFailure { message };
+    let issue: WireIssue = serde_json::from_value(response.clone())
+        .map_err(|e| parse_failure(format!("issue: {e}")))?;
+
+    if issue.pull_request.is_some() {
+        return Err(GitHubOperationError::NotFound {
+            resource: format!("issue #{} (it is a pull request)", issue.number),
+        });
+    }
+    let state = match issue.state.as_str() {
+        "open" => IssueState::Open,
+        "closed" => IssueState::Closed,
+        other => return Err(parse_failure(format!("issue: unknown state '{other}'"))),
+    };
+    let repository = issue
+        .repository_url
+        .split_once(REPOS_PATH)
+        .and_then(|(_, name)| RepositoryId::new(name.trim_end_matches('/')))
+        .ok_or_else(|| {
+            parse_failure(format!(
+                "issue: unrecognised repository_url '{}'",
+                issue.repository_url
+            ))
+        })?;
+
+    Ok(IssueSnapshot {
+        id: WorkItemId::new(issue.number),
+        repository,
+        title: issue.title,
+        body: issue.body.unwrap_or_default(),
+        state,
+        labels: issue
+            .labels
+            .into_iter()
+            .map(|label| Label {
+                name: label.name,
+                color: label.color,
+            })
+            .collect(),
+        milestone: issue
+            .milestone
+            .map(|milestone| MilestoneId::new(milestone.number)),
+        assignees: issue.assignees.into_iter().map(|user| user.login).collect(),
+        updated_at: issue.updated_at,
+    })
+}
+
+impl GithubClient {
+    /// Read the title, body, state, labels, milestone, and assignees of
+    /// `work_item` in one request.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — the issue does not exist or is a
+    ///   pull request.
+    /// - [`GitHubOperationError::ParseFailure`] — see [`parse_issue_snapshot`].
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   repository or transport.
+    #[instrument(skip(self))]
+    pub async fn get_issue_snapshot(
+        &self,
+        work_item: WorkItemId,
+    ) -> Result<IssueSnapshot, GitHubOperationError> {
+        let response = self
+            .send(RestRequest::get(self.issue_path(work_item)?))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("issue #{work_item}")) {
+            return Err(error);
+        }
+        parse_issue_snapshot(&response.body)
+    }
+}
+
+#[cfg(test)]
+#[path = "issue_snapshot_tests.rs"]
+mod tests;
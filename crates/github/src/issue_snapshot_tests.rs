@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::{
+    test_support::{client, repository},
+    transport::{RestMethod, ScriptedTransport},
+};
+
+use super::*;
+
+/// A recorded `GET /repos/octo/widgets/issues/42` response.
+fn issue_response() -> JsonValue {
+    json!({
+        "url": "https://api.github.com/repos/octo/widgets/issues/42",
+        "repository_url": "https://api.github.com/repos/octo/widgets",
+        "number": 42,
+        "title": "Add dark mode",
+        "body": "The settings page should offer a dark theme.",
+        "state": "open",
+        "user": { "login": "alice" },
+        "labels": [
+            { "id": 1, "name": "cogworks:run", "color": "0e8a16" },
+            { "id": 2, "name": "ui", "color": null }
+        ],
+        "milestone": { "id": 990011, "number": 3, "title": "v1.2" },
+        "assignees": [{ "login": "alice" }, { "login": "bob" }],
+        "created_at": "2026-03-01T10:00:00Z",
+        "updated_at": "2026-03-02T12:30:00Z"
+    })
+}
+
+// ─── parse_issue_snapshot ───────────────────────────────────────────────────
+
+#[test]
+fn test_parse_issue_snapshot_recorded_response_maps_all_fields() {
+    let snapshot = parse_issue_snapshot(&issue_response()).unwrap();
+
+    assert_eq!(snapshot.id, WorkItemId::new(42));
+    assert_eq!(snapshot.repository, repository());
+    assert_eq!(snapshot.title, "Add dark mode");
+    assert_eq!(
+        snapshot.body,
+        "The settings page should offer a dark theme."
+    );
+    assert_eq!(snapshot.state, IssueState::Open);
+    assert_eq!(
+        snapshot.labels,
+        vec![
+            Label {
+                name: "cogworks:run".to_string(),
+                color: Some("0e8a16".to_string())
+            },
+            Label {
+                name: "ui".to_string(),
+                color: None
+            },
+        ]
+    );
+    assert_eq!(snapshot.milestone, Some(MilestoneId::new(3)));
+    assert_eq!(
+        snapshot.assignees,
+        vec!["alice".to_string(), "bob".to_string()]
+    );
+    assert_eq!(
+        snapshot.updated_at.to_rfc3339(),
+        "2026-03-02T12:30:00+00:00"
+    );
+}
+
+#[test]
+fn test_parse_issue_snapshot_null_body_and_milestone_become_empty() {
+    let mut response = issue_response();
+    response["body"] = JsonValue::Null;
+    response["milestone"] = JsonValue::Null;
+    response["state"] = json!("closed");
+
+    let snapshot = parse_issue_snapshot(&response).unwrap();
+
+    assert_eq!(snapshot.body, "");
+    assert_eq!(snapshot.milestone, None);
+    assert_eq!(snapshot.state, IssueState::Closed);
+}
+
+#[test]
+fn test_parse_issue_snapshot_missing_labels_and_assignees_default_to_empty() {
+    let mut response = issue_response();
+    let object = response.as_object_mut().unwrap();
+    object.remove("labels");
+    object.remove("assignees");
+
+    let snapshot = parse_issue_snapshot(&response).unwrap();
+
+    assert!(snapshot.labels.is_empty());
+    assert!(snapshot.assignees.is_empty());
+}
+
+#[test]
+fn test_parse_issue_snapshot_pull_request_returns_not_found() {
+    let mut response = issue_response();
+    response["pull_request"] =
+        json!({ "url": "https://api.github.com/repos/octo/widgets/pulls/42" });
+
+    let result = parse_issue_snapshot(&response);
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[test]
+fn test_parse_issue_snapshot_unknown_state_returns_parse_failure() {
+    let mut response = issue_response();
+    response["state"] = json!("archived");
+
+    let result = parse_issue_snapshot(&response);
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_parse_issue_snapshot_unrecognised_repository_url_returns_parse_failure() {
+    let mut response = issue_response();
+    response["repository_url"] = json!("https://example.com/octo/widgets");
+
+    let result = parse_issue_snapshot(&response);
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_parse_issue_snapshot_missing_title_returns_parse_failure() {
+    let mut response = issue_response();
+    response.as_object_mut().unwrap().remove("title");
+
+    let result = parse_issue_snapshot(&response);
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+// ─── GithubClient::get_issue_snapshot ───────────────────────────────────────
+
+#[tokio::test]
+async fn test_get_issue_snapshot_sends_one_issue_request() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, issue_response());
+
+    let snapshot = client(&transport)
+        .get_issue_snapshot(WorkItemId::new(42))
+        .await
+        .unwrap();
+
+    assert_eq!(snapshot.id, WorkItemId::new(42));
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Get);
+    assert_eq!(requests[0].path, "/repos/octo/widgets/issues/42");
+}
+
+#[tokio::test]
+async fn test_get_issue_snapshot_missing_issue_returns_not_found() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client(&transport)
+        .get_issue_snapshot(WorkItemId::new(42))
+        .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_get_issue_snapshot_without_repository_returns_capability_missing() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let client = GithubClient::new(Arc::new(())).with_transport(Arc::clone(&transport) as _);
+
+    let result = client.get_issue_snapshot(WorkItemId::new(42)).await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::SdkCapabilityMissing { capability })
+            if capability == crate::transport::REPOSITORY_CAPABILITY
+    ));
+    assert!(transport.requests().is_empty());
+}
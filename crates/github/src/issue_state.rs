@@ -0,0 +1,76 @@
+//! Issue close and reopen requests.
+//!
+//! The issues API closes and reopens through `PATCH /repos/{o}/{r}/issues/{n}`
+//! with a `state` and a `state_reason`. [`issue_state_body`] builds that body
+//! from the domain [`IssueState`] and [`IssueStateReason`]: a closed issue
+//! records why it was closed, and a reopened one always records `reopened`.
+//! [`GithubClient::patch_issue_state`] sends it for
+//! [`IssueTracker::set_issue_state`](pipeline::IssueTracker::set_issue_state).
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §IssueTracker.
+
+use serde_json::{json, Value as JsonValue};
+use tracing::instrument;
+
+use pipeline::{
+    github::{GitHubOperationError, IssueState, IssueStateReason},
+    WorkItemId,
+};
+
+use crate::{rate_limited::status_error, transport::RestRequest, GithubClient};
+
+/// `state_reason` GitHub records when an issue is reopened.
+pub const REOPENED_STATE_REASON: &str = "reopened";
+
+/// Builds the `PATCH` body that moves an issue to `state`.
+///
+/// `reason` is sent when closing and ignored when reopening.
+pub fn issue_state_body(state: IssueState, reason: IssueStateReason) -> JsonValue {
+    match state {
+        IssueState::Open => json!({
+            "state": "open",
+            "state_reason": REOPENED_STATE_REASON,
+        }),
+        IssueState::Closed => json!({
+            "state": "closed",
+            "state_reason": reason.as_str(),
+        }),
+    }
+}
+
+impl GithubClient {
+    /// Moves `work_item` to `state`, recording `reason` when closing.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — the issue does not exist.
+    /// - [`GitHubOperationError::PermissionDenied`] — the installation may
+    ///   not edit issues.
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   repository or transport.
+    #[instrument(skip(self))]
+    pub(crate) async fn patch_issue_state(
+        &self,
+        work_item: WorkItemId,
+        state: IssueState,
+        reason: IssueStateReason,
+    ) -> Result<(), GitHubOperationError> {
+        let response = self
+            .send(RestRequest::patch(
+                self.issue_path(work_item)?,
+                issue_state_body(state, reason),
+            ))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("issue #{work_item}")) {
+            return Err(error);
+        }
+        tracing::info!(?state, reason = reason.as_str(), "issue state changed");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "issue_state_tests.rs"]
+mod tests;
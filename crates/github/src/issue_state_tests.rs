@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use crate::{
+    test_support::client,
+    transport::{RestMethod, ScriptedTransport},
+};
+
+use super::*;
+
+// ─── issue_state_body ───────────────────────────────────────────────────────
+
+#[test]
+fn test_issue_state_body_closed_completed_sends_completed_reason() {
+    assert_eq!(
+        issue_state_body(IssueState::Closed, IssueStateReason::Completed),
+        json!({ "state": "closed", "state_reason": "completed" })
+    );
+}
+
+#[test]
+fn test_issue_state_body_closed_not_planned_sends_not_planned_reason() {
+    assert_eq!(
+        issue_state_body(IssueState::Closed, IssueStateReason::NotPlanned),
+        json!({ "state": "closed", "state_reason": "not_planned" })
+    );
+}
+
+#[test]
+fn test_issue_state_body_open_sends_reopened_whatever_the_reason() {
+    for reason in [IssueStateReason::Completed, IssueStateReason::NotPlanned] {
+        assert_eq!(
+            issue_state_body(IssueState::Open, reason),
+            json!({ "state": "open", "state_reason": REOPENED_STATE_REASON })
+        );
+    }
+}
+
+// ─── patch_issue_state ──────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_patch_issue_state_close_completed_patches_issue() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, json!({ "number": 42, "state": "closed" }));
+
+    client(&transport)
+        .patch_issue_state(
+            WorkItemId::new(42),
+            IssueState::Closed,
+            IssueStateReason::Completed,
+        )
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Patch);
+    assert_eq!(requests[0].path, "/repos/octo/widgets/issues/42");
+    assert_eq!(
+        requests[0].body,
+        Some(json!({ "state": "closed", "state_reason": "completed" }))
+    );
+}
+
+#[tokio::test]
+async fn test_patch_issue_state_close_not_planned_sends_not_planned() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, json!({ "number": 42, "state": "closed" }));
+
+    client(&transport)
+        .patch_issue_state(
+            WorkItemId::new(42),
+            IssueState::Closed,
+            IssueStateReason::NotPlanned,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        transport.requests()[0].body,
+        Some(json!({ "state": "closed", "state_reason": "not_planned" }))
+    );
+}
+
+#[tokio::test]
+async fn test_patch_issue_state_reopen_sends_reopened() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, json!({ "number": 42, "state": "open" }));
+
+    client(&transport)
+        .patch_issue_state(
+            WorkItemId::new(42),
+            IssueState::Open,
+            IssueStateReason::Completed,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        transport.requests()[0].body,
+        Some(json!({ "state": "open", "state_reason": "reopened" }))
+    );
+}
+
+#[tokio::test]
+async fn test_patch_issue_state_missing_issue_returns_not_found() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client(&transport)
+        .patch_issue_state(
+            WorkItemId::new(42),
+            IssueState::Closed,
+            IssueStateReason::Completed,
+        )
+        .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_patch_issue_state_without_repository_returns_capability_missing() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let client = GithubClient::new(Arc::new(())).with_transport(Arc::clone(&transport) as _);
+
+    let result = client
+        .patch_issue_state(
+            WorkItemId::new(42),
+            IssueState::Closed,
+            IssueStateReason::Completed,
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::SdkCapabilityMissing { .. })
+    ));
+    assert!(transport.requests().is_empty());
+}
@@ -0,0 +1,340 @@
+//! Full issue reads for [`IssueTracker::get_issue`](pipeline::IssueTracker::get_issue)
+//! and [`IssueTracker::list_issues`](pipeline::IssueTracker::list_issues).
+//!
+//! `IssueTracker::get_issue` reads `GET /repos/{owner}/{repo}/issues/{number}`.
+//! [`issue_response_error`] classifies a non-success response (recording any
+//! rate-limit headers on the way) and [`parse_issue`] maps a success body onto
+//! [`Issue`], including the milestone's title and due date.
+//!
+//! `IssueTracker::list_issues` reads `GET /repos/{owner}/{repo}/issues` in
+//! pages of [`ISSUES_PER_PAGE`]. [`collect_issues`] follows the `Link` header's
+//! `rel="next"` entry until it is absent, failing with
+//! [`GitHubOperationError::PaginationLimitExceeded`] rather than reading past
+//! the client's page cap.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §IssueTracker.
+
+use std::future::Future;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+
+use pipeline::{
+    github::{GitHubOperationError, Issue, IssueFilter, IssueState, Label, Milestone},
+    MilestoneId, RepositoryId, WorkItemId,
+};
+
+use crate::{
+    pr_files::{has_next_page, LINK_HEADER},
+    rate_limit::{EndpointClass, RateLimitTracker},
+    rate_limited::status_error,
+    GithubClient,
+};
+
+/// Path prefix of `repository_url` in REST issue responses.
+const REPOS_PATH: &str = "/repos/";
+
+/// Issues requested per page (the API maximum).
+pub const ISSUES_PER_PAGE: u32 = 100;
+
+/// Pages [`GithubClient::list_issues`](pipeline::IssueTracker::list_issues)
+/// reads before failing with
+/// [`GitHubOperationError::PaginationLimitExceeded`] (10 000 issues).
+pub const DEFAULT_MAX_ISSUE_PAGES: u32 = 100;
+
+/// One page of [`collect_issues`] input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssuesPage {
+    /// The page's JSON array of issue objects.
+    pub body: JsonValue,
+    /// The response's `Link` header, if any.
+    pub link: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WireIssue {
+    number: u64,
+    repository_url: String,
+    title: String,
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    labels: Vec<WireLabel>,
+    milestone: Option<WireMilestone>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    /// Present only when the "issue" is a pull request.
+    pull_request: Option<JsonValue>,
+}
+
+#[derive(Deserialize)]
+struct WireLabel {
+    name: String,
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WireMilestone {
+    number: u64,
+    title: String,
+    due_on: Option<DateTime<Utc>>,
+}
+
+/// Maps the status and headers of an issue response to the error to surface,
+/// or `None` for a 2xx response.
+///
+/// The headers are passed to [`RateLimitTracker::observe`] first, so a 403 or
+/// 429 rate-limit rejection becomes [`GitHubOperationError::RateLimitExhausted`]
+/// with the `x-ratelimit-reset` (or `Retry-After`) time, which
+/// [`GitHubOperationError::retry_policy`] turns into
+/// `RetryPolicy::Retryable { after }`. Otherwise:
+///
+/// | Status | Error |
+/// |---|---|
+/// | 404, 410 | [`GitHubOperationError::NotFound`] |
+/// | 401, 403 | [`GitHubOperationError::PermissionDenied`] |
+/// | 5xx | [`GitHubOperationError::Transient`] |
+/// | other | [`GitHubOperationError::ParseFailure`] |
+pub fn issue_response_error<'h>(
+    rate_limits: &RateLimitTracker,
+    id: WorkItemId,
+    status: u16,
+    now: DateTime<Utc>,
+    header: impl Fn(&str) -> Option<&'h str>,
+) -> Option<GitHubOperationError> {
+    let throttled = rate_limits.observe(EndpointClass::Core, status, now, header);
+    if (200..300).contains(&status) {
+        return None;
+    }
+    Some(throttled.unwrap_or_else(|| match status {
+        404 | 410 => GitHubOperationError::NotFound {
+            resource: format!("issue #{id}"),
+        },
+        401 | 403 => GitHubOperationError::PermissionDenied {
+            action: format!("read issue #{id}"),
+        },
+        500..=599 => GitHubOperationError::Transient {
+            message: format!("reading issue #{id} returned HTTP {status}"),
+        },
+        _ => GitHubOperationError::ParseFailure {
+            message: format!("issue #{id}: unexpected HTTP {status}"),
+        },
+    }))
+}
+
+/// Maps a REST issue response onto an [`Issue`].
+///
+/// The repository is taken from the response's `repository_url`; the
+/// milestone keeps its repository-scoped `number`, title, and due date.
+///
+/// # Errors
+///
+/// - [`GitHubOperationError::NotFound`] — the number belongs to a pull
+///   request, not an issue.
+/// - [`GitHubOperationError::ParseFailure`] — the response does not have the
+///   expected shape, or `state` or `repository_url` is unrecognised.
+pub fn parse_issue(response: &JsonValue) -> Result<Issue, GitHubOperationError> {
+    let parse_failure = |message: String| GitHubOperationError::ParseFailure { message };
+    let issue: WireIssue = serde_json::from_value(response.clone())
+        .map_err(|e| parse_failure(format!("issue: {e}")))?;
+
+    if issue.pull_request.is_some() {
+        return Err(GitHubOperationError::NotFound {
+            resource: format!("issue #{} (it is a pull request)", issue.number),
+        });
+    }
+    let state = match issue.state.as_str() {
+        "open" => IssueState::Open,
+        "closed" => IssueState::Closed,
+        other => return Err(parse_failure(format!("issue: unknown state '{other}'"))),
+    };
+    let repository = issue
+        .repository_url
+        .split_once(REPOS_PATH)
+        .and_then(|(_, name)| RepositoryId::new(name.trim_end_matches('/')))
+        .ok_or_else(|| {
+            parse_failure(format!(
+                "issue: unrecognised repository_url '{}'",
+                issue.repository_url
+            ))
+        })?;
+
+    Ok(Issue {
+        id: WorkItemId::new(issue.number),
+        repository,
+        title: issue.title,
+        body: issue.body.unwrap_or_default(),
+        state,
+        labels: issue
+            .labels
+            .into_iter()
+            .map(|label| Label {
+                name: label.name,
+                color: label.color,
+            })
+            .collect(),
+        milestone: issue.milestone.map(|milestone| Milestone {
+            id: MilestoneId::new(milestone.number),
+            title: milestone.title,
+            due_on: milestone.due_on,
+        }),
+        created_at: issue.created_at,
+        updated_at: issue.updated_at,
+    })
+}
+
+/// Returns the request path for `page` (1-based) of the issues of
+/// `repository` that match `filter`.
+pub fn issues_path(repository: &RepositoryId, filter: &IssueFilter, page: u32) -> String {
+    let mut path = format!(
+        "/repos/{}/{}/issues?per_page={ISSUES_PER_PAGE}&page={page}",
+        repository.owner(),
+        repository.repo()
+    );
+    if let Some(state) = filter.state {
+        path.push_str("&state=");
+        path.push_str(state.as_str());
+    }
+    if !filter.labels.is_empty() {
+        path.push_str("&labels=");
+        path.push_str(&encode_query_value(&filter.labels.join(",")));
+    }
+    if let Some(since) = filter.since {
+        path.push_str("&since=");
+        path.push_str(&encode_query_value(
+            &since
+                .as_datetime()
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+        ));
+    }
+    path
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters and the `,`
+/// that separates label names.
+pub(crate) fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~' | b',') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Maps one page of the issues listing onto [`Issue`]s.
+///
+/// The listing also returns pull requests; entries carrying `pull_request`
+/// are skipped rather than treated as errors.
+///
+/// # Errors
+///
+/// [`GitHubOperationError::ParseFailure`] — the page is not an array, or an
+/// entry fails [`parse_issue`].
+pub fn parse_issues_page(body: &JsonValue) -> Result<Vec<Issue>, GitHubOperationError> {
+    let entries = body
+        .as_array()
+        .ok_or_else(|| GitHubOperationError::ParseFailure {
+            message: "issues: expected an array".to_string(),
+        })?;
+    entries
+        .iter()
+        .filter(|entry| entry.get("pull_request").is_none())
+        .map(parse_issue)
+        .collect()
+}
+
+/// Reads pages from `fetch_page` (called with 1, 2, ...) until one has no
+/// next link, and returns all their issues in order.
+///
+/// # Errors
+///
+/// - [`GitHubOperationError::PaginationLimitExceeded`] — page `max_pages`
+///   still links a next page.
+/// - The first error from `fetch_page` or [`parse_issues_page`].
+pub async fn collect_issues<F, Fut>(
+    mut fetch_page: F,
+    max_pages: u32,
+) -> Result<Vec<Issue>, GitHubOperationError>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<IssuesPage, GitHubOperationError>>,
+{
+    let mut issues = Vec::new();
+    let mut page = 1;
+    loop {
+        let response = fetch_page(page).await?;
+        issues.extend(parse_issues_page(&response.body)?);
+        if !has_next_page(response.link.as_deref()) {
+            tracing::debug!(pages = page, issues = issues.len(), "listed issues");
+            return Ok(issues);
+        }
+        if page >= max_pages {
+            return Err(GitHubOperationError::PaginationLimitExceeded { max_pages });
+        }
+        page += 1;
+    }
+}
+
+impl GithubClient {
+    /// Reads issue `id` from the client's repository.
+    ///
+    /// The rate limiter is consulted before the request, which is conditional
+    /// when the ETag cache is enabled; the response's status and headers go
+    /// through [`issue_response_error`] and its body through [`parse_issue`].
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::RateLimitExhausted`] — the core budget is
+    ///   exhausted, before or by this request.
+    /// - Any error from [`issue_response_error`] or [`parse_issue`].
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   repository or no [transport](crate::transport).
+    pub(crate) async fn read_issue(&self, id: WorkItemId) -> Result<Issue, GitHubOperationError> {
+        self.rate_limits().check(EndpointClass::Core, Utc::now())?;
+        let response = self
+            .conditional_get_unmetered(&self.issue_path(id)?)
+            .await?;
+        if let Some(error) = issue_response_error(
+            self.rate_limits(),
+            id,
+            response.status,
+            Utc::now(),
+            |name| response.header(name),
+        ) {
+            return Err(error);
+        }
+        parse_issue(&response.body)
+    }
+
+    /// Reads one page of the issues of `repository` that match `filter`.
+    ///
+    /// Goes through [`GithubClient::conditional_get`], so a re-read of an
+    /// unchanged page is answered from the ETag cache when it is enabled.
+    pub(crate) async fn read_issues_page(
+        &self,
+        repository: &RepositoryId,
+        filter: &IssueFilter,
+        page: u32,
+    ) -> Result<IssuesPage, GitHubOperationError> {
+        let response = self
+            .conditional_get(&issues_path(repository, filter, page))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("issues of {repository}")) {
+            return Err(error);
+        }
+        Ok(IssuesPage {
+            link: response.header(LINK_HEADER).map(str::to_string),
+            body: response.body,
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "issues_tests.rs"]
+mod tests;
@@ -0,0 +1,445 @@
+use std::{cell::RefCell, sync::Arc};
+
+use chrono::{TimeDelta, TimeZone};
+use pipeline::{github::IssueStateFilter, IssueTracker, RetryPolicy, Timestamp};
+use serde_json::json;
+
+use crate::{
+    rate_limited::RestResponse,
+    test_support::{client, repository},
+    transport::{RestMethod, ScriptedTransport},
+};
+
+use super::*;
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap()
+}
+
+fn headers<'h>(pairs: &'h [(&'h str, &'h str)]) -> impl Fn(&str) -> Option<&'h str> {
+    move |name| {
+        pairs
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+}
+
+/// A recorded `GET /repos/octo/widgets/issues/42` response.
+fn issue_response() -> JsonValue {
+    json!({
+        "url": "https://api.github.com/repos/octo/widgets/issues/42",
+        "repository_url": "https://api.github.com/repos/octo/widgets",
+        "number": 42,
+        "title": "Add dark mode",
+        "body": "The settings page should offer a dark theme.",
+        "state": "open",
+        "labels": [
+            { "id": 1, "name": "cogworks:run", "color": "0e8a16" },
+            { "id": 2, "name": "ui", "color": null }
+        ],
+        "milestone": {
+            "id": 990011,
+            "number": 3,
+            "title": "v1.2",
+            "due_on": "2026-04-01T07:00:00Z"
+        },
+        "created_at": "2026-03-01T10:00:00Z",
+        "updated_at": "2026-03-02T12:30:00Z"
+    })
+}
+
+// ─── parse_issue ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_issue_recorded_response_maps_all_fields() {
+    let issue = parse_issue(&issue_response()).unwrap();
+
+    assert_eq!(issue.id, WorkItemId::new(42));
+    assert_eq!(issue.repository, repository());
+    assert_eq!(issue.title, "Add dark mode");
+    assert_eq!(issue.body, "The settings page should offer a dark theme.");
+    assert_eq!(issue.state, IssueState::Open);
+    assert_eq!(
+        issue.labels,
+        vec![
+            Label {
+                name: "cogworks:run".to_string(),
+                color: Some("0e8a16".to_string()),
+            },
+            Label {
+                name: "ui".to_string(),
+                color: None,
+            },
+        ]
+    );
+    let milestone = issue.milestone.unwrap();
+    assert_eq!(milestone.id, MilestoneId::new(3));
+    assert_eq!(milestone.title, "v1.2");
+    assert!(milestone.due_on.is_some());
+}
+
+#[test]
+fn test_parse_issue_null_body_and_milestone_become_empty() {
+    let mut response = issue_response();
+    response["body"] = JsonValue::Null;
+    response["milestone"] = JsonValue::Null;
+
+    let issue = parse_issue(&response).unwrap();
+
+    assert_eq!(issue.body, "");
+    assert_eq!(issue.milestone, None);
+}
+
+#[test]
+fn test_parse_issue_pull_request_returns_not_found() {
+    let mut response = issue_response();
+    response["pull_request"] =
+        json!({ "url": "https://api.github.com/repos/octo/widgets/pulls/42" });
+
+    assert!(matches!(
+        parse_issue(&response),
+        Err(GitHubOperationError::NotFound { .. })
+    ));
+}
+
+#[test]
+fn test_parse_issue_unknown_state_returns_parse_failure() {
+    let mut response = issue_response();
+    response["state"] = json!("draft");
+
+    assert!(matches!(
+        parse_issue(&response),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_parse_issue_unrecognised_repository_url_returns_parse_failure() {
+    let mut response = issue_response();
+    response["repository_url"] = json!("https://example.com/widgets");
+
+    assert!(matches!(
+        parse_issue(&response),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+// ─── issue_response_error ───────────────────────────────────────────────────
+
+#[test]
+fn test_issue_response_error_success_returns_none() {
+    let tracker = RateLimitTracker::default();
+
+    let error = issue_response_error(&tracker, WorkItemId::new(42), 200, now(), headers(&[]));
+
+    assert!(error.is_none());
+}
+
+#[test]
+fn test_issue_response_error_not_found_returns_not_found() {
+    let tracker = RateLimitTracker::default();
+
+    let error = issue_response_error(&tracker, WorkItemId::new(42), 404, now(), headers(&[]));
+
+    assert!(matches!(
+        error,
+        Some(GitHubOperationError::NotFound { resource }) if resource == "issue #42"
+    ));
+}
+
+#[test]
+fn test_issue_response_error_rate_limited_403_returns_retryable_at_reset() {
+    let tracker = RateLimitTracker::default();
+    let reset_at = now() + TimeDelta::seconds(90);
+    let reset = reset_at.timestamp().to_string();
+    let pairs = [
+        ("x-ratelimit-remaining", "0"),
+        ("x-ratelimit-reset", reset.as_str()),
+    ];
+
+    let error =
+        issue_response_error(&tracker, WorkItemId::new(42), 403, now(), headers(&pairs)).unwrap();
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::RateLimitExhausted { reset_at: r } if r == reset_at
+    ));
+    assert!(matches!(
+        error.retry_policy(),
+        RetryPolicy::Retryable { .. }
+    ));
+    assert!(tracker.check(EndpointClass::Core, now()).is_err());
+}
+
+#[test]
+fn test_issue_response_error_plain_403_returns_permission_denied() {
+    let tracker = RateLimitTracker::default();
+
+    let error = issue_response_error(&tracker, WorkItemId::new(42), 403, now(), headers(&[]));
+
+    assert!(matches!(
+        error,
+        Some(GitHubOperationError::PermissionDenied { .. })
+    ));
+}
+
+#[test]
+fn test_issue_response_error_server_error_returns_transient() {
+    let tracker = RateLimitTracker::default();
+
+    let error = issue_response_error(&tracker, WorkItemId::new(42), 502, now(), headers(&[]));
+
+    assert!(matches!(
+        error,
+        Some(GitHubOperationError::Transient { .. })
+    ));
+}
+
+// ─── issues_path ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_issues_path_default_filter_requests_full_pages_only() {
+    assert_eq!(
+        issues_path(&repository(), &IssueFilter::default(), 1),
+        "/repos/octo/widgets/issues?per_page=100&page=1"
+    );
+}
+
+#[test]
+fn test_issues_path_full_filter_encodes_state_labels_and_since() {
+    let filter = IssueFilter {
+        state: Some(IssueStateFilter::All),
+        labels: vec!["cogworks:run".to_string(), "good first issue".to_string()],
+        since: Some(Timestamp::from_utc(
+            Utc.with_ymd_and_hms(2026, 3, 1, 10, 0, 0).unwrap(),
+        )),
+    };
+
+    assert_eq!(
+        issues_path(&repository(), &filter, 2),
+        "/repos/octo/widgets/issues?per_page=100&page=2&state=all\
+         &labels=cogworks%3Arun,good%20first%20issue&since=2026-03-01T10%3A00%3A00Z"
+    );
+}
+
+// ─── parse_issues_page ──────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_issues_page_pull_requests_are_skipped() {
+    let mut pull_request = issue_response();
+    pull_request["number"] = json!(43);
+    pull_request["pull_request"] =
+        json!({ "url": "https://api.github.com/repos/octo/widgets/pulls/43" });
+
+    let issues = parse_issues_page(&json!([issue_response(), pull_request])).unwrap();
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].id, WorkItemId::new(42));
+}
+
+#[test]
+fn test_parse_issues_page_not_an_array_returns_parse_failure() {
+    assert!(matches!(
+        parse_issues_page(&json!({ "message": "Not Found" })),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+// ─── collect_issues ─────────────────────────────────────────────────────────
+
+fn issue_with_number(number: u64) -> JsonValue {
+    let mut issue = issue_response();
+    issue["number"] = json!(number);
+    issue
+}
+
+fn next_link(page: u32) -> Option<String> {
+    Some(format!(
+        "<https://api.github.com/repositories/1/issues?per_page=100&page={page}>; rel=\"next\""
+    ))
+}
+
+/// Three pages of a listing: two linked onward, the last with no next link.
+fn three_pages() -> Vec<IssuesPage> {
+    vec![
+        IssuesPage {
+            body: json!([issue_with_number(1), issue_with_number(2)]),
+            link: next_link(2),
+        },
+        IssuesPage {
+            body: json!([issue_with_number(3)]),
+            link: next_link(3),
+        },
+        IssuesPage {
+            body: json!([issue_with_number(4)]),
+            link: None,
+        },
+    ]
+}
+
+#[tokio::test]
+async fn test_collect_issues_three_pages_returns_every_issue_in_order() {
+    let pages = RefCell::new(three_pages().into_iter());
+    let requested = RefCell::new(Vec::new());
+
+    let issues = collect_issues(
+        |page| {
+            requested.borrow_mut().push(page);
+            let next = pages.borrow_mut().next();
+            async move { Ok(next.unwrap()) }
+        },
+        DEFAULT_MAX_ISSUE_PAGES,
+    )
+    .await
+    .unwrap();
+
+    let numbers: Vec<u64> = issues.iter().map(|issue| issue.id.as_u64()).collect();
+    assert_eq!(numbers, vec![1, 2, 3, 4]);
+    assert_eq!(*requested.borrow(), vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_collect_issues_cap_reached_with_next_link_returns_pagination_limit() {
+    let pages = RefCell::new(three_pages().into_iter());
+
+    let result = collect_issues(
+        |_| {
+            let next = pages.borrow_mut().next();
+            async move { Ok(next.unwrap()) }
+        },
+        2,
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::PaginationLimitExceeded { max_pages: 2 })
+    ));
+}
+
+#[tokio::test]
+async fn test_collect_issues_last_page_at_cap_returns_issues() {
+    let pages = RefCell::new(three_pages().into_iter());
+
+    let issues = collect_issues(
+        |_| {
+            let next = pages.borrow_mut().next();
+            async move { Ok(next.unwrap()) }
+        },
+        3,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(issues.len(), 4);
+}
+
+#[tokio::test]
+async fn test_collect_issues_page_error_returns_error() {
+    let result = collect_issues(
+        |page| async move {
+            if page == 1 {
+                Ok(three_pages().remove(0))
+            } else {
+                Err(GitHubOperationError::Transient {
+                    message: "connection reset".to_string(),
+                })
+            }
+        },
+        DEFAULT_MAX_ISSUE_PAGES,
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::Transient { .. })
+    ));
+}
+
+// ─── IssueTracker::get_issue ────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_get_issue_success_reads_issue_path() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, issue_response());
+
+    let issue = client(&transport)
+        .get_issue(WorkItemId::new(42))
+        .await
+        .unwrap();
+
+    assert_eq!(issue.title, "Add dark mode");
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Get);
+    assert_eq!(requests[0].path, "/repos/octo/widgets/issues/42");
+}
+
+#[tokio::test]
+async fn test_get_issue_missing_issue_returns_not_found() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client(&transport).get_issue(WorkItemId::new(7)).await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_get_issue_rate_limited_returns_exhausted_and_blocks_next_call() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let reset_at = Utc::now() + TimeDelta::minutes(10);
+    transport.push(Ok(RestResponse {
+        status: 403,
+        headers: vec![
+            ("x-ratelimit-remaining".to_string(), "0".to_string()),
+            (
+                "x-ratelimit-reset".to_string(),
+                reset_at.timestamp().to_string(),
+            ),
+        ],
+        body: json!({ "message": "API rate limit exceeded" }),
+    }));
+    let client = client(&transport);
+
+    let first = client.get_issue(WorkItemId::new(42)).await;
+    let second = client.get_issue(WorkItemId::new(42)).await;
+
+    assert!(matches!(
+        first,
+        Err(GitHubOperationError::RateLimitExhausted { .. })
+    ));
+    assert!(matches!(
+        second,
+        Err(GitHubOperationError::RateLimitExhausted { .. })
+    ));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_get_issue_repeated_read_not_modified_returns_cached_issue() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push(Ok(RestResponse {
+        status: 200,
+        headers: vec![("ETag".to_string(), r#""abc""#.to_string())],
+        body: issue_response(),
+    }));
+    transport.push(Ok(RestResponse {
+        status: 304,
+        headers: Vec::new(),
+        body: JsonValue::Null,
+    }));
+    let client = client(&transport).with_etag_cache(16);
+
+    let first = client.get_issue(WorkItemId::new(42)).await.unwrap();
+    let second = client.get_issue(WorkItemId::new(42)).await.unwrap();
+
+    assert_eq!(second, first);
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[1].header(crate::etag_cache::IF_NONE_MATCH_HEADER),
+        Some(r#""abc""#)
+    );
+}
@@ -25,12 +25,198 @@
 //! | `IssueTracker::add_typed_link` | GraphQL `issueLink` mutation |
 //! | `IssueTracker::get_typed_links` | GraphQL `issueLink` query |
 //! | `IssueTracker::set_milestone` | PATCH issue milestone field |
-//! | `PullRequestManager::find_pull_requests` | List PRs with filter params |
 //! | `PullRequestManager::post_review_comment` | Create inline PR review comment |
 //! | `CodeRepository::read_file` | GitHub Contents API |
 //! | `CodeRepository::list_directory` | GitHub Contents API |
 //! | `CodeRepository::file_exists` | GitHub Contents API HEAD check |
 //! | `CodeRepository::read_tree` | GitHub Trees API recursive |
+//! | `GithubClient::stream_pull_request_diff` | Raw response body streaming |
+//! | `GithubClient::stream_tree` | Raw response body streaming |
+//!
+//! ## REST Transport
+//!
+//! Requests leave the client through a [`transport::GitHubTransport`] set
+//! with [`GithubClient::with_transport`], after passing the rate limiter.
+//! A client without a transport returns `SdkCapabilityMissing` from every
+//! operation that needs the API.
+//!
+//! Trait methods that take only a [`pipeline::WorkItemId`] address issues in
+//! the repository set with [`GithubClient::with_repository`]; without one
+//! they return `SdkCapabilityMissing` (capability
+//! [`transport::REPOSITORY_CAPABILITY`]).
+//!
+//! ## Cross-reference Linking
+//!
+//! [`GithubClient::link_pr_to_issue`] (in [`linking`]) records the work item ↔
+//! pull request linkage on both sides: a closing keyword in the PR body and a
+//! reference comment on the issue.
+//!
+//! ## Streaming Readers
+//!
+//! [`streaming::DiffStream`] and [`streaming::TreeStream`] read large diffs and
+//! recursive trees incrementally, yielding one file or entry at a time under an
+//! overall byte cap.
+//!
+//! ## Retries
+//!
+//! Retried calls go through [`pipeline::retry::retry`], which derives the
+//! retry decision from [`GitHubOperationError::retry_policy`] and records
+//! attempt counts and total duration per operation.
+//!
+//! ## Rate Limits
+//!
+//! [`rate_limit::RateLimitTracker`] records core REST, search, and GraphQL
+//! limits separately so a throttle on one budget does not block the others.
+//! GraphQL queries also select their `rateLimit` point cost; the tracker
+//! throttles GraphQL once the remaining points drop below a reserve.
+//! [`rate_limited::RateLimitedClient`] sends every request through the
+//! tracker: it sleeps out a throttle shorter than a configurable ceiling,
+//! fails with `RateLimitExhausted` on a longer one, and turns
+//! abuse-detection responses into `RateLimitExhausted` too.
+//!
+//! ## Conditional Requests
+//!
+//! [`GithubClient::with_etag_cache`] enables an LRU cache of GET bodies keyed
+//! by URL ([`etag_cache::EtagCache`]). Cached URLs are re-read with
+//! `If-None-Match`; a `304 Not Modified` costs no primary rate-limit budget
+//! and returns the cached body.
+//!
+//! ## Commit Signing
+//!
+//! [`GithubClient::commit_files`] and [`GithubClient::amend_last_commit`]
+//! write commits through the Git Data API. A [`commits::CommitSigning`]
+//! option has GitHub sign them with the App's key or sends a locally made GPG
+//! or SSH signature; an unsigned commit to a branch that requires signatures
+//! is refused with `CommitSigningUnavailable` before anything is written.
+//!
+//! ## Environment Protection
+//!
+//! [`GithubClient::get_environment_protection`] reports the required reviewers
+//! and wait timer of a deployment environment so a merge into a gated flow can
+//! be held. It reads the environments endpoint through the
+//! [transport](transport).
+//!
+//! ## GraphQL Node IDs
+//!
+//! GraphQL paths identify objects by [`graphql::GraphQlNodeId`], never by the
+//! domain identifiers in [`pipeline`]; the mapping to domain types happens in
+//! each module's response parser. Errors in a GraphQL response's `errors`
+//! array are mapped by [`graphql::classify_error`] onto the same
+//! [`GitHubOperationError`] variants REST failures produce.
+//!
+//! ## Project Fields
+//!
+//! `ProjectBoard::set_item_fields` on [`GithubClient`] writes all of a card's
+//! Projects V2 field updates in one GraphQL request of aliased
+//! `updateProjectV2ItemFieldValue` mutations (see [`project_fields`]). The
+//! card is looked up on the board set with [`GithubClient::with_project`].
+//!
+//! ## Merge Readiness
+//!
+//! [`GithubClient::get_mergeability`] reads a pull request's mergeable state,
+//! re-reading it while GitHub is still computing it (state `unknown`) up to
+//! the attempts of [`mergeability::MergeabilityPolling`].
+//!
+//! ## Discussions
+//!
+//! [`GithubClient::get_discussion`] and [`GithubClient::post_discussion_comment`]
+//! use a GitHub Discussion as the work source instead of an issue, mapping the
+//! discussion onto [`pipeline::github::Issue`] and its comments onto
+//! [`pipeline::github::IssueComment`] (see [`discussions`]).
+//!
+//! ## Audit Replay
+//!
+//! [`GithubClient::read_events_filtered`] streams the audit events of one run
+//! that match a predicate (e.g. only LLM calls), reading the work item's
+//! comments a page at a time instead of loading the whole audit trail.
+//! [`GithubClient::append_event`] writes an audit comment carrying a hidden
+//! idempotency key and skips the write if a recent comment already has that
+//! key, so a retry after a transient failure does not log the event twice (see
+//! [`audit_append`]).
+//!
+//! ## Cleanup
+//!
+//! [`GithubClient::list_cogworks_prs`] lists the open PRs CogWorks opened
+//! (by bot login or [`cleanup::DEFAULT_BRANCH_PREFIX`] head branch) so stale
+//! ones can be closed. [`GithubClient::find_orphaned_branches`] lists the
+//! `cogworks/<work-item>/...` branches whose work item is closed and that no
+//! open PR uses.
+//!
+//! ## Comment Throttling
+//!
+//! [`GithubClient::upsert_comment_throttled`] writes a marker comment at most
+//! once per [`comment_throttle::CommentThrottle`] window, keeping only the
+//! latest content in between; [`GithubClient::flush_comments`] writes whatever
+//! is pending when a run completes.
+//!
+//! ## Issue Reads
+//!
+//! `IssueTracker::get_issue` maps the REST issue response onto
+//! [`pipeline::github::Issue`] with [`issues::parse_issue`]. Error responses
+//! go through [`issues::issue_response_error`]: 404 becomes `NotFound` and a
+//! rate-limit 403 becomes `RateLimitExhausted` at the `x-ratelimit-reset`
+//! time, so retries wait for the window to reset.
+//!
+//! `IssueTracker::list_issues` follows the issues endpoint's `Link` pagination
+//! through [`issues::collect_issues`], up to the page cap set by
+//! [`GithubClient::with_max_issue_pages`].
+//!
+//! ## Issue Comments
+//!
+//! `IssueTracker::list_comments` follows the comments endpoint's `Link`
+//! pagination through [`comments::collect_comments`];
+//! `IssueTracker::post_comment` and `IssueTracker::update_comment` write
+//! through the [transport](transport) (see [`comments`]).
+//!
+//! ## Issue Snapshot
+//!
+//! [`GithubClient::get_issue_snapshot`] reads an issue's title, body, labels,
+//! milestone, and assignees in one request for state reconstruction.
+//! [`GithubClient::get_issues`] reads many issues at once through aliased
+//! GraphQL fields, up to [`issue_batch::ISSUE_BATCH_SIZE`] per request, and
+//! returns a result per work item so one missing issue does not fail the
+//! rest.
+//!
+//! ## Milestone Info
+//!
+//! [`GithubClient::get_milestone_info`] reads a milestone's due date, state,
+//! and open/closed item counts so scheduling can weigh milestone urgency.
+//!
+//! ## Commit Status
+//!
+//! [`GithubClient::get_commit_status`] reads a commit's combined CI state and
+//! the latest status of each context so the executor can wait for CI to pass.
+//!
+//! ## Default Branch
+//!
+//! [`GithubClient::default_branch`] fetches a repository's default branch once
+//! and caches it for the rest of the run.
+//!
+//! ## Opening Pull Requests
+//!
+//! `PullRequestManager::create_pull_request` (in [`pull_requests`]) turns
+//! GitHub's 422 "no commits between" into `EmptyDiff` and "a pull request
+//! already exists" into `PullRequestAlreadyExists` carrying the existing PR's
+//! number, so the integration node can skip or reuse instead of failing.
+//!
+//! ## Auto-merge
+//!
+//! [`GithubClient::enable_auto_merge`] turns on GitHub auto-merge for a pull
+//! request with a chosen [`auto_merge::AutoMergeMethod`], via the GraphQL
+//! `enablePullRequestAutoMerge` mutation. A repository that does not allow
+//! auto-merge yields `AutoMergeDisabled` (see [`auto_merge`]).
+//!
+//! ## Pull Request Files
+//!
+//! `PullRequestManager::list_pr_files` follows the files endpoint's `Link`
+//! pagination through [`pr_files::collect_pr_files`] and returns every
+//! changed file with its status and line counts.
+//!
+//! ## Cargo Features
+//!
+//! | Feature | Enables |
+//! |---------|---------|
+//! | `mock-transport` | `transport::ScriptedTransport`, a test-only transport replaying queued responses |
 //!
 //! ## Architectural Layer
 //!
@@ -44,6 +230,37 @@
 //!
 //! *This crate is a skeleton. Method bodies are filled in during PR 10.*
 
+pub mod audit_append;
+pub mod audit_replay;
+pub mod auto_merge;
+pub mod cleanup;
+pub mod comment_throttle;
+pub mod comments;
+pub mod commit_status;
+pub mod commits;
+mod default_branch;
+pub mod discussions;
+mod environments;
+pub mod etag_cache;
+pub mod graphql;
+pub mod issue_batch;
+pub mod issue_snapshot;
+pub mod issue_state;
+pub mod issues;
+pub mod linking;
+pub mod mergeability;
+pub mod milestones;
+pub mod pr_files;
+pub mod project_fields;
+pub mod pull_requests;
+pub mod rate_limit;
+pub mod rate_limited;
+pub mod streaming;
+pub mod transport;
+
+#[cfg(test)]
+mod test_support;
+
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -53,11 +270,13 @@ use tracing::instrument;
 use pipeline::{
     audit::{AuditEvent, AuditStore, AuditStoreError, PipelineSummary},
     github::{
-        CodeRepository, DirectoryEntry, FileContent, GitHubOperationError, Issue, IssueState,
-        IssueTracker, Label, Milestone, ProjectBoard, PullRequest, PullRequestFilter,
-        PullRequestManager, ReviewStatus, SubIssue, TypedLink, TypedLinkKind,
+        ChangedFile, CodeRepository, DirectoryEntry, FileContent, GitHubOperationError, Issue,
+        IssueComment, IssueFilter, IssueState, IssueStateReason, IssueTracker, Label, Milestone,
+        ProjectBoard, ProjectFieldValue, PullRequest, PullRequestFilter, PullRequestManager,
+        ReviewStatus, SubIssue, TypedLink, TypedLinkKind,
     },
-    BranchName, CommitSha, MilestoneId, PipelineRunId, PullRequestId, RepositoryId, WorkItemId,
+    BranchName, CommentId, CommitSha, MilestoneId, PipelineRunId, PullRequestId, RepositoryId,
+    WorkItemId,
 };
 
 // ─── Client struct ───────────────────────────────────────────────────────────
@@ -81,8 +300,26 @@ use pipeline::{
 /// See `docs/spec/interfaces/github-traits.md` §GithubClient.
 pub struct GithubClient {
     // Internal SDK client and installation handle filled in during PR 10.
-    // Declared as `_private` to avoid unused-field warnings on the skeleton.
-    _private: (),
+    /// Run-scoped cache backing [`GithubClient::default_branch`].
+    default_branches: default_branch::DefaultBranchCache,
+    /// Per-endpoint-class throttle state shared by every request.
+    http: rate_limited::RateLimitedClient,
+    /// Coalesces frequent edits to marker comments.
+    comment_throttle: comment_throttle::CommentThrottle,
+    /// Page cap for `IssueTracker::list_issues`.
+    max_issue_pages: u32,
+    /// Conditional-GET cache; `None` unless enabled with
+    /// [`GithubClient::with_etag_cache`].
+    etag_cache: Option<etag_cache::EtagCache>,
+    /// Sends REST and GraphQL requests; `None` until set with
+    /// [`GithubClient::with_transport`].
+    transport: Option<Arc<dyn transport::GitHubTransport>>,
+    /// Repository that bare work-item IDs refer to; `None` until set with
+    /// [`GithubClient::with_repository`].
+    repository: Option<RepositoryId>,
+    /// Number of the Projects V2 board work items are tracked on; `None`
+    /// until set with [`GithubClient::with_project`].
+    project: Option<u64>,
 }
 
 /// Placeholder type for the SDK client until the real type is wired in.
@@ -97,7 +334,108 @@ impl GithubClient {
     ///
     /// `_sdk_client` is a placeholder — see [`SdkClientPlaceholder`].
     pub fn new(_sdk_client: SdkClientPlaceholder) -> Self {
-        Self { _private: () }
+        Self {
+            default_branches: default_branch::DefaultBranchCache::default(),
+            http: rate_limited::RateLimitedClient::default(),
+            comment_throttle: comment_throttle::CommentThrottle::default(),
+            max_issue_pages: issues::DEFAULT_MAX_ISSUE_PAGES,
+            etag_cache: None,
+            transport: None,
+            repository: None,
+            project: None,
+        }
+    }
+
+    /// Sets the transport requests are sent through.
+    ///
+    /// Without one, every operation that needs the API fails with
+    /// [`GitHubOperationError::SdkCapabilityMissing`].
+    #[must_use]
+    pub fn with_transport(mut self, transport: Arc<dyn transport::GitHubTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Sets the repository that work-item IDs refer to.
+    ///
+    /// Operations addressed only by a [`WorkItemId`] (issue numbers are
+    /// repository-scoped) fail with
+    /// [`GitHubOperationError::SdkCapabilityMissing`] until this is set.
+    #[must_use]
+    pub fn with_repository(mut self, repository: RepositoryId) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Sets the number of the Projects V2 board work items are tracked on.
+    ///
+    /// `ProjectBoard::set_item_fields` fails with
+    /// [`GitHubOperationError::SdkCapabilityMissing`] until this is set.
+    #[must_use]
+    pub fn with_project(mut self, number: u64) -> Self {
+        self.project = Some(number);
+        self
+    }
+
+    /// Sets the minimum interval between writes to the same marker comment.
+    ///
+    /// Defaults to [`comment_throttle::DEFAULT_COMMENT_THROTTLE_WINDOW`].
+    #[must_use]
+    pub fn with_comment_throttle_window(mut self, window: std::time::Duration) -> Self {
+        self.comment_throttle = comment_throttle::CommentThrottle::new(window);
+        self
+    }
+
+    /// Sets how many pages `IssueTracker::list_issues` reads before failing
+    /// with [`GitHubOperationError::PaginationLimitExceeded`].
+    ///
+    /// Defaults to [`issues::DEFAULT_MAX_ISSUE_PAGES`].
+    #[must_use]
+    pub fn with_max_issue_pages(mut self, max_pages: u32) -> Self {
+        self.max_issue_pages = max_pages;
+        self
+    }
+
+    /// Sets the longest a request waits for a throttled endpoint class
+    /// before failing with [`GitHubOperationError::RateLimitExhausted`].
+    ///
+    /// Defaults to [`rate_limited::DEFAULT_MAX_RATE_LIMIT_WAIT`].
+    #[must_use]
+    pub fn with_max_rate_limit_wait(mut self, max_wait: std::time::Duration) -> Self {
+        self.http = self.http.with_max_wait(max_wait);
+        self
+    }
+
+    /// Enables ETag-based conditional GETs, caching the bodies of up to
+    /// `capacity` URLs (least recently used evicted first).
+    ///
+    /// A capacity of zero disables the cache, which is also the default.
+    #[must_use]
+    pub fn with_etag_cache(mut self, capacity: usize) -> Self {
+        self.etag_cache = std::num::NonZeroUsize::new(capacity).map(etag_cache::EtagCache::new);
+        self
+    }
+
+    /// The conditional-GET cache, if enabled.
+    pub fn etag_cache(&self) -> Option<&etag_cache::EtagCache> {
+        self.etag_cache.as_ref()
+    }
+
+    /// Rate-limit state for this client, keyed by endpoint class.
+    pub fn rate_limits(&self) -> &rate_limit::RateLimitTracker {
+        self.http.tracker()
+    }
+
+    /// The rate-limit-aware executor every request goes through; its
+    /// [`remaining`](rate_limited::RateLimitedClient::remaining) count can be
+    /// logged to show the API budget left.
+    pub fn rate_limited(&self) -> &rate_limited::RateLimitedClient {
+        &self.http
+    }
+
+    /// Throttle applied by [`GithubClient::upsert_comment_throttled`].
+    pub fn comment_throttle(&self) -> &comment_throttle::CommentThrottle {
+        &self.comment_throttle
     }
 }
 
@@ -106,8 +444,8 @@ impl GithubClient {
 #[async_trait]
 impl IssueTracker for GithubClient {
     #[instrument(skip(self))]
-    async fn get_issue(&self, _id: WorkItemId) -> Result<Issue, GitHubOperationError> {
-        todo!("IssueTracker::get_issue — implemented in PR 10")
+    async fn get_issue(&self, id: WorkItemId) -> Result<Issue, GitHubOperationError> {
+        self.read_issue(id).await
     }
 
     #[instrument(skip(self))]
@@ -177,9 +515,30 @@ impl IssueTracker for GithubClient {
         todo!("IssueTracker::remove_label — implemented in PR 10")
     }
 
+    #[instrument(skip(self, body))]
+    async fn post_comment(&self, id: WorkItemId, body: &str) -> Result<(), GitHubOperationError> {
+        self.create_comment(id, body).await
+    }
+
     #[instrument(skip(self))]
-    async fn post_comment(&self, _id: WorkItemId, _body: &str) -> Result<(), GitHubOperationError> {
-        todo!("IssueTracker::post_comment — implemented in PR 10")
+    async fn list_comments(
+        &self,
+        id: WorkItemId,
+    ) -> Result<Vec<IssueComment>, GitHubOperationError> {
+        comments::collect_comments(
+            |page| self.read_comments_page(id, page),
+            comments::DEFAULT_MAX_COMMENT_PAGES,
+        )
+        .await
+    }
+
+    #[instrument(skip(self, body))]
+    async fn update_comment(
+        &self,
+        comment: CommentId,
+        body: &str,
+    ) -> Result<(), GitHubOperationError> {
+        self.patch_comment(comment, body).await
     }
 
     #[instrument(skip(self))]
@@ -187,6 +546,29 @@ impl IssueTracker for GithubClient {
         todo!("IssueTracker::get_issue_state — implemented in PR 10")
     }
 
+    #[instrument(skip(self))]
+    async fn set_issue_state(
+        &self,
+        id: WorkItemId,
+        state: IssueState,
+        reason: IssueStateReason,
+    ) -> Result<(), GitHubOperationError> {
+        self.patch_issue_state(id, state, reason).await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_issues(
+        &self,
+        repository: &RepositoryId,
+        filter: &IssueFilter,
+    ) -> Result<Vec<Issue>, GitHubOperationError> {
+        issues::collect_issues(
+            |page| self.read_issues_page(repository, filter, page),
+            self.max_issue_pages,
+        )
+        .await
+    }
+
     #[instrument(skip(self))]
     async fn get_milestone(&self, _id: MilestoneId) -> Result<Milestone, GitHubOperationError> {
         todo!("IssueTracker::get_milestone — implemented in PR 10")
@@ -212,34 +594,33 @@ impl PullRequestManager for GithubClient {
     #[instrument(skip(self))]
     async fn create_pull_request(
         &self,
-        _repository: &RepositoryId,
-        _title: &str,
-        _body: &str,
-        _head: &BranchName,
-        _base: &BranchName,
+        repository: &RepositoryId,
+        title: &str,
+        body: &str,
+        head: &BranchName,
+        base: &BranchName,
+        draft: bool,
     ) -> Result<PullRequest, GitHubOperationError> {
-        todo!("PullRequestManager::create_pull_request — implemented in PR 10")
+        self.open_pull_request(repository, title, body, head, base, draft)
+            .await
     }
 
     #[instrument(skip(self))]
     async fn get_pull_request(
         &self,
-        _repository: &RepositoryId,
-        _id: PullRequestId,
+        repository: &RepositoryId,
+        id: PullRequestId,
     ) -> Result<PullRequest, GitHubOperationError> {
-        todo!("PullRequestManager::get_pull_request — implemented in PR 10")
+        self.read_pull_request(repository, id).await
     }
 
     #[instrument(skip(self))]
     async fn find_pull_requests(
         &self,
-        _repository: &RepositoryId,
-        _filter: &PullRequestFilter,
+        repository: &RepositoryId,
+        filter: &PullRequestFilter,
     ) -> Result<Vec<PullRequest>, GitHubOperationError> {
-        // SDK gap: list PRs with filter params not yet in github-bot-sdk.
-        Err(GitHubOperationError::SdkCapabilityMissing {
-            capability: "list_prs_with_filter".to_string(),
-        })
+        self.list_pull_requests(repository, filter).await
     }
 
     #[instrument(skip(self))]
@@ -266,6 +647,25 @@ impl PullRequestManager for GithubClient {
     ) -> Result<ReviewStatus, GitHubOperationError> {
         todo!("PullRequestManager::get_review_status — implemented in PR 10")
     }
+
+    #[instrument(skip(self, body))]
+    async fn update_pull_request_body(
+        &self,
+        repository: &RepositoryId,
+        id: PullRequestId,
+        body: &str,
+    ) -> Result<(), GitHubOperationError> {
+        self.patch_pull_request_body(repository, id, body).await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_pr_files(
+        &self,
+        repository: &RepositoryId,
+        id: PullRequestId,
+    ) -> Result<Vec<ChangedFile>, GitHubOperationError> {
+        pr_files::collect_pr_files(|page| self.read_pr_files_page(repository, id, page)).await
+    }
 }
 
 // ─── CodeRepository ──────────────────────────────────────────────────────────
@@ -346,6 +746,17 @@ impl ProjectBoard for GithubClient {
     ) -> Result<(), GitHubOperationError> {
         todo!("ProjectBoard::sync_custom_field — implemented in PR 10")
     }
+
+    /// Sends every update as one GraphQL request of aliased
+    /// `updateProjectV2ItemFieldValue` mutations; see [`project_fields`].
+    #[instrument(skip(self, fields), fields(count = fields.len()))]
+    async fn set_item_fields(
+        &self,
+        work_item_id: WorkItemId,
+        fields: &[(String, ProjectFieldValue)],
+    ) -> Result<(), GitHubOperationError> {
+        self.update_item_fields(work_item_id, fields).await
+    }
 }
 
 // ─── AuditStore ──────────────────────────────────────────────────────────────
@@ -355,19 +766,21 @@ impl AuditStore for GithubClient {
     /// Records an audit event as a Markdown-formatted comment on the work-item issue.
     ///
     /// Format: a collapsible `<details>` block with the event's JSON body inside
-    /// a fenced code block. Each event is a separate comment to preserve the
+    /// a fenced code block (see [`audit_replay::render_audit_comment`]). Each event is a separate comment to preserve the
     /// audit trail even if earlier comments are edited.
     ///
-    /// Implementation detail (PR 10): batches events and flushes on a timer to
-    /// avoid GitHub API rate-limit exhaustion during parallel node execution.
-    #[instrument(skip(self, _event))]
+    /// Written through [`GithubClient::append_event`] with the key from
+    /// [`audit_append::event_idempotency_key`], so recording the same event
+    /// again after a failure does not post a second comment.
+    #[instrument(skip(self, event))]
     async fn record_event(
         &self,
-        _run_id: PipelineRunId,
-        _work_item_id: WorkItemId,
-        _event: AuditEvent,
+        run_id: PipelineRunId,
+        work_item_id: WorkItemId,
+        event: AuditEvent,
     ) -> Result<(), AuditStoreError> {
-        todo!("AuditStore::record_event — implemented in PR 10")
+        let key = audit_append::event_idempotency_key(run_id, &event)?;
+        self.append_event(work_item_id, run_id, event, &key).await
     }
 
     /// Writes the pipeline run summary as a Markdown collapsible section
@@ -0,0 +1,151 @@
+//! Bidirectional work-item ↔ pull-request cross-referencing.
+//!
+//! When the Integration node opens a PR for a work item, both sides must point
+//! at each other so that state reconstruction can find the PR from the issue
+//! and GitHub closes the issue when the PR merges:
+//!
+//! - the PR body carries a closing keyword (`Closes #42`);
+//! - the work-item issue carries a comment referencing the PR (`#57`).
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Cross-reference linking.
+
+use tracing::instrument;
+
+use pipeline::{
+    github::{GitHubOperationError, IssueComment, IssueTracker, PullRequestManager},
+    PullRequestId, RepositoryId, WorkItemId,
+};
+
+use crate::GithubClient;
+
+/// Keywords GitHub recognises as closing references in a PR body.
+///
+/// Matching is case-insensitive and accepts an optional `:` after the keyword.
+const CLOSING_KEYWORDS: &[&str] = &[
+    "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+];
+
+/// Returns the closing-reference line CogWorks inserts into PR bodies.
+#[must_use]
+pub fn closing_reference(work_item: WorkItemId) -> String {
+    format!("Closes #{work_item}")
+}
+
+/// Returns the comment body posted on the work item to reference its PR.
+#[must_use]
+pub fn pull_request_reference_comment(pr: PullRequestId) -> String {
+    format!("CogWorks opened pull request #{pr} for this work item.")
+}
+
+/// Returns `true` if `body` already contains a GitHub closing reference to
+/// `work_item` (e.g. `Fixes #42`, `closes: #42`).
+///
+/// A reference to a different issue whose number merely starts with the same
+/// digits (e.g. `#420` when looking for `#42`) does not match.
+#[must_use]
+pub fn has_closing_reference(body: &str, work_item: WorkItemId) -> bool {
+    let lower = body.to_ascii_lowercase();
+    let target = format!("#{work_item}");
+
+    lower.match_indices(&target).any(|(start, _)| {
+        let end = start + target.len();
+        let number_terminated = !lower[end..].starts_with(|c: char| c.is_ascii_digit());
+        if !number_terminated {
+            return false;
+        }
+
+        // Walk back over separators (`:` and whitespace) to the keyword.
+        let prefix = lower[..start].trim_end();
+        let prefix = prefix.strip_suffix(':').unwrap_or(prefix).trim_end();
+        if prefix.len() == lower[..start].len() {
+            // No separator between keyword and `#N` — not a closing reference.
+            return false;
+        }
+
+        CLOSING_KEYWORDS.iter().any(|keyword| {
+            prefix.strip_suffix(keyword).is_some_and(|before| {
+                !before
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_ascii_alphanumeric())
+            })
+        })
+    })
+}
+
+/// Returns `body` with a closing reference to `work_item` appended, or `None`
+/// if the body already references the work item.
+#[must_use]
+pub fn with_closing_reference(body: &str, work_item: WorkItemId) -> Option<String> {
+    if has_closing_reference(body, work_item) {
+        return None;
+    }
+
+    let reference = closing_reference(work_item);
+    let trimmed = body.trim_end();
+    if trimmed.is_empty() {
+        Some(reference)
+    } else {
+        Some(format!("{trimmed}\n\n{reference}"))
+    }
+}
+
+/// Returns `true` if one of `comments` is the reference comment for `pr`.
+#[must_use]
+pub fn has_reference_comment(comments: &[IssueComment], pr: PullRequestId) -> bool {
+    let reference = pull_request_reference_comment(pr);
+    comments
+        .iter()
+        .any(|comment| comment.body.trim() == reference)
+}
+
+impl GithubClient {
+    /// Record the linkage between a work item and its pull request on both sides.
+    ///
+    /// 1. Appends a closing keyword for the work item to the PR body, unless
+    ///    the body already carries a closing reference to `work_item`.
+    /// 2. Posts a reference comment on the work-item issue, unless one of its
+    ///    comments already is that reference.
+    ///
+    /// Idempotent: each side is checked before it is written, so a retry
+    /// after a failure part way through completes the missing side without
+    /// duplicating the other.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — PR or issue does not exist.
+    /// - [`GitHubOperationError::PermissionDenied`] — insufficient write access.
+    /// - [`GitHubOperationError::Transient`] — transient network failure.
+    #[instrument(skip(self))]
+    pub async fn link_pr_to_issue(
+        &self,
+        repository: &RepositoryId,
+        work_item: WorkItemId,
+        pr: PullRequestId,
+    ) -> Result<(), GitHubOperationError> {
+        let pull_request = self.get_pull_request(repository, pr).await?;
+        if let Some(updated_body) = with_closing_reference(&pull_request.body, work_item) {
+            self.update_pull_request_body(repository, pr, &updated_body)
+                .await?;
+        } else {
+            tracing::debug!(%work_item, %pr, "pull request already references work item");
+        }
+
+        let comments = self.list_comments(work_item).await?;
+        if has_reference_comment(&comments, pr) {
+            tracing::debug!(%work_item, %pr, "work item already references pull request");
+        } else {
+            self.post_comment(work_item, &pull_request_reference_comment(pr))
+                .await?;
+        }
+
+        tracing::info!(%work_item, %pr, "linked pull request to work item");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "linking_tests.rs"]
+mod tests;
@@ -0,0 +1,228 @@
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use pipeline::CommentId;
+use serde_json::{json, Value as JsonValue};
+
+use crate::{
+    test_support::{client, repository},
+    transport::{RestMethod, RestRequest, ScriptedTransport},
+};
+
+use super::*;
+
+/// A recorded `GET /repos/octo/widgets/pulls/57` response with `body`.
+fn pull_request_response(body: &str) -> JsonValue {
+    json!({
+        "number": 57,
+        "state": "open",
+        "title": "Add dark mode",
+        "body": body,
+        "user": { "login": "cogworks[bot]" },
+        "created_at": "2026-10-15T09:00:00Z",
+        "merged": false,
+        "head": { "ref": "cogworks/42-dark-mode", "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e" },
+        "base": { "ref": "main", "repo": { "full_name": "octo/widgets" } }
+    })
+}
+
+fn comments_response(bodies: &[&str]) -> JsonValue {
+    JsonValue::Array(
+        bodies
+            .iter()
+            .enumerate()
+            .map(|(i, body)| {
+                json!({
+                    "id": i + 1,
+                    "body": body,
+                    "user": { "login": "cogworks[bot]" },
+                    "created_at": "2026-10-15T09:00:00Z"
+                })
+            })
+            .collect(),
+    )
+}
+
+fn writes(transport: &ScriptedTransport) -> Vec<RestRequest> {
+    transport
+        .requests()
+        .into_iter()
+        .filter(|r| r.method != RestMethod::Get)
+        .collect()
+}
+
+fn comment(body: &str) -> IssueComment {
+    IssueComment {
+        id: CommentId::new(1),
+        author: "cogworks[bot]".to_string(),
+        body: body.to_string(),
+        created_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+    }
+}
+
+#[test]
+fn test_closing_reference_work_item_uses_closes_keyword() {
+    assert_eq!(closing_reference(WorkItemId::new(42)), "Closes #42");
+}
+
+#[test]
+fn test_pull_request_reference_comment_pr_names_pr_number() {
+    assert_eq!(
+        pull_request_reference_comment(PullRequestId::new(57)),
+        "CogWorks opened pull request #57 for this work item."
+    );
+}
+
+#[test]
+fn test_has_closing_reference_each_keyword_matches() {
+    for keyword in CLOSING_KEYWORDS {
+        let body = format!("Some change.\n\n{keyword} #42");
+        assert!(
+            has_closing_reference(&body, WorkItemId::new(42)),
+            "{keyword} should close #42"
+        );
+    }
+}
+
+#[test]
+fn test_has_closing_reference_mixed_case_and_colon_matches() {
+    assert!(has_closing_reference("FIXES: #42", WorkItemId::new(42)));
+    assert!(has_closing_reference(
+        "Resolves:   #42.",
+        WorkItemId::new(42)
+    ));
+}
+
+#[test]
+fn test_has_closing_reference_longer_number_does_not_match() {
+    assert!(!has_closing_reference("Closes #420", WorkItemId::new(42)));
+}
+
+#[test]
+fn test_has_closing_reference_plain_mention_does_not_match() {
+    assert!(!has_closing_reference(
+        "Related to #42",
+        WorkItemId::new(42)
+    ));
+    assert!(!has_closing_reference("See #42", WorkItemId::new(42)));
+}
+
+#[test]
+fn test_has_closing_reference_keyword_inside_word_does_not_match() {
+    assert!(!has_closing_reference("prefixes #42", WorkItemId::new(42)));
+}
+
+#[test]
+fn test_has_closing_reference_no_separator_does_not_match() {
+    assert!(!has_closing_reference("closes#42", WorkItemId::new(42)));
+}
+
+#[test]
+fn test_with_closing_reference_body_without_reference_appends_paragraph() {
+    assert_eq!(
+        with_closing_reference("Adds the parser.\n\n", WorkItemId::new(42)),
+        Some("Adds the parser.\n\nCloses #42".to_string())
+    );
+}
+
+#[test]
+fn test_with_closing_reference_empty_body_returns_reference_only() {
+    assert_eq!(
+        with_closing_reference("  \n", WorkItemId::new(42)),
+        Some("Closes #42".to_string())
+    );
+}
+
+#[test]
+fn test_with_closing_reference_existing_reference_returns_none() {
+    assert_eq!(
+        with_closing_reference("Fixes #42", WorkItemId::new(42)),
+        None
+    );
+}
+
+#[test]
+fn test_has_reference_comment_matching_comment_returns_true() {
+    let comments = vec![
+        comment("Picked up by CogWorks."),
+        comment("CogWorks opened pull request #57 for this work item.\n"),
+    ];
+    assert!(has_reference_comment(&comments, PullRequestId::new(57)));
+}
+
+#[test]
+fn test_has_reference_comment_other_pr_returns_false() {
+    let comments = vec![comment(
+        "CogWorks opened pull request #56 for this work item.",
+    )];
+    assert!(!has_reference_comment(&comments, PullRequestId::new(57)));
+}
+
+#[test]
+fn test_has_reference_comment_no_comments_returns_false() {
+    assert!(!has_reference_comment(&[], PullRequestId::new(57)));
+}
+
+#[tokio::test]
+async fn test_link_pr_to_issue_missing_reference_rewrites_body_and_posts_comment() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, pull_request_response("Adds dark mode."));
+    transport.push_json(200, pull_request_response("Adds dark mode.\n\nCloses #42"));
+    transport.push_json(200, comments_response(&[]));
+    transport.push_json(201, json!({ "id": 9 }));
+
+    client(&transport)
+        .link_pr_to_issue(&repository(), WorkItemId::new(42), PullRequestId::new(57))
+        .await
+        .unwrap();
+
+    let writes = writes(&transport);
+    assert_eq!(writes.len(), 2);
+    assert_eq!(writes[0].method, RestMethod::Patch);
+    assert_eq!(writes[0].path, "/repos/octo/widgets/pulls/57");
+    assert_eq!(
+        writes[0].body,
+        Some(json!({ "body": "Adds dark mode.\n\nCloses #42" }))
+    );
+    assert_eq!(writes[1].method, RestMethod::Post);
+    assert_eq!(writes[1].path, "/repos/octo/widgets/issues/42/comments");
+    assert_eq!(
+        writes[1].body,
+        Some(json!({ "body": pull_request_reference_comment(PullRequestId::new(57)) }))
+    );
+}
+
+#[tokio::test]
+async fn test_link_pr_to_issue_reference_present_does_not_rewrite_body() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, pull_request_response("Fixes #42"));
+    transport.push_json(200, comments_response(&[]));
+    transport.push_json(201, json!({ "id": 9 }));
+
+    client(&transport)
+        .link_pr_to_issue(&repository(), WorkItemId::new(42), PullRequestId::new(57))
+        .await
+        .unwrap();
+
+    let writes = writes(&transport);
+    assert_eq!(writes.len(), 1);
+    assert_eq!(writes[0].method, RestMethod::Post);
+}
+
+#[tokio::test]
+async fn test_link_pr_to_issue_reference_comment_present_does_not_post_again() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, pull_request_response("Fixes #42"));
+    transport.push_json(
+        200,
+        comments_response(&["CogWorks opened pull request #57 for this work item."]),
+    );
+
+    client(&transport)
+        .link_pr_to_issue(&repository(), WorkItemId::new(42), PullRequestId::new(57))
+        .await
+        .unwrap();
+
+    assert!(writes(&transport).is_empty());
+    assert_eq!(transport.requests().len(), 2);
+}
@@ -0,0 +1,182 @@
+//! Merge readiness from a pull request's `mergeable` / `mergeable_state`.
+//!
+//! GitHub computes mergeability asynchronously: right after a push, or on the
+//! first read of a pull request, `mergeable` is `null` and `mergeable_state`
+//! is `unknown` while a background job runs. Reading the pull request again a
+//! moment later returns the resolved values. [`GithubClient::get_mergeability`]
+//! re-reads the pull request until the state resolves, up to a bounded number
+//! of attempts, and reports [`MergeableState::Unknown`] if it never does.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Merge readiness.
+
+use std::{future::Future, num::NonZeroU32, time::Duration};
+
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::instrument;
+
+use pipeline::{
+    github::{GitHubOperationError, Mergeability, MergeableState},
+    PullRequestId, RepositoryId,
+};
+
+use crate::{
+    default_branch::repository_path, rate_limited::status_error, transport::RestRequest,
+    GithubClient,
+};
+
+/// Default number of reads made while waiting for GitHub to resolve
+/// mergeability.
+pub const DEFAULT_MERGEABILITY_ATTEMPTS: NonZeroU32 = match NonZeroU32::new(5) {
+    Some(attempts) => attempts,
+    None => unreachable!(),
+};
+
+/// Default delay between reads while mergeability is unresolved.
+pub const DEFAULT_MERGEABILITY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long to wait for GitHub to resolve mergeability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeabilityPolling {
+    /// Maximum number of reads, including the first.
+    pub max_attempts: NonZeroU32,
+    /// Delay between consecutive reads.
+    pub interval: Duration,
+}
+
+impl Default for MergeabilityPolling {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MERGEABILITY_ATTEMPTS,
+            interval: DEFAULT_MERGEABILITY_INTERVAL,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct WirePullRequest {
+    mergeable: Option<bool>,
+    mergeable_state: Option<String>,
+}
+
+/// Maps a `mergeable_state` value; unrecognised values are [`MergeableState::Unknown`].
+fn mergeable_state(value: &str) -> MergeableState {
+    match value {
+        "clean" => MergeableState::Clean,
+        "unstable" => MergeableState::Unstable,
+        "has_hooks" => MergeableState::HasHooks,
+        "behind" => MergeableState::Behind,
+        "blocked" => MergeableState::Blocked,
+        "dirty" => MergeableState::Dirty,
+        "draft" => MergeableState::Draft,
+        _ => MergeableState::Unknown,
+    }
+}
+
+/// Reads the mergeability fields of a REST pull request response.
+///
+/// A `null` `mergeable` or an `unknown` (or missing) `mergeable_state` yields
+/// an unresolved result ([`Mergeability::is_resolved`] is `false`).
+///
+/// # Errors
+///
+/// - [`GitHubOperationError::ParseFailure`] — the response is not a pull
+///   request object.
+pub fn parse_mergeability(response: &JsonValue) -> Result<Mergeability, GitHubOperationError> {
+    let pr: WirePullRequest = serde_json::from_value(response.clone()).map_err(|e| {
+        GitHubOperationError::ParseFailure {
+            message: format!("pull request mergeability: {e}"),
+        }
+    })?;
+    let state = match (pr.mergeable, pr.mergeable_state.as_deref()) {
+        (Some(_), Some(state)) => mergeable_state(state),
+        _ => MergeableState::Unknown,
+    };
+    Ok(Mergeability {
+        mergeable: state != MergeableState::Unknown && pr.mergeable.unwrap_or(false),
+        state,
+    })
+}
+
+/// Calls `read` until it returns a resolved [`Mergeability`] or
+/// `polling.max_attempts` reads have been made, sleeping `polling.interval`
+/// between reads.
+///
+/// Returns the last result, which is unresolved if GitHub never finished.
+///
+/// # Errors
+///
+/// The first error returned by `read`.
+pub async fn poll_mergeability<F, Fut>(
+    polling: MergeabilityPolling,
+    mut read: F,
+) -> Result<Mergeability, GitHubOperationError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Mergeability, GitHubOperationError>>,
+{
+    let mut attempt = 1;
+    loop {
+        let mergeability = read().await?;
+        if mergeability.is_resolved() || attempt >= polling.max_attempts.get() {
+            if !mergeability.is_resolved() {
+                tracing::warn!(attempts = attempt, "GitHub did not resolve mergeability");
+            }
+            return Ok(mergeability);
+        }
+        tracing::debug!(attempt, "mergeability unknown; polling again");
+        attempt += 1;
+        tokio::time::sleep(polling.interval).await;
+    }
+}
+
+impl GithubClient {
+    /// Return whether `pr` can be merged, waiting for GitHub to compute it.
+    ///
+    /// Polls with [`MergeabilityPolling::default`]. The result is
+    /// [`MergeableState::Unknown`] (and not mergeable) if GitHub has still not
+    /// resolved it after the last attempt.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — the pull request does not exist.
+    /// - [`GitHubOperationError::ParseFailure`] — see [`parse_mergeability`].
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   transport.
+    #[instrument(skip(self))]
+    pub async fn get_mergeability(
+        &self,
+        repository: &RepositoryId,
+        pr: PullRequestId,
+    ) -> Result<Mergeability, GitHubOperationError> {
+        poll_mergeability(MergeabilityPolling::default(), || {
+            self.read_mergeability(repository, pr)
+        })
+        .await
+    }
+
+    /// Reads the pull request once and maps its mergeability fields.
+    async fn read_mergeability(
+        &self,
+        repository: &RepositoryId,
+        pr: PullRequestId,
+    ) -> Result<Mergeability, GitHubOperationError> {
+        let response = self
+            .send(RestRequest::get(format!(
+                "{}/pulls/{}",
+                repository_path(repository),
+                pr.as_u64()
+            )))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("pull request #{pr}")) {
+            return Err(error);
+        }
+        parse_mergeability(&response.body)
+    }
+}
+
+#[cfg(test)]
+#[path = "mergeability_tests.rs"]
+mod tests;
@@ -0,0 +1,202 @@
+use std::{collections::VecDeque, sync::Arc, sync::Mutex};
+
+use serde_json::json;
+
+use crate::{
+    test_support::{client_without_repository, repository},
+    transport::{RestMethod, ScriptedTransport},
+};
+
+use super::*;
+
+fn pr_response(mergeable: JsonValue, state: &str) -> JsonValue {
+    json!({
+        "number": 57,
+        "state": "open",
+        "mergeable": mergeable,
+        "mergeable_state": state,
+    })
+}
+
+fn polling(max_attempts: u32) -> MergeabilityPolling {
+    MergeabilityPolling {
+        max_attempts: NonZeroU32::new(max_attempts).unwrap(),
+        interval: Duration::ZERO,
+    }
+}
+
+/// Polls with `responses` as the successive pull request reads, returning
+/// the result and the number of reads made.
+async fn poll(
+    max_attempts: u32,
+    responses: Vec<JsonValue>,
+) -> (Result<Mergeability, GitHubOperationError>, usize) {
+    let remaining = Mutex::new(VecDeque::from(responses));
+    let reads = Mutex::new(0);
+    let result = poll_mergeability(polling(max_attempts), || {
+        *reads.lock().unwrap() += 1;
+        let response = remaining.lock().unwrap().pop_front().unwrap();
+        async move { parse_mergeability(&response) }
+    })
+    .await;
+    let reads = *reads.lock().unwrap();
+    (result, reads)
+}
+
+// ─── parse_mergeability ─────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_mergeability_clean_is_resolved_and_ready() {
+    let mergeability = parse_mergeability(&pr_response(json!(true), "clean")).unwrap();
+
+    assert_eq!(
+        mergeability,
+        Mergeability {
+            mergeable: true,
+            state: MergeableState::Clean
+        }
+    );
+    assert!(mergeability.is_resolved());
+    assert!(mergeability.is_ready());
+}
+
+#[test]
+fn test_parse_mergeability_null_mergeable_is_unresolved() {
+    let mergeability = parse_mergeability(&pr_response(JsonValue::Null, "unknown")).unwrap();
+
+    assert_eq!(mergeability.state, MergeableState::Unknown);
+    assert!(!mergeability.mergeable);
+    assert!(!mergeability.is_resolved());
+}
+
+#[test]
+fn test_parse_mergeability_each_state_maps_to_its_variant() {
+    for (wire, state) in [
+        ("unstable", MergeableState::Unstable),
+        ("has_hooks", MergeableState::HasHooks),
+        ("behind", MergeableState::Behind),
+        ("blocked", MergeableState::Blocked),
+        ("dirty", MergeableState::Dirty),
+        ("draft", MergeableState::Draft),
+        ("something_new", MergeableState::Unknown),
+    ] {
+        let mergeability = parse_mergeability(&pr_response(json!(false), wire)).unwrap();
+        assert_eq!(mergeability.state, state, "mergeable_state '{wire}'");
+    }
+}
+
+#[test]
+fn test_parse_mergeability_dirty_is_not_mergeable() {
+    let mergeability = parse_mergeability(&pr_response(json!(false), "dirty")).unwrap();
+
+    assert!(mergeability.is_resolved());
+    assert!(!mergeability.is_ready());
+}
+
+#[test]
+fn test_parse_mergeability_non_object_returns_parse_failure() {
+    let result = parse_mergeability(&json!("not a pull request"));
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+// ─── poll_mergeability ──────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_poll_mergeability_clean_first_read_stops_after_one_read() {
+    let (result, reads) = poll(5, vec![pr_response(json!(true), "clean")]).await;
+
+    assert_eq!(result.unwrap().state, MergeableState::Clean);
+    assert_eq!(reads, 1);
+}
+
+#[tokio::test]
+async fn test_poll_mergeability_unknown_then_clean_returns_clean() {
+    let (result, reads) = poll(
+        5,
+        vec![
+            pr_response(JsonValue::Null, "unknown"),
+            pr_response(JsonValue::Null, "unknown"),
+            pr_response(json!(true), "clean"),
+        ],
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap(),
+        Mergeability {
+            mergeable: true,
+            state: MergeableState::Clean
+        }
+    );
+    assert_eq!(reads, 3);
+}
+
+#[tokio::test]
+async fn test_poll_mergeability_never_resolved_returns_unknown_at_limit() {
+    let (result, reads) = poll(
+        2,
+        vec![
+            pr_response(JsonValue::Null, "unknown"),
+            pr_response(JsonValue::Null, "unknown"),
+        ],
+    )
+    .await;
+
+    assert_eq!(result.unwrap().state, MergeableState::Unknown);
+    assert_eq!(reads, 2);
+}
+
+#[tokio::test]
+async fn test_poll_mergeability_read_error_is_returned() {
+    let (result, reads) = poll(5, vec![json!(42)]).await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+    assert_eq!(reads, 1);
+}
+
+#[test]
+fn test_mergeability_polling_default_uses_default_constants() {
+    let polling = MergeabilityPolling::default();
+
+    assert_eq!(polling.max_attempts, DEFAULT_MERGEABILITY_ATTEMPTS);
+    assert_eq!(polling.interval, DEFAULT_MERGEABILITY_INTERVAL);
+}
+
+// ─── GithubClient::get_mergeability ─────────────────────────────────────────
+
+#[tokio::test]
+async fn test_get_mergeability_clean_reads_pull_request_once() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, pr_response(json!(true), "clean"));
+
+    let mergeability = client_without_repository(&transport)
+        .get_mergeability(&repository(), PullRequestId::new(57))
+        .await
+        .unwrap();
+
+    assert!(mergeability.is_ready());
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Get);
+    assert_eq!(requests[0].path, "/repos/octo/widgets/pulls/57");
+}
+
+#[tokio::test]
+async fn test_get_mergeability_missing_pull_request_returns_not_found() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client_without_repository(&transport)
+        .get_mergeability(&repository(), PullRequestId::new(57))
+        .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+    assert_eq!(transport.requests().len(), 1);
+}
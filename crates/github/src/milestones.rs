@@ -0,0 +1,96 @@
+//! Milestone due date and progress for scheduling.
+//!
+//! The executor may order work by milestone urgency, which needs more than
+//! [`Milestone`](pipeline::github::Milestone) carries: the milestone's state
+//! and how many of its items are still open. [`GithubClient::get_milestone_info`]
+//! reads `GET /repos/{owner}/{repo}/milestones/{number}` and
+//! [`parse_milestone_info`] maps the response onto [`MilestoneInfo`].
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Milestone info.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::instrument;
+
+use pipeline::{
+    github::{GitHubOperationError, MilestoneInfo},
+    MilestoneId, RepositoryId, Timestamp,
+};
+
+use crate::{
+    default_branch::repository_path, rate_limited::status_error, transport::RestRequest,
+    GithubClient,
+};
+
+#[derive(Deserialize)]
+struct WireMilestone {
+    number: u64,
+    title: String,
+    state: String,
+    due_on: Option<DateTime<Utc>>,
+    #[serde(default)]
+    open_issues: u32,
+    #[serde(default)]
+    closed_issues: u32,
+}
+
+/// Maps a REST milestone response onto a [`MilestoneInfo`].
+///
+/// # Errors
+///
+/// [`GitHubOperationError::ParseFailure`] — the response does not have the
+/// expected shape, or `state` is unrecognised.
+pub fn parse_milestone_info(response: &JsonValue) -> Result<MilestoneInfo, GitHubOperationError> {
+    let parse_failure = |message: String| GitHubOperationError::ParseFailure { message };
+    let milestone: WireMilestone = serde_json::from_value(response.clone())
+        .map_err(|e| parse_failure(format!("milestone: {e}")))?;
+    let is_open = match milestone.state.as_str() {
+        "open" => true,
+        "closed" => false,
+        other => return Err(parse_failure(format!("milestone: unknown state '{other}'"))),
+    };
+    Ok(MilestoneInfo {
+        id: MilestoneId::new(milestone.number),
+        title: milestone.title,
+        due_on: milestone.due_on.map(Timestamp::from_utc),
+        is_open,
+        open_issues: milestone.open_issues,
+        closed_issues: milestone.closed_issues,
+    })
+}
+
+impl GithubClient {
+    /// Read the title, due date, state, and open/closed item counts of
+    /// milestone `id`.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — the milestone does not exist.
+    /// - [`GitHubOperationError::ParseFailure`] — see [`parse_milestone_info`].
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   repository or no [transport](crate::transport).
+    #[instrument(skip(self))]
+    pub async fn get_milestone_info(
+        &self,
+        id: MilestoneId,
+    ) -> Result<MilestoneInfo, GitHubOperationError> {
+        let path = milestone_path(self.repository()?, id);
+        let response = self.send(RestRequest::get(path)).await?;
+        if let Some(error) = status_error(&response, &format!("milestone {}", id.as_u64())) {
+            return Err(error);
+        }
+        parse_milestone_info(&response.body)
+    }
+}
+
+/// Returns the `GET` path of milestone `id` in `repository`.
+pub(crate) fn milestone_path(repository: &RepositoryId, id: MilestoneId) -> String {
+    format!("{}/milestones/{}", repository_path(repository), id.as_u64())
+}
+
+#[cfg(test)]
+#[path = "milestones_tests.rs"]
+mod tests;
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::{
+    test_support::client,
+    transport::{RestMethod, ScriptedTransport, REPOSITORY_CAPABILITY},
+};
+
+use super::*;
+
+/// Response as recorded from `GET /repos/octo/widgets/milestones/3`.
+fn recorded_milestone() -> JsonValue {
+    json!({
+        "url": "https://api.github.com/repos/octo/widgets/milestones/3",
+        "id": 1002604,
+        "number": 3,
+        "state": "open",
+        "title": "v1.0",
+        "description": "Tracking milestone for version 1.0",
+        "open_issues": 4,
+        "closed_issues": 8,
+        "created_at": "2026-04-10T20:09:31Z",
+        "updated_at": "2026-05-14T16:00:49Z",
+        "closed_at": null,
+        "due_on": "2026-10-09T07:00:00Z"
+    })
+}
+
+// ─── parse_milestone_info ───────────────────────────────────────────────────
+
+#[test]
+fn test_parse_milestone_info_recorded_response_returns_typed_info() {
+    let info = parse_milestone_info(&recorded_milestone()).unwrap();
+
+    assert_eq!(
+        info,
+        MilestoneInfo {
+            id: MilestoneId::new(3),
+            title: "v1.0".to_string(),
+            due_on: Timestamp::parse_rfc3339("2026-10-09T07:00:00Z"),
+            is_open: true,
+            open_issues: 4,
+            closed_issues: 8,
+        }
+    );
+}
+
+#[test]
+fn test_parse_milestone_info_no_due_date_returns_none_due_on() {
+    let mut response = recorded_milestone();
+    response["due_on"] = JsonValue::Null;
+
+    let info = parse_milestone_info(&response).unwrap();
+
+    assert_eq!(info.due_on, None);
+}
+
+#[test]
+fn test_parse_milestone_info_closed_state_returns_not_open() {
+    let mut response = recorded_milestone();
+    response["state"] = json!("closed");
+
+    let info = parse_milestone_info(&response).unwrap();
+
+    assert!(!info.is_open);
+}
+
+#[test]
+fn test_parse_milestone_info_missing_counts_default_to_zero() {
+    let response = json!({ "number": 1, "title": "Backlog", "state": "open" });
+
+    let info = parse_milestone_info(&response).unwrap();
+
+    assert_eq!((info.open_issues, info.closed_issues), (0, 0));
+}
+
+#[test]
+fn test_parse_milestone_info_unknown_state_returns_parse_failure() {
+    let mut response = recorded_milestone();
+    response["state"] = json!("archived");
+
+    assert!(matches!(
+        parse_milestone_info(&response),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_parse_milestone_info_missing_title_returns_parse_failure() {
+    let response = json!({ "number": 3, "state": "open" });
+
+    assert!(matches!(
+        parse_milestone_info(&response),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+// ─── get_milestone_info ─────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_get_milestone_info_recorded_response_reads_milestone() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, recorded_milestone());
+
+    let info = client(&transport)
+        .get_milestone_info(MilestoneId::new(3))
+        .await
+        .unwrap();
+
+    assert_eq!(info.title, "v1.0");
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Get);
+    assert_eq!(requests[0].path, "/repos/octo/widgets/milestones/3");
+}
+
+#[tokio::test]
+async fn test_get_milestone_info_missing_milestone_returns_not_found() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client(&transport)
+        .get_milestone_info(MilestoneId::new(99))
+        .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_get_milestone_info_no_repository_returns_capability_missing() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let client = GithubClient::new(Arc::new(())).with_transport(Arc::clone(&transport) as _);
+
+    let result = client.get_milestone_info(MilestoneId::new(3)).await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::SdkCapabilityMissing { capability })
+            if capability == REPOSITORY_CAPABILITY
+    ));
+    assert!(transport.requests().is_empty());
+}
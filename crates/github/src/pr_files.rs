@@ -0,0 +1,160 @@
+//! Paginated listing of the files changed by a pull request.
+//!
+//! The review node scopes its analysis to the files a PR touches. GitHub
+//! returns them from `GET /repos/{owner}/{repo}/pulls/{number}/files` in pages
+//! of at most [`PR_FILES_PER_PAGE`]. [`collect_pr_files`] reads pages until the
+//! `Link` header has no `rel="next"` entry, and [`parse_pr_files_page`] maps
+//! each page onto [`ChangedFile`]s.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §PullRequestManager.
+
+use std::future::Future;
+
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+
+use pipeline::{
+    github::{ChangedFile, FileChangeStatus, GitHubOperationError},
+    ArtifactPath, PullRequestId, RepositoryId,
+};
+
+use crate::{rate_limited::status_error, GithubClient};
+
+/// Response header carrying the pagination links.
+pub(crate) const LINK_HEADER: &str = "link";
+
+/// Files requested per page (the API maximum).
+pub const PR_FILES_PER_PAGE: u32 = 100;
+
+/// Most files GitHub lists for one pull request.
+pub const MAX_PR_FILES: usize = 3000;
+
+/// One page of [`collect_pr_files`] input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrFilesPage {
+    /// The page's JSON array of file objects.
+    pub body: JsonValue,
+    /// The response's `Link` header, if any.
+    pub link: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WireFile {
+    filename: String,
+    status: FileChangeStatus,
+    #[serde(default)]
+    additions: u32,
+    #[serde(default)]
+    deletions: u32,
+    previous_filename: Option<String>,
+}
+
+/// Returns the request path for `page` (1-based) of the files of `pr`.
+pub fn pr_files_path(repository: &RepositoryId, pr: PullRequestId, page: u32) -> String {
+    format!(
+        "/repos/{}/{}/pulls/{pr}/files?per_page={PR_FILES_PER_PAGE}&page={page}",
+        repository.owner(),
+        repository.repo()
+    )
+}
+
+/// Returns `true` if a `Link` header has a `rel="next"` entry.
+pub fn has_next_page(link: Option<&str>) -> bool {
+    link.is_some_and(|link| {
+        link.split(',').any(|entry| {
+            entry
+                .split(';')
+                .skip(1)
+                .any(|param| param.trim() == r#"rel="next""#)
+        })
+    })
+}
+
+/// Maps one page of the PR files response onto [`ChangedFile`]s.
+///
+/// # Errors
+///
+/// [`GitHubOperationError::ParseFailure`] — the page is not an array of file
+/// objects, a file has an unknown `status`, or a `filename` is empty.
+pub fn parse_pr_files_page(body: &JsonValue) -> Result<Vec<ChangedFile>, GitHubOperationError> {
+    let parse_failure = |message: String| GitHubOperationError::ParseFailure { message };
+    let files: Vec<WireFile> = serde_json::from_value(body.clone())
+        .map_err(|e| parse_failure(format!("pull request files: {e}")))?;
+    files
+        .into_iter()
+        .map(|file| {
+            let path = ArtifactPath::new(file.filename)
+                .ok_or_else(|| parse_failure("pull request files: empty filename".to_string()))?;
+            Ok(ChangedFile {
+                path,
+                status: file.status,
+                additions: file.additions,
+                deletions: file.deletions,
+                previous_path: file.previous_filename.and_then(ArtifactPath::new),
+            })
+        })
+        .collect()
+}
+
+/// Reads pages from `fetch_page` (called with 1, 2, ...) until one has no
+/// next link, and returns all their files in order.
+///
+/// Stops after [`MAX_PR_FILES`] files even if GitHub links another page.
+///
+/// # Errors
+///
+/// The first error from `fetch_page` or [`parse_pr_files_page`].
+pub async fn collect_pr_files<F, Fut>(
+    mut fetch_page: F,
+) -> Result<Vec<ChangedFile>, GitHubOperationError>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<PrFilesPage, GitHubOperationError>>,
+{
+    let mut files = Vec::new();
+    let mut page = 1;
+    loop {
+        let response = fetch_page(page).await?;
+        files.extend(parse_pr_files_page(&response.body)?);
+        if !has_next_page(response.link.as_deref()) || files.len() >= MAX_PR_FILES {
+            tracing::debug!(
+                pages = page,
+                files = files.len(),
+                "listed pull request files"
+            );
+            return Ok(files);
+        }
+        page += 1;
+    }
+}
+
+impl GithubClient {
+    /// Reads one page of the files changed by `pr`.
+    ///
+    /// Goes through [`GithubClient::conditional_get`], so a re-read of an
+    /// unchanged page is answered from the ETag cache when it is enabled.
+    pub(crate) async fn read_pr_files_page(
+        &self,
+        repository: &RepositoryId,
+        pr: PullRequestId,
+        page: u32,
+    ) -> Result<PrFilesPage, GitHubOperationError> {
+        let response = self
+            .conditional_get(&pr_files_path(repository, pr, page))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("pull request #{pr} in {repository}"))
+        {
+            return Err(error);
+        }
+        Ok(PrFilesPage {
+            link: response.header(LINK_HEADER).map(str::to_string),
+            body: response.body,
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "pr_files_tests.rs"]
+mod tests;
@@ -0,0 +1,219 @@
+use std::cell::RefCell;
+
+use serde_json::json;
+
+use crate::test_support::repository;
+
+use super::*;
+
+fn path(value: &str) -> ArtifactPath {
+    ArtifactPath::new(value).unwrap()
+}
+
+fn next_link(page: u32) -> String {
+    format!(
+        "<https://api.github.com/repositories/1/pulls/7/files?per_page=100&page={page}>; \
+         rel=\"next\", <https://api.github.com/repositories/1/pulls/7/files?per_page=100&page=2>; \
+         rel=\"last\""
+    )
+}
+
+/// Two pages as recorded from `GET /repos/octo/widgets/pulls/7/files`.
+fn recorded_pages() -> Vec<PrFilesPage> {
+    vec![
+        PrFilesPage {
+            body: json!([
+                {
+                    "sha": "bbcd538c8e72b8c175046e27cc8f907076331401",
+                    "filename": "src/lib.rs",
+                    "status": "modified",
+                    "additions": 10,
+                    "deletions": 2,
+                    "changes": 12,
+                    "patch": "@@ -1 +1 @@"
+                },
+                {
+                    "filename": "src/new.rs",
+                    "status": "added",
+                    "additions": 40,
+                    "deletions": 0
+                }
+            ]),
+            link: Some(next_link(2)),
+        },
+        PrFilesPage {
+            body: json!([
+                {
+                    "filename": "docs/guide.md",
+                    "status": "renamed",
+                    "additions": 0,
+                    "deletions": 0,
+                    "previous_filename": "docs/old-guide.md"
+                }
+            ]),
+            link: Some(
+                "<https://api.github.com/repositories/1/pulls/7/files?per_page=100&page=1>; \
+                 rel=\"prev\""
+                    .to_string(),
+            ),
+        },
+    ]
+}
+
+// ─── pr_files_path ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_pr_files_path_page_requests_maximum_page_size() {
+    assert_eq!(
+        pr_files_path(&repository(), PullRequestId::new(7), 3),
+        "/repos/octo/widgets/pulls/7/files?per_page=100&page=3"
+    );
+}
+
+// ─── has_next_page ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_has_next_page_next_relation_returns_true() {
+    assert!(has_next_page(Some(&next_link(2))));
+}
+
+#[test]
+fn test_has_next_page_only_prev_and_last_returns_false() {
+    let link = "<https://api.github.com/x?page=1>; rel=\"prev\", \
+                <https://api.github.com/x?page=1>; rel=\"last\"";
+
+    assert!(!has_next_page(Some(link)));
+}
+
+#[test]
+fn test_has_next_page_missing_header_returns_false() {
+    assert!(!has_next_page(None));
+}
+
+// ─── parse_pr_files_page ────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_pr_files_page_recorded_page_returns_typed_files() {
+    let files = parse_pr_files_page(&recorded_pages()[0].body).unwrap();
+
+    assert_eq!(
+        files,
+        vec![
+            ChangedFile {
+                path: path("src/lib.rs"),
+                status: FileChangeStatus::Modified,
+                additions: 10,
+                deletions: 2,
+                previous_path: None,
+            },
+            ChangedFile {
+                path: path("src/new.rs"),
+                status: FileChangeStatus::Added,
+                additions: 40,
+                deletions: 0,
+                previous_path: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_pr_files_page_rename_keeps_previous_path() {
+    let files = parse_pr_files_page(&recorded_pages()[1].body).unwrap();
+
+    assert_eq!(files[0].status, FileChangeStatus::Renamed);
+    assert_eq!(files[0].previous_path, Some(path("docs/old-guide.md")));
+}
+
+#[test]
+fn test_parse_pr_files_page_unknown_status_returns_parse_failure() {
+    let body = json!([{ "filename": "a.rs", "status": "exploded" }]);
+
+    assert!(matches!(
+        parse_pr_files_page(&body),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_parse_pr_files_page_empty_filename_returns_parse_failure() {
+    let body = json!([{ "filename": "", "status": "added" }]);
+
+    assert!(matches!(
+        parse_pr_files_page(&body),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_parse_pr_files_page_not_an_array_returns_parse_failure() {
+    let body = json!({ "message": "Not Found" });
+
+    assert!(matches!(
+        parse_pr_files_page(&body),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+// ─── collect_pr_files ───────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_collect_pr_files_recorded_pages_returns_files_in_order() {
+    let pages = RefCell::new(recorded_pages().into_iter());
+    let requested = RefCell::new(Vec::new());
+
+    let files = collect_pr_files(|page| {
+        requested.borrow_mut().push(page);
+        let next = pages.borrow_mut().next();
+        async move { Ok(next.unwrap()) }
+    })
+    .await
+    .unwrap();
+
+    let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+    assert_eq!(paths, vec!["src/lib.rs", "src/new.rs", "docs/guide.md"]);
+    assert_eq!(*requested.borrow(), vec![1, 2]);
+}
+
+#[tokio::test]
+async fn test_collect_pr_files_page_error_returns_error() {
+    let result = collect_pr_files(|page| async move {
+        if page == 1 {
+            Ok(recorded_pages().remove(0))
+        } else {
+            Err(GitHubOperationError::NotFound {
+                resource: "pull request #7".to_string(),
+            })
+        }
+    })
+    .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_collect_pr_files_file_cap_stops_despite_next_link() {
+    let full_page: Vec<JsonValue> = (0..PR_FILES_PER_PAGE)
+        .map(|i| json!({ "filename": format!("f{i}.rs"), "status": "added" }))
+        .collect();
+    let calls = RefCell::new(0u32);
+
+    let files = collect_pr_files(|page| {
+        *calls.borrow_mut() += 1;
+        let body = JsonValue::Array(full_page.clone());
+        async move {
+            Ok(PrFilesPage {
+                body,
+                link: Some(next_link(page + 1)),
+            })
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(files.len(), MAX_PR_FILES);
+    assert_eq!(
+        *calls.borrow() as usize,
+        MAX_PR_FILES / PR_FILES_PER_PAGE as usize
+    );
+}
@@ -0,0 +1,409 @@
+//! Batched Projects V2 field updates.
+//!
+//! Projects V2 fields can only be written through GraphQL, one
+//! `updateProjectV2ItemFieldValue` mutation per field.
+//! [`ProjectBoard::set_item_fields`](pipeline::ProjectBoard::set_item_fields)
+//! on [`GithubClient`](crate::GithubClient) sends all of a card's updates in one request instead: each field becomes
+//! an aliased mutation (`f0`, `f1`, ...) in a single document built by
+//! [`set_item_fields_request`].
+//!
+//! The mutation addresses the project, item, field, and option by GraphQL
+//! node ID, while callers name fields and options as they appear on the board.
+//! A first query ([`project_item_request`]) finds the work item's card on the
+//! board set with [`GithubClient::with_project`] together with the board's
+//! field definitions, and [`resolve_field_inputs`] translates names to IDs.
+//!
+//! GraphQL answers HTTP 200 even when some of the aliased mutations failed.
+//! [`parse_set_item_fields`] inspects the `errors` array and reports the first
+//! failure through [`crate::graphql::classify_error`], so a `RATE_LIMITED`
+//! error is retried after the reset like an exhausted REST limit.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §ProjectBoard.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+use tracing::instrument;
+
+use pipeline::{
+    github::{GitHubOperationError, ProjectFieldValue},
+    RepositoryId, WorkItemId,
+};
+
+use crate::{
+    graphql::{classify_error, parse_errors, GraphQlNodeId},
+    GithubClient,
+};
+
+/// Maximum number of project cards read for one work item.
+pub const PROJECT_ITEMS_PAGE_SIZE: u32 = 20;
+
+/// Maximum number of fields read for a project.
+pub const PROJECT_FIELDS_PAGE_SIZE: u32 = 100;
+
+/// A work item's card on a Projects V2 board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectItemRef {
+    /// Node ID of the project.
+    pub project: GraphQlNodeId,
+    /// Node ID of the item (the card, not the issue).
+    pub item: GraphQlNodeId,
+}
+
+/// A custom field of a project, with its options for single-select and
+/// iteration fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectFieldDefinition {
+    /// Node ID of the field.
+    pub id: GraphQlNodeId,
+    /// Field name as shown on the board.
+    pub name: String,
+    /// `(name, node ID)` of each single-select option or iteration; empty for
+    /// other field types.
+    pub options: Vec<(String, GraphQlNodeId)>,
+}
+
+/// A field update ready to send: the field's node ID and the
+/// `ProjectV2FieldValue` input object.
+pub type FieldInput = (GraphQlNodeId, JsonValue);
+
+// ─── Card lookup ─────────────────────────────────────────────────────────────
+
+const PROJECT_ITEM_QUERY: &str = "\
+query($owner: String!, $name: String!, $number: Int!, $items: Int!, $fields: Int!) {
+  repository(owner: $owner, name: $name) {
+    issue(number: $number) {
+      projectItems(first: $items) {
+        nodes {
+          id
+          project {
+            id number
+            fields(first: $fields) {
+              nodes {
+                ... on ProjectV2FieldCommon { id name }
+                ... on ProjectV2SingleSelectField { options { id name } }
+                ... on ProjectV2IterationField { configuration { iterations { id title } } }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+  rateLimit { cost remaining resetAt }
+}";
+
+/// Builds the GraphQL request reading the project cards of issue
+/// `work_item_id` in `repository`, with each card's project and its fields.
+pub fn project_item_request(repository: &RepositoryId, work_item_id: WorkItemId) -> JsonValue {
+    json!({
+        "query": PROJECT_ITEM_QUERY,
+        "variables": {
+            "owner": repository.owner(),
+            "name": repository.repo(),
+            "number": work_item_id.as_u64(),
+            "items": PROJECT_ITEMS_PAGE_SIZE,
+            "fields": PROJECT_FIELDS_PAGE_SIZE,
+        },
+    })
+}
+
+#[derive(Deserialize)]
+struct WireNodes<T> {
+    nodes: Vec<Option<T>>,
+}
+
+#[derive(Deserialize)]
+struct WireIssue {
+    #[serde(rename = "projectItems")]
+    project_items: WireNodes<WireItem>,
+}
+
+#[derive(Deserialize)]
+struct WireItem {
+    id: GraphQlNodeId,
+    project: WireProject,
+}
+
+#[derive(Deserialize)]
+struct WireProject {
+    id: GraphQlNodeId,
+    number: u64,
+    fields: WireNodes<WireField>,
+}
+
+/// A field node; fields of types without the selected fragments come back
+/// without `id` and `name`.
+#[derive(Deserialize)]
+struct WireField {
+    id: Option<GraphQlNodeId>,
+    name: Option<String>,
+    #[serde(default)]
+    options: Vec<WireOption>,
+    configuration: Option<WireIterations>,
+}
+
+#[derive(Deserialize)]
+struct WireOption {
+    id: GraphQlNodeId,
+    #[serde(alias = "title")]
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct WireIterations {
+    #[serde(default)]
+    iterations: Vec<WireOption>,
+}
+
+impl WireField {
+    fn into_definition(self) -> Option<ProjectFieldDefinition> {
+        let options = self
+            .options
+            .into_iter()
+            .chain(self.configuration.into_iter().flat_map(|c| c.iterations))
+            .map(|option| (option.name, option.id))
+            .collect();
+        Some(ProjectFieldDefinition {
+            id: self.id?,
+            name: self.name?,
+            options,
+        })
+    }
+}
+
+/// Maps a response to [`project_item_request`] onto the work item's card on
+/// project `project_number` and that project's field definitions.
+///
+/// # Errors
+///
+/// - [`GitHubOperationError::NotFound`] — the repository or issue does not
+///   exist, or the issue has no card on the project.
+/// - [`GitHubOperationError::ParseFailure`] — the response has an unexpected
+///   shape.
+/// - Any other reported error, mapped by [`classify_error`]; a
+///   `RATE_LIMITED` error is retryable at `rate_limit_reset`.
+pub fn parse_project_item(
+    response: &JsonValue,
+    work_item_id: WorkItemId,
+    project_number: u64,
+    rate_limit_reset: DateTime<Utc>,
+) -> Result<(ProjectItemRef, Vec<ProjectFieldDefinition>), GitHubOperationError> {
+    let resource = format!("work item #{work_item_id} on project #{project_number}");
+    if let Some(error) = parse_errors(response).first() {
+        return Err(classify_error(&resource, error, rate_limit_reset));
+    }
+    let Some(repository) = response.get("data").and_then(|data| data.get("repository")) else {
+        return Err(GitHubOperationError::ParseFailure {
+            message: format!("{resource}: response has no repository data"),
+        });
+    };
+    let issue = repository
+        .get("issue")
+        .filter(|issue| !issue.is_null())
+        .ok_or_else(|| GitHubOperationError::NotFound {
+            resource: resource.clone(),
+        })?;
+    let issue: WireIssue =
+        serde_json::from_value(issue.clone()).map_err(|e| GitHubOperationError::ParseFailure {
+            message: format!("{resource}: {e}"),
+        })?;
+    let item = issue
+        .project_items
+        .nodes
+        .into_iter()
+        .flatten()
+        .find(|item| item.project.number == project_number)
+        .ok_or(GitHubOperationError::NotFound { resource })?;
+    let definitions = item
+        .project
+        .fields
+        .nodes
+        .into_iter()
+        .flatten()
+        .filter_map(WireField::into_definition)
+        .collect();
+    Ok((
+        ProjectItemRef {
+            project: item.project.id,
+            item: item.id,
+        },
+        definitions,
+    ))
+}
+
+// ─── Field updates ───────────────────────────────────────────────────────────
+
+/// Translates `(field name, value)` pairs into [`FieldInput`]s using the
+/// project's `definitions`.
+///
+/// # Errors
+///
+/// - [`GitHubOperationError::NotFound`] — no field has the given name, or a
+///   single-select or iteration value names no option of its field.
+pub fn resolve_field_inputs(
+    definitions: &[ProjectFieldDefinition],
+    fields: &[(String, ProjectFieldValue)],
+) -> Result<Vec<FieldInput>, GitHubOperationError> {
+    fields
+        .iter()
+        .map(|(name, value)| {
+            let definition = definitions
+                .iter()
+                .find(|definition| definition.name == *name)
+                .ok_or_else(|| GitHubOperationError::NotFound {
+                    resource: format!("project field '{name}'"),
+                })?;
+            let option_id = |option: &str| {
+                definition
+                    .options
+                    .iter()
+                    .find(|(option_name, _)| option_name == option)
+                    .map(|(_, id)| id.as_str().to_string())
+                    .ok_or_else(|| GitHubOperationError::NotFound {
+                        resource: format!("option '{option}' of project field '{name}'"),
+                    })
+            };
+            let input = match value {
+                ProjectFieldValue::Text(text) => json!({ "text": text }),
+                ProjectFieldValue::Number(number) => json!({ "number": number }),
+                ProjectFieldValue::Date(date) => json!({ "date": date.to_string() }),
+                ProjectFieldValue::SingleSelect(option) => {
+                    json!({ "singleSelectOptionId": option_id(option)? })
+                }
+                ProjectFieldValue::Iteration(iteration) => {
+                    json!({ "iterationId": option_id(iteration)? })
+                }
+            };
+            Ok((definition.id.clone(), input))
+        })
+        .collect()
+}
+
+/// Returns the alias used for the field update at `index`.
+fn alias(index: usize) -> String {
+    format!("f{index}")
+}
+
+/// Builds one GraphQL request applying every update in `inputs` to `item`.
+pub fn set_item_fields_request(item: &ProjectItemRef, inputs: &[FieldInput]) -> JsonValue {
+    let mut parameters = String::from("$projectId: ID!, $itemId: ID!");
+    let mut mutations = String::new();
+    let mut variables = serde_json::Map::new();
+    variables.insert("projectId".to_string(), json!(item.project.as_str()));
+    variables.insert("itemId".to_string(), json!(item.item.as_str()));
+    for (index, (field, value)) in inputs.iter().enumerate() {
+        let alias = alias(index);
+        parameters.push_str(&format!(
+            ", ${alias}Field: ID!, ${alias}Value: ProjectV2FieldValue!"
+        ));
+        mutations.push_str(&format!(
+            "  {alias}: updateProjectV2ItemFieldValue(input: {{ projectId: $projectId, \
+             itemId: $itemId, fieldId: ${alias}Field, value: ${alias}Value }}) \
+             {{ projectV2Item {{ id }} }}\n"
+        ));
+        variables.insert(format!("{alias}Field"), json!(field.as_str()));
+        variables.insert(format!("{alias}Value"), value.clone());
+    }
+    json!({
+        "query": format!("mutation({parameters}) {{\n{mutations}}}"),
+        "variables": variables,
+    })
+}
+
+/// Checks a response to [`set_item_fields_request`].
+///
+/// `field_names` are the names of the updated fields, in request order; they
+/// only label errors. Updates whose alias has no error were applied even when
+/// others failed.
+///
+/// # Errors
+///
+/// The first reported failure, mapped by [`classify_error`]; a
+/// `RATE_LIMITED` error is retryable at `rate_limit_reset`.
+pub fn parse_set_item_fields(
+    response: &JsonValue,
+    field_names: &[String],
+    rate_limit_reset: DateTime<Utc>,
+) -> Result<(), GitHubOperationError> {
+    let errors = parse_errors(response);
+    let Some(first) = errors.first() else {
+        return Ok(());
+    };
+    let failed: Vec<&str> = field_names
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| errors.iter().any(|error| error.concerns(&alias(*index))))
+        .map(|(_, name)| name.as_str())
+        .collect();
+    let applied = field_names.len().saturating_sub(failed.len());
+    tracing::warn!(
+        applied,
+        failed = ?failed,
+        errors = errors.len(),
+        "project field update partially failed"
+    );
+    let resource = field_names
+        .iter()
+        .enumerate()
+        .find(|(index, _)| first.concerns(&alias(*index)))
+        .map_or_else(
+            || "project item fields".to_string(),
+            |(_, name)| format!("project field '{name}'"),
+        );
+    Err(classify_error(&resource, first, rate_limit_reset))
+}
+
+// ─── GithubClient entry point ────────────────────────────────────────────────
+
+impl GithubClient {
+    /// Applies `fields` to the card of `work_item_id` on the client's project
+    /// board: one query to find the card and the board's fields, then one
+    /// request of aliased mutations. Nothing is sent when `fields` is empty.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   transport, repository, or project.
+    /// - As for [`parse_project_item`], [`resolve_field_inputs`],
+    ///   [`parse_set_item_fields`], and [`GithubClient::send_graphql`].
+    #[instrument(skip(self, fields), fields(count = fields.len()))]
+    pub(crate) async fn update_item_fields(
+        &self,
+        work_item_id: WorkItemId,
+        fields: &[(String, ProjectFieldValue)],
+    ) -> Result<(), GitHubOperationError> {
+        if fields.is_empty() {
+            return Ok(());
+        }
+        let repository = self.repository()?;
+        let project = self.project()?;
+        let response = self
+            .send_graphql(
+                project_item_request(repository, work_item_id),
+                &format!("project item of work item #{work_item_id}"),
+            )
+            .await?;
+        let (item, definitions) = parse_project_item(
+            &response,
+            work_item_id,
+            project,
+            self.graphql_rate_limit_reset(),
+        )?;
+        let inputs = resolve_field_inputs(&definitions, fields)?;
+        let response = self
+            .send_graphql(
+                set_item_fields_request(&item, &inputs),
+                &format!("project fields of work item #{work_item_id}"),
+            )
+            .await?;
+        let names: Vec<String> = fields.iter().map(|(name, _)| name.clone()).collect();
+        parse_set_item_fields(&response, &names, self.graphql_rate_limit_reset())
+    }
+}
+
+#[cfg(test)]
+#[path = "project_fields_tests.rs"]
+mod tests;
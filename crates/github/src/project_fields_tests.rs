@@ -0,0 +1,384 @@
+use std::sync::Arc;
+
+use chrono::TimeZone;
+use pipeline::ProjectBoard;
+
+use crate::{
+    test_support::repository,
+    transport::{RestMethod, ScriptedTransport, GRAPHQL_PATH, PROJECT_CAPABILITY},
+};
+
+use super::*;
+
+fn node(id: &str) -> GraphQlNodeId {
+    GraphQlNodeId::new(id).unwrap()
+}
+
+fn reset() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 15, 13, 0, 0).unwrap()
+}
+
+fn client(transport: &Arc<ScriptedTransport>) -> GithubClient {
+    crate::test_support::client(transport).with_project(3)
+}
+
+fn item() -> ProjectItemRef {
+    ProjectItemRef {
+        project: node("PVT_kwDOAbc"),
+        item: node("PVTI_lADOAbc"),
+    }
+}
+
+fn definitions() -> Vec<ProjectFieldDefinition> {
+    vec![
+        ProjectFieldDefinition {
+            id: node("PVTF_estimate"),
+            name: "Estimate".to_string(),
+            options: Vec::new(),
+        },
+        ProjectFieldDefinition {
+            id: node("PVTSSF_status"),
+            name: "Status".to_string(),
+            options: vec![
+                ("Todo".to_string(), node("opt_todo")),
+                ("In Progress".to_string(), node("opt_progress")),
+            ],
+        },
+    ]
+}
+
+fn fields() -> Vec<(String, ProjectFieldValue)> {
+    vec![
+        ("Estimate".to_string(), ProjectFieldValue::Number(3.0)),
+        (
+            "Status".to_string(),
+            ProjectFieldValue::SingleSelect("In Progress".to_string()),
+        ),
+    ]
+}
+
+/// A recorded response to [`project_item_request`] for issue #42, with one
+/// card on project #1 and one on project #3.
+fn project_item_response() -> JsonValue {
+    json!({
+        "data": {
+            "repository": {
+                "issue": {
+                    "projectItems": {
+                        "nodes": [
+                            {
+                                "id": "PVTI_other",
+                                "project": {
+                                    "id": "PVT_other",
+                                    "number": 1,
+                                    "fields": { "nodes": [] }
+                                }
+                            },
+                            {
+                                "id": "PVTI_lADOAbc",
+                                "project": {
+                                    "id": "PVT_kwDOAbc",
+                                    "number": 3,
+                                    "fields": {
+                                        "nodes": [
+                                            { "id": "PVTF_estimate", "name": "Estimate" },
+                                            {
+                                                "id": "PVTSSF_status",
+                                                "name": "Status",
+                                                "options": [
+                                                    { "id": "opt_todo", "name": "Todo" },
+                                                    { "id": "opt_progress", "name": "In Progress" }
+                                                ]
+                                            },
+                                            {
+                                                "id": "PVTIF_sprint",
+                                                "name": "Sprint",
+                                                "configuration": {
+                                                    "iterations": [
+                                                        { "id": "it_1", "title": "Sprint 1" }
+                                                    ]
+                                                }
+                                            },
+                                            {}
+                                        ]
+                                    }
+                                }
+                            }
+                        ]
+                    }
+                }
+            },
+            "rateLimit": { "cost": 1, "remaining": 4990, "resetAt": "2026-10-15T13:00:00Z" }
+        }
+    })
+}
+
+fn updated_response() -> JsonValue {
+    json!({
+        "data": {
+            "f0": { "projectV2Item": { "id": "PVTI_lADOAbc" } },
+            "f1": { "projectV2Item": { "id": "PVTI_lADOAbc" } }
+        }
+    })
+}
+
+// ─── resolve_field_inputs ────────────────────────────────────────────────────
+
+#[test]
+fn test_resolve_field_inputs_named_fields_map_to_node_ids() {
+    let inputs = resolve_field_inputs(&definitions(), &fields()).unwrap();
+
+    assert_eq!(
+        inputs,
+        vec![
+            (node("PVTF_estimate"), json!({ "number": 3.0 })),
+            (
+                node("PVTSSF_status"),
+                json!({ "singleSelectOptionId": "opt_progress" })
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_resolve_field_inputs_unknown_field_returns_not_found() {
+    let fields = vec![(
+        "Priority".to_string(),
+        ProjectFieldValue::Text("P1".to_string()),
+    )];
+
+    assert!(matches!(
+        resolve_field_inputs(&definitions(), &fields),
+        Err(GitHubOperationError::NotFound { resource }) if resource.contains("Priority")
+    ));
+}
+
+#[test]
+fn test_resolve_field_inputs_unknown_option_returns_not_found() {
+    let fields = vec![(
+        "Status".to_string(),
+        ProjectFieldValue::SingleSelect("Blocked".to_string()),
+    )];
+
+    assert!(matches!(
+        resolve_field_inputs(&definitions(), &fields),
+        Err(GitHubOperationError::NotFound { resource }) if resource.contains("Blocked")
+    ));
+}
+
+// ─── set_item_fields_request ─────────────────────────────────────────────────
+
+#[test]
+fn test_set_item_fields_request_two_inputs_aliases_each_mutation() {
+    let inputs = resolve_field_inputs(&definitions(), &fields()).unwrap();
+
+    let request = set_item_fields_request(&item(), &inputs);
+
+    let query = request["query"].as_str().unwrap();
+    assert!(query.contains("f0: updateProjectV2ItemFieldValue"));
+    assert!(query.contains("f1: updateProjectV2ItemFieldValue"));
+    let variables = &request["variables"];
+    assert_eq!(variables["projectId"], json!("PVT_kwDOAbc"));
+    assert_eq!(variables["itemId"], json!("PVTI_lADOAbc"));
+    assert_eq!(variables["f1Field"], json!("PVTSSF_status"));
+    assert_eq!(
+        variables["f1Value"],
+        json!({ "singleSelectOptionId": "opt_progress" })
+    );
+}
+
+// ─── parse_set_item_fields ───────────────────────────────────────────────────
+
+#[test]
+fn test_parse_set_item_fields_no_errors_returns_ok() {
+    let names = vec!["Estimate".to_string(), "Status".to_string()];
+
+    assert!(parse_set_item_fields(&updated_response(), &names, reset()).is_ok());
+}
+
+#[test]
+fn test_parse_set_item_fields_second_alias_failed_returns_error_naming_field() {
+    let response = json!({
+        "data": { "f0": { "projectV2Item": { "id": "PVTI_lADOAbc" } }, "f1": null },
+        "errors": [{
+            "type": "NOT_FOUND",
+            "path": ["f1"],
+            "message": "Could not resolve to a node with the global id of 'opt_progress'"
+        }]
+    });
+    let names = vec!["Estimate".to_string(), "Status".to_string()];
+
+    assert!(matches!(
+        parse_set_item_fields(&response, &names, reset()),
+        Err(GitHubOperationError::NotFound { resource }) if resource == "project field 'Status'"
+    ));
+}
+
+#[test]
+fn test_parse_set_item_fields_rate_limited_returns_rate_limit_exhausted() {
+    let response = json!({
+        "errors": [{ "type": "RATE_LIMITED", "message": "API rate limit exceeded" }]
+    });
+
+    assert!(matches!(
+        parse_set_item_fields(&response, &["Estimate".to_string()], reset()),
+        Err(GitHubOperationError::RateLimitExhausted { reset_at }) if reset_at == reset()
+    ));
+}
+
+// ─── Card lookup ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_project_item_request_variables_name_issue_and_page_sizes() {
+    let request = project_item_request(&repository(), WorkItemId::new(42));
+
+    assert_eq!(
+        request["variables"],
+        json!({
+            "owner": "octo",
+            "name": "widgets",
+            "number": 42,
+            "items": PROJECT_ITEMS_PAGE_SIZE,
+            "fields": PROJECT_FIELDS_PAGE_SIZE,
+        })
+    );
+}
+
+#[test]
+fn test_parse_project_item_recorded_response_selects_card_on_project() {
+    let (card, definitions) =
+        parse_project_item(&project_item_response(), WorkItemId::new(42), 3, reset()).unwrap();
+
+    assert_eq!(card, item());
+    let names: Vec<_> = definitions.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, vec!["Estimate", "Status", "Sprint"]);
+    assert_eq!(
+        definitions[2].options,
+        vec![("Sprint 1".to_string(), node("it_1"))]
+    );
+}
+
+#[test]
+fn test_parse_project_item_no_card_on_project_returns_not_found() {
+    let result = parse_project_item(&project_item_response(), WorkItemId::new(42), 9, reset());
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[test]
+fn test_parse_project_item_null_issue_returns_not_found() {
+    let response = json!({ "data": { "repository": { "issue": null } } });
+
+    let result = parse_project_item(&response, WorkItemId::new(42), 3, reset());
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[test]
+fn test_parse_project_item_forbidden_error_returns_permission_denied() {
+    let response = json!({
+        "data": { "repository": null },
+        "errors": [{ "type": "FORBIDDEN", "message": "Resource not accessible by integration" }]
+    });
+
+    let result = parse_project_item(&response, WorkItemId::new(42), 3, reset());
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::PermissionDenied { .. })
+    ));
+}
+
+// ─── set_item_fields ─────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_set_item_fields_two_fields_looks_up_card_then_sends_one_mutation() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, project_item_response());
+    transport.push_json(200, updated_response());
+
+    client(&transport)
+        .set_item_fields(WorkItemId::new(42), &fields())
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests
+        .iter()
+        .all(|r| r.method == RestMethod::Post && r.path == GRAPHQL_PATH));
+    assert_eq!(
+        requests[0].body,
+        Some(project_item_request(&repository(), WorkItemId::new(42)))
+    );
+    let inputs = resolve_field_inputs(&definitions(), &fields()).unwrap();
+    assert_eq!(
+        requests[1].body,
+        Some(set_item_fields_request(&item(), &inputs))
+    );
+}
+
+#[tokio::test]
+async fn test_set_item_fields_empty_fields_sends_nothing() {
+    let transport = Arc::new(ScriptedTransport::new());
+
+    client(&transport)
+        .set_item_fields(WorkItemId::new(42), &[])
+        .await
+        .unwrap();
+
+    assert!(transport.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_set_item_fields_unknown_field_returns_not_found_without_mutation() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, project_item_response());
+    let fields = vec![(
+        "Priority".to_string(),
+        ProjectFieldValue::Text("P1".to_string()),
+    )];
+
+    let result = client(&transport)
+        .set_item_fields(WorkItemId::new(42), &fields)
+        .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_set_item_fields_rate_limited_mutation_returns_rate_limit_exhausted() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, project_item_response());
+    transport.push_json(
+        200,
+        json!({ "errors": [{ "type": "RATE_LIMITED", "message": "API rate limit exceeded" }] }),
+    );
+
+    let result = client(&transport)
+        .set_item_fields(WorkItemId::new(42), &fields())
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::RateLimitExhausted { reset_at }) if reset_at == reset()
+    ));
+}
+
+#[tokio::test]
+async fn test_set_item_fields_without_project_returns_capability_missing() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let client = GithubClient::new(Arc::new(()))
+        .with_transport(Arc::clone(&transport) as _)
+        .with_repository(repository());
+
+    let result = client.set_item_fields(WorkItemId::new(42), &fields()).await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::SdkCapabilityMissing { capability })
+            if capability == PROJECT_CAPABILITY
+    ));
+    assert!(transport.requests().is_empty());
+}
@@ -0,0 +1,492 @@
+//! Opening pull requests.
+//!
+//! `PullRequestManager::create_pull_request` sends
+//! `POST /repos/{owner}/{repo}/pulls` with the body built by
+//! [`create_pull_request_body`]. GitHub rejects two common cases with a 422
+//! that a node needs to tell apart from other failures:
+//!
+//! - **No commits between base and head** — the branch has nothing to merge.
+//!   Surfaced as [`GitHubOperationError::EmptyDiff`] so the node can skip the
+//!   PR instead of failing the run.
+//! - **A pull request already exists** — an earlier attempt already opened
+//!   one. The existing PR is looked up by head and base and surfaced as
+//!   [`GitHubOperationError::PullRequestAlreadyExists`].
+//!
+//! [`classify_create_rejection`] recognises both from the 422 body, and
+//! [`parse_created_pull_request`] maps a 201 body onto [`PullRequest`].
+//!
+//! `PullRequestManager::find_pull_requests` reads
+//! `GET /repos/{owner}/{repo}/pulls` with the query built by
+//! [`pull_requests_path`], following the `Link` header for up to
+//! [`MAX_PULL_REQUEST_PAGES`] pages; [`parse_pull_requests_page`] maps each
+//! page.
+//!
+//! `PullRequestManager::get_pull_request` reads
+//! `GET /repos/{owner}/{repo}/pulls/{number}`, mapped like a created PR, and
+//! `PullRequestManager::update_pull_request_body` sends a `PATCH` of the same
+//! path with the new `body`.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Creating pull requests.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+
+use pipeline::{
+    github::{
+        GitHubOperationError, PullRequest, PullRequestFilter, PullRequestStateFilter, ReviewStatus,
+    },
+    BranchName, CommitSha, PullRequestId, RepositoryId,
+};
+
+use crate::{
+    default_branch::repository_path,
+    issues::encode_query_value,
+    pr_files::{has_next_page, LINK_HEADER},
+    rate_limit::EndpointClass,
+    rate_limited::{status_error, RestResponse},
+    transport::RestRequest,
+    GithubClient,
+};
+
+/// Pull requests requested per page (the API maximum).
+pub const PULL_REQUESTS_PER_PAGE: u32 = 100;
+
+/// Most pages `PullRequestManager::find_pull_requests` reads before
+/// returning [`GitHubOperationError::PaginationLimitExceeded`].
+pub const MAX_PULL_REQUEST_PAGES: u32 = 10;
+
+/// Error message fragment GitHub uses when head has no commits over base.
+const NO_COMMITS_MESSAGE: &str = "no commits between";
+
+/// Error message fragment GitHub uses when an open PR already exists for the
+/// head branch.
+const ALREADY_EXISTS_MESSAGE: &str = "a pull request already exists";
+
+/// A 422 rejection of a create-pull-request call that callers handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreatePullRequestRejection {
+    /// Head has no commits that base does not already contain.
+    EmptyDiff,
+    /// An open pull request already exists for the head branch.
+    AlreadyExists,
+}
+
+#[derive(Deserialize)]
+struct WirePullRequest {
+    number: u64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    user: WireUser,
+    head: WireRef,
+    base: WireBaseRef,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct WireUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct WireRef {
+    #[serde(rename = "ref")]
+    name: String,
+    sha: String,
+}
+
+#[derive(Deserialize)]
+struct WireBaseRef {
+    #[serde(rename = "ref")]
+    name: String,
+    repo: WireRepo,
+}
+
+#[derive(Deserialize)]
+struct WireRepo {
+    full_name: String,
+}
+
+/// Builds the JSON body of `POST /repos/{owner}/{repo}/pulls`.
+///
+/// `draft` maps to GitHub's `draft` field; a draft PR cannot be merged until
+/// it is marked ready for review.
+pub fn create_pull_request_body(
+    title: &str,
+    body: &str,
+    head: &BranchName,
+    base: &BranchName,
+    draft: bool,
+) -> JsonValue {
+    json!({
+        "title": title,
+        "body": body,
+        "head": head.as_str(),
+        "base": base.as_str(),
+        "draft": draft,
+    })
+}
+
+/// Recognises the 422 rejections that [`CreatePullRequestRejection`] names.
+///
+/// Both the top-level `message` and each `errors[].message` are checked,
+/// case-insensitively. Returns `None` for any other status or message.
+pub fn classify_create_rejection(
+    status: u16,
+    response: &JsonValue,
+) -> Option<CreatePullRequestRejection> {
+    if status != 422 {
+        return None;
+    }
+    let top_level = response.get("message").and_then(JsonValue::as_str);
+    let nested = response
+        .get("errors")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|error| error.get("message").and_then(JsonValue::as_str));
+    top_level.into_iter().chain(nested).find_map(|message| {
+        let message = message.to_ascii_lowercase();
+        if message.contains(NO_COMMITS_MESSAGE) {
+            Some(CreatePullRequestRejection::EmptyDiff)
+        } else if message.contains(ALREADY_EXISTS_MESSAGE) {
+            Some(CreatePullRequestRejection::AlreadyExists)
+        } else {
+            None
+        }
+    })
+}
+
+/// Maps the response of a create-pull-request call onto a [`PullRequest`].
+///
+/// A new PR has no reviews, so its review status is empty.
+///
+/// # Errors
+///
+/// [`GitHubOperationError::ParseFailure`] — the response does not have the
+/// expected shape, or a branch, SHA, or repository name is empty.
+pub fn parse_created_pull_request(
+    response: &JsonValue,
+) -> Result<PullRequest, GitHubOperationError> {
+    let pr: WirePullRequest = serde_json::from_value(response.clone()).map_err(|e| {
+        GitHubOperationError::ParseFailure {
+            message: format!("pull request: {e}"),
+        }
+    })?;
+    pull_request_from_wire(pr)
+}
+
+/// Returns the request path for `page` (1-based) of the pull requests of
+/// `repository` that match `filter`.
+///
+/// GitHub filters `head` by `owner:branch`, so the head branch is qualified
+/// with the repository owner. No state filter lists every state, as
+/// [`PullRequestFilter::state`] documents.
+pub fn pull_requests_path(
+    repository: &RepositoryId,
+    filter: &PullRequestFilter,
+    page: u32,
+) -> String {
+    let state = match filter.state {
+        Some(PullRequestStateFilter::Open) => "open",
+        Some(PullRequestStateFilter::Closed) => "closed",
+        Some(PullRequestStateFilter::All) | None => "all",
+    };
+    let mut path = format!(
+        "{}/pulls?state={state}&per_page={PULL_REQUESTS_PER_PAGE}&page={page}",
+        repository_path(repository)
+    );
+    if let Some(head) = &filter.head_branch {
+        path.push_str("&head=");
+        path.push_str(&encode_query_value(&format!(
+            "{}:{head}",
+            repository.owner()
+        )));
+    }
+    if let Some(base) = &filter.base_branch {
+        path.push_str("&base=");
+        path.push_str(&encode_query_value(base.as_str()));
+    }
+    path
+}
+
+/// Returns the request path of pull request `id` in `repository`.
+pub fn pull_request_path(repository: &RepositoryId, id: PullRequestId) -> String {
+    format!("{}/pulls/{id}", repository_path(repository))
+}
+
+/// Maps one page of the pull request listing onto [`PullRequest`]s.
+///
+/// The listing carries no reviews, so each review status is empty, as for a
+/// created PR.
+///
+/// # Errors
+///
+/// [`GitHubOperationError::ParseFailure`] — the page is not an array of pull
+/// requests, or an entry has an empty branch, SHA, or repository name.
+pub fn parse_pull_requests_page(
+    body: &JsonValue,
+) -> Result<Vec<PullRequest>, GitHubOperationError> {
+    let prs: Vec<WirePullRequest> =
+        serde_json::from_value(body.clone()).map_err(|e| GitHubOperationError::ParseFailure {
+            message: format!("pull requests: {e}"),
+        })?;
+    prs.into_iter().map(pull_request_from_wire).collect()
+}
+
+fn pull_request_from_wire(pr: WirePullRequest) -> Result<PullRequest, GitHubOperationError> {
+    let parse_failure = |message: String| GitHubOperationError::ParseFailure { message };
+    let branch = |name: String| {
+        BranchName::new(name).ok_or_else(|| parse_failure("pull request: empty ref".to_string()))
+    };
+    Ok(PullRequest {
+        id: PullRequestId::new(pr.number),
+        repository: RepositoryId::new(&pr.base.repo.full_name).ok_or_else(|| {
+            parse_failure(format!(
+                "pull request: unrecognised repository '{}'",
+                pr.base.repo.full_name
+            ))
+        })?,
+        title: pr.title,
+        body: pr.body.unwrap_or_default(),
+        author: pr.user.login,
+        head_branch: branch(pr.head.name)?,
+        base_branch: branch(pr.base.name)?,
+        head_sha: CommitSha::new(pr.head.sha)
+            .ok_or_else(|| parse_failure("pull request: empty head sha".to_string()))?,
+        is_open: pr.state == "open",
+        is_merged: pr.merged,
+        review_status: ReviewStatus {
+            approvals: 0,
+            changes_requested: false,
+            approved: false,
+        },
+        created_at: pr.created_at,
+    })
+}
+
+/// Maps a create-pull-request response that is neither a 201 nor a
+/// recognised [`CreatePullRequestRejection`] to an error.
+fn create_status_error(repository: &RepositoryId, response: &RestResponse) -> GitHubOperationError {
+    let message = response
+        .body
+        .get("message")
+        .and_then(JsonValue::as_str)
+        .unwrap_or_default();
+    if let Some(permission) = response.missing_permission() {
+        return GitHubOperationError::MissingAppPermission {
+            action: format!("create pull request in {repository}"),
+            permission: permission.to_string(),
+        };
+    }
+    match response.status {
+        401 | 403 => GitHubOperationError::PermissionDenied {
+            action: format!("create pull request in {repository}"),
+        },
+        404 => GitHubOperationError::NotFound {
+            resource: format!("repository {repository}"),
+        },
+        500..=599 => GitHubOperationError::Transient {
+            message: format!("creating pull request returned HTTP {}", response.status),
+        },
+        status => GitHubOperationError::ParseFailure {
+            message: format!("creating pull request returned HTTP {status}: {message}"),
+        },
+    }
+}
+
+impl GithubClient {
+    /// Opens a pull request from `head` into `base`; the body of
+    /// `PullRequestManager::create_pull_request`.
+    pub(crate) async fn open_pull_request(
+        &self,
+        repository: &RepositoryId,
+        title: &str,
+        body: &str,
+        head: &BranchName,
+        base: &BranchName,
+        draft: bool,
+    ) -> Result<PullRequest, GitHubOperationError> {
+        let request = create_pull_request_body(title, body, head, base, draft);
+        let response = self
+            .rate_limited()
+            .execute(EndpointClass::Core, || {
+                self.send_create_pull_request(repository, &request)
+            })
+            .await?;
+        match classify_create_rejection(response.status, &response.body) {
+            Some(CreatePullRequestRejection::EmptyDiff) => {
+                tracing::info!(%head, %base, "no commits to open a pull request for");
+                Err(GitHubOperationError::EmptyDiff {
+                    head: head.clone(),
+                    base: base.clone(),
+                })
+            }
+            Some(CreatePullRequestRejection::AlreadyExists) => {
+                Err(self.existing_pull_request(repository, head, base).await?)
+            }
+            None if (200..300).contains(&response.status) => {
+                parse_created_pull_request(&response.body)
+            }
+            None => Err(create_status_error(repository, &response)),
+        }
+    }
+
+    /// Finds the open pull request from `head` into `base` after GitHub
+    /// reported that one exists, returned as
+    /// [`GitHubOperationError::PullRequestAlreadyExists`].
+    ///
+    /// # Errors
+    ///
+    /// Errors from [`GithubClient::list_pull_requests`]. If no open PR
+    /// is found (it was closed in between), returns
+    /// [`GitHubOperationError::Transient`] so creation is retried.
+    async fn existing_pull_request(
+        &self,
+        repository: &RepositoryId,
+        head: &BranchName,
+        base: &BranchName,
+    ) -> Result<GitHubOperationError, GitHubOperationError> {
+        let filter = PullRequestFilter {
+            base_branch: Some(base.clone()),
+            head_branch: Some(head.clone()),
+            state: Some(PullRequestStateFilter::Open),
+        };
+        let existing = self.list_pull_requests(repository, &filter).await?;
+        match existing.first() {
+            Some(pr) => {
+                tracing::info!(existing = %pr.id, %head, "pull request already exists");
+                Ok(GitHubOperationError::PullRequestAlreadyExists { existing: pr.id })
+            }
+            None => Err(GitHubOperationError::Transient {
+                message: format!(
+                    "GitHub reported an existing pull request from {head} but none is open"
+                ),
+            }),
+        }
+    }
+
+    /// Sends `POST /repos/{owner}/{repo}/pulls` with `request`.
+    ///
+    /// Called inside [`GithubClient::rate_limited`], so it goes to the
+    /// transport directly; the response is returned whatever its status.
+    ///
+    /// # Errors
+    ///
+    /// As for [`GithubClient::send_unmetered`].
+    async fn send_create_pull_request(
+        &self,
+        repository: &RepositoryId,
+        request: &JsonValue,
+    ) -> Result<RestResponse, GitHubOperationError> {
+        self.send_unmetered(RestRequest::post(
+            format!("{}/pulls", repository_path(repository)),
+            request.clone(),
+        ))
+        .await
+    }
+
+    /// Reads pull request `id`; the body of
+    /// `PullRequestManager::get_pull_request`.
+    ///
+    /// The single-PR response carries no reviews, so the review status is
+    /// empty, as for a created PR.
+    ///
+    /// # Errors
+    ///
+    /// - The [`status_error`] of a non-success response.
+    /// - Any error from [`parse_created_pull_request`].
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   [transport](crate::transport).
+    pub(crate) async fn read_pull_request(
+        &self,
+        repository: &RepositoryId,
+        id: PullRequestId,
+    ) -> Result<PullRequest, GitHubOperationError> {
+        let response = self
+            .send(RestRequest::get(pull_request_path(repository, id)))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("pull request #{id}")) {
+            return Err(error);
+        }
+        parse_created_pull_request(&response.body)
+    }
+
+    /// Replaces the body of pull request `id`; the body of
+    /// `PullRequestManager::update_pull_request_body`.
+    ///
+    /// # Errors
+    ///
+    /// - The [`status_error`] of a non-success response.
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   [transport](crate::transport).
+    pub(crate) async fn patch_pull_request_body(
+        &self,
+        repository: &RepositoryId,
+        id: PullRequestId,
+        body: &str,
+    ) -> Result<(), GitHubOperationError> {
+        let response = self
+            .send(RestRequest::patch(
+                pull_request_path(repository, id),
+                json!({ "body": body }),
+            ))
+            .await?;
+        if let Some(error) = status_error(&response, &format!("pull request #{id}")) {
+            return Err(error);
+        }
+        tracing::debug!(%id, "pull request body updated");
+        Ok(())
+    }
+
+    /// Lists the pull requests of `repository` that match `filter`; the body
+    /// of `PullRequestManager::find_pull_requests`.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::PaginationLimitExceeded`] — page
+    ///   [`MAX_PULL_REQUEST_PAGES`] still links a next page.
+    /// - The [`status_error`] of a non-success response.
+    /// - Any error from [`parse_pull_requests_page`].
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   [transport](crate::transport).
+    pub(crate) async fn list_pull_requests(
+        &self,
+        repository: &RepositoryId,
+        filter: &PullRequestFilter,
+    ) -> Result<Vec<PullRequest>, GitHubOperationError> {
+        let mut prs = Vec::new();
+        let mut page = 1;
+        loop {
+            let response = self
+                .send(RestRequest::get(pull_requests_path(
+                    repository, filter, page,
+                )))
+                .await?;
+            if let Some(error) = status_error(&response, &format!("pull requests of {repository}"))
+            {
+                return Err(error);
+            }
+            prs.extend(parse_pull_requests_page(&response.body)?);
+            if !has_next_page(response.header(LINK_HEADER)) {
+                tracing::debug!(pages = page, prs = prs.len(), "listed pull requests");
+                return Ok(prs);
+            }
+            if page >= MAX_PULL_REQUEST_PAGES {
+                return Err(GitHubOperationError::PaginationLimitExceeded {
+                    max_pages: MAX_PULL_REQUEST_PAGES,
+                });
+            }
+            page += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "pull_requests_tests.rs"]
+mod tests;
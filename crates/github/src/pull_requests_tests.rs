@@ -0,0 +1,480 @@
+use std::sync::Arc;
+
+use pipeline::github::PullRequestManager;
+use serde_json::json;
+
+use crate::{
+    test_support::{client, repository},
+    transport::{RestMethod, ScriptedTransport, REST_TRANSPORT_CAPABILITY},
+};
+
+use super::*;
+
+fn branch(name: &str) -> BranchName {
+    BranchName::new(name).unwrap()
+}
+
+/// A recorded `POST /repos/octo/widgets/pulls` response, trimmed to the
+/// fields that are read.
+fn pull_request_response(number: u64, head: &str) -> JsonValue {
+    json!({
+        "url": format!("https://api.github.com/repos/octo/widgets/pulls/{number}"),
+        "number": number,
+        "state": "open",
+        "title": "Add dark mode",
+        "body": "Closes #42",
+        "user": { "login": "cogworks[bot]", "type": "Bot" },
+        "created_at": "2026-10-15T09:00:00Z",
+        "draft": true,
+        "merged": false,
+        "head": {
+            "ref": head,
+            "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+            "repo": { "full_name": "octo/widgets" }
+        },
+        "base": {
+            "ref": "main",
+            "sha": "aa218f56b14c9653891f9e74264a383fa43fefbd",
+            "repo": { "full_name": "octo/widgets" }
+        }
+    })
+}
+
+fn rejection(message: &str) -> JsonValue {
+    json!({
+        "message": "Validation Failed",
+        "errors": [{ "resource": "PullRequest", "code": "custom", "message": message }],
+        "documentation_url": "https://docs.github.com/rest/pulls/pulls#create-a-pull-request"
+    })
+}
+
+fn linked_page(status: u16, body: JsonValue, next: bool) -> RestResponse {
+    let mut headers = Vec::new();
+    if next {
+        headers.push((
+            "Link".to_string(),
+            r#"<https://api.github.com/repositories/1/pulls?page=2>; rel="next""#.to_string(),
+        ));
+    }
+    RestResponse {
+        status,
+        headers,
+        body,
+    }
+}
+
+// ─── Request body and rejections ────────────────────────────────────────────
+
+#[test]
+fn test_create_pull_request_body_draft_flag_mapped_to_draft_field() {
+    let body = create_pull_request_body(
+        "Add dark mode",
+        "Closes #42",
+        &branch("cogworks/42/code"),
+        &branch("main"),
+        true,
+    );
+
+    assert_eq!(
+        body,
+        json!({
+            "title": "Add dark mode",
+            "body": "Closes #42",
+            "head": "cogworks/42/code",
+            "base": "main",
+            "draft": true,
+        })
+    );
+}
+
+#[test]
+fn test_classify_create_rejection_no_commits_message_returns_empty_diff() {
+    let response = rejection("No commits between main and cogworks/42/code");
+
+    assert_eq!(
+        classify_create_rejection(422, &response),
+        Some(CreatePullRequestRejection::EmptyDiff)
+    );
+}
+
+#[test]
+fn test_classify_create_rejection_existing_pr_message_returns_already_exists() {
+    let response = rejection("A pull request already exists for octo:cogworks/42/code.");
+
+    assert_eq!(
+        classify_create_rejection(422, &response),
+        Some(CreatePullRequestRejection::AlreadyExists)
+    );
+}
+
+#[test]
+fn test_classify_create_rejection_other_status_returns_none() {
+    let response = rejection("No commits between main and cogworks/42/code");
+
+    assert_eq!(classify_create_rejection(400, &response), None);
+    assert_eq!(
+        classify_create_rejection(422, &rejection("head is invalid")),
+        None
+    );
+}
+
+// ─── Parsing ────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_created_pull_request_recorded_response_maps_fields() {
+    let pr = parse_created_pull_request(&pull_request_response(7, "cogworks/42/code")).unwrap();
+
+    assert_eq!(pr.id, PullRequestId::new(7));
+    assert_eq!(pr.repository, repository());
+    assert_eq!(pr.author, "cogworks[bot]");
+    assert_eq!(pr.head_branch, branch("cogworks/42/code"));
+    assert_eq!(pr.base_branch, branch("main"));
+    assert!(pr.is_open);
+    assert!(!pr.is_merged);
+    assert_eq!(pr.review_status.approvals, 0);
+}
+
+#[test]
+fn test_parse_created_pull_request_empty_head_ref_returns_parse_failure() {
+    let response = pull_request_response(7, "");
+
+    assert!(matches!(
+        parse_created_pull_request(&response),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_parse_pull_requests_page_not_an_array_returns_parse_failure() {
+    let body = pull_request_response(7, "cogworks/42/code");
+
+    assert!(matches!(
+        parse_pull_requests_page(&body),
+        Err(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_parse_pull_requests_page_recorded_response_maps_each_entry() {
+    let body = json!([
+        pull_request_response(7, "cogworks/42/code"),
+        pull_request_response(8, "cogworks/43/code"),
+    ]);
+
+    let prs = parse_pull_requests_page(&body).unwrap();
+
+    let ids: Vec<_> = prs.iter().map(|pr| pr.id).collect();
+    assert_eq!(ids, vec![PullRequestId::new(7), PullRequestId::new(8)]);
+}
+
+// ─── pull_requests_path ─────────────────────────────────────────────────────
+
+#[test]
+fn test_pull_requests_path_head_and_base_qualifies_head_with_owner() {
+    let filter = PullRequestFilter {
+        base_branch: Some(branch("main")),
+        head_branch: Some(branch("cogworks/42/code")),
+        state: Some(PullRequestStateFilter::Open),
+    };
+
+    assert_eq!(
+        pull_requests_path(&repository(), &filter, 1),
+        "/repos/octo/widgets/pulls?state=open&per_page=100&page=1\
+         &head=octo%3Acogworks%2F42%2Fcode&base=main"
+    );
+}
+
+#[test]
+fn test_pull_requests_path_no_state_lists_all_states() {
+    assert_eq!(
+        pull_requests_path(&repository(), &PullRequestFilter::default(), 2),
+        "/repos/octo/widgets/pulls?state=all&per_page=100&page=2"
+    );
+}
+
+// ─── create_pull_request ────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_create_pull_request_created_posts_body_and_returns_pr() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(201, pull_request_response(7, "cogworks/42/code"));
+
+    let pr = client(&transport)
+        .create_pull_request(
+            &repository(),
+            "Add dark mode",
+            "Closes #42",
+            &branch("cogworks/42/code"),
+            &branch("main"),
+            true,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(pr.id, PullRequestId::new(7));
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Post);
+    assert_eq!(requests[0].path, "/repos/octo/widgets/pulls");
+    assert_eq!(
+        requests[0].body,
+        Some(create_pull_request_body(
+            "Add dark mode",
+            "Closes #42",
+            &branch("cogworks/42/code"),
+            &branch("main"),
+            true,
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_create_pull_request_no_commits_returns_empty_diff() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(
+        422,
+        rejection("No commits between main and cogworks/42/code"),
+    );
+
+    let result = client(&transport)
+        .create_pull_request(
+            &repository(),
+            "Add dark mode",
+            "",
+            &branch("cogworks/42/code"),
+            &branch("main"),
+            false,
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::EmptyDiff { head, base })
+            if head == branch("cogworks/42/code") && base == branch("main")
+    ));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_create_pull_request_already_exists_returns_existing_number() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(
+        422,
+        rejection("A pull request already exists for octo:cogworks/42/code."),
+    );
+    transport.push_json(200, json!([pull_request_response(7, "cogworks/42/code")]));
+
+    let result = client(&transport)
+        .create_pull_request(
+            &repository(),
+            "Add dark mode",
+            "",
+            &branch("cogworks/42/code"),
+            &branch("main"),
+            false,
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::PullRequestAlreadyExists { existing })
+            if existing == PullRequestId::new(7)
+    ));
+    let requests = transport.requests();
+    assert_eq!(requests[1].method, RestMethod::Get);
+    assert_eq!(
+        requests[1].path,
+        "/repos/octo/widgets/pulls?state=open&per_page=100&page=1\
+         &head=octo%3Acogworks%2F42%2Fcode&base=main"
+    );
+}
+
+#[tokio::test]
+async fn test_create_pull_request_already_exists_but_none_open_returns_transient() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(
+        422,
+        rejection("A pull request already exists for octo:cogworks/42/code."),
+    );
+    transport.push_json(200, json!([]));
+
+    let result = client(&transport)
+        .create_pull_request(
+            &repository(),
+            "Add dark mode",
+            "",
+            &branch("cogworks/42/code"),
+            &branch("main"),
+            false,
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::Transient { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_create_pull_request_missing_repository_returns_not_found() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client(&transport)
+        .create_pull_request(
+            &repository(),
+            "Add dark mode",
+            "",
+            &branch("cogworks/42/code"),
+            &branch("main"),
+            false,
+        )
+        .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+// ─── find_pull_requests ─────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_find_pull_requests_two_pages_follows_next_link() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push(Ok(linked_page(
+        200,
+        json!([pull_request_response(7, "cogworks/42/code")]),
+        true,
+    )));
+    transport.push(Ok(linked_page(
+        200,
+        json!([pull_request_response(8, "cogworks/43/code")]),
+        false,
+    )));
+
+    let prs = client(&transport)
+        .find_pull_requests(&repository(), &PullRequestFilter::default())
+        .await
+        .unwrap();
+
+    assert_eq!(prs.len(), 2);
+    let paths: Vec<_> = transport
+        .requests()
+        .into_iter()
+        .map(|request| request.path)
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/repos/octo/widgets/pulls?state=all&per_page=100&page=1",
+            "/repos/octo/widgets/pulls?state=all&per_page=100&page=2",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_find_pull_requests_next_link_past_cap_returns_pagination_limit() {
+    let transport = Arc::new(ScriptedTransport::new());
+    for _ in 0..MAX_PULL_REQUEST_PAGES {
+        transport.push(Ok(linked_page(200, json!([]), true)));
+    }
+
+    let result = client(&transport)
+        .find_pull_requests(&repository(), &PullRequestFilter::default())
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::PaginationLimitExceeded { max_pages })
+            if max_pages == MAX_PULL_REQUEST_PAGES
+    ));
+}
+
+#[tokio::test]
+async fn test_find_pull_requests_error_status_returns_error() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client(&transport)
+        .find_pull_requests(&repository(), &PullRequestFilter::default())
+        .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_find_pull_requests_without_transport_returns_capability_missing() {
+    let client = GithubClient::new(Arc::new(()));
+
+    let result = client
+        .find_pull_requests(&repository(), &PullRequestFilter::default())
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::SdkCapabilityMissing { capability })
+            if capability == REST_TRANSPORT_CAPABILITY
+    ));
+}
+
+// ─── get_pull_request / update_pull_request_body ────────────────────────────
+
+#[tokio::test]
+async fn test_get_pull_request_reads_pull_by_number() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, pull_request_response(57, "cogworks/42-dark-mode"));
+
+    let pr = client(&transport)
+        .get_pull_request(&repository(), PullRequestId::new(57))
+        .await
+        .unwrap();
+
+    assert_eq!(pr.id, PullRequestId::new(57));
+    assert_eq!(pr.body, "Closes #42");
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Get);
+    assert_eq!(requests[0].path, "/repos/octo/widgets/pulls/57");
+}
+
+#[tokio::test]
+async fn test_get_pull_request_missing_returns_not_found() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, json!({ "message": "Not Found" }));
+
+    let result = client(&transport)
+        .get_pull_request(&repository(), PullRequestId::new(57))
+        .await;
+
+    assert!(matches!(result, Err(GitHubOperationError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_update_pull_request_body_patches_body() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, pull_request_response(57, "cogworks/42-dark-mode"));
+
+    client(&transport)
+        .update_pull_request_body(&repository(), PullRequestId::new(57), "Closes #42")
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RestMethod::Patch);
+    assert_eq!(requests[0].path, "/repos/octo/widgets/pulls/57");
+    assert_eq!(requests[0].body, Some(json!({ "body": "Closes #42" })));
+}
+
+#[tokio::test]
+async fn test_update_pull_request_body_forbidden_returns_permission_denied() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(403, json!({ "message": "Forbidden" }));
+
+    let result = client(&transport)
+        .update_pull_request_body(&repository(), PullRequestId::new(57), "Closes #42")
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::PermissionDenied { .. })
+    ));
+}
@@ -0,0 +1,251 @@
+//! Endpoint-class-aware rate-limit tracking.
+//!
+//! GitHub meters the core REST API, the search API, and GraphQL against
+//! separate budgets, each with its own reset time. A search-limit 403 says
+//! nothing about whether core REST calls may proceed, so limits are tracked
+//! per [`EndpointClass`] and a throttle in one class never blocks another.
+//!
+//! Two kinds of limit are recorded:
+//!
+//! - **Primary** — `x-ratelimit-remaining: 0`; the class is blocked until
+//!   `x-ratelimit-reset` (epoch seconds).
+//! - **Secondary** — a 403 or 429 carrying `Retry-After`; the class is blocked
+//!   for that many seconds from when the response was observed.
+//!
+//! GraphQL also reports its point budget in the response body (see
+//! [`crate::graphql::parse_rate_limit`]). [`RateLimitTracker::observe_graphql`]
+//! keeps the latest report and blocks the GraphQL class until the reset once
+//! fewer than the reserve points remain, so an expensive query never drains
+//! the budget to zero.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Rate limiting.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use tracing::warn;
+
+use pipeline::github::GitHubOperationError;
+
+use crate::graphql::GraphQlRateLimit;
+
+/// Header naming the budget a response was metered against.
+const RESOURCE_HEADER: &str = "x-ratelimit-resource";
+/// Header carrying the remaining request count in the current window.
+const REMAINING_HEADER: &str = "x-ratelimit-remaining";
+/// Header carrying the window reset time in epoch seconds.
+const RESET_HEADER: &str = "x-ratelimit-reset";
+/// Header carrying the secondary-limit back-off in seconds.
+const RETRY_AFTER_HEADER: &str = "retry-after";
+
+/// GraphQL points held in reserve by [`RateLimitTracker::default`]; below
+/// this the GraphQL class is throttled until the window resets.
+pub const DEFAULT_GRAPHQL_POINT_RESERVE: u32 = 100;
+
+// ─── Endpoint class ─────────────────────────────────────────────────────────
+
+/// A GitHub API budget with its own limit and reset time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndpointClass {
+    /// Core REST API (issues, pulls, contents, ...).
+    Core,
+    /// Search REST API (`/search/...`).
+    Search,
+    /// GraphQL API (`/graphql`).
+    GraphQl,
+}
+
+impl EndpointClass {
+    /// Classifies a request by its API path (e.g. `/search/issues`).
+    pub fn for_path(path: &str) -> Self {
+        let path = path.trim_start_matches('/');
+        if path == "graphql" || path.starts_with("graphql?") {
+            Self::GraphQl
+        } else if path.starts_with("search/") {
+            Self::Search
+        } else {
+            Self::Core
+        }
+    }
+
+    /// Maps the `x-ratelimit-resource` header value to a class.
+    ///
+    /// `code_search` is metered as search. Returns `None` for resources the
+    /// client does not call (e.g. `integration_manifest`).
+    pub fn from_resource(resource: &str) -> Option<Self> {
+        match resource {
+            "core" => Some(Self::Core),
+            "search" | "code_search" => Some(Self::Search),
+            "graphql" => Some(Self::GraphQl),
+            _ => None,
+        }
+    }
+}
+
+// ─── Tracker ────────────────────────────────────────────────────────────────
+
+/// Per-class throttle state shared by all requests made through one client.
+///
+/// Each class records the time before which no request should be sent. The
+/// tracker never sleeps; callers check [`RateLimitTracker::check`] before a
+/// request and surface the error to the retry layer.
+#[derive(Debug)]
+pub struct RateLimitTracker {
+    blocked_until: Mutex<HashMap<EndpointClass, DateTime<Utc>>>,
+    /// Latest GraphQL point budget reported by a query.
+    graphql_budget: Mutex<Option<GraphQlRateLimit>>,
+    /// Points below which GraphQL requests are throttled.
+    graphql_reserve: u32,
+}
+
+impl Default for RateLimitTracker {
+    fn default() -> Self {
+        Self::with_graphql_reserve(DEFAULT_GRAPHQL_POINT_RESERVE)
+    }
+}
+
+impl RateLimitTracker {
+    /// Creates a tracker that throttles GraphQL once fewer than `reserve`
+    /// points remain.
+    pub fn with_graphql_reserve(reserve: u32) -> Self {
+        Self {
+            blocked_until: Mutex::new(HashMap::new()),
+            graphql_budget: Mutex::new(None),
+            graphql_reserve: reserve,
+        }
+    }
+
+    /// Returns the latest GraphQL point budget, if a query has reported one.
+    pub fn graphql_budget(&self) -> Option<GraphQlRateLimit> {
+        *self
+            .graphql_budget
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Returns the time `class` is throttled until, if it is throttled at
+    /// `now`.
+    pub fn throttled_until(
+        &self,
+        class: EndpointClass,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        self.entries()
+            .get(&class)
+            .copied()
+            .filter(|until| *until > now)
+    }
+
+    /// Checks whether a request in `class` may be sent at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitHubOperationError::RateLimitExhausted`] with the class's
+    /// reset time if it is still throttled.
+    pub fn check(
+        &self,
+        class: EndpointClass,
+        now: DateTime<Utc>,
+    ) -> Result<(), GitHubOperationError> {
+        match self.throttled_until(class, now) {
+            Some(reset_at) => Err(GitHubOperationError::RateLimitExhausted { reset_at }),
+            None => Ok(()),
+        }
+    }
+
+    /// Records the rate-limit headers of a response to a request in `class`.
+    ///
+    /// `header` looks up a response header by lower-case name. If the
+    /// response names its budget in `x-ratelimit-resource`, that class is
+    /// used instead of `class`, so the reset source always matches the
+    /// budget GitHub actually metered.
+    ///
+    /// Returns the throttle error to surface if the response was a rate-limit
+    /// rejection (`status` 403 or 429 with an exhausted budget or a
+    /// `Retry-After` header), otherwise `None`.
+    pub fn observe<'h>(
+        &self,
+        class: EndpointClass,
+        status: u16,
+        now: DateTime<Utc>,
+        header: impl Fn(&str) -> Option<&'h str>,
+    ) -> Option<GitHubOperationError> {
+        let class = header(RESOURCE_HEADER)
+            .and_then(EndpointClass::from_resource)
+            .unwrap_or(class);
+
+        let primary_reset = header(REMAINING_HEADER)
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|remaining| *remaining == 0)
+            .and_then(|_| header(RESET_HEADER))
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .and_then(|epoch| DateTime::<Utc>::from_timestamp(epoch, 0));
+
+        let secondary_reset = matches!(status, 403 | 429)
+            .then(|| header(RETRY_AFTER_HEADER))
+            .flatten()
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .and_then(TimeDelta::try_seconds)
+            .and_then(|delay| now.checked_add_signed(delay));
+
+        let reset_at = match (primary_reset, secondary_reset) {
+            (Some(a), Some(b)) => a.max(b),
+            (a, b) => a.or(b)?,
+        };
+        self.block(class, reset_at);
+
+        matches!(status, 403 | 429).then(|| {
+            warn!(?class, %reset_at, "GitHub rate limit hit");
+            GitHubOperationError::RateLimitExhausted { reset_at }
+        })
+    }
+
+    /// Records the point budget reported by a GraphQL query.
+    ///
+    /// A report from an earlier window than the one already held is ignored.
+    /// If fewer than the reserve points remain, the GraphQL class is blocked
+    /// until `reset_at`, so [`RateLimitTracker::check`] rejects further
+    /// GraphQL requests before GitHub does.
+    pub fn observe_graphql(&self, rate_limit: GraphQlRateLimit) {
+        {
+            let mut budget = self
+                .graphql_budget
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if budget.is_some_and(|held| held.reset_at > rate_limit.reset_at) {
+                return;
+            }
+            *budget = Some(rate_limit);
+        }
+        if rate_limit.remaining < self.graphql_reserve {
+            warn!(
+                cost = rate_limit.cost,
+                remaining = rate_limit.remaining,
+                reset_at = %rate_limit.reset_at,
+                "GraphQL point budget low; throttling until reset"
+            );
+            self.block(EndpointClass::GraphQl, rate_limit.reset_at);
+        }
+    }
+
+    /// Blocks `class` until `reset_at`, keeping any later existing block.
+    pub(crate) fn block(&self, class: EndpointClass, reset_at: DateTime<Utc>) {
+        let mut entries = self.entries();
+        let until = entries.entry(class).or_insert(reset_at);
+        if *until < reset_at {
+            *until = reset_at;
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<EndpointClass, DateTime<Utc>>> {
+        self.blocked_until
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+#[path = "rate_limit_tests.rs"]
+mod tests;
@@ -0,0 +1,260 @@
+use chrono::TimeZone;
+
+use super::*;
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+}
+
+fn headers<'h>(pairs: &'h [(&'h str, &'h str)]) -> impl Fn(&str) -> Option<&'h str> {
+    move |name| {
+        pairs
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+// ─── EndpointClass ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_for_path_search_graphql_and_rest_paths_classified() {
+    assert_eq!(
+        EndpointClass::for_path("/search/issues?q=x"),
+        EndpointClass::Search
+    );
+    assert_eq!(EndpointClass::for_path("/graphql"), EndpointClass::GraphQl);
+    assert_eq!(EndpointClass::for_path("graphql"), EndpointClass::GraphQl);
+    assert_eq!(
+        EndpointClass::for_path("/repos/o/r/issues/1"),
+        EndpointClass::Core
+    );
+    assert_eq!(
+        EndpointClass::for_path("/repos/o/r/search/x"),
+        EndpointClass::Core
+    );
+}
+
+#[test]
+fn test_from_resource_known_resources_mapped() {
+    assert_eq!(
+        EndpointClass::from_resource("core"),
+        Some(EndpointClass::Core)
+    );
+    assert_eq!(
+        EndpointClass::from_resource("code_search"),
+        Some(EndpointClass::Search)
+    );
+    assert_eq!(
+        EndpointClass::from_resource("graphql"),
+        Some(EndpointClass::GraphQl)
+    );
+    assert_eq!(EndpointClass::from_resource("integration_manifest"), None);
+}
+
+// ─── RateLimitTracker ───────────────────────────────────────────────────────
+
+#[test]
+fn test_observe_search_secondary_limit_does_not_throttle_core() {
+    let tracker = RateLimitTracker::default();
+
+    let error = tracker.observe(
+        EndpointClass::Search,
+        403,
+        now(),
+        headers(&[("retry-after", "60")]),
+    );
+
+    let reset_at = now() + TimeDelta::seconds(60);
+    assert!(matches!(
+        error,
+        Some(GitHubOperationError::RateLimitExhausted { reset_at: r }) if r == reset_at
+    ));
+    assert!(tracker.check(EndpointClass::Search, now()).is_err());
+    assert!(tracker.check(EndpointClass::Core, now()).is_ok());
+    assert!(tracker.check(EndpointClass::GraphQl, now()).is_ok());
+}
+
+#[test]
+fn test_observe_core_primary_limit_uses_core_reset_only() {
+    let tracker = RateLimitTracker::default();
+    let reset_at = now() + TimeDelta::minutes(30);
+    let reset = reset_at.timestamp().to_string();
+
+    let error = tracker.observe(
+        EndpointClass::Core,
+        403,
+        now(),
+        headers(&[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", &reset),
+        ]),
+    );
+
+    assert!(error.is_some());
+    assert_eq!(
+        tracker.throttled_until(EndpointClass::Core, now()),
+        Some(reset_at)
+    );
+    assert_eq!(tracker.throttled_until(EndpointClass::Search, now()), None);
+}
+
+#[test]
+fn test_observe_search_and_core_limits_reset_independently() {
+    let tracker = RateLimitTracker::default();
+    let core_reset = (now() + TimeDelta::minutes(30)).timestamp().to_string();
+    tracker.observe(
+        EndpointClass::Core,
+        403,
+        now(),
+        headers(&[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", &core_reset),
+        ]),
+    );
+    tracker.observe(
+        EndpointClass::Search,
+        429,
+        now(),
+        headers(&[("retry-after", "60")]),
+    );
+
+    let later = now() + TimeDelta::minutes(2);
+
+    assert!(tracker.check(EndpointClass::Search, later).is_ok());
+    assert!(tracker.check(EndpointClass::Core, later).is_err());
+}
+
+#[test]
+fn test_observe_resource_header_overrides_request_class() {
+    let tracker = RateLimitTracker::default();
+
+    tracker.observe(
+        EndpointClass::Core,
+        403,
+        now(),
+        headers(&[("x-ratelimit-resource", "search"), ("retry-after", "30")]),
+    );
+
+    assert!(tracker.check(EndpointClass::Search, now()).is_err());
+    assert!(tracker.check(EndpointClass::Core, now()).is_ok());
+}
+
+#[test]
+fn test_observe_success_with_budget_left_does_not_throttle() {
+    let tracker = RateLimitTracker::default();
+
+    let error = tracker.observe(
+        EndpointClass::Core,
+        200,
+        now(),
+        headers(&[("x-ratelimit-remaining", "4999"), ("retry-after", "60")]),
+    );
+
+    assert!(error.is_none());
+    assert_eq!(tracker.throttled_until(EndpointClass::Core, now()), None);
+}
+
+#[test]
+fn test_observe_exhausted_budget_on_success_blocks_without_error() {
+    let tracker = RateLimitTracker::default();
+    let reset = (now() + TimeDelta::minutes(5)).timestamp().to_string();
+
+    let error = tracker.observe(
+        EndpointClass::Core,
+        200,
+        now(),
+        headers(&[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", &reset),
+        ]),
+    );
+
+    assert!(error.is_none());
+    assert!(tracker.check(EndpointClass::Core, now()).is_err());
+}
+
+#[test]
+fn test_block_earlier_reset_keeps_later_block() {
+    let tracker = RateLimitTracker::default();
+    let later = now() + TimeDelta::minutes(10);
+    tracker.block(EndpointClass::Core, later);
+
+    tracker.block(EndpointClass::Core, now() + TimeDelta::minutes(1));
+
+    assert_eq!(
+        tracker.throttled_until(EndpointClass::Core, now()),
+        Some(later)
+    );
+}
+
+#[test]
+fn test_check_after_reset_allows_request() {
+    let tracker = RateLimitTracker::default();
+    tracker.block(EndpointClass::GraphQl, now() + TimeDelta::seconds(1));
+
+    assert!(tracker
+        .check(EndpointClass::GraphQl, now() + TimeDelta::seconds(1))
+        .is_ok());
+}
+
+// ─── GraphQL point budget ───────────────────────────────────────────────────
+
+fn points(cost: u32, remaining: u32, reset_at: DateTime<Utc>) -> GraphQlRateLimit {
+    GraphQlRateLimit {
+        cost,
+        remaining,
+        reset_at,
+    }
+}
+
+#[test]
+fn test_observe_graphql_above_reserve_records_budget_without_throttling() {
+    let tracker = RateLimitTracker::with_graphql_reserve(100);
+    let report = points(5, 4_000, now() + TimeDelta::minutes(30));
+
+    tracker.observe_graphql(report);
+
+    assert_eq!(tracker.graphql_budget(), Some(report));
+    assert!(tracker.check(EndpointClass::GraphQl, now()).is_ok());
+}
+
+#[test]
+fn test_observe_graphql_below_reserve_blocks_graphql_until_reset() {
+    let tracker = RateLimitTracker::with_graphql_reserve(100);
+    let reset_at = now() + TimeDelta::minutes(30);
+
+    tracker.observe_graphql(points(50, 99, reset_at));
+
+    assert!(matches!(
+        tracker.check(EndpointClass::GraphQl, now()),
+        Err(GitHubOperationError::RateLimitExhausted { reset_at: until }) if until == reset_at
+    ));
+    assert!(tracker.check(EndpointClass::Core, now()).is_ok());
+}
+
+#[test]
+fn test_observe_graphql_at_reserve_does_not_throttle() {
+    let tracker = RateLimitTracker::with_graphql_reserve(100);
+
+    tracker.observe_graphql(points(1, 100, now() + TimeDelta::minutes(30)));
+
+    assert!(tracker.check(EndpointClass::GraphQl, now()).is_ok());
+}
+
+#[test]
+fn test_observe_graphql_report_from_earlier_window_ignored() {
+    let tracker = RateLimitTracker::default();
+    let current = points(1, 4_000, now() + TimeDelta::minutes(30));
+    tracker.observe_graphql(current);
+
+    tracker.observe_graphql(points(1, 10, now() - TimeDelta::minutes(30)));
+
+    assert_eq!(tracker.graphql_budget(), Some(current));
+    assert!(tracker.check(EndpointClass::GraphQl, now()).is_ok());
+}
+
+#[test]
+fn test_graphql_budget_before_any_query_returns_none() {
+    assert_eq!(RateLimitTracker::default().graphql_budget(), None);
+}
@@ -0,0 +1,317 @@
+//! Rate-limit-aware execution of REST requests.
+//!
+//! [`RateLimitedClient`] wraps every request the client sends. Before a
+//! request it consults the [`RateLimitTracker`]: if the request's endpoint
+//! class is throttled, it sleeps until the reset when that is within the
+//! configured ceiling and fails with
+//! [`GitHubOperationError::RateLimitExhausted`] otherwise, so the retry layer
+//! can schedule the call for later instead of holding a task for up to an
+//! hour. After a response it records the rate-limit headers and the remaining
+//! request count.
+//!
+//! Secondary (abuse-detection) limits are reported as a 403 or 429. With a
+//! `Retry-After` header the tracker handles them; without one, a response
+//! whose message mentions a secondary rate limit or abuse detection blocks
+//! the class for [`DEFAULT_SECONDARY_LIMIT_BACKOFF`]. Either way the error is
+//! `RateLimitExhausted`, which `retry_policy()` makes
+//! `RetryPolicy::Retryable { after }`.
+//!
+//! A 403 is also how GitHub reports a permission the App was not granted.
+//! [`status_error`] tells the two apart: a 403 carrying rate-limit signals is
+//! `RateLimitExhausted`, and one naming the expected permission in
+//! `x-accepted-github-permissions` is
+//! [`GitHubOperationError::MissingAppPermission`], which is not retryable.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Rate limiting.
+
+use std::{collections::HashMap, future::Future, sync::Mutex, time::Duration};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde_json::Value as JsonValue;
+use tracing::{debug, warn};
+
+use pipeline::github::GitHubOperationError;
+
+use crate::rate_limit::{EndpointClass, RateLimitTracker};
+
+/// Header in which GitHub names the permissions a denied request needed.
+pub const ACCEPTED_PERMISSIONS_HEADER: &str = "x-accepted-github-permissions";
+
+/// Longest [`RateLimitedClient::execute`] sleeps for a throttled class before
+/// failing instead.
+pub const DEFAULT_MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// How long a class is blocked after a secondary-limit response that carries
+/// no `Retry-After` header (GitHub's documented minimum wait).
+pub const DEFAULT_SECONDARY_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Header carrying the remaining request count in the current window.
+const REMAINING_HEADER: &str = "x-ratelimit-remaining";
+
+/// Phrases in a 403/429 message that identify a secondary rate limit.
+const SECONDARY_LIMIT_MARKERS: [&str; 2] = ["secondary rate limit", "abuse detection"];
+
+/// A REST response as seen by [`RateLimitedClient`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers as `(name, value)` pairs.
+    pub headers: Vec<(String, String)>,
+    /// Parsed JSON body; `Null` when the body was empty.
+    pub body: JsonValue,
+}
+
+impl RestResponse {
+    /// Returns the first header named `name` (case-insensitive).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns `true` if this is a 403 or 429 whose message identifies a
+    /// secondary (abuse-detection) rate limit.
+    pub fn is_secondary_limit(&self) -> bool {
+        matches!(self.status, 403 | 429)
+            && self
+                .body
+                .get("message")
+                .and_then(JsonValue::as_str)
+                .is_some_and(|message| {
+                    let message = message.to_ascii_lowercase();
+                    SECONDARY_LIMIT_MARKERS
+                        .iter()
+                        .any(|marker| message.contains(marker))
+                })
+    }
+
+    /// Returns the permission a 403 says the App is missing, from the
+    /// [`ACCEPTED_PERMISSIONS_HEADER`] (e.g. `checks=write`), or `None` for
+    /// any other response.
+    pub fn missing_permission(&self) -> Option<&str> {
+        (self.status == 403)
+            .then(|| self.header(ACCEPTED_PERMISSIONS_HEADER))
+            .flatten()
+            .map(str::trim)
+            .filter(|permission| !permission.is_empty())
+    }
+
+    /// Returns the [`GitHubOperationError::RateLimitExhausted`] for a 403 or
+    /// 429 that is a primary or secondary rate-limit rejection.
+    fn rate_limit_rejection(&self, now: DateTime<Utc>) -> Option<GitHubOperationError> {
+        if let Some(throttled) =
+            RateLimitTracker::default().observe(EndpointClass::Core, self.status, now, |name| {
+                self.header(name)
+            })
+        {
+            return Some(throttled);
+        }
+        self.is_secondary_limit().then(|| {
+            let backoff = TimeDelta::from_std(DEFAULT_SECONDARY_LIMIT_BACKOFF).unwrap_or_default();
+            GitHubOperationError::RateLimitExhausted {
+                reset_at: now + backoff,
+            }
+        })
+    }
+}
+
+/// Maps a response that is not 2xx to the error to surface for `resource`;
+/// `None` for a 2xx.
+///
+/// Responses sent through [`RateLimitedClient::execute`] never reach this
+/// as rate-limit rejections; other callers get them classified here too.
+///
+/// | Status | Error |
+/// |---|---|
+/// | 404, 410 | [`GitHubOperationError::NotFound`] |
+/// | 403 / 429 with an exhausted budget, `Retry-After`, or a secondary-limit message | [`GitHubOperationError::RateLimitExhausted`] |
+/// | 403 with `x-accepted-github-permissions` | [`GitHubOperationError::MissingAppPermission`] |
+/// | other 401, 403 | [`GitHubOperationError::PermissionDenied`] |
+/// | 5xx | [`GitHubOperationError::Transient`] |
+/// | other | [`GitHubOperationError::ParseFailure`] |
+pub fn status_error(response: &RestResponse, resource: &str) -> Option<GitHubOperationError> {
+    let status = response.status;
+    if (200..300).contains(&status) {
+        return None;
+    }
+    if let Some(throttled) = response.rate_limit_rejection(Utc::now()) {
+        return Some(throttled);
+    }
+    if let Some(permission) = response.missing_permission() {
+        return Some(GitHubOperationError::MissingAppPermission {
+            action: format!("read {resource}"),
+            permission: permission.to_string(),
+        });
+    }
+    Some(match status {
+        404 | 410 => GitHubOperationError::NotFound {
+            resource: resource.to_string(),
+        },
+        401 | 403 => GitHubOperationError::PermissionDenied {
+            action: format!("read {resource}"),
+        },
+        500..=599 => GitHubOperationError::Transient {
+            message: format!("reading {resource} returned HTTP {status}"),
+        },
+        _ => GitHubOperationError::ParseFailure {
+            message: format!("{resource}: unexpected HTTP {status}"),
+        },
+    })
+}
+
+/// Sends requests through the rate-limit tracker.
+///
+/// Holds the client's [`RateLimitTracker`] and the latest remaining request
+/// count per endpoint class.
+#[derive(Debug)]
+pub struct RateLimitedClient {
+    tracker: RateLimitTracker,
+    max_wait: Duration,
+    remaining: Mutex<HashMap<EndpointClass, u32>>,
+}
+
+impl Default for RateLimitedClient {
+    fn default() -> Self {
+        Self::new(RateLimitTracker::default())
+    }
+}
+
+impl RateLimitedClient {
+    /// Creates a client over `tracker` that waits at most
+    /// [`DEFAULT_MAX_RATE_LIMIT_WAIT`] for a throttled class.
+    pub fn new(tracker: RateLimitTracker) -> Self {
+        Self {
+            tracker,
+            max_wait: DEFAULT_MAX_RATE_LIMIT_WAIT,
+            remaining: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the longest wait for a throttled class before failing instead.
+    ///
+    /// `Duration::ZERO` never waits.
+    #[must_use]
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    /// The rate-limit tracker requests are checked against.
+    pub fn tracker(&self) -> &RateLimitTracker {
+        &self.tracker
+    }
+
+    /// Core REST requests remaining in the current window, as last reported
+    /// by GitHub, or `None` before the first core response.
+    pub fn remaining(&self) -> Option<u32> {
+        self.remaining_in(EndpointClass::Core)
+    }
+
+    /// Requests remaining in `class` as last reported by GitHub.
+    pub fn remaining_in(&self, class: EndpointClass) -> Option<u32> {
+        self.remaining
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(&class)
+            .copied()
+    }
+
+    /// Returns how long to wait before sending a request in `class` at
+    /// `now`: zero if it is not throttled.
+    ///
+    /// # Errors
+    ///
+    /// [`GitHubOperationError::RateLimitExhausted`] — the class is throttled
+    /// for longer than the wait ceiling.
+    pub fn wait_before(
+        &self,
+        class: EndpointClass,
+        now: DateTime<Utc>,
+    ) -> Result<Duration, GitHubOperationError> {
+        let Some(reset_at) = self.tracker.throttled_until(class, now) else {
+            return Ok(Duration::ZERO);
+        };
+        let wait = (reset_at - now).to_std().unwrap_or_default();
+        if wait > self.max_wait {
+            return Err(GitHubOperationError::RateLimitExhausted { reset_at });
+        }
+        Ok(wait)
+    }
+
+    /// Records the rate-limit state of `response` to a request in `class`
+    /// and returns the throttle error to surface, if any.
+    ///
+    /// A secondary-limit response without `Retry-After` blocks the class for
+    /// [`DEFAULT_SECONDARY_LIMIT_BACKOFF`] from `now`.
+    pub fn observe(
+        &self,
+        class: EndpointClass,
+        response: &RestResponse,
+        now: DateTime<Utc>,
+    ) -> Option<GitHubOperationError> {
+        if let Some(remaining) = response
+            .header(REMAINING_HEADER)
+            .and_then(|v| v.trim().parse::<u32>().ok())
+        {
+            self.remaining
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .insert(class, remaining);
+        }
+        if let Some(throttled) = self
+            .tracker
+            .observe(class, response.status, now, |name| response.header(name))
+        {
+            return Some(throttled);
+        }
+        if !response.is_secondary_limit() {
+            return None;
+        }
+        let backoff = TimeDelta::from_std(DEFAULT_SECONDARY_LIMIT_BACKOFF).unwrap_or_default();
+        let reset_at = now + backoff;
+        warn!(?class, %reset_at, "GitHub secondary rate limit hit");
+        self.tracker.block(class, reset_at);
+        Some(GitHubOperationError::RateLimitExhausted { reset_at })
+    }
+
+    /// Sends one request in `class` through `send`, waiting out a throttle
+    /// within the ceiling first.
+    ///
+    /// Returns the response for any status that is not a rate-limit
+    /// rejection; callers map other error statuses themselves.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::RateLimitExhausted`] — the class is
+    ///   throttled past the ceiling, or the response was a primary or
+    ///   secondary rate-limit rejection.
+    /// - Any error returned by `send`.
+    pub async fn execute<F, Fut>(
+        &self,
+        class: EndpointClass,
+        send: F,
+    ) -> Result<RestResponse, GitHubOperationError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<RestResponse, GitHubOperationError>>,
+    {
+        let wait = self.wait_before(class, Utc::now())?;
+        if !wait.is_zero() {
+            debug!(?class, ?wait, "waiting for GitHub rate limit reset");
+            tokio::time::sleep(wait).await;
+        }
+        let response = send().await?;
+        match self.observe(class, &response, Utc::now()) {
+            Some(throttled) => Err(throttled),
+            None => Ok(response),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "rate_limited_tests.rs"]
+mod tests;
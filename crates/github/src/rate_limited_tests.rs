@@ -0,0 +1,400 @@
+use std::cell::Cell;
+
+use chrono::TimeZone;
+use pipeline::RetryPolicy;
+use serde_json::json;
+
+use super::*;
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+}
+
+fn response(status: u16, headers: &[(&str, &str)], body: JsonValue) -> RestResponse {
+    RestResponse {
+        status,
+        headers: headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+        body,
+    }
+}
+
+fn exhausted_until(reset_at: DateTime<Utc>) -> RestResponse {
+    let reset = reset_at.timestamp().to_string();
+    response(
+        403,
+        &[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", reset.as_str()),
+        ],
+        json!({ "message": "API rate limit exceeded" }),
+    )
+}
+
+// ─── RestResponse ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_header_lookup_is_case_insensitive() {
+    let response = response(200, &[("X-RateLimit-Remaining", "12")], JsonValue::Null);
+
+    assert_eq!(response.header("x-ratelimit-remaining"), Some("12"));
+    assert_eq!(response.header("etag"), None);
+}
+
+#[test]
+fn test_is_secondary_limit_abuse_message_returns_true() {
+    let response = response(
+        403,
+        &[],
+        json!({ "message": "You have exceeded a secondary rate limit." }),
+    );
+
+    assert!(response.is_secondary_limit());
+}
+
+#[test]
+fn test_is_secondary_limit_other_403_returns_false() {
+    let response = response(403, &[], json!({ "message": "Resource not accessible" }));
+
+    assert!(!response.is_secondary_limit());
+}
+
+#[test]
+fn test_missing_permission_header_on_403_returns_permission() {
+    let response = response(
+        403,
+        &[(ACCEPTED_PERMISSIONS_HEADER, " checks=write ")],
+        JsonValue::Null,
+    );
+
+    assert_eq!(response.missing_permission(), Some("checks=write"));
+}
+
+#[test]
+fn test_missing_permission_blank_header_or_other_status_returns_none() {
+    let blank = response(403, &[(ACCEPTED_PERMISSIONS_HEADER, "  ")], JsonValue::Null);
+    let not_forbidden = response(
+        404,
+        &[(ACCEPTED_PERMISSIONS_HEADER, "checks=write")],
+        JsonValue::Null,
+    );
+
+    assert_eq!(blank.missing_permission(), None);
+    assert_eq!(not_forbidden.missing_permission(), None);
+}
+
+// ─── status_error ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_status_error_success_returns_none() {
+    assert!(status_error(&response(204, &[], JsonValue::Null), "issue #1").is_none());
+}
+
+#[test]
+fn test_status_error_each_status_maps_to_error_kind() {
+    let error = |status| status_error(&response(status, &[], JsonValue::Null), "issue #1");
+
+    assert!(matches!(
+        error(404),
+        Some(GitHubOperationError::NotFound { .. })
+    ));
+    assert!(matches!(
+        error(410),
+        Some(GitHubOperationError::NotFound { .. })
+    ));
+    assert!(matches!(
+        error(401),
+        Some(GitHubOperationError::PermissionDenied { .. })
+    ));
+    assert!(matches!(
+        error(403),
+        Some(GitHubOperationError::PermissionDenied { .. })
+    ));
+    assert!(matches!(
+        error(503),
+        Some(GitHubOperationError::Transient { .. })
+    ));
+    assert!(matches!(
+        error(422),
+        Some(GitHubOperationError::ParseFailure { .. })
+    ));
+}
+
+#[test]
+fn test_status_error_exhausted_403_returns_rate_limit_exhausted() {
+    let reset_at = Utc::now() + TimeDelta::minutes(5);
+
+    let error = status_error(&exhausted_until(reset_at), "issue #1");
+
+    assert!(matches!(
+        error,
+        Some(GitHubOperationError::RateLimitExhausted { .. })
+    ));
+}
+
+#[test]
+fn test_status_error_missing_permission_returns_missing_app_permission() {
+    let response = response(
+        403,
+        &[(ACCEPTED_PERMISSIONS_HEADER, "issues=write")],
+        JsonValue::Null,
+    );
+
+    assert!(matches!(
+        status_error(&response, "issue #1"),
+        Some(GitHubOperationError::MissingAppPermission { permission, .. })
+            if permission == "issues=write"
+    ));
+}
+
+#[test]
+fn test_status_error_missing_permission_is_non_retryable_and_names_permission() {
+    let response = response(
+        403,
+        &[(ACCEPTED_PERMISSIONS_HEADER, "checks=write")],
+        json!({ "message": "Resource not accessible by integration" }),
+    );
+
+    let error = status_error(&response, "check run").unwrap();
+
+    assert_eq!(error.retry_policy(), RetryPolicy::NonRetryable);
+    assert!(error.to_string().contains("checks=write"));
+}
+
+#[test]
+fn test_status_error_exhausted_403_with_permission_header_returns_rate_limit_exhausted() {
+    let reset_at = Utc::now() + TimeDelta::minutes(5);
+    let mut response = exhausted_until(reset_at);
+    response.headers.push((
+        ACCEPTED_PERMISSIONS_HEADER.to_string(),
+        "issues=write".to_string(),
+    ));
+
+    let error = status_error(&response, "issue #1").unwrap();
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::RateLimitExhausted { .. }
+    ));
+    assert!(matches!(
+        error.retry_policy(),
+        RetryPolicy::Retryable { .. }
+    ));
+}
+
+#[test]
+fn test_status_error_secondary_limit_403_returns_retryable_rate_limit_exhausted() {
+    let response = response(
+        403,
+        &[],
+        json!({ "message": "You have exceeded a secondary rate limit." }),
+    );
+
+    let error = status_error(&response, "issue #1").unwrap();
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::RateLimitExhausted { .. }
+    ));
+    assert!(matches!(
+        error.retry_policy(),
+        RetryPolicy::Retryable { .. }
+    ));
+}
+
+// ─── RateLimitedClient ──────────────────────────────────────────────────────
+
+#[test]
+fn test_remaining_before_any_response_returns_none() {
+    assert_eq!(RateLimitedClient::default().remaining(), None);
+}
+
+#[test]
+fn test_observe_remaining_header_records_count_per_class() {
+    let client = RateLimitedClient::default();
+
+    client.observe(
+        EndpointClass::Core,
+        &response(200, &[("x-ratelimit-remaining", "4321")], JsonValue::Null),
+        now(),
+    );
+    client.observe(
+        EndpointClass::Search,
+        &response(200, &[("x-ratelimit-remaining", "29")], JsonValue::Null),
+        now(),
+    );
+
+    assert_eq!(client.remaining(), Some(4321));
+    assert_eq!(client.remaining_in(EndpointClass::Search), Some(29));
+}
+
+#[test]
+fn test_observe_exhausted_budget_returns_retryable_error() {
+    let client = RateLimitedClient::default();
+    let reset_at = now() + TimeDelta::minutes(5);
+
+    let error = client
+        .observe(EndpointClass::Core, &exhausted_until(reset_at), now())
+        .unwrap();
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::RateLimitExhausted { reset_at: r } if r == reset_at
+    ));
+    assert!(matches!(
+        error.retry_policy(),
+        RetryPolicy::Retryable { .. }
+    ));
+}
+
+#[test]
+fn test_observe_secondary_limit_with_retry_after_blocks_for_that_long() {
+    let client = RateLimitedClient::default();
+    let response = response(
+        403,
+        &[("retry-after", "30")],
+        json!({ "message": "You have exceeded a secondary rate limit." }),
+    );
+
+    let error = client.observe(EndpointClass::Core, &response, now());
+
+    assert!(matches!(
+        error,
+        Some(GitHubOperationError::RateLimitExhausted { reset_at })
+            if reset_at == now() + TimeDelta::seconds(30)
+    ));
+}
+
+#[test]
+fn test_observe_secondary_limit_without_retry_after_blocks_for_default_backoff() {
+    let client = RateLimitedClient::default();
+    let response = response(
+        429,
+        &[],
+        json!({ "message": "You have triggered an abuse detection mechanism." }),
+    );
+
+    let error = client.observe(EndpointClass::Core, &response, now());
+
+    let reset_at = now() + TimeDelta::from_std(DEFAULT_SECONDARY_LIMIT_BACKOFF).unwrap();
+    assert!(matches!(
+        error,
+        Some(GitHubOperationError::RateLimitExhausted { reset_at: r }) if r == reset_at
+    ));
+    assert!(client.tracker().check(EndpointClass::Core, now()).is_err());
+}
+
+#[test]
+fn test_wait_before_unthrottled_class_returns_zero() {
+    let client = RateLimitedClient::default();
+
+    assert_eq!(
+        client.wait_before(EndpointClass::Core, now()).unwrap(),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn test_wait_before_reset_within_ceiling_returns_wait() {
+    let client = RateLimitedClient::default();
+    client
+        .tracker()
+        .block(EndpointClass::Core, now() + TimeDelta::seconds(20));
+
+    assert_eq!(
+        client.wait_before(EndpointClass::Core, now()).unwrap(),
+        Duration::from_secs(20)
+    );
+}
+
+#[test]
+fn test_wait_before_reset_past_ceiling_returns_exhausted() {
+    let client = RateLimitedClient::default().with_max_wait(Duration::from_secs(10));
+    client
+        .tracker()
+        .block(EndpointClass::Search, now() + TimeDelta::seconds(20));
+
+    assert!(matches!(
+        client.wait_before(EndpointClass::Search, now()),
+        Err(GitHubOperationError::RateLimitExhausted { .. })
+    ));
+    assert_eq!(
+        client.wait_before(EndpointClass::Core, now()).unwrap(),
+        Duration::ZERO
+    );
+}
+
+#[tokio::test]
+async fn test_execute_success_returns_response_and_records_remaining() {
+    let client = RateLimitedClient::default();
+
+    let result = client
+        .execute(EndpointClass::Core, || async {
+            Ok(response(
+                200,
+                &[("x-ratelimit-remaining", "100")],
+                JsonValue::Null,
+            ))
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(result.status, 200);
+    assert_eq!(client.remaining(), Some(100));
+}
+
+#[tokio::test]
+async fn test_execute_error_status_is_returned_for_caller_to_map() {
+    let client = RateLimitedClient::default();
+
+    let result = client
+        .execute(EndpointClass::Core, || async {
+            Ok(response(404, &[], JsonValue::Null))
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(result.status, 404);
+}
+
+#[tokio::test]
+async fn test_execute_throttled_past_ceiling_does_not_send() {
+    let client = RateLimitedClient::default();
+    client
+        .tracker()
+        .block(EndpointClass::Core, Utc::now() + TimeDelta::hours(1));
+    let sent = Cell::new(false);
+
+    let result = client
+        .execute(EndpointClass::Core, || async {
+            sent.set(true);
+            Ok(response(200, &[], JsonValue::Null))
+        })
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::RateLimitExhausted { .. })
+    ));
+    assert!(!sent.get());
+}
+
+#[tokio::test]
+async fn test_execute_rate_limit_response_returns_exhausted_and_throttles_class() {
+    let client = RateLimitedClient::default();
+    let reset_at = Utc::now() + TimeDelta::hours(1);
+
+    let result = client
+        .execute(EndpointClass::Core, || async {
+            Ok(exhausted_until(reset_at))
+        })
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(GitHubOperationError::RateLimitExhausted { .. })
+    ));
+    assert!(client.wait_before(EndpointClass::Core, Utc::now()).is_err());
+}
@@ -0,0 +1,488 @@
+//! Bounded-memory streaming readers for pull request diffs and repository trees.
+//!
+//! Reading a large diff or a recursive tree of a large repository into a
+//! single `String` or `Vec` can exhaust memory. The readers here consume the
+//! raw response body incrementally and yield one file (diff) or one entry
+//! (tree) at a time. Every reader enforces an overall size cap on the bytes
+//! consumed and fails with [`GitHubOperationError::ResponseTooLarge`] as soon
+//! as the cap is passed, without buffering the rest of the response.
+//!
+//! Both readers follow the `next_*` convention used by
+//! [`pipeline::EventSource::next_event`]: `Ok(Some(_))` for an item,
+//! `Ok(None)` at the end of the stream.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §Streaming readers.
+
+use std::mem;
+
+use serde::Deserialize;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt};
+use tracing::instrument;
+
+use pipeline::{
+    github::{DirectoryEntry, DirectoryEntryKind, GitHubOperationError},
+    GitObjectSha, PullRequestId, RepositoryId,
+};
+
+use crate::GithubClient;
+
+/// Default cap on the bytes a streaming reader consumes (50 MiB).
+pub const DEFAULT_STREAM_SIZE_CAP: u64 = 50 * 1024 * 1024;
+
+/// Size of the read buffer used by [`TreeStream`].
+const TREE_READ_CHUNK: usize = 8 * 1024;
+
+/// Prefix of the line that starts each file section of a unified git diff.
+const DIFF_FILE_HEADER: &str = "diff --git ";
+
+/// Boxed response body handed to the streaming readers by [`GithubClient`].
+pub type ResponseBody = Box<dyn AsyncBufRead + Send + Unpin>;
+
+fn size_cap_exceeded(cap: u64) -> GitHubOperationError {
+    GitHubOperationError::ResponseTooLarge { limit_bytes: cap }
+}
+
+fn read_failure(error: std::io::Error) -> GitHubOperationError {
+    GitHubOperationError::Transient {
+        message: format!("failed to read response body: {error}"),
+    }
+}
+
+// ─── Diff stream ─────────────────────────────────────────────────────────────
+
+/// The portion of a unified diff that belongs to one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffFile {
+    /// Path of the file after the change (the `b/` side of the header).
+    pub path: String,
+    /// The file's section of the diff, starting with its `diff --git` line.
+    pub patch: String,
+}
+
+/// Splits a unified diff (`application/vnd.github.diff`) into per-file chunks.
+///
+/// Memory use is bounded by the largest single file section, which is itself
+/// bounded by the size cap.
+pub struct DiffStream<R> {
+    reader: R,
+    cap: u64,
+    bytes_read: u64,
+    /// Header of the next file section, read while finishing the previous one.
+    pending_header: Option<String>,
+    finished: bool,
+}
+
+impl<R: AsyncBufRead + Unpin> DiffStream<R> {
+    /// Creates a reader that fails once more than `cap` bytes are consumed.
+    pub fn new(reader: R, cap: u64) -> Self {
+        Self {
+            reader,
+            cap,
+            bytes_read: 0,
+            pending_header: None,
+            finished: false,
+        }
+    }
+
+    /// Returns the number of bytes consumed so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Returns the next file section, or `None` at the end of the diff.
+    ///
+    /// Any text before the first `diff --git` line is skipped.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::ResponseTooLarge`] — the cap was passed.
+    ///   The stream is finished afterwards.
+    /// - [`GitHubOperationError::ParseFailure`] — a file header has no `b/`
+    ///   path.
+    /// - [`GitHubOperationError::Transient`] — reading the body failed.
+    pub async fn next_file(&mut self) -> Result<Option<DiffFile>, GitHubOperationError> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let header = match self.pending_header.take() {
+            Some(header) => header,
+            None => loop {
+                match self.read_line().await? {
+                    Some(line) if line.starts_with(DIFF_FILE_HEADER) => break line,
+                    Some(_) => continue,
+                    None => return Ok(None),
+                }
+            },
+        };
+
+        let path = diff_header_path(&header).ok_or_else(|| {
+            self.finished = true;
+            GitHubOperationError::ParseFailure {
+                message: format!("diff file header has no b/ path: {}", header.trim_end()),
+            }
+        })?;
+
+        let mut patch = header;
+        while let Some(line) = self.read_line().await? {
+            if line.starts_with(DIFF_FILE_HEADER) {
+                self.pending_header = Some(line);
+                break;
+            }
+            patch.push_str(&line);
+        }
+
+        Ok(Some(DiffFile { path, patch }))
+    }
+
+    /// Reads one line (including its newline), enforcing the cap.
+    ///
+    /// At most one byte past the cap is read, so an oversized single line is
+    /// rejected without being buffered in full.
+    async fn read_line(&mut self) -> Result<Option<String>, GitHubOperationError> {
+        let remaining = self.cap.saturating_sub(self.bytes_read);
+        let mut line = Vec::new();
+        let read = (&mut self.reader)
+            .take(remaining.saturating_add(1))
+            .read_until(b'\n', &mut line)
+            .await
+            .map_err(|e| {
+                self.finished = true;
+                read_failure(e)
+            })?;
+
+        if read == 0 {
+            self.finished = true;
+            return Ok(None);
+        }
+
+        self.bytes_read += read as u64;
+        if self.bytes_read > self.cap {
+            self.finished = true;
+            return Err(size_cap_exceeded(self.cap));
+        }
+
+        Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+    }
+}
+
+/// Extracts the post-change path from a `diff --git a/<old> b/<new>` line.
+fn diff_header_path(header: &str) -> Option<String> {
+    let paths = header.strip_prefix(DIFF_FILE_HEADER)?.trim_end();
+    let (_, new_path) = paths.rsplit_once(" b/")?;
+    (!new_path.is_empty()).then(|| new_path.to_string())
+}
+
+// ─── Tree stream ─────────────────────────────────────────────────────────────
+
+/// One element of the Trees API `tree` array.
+#[derive(Debug, Deserialize)]
+struct WireTreeEntry {
+    path: String,
+    mode: String,
+    #[serde(rename = "type")]
+    kind: String,
+    sha: String,
+}
+
+/// Git file mode GitHub reports for symbolic links.
+const SYMLINK_MODE: &str = "120000";
+
+impl TryFrom<WireTreeEntry> for DirectoryEntry {
+    type Error = GitHubOperationError;
+
+    fn try_from(entry: WireTreeEntry) -> Result<Self, Self::Error> {
+        let kind = match (entry.kind.as_str(), entry.mode.as_str()) {
+            ("blob", SYMLINK_MODE) => DirectoryEntryKind::Symlink,
+            ("blob", _) => DirectoryEntryKind::File,
+            ("tree", _) => DirectoryEntryKind::Directory,
+            ("commit", _) => DirectoryEntryKind::Submodule,
+            (other, _) => {
+                return Err(GitHubOperationError::ParseFailure {
+                    message: format!("unknown tree entry type '{other}' at '{}'", entry.path),
+                })
+            }
+        };
+        let sha =
+            GitObjectSha::new(entry.sha).ok_or_else(|| GitHubOperationError::ParseFailure {
+                message: format!("tree entry '{}' has an empty sha", entry.path),
+            })?;
+        let name = entry
+            .path
+            .rsplit('/')
+            .next()
+            .unwrap_or(&entry.path)
+            .to_string();
+
+        Ok(Self {
+            name,
+            path: entry.path,
+            kind,
+            sha,
+        })
+    }
+}
+
+/// Where a [`TreeScanner`] is within the Trees API response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TreePhase {
+    /// Looking for the top-level `"tree": [` array.
+    Seeking,
+    /// Inside the `tree` array.
+    InTree,
+    /// The `tree` array has been closed.
+    Done,
+}
+
+/// Incremental scanner that cuts the elements of the top-level `tree` array
+/// out of a Trees API JSON response, one byte at a time.
+///
+/// Tracks only nesting depth and string state, so memory is bounded by the
+/// size of one array element.
+#[derive(Debug)]
+struct TreeScanner {
+    phase: TreePhase,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// Contents of the string being read at depth 1 (a top-level key or value).
+    string: Vec<u8>,
+    /// The most recently completed string at depth 1.
+    last_string: Vec<u8>,
+    /// Bytes of the array element being collected; empty between elements.
+    element: Vec<u8>,
+}
+
+impl TreeScanner {
+    fn new() -> Self {
+        Self {
+            phase: TreePhase::Seeking,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            string: Vec::new(),
+            last_string: Vec::new(),
+            element: Vec::new(),
+        }
+    }
+
+    /// Consumes one byte. Returns the raw JSON of a `tree` element when this
+    /// byte completes one.
+    fn feed(&mut self, byte: u8) -> Option<Vec<u8>> {
+        let collecting = !self.element.is_empty();
+        if collecting {
+            self.element.push(byte);
+        }
+
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+            } else if byte == b'\\' {
+                self.escaped = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+                if self.depth == 1 {
+                    self.last_string = mem::take(&mut self.string);
+                }
+                return None;
+            }
+            if self.depth == 1 {
+                self.string.push(byte);
+            }
+            return None;
+        }
+
+        match byte {
+            b'"' => {
+                self.in_string = true;
+                self.string.clear();
+            }
+            b'{' | b'[' => {
+                self.depth += 1;
+                match self.phase {
+                    TreePhase::Seeking
+                        if byte == b'[' && self.depth == 2 && self.last_string == b"tree" =>
+                    {
+                        self.phase = TreePhase::InTree;
+                    }
+                    TreePhase::InTree if byte == b'{' && self.depth == 3 && !collecting => {
+                        self.element.push(byte);
+                    }
+                    _ => {}
+                }
+            }
+            b'}' | b']' => {
+                self.depth = self.depth.saturating_sub(1);
+                if self.phase == TreePhase::InTree {
+                    if collecting && self.depth == 2 {
+                        return Some(mem::take(&mut self.element));
+                    }
+                    if self.depth == 1 {
+                        self.phase = TreePhase::Done;
+                    }
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Yields the entries of a recursive Trees API response
+/// (`GET /repos/{owner}/{repo}/git/trees/{ref}?recursive=1`) one at a time.
+///
+/// Only the `tree` array is read. Reading stops once it closes, so trailing
+/// fields such as `truncated` are not consumed.
+pub struct TreeStream<R> {
+    reader: R,
+    cap: u64,
+    bytes_read: u64,
+    chunk: Vec<u8>,
+    position: usize,
+    filled: usize,
+    scanner: TreeScanner,
+    finished: bool,
+}
+
+impl<R: AsyncRead + Unpin> TreeStream<R> {
+    /// Creates a reader that fails once more than `cap` bytes are consumed.
+    pub fn new(reader: R, cap: u64) -> Self {
+        Self {
+            reader,
+            cap,
+            bytes_read: 0,
+            chunk: vec![0; TREE_READ_CHUNK],
+            position: 0,
+            filled: 0,
+            scanner: TreeScanner::new(),
+            finished: false,
+        }
+    }
+
+    /// Returns the number of bytes consumed so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Returns the next tree entry, or `None` once the `tree` array ends.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::ResponseTooLarge`] — the cap was passed.
+    /// - [`GitHubOperationError::ParseFailure`] — the body has no `tree`
+    ///   array, ends inside it, or contains an entry that cannot be parsed.
+    /// - [`GitHubOperationError::Transient`] — reading the body failed.
+    ///
+    /// The stream is finished after any error.
+    pub async fn next_entry(&mut self) -> Result<Option<DirectoryEntry>, GitHubOperationError> {
+        let result = self.advance().await;
+        if result.is_err() {
+            self.finished = true;
+        }
+        result
+    }
+
+    async fn advance(&mut self) -> Result<Option<DirectoryEntry>, GitHubOperationError> {
+        loop {
+            if self.finished || self.scanner.phase == TreePhase::Done {
+                self.finished = true;
+                return Ok(None);
+            }
+
+            while self.position < self.filled {
+                let byte = self.chunk[self.position];
+                self.position += 1;
+                if let Some(element) = self.scanner.feed(byte) {
+                    let entry: WireTreeEntry = serde_json::from_slice(&element).map_err(|e| {
+                        GitHubOperationError::ParseFailure {
+                            message: format!("malformed tree entry: {e}"),
+                        }
+                    })?;
+                    return DirectoryEntry::try_from(entry).map(Some);
+                }
+                if self.scanner.phase == TreePhase::Done {
+                    break;
+                }
+            }
+            if self.scanner.phase == TreePhase::Done {
+                continue;
+            }
+
+            let read = self
+                .reader
+                .read(&mut self.chunk)
+                .await
+                .map_err(read_failure)?;
+            if read == 0 {
+                let message = match self.scanner.phase {
+                    TreePhase::Seeking => "tree response has no `tree` array",
+                    _ => "tree response ended inside the `tree` array",
+                };
+                return Err(GitHubOperationError::ParseFailure {
+                    message: message.to_string(),
+                });
+            }
+
+            self.bytes_read += read as u64;
+            if self.bytes_read > self.cap {
+                return Err(size_cap_exceeded(self.cap));
+            }
+            self.position = 0;
+            self.filled = read;
+        }
+    }
+}
+
+// ─── GithubClient entry points ───────────────────────────────────────────────
+
+impl GithubClient {
+    /// Open a streaming reader over the unified diff of a pull request.
+    ///
+    /// **SDK gap**: requires raw (non-JSON) response body streaming in
+    /// `github-bot-sdk`.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — PR does not exist.
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — SDK addition pending.
+    #[instrument(skip(self))]
+    pub async fn stream_pull_request_diff(
+        &self,
+        _repository: &RepositoryId,
+        _pr: PullRequestId,
+        _cap: u64,
+    ) -> Result<DiffStream<ResponseBody>, GitHubOperationError> {
+        // SDK gap: raw response streaming not yet in github-bot-sdk.
+        Err(GitHubOperationError::SdkCapabilityMissing {
+            capability: "raw_response_streaming".to_string(),
+        })
+    }
+
+    /// Open a streaming reader over the recursive tree of a repository.
+    ///
+    /// **SDK gap**: requires raw response body streaming and the recursive
+    /// Trees API in `github-bot-sdk`.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — ref does not exist.
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — SDK addition pending.
+    #[instrument(skip(self))]
+    pub async fn stream_tree(
+        &self,
+        _repository: &RepositoryId,
+        _git_ref: &str,
+        _cap: u64,
+    ) -> Result<TreeStream<ResponseBody>, GitHubOperationError> {
+        // SDK gap: raw response streaming not yet in github-bot-sdk.
+        Err(GitHubOperationError::SdkCapabilityMissing {
+            capability: "raw_response_streaming".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "streaming_tests.rs"]
+mod tests;
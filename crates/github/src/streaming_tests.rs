@@ -0,0 +1,243 @@
+use super::*;
+
+fn file_section(index: usize) -> String {
+    format!(
+        "diff --git a/src/file_{index}.rs b/src/file_{index}.rs\n\
+         index 1111111..2222222 100644\n\
+         --- a/src/file_{index}.rs\n\
+         +++ b/src/file_{index}.rs\n\
+         @@ -1 +1 @@\n\
+         -old {index}\n\
+         +new {index}\n"
+    )
+}
+
+fn large_diff(files: usize) -> String {
+    (0..files).map(file_section).collect()
+}
+
+fn tree_entry(path: &str, mode: &str, kind: &str) -> String {
+    format!(
+        r#"{{"path":"{path}","mode":"{mode}","type":"{kind}","sha":"3f786850e387550fdab836ed7e6dc881de23001b","size":12,"url":"https://api.github.com/x"}}"#
+    )
+}
+
+fn tree_body(entries: &[String]) -> String {
+    format!(
+        r#"{{"sha":"9fb037999f264ba9a7fc6274d15fa3ae2ab98312","url":"https://api.github.com/tree","tree":[{}],"truncated":false}}"#,
+        entries.join(",")
+    )
+}
+
+fn large_tree(entries: usize) -> String {
+    let entries: Vec<_> = (0..entries)
+        .map(|index| tree_entry(&format!("src/module_{index}.rs"), "100644", "blob"))
+        .collect();
+    tree_body(&entries)
+}
+
+// ─── DiffStream ─────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_next_file_large_diff_yields_one_chunk_per_file() {
+    let diff = large_diff(500);
+    let mut stream = DiffStream::new(diff.as_bytes(), DEFAULT_STREAM_SIZE_CAP);
+
+    let mut files = Vec::new();
+    while let Some(file) = stream.next_file().await.unwrap() {
+        files.push(file);
+    }
+
+    assert_eq!(files.len(), 500);
+    assert_eq!(files[0].path, "src/file_0.rs");
+    assert_eq!(files[0].patch, file_section(0));
+    assert_eq!(files[499].path, "src/file_499.rs");
+    assert_eq!(stream.bytes_read(), diff.len() as u64);
+}
+
+#[tokio::test]
+async fn test_next_file_preamble_before_first_header_is_skipped() {
+    let diff = format!("From abc Mon Sep 17 00:00:00 2001\n\n{}", file_section(1));
+    let mut stream = DiffStream::new(diff.as_bytes(), DEFAULT_STREAM_SIZE_CAP);
+
+    let file = stream.next_file().await.unwrap().unwrap();
+
+    assert_eq!(file.path, "src/file_1.rs");
+    assert!(file.patch.starts_with(DIFF_FILE_HEADER));
+    assert_eq!(stream.next_file().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_next_file_past_cap_returns_response_too_large_then_ends() {
+    let diff = large_diff(500);
+    let cap = (file_section(0).len() * 10) as u64;
+    let mut stream = DiffStream::new(diff.as_bytes(), cap);
+
+    let mut delivered = 0;
+    let error = loop {
+        match stream.next_file().await {
+            Ok(Some(_)) => delivered += 1,
+            Ok(None) => panic!("stream ended without hitting the cap"),
+            Err(error) => break error,
+        }
+    };
+
+    assert!(delivered < 10);
+    assert!(matches!(
+        error,
+        GitHubOperationError::ResponseTooLarge { limit_bytes } if limit_bytes == cap
+    ));
+    assert!(stream.bytes_read() <= cap + 1);
+    assert_eq!(stream.next_file().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_next_file_oversized_single_line_is_rejected_at_cap() {
+    let diff = format!("diff --git a/big b/big\n+{}\n", "x".repeat(10_000));
+    let mut stream = DiffStream::new(diff.as_bytes(), 1_000);
+
+    let error = stream.next_file().await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::ResponseTooLarge { .. }
+    ));
+    assert_eq!(stream.bytes_read(), 1_001);
+}
+
+#[tokio::test]
+async fn test_next_file_header_without_b_path_returns_parse_failure() {
+    let diff = "diff --git a/only-one-side\n+x\n";
+    let mut stream = DiffStream::new(diff.as_bytes(), DEFAULT_STREAM_SIZE_CAP);
+
+    let error = stream.next_file().await.unwrap_err();
+
+    assert!(matches!(error, GitHubOperationError::ParseFailure { .. }));
+}
+
+#[tokio::test]
+async fn test_next_file_empty_body_returns_none() {
+    let mut stream = DiffStream::new(&b""[..], DEFAULT_STREAM_SIZE_CAP);
+
+    assert_eq!(stream.next_file().await.unwrap(), None);
+}
+
+#[test]
+fn test_diff_header_path_renamed_file_returns_new_path() {
+    assert_eq!(
+        diff_header_path("diff --git a/old/name.rs b/new/name.rs\n"),
+        Some("new/name.rs".to_string())
+    );
+}
+
+// ─── TreeStream ─────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_next_entry_large_tree_yields_every_entry() {
+    let body = large_tree(2_000);
+    let mut stream = TreeStream::new(body.as_bytes(), DEFAULT_STREAM_SIZE_CAP);
+
+    let mut entries = Vec::new();
+    while let Some(entry) = stream.next_entry().await.unwrap() {
+        entries.push(entry);
+    }
+
+    assert_eq!(entries.len(), 2_000);
+    assert_eq!(entries[0].path, "src/module_0.rs");
+    assert_eq!(entries[0].name, "module_0.rs");
+    assert_eq!(entries[1_999].path, "src/module_1999.rs");
+}
+
+#[tokio::test]
+async fn test_next_entry_maps_entry_kinds() {
+    let body = tree_body(&[
+        tree_entry("src", "040000", "tree"),
+        tree_entry("src/lib.rs", "100644", "blob"),
+        tree_entry("link", "120000", "blob"),
+        tree_entry("vendor/dep", "160000", "commit"),
+    ]);
+    let mut stream = TreeStream::new(body.as_bytes(), DEFAULT_STREAM_SIZE_CAP);
+
+    let mut kinds = Vec::new();
+    while let Some(entry) = stream.next_entry().await.unwrap() {
+        kinds.push(entry.kind);
+    }
+
+    assert_eq!(
+        kinds,
+        [
+            DirectoryEntryKind::Directory,
+            DirectoryEntryKind::File,
+            DirectoryEntryKind::Symlink,
+            DirectoryEntryKind::Submodule,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_next_entry_past_cap_returns_response_too_large() {
+    let body = large_tree(2_000);
+    let mut stream = TreeStream::new(body.as_bytes(), 64 * 1024);
+
+    let error = loop {
+        match stream.next_entry().await {
+            Ok(Some(_)) => {}
+            Ok(None) => panic!("stream ended without hitting the cap"),
+            Err(error) => break error,
+        }
+    };
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::ResponseTooLarge { limit_bytes } if limit_bytes == 64 * 1024
+    ));
+    assert_eq!(stream.next_entry().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_next_entry_escaped_quote_in_path_is_not_a_delimiter() {
+    let body = tree_body(&[tree_entry(r#"docs/say \"hi\".md"#, "100644", "blob")]);
+    let mut stream = TreeStream::new(body.as_bytes(), DEFAULT_STREAM_SIZE_CAP);
+
+    let entry = stream.next_entry().await.unwrap().unwrap();
+
+    assert_eq!(entry.path, r#"docs/say "hi".md"#);
+    assert_eq!(stream.next_entry().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_next_entry_body_without_tree_returns_parse_failure() {
+    let body = r#"{"message":"Not Found"}"#;
+    let mut stream = TreeStream::new(body.as_bytes(), DEFAULT_STREAM_SIZE_CAP);
+
+    let error = stream.next_entry().await.unwrap_err();
+
+    assert!(matches!(error, GitHubOperationError::ParseFailure { .. }));
+}
+
+#[tokio::test]
+async fn test_next_entry_body_ending_inside_tree_returns_parse_failure() {
+    let body = large_tree(3);
+    let truncated = &body[..body.len() / 2];
+    let mut stream = TreeStream::new(truncated.as_bytes(), DEFAULT_STREAM_SIZE_CAP);
+
+    let error = loop {
+        match stream.next_entry().await {
+            Ok(Some(_)) => {}
+            Ok(None) => panic!("truncated body must not end cleanly"),
+            Err(error) => break error,
+        }
+    };
+
+    assert!(matches!(error, GitHubOperationError::ParseFailure { .. }));
+}
+
+#[tokio::test]
+async fn test_next_entry_unknown_type_returns_parse_failure() {
+    let body = tree_body(&[tree_entry("x", "100644", "tag")]);
+    let mut stream = TreeStream::new(body.as_bytes(), DEFAULT_STREAM_SIZE_CAP);
+
+    let error = stream.next_entry().await.unwrap_err();
+
+    assert!(matches!(error, GitHubOperationError::ParseFailure { .. }));
+}
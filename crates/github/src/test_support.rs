@@ -0,0 +1,23 @@
+//! Clients and identifiers shared by the crate's unit tests.
+
+use std::sync::Arc;
+
+use pipeline::RepositoryId;
+
+use crate::{transport::ScriptedTransport, GithubClient};
+
+/// The repository every test client targets.
+pub(crate) fn repository() -> RepositoryId {
+    RepositoryId::parse("octo/widgets").unwrap()
+}
+
+/// A client sending through `transport`, scoped to [`repository`].
+pub(crate) fn client(transport: &Arc<ScriptedTransport>) -> GithubClient {
+    client_without_repository(transport).with_repository(repository())
+}
+
+/// A client sending through `transport` with no repository, for operations
+/// that take the repository as an argument.
+pub(crate) fn client_without_repository(transport: &Arc<ScriptedTransport>) -> GithubClient {
+    GithubClient::new(Arc::new(())).with_transport(Arc::clone(transport) as _)
+}
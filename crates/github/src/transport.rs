@@ -0,0 +1,318 @@
+//! The REST transport [`GithubClient`](crate::GithubClient) sends requests
+//! through.
+//!
+//! | Implementation | Use |
+//! |---|---|
+//! | Supplied by the embedder | Production; wraps the authenticated `github-bot-sdk` installation client |
+//! | `ScriptedTransport` | Tests; replays queued responses (feature `mock-transport`) |
+//!
+//! Transports only move requests and responses. Rate limiting is applied
+//! around them by [`RateLimitedClient`](crate::rate_limited::RateLimitedClient),
+//! and mapping status codes to [`GitHubOperationError`] variants is left to
+//! each operation (usually through
+//! [`status_error`](crate::rate_limited::status_error)), so the transport
+//! returns every HTTP status as a response. An `Err` from a transport means
+//! no response arrived.
+//!
+//! Paths are relative to the API root (`/repos/{owner}/{repo}/...`); GraphQL
+//! requests are a `POST` to [`GRAPHQL_PATH`].
+//!
+//! A client built without a transport answers every request with
+//! [`GitHubOperationError::SdkCapabilityMissing`] (capability
+//! [`REST_TRANSPORT_CAPABILITY`]) instead of panicking.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §REST transport.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+
+use pipeline::{github::GitHubOperationError, RepositoryId, WorkItemId};
+
+use crate::{
+    default_branch::repository_path, rate_limit::EndpointClass, rate_limited::RestResponse,
+    GithubClient,
+};
+
+/// Path of the GraphQL endpoint.
+pub const GRAPHQL_PATH: &str = "/graphql";
+
+/// Capability named by [`GitHubOperationError::SdkCapabilityMissing`] when
+/// the client has no transport.
+pub const REST_TRANSPORT_CAPABILITY: &str = "rest_transport";
+
+/// Capability named by [`GitHubOperationError::SdkCapabilityMissing`] when an
+/// operation addressed by a bare [`WorkItemId`] is called on a client without
+/// a repository (see [`GithubClient::with_repository`]).
+pub const REPOSITORY_CAPABILITY: &str = "installation_repository";
+
+/// Capability named by [`GitHubOperationError::SdkCapabilityMissing`] when a
+/// project board operation is called on a client without a project (see
+/// [`GithubClient::with_project`]).
+pub const PROJECT_CAPABILITY: &str = "projects_v2_board";
+
+// ─── Request ────────────────────────────────────────────────────────────────
+
+/// HTTP method of a [`RestRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestMethod {
+    /// `GET`
+    Get,
+    /// `POST`
+    Post,
+    /// `PATCH`
+    Patch,
+    /// `PUT`
+    Put,
+    /// `DELETE`
+    Delete,
+}
+
+impl RestMethod {
+    /// The method name as sent on the wire.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+            Self::Patch => "PATCH",
+            Self::Put => "PUT",
+            Self::Delete => "DELETE",
+        }
+    }
+}
+
+/// A request to the GitHub API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestRequest {
+    /// HTTP method.
+    pub method: RestMethod,
+    /// Path and query relative to the API root, e.g.
+    /// `/repos/octo/widgets/issues/42`.
+    pub path: String,
+    /// Extra request headers as `(name, value)` pairs. Authentication and
+    /// `Accept` are the transport's concern.
+    pub headers: Vec<(String, String)>,
+    /// JSON request body, if any.
+    pub body: Option<JsonValue>,
+}
+
+impl RestRequest {
+    /// Creates a request with no headers or body.
+    pub fn new(method: RestMethod, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// A `GET` of `path`.
+    pub fn get(path: impl Into<String>) -> Self {
+        Self::new(RestMethod::Get, path)
+    }
+
+    /// A `POST` of `body` to `path`.
+    pub fn post(path: impl Into<String>, body: JsonValue) -> Self {
+        Self::new(RestMethod::Post, path).with_body(body)
+    }
+
+    /// A `PATCH` of `body` to `path`.
+    pub fn patch(path: impl Into<String>, body: JsonValue) -> Self {
+        Self::new(RestMethod::Patch, path).with_body(body)
+    }
+
+    /// Sets the JSON body.
+    #[must_use]
+    pub fn with_body(mut self, body: JsonValue) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Adds a request header.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Returns the first header named `name` (case-insensitive).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+// ─── Transport ──────────────────────────────────────────────────────────────
+
+/// Sends [`RestRequest`]s to GitHub.
+#[async_trait]
+pub trait GitHubTransport: Send + Sync {
+    /// Sends `request` and returns the response, whatever its status.
+    ///
+    /// # Errors
+    ///
+    /// [`GitHubOperationError::Transient`] — no response was received
+    /// (connection failure, timeout).
+    async fn send(&self, request: RestRequest) -> Result<RestResponse, GitHubOperationError>;
+}
+
+impl GithubClient {
+    /// Returns the repository the client operates on.
+    ///
+    /// # Errors
+    ///
+    /// [`GitHubOperationError::SdkCapabilityMissing`] (capability
+    /// [`REPOSITORY_CAPABILITY`]) — no repository was set.
+    pub(crate) fn repository(&self) -> Result<&RepositoryId, GitHubOperationError> {
+        self.repository
+            .as_ref()
+            .ok_or_else(|| GitHubOperationError::SdkCapabilityMissing {
+                capability: REPOSITORY_CAPABILITY.to_string(),
+            })
+    }
+
+    /// Returns the number of the Projects V2 board the client updates.
+    ///
+    /// # Errors
+    ///
+    /// [`GitHubOperationError::SdkCapabilityMissing`] (capability
+    /// [`PROJECT_CAPABILITY`]) — no project was set.
+    pub(crate) fn project(&self) -> Result<u64, GitHubOperationError> {
+        self.project
+            .ok_or_else(|| GitHubOperationError::SdkCapabilityMissing {
+                capability: PROJECT_CAPABILITY.to_string(),
+            })
+    }
+
+    /// Returns the REST path of `work_item_id` in the client's repository.
+    ///
+    /// # Errors
+    ///
+    /// As for [`GithubClient::repository`].
+    pub(crate) fn issue_path(
+        &self,
+        work_item_id: WorkItemId,
+    ) -> Result<String, GitHubOperationError> {
+        Ok(format!(
+            "{}/issues/{}",
+            repository_path(self.repository()?),
+            work_item_id.as_u64()
+        ))
+    }
+
+    /// Sends `request` through the transport without rate limiting.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::SdkCapabilityMissing`] — the client has no
+    ///   transport.
+    /// - Any error returned by the transport.
+    pub(crate) async fn send_unmetered(
+        &self,
+        request: RestRequest,
+    ) -> Result<RestResponse, GitHubOperationError> {
+        let transport = self.transport.as_ref().map(Arc::clone).ok_or_else(|| {
+            GitHubOperationError::SdkCapabilityMissing {
+                capability: REST_TRANSPORT_CAPABILITY.to_string(),
+            }
+        })?;
+        transport.send(request).await
+    }
+
+    /// Sends `request` through the rate limiter and the transport.
+    ///
+    /// The endpoint class is derived from the request path.
+    ///
+    /// # Errors
+    ///
+    /// As for [`GithubClient::send_unmetered`] and
+    /// [`RateLimitedClient::execute`](crate::rate_limited::RateLimitedClient::execute).
+    pub(crate) async fn send(
+        &self,
+        request: RestRequest,
+    ) -> Result<RestResponse, GitHubOperationError> {
+        let class = EndpointClass::for_path(&request.path);
+        self.rate_limited()
+            .execute(class, || self.send_unmetered(request))
+            .await
+    }
+}
+
+// ─── Scripted ───────────────────────────────────────────────────────────────
+
+/// [`GitHubTransport`] that replays queued responses instead of sending
+/// requests.
+///
+/// Every request is recorded so tests can assert on the method, path,
+/// headers, and body an operation produced. Once the queue is empty, further
+/// calls return [`GitHubOperationError::Transient`].
+#[cfg(any(test, feature = "mock-transport"))]
+#[derive(Debug, Default)]
+pub struct ScriptedTransport {
+    responses:
+        std::sync::Mutex<std::collections::VecDeque<Result<RestResponse, GitHubOperationError>>>,
+    requests: std::sync::Mutex<Vec<RestRequest>>,
+}
+
+#[cfg(any(test, feature = "mock-transport"))]
+impl ScriptedTransport {
+    /// Creates a transport with no queued responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues `response` to be returned by the next unanswered call.
+    pub fn push(&self, response: Result<RestResponse, GitHubOperationError>) {
+        self.responses
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push_back(response);
+    }
+
+    /// Queues a response with `status`, no headers, and a JSON `body`.
+    pub fn push_json(&self, status: u16, body: JsonValue) {
+        self.push(Ok(RestResponse {
+            status,
+            headers: Vec::new(),
+            body,
+        }));
+    }
+
+    /// Returns every request received so far, oldest first.
+    pub fn requests(&self) -> Vec<RestRequest> {
+        self.requests
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[cfg(any(test, feature = "mock-transport"))]
+#[async_trait]
+impl GitHubTransport for ScriptedTransport {
+    async fn send(&self, request: RestRequest) -> Result<RestResponse, GitHubOperationError> {
+        self.requests
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(request);
+        self.responses
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .pop_front()
+            .unwrap_or_else(|| {
+                Err(GitHubOperationError::Transient {
+                    message: "scripted transport has no queued response".to_string(),
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+#[path = "transport_tests.rs"]
+mod tests;
@@ -0,0 +1,98 @@
+use serde_json::json;
+
+use super::*;
+
+#[test]
+fn test_rest_method_as_str_each_method_matches_wire_name() {
+    assert_eq!(RestMethod::Get.as_str(), "GET");
+    assert_eq!(RestMethod::Post.as_str(), "POST");
+    assert_eq!(RestMethod::Patch.as_str(), "PATCH");
+    assert_eq!(RestMethod::Put.as_str(), "PUT");
+    assert_eq!(RestMethod::Delete.as_str(), "DELETE");
+}
+
+#[test]
+fn test_rest_request_get_has_no_body_or_headers() {
+    let request = RestRequest::get("/repos/octo/widgets");
+    assert_eq!(request.method, RestMethod::Get);
+    assert_eq!(request.path, "/repos/octo/widgets");
+    assert!(request.headers.is_empty());
+    assert_eq!(request.body, None);
+}
+
+#[test]
+fn test_rest_request_patch_carries_body() {
+    let request = RestRequest::patch("/repos/octo/widgets/issues/1", json!({ "state": "closed" }));
+    assert_eq!(request.method, RestMethod::Patch);
+    assert_eq!(request.body, Some(json!({ "state": "closed" })));
+}
+
+#[test]
+fn test_rest_request_header_lookup_is_case_insensitive() {
+    let request = RestRequest::get("/").with_header("If-None-Match", "\"abc\"");
+    assert_eq!(request.header("if-none-match"), Some("\"abc\""));
+    assert_eq!(request.header("etag"), None);
+}
+
+#[tokio::test]
+async fn test_send_without_transport_returns_capability_missing() {
+    let client = GithubClient::new(Arc::new(()));
+
+    let error = client
+        .send(RestRequest::get("/rate_limit"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::SdkCapabilityMissing { capability } if capability == REST_TRANSPORT_CAPABILITY
+    ));
+}
+
+#[tokio::test]
+async fn test_send_with_transport_records_request_and_returns_response() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(201, json!({ "id": 7 }));
+    let client = GithubClient::new(Arc::new(())).with_transport(Arc::clone(&transport) as _);
+
+    let response = client
+        .send(RestRequest::post(
+            "/repos/octo/widgets/issues",
+            json!({ "title": "t" }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 201);
+    assert_eq!(transport.requests()[0].path, "/repos/octo/widgets/issues");
+}
+
+#[tokio::test]
+async fn test_send_rate_limited_response_returns_rate_limit_exhausted() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push(Ok(RestResponse {
+        status: 429,
+        headers: vec![("retry-after".to_string(), "30".to_string())],
+        body: JsonValue::Null,
+    }));
+    let client = GithubClient::new(Arc::new(())).with_transport(Arc::clone(&transport) as _);
+
+    let error = client
+        .send(RestRequest::get("/repos/octo/widgets"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        GitHubOperationError::RateLimitExhausted { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_scripted_transport_empty_queue_returns_transient() {
+    let transport = ScriptedTransport::new();
+
+    let error = transport.send(RestRequest::get("/")).await.unwrap_err();
+
+    assert!(matches!(error, GitHubOperationError::Transient { .. }));
+}
@@ -0,0 +1,228 @@
+//! Bounded buffering between an event source and the executor.
+//!
+//! Events can arrive faster than `run_step` processes them. Rather than queue
+//! them without limit, every source feeds the executor through a bounded
+//! [`event_buffer`]. What happens at capacity depends on who is producing:
+//!
+//! | Producer | At capacity |
+//! |----------|-------------|
+//! | Webhook HTTP handler | [`EventBufferSender::try_offer`] fails; the delivery is answered with [`WebhookAck::Busy`] (`503`) |
+//! | Pull-based source via [`pump`] | [`EventBufferSender::send`] waits, so the next message is not received (or acknowledged) until there is room |
+//!
+//! The receiving half, [`EventBuffer`], is itself an [`EventSource`], so the
+//! executor's event loop is unchanged.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! let (sender, mut buffer) = event_buffer(capacity);
+//! tokio::spawn(async move { pump(&mut queue_source, &sender, poll).await });
+//! while let Some(event) = buffer.next_event(timeout).await? {
+//!     run_step(event).await;
+//! }
+//! ```
+
+use std::{num::NonZeroUsize, time::Duration};
+
+use async_trait::async_trait;
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tracing::instrument;
+
+use pipeline::github::{EventSource, EventSourceError, GitHubEvent};
+
+/// `Retry-After` value, in seconds, sent with a [`WebhookAck::Busy`] response.
+pub const BUSY_RETRY_AFTER_SECS: u64 = 30;
+
+/// Creates a bounded event buffer holding at most `capacity` events.
+pub fn event_buffer(capacity: NonZeroUsize) -> (EventBufferSender, EventBuffer) {
+    let (sender, receiver) = mpsc::channel(capacity.get());
+    (
+        EventBufferSender { sender, capacity },
+        EventBuffer { receiver },
+    )
+}
+
+// ─── Producer side ───────────────────────────────────────────────────────────
+
+/// Returned when an event cannot be placed in the buffer. Carries the event
+/// back to the caller.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum OfferError {
+    /// The buffer is at capacity.
+    #[error("event buffer is full")]
+    Full(Box<GitHubEvent>),
+
+    /// The executor side has been dropped; no more events will be processed.
+    #[error("event buffer is closed")]
+    Closed(Box<GitHubEvent>),
+}
+
+/// Returned by [`EventBufferSender::send`] after the executor side has been
+/// dropped.
+#[derive(Debug, Error)]
+#[error("event buffer is closed; the executor is shutting down")]
+pub struct BufferClosed;
+
+/// How the webhook HTTP handler should answer a delivery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookAck {
+    /// The event was buffered for processing.
+    Accepted,
+    /// The buffer is full or closed; the event was not buffered.
+    Busy,
+    /// The event type is not in the source's
+    /// [`EventFilter`](crate::event_filter::EventFilter); it was neither
+    /// parsed nor buffered.
+    Ignored,
+}
+
+impl WebhookAck {
+    /// HTTP status code for the response: `202`, `503`, or `204`.
+    pub fn status_code(self) -> u16 {
+        match self {
+            WebhookAck::Accepted => 202,
+            WebhookAck::Busy => 503,
+            WebhookAck::Ignored => 204,
+        }
+    }
+
+    /// `Retry-After` header value in seconds, if one should be sent.
+    pub fn retry_after_secs(self) -> Option<u64> {
+        match self {
+            WebhookAck::Accepted | WebhookAck::Ignored => None,
+            WebhookAck::Busy => Some(BUSY_RETRY_AFTER_SECS),
+        }
+    }
+}
+
+/// Producer half of an [`event_buffer`].
+///
+/// Cloning is cheap; clones feed the same buffer.
+#[derive(Debug, Clone)]
+pub struct EventBufferSender {
+    sender: mpsc::Sender<GitHubEvent>,
+    capacity: NonZeroUsize,
+}
+
+impl EventBufferSender {
+    /// Returns the configured capacity.
+    pub fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
+    /// Returns the number of free slots.
+    pub fn available(&self) -> usize {
+        self.sender.capacity()
+    }
+
+    /// Buffers `event` without waiting.
+    ///
+    /// # Errors
+    ///
+    /// - [`OfferError::Full`] — the buffer is at capacity.
+    /// - [`OfferError::Closed`] — the executor side has been dropped.
+    pub fn try_offer(&self, event: GitHubEvent) -> Result<(), OfferError> {
+        self.sender.try_send(event).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(event) => {
+                tracing::warn!(
+                    capacity = self.capacity.get(),
+                    "event buffer full; applying backpressure"
+                );
+                OfferError::Full(Box::new(event))
+            }
+            mpsc::error::TrySendError::Closed(event) => OfferError::Closed(Box::new(event)),
+        })
+    }
+
+    /// Buffers a verified webhook event and returns how to answer the
+    /// delivery.
+    ///
+    /// A full or closed buffer yields [`WebhookAck::Busy`]; the event is
+    /// dropped and GitHub's redelivery is relied on.
+    pub fn accept_webhook(&self, event: GitHubEvent) -> WebhookAck {
+        match self.try_offer(event) {
+            Ok(()) => WebhookAck::Accepted,
+            Err(_) => WebhookAck::Busy,
+        }
+    }
+
+    /// Buffers `event`, waiting for a free slot if the buffer is full.
+    ///
+    /// # Errors
+    ///
+    /// - [`BufferClosed`] — the executor side has been dropped.
+    pub async fn send(&self, event: GitHubEvent) -> Result<(), BufferClosed> {
+        self.sender.send(event).await.map_err(|_| BufferClosed)
+    }
+}
+
+/// Moves events from `source` into the buffer until the buffer closes.
+///
+/// Each event is sent with [`EventBufferSender::send`], so a full buffer stops
+/// `source` from being polled. For a queue source this delays receiving, and
+/// therefore acknowledging, further messages.
+///
+/// # Errors
+///
+/// Returns the first error reported by `source`.
+#[instrument(skip(source, sender))]
+pub async fn pump(
+    source: &mut dyn EventSource,
+    sender: &EventBufferSender,
+    poll_timeout: Duration,
+) -> Result<(), EventSourceError> {
+    loop {
+        let Some(event) = source.next_event(poll_timeout).await? else {
+            if sender.sender.is_closed() {
+                return Ok(());
+            }
+            continue;
+        };
+        if sender.send(event).await.is_err() {
+            return Ok(());
+        }
+    }
+}
+
+// ─── Consumer side ───────────────────────────────────────────────────────────
+
+/// Consumer half of an [`event_buffer`], read by the executor's event loop.
+#[derive(Debug)]
+pub struct EventBuffer {
+    receiver: mpsc::Receiver<GitHubEvent>,
+}
+
+impl EventBuffer {
+    /// Returns the number of events currently buffered.
+    pub fn len(&self) -> usize {
+        self.receiver.len()
+    }
+
+    /// Returns `true` if no events are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.receiver.is_empty()
+    }
+}
+
+#[async_trait]
+impl EventSource for EventBuffer {
+    /// Takes the next buffered event, waiting for at most `timeout`.
+    ///
+    /// Returns `Ok(None)` on timeout and once every sender has been dropped
+    /// and the buffer is drained.
+    async fn next_event(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<GitHubEvent>, EventSourceError> {
+        Ok(tokio::time::timeout(timeout, self.receiver.recv())
+            .await
+            .ok()
+            .flatten())
+    }
+}
+
+#[cfg(test)]
+#[path = "backpressure_tests.rs"]
+mod tests;
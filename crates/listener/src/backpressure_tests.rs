@@ -0,0 +1,169 @@
+use pipeline::WorkItemId;
+
+use super::*;
+
+/// Long enough for a waiting `send` to have completed if it could.
+const WAIT: Duration = Duration::from_millis(50);
+
+fn capacity(n: usize) -> NonZeroUsize {
+    NonZeroUsize::new(n).unwrap()
+}
+
+fn label_event(issue: u64) -> GitHubEvent {
+    GitHubEvent::LabelApplied {
+        work_item_id: WorkItemId::new(issue),
+        label: "cogworks:run".to_string(),
+        context: Default::default(),
+    }
+}
+
+/// Event source yielding a fixed list of events, then `Ok(None)` forever.
+struct ListSource {
+    events: std::vec::IntoIter<GitHubEvent>,
+}
+
+#[async_trait]
+impl EventSource for ListSource {
+    async fn next_event(
+        &mut self,
+        _timeout: Duration,
+    ) -> Result<Option<GitHubEvent>, EventSourceError> {
+        Ok(self.events.next())
+    }
+}
+
+#[test]
+fn test_try_offer_below_capacity_buffers_event() {
+    let (sender, buffer) = event_buffer(capacity(2));
+
+    sender.try_offer(label_event(1)).unwrap();
+
+    assert_eq!(buffer.len(), 1);
+    assert_eq!(sender.available(), 1);
+    assert_eq!(sender.capacity(), capacity(2));
+}
+
+#[test]
+fn test_try_offer_at_capacity_returns_full_with_event() {
+    let (sender, buffer) = event_buffer(capacity(1));
+    sender.try_offer(label_event(1)).unwrap();
+
+    let error = sender.try_offer(label_event(2)).unwrap_err();
+
+    assert!(matches!(error, OfferError::Full(event) if *event == label_event(2)));
+    assert_eq!(buffer.len(), 1);
+}
+
+#[test]
+fn test_try_offer_after_buffer_dropped_returns_closed() {
+    let (sender, buffer) = event_buffer(capacity(1));
+    drop(buffer);
+
+    let error = sender.try_offer(label_event(1)).unwrap_err();
+
+    assert!(matches!(error, OfferError::Closed(_)));
+}
+
+#[test]
+fn test_accept_webhook_at_capacity_returns_busy() {
+    let (sender, _buffer) = event_buffer(capacity(1));
+
+    assert_eq!(sender.accept_webhook(label_event(1)), WebhookAck::Accepted);
+    assert_eq!(sender.accept_webhook(label_event(2)), WebhookAck::Busy);
+}
+
+#[test]
+fn test_status_code_each_ack_returns_http_status() {
+    assert_eq!(WebhookAck::Accepted.status_code(), 202);
+    assert_eq!(WebhookAck::Busy.status_code(), 503);
+    assert_eq!(WebhookAck::Ignored.status_code(), 204);
+}
+
+#[test]
+fn test_retry_after_secs_only_busy_returns_value() {
+    assert_eq!(
+        WebhookAck::Busy.retry_after_secs(),
+        Some(BUSY_RETRY_AFTER_SECS)
+    );
+    assert_eq!(WebhookAck::Accepted.retry_after_secs(), None);
+    assert_eq!(WebhookAck::Ignored.retry_after_secs(), None);
+}
+
+#[tokio::test]
+async fn test_send_at_capacity_waits_until_event_taken() {
+    let (sender, mut buffer) = event_buffer(capacity(1));
+    sender.send(label_event(1)).await.unwrap();
+
+    let blocked = tokio::time::timeout(WAIT, sender.send(label_event(2))).await;
+    assert!(blocked.is_err(), "send must wait while the buffer is full");
+
+    let pending = tokio::spawn({
+        let sender = sender.clone();
+        async move { sender.send(label_event(2)).await }
+    });
+    assert_eq!(buffer.next_event(WAIT).await.unwrap(), Some(label_event(1)));
+    tokio::time::timeout(Duration::from_secs(1), pending)
+        .await
+        .expect("send completes once a slot is freed")
+        .unwrap()
+        .unwrap();
+    assert_eq!(buffer.next_event(WAIT).await.unwrap(), Some(label_event(2)));
+}
+
+#[tokio::test]
+async fn test_send_after_buffer_dropped_returns_buffer_closed() {
+    let (sender, buffer) = event_buffer(capacity(1));
+    drop(buffer);
+
+    assert!(sender.send(label_event(1)).await.is_err());
+}
+
+#[tokio::test]
+async fn test_next_event_empty_buffer_returns_none_after_timeout() {
+    let (_sender, mut buffer) = event_buffer(capacity(1));
+
+    assert_eq!(buffer.next_event(WAIT).await.unwrap(), None);
+    assert!(buffer.is_empty());
+}
+
+#[tokio::test]
+async fn test_next_event_senders_dropped_drains_then_returns_none() {
+    let (sender, mut buffer) = event_buffer(capacity(2));
+    sender.try_offer(label_event(1)).unwrap();
+    drop(sender);
+
+    assert_eq!(buffer.next_event(WAIT).await.unwrap(), Some(label_event(1)));
+    assert_eq!(buffer.next_event(WAIT).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_pump_full_buffer_stops_polling_source() {
+    let (sender, mut buffer) = event_buffer(capacity(1));
+    let mut source = ListSource {
+        events: vec![label_event(1), label_event(2), label_event(3)].into_iter(),
+    };
+
+    let pumping = tokio::time::timeout(WAIT, pump(&mut source, &sender, WAIT)).await;
+
+    assert!(pumping.is_err(), "pump must wait while the buffer is full");
+    assert_eq!(buffer.len(), 1);
+    // The first event is buffered and the second is held by the blocked
+    // send; the third has not been requested from the source.
+    assert_eq!(source.events.len(), 1);
+    assert_eq!(buffer.next_event(WAIT).await.unwrap(), Some(label_event(1)));
+}
+
+#[tokio::test]
+async fn test_pump_buffer_dropped_returns_ok() {
+    let (sender, buffer) = event_buffer(capacity(1));
+    drop(buffer);
+    let mut source = ListSource {
+        events: Vec::new().into_iter(),
+    };
+
+    let result = tokio::time::timeout(Duration::from_secs(1), pump(&mut source, &sender, WAIT))
+        .await
+        .expect("pump stops once the buffer is closed");
+
+    assert!(result.is_ok());
+}
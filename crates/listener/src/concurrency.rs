@@ -0,0 +1,123 @@
+//! Global limit on the number of work items processed at once.
+//!
+//! A long-lived listener receives events for many work items. Without a limit,
+//! each one would start a pipeline run concurrently and compete for LLM rate
+//! limits, GitHub API quota, and memory. [`WorkItemLimiter`] caps the number of
+//! work items in flight; events for further work items wait for a slot.
+//!
+//! Waiting work items are admitted in arrival order (the underlying semaphore
+//! is fair), so a busy listener cannot starve an early event.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! let limiter = WorkItemLimiter::new(limit);
+//! while let Some(event) = source.next_event(timeout).await? {
+//!     let work_item = /* work item the event refers to */;
+//!     let permit = limiter.acquire(work_item).await?;
+//!     tokio::spawn(async move {
+//!         run_step(event).await;
+//!         drop(permit); // frees the slot for the next waiting work item
+//!     });
+//! }
+//! ```
+
+use std::{num::NonZeroUsize, sync::Arc};
+
+use thiserror::Error;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::instrument;
+
+use pipeline::WorkItemId;
+
+/// Default maximum number of work items processed concurrently.
+pub const DEFAULT_MAX_CONCURRENT_WORK_ITEMS: NonZeroUsize = match NonZeroUsize::new(4) {
+    Some(limit) => limit,
+    None => unreachable!(),
+};
+
+/// Returned by [`WorkItemLimiter::acquire`] after [`WorkItemLimiter::close`]
+/// has been called.
+#[derive(Debug, Error)]
+#[error("work-item limiter is closed; the listener is shutting down")]
+pub struct LimiterClosed;
+
+/// Caps the number of work items processed simultaneously across all runs.
+///
+/// Cloning is cheap; clones share the same slots.
+#[derive(Debug, Clone)]
+pub struct WorkItemLimiter {
+    slots: Arc<Semaphore>,
+    limit: NonZeroUsize,
+}
+
+impl WorkItemLimiter {
+    /// Creates a limiter admitting at most `limit` work items at once.
+    pub fn new(limit: NonZeroUsize) -> Self {
+        Self {
+            slots: Arc::new(Semaphore::new(limit.get())),
+            limit,
+        }
+    }
+
+    /// Returns the configured limit.
+    pub fn limit(&self) -> NonZeroUsize {
+        self.limit
+    }
+
+    /// Returns the number of free slots.
+    pub fn available(&self) -> usize {
+        self.slots.available_permits()
+    }
+
+    /// Waits for a free slot and reserves it for `work_item`.
+    ///
+    /// The slot is released when the returned [`WorkItemPermit`] is dropped.
+    ///
+    /// # Errors
+    ///
+    /// - [`LimiterClosed`] — [`WorkItemLimiter::close`] was called, either
+    ///   before this call or while it was waiting.
+    #[instrument(skip(self))]
+    pub async fn acquire(&self, work_item: WorkItemId) -> Result<WorkItemPermit, LimiterClosed> {
+        if self.slots.available_permits() == 0 {
+            tracing::info!(%work_item, limit = self.limit.get(), "work item queued; concurrency limit reached");
+        }
+        let permit = Arc::clone(&self.slots)
+            .acquire_owned()
+            .await
+            .map_err(|_| LimiterClosed)?;
+        Ok(WorkItemPermit {
+            work_item,
+            _permit: permit,
+        })
+    }
+
+    /// Stops admitting work items.
+    ///
+    /// Pending and future [`WorkItemLimiter::acquire`] calls fail with
+    /// [`LimiterClosed`]. Permits already held remain valid.
+    pub fn close(&self) {
+        self.slots.close();
+    }
+}
+
+/// A reserved processing slot for one work item.
+///
+/// Dropping the permit frees the slot.
+#[derive(Debug)]
+pub struct WorkItemPermit {
+    work_item: WorkItemId,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl WorkItemPermit {
+    /// Returns the work item holding this slot.
+    pub fn work_item(&self) -> WorkItemId {
+        self.work_item
+    }
+}
+
+#[cfg(test)]
+#[path = "concurrency_tests.rs"]
+mod tests;
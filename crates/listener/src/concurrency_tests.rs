@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use super::*;
+
+/// Long enough for a waiting `acquire` to have completed if it could.
+const WAIT: Duration = Duration::from_millis(50);
+
+fn limit(n: usize) -> NonZeroUsize {
+    NonZeroUsize::new(n).unwrap()
+}
+
+#[tokio::test]
+async fn test_acquire_free_slot_returns_permit_for_work_item() {
+    let limiter = WorkItemLimiter::new(limit(2));
+
+    let permit = limiter.acquire(WorkItemId::new(7)).await.unwrap();
+
+    assert_eq!(permit.work_item(), WorkItemId::new(7));
+    assert_eq!(limiter.available(), 1);
+}
+
+#[tokio::test]
+async fn test_acquire_limit_one_second_work_item_waits_for_first() {
+    let limiter = WorkItemLimiter::new(limit(1));
+    let first = limiter.acquire(WorkItemId::new(1)).await.unwrap();
+
+    let waiting = tokio::time::timeout(WAIT, limiter.acquire(WorkItemId::new(2))).await;
+    assert!(waiting.is_err(), "second work item must wait for a slot");
+
+    let second = tokio::spawn({
+        let limiter = limiter.clone();
+        async move { limiter.acquire(WorkItemId::new(2)).await }
+    });
+    drop(first);
+
+    let permit = tokio::time::timeout(Duration::from_secs(1), second)
+        .await
+        .expect("second work item admitted once the first completes")
+        .unwrap()
+        .unwrap();
+    assert_eq!(permit.work_item(), WorkItemId::new(2));
+}
+
+#[tokio::test]
+async fn test_acquire_dropped_permit_frees_slot() {
+    let limiter = WorkItemLimiter::new(limit(1));
+
+    drop(limiter.acquire(WorkItemId::new(1)).await.unwrap());
+
+    assert_eq!(limiter.available(), 1);
+}
+
+#[tokio::test]
+async fn test_acquire_after_close_returns_limiter_closed() {
+    let limiter = WorkItemLimiter::new(limit(1));
+    limiter.close();
+
+    assert!(limiter.acquire(WorkItemId::new(1)).await.is_err());
+}
+
+#[tokio::test]
+async fn test_close_held_permit_stays_valid() {
+    let limiter = WorkItemLimiter::new(limit(1));
+    let permit = limiter.acquire(WorkItemId::new(1)).await.unwrap();
+
+    limiter.close();
+
+    assert_eq!(permit.work_item(), WorkItemId::new(1));
+}
+
+#[test]
+fn test_default_max_concurrent_work_items_is_four() {
+    assert_eq!(DEFAULT_MAX_CONCURRENT_WORK_ITEMS.get(), 4);
+    assert_eq!(
+        WorkItemLimiter::new(DEFAULT_MAX_CONCURRENT_WORK_ITEMS).limit(),
+        DEFAULT_MAX_CONCURRENT_WORK_ITEMS
+    );
+}
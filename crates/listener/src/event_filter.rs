@@ -0,0 +1,89 @@
+//! Allowlist of webhook event types forwarded to the executor.
+//!
+//! A GitHub App receives every event it subscribes to, most of which no
+//! pipeline acts on. An [`EventFilter`] names the deliveries worth parsing,
+//! keyed on the `X-GitHub-Event` header and the payload's `action`:
+//!
+//! | Entry | Matches |
+//! |-------|---------|
+//! | `issues.opened` | `X-GitHub-Event: issues` with `"action": "opened"` |
+//! | `issue_comment.created` | `X-GitHub-Event: issue_comment` with `"action": "created"` |
+//! | `push` | `X-GitHub-Event: push`, with any action or none |
+//!
+//! A delivery the filter does not match is answered with `204` and logged at
+//! DEBUG; only its `action` field is read, and it is never parsed into a
+//! [`pipeline::GitHubEvent`]. The filter is applied after the signature is
+//! verified, so unsigned requests are still rejected with `401`.
+//!
+//! Without a filter every delivery is forwarded.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §GitHubWebhookEventSource.
+
+use std::collections::HashSet;
+
+use serde::Deserialize;
+
+/// Header naming the delivery's event type.
+pub const EVENT_HEADER: &str = "x-github-event";
+
+/// Event types, optionally narrowed to actions, that are forwarded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// Entries as configured: `event` or `event.action`.
+    allowed: HashSet<String>,
+}
+
+impl EventFilter {
+    /// Creates a filter forwarding the deliveries named by `entries`, each
+    /// `event` (any action) or `event.action`.
+    pub fn new<S: Into<String>>(entries: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            allowed: entries.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns `true` if a delivery of `event` with `action` is forwarded.
+    pub fn allows(&self, event: &str, action: Option<&str>) -> bool {
+        self.allowed.contains(event)
+            || action.is_some_and(|action| self.allowed.contains(&format!("{event}.{action}")))
+    }
+
+    /// Returns `true` if a verified delivery is forwarded, reading only the
+    /// top-level `action` field of `body`.
+    ///
+    /// `event` is the `X-GitHub-Event` header value; a delivery without one
+    /// is never forwarded. A body whose `action` cannot be read is treated as
+    /// having no action.
+    pub fn allows_delivery(&self, event: Option<&str>, body: &[u8]) -> bool {
+        let Some(event) = event else {
+            tracing::debug!("webhook delivery without an event type ignored");
+            return false;
+        };
+        if self.allowed.contains(event) {
+            return true;
+        }
+        let action = delivery_action(body);
+        let allowed = self.allows(event, action.as_deref());
+        if !allowed {
+            tracing::debug!(event, action = ?action, "webhook delivery filtered out");
+        }
+        allowed
+    }
+}
+
+/// The payload's top-level `action`; every other field is skipped unread.
+fn delivery_action(body: &[u8]) -> Option<String> {
+    #[derive(Deserialize)]
+    struct ActionOnly {
+        action: Option<String>,
+    }
+    serde_json::from_slice::<ActionOnly>(body)
+        .ok()
+        .and_then(|payload| payload.action)
+}
+
+#[cfg(test)]
+#[path = "event_filter_tests.rs"]
+mod tests;
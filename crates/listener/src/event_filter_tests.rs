@@ -0,0 +1,58 @@
+use super::*;
+
+fn filter() -> EventFilter {
+    EventFilter::new(["issues.opened", "issue_comment.created", "push"])
+}
+
+// ─── allows ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_allows_listed_event_and_action_returns_true() {
+    assert!(filter().allows("issues", Some("opened")));
+    assert!(filter().allows("issue_comment", Some("created")));
+}
+
+#[test]
+fn test_allows_unlisted_action_returns_false() {
+    assert!(!filter().allows("issues", Some("closed")));
+    assert!(!filter().allows("issues", None));
+}
+
+#[test]
+fn test_allows_event_listed_without_action_matches_any_action() {
+    assert!(filter().allows("push", None));
+    assert!(filter().allows("push", Some("anything")));
+}
+
+// ─── allows_delivery ────────────────────────────────────────────────────────
+
+#[test]
+fn test_allows_delivery_reads_action_from_body() {
+    let opened = br#"{"action":"opened","issue":{"number":7}}"#;
+    let closed = br#"{"action":"closed","issue":{"number":7}}"#;
+
+    assert!(filter().allows_delivery(Some("issues"), opened));
+    assert!(!filter().allows_delivery(Some("issues"), closed));
+}
+
+#[test]
+fn test_allows_delivery_without_event_header_returns_false() {
+    assert!(!filter().allows_delivery(None, br#"{"action":"opened"}"#));
+}
+
+#[test]
+fn test_allows_delivery_action_missing_or_unreadable_treated_as_none() {
+    assert!(!filter().allows_delivery(Some("issues"), b"{}"));
+    assert!(!filter().allows_delivery(Some("issues"), br#"{"action":1}"#));
+    assert!(!filter().allows_delivery(Some("issues"), b"not json"));
+}
+
+#[test]
+fn test_allows_delivery_event_listed_without_action_skips_body() {
+    assert!(filter().allows_delivery(Some("push"), b"not json"));
+}
+
+#[test]
+fn test_allows_delivery_empty_filter_forwards_nothing() {
+    assert!(!EventFilter::default().allows_delivery(Some("issues"), br#"{"action":"opened"}"#));
+}
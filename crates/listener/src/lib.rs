@@ -3,9 +3,9 @@
 //! Implements the [`pipeline::EventSource`] trait with two backends:
 //!
 //! - [`GitHubWebhookEventSource`] — binds an HTTP server and receives GitHub
-//!   webhook payloads directly (or via smee.io in development). Uses the
-//!   webhook responder built into `github-bot-sdk` and validates the HMAC-SHA256
-//!   signature of every incoming request.
+//!   webhook payloads directly (or via smee.io in development). Validates the
+//!   HMAC-SHA256 signature of every incoming request with
+//!   [`signature::WebhookVerifier`] before parsing it.
 //!
 //! - [`QueueEventSource`] — consumes messages from a cloud message queue via
 //!   `queue-runtime` (Azure Service Bus today; AWS SQS planned). Each message
@@ -24,6 +24,51 @@
 //! | Azure queue | `QueueEventSource` + Azure Service Bus | Managed identity recommended |
 //! | AWS queue | `QueueEventSource` + AWS SQS | Planned in `queue-runtime` |
 //!
+//! ## Event Context
+//!
+//! Every parsed [`GitHubEvent`] carries a [`pipeline::EventContext`] built by
+//! [`payload::event_context`] from the payload's `installation.id` and
+//! `repository.full_name`. The `cli` uses it to select the installation token
+//! and the target repository.
+//!
+//! ## Concurrency Limit
+//!
+//! [`WorkItemLimiter`] caps how many work items are processed at the same
+//! time when running as a long-lived listener. Events for additional work
+//! items wait, in arrival order, until a slot is freed.
+//!
+//! ## Backpressure
+//!
+//! Events reach the executor through a bounded [`backpressure::event_buffer`].
+//! When it is full, the webhook source answers new deliveries with `503` and
+//! pull-based sources stop receiving until the executor catches up. The
+//! webhook buffer size is [`pipeline::WebhookConfig::event_buffer_capacity`].
+//!
+//! ## Signature Verification
+//!
+//! Each delivery's `X-Hub-Signature-256` header is checked against the raw
+//! body with [`WebhookVerifier`], in constant time, before the event filter
+//! runs or the JSON is parsed (see
+//! [`GitHubWebhookEventSource::screen_delivery`]). A missing or wrong
+//! signature is answered with `401`. The verifier
+//! accepts [`pipeline::WebhookConfig::secret`] and any
+//! [`pipeline::WebhookConfig::previous_secrets`], so the secret can be
+//! rotated without rejecting deliveries in flight.
+//!
+//! ## Event Filtering
+//!
+//! [`GitHubWebhookEventSource::with_event_filter`] restricts forwarding to
+//! the listed event types and actions (e.g. `issues.opened`). Other verified
+//! deliveries are answered with `204` ([`WebhookAck::Ignored`]) without being
+//! parsed (see [`event_filter::EventFilter`]).
+//!
+//! ## Routing
+//!
+//! The webhook server accepts deliveries only on
+//! [`pipeline::WebhookConfig::webhook_path`] and answers liveness probes on
+//! [`pipeline::WebhookConfig::health_path`]; any other path gets `404` (see
+//! [`routes::WebhookRoutes`]).
+//!
 //! ## Architectural Layer
 //!
 //! **Infrastructure.** Transport details, provider configuration, and message
@@ -37,9 +82,27 @@
 //!
 //! *This crate is a skeleton. Method bodies are filled in during PR 10.*
 
+pub mod backpressure;
+pub mod concurrency;
+pub mod event_filter;
+pub mod payload;
+pub mod routes;
+pub mod signature;
+
+pub use backpressure::{
+    event_buffer, pump, BufferClosed, EventBuffer, EventBufferSender, OfferError, WebhookAck,
+};
+pub use concurrency::{
+    LimiterClosed, WorkItemLimiter, WorkItemPermit, DEFAULT_MAX_CONCURRENT_WORK_ITEMS,
+};
+pub use event_filter::{EventFilter, EVENT_HEADER};
+pub use routes::{Route, RouteConfigError, WebhookRoutes};
+pub use signature::{DeliveryError, SignatureError, WebhookVerifier, SIGNATURE_HEADER};
+
 use std::time::Duration;
 
 use async_trait::async_trait;
+use serde_json::Value as JsonValue;
 use tracing::instrument;
 
 use pipeline::github::{
@@ -51,8 +114,17 @@ use pipeline::github::{
 /// GitHub webhook-based [`EventSource`] implementation.
 ///
 /// Binds an HTTP server on `config.bind_address` using `github-bot-sdk`'s
-/// webhook responder. Every incoming POST is HMAC-SHA256 verified against
-/// `config.secret` before being parsed into a [`GitHubEvent`].
+/// webhook responder. Requests are routed by [`WebhookRoutes`]: POSTs to
+/// `config.webhook_path` pass through [`Self::screen_delivery`], which
+/// verifies the HMAC-SHA256 signature against `config.secret` and
+/// `config.previous_secrets` (`401` on failure) before the event filter runs
+/// or the body is parsed into a [`GitHubEvent`], `config.health_path` answers
+/// `200`, and every other path answers `404`.
+///
+/// Verified events are placed in a bounded [`EventBuffer`] of
+/// `config.event_buffer_capacity` events, which [`EventSource::next_event`]
+/// drains. When the buffer is full the delivery is answered with `503` (see
+/// [`WebhookAck`]) instead of being queued.
 ///
 /// ## Local Development
 ///
@@ -65,21 +137,113 @@ use pipeline::github::{
 /// See `docs/spec/interfaces/github-traits.md` §GitHubWebhookEventSource.
 pub struct GitHubWebhookEventSource {
     /// Configuration for the webhook HTTP server.
-    #[allow(dead_code)]
     config: WebhookConfig,
-    // Internal fields (channel receiver, server handle) filled in during PR 10.
+    /// Events verified by the HTTP server, awaiting the executor.
+    buffer: EventBuffer,
+    /// Producer handle given to the HTTP server's request handler.
+    ingress: EventBufferSender,
+    /// Route table consulted by the HTTP server's request handler.
+    routes: WebhookRoutes,
+    /// Signature check applied to every delivery before it is parsed.
+    verifier: WebhookVerifier,
+    /// Event types forwarded; `None` forwards every delivery.
+    event_filter: Option<EventFilter>,
+    // Internal fields (server handle) filled in during PR 10.
 }
 
 impl GitHubWebhookEventSource {
     /// Construct and start the webhook HTTP server.
     ///
-    /// # Panics
-    ///
-    /// Panics (via `todo!()`) until a later change provides the implementation.
     /// Binding to `config.bind_address` and server lifecycle management are
-    /// implemented at that point.
-    pub fn new(config: WebhookConfig) -> Self {
-        Self { config }
+    /// implemented in a later change.
+    ///
+    /// # Errors
+    ///
+    /// Any [`RouteConfigError`] from `config.webhook_path` and
+    /// `config.health_path`.
+    pub fn new(config: WebhookConfig) -> Result<Self, RouteConfigError> {
+        let routes = WebhookRoutes::from_config(&config)?;
+        let (ingress, buffer) = event_buffer(config.event_buffer_capacity);
+        let verifier = WebhookVerifier::from_config(&config);
+        Ok(Self {
+            config,
+            buffer,
+            ingress,
+            routes,
+            verifier,
+            event_filter: None,
+        })
+    }
+
+    /// Returns the configuration the source was constructed with.
+    pub fn config(&self) -> &WebhookConfig {
+        &self.config
+    }
+
+    /// Forwards only deliveries matching `filter`; others are answered with
+    /// `204` without being parsed. The default forwards every delivery.
+    #[must_use]
+    pub fn with_event_filter(mut self, filter: EventFilter) -> Self {
+        self.event_filter = Some(filter);
+        self
+    }
+
+    /// Returns the event filter, if one is set.
+    pub fn event_filter(&self) -> Option<&EventFilter> {
+        self.event_filter.as_ref()
+    }
+
+    /// Checks a delivery to the webhook path and returns its parsed payload.
+    ///
+    /// `signature` and `event` are the `X-Hub-Signature-256` and
+    /// `X-GitHub-Event` header values; `body` is the raw request body. The
+    /// signature is verified first, so nothing in an unauthenticated body is
+    /// read. The event filter runs next, and only a forwarded delivery is
+    /// parsed. `Ok(None)` means the delivery was filtered out and is answered
+    /// with [`WebhookAck::Ignored`].
+    ///
+    /// # Errors
+    ///
+    /// - [`DeliveryError::Signature`] (`401`) — the signature is missing or
+    ///   does not match any configured secret.
+    /// - [`DeliveryError::InvalidPayload`] (`400`) — a forwarded body is not
+    ///   JSON.
+    pub fn screen_delivery(
+        &self,
+        signature: Option<&str>,
+        event: Option<&str>,
+        body: &[u8],
+    ) -> Result<Option<JsonValue>, DeliveryError> {
+        self.verifier.authenticate(signature, body)?;
+        if !self.forwards(event, body) {
+            return Ok(None);
+        }
+        signature::parse_payload(body).map(Some)
+    }
+
+    /// Returns `true` if a verified delivery of type `event` with raw `body`
+    /// is forwarded. Always `true` without a filter.
+    fn forwards(&self, event: Option<&str>, body: &[u8]) -> bool {
+        self.event_filter
+            .as_ref()
+            .is_none_or(|filter| filter.allows_delivery(event, body))
+    }
+
+    /// Returns the route table the HTTP request handler dispatches with.
+    pub fn routes(&self) -> &WebhookRoutes {
+        &self.routes
+    }
+
+    /// Returns the signature check [`screen_delivery`](Self::screen_delivery)
+    /// applies to each delivery before filtering or parsing it.
+    pub fn verifier(&self) -> &WebhookVerifier {
+        &self.verifier
+    }
+
+    /// Returns the handle the HTTP request handler uses to buffer verified
+    /// events and decide each delivery's response status.
+    pub fn ingress(&self) -> EventBufferSender {
+        self.ingress.clone()
     }
 }
 
@@ -87,16 +251,17 @@ impl GitHubWebhookEventSource {
 impl EventSource for GitHubWebhookEventSource {
     /// Await the next webhook event, blocking for at most `timeout`.
     ///
-    /// - Parses and HMAC-verifies the raw HTTP payload.
-    /// - On signature failure, returns [`EventSourceError::AuthError`].
-    /// - On parse failure, returns [`EventSourceError::ParseError`].
-    /// - On timeout, returns `Ok(None)`.
+    /// Pops the oldest event from the [`EventBuffer`]. Deliveries that fail
+    /// signature verification or parsing are answered by the HTTP handler and
+    /// never reach the buffer.
+    ///
+    /// On timeout, returns `Ok(None)`.
     #[instrument(skip(self))]
     async fn next_event(
         &mut self,
-        _timeout: Duration,
+        timeout: Duration,
     ) -> Result<Option<GitHubEvent>, EventSourceError> {
-        todo!("GitHubWebhookEventSource::next_event — implemented in PR 10")
+        self.buffer.next_event(timeout).await
     }
 }
 
@@ -159,3 +324,7 @@ impl EventSource for QueueEventSource {
         todo!("QueueEventSource::next_event — implemented in PR 10")
     }
 }
+
+#[cfg(test)]
+#[path = "lib_tests.rs"]
+mod tests;
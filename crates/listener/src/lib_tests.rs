@@ -0,0 +1,214 @@
+use std::num::NonZeroUsize;
+
+use pipeline::WorkItemId;
+
+use super::*;
+
+const WAIT: Duration = Duration::from_millis(50);
+
+fn config(capacity: usize) -> WebhookConfig {
+    WebhookConfig {
+        bind_address: "127.0.0.1:0".parse().unwrap(),
+        webhook_path: "/webhook".to_string(),
+        health_path: "/health".to_string(),
+        secret: "secret".to_string(),
+        previous_secrets: Vec::new(),
+        event_buffer_capacity: NonZeroUsize::new(capacity).unwrap(),
+    }
+}
+
+fn label_event(issue: u64) -> GitHubEvent {
+    GitHubEvent::LabelApplied {
+        work_item_id: WorkItemId::new(issue),
+        label: "cogworks:run".to_string(),
+        context: Default::default(),
+    }
+}
+
+#[tokio::test]
+async fn test_next_event_ingress_offered_event_returns_event_in_order() {
+    let mut source = GitHubWebhookEventSource::new(config(4)).unwrap();
+    let ingress = source.ingress();
+    ingress.try_offer(label_event(1)).unwrap();
+    ingress.try_offer(label_event(2)).unwrap();
+
+    assert_eq!(source.next_event(WAIT).await.unwrap(), Some(label_event(1)));
+    assert_eq!(source.next_event(WAIT).await.unwrap(), Some(label_event(2)));
+}
+
+#[tokio::test]
+async fn test_next_event_no_deliveries_returns_none_after_timeout() {
+    let mut source = GitHubWebhookEventSource::new(config(4)).unwrap();
+
+    assert_eq!(source.next_event(WAIT).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_next_event_full_buffer_drained_accepts_again() {
+    let mut source = GitHubWebhookEventSource::new(config(1)).unwrap();
+    let ingress = source.ingress();
+    assert_eq!(ingress.accept_webhook(label_event(1)), WebhookAck::Accepted);
+    assert_eq!(ingress.accept_webhook(label_event(2)), WebhookAck::Busy);
+
+    assert_eq!(source.next_event(WAIT).await.unwrap(), Some(label_event(1)));
+
+    assert_eq!(ingress.accept_webhook(label_event(3)), WebhookAck::Accepted);
+}
+
+#[test]
+fn test_new_buffer_capacity_from_config_returns_configured_capacity() {
+    let source = GitHubWebhookEventSource::new(config(3)).unwrap();
+
+    assert_eq!(source.ingress().capacity().get(), 3);
+    assert_eq!(source.config().event_buffer_capacity.get(), 3);
+}
+
+#[test]
+fn test_new_routes_follow_configured_webhook_path() {
+    let mut config = config(4);
+    config.webhook_path = "/github/webhook".to_string();
+
+    let source = GitHubWebhookEventSource::new(config).unwrap();
+
+    assert_eq!(source.routes().route("/github/webhook"), Route::Webhook);
+    assert_eq!(source.routes().route("/webhook"), Route::NotFound);
+    assert_eq!(source.routes().route("/health"), Route::Health);
+}
+
+#[test]
+fn test_new_conflicting_paths_returns_route_config_error() {
+    let mut config = config(4);
+    config.health_path = config.webhook_path.clone();
+
+    let result = GitHubWebhookEventSource::new(config);
+
+    assert!(matches!(result, Err(RouteConfigError::Conflict { .. })));
+}
+
+// ─── screen_delivery ────────────────────────────────────────────────────────
+
+/// Deliveries signed with `config`'s secret, `"secret"`.
+const OPENED: &[u8] = br#"{"action":"opened","issue":{"number":7}}"#;
+const OPENED_SIGNATURE: &str =
+    "sha256=2486f112e22969d637c65f8c59e129c810c72f471b3bd021fcd824d541417d45";
+const CLOSED: &[u8] = br#"{"action":"closed","issue":{"number":7}}"#;
+const CLOSED_SIGNATURE: &str =
+    "sha256=bc72bf198ab5baa0921b45ab16d2e481c1d98bea42d833f3d166c5294c014e96";
+const NOT_JSON: &[u8] = b"not json";
+const NOT_JSON_SIGNATURE: &str =
+    "sha256=19e44cefdf4796e0dc616e940e49c2ecd3fc476343e40c7c95d39a75dc10e958";
+
+fn filtered_source() -> GitHubWebhookEventSource {
+    GitHubWebhookEventSource::new(config(4))
+        .unwrap()
+        .with_event_filter(EventFilter::new(["issues.opened"]))
+}
+
+#[test]
+fn test_screen_delivery_valid_signature_returns_payload() {
+    let source = GitHubWebhookEventSource::new(config(4)).unwrap();
+
+    let payload = source
+        .screen_delivery(Some(OPENED_SIGNATURE), Some("issues"), OPENED)
+        .unwrap();
+
+    assert_eq!(payload.unwrap()["action"], "opened");
+}
+
+#[test]
+fn test_screen_delivery_tampered_body_returns_401() {
+    let source = GitHubWebhookEventSource::new(config(4)).unwrap();
+
+    let error = source
+        .screen_delivery(Some(OPENED_SIGNATURE), Some("issues"), CLOSED)
+        .unwrap_err();
+
+    assert_eq!(error.status_code(), 401);
+}
+
+#[test]
+fn test_screen_delivery_signed_with_previous_secret_returns_payload() {
+    let mut config = config(4);
+    config.previous_secrets = vec![std::mem::replace(&mut config.secret, "rotated".to_string())];
+    let source = GitHubWebhookEventSource::new(config).unwrap();
+
+    let payload = source
+        .screen_delivery(Some(OPENED_SIGNATURE), Some("issues"), OPENED)
+        .unwrap();
+
+    assert!(payload.is_some());
+}
+
+#[test]
+fn test_screen_delivery_filtered_out_unsigned_returns_401_not_204() {
+    let source = filtered_source();
+
+    let error = source
+        .screen_delivery(None, Some("issues"), CLOSED)
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        DeliveryError::Signature(SignatureError::Missing)
+    ));
+    assert_eq!(error.status_code(), 401);
+}
+
+#[test]
+fn test_screen_delivery_filtered_out_signed_returns_none() {
+    let source = filtered_source();
+
+    let payload = source
+        .screen_delivery(Some(CLOSED_SIGNATURE), Some("issues"), CLOSED)
+        .unwrap();
+
+    assert!(payload.is_none());
+}
+
+#[test]
+fn test_screen_delivery_signed_non_json_returns_400() {
+    let source = GitHubWebhookEventSource::new(config(4)).unwrap();
+
+    let error = source
+        .screen_delivery(Some(NOT_JSON_SIGNATURE), Some("issues"), NOT_JSON)
+        .unwrap_err();
+
+    assert_eq!(error.status_code(), 400);
+}
+
+#[test]
+fn test_screen_delivery_listed_event_returns_parsed_payload() {
+    let source = filtered_source();
+
+    let payload = source
+        .screen_delivery(Some(OPENED_SIGNATURE), Some("issues"), OPENED)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(payload["issue"]["number"], 7);
+}
+
+#[test]
+fn test_screen_delivery_filtered_non_json_returns_none_without_parsing() {
+    let source = filtered_source();
+
+    let payload = source
+        .screen_delivery(Some(NOT_JSON_SIGNATURE), Some("issues"), NOT_JSON)
+        .unwrap();
+
+    assert!(payload.is_none());
+    assert_eq!(WebhookAck::Ignored.status_code(), 204);
+}
+
+#[tokio::test]
+async fn test_screen_delivery_filtered_out_is_acknowledged_but_not_forwarded() {
+    let mut source = filtered_source();
+
+    let payload = source
+        .screen_delivery(Some(CLOSED_SIGNATURE), Some("issues"), CLOSED)
+        .unwrap();
+
+    assert!(payload.is_none());
+    assert_eq!(WebhookAck::Ignored.status_code(), 204);
+    assert_eq!(source.next_event(WAIT).await.unwrap(), None);
+}
@@ -0,0 +1,43 @@
+//! Extraction of typed fields from raw GitHub webhook payloads.
+//!
+//! Both event sources receive the same JSON webhook body (directly, or
+//! forwarded through a queue), so the field extraction is shared here.
+
+use serde_json::Value as JsonValue;
+
+use pipeline::{EventContext, InstallationId, RepositoryId};
+
+/// Extracts the installation and repository context from a webhook payload.
+///
+/// Reads `installation.id` and `repository.full_name` (`"owner/repo"`).
+/// Either field is `None` when it is absent or has an unexpected type; GitHub
+/// omits `installation` for webhooks that are not delivered to a GitHub App,
+/// and omits `repository` for organisation-level events.
+///
+/// The delivery fields are left `None`; they come from the `X-GitHub-Delivery`
+/// header and the receive time, which the event source fills in.
+#[must_use]
+pub fn event_context(payload: &JsonValue) -> EventContext {
+    let installation_id = payload
+        .get("installation")
+        .and_then(|installation| installation.get("id"))
+        .and_then(JsonValue::as_u64)
+        .map(InstallationId::new);
+
+    let repository = payload
+        .get("repository")
+        .and_then(|repository| repository.get("full_name"))
+        .and_then(JsonValue::as_str)
+        .and_then(RepositoryId::new);
+
+    EventContext {
+        installation_id,
+        repository,
+        delivery_id: None,
+        delivered_at: None,
+    }
+}
+
+#[cfg(test)]
+#[path = "payload_tests.rs"]
+mod tests;
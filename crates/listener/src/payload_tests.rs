@@ -0,0 +1,58 @@
+use serde_json::json;
+
+use super::*;
+
+#[test]
+fn test_event_context_issues_payload_extracts_installation_and_repository() {
+    let payload = json!({
+        "action": "labeled",
+        "issue": { "number": 42 },
+        "label": { "name": "cogworks:run" },
+        "repository": { "id": 1296269, "full_name": "octo/widgets" },
+        "installation": { "id": 12345678 }
+    });
+
+    let context = event_context(&payload);
+
+    assert_eq!(context.installation_id, Some(InstallationId::new(12345678)));
+    assert_eq!(context.repository, RepositoryId::parse("octo/widgets"));
+    assert_eq!(context.delivery_id, None);
+    assert_eq!(context.delivered_at, None);
+}
+
+#[test]
+fn test_event_context_without_installation_returns_none_installation() {
+    let payload = json!({ "repository": { "full_name": "octo/widgets" } });
+
+    let context = event_context(&payload);
+
+    assert_eq!(context.installation_id, None);
+    assert_eq!(context.repository, RepositoryId::parse("octo/widgets"));
+}
+
+#[test]
+fn test_event_context_organisation_event_returns_none_repository() {
+    let payload = json!({ "organization": { "login": "octo" }, "installation": { "id": 7 } });
+
+    let context = event_context(&payload);
+
+    assert_eq!(context.installation_id, Some(InstallationId::new(7)));
+    assert_eq!(context.repository, None);
+}
+
+#[test]
+fn test_event_context_unexpected_types_return_none() {
+    let payload = json!({
+        "repository": { "full_name": 17 },
+        "installation": { "id": "12345678" }
+    });
+
+    assert_eq!(event_context(&payload), EventContext::default());
+}
+
+#[test]
+fn test_event_context_malformed_full_name_returns_none_repository() {
+    let payload = json!({ "repository": { "full_name": "octo/widgets/extra" } });
+
+    assert_eq!(event_context(&payload).repository, None);
+}
@@ -0,0 +1,140 @@
+//! Request routing for the webhook HTTP server.
+//!
+//! The server answers on two configured paths: [`WebhookConfig::webhook_path`]
+//! for GitHub deliveries and [`WebhookConfig::health_path`] for liveness
+//! probes. Deployments that mount the listener behind a reverse proxy set the
+//! webhook path to the proxied location (e.g. `/github/webhook`); the health
+//! path is unaffected. Every other path gets `404`.
+//!
+//! Paths are compared exactly, ignoring any query string and a trailing `/`
+//! (so `/github/webhook/` matches `/github/webhook`, but `/` only matches
+//! `/`).
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §WebhookConfig.
+
+use thiserror::Error;
+
+use pipeline::github::WebhookConfig;
+
+/// Where an incoming request is dispatched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    /// A GitHub delivery: verify, parse, and buffer it.
+    Webhook,
+    /// A liveness probe; answered with `200`.
+    Health,
+    /// Any other path; answered with `404`.
+    NotFound,
+}
+
+impl Route {
+    /// HTTP status for routes answered without further processing.
+    ///
+    /// Returns `None` for [`Route::Webhook`], whose status depends on the
+    /// delivery (see [`crate::WebhookAck`]).
+    pub fn fixed_status(self) -> Option<u16> {
+        match self {
+            Route::Webhook => None,
+            Route::Health => Some(200),
+            Route::NotFound => Some(404),
+        }
+    }
+}
+
+/// Returned when the configured paths cannot be served.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RouteConfigError {
+    /// A path does not start with `/`.
+    #[error("{field} '{path}' must start with '/'")]
+    NotAbsolute {
+        /// The offending config field (`webhook_path` or `health_path`).
+        field: &'static str,
+        /// The configured value.
+        path: String,
+    },
+
+    /// The webhook and health endpoints share a path.
+    #[error("webhook_path and health_path are both '{path}'")]
+    Conflict {
+        /// The shared path.
+        path: String,
+    },
+}
+
+/// The webhook server's route table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookRoutes {
+    webhook_path: String,
+    health_path: String,
+}
+
+impl WebhookRoutes {
+    /// Builds the route table from `config`.
+    ///
+    /// # Errors
+    ///
+    /// - [`RouteConfigError::NotAbsolute`] — a path does not start with `/`.
+    /// - [`RouteConfigError::Conflict`] — both paths normalise to the same
+    ///   value.
+    pub fn from_config(config: &WebhookConfig) -> Result<Self, RouteConfigError> {
+        let webhook_path = absolute("webhook_path", &config.webhook_path)?;
+        let health_path = absolute("health_path", &config.health_path)?;
+        if webhook_path == health_path {
+            return Err(RouteConfigError::Conflict { path: webhook_path });
+        }
+        Ok(Self {
+            webhook_path,
+            health_path,
+        })
+    }
+
+    /// The normalised webhook path.
+    pub fn webhook_path(&self) -> &str {
+        &self.webhook_path
+    }
+
+    /// The normalised health path.
+    pub fn health_path(&self) -> &str {
+        &self.health_path
+    }
+
+    /// Routes a request for `path` (which may include a query string).
+    pub fn route(&self, path: &str) -> Route {
+        let path = path.split_once('?').map_or(path, |(path, _)| path);
+        let path = normalise(path);
+        if path == self.webhook_path {
+            Route::Webhook
+        } else if path == self.health_path {
+            Route::Health
+        } else {
+            Route::NotFound
+        }
+    }
+}
+
+fn absolute(field: &'static str, path: &str) -> Result<String, RouteConfigError> {
+    if !path.starts_with('/') {
+        return Err(RouteConfigError::NotAbsolute {
+            field,
+            path: path.to_string(),
+        });
+    }
+    Ok(normalise(path).to_string())
+}
+
+/// Strips trailing `/`s, keeping the root path as `/`.
+fn normalise(path: &str) -> &str {
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() {
+        "/"
+    } else {
+        trimmed
+    }
+}
+
+#[cfg(test)]
+#[path = "routes_tests.rs"]
+mod tests;
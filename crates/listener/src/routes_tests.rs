@@ -0,0 +1,125 @@
+use std::num::NonZeroUsize;
+
+use super::*;
+
+fn config(webhook_path: &str, health_path: &str) -> WebhookConfig {
+    WebhookConfig {
+        bind_address: "127.0.0.1:0".parse().unwrap(),
+        webhook_path: webhook_path.to_string(),
+        health_path: health_path.to_string(),
+        secret: "secret".to_string(),
+        previous_secrets: Vec::new(),
+        event_buffer_capacity: NonZeroUsize::new(4).unwrap(),
+    }
+}
+
+fn routes(webhook_path: &str, health_path: &str) -> WebhookRoutes {
+    WebhookRoutes::from_config(&config(webhook_path, health_path)).unwrap()
+}
+
+// ─── Route ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_fixed_status_each_route_returns_its_status() {
+    assert_eq!(Route::Webhook.fixed_status(), None);
+    assert_eq!(Route::Health.fixed_status(), Some(200));
+    assert_eq!(Route::NotFound.fixed_status(), Some(404));
+}
+
+// ─── WebhookRoutes::route ───────────────────────────────────────────────────
+
+#[test]
+fn test_route_configured_webhook_path_dispatches_to_webhook() {
+    let routes = routes("/github/webhook", "/healthz");
+
+    assert_eq!(routes.route("/github/webhook"), Route::Webhook);
+}
+
+#[test]
+fn test_route_other_path_returns_not_found() {
+    let routes = routes("/github/webhook", "/healthz");
+
+    for path in [
+        "/",
+        "/github",
+        "/github/webhook/extra",
+        "/webhook",
+        "/GITHUB/WEBHOOK",
+    ] {
+        assert_eq!(routes.route(path), Route::NotFound, "path {path}");
+    }
+}
+
+#[test]
+fn test_route_health_path_unaffected_by_webhook_prefix() {
+    let routes = routes("/github/webhook", "/healthz");
+
+    assert_eq!(routes.route("/healthz"), Route::Health);
+    assert_eq!(routes.route("/github/healthz"), Route::NotFound);
+}
+
+#[test]
+fn test_route_query_string_and_trailing_slash_are_ignored() {
+    let routes = routes("/github/webhook", "/healthz");
+
+    assert_eq!(routes.route("/github/webhook/"), Route::Webhook);
+    assert_eq!(routes.route("/github/webhook?delivery=1"), Route::Webhook);
+    assert_eq!(routes.route("/healthz/?probe=k8s"), Route::Health);
+}
+
+#[test]
+fn test_route_root_webhook_path_matches_only_root() {
+    let routes = routes("/", "/health");
+
+    assert_eq!(routes.route("/"), Route::Webhook);
+    assert_eq!(routes.route("/?x=1"), Route::Webhook);
+    assert_eq!(routes.route("/anything"), Route::NotFound);
+}
+
+// ─── WebhookRoutes::from_config ─────────────────────────────────────────────
+
+#[test]
+fn test_from_config_trailing_slashes_are_normalised() {
+    let routes = routes("/github/webhook/", "/health//");
+
+    assert_eq!(routes.webhook_path(), "/github/webhook");
+    assert_eq!(routes.health_path(), "/health");
+}
+
+#[test]
+fn test_from_config_relative_path_returns_not_absolute() {
+    let result = WebhookRoutes::from_config(&config("github/webhook", "/health"));
+
+    assert_eq!(
+        result,
+        Err(RouteConfigError::NotAbsolute {
+            field: "webhook_path",
+            path: "github/webhook".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_from_config_relative_health_path_returns_not_absolute() {
+    let result = WebhookRoutes::from_config(&config("/webhook", "health"));
+
+    assert!(matches!(
+        result,
+        Err(RouteConfigError::NotAbsolute {
+            field: "health_path",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_from_config_same_paths_after_normalising_returns_conflict() {
+    let result = WebhookRoutes::from_config(&config("/hooks/", "/hooks"));
+
+    assert_eq!(
+        result,
+        Err(RouteConfigError::Conflict {
+            path: "/hooks".to_string()
+        })
+    );
+}
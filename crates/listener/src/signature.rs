@@ -0,0 +1,206 @@
+//! HMAC-SHA256 verification of webhook deliveries.
+//!
+//! GitHub signs every delivery with the webhook secret and sends the result
+//! in the `X-Hub-Signature-256` header as `sha256=<hex digest>`.
+//! [`WebhookVerifier`] recomputes the HMAC over the raw request body and
+//! compares it in constant time. [`WebhookVerifier::authenticate`] is the
+//! first check applied to a delivery; the body is filtered and parsed only
+//! after it passes, so an unauthenticated request never reaches the JSON
+//! parser.
+//!
+//! ## Secret rotation
+//!
+//! A verifier holds every accepted secret: [`WebhookConfig::secret`] and any
+//! [`WebhookConfig::previous_secrets`]. A delivery passes if any of them
+//! produces its signature. To rotate, move the current secret to
+//! `previous_secrets`, set the new one as `secret`, update the GitHub
+//! webhook, then remove the old secret once deliveries signed with it stop.
+//!
+//! | Outcome | HTTP status |
+//! |---------|-------------|
+//! | Header missing, malformed, or no secret matches | `401` |
+//! | Signature valid, body not JSON | `400` |
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/github-traits.md` §GitHubWebhookEventSource.
+
+use hmac::{Hmac, Mac};
+use serde_json::Value as JsonValue;
+use sha2::Sha256;
+use thiserror::Error;
+
+use pipeline::github::WebhookConfig;
+
+/// Header carrying the delivery's signature.
+pub const SIGNATURE_HEADER: &str = "x-hub-signature-256";
+
+/// Prefix of the signature header value.
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+/// Length of an HMAC-SHA256 digest in bytes.
+const DIGEST_LEN: usize = 32;
+
+/// Returned when a delivery's signature does not verify.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SignatureError {
+    /// The request has no `X-Hub-Signature-256` header.
+    #[error("webhook delivery has no {SIGNATURE_HEADER} header")]
+    Missing,
+
+    /// The header is not `sha256=` followed by a 64-digit hex digest.
+    #[error("webhook delivery has a malformed {SIGNATURE_HEADER} header")]
+    Malformed,
+
+    /// No configured secret produces the signature.
+    #[error("webhook delivery signature does not match any configured secret")]
+    Mismatch,
+}
+
+/// Returned by [`WebhookVerifier::verified_payload`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum DeliveryError {
+    /// The signature did not verify; the body was not parsed.
+    #[error(transparent)]
+    Signature(SignatureError),
+
+    /// The signature verified but the body is not valid JSON.
+    #[error("webhook delivery body is not valid JSON: {message}")]
+    InvalidPayload {
+        /// The parser's description of the failure.
+        message: String,
+    },
+}
+
+impl DeliveryError {
+    /// HTTP status for the response: `401` for signature failures, `400` for
+    /// an unreadable body.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::Signature(_) => 401,
+            Self::InvalidPayload { .. } => 400,
+        }
+    }
+}
+
+/// Verifies `X-Hub-Signature-256` against one or more webhook secrets.
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    secrets: Vec<Vec<u8>>,
+}
+
+impl WebhookVerifier {
+    /// Creates a verifier accepting signatures made with any of `secrets`.
+    pub fn new<S: AsRef<[u8]>>(secrets: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            secrets: secrets
+                .into_iter()
+                .map(|secret| secret.as_ref().to_vec())
+                .collect(),
+        }
+    }
+
+    /// Creates a verifier for `config.secret` and `config.previous_secrets`.
+    pub fn from_config(config: &WebhookConfig) -> Self {
+        Self::new(std::iter::once(&config.secret).chain(&config.previous_secrets))
+    }
+
+    /// Checks `signature`, the `X-Hub-Signature-256` header value if present,
+    /// against the raw request `body`.
+    ///
+    /// Every secret is tried; each comparison is constant-time.
+    ///
+    /// # Errors
+    ///
+    /// - [`SignatureError::Missing`] — `signature` is `None`.
+    /// - [`SignatureError::Malformed`] — not `sha256=<64 hex digits>`.
+    /// - [`SignatureError::Mismatch`] — no secret produces the signature.
+    pub fn verify(&self, signature: Option<&str>, body: &[u8]) -> Result<(), SignatureError> {
+        let signature = signature.ok_or(SignatureError::Missing)?;
+        let digest = signature
+            .trim()
+            .strip_prefix(SIGNATURE_PREFIX)
+            .and_then(decode_hex)
+            .ok_or(SignatureError::Malformed)?;
+        let matches = self.secrets.iter().any(|secret| {
+            // HMAC accepts keys of any length, so construction cannot fail.
+            Hmac::<Sha256>::new_from_slice(secret).is_ok_and(|mut mac| {
+                mac.update(body);
+                mac.verify_slice(&digest).is_ok()
+            })
+        });
+        if matches {
+            Ok(())
+        } else {
+            Err(SignatureError::Mismatch)
+        }
+    }
+
+    /// As [`verify`](Self::verify), logging a rejection at WARN without the
+    /// signature or the secrets.
+    ///
+    /// # Errors
+    ///
+    /// [`DeliveryError::Signature`] (`401`) when the signature does not
+    /// verify.
+    pub fn authenticate(&self, signature: Option<&str>, body: &[u8]) -> Result<(), DeliveryError> {
+        self.verify(signature, body).map_err(|error| {
+            tracing::warn!(%error, "webhook delivery rejected");
+            DeliveryError::Signature(error)
+        })
+    }
+
+    /// Verifies the delivery, then parses `body` as JSON.
+    ///
+    /// # Errors
+    ///
+    /// - [`DeliveryError::Signature`] — as for
+    ///   [`authenticate`](Self::authenticate); the body is not parsed.
+    /// - [`DeliveryError::InvalidPayload`] — the body is not JSON.
+    pub fn verified_payload(
+        &self,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> Result<JsonValue, DeliveryError> {
+        self.authenticate(signature, body)?;
+        parse_payload(body)
+    }
+}
+
+impl std::fmt::Debug for WebhookVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookVerifier")
+            .field(
+                "secrets",
+                &format_args!("[{} REDACTED]", self.secrets.len()),
+            )
+            .finish()
+    }
+}
+
+/// Parses an already verified delivery body.
+pub(crate) fn parse_payload(body: &[u8]) -> Result<JsonValue, DeliveryError> {
+    serde_json::from_slice(body).map_err(|error| DeliveryError::InvalidPayload {
+        message: error.to_string(),
+    })
+}
+
+/// Decodes a digest of exactly [`DIGEST_LEN`] bytes from hex.
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() != DIGEST_LEN * 2 {
+        return None;
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let digit = |byte: u8| char::from(byte).to_digit(16);
+            Some((digit(pair[0])? * 16 + digit(pair[1])?) as u8)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "signature_tests.rs"]
+mod tests;
@@ -0,0 +1,131 @@
+use super::*;
+
+// Example delivery from GitHub's webhook validation documentation.
+const SECRET: &str = "It's a Secret to Everybody";
+const BODY: &[u8] = b"Hello, World!";
+const SIGNATURE: &str = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+// ─── verify ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_verify_valid_signature_returns_ok() {
+    let verifier = WebhookVerifier::new([SECRET]);
+
+    assert_eq!(verifier.verify(Some(SIGNATURE), BODY), Ok(()));
+}
+
+#[test]
+fn test_verify_tampered_body_returns_mismatch() {
+    let verifier = WebhookVerifier::new([SECRET]);
+
+    assert_eq!(
+        verifier.verify(Some(SIGNATURE), b"Hello, World?"),
+        Err(SignatureError::Mismatch)
+    );
+}
+
+#[test]
+fn test_verify_signed_with_previous_secret_returns_ok() {
+    let verifier = WebhookVerifier::new(["the-new-secret", SECRET]);
+
+    assert_eq!(verifier.verify(Some(SIGNATURE), BODY), Ok(()));
+}
+
+#[test]
+fn test_verify_secret_rotated_out_returns_mismatch() {
+    let verifier = WebhookVerifier::new(["the-new-secret"]);
+
+    assert_eq!(
+        verifier.verify(Some(SIGNATURE), BODY),
+        Err(SignatureError::Mismatch)
+    );
+}
+
+#[test]
+fn test_verify_from_config_accepts_previous_secrets() {
+    let config = pipeline::github::WebhookConfig {
+        bind_address: "127.0.0.1:0".parse().unwrap(),
+        webhook_path: "/webhook".to_string(),
+        health_path: "/health".to_string(),
+        secret: "the-new-secret".to_string(),
+        previous_secrets: vec![SECRET.to_string()],
+        event_buffer_capacity: std::num::NonZeroUsize::new(4).unwrap(),
+    };
+
+    let verifier = WebhookVerifier::from_config(&config);
+
+    assert_eq!(verifier.verify(Some(SIGNATURE), BODY), Ok(()));
+}
+
+#[test]
+fn test_verify_missing_header_returns_missing() {
+    let verifier = WebhookVerifier::new([SECRET]);
+
+    assert_eq!(verifier.verify(None, BODY), Err(SignatureError::Missing));
+}
+
+#[test]
+fn test_verify_malformed_header_returns_malformed() {
+    let verifier = WebhookVerifier::new([SECRET]);
+
+    for header in [
+        "757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17",
+        "sha1=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17",
+        "sha256=757107ea",
+        "sha256=zz7107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17",
+    ] {
+        assert_eq!(
+            verifier.verify(Some(header), BODY),
+            Err(SignatureError::Malformed),
+            "{header}"
+        );
+    }
+}
+
+// ─── authenticate / verified_payload ────────────────────────────────────────
+
+#[test]
+fn test_authenticate_tampered_body_returns_401() {
+    let verifier = WebhookVerifier::new([SECRET]);
+
+    let error = verifier
+        .authenticate(Some(SIGNATURE), b"Hello, World?")
+        .unwrap_err();
+
+    assert_eq!(error.status_code(), 401);
+}
+
+#[test]
+fn test_verified_payload_valid_signature_non_json_body_returns_400() {
+    let verifier = WebhookVerifier::new([SECRET]);
+
+    let error = verifier
+        .verified_payload(Some(SIGNATURE), BODY)
+        .unwrap_err();
+
+    assert!(matches!(error, DeliveryError::InvalidPayload { .. }));
+    assert_eq!(error.status_code(), 400);
+}
+
+#[test]
+fn test_verified_payload_unsigned_body_returns_401_without_parsing() {
+    let verifier = WebhookVerifier::new([SECRET]);
+
+    let error = verifier.verified_payload(None, b"not json").unwrap_err();
+
+    assert!(matches!(
+        error,
+        DeliveryError::Signature(SignatureError::Missing)
+    ));
+    assert_eq!(error.status_code(), 401);
+}
+
+// ─── Debug ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_debug_does_not_reveal_secrets() {
+    let rendered = format!("{:?}", WebhookVerifier::new([SECRET, "older"]));
+
+    assert!(rendered.contains("[2 REDACTED]"));
+    assert!(!rendered.contains(SECRET));
+}
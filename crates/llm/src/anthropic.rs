@@ -0,0 +1,835 @@
+//! Anthropic Messages API request formatting.
+//!
+//! Anthropic takes the system prompt as a top-level `system` field rather than
+//! as a message. Each [`pipeline::SystemSegment`] becomes one text block in
+//! that array, emitted in [`pipeline::SystemLayer`] order, which keeps the
+//! segment boundaries visible to the provider and lets blocks named in
+//! [`CompletionRequest::cache_breakpoints`] carry a
+//! `"cache_control": {"type": "ephemeral"}` marker, so a large stable prefix
+//! (constitutional rules, context packs) is cached between calls. Cache writes
+//! and reads are reported in [`TokenUsage`].
+//!
+//! [`AnthropicProvider`] sends the formatted body through an
+//! [`LlmTransport`] and parses the Messages API response. The request's
+//! client-generated ID is sent in a configurable header, and the ID Anthropic
+//! returns in [`RESPONSE_REQUEST_ID_HEADER`] is recorded on the response.
+//!
+//! [`LlmProvider::complete_streaming`] sets `"stream": true` and reads the
+//! server-sent events as they arrive (see [`AnthropicStream`]): text comes
+//! from `content_block_delta` events, input tokens from `message_start`, and
+//! the stop reason and output tokens from `message_delta`.
+//!
+//! [`LlmProvider::complete`] retries 429s, 5xx responses (including 529
+//! overloaded), and connection failures with jittered exponential back-off,
+//! honouring `retry-after` (see [`crate::backoff`]).
+//!
+//! At DEBUG, each request and response is logged with the `x-api-key` header
+//! and any [`AnthropicProvider::with_redaction`] pattern matches replaced by
+//! `***` (see [`crate::redaction`]).
+//!
+//! [`LlmProvider::estimate_prompt_tokens`] counts the prompt locally with the
+//! bundled [`BpeTokenizer`](crate::tokenizer::BpeTokenizer).
+//!
+//! [`AnthropicProvider::cancel_batch`] stops a Message Batch that is no longer
+//! needed, e.g. because its run was cancelled, so it stops incurring cost.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §Provider wire formats.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tracing::instrument;
+
+use pipeline::retry::{NoopRetryMetrics, RetryMetrics, RunRetryBudget};
+use pipeline::{
+    CompletionChunk, CompletionRequest, CompletionResponse, CompletionStream, FinishReason,
+    LlmError, LlmProvider, MessageRole, PartialCompletion, TokenCount, TokenEstimate, TokenUsage,
+};
+
+use crate::backoff::{with_backoff, BackoffConfig};
+use crate::redaction::Redactor;
+use crate::sse::{SseDecoder, SseEvent};
+use crate::tokenizer::BpeTokenizer;
+use crate::transport::{
+    status_error, HttpRequest, HttpResponse, LlmTransport, ResponseBody, DEFAULT_REQUEST_ID_HEADER,
+};
+
+/// Provider name recorded on logged requests and responses.
+const PROVIDER_NAME: &str = "anthropic";
+
+/// Default Anthropic API origin.
+pub const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+
+/// Value sent in the `anthropic-version` header.
+pub const API_VERSION: &str = "2023-06-01";
+
+/// Response header carrying the ID Anthropic assigned to the request.
+pub const RESPONSE_REQUEST_ID_HEADER: &str = "request-id";
+
+/// Most `cache_control` breakpoints Anthropic accepts in one request.
+pub const MAX_CACHE_BREAKPOINTS: usize = 4;
+
+/// Operation label of the retry samples recorded for
+/// [`LlmProvider::complete`].
+pub const COMPLETE_RETRY_OPERATION: &str = "llm.anthropic.complete";
+
+/// The `{"type": "ephemeral"}` cache marker.
+#[derive(Debug, Serialize)]
+struct CacheControl {
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+/// A `{"type": "text", "text": ...}` content block.
+#[derive(Debug, Serialize)]
+struct TextBlock<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<CacheControl>,
+}
+
+impl<'a> TextBlock<'a> {
+    fn new(text: &'a str, cached: bool) -> Self {
+        Self {
+            kind: "text",
+            text,
+            cache_control: cached.then_some(CacheControl { kind: "ephemeral" }),
+        }
+    }
+}
+
+/// Message content: a plain string, or a block array when the message
+/// carries a cache breakpoint.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum WireContent<'a> {
+    Text(&'a str),
+    Blocks(Vec<TextBlock<'a>>),
+}
+
+/// One entry of the Messages API `messages` array.
+#[derive(Debug, Serialize)]
+struct WireMessage<'a> {
+    role: &'static str,
+    content: WireContent<'a>,
+}
+
+/// Body of `POST /v1/messages`.
+#[derive(Debug, Serialize)]
+struct MessagesRequest<'a> {
+    model: &'a str,
+    max_tokens: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    system: Vec<TextBlock<'a>>,
+    messages: Vec<WireMessage<'a>>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    stop_sequences: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f64>,
+}
+
+fn role_name(role: MessageRole) -> &'static str {
+    match role {
+        MessageRole::User => "user",
+        MessageRole::Assistant => "assistant",
+    }
+}
+
+/// Build the JSON body for an Anthropic Messages API call.
+///
+/// - System segments become the top-level `system` array, one text block per
+///   segment, in layer order. The field is omitted when there are none.
+/// - `stop_sequences` is passed through unchanged and omitted when empty.
+/// - Each block named in `cache_breakpoints` (system segments first, then
+///   messages) carries `"cache_control": {"type": "ephemeral"}`. A message
+///   with a breakpoint is sent as a one-block content array.
+///
+/// # Errors
+///
+/// - [`LlmError::InvalidRequest`] — the request has no messages, a cache
+///   breakpoint is out of range, there are more than
+///   [`MAX_CACHE_BREAKPOINTS`] breakpoints, or the body could not be
+///   serialised.
+pub fn request_body(request: &CompletionRequest) -> Result<JsonValue, LlmError> {
+    if request.messages.is_empty() {
+        return Err(LlmError::InvalidRequest {
+            message: "Anthropic requests require at least one message".to_string(),
+        });
+    }
+
+    let system = request.layered_system();
+    let system_len = system.len();
+    let block_count = system_len + request.messages.len();
+    if let Some(index) = request
+        .cache_breakpoints
+        .iter()
+        .find(|&&index| index >= block_count)
+    {
+        return Err(LlmError::InvalidRequest {
+            message: format!(
+                "cache breakpoint {index} is out of range; the request has {block_count} content blocks"
+            ),
+        });
+    }
+    if request.cache_breakpoints.len() > MAX_CACHE_BREAKPOINTS {
+        return Err(LlmError::InvalidRequest {
+            message: format!(
+                "Anthropic accepts at most {MAX_CACHE_BREAKPOINTS} cache breakpoints, got {}",
+                request.cache_breakpoints.len()
+            ),
+        });
+    }
+    let cached = |index: usize| request.cache_breakpoints.contains(&index);
+
+    let body = MessagesRequest {
+        model: &request.model,
+        max_tokens: request.max_tokens.as_u64(),
+        system: system
+            .into_iter()
+            .enumerate()
+            .map(|(index, segment)| TextBlock::new(&segment.content, cached(index)))
+            .collect(),
+        messages: request
+            .messages
+            .iter()
+            .enumerate()
+            .map(|(index, message)| WireMessage {
+                role: role_name(message.role),
+                content: if cached(system_len + index) {
+                    WireContent::Blocks(vec![TextBlock::new(&message.content, true)])
+                } else {
+                    WireContent::Text(&message.content)
+                },
+            })
+            .collect(),
+        stop_sequences: &request.stop_sequences,
+        temperature: request.temperature,
+        top_p: request.top_p,
+    };
+
+    serde_json::to_value(body).map_err(|e| LlmError::InvalidRequest {
+        message: format!("failed to serialise Anthropic request: {e}"),
+    })
+}
+
+// ─── Response ───────────────────────────────────────────────────────────────
+
+/// One block of the response `content` array.
+#[derive(Debug, Deserialize)]
+struct ResponseBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: String,
+}
+
+/// The `usage` object of a Messages API response.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct ResponseUsage {
+    input_tokens: u64,
+    output_tokens: u64,
+    #[serde(default)]
+    cache_creation_input_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+}
+
+impl ResponseUsage {
+    /// Converts to [`TokenUsage`], taking the output count from
+    /// `output_tokens` (which streaming reports separately).
+    fn token_usage(&self, output_tokens: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: TokenCount::new(self.input_tokens),
+            output_tokens: TokenCount::new(output_tokens),
+            cache_creation_input_tokens: TokenCount::new(self.cache_creation_input_tokens),
+            cache_read_input_tokens: TokenCount::new(self.cache_read_input_tokens),
+        }
+    }
+}
+
+/// Body of a successful `POST /v1/messages` response.
+#[derive(Debug, Deserialize)]
+struct MessagesResponse {
+    model: String,
+    content: Vec<ResponseBlock>,
+    usage: ResponseUsage,
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+/// Map a Messages API `stop_reason` to a [`FinishReason`].
+///
+/// | `stop_reason` | [`FinishReason`] |
+/// |---------------|------------------|
+/// | `end_turn` | `EndTurn` |
+/// | `max_tokens` | `MaxTokens` |
+/// | `stop_sequence` | `StopSequence` |
+/// | `refusal` | `Refusal` |
+/// | anything else (e.g. `tool_use`, `pause_turn`) | `Other` |
+pub fn finish_reason(stop_reason: &str) -> FinishReason {
+    match stop_reason {
+        "end_turn" => FinishReason::EndTurn,
+        "max_tokens" => FinishReason::MaxTokens,
+        "stop_sequence" => FinishReason::StopSequence,
+        "refusal" => FinishReason::Refusal,
+        other => FinishReason::Other(other.to_string()),
+    }
+}
+
+/// Parse a Messages API response body into a [`CompletionResponse`].
+///
+/// Text blocks are concatenated in order; other block types are ignored. A
+/// missing `stop_reason` is reported as [`FinishReason::Other`] with an empty
+/// string.
+///
+/// # Errors
+///
+/// - [`LlmError::ResponseParse`] — the body is not a Messages API response.
+pub fn parse_response(body: &[u8]) -> Result<CompletionResponse, LlmError> {
+    let response: MessagesResponse =
+        serde_json::from_slice(body).map_err(|e| LlmError::ResponseParse {
+            message: format!("failed to parse Anthropic response: {e}"),
+        })?;
+
+    Ok(CompletionResponse {
+        content: response
+            .content
+            .iter()
+            .filter(|block| block.kind == "text")
+            .map(|block| block.text.as_str())
+            .collect(),
+        model: response.model,
+        usage: response.usage.token_usage(response.usage.output_tokens),
+        finish_reason: finish_reason(response.stop_reason.as_deref().unwrap_or_default()),
+        provider_request_id: None,
+    })
+}
+
+// ─── Streaming ──────────────────────────────────────────────────────────────
+
+/// One server-sent event of a streamed Messages API response.
+///
+/// `content_block_start`, `content_block_stop`, `ping`, and event types added
+/// later are ignored.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    MessageStart {
+        message: StreamMessage,
+    },
+    ContentBlockDelta {
+        delta: BlockDelta,
+    },
+    MessageDelta {
+        delta: MessageDeltaBody,
+        #[serde(default)]
+        usage: Option<DeltaUsage>,
+    },
+    MessageStop,
+    Error {
+        error: StreamError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamMessage {
+    model: String,
+    usage: ResponseUsage,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockDelta {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageDeltaBody {
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+/// Cumulative output token count reported by `message_delta`.
+#[derive(Debug, Deserialize)]
+struct DeltaUsage {
+    output_tokens: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamError {
+    #[serde(rename = "type")]
+    kind: String,
+    message: String,
+}
+
+/// A streamed Messages API response, read event by event.
+///
+/// Text deltas are accumulated as they are yielded, so a stream that breaks
+/// part-way returns [`LlmError::Interrupted`] with the text and usage received
+/// so far.
+pub struct AnthropicStream {
+    body: Box<dyn ResponseBody>,
+    decoder: SseDecoder,
+    partial: PartialCompletion,
+    model: String,
+    provider_request_id: Option<String>,
+    prompt_usage: ResponseUsage,
+    finish_reason: Option<FinishReason>,
+    done: bool,
+}
+
+impl AnthropicStream {
+    /// Wraps a successful streamed response body.
+    pub fn new(body: Box<dyn ResponseBody>, provider_request_id: Option<String>) -> Self {
+        Self {
+            body,
+            decoder: SseDecoder::new(),
+            partial: PartialCompletion::new(),
+            model: String::new(),
+            provider_request_id,
+            prompt_usage: ResponseUsage::default(),
+            finish_reason: None,
+            done: false,
+        }
+    }
+
+    /// The model serving the request; empty until `message_start` is read.
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Reads the rest of the stream into a [`CompletionResponse`].
+    ///
+    /// # Errors
+    ///
+    /// As for [`CompletionStream::next_chunk`].
+    pub async fn into_response(mut self) -> Result<CompletionResponse, LlmError> {
+        let mut content = String::new();
+        loop {
+            match self.next_chunk().await? {
+                Some(CompletionChunk::Text(text)) => content.push_str(&text),
+                Some(CompletionChunk::Finished {
+                    finish_reason,
+                    usage,
+                }) => {
+                    return Ok(CompletionResponse {
+                        content,
+                        model: self.model,
+                        usage,
+                        finish_reason,
+                        provider_request_id: self.provider_request_id,
+                    })
+                }
+                None => {
+                    return Err(LlmError::ResponseParse {
+                        message: "Anthropic stream ended without message_stop".to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    /// Ends the stream with [`LlmError::Interrupted`].
+    fn interrupt(&mut self, message: String) -> LlmError {
+        self.done = true;
+        std::mem::take(&mut self.partial).interrupted(message)
+    }
+
+    /// Applies one event, returning the chunk it produces, if any.
+    fn apply(&mut self, event: &SseEvent) -> Result<Option<CompletionChunk>, LlmError> {
+        let parsed: StreamEvent =
+            serde_json::from_str(&event.data).map_err(|e| LlmError::ResponseParse {
+                message: format!("Anthropic stream event '{}': {e}", event.event),
+            })?;
+        match parsed {
+            StreamEvent::MessageStart { message } => {
+                self.model = message.model;
+                self.prompt_usage = message.usage;
+                self.set_output_tokens(message.usage.output_tokens);
+                Ok(None)
+            }
+            StreamEvent::ContentBlockDelta { delta } if delta.kind == "text_delta" => {
+                self.partial.push_text(&delta.text);
+                Ok(Some(CompletionChunk::Text(delta.text)))
+            }
+            StreamEvent::ContentBlockDelta { .. } | StreamEvent::Other => Ok(None),
+            StreamEvent::MessageDelta { delta, usage } => {
+                if let Some(stop_reason) = delta.stop_reason {
+                    self.finish_reason = Some(finish_reason(&stop_reason));
+                }
+                if let Some(usage) = usage {
+                    self.set_output_tokens(usage.output_tokens);
+                }
+                Ok(None)
+            }
+            StreamEvent::MessageStop => {
+                self.done = true;
+                Ok(Some(CompletionChunk::Finished {
+                    finish_reason: self
+                        .finish_reason
+                        .take()
+                        .unwrap_or_else(|| finish_reason("")),
+                    usage: self.partial.usage(),
+                }))
+            }
+            StreamEvent::Error { error } => {
+                let message = format!("{}: {}", error.kind, error.message);
+                Err(match error.kind.as_str() {
+                    "rate_limit_error" => {
+                        self.done = true;
+                        LlmError::RateLimited { retry_after: None }
+                    }
+                    "overloaded_error" | "api_error" => self.interrupt(message),
+                    _ => {
+                        self.done = true;
+                        LlmError::InvalidRequest { message }
+                    }
+                })
+            }
+        }
+    }
+
+    fn set_output_tokens(&mut self, output_tokens: u64) {
+        self.partial
+            .set_usage(self.prompt_usage.token_usage(output_tokens));
+    }
+}
+
+#[async_trait]
+impl CompletionStream for AnthropicStream {
+    async fn next_chunk(&mut self) -> Result<Option<CompletionChunk>, LlmError> {
+        loop {
+            while !self.done {
+                let Some(event) = self.decoder.next_event() else {
+                    break;
+                };
+                if let Some(chunk) = self.apply(&event)? {
+                    return Ok(Some(chunk));
+                }
+            }
+            if self.done {
+                return Ok(None);
+            }
+            match self.body.next_bytes().await {
+                Ok(Some(bytes)) => self.decoder.push(&bytes),
+                Ok(None) => {
+                    return Err(
+                        self.interrupt("Anthropic stream ended before message_stop".to_string())
+                    )
+                }
+                Err(e) => return Err(self.interrupt(e.to_string())),
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for AnthropicStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnthropicStream")
+            .field("model", &self.model)
+            .field("provider_request_id", &self.provider_request_id)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+// ─── Message batches ────────────────────────────────────────────────────────
+
+/// Result of [`AnthropicProvider::cancel_batch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchCancellation {
+    /// The batch was in progress and is now being cancelled. Requests already
+    /// processing finish; the rest are not started.
+    Canceling,
+    /// The batch had already finished; nothing was cancelled.
+    AlreadyEnded,
+}
+
+/// `processing_status` of a Message Batch response.
+#[derive(Debug, Deserialize)]
+struct BatchStatus {
+    processing_status: String,
+}
+
+/// Maps the response of `POST /v1/messages/batches/{id}/cancel`.
+///
+/// A `409 Conflict` (the batch can no longer be cancelled) and a batch
+/// reported as `ended` are both [`BatchCancellation::AlreadyEnded`].
+///
+/// # Errors
+///
+/// - The [`status_error`] of any other non-success response.
+/// - [`LlmError::ResponseParse`] — the body has no `processing_status`.
+pub fn parse_cancel_response(response: &HttpResponse) -> Result<BatchCancellation, LlmError> {
+    if response.status == 409 {
+        return Ok(BatchCancellation::AlreadyEnded);
+    }
+    if let Some(error) = status_error(response) {
+        return Err(error);
+    }
+    let status: BatchStatus =
+        serde_json::from_slice(&response.body).map_err(|e| LlmError::ResponseParse {
+            message: format!("batch cancel response: {e}"),
+        })?;
+    Ok(match status.processing_status.as_str() {
+        "ended" => BatchCancellation::AlreadyEnded,
+        _ => BatchCancellation::Canceling,
+    })
+}
+
+// ─── Provider ───────────────────────────────────────────────────────────────
+
+/// [`LlmProvider`] for the Anthropic Messages API.
+pub struct AnthropicProvider {
+    transport: Arc<dyn LlmTransport>,
+    api_key: String,
+    base_url: String,
+    /// Header carrying the client-generated request ID; `None` never sends it.
+    request_id_header: Option<String>,
+    /// Retries of [`LlmProvider::complete`].
+    backoff: BackoffConfig,
+    /// The run's shared retry budget; `None` gives each call its own
+    /// [`BackoffConfig::budget`].
+    retry_budget: Option<Arc<RunRetryBudget>>,
+    /// Receives one sample per [`LlmProvider::complete`] call.
+    retry_metrics: Arc<dyn RetryMetrics>,
+    /// Applied to requests and responses logged at DEBUG.
+    redactor: Redactor,
+}
+
+impl AnthropicProvider {
+    /// Creates a provider sending requests to [`DEFAULT_BASE_URL`] through
+    /// `transport`.
+    pub fn new(transport: Arc<dyn LlmTransport>, api_key: impl Into<String>) -> Self {
+        Self {
+            transport,
+            api_key: api_key.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            request_id_header: Some(DEFAULT_REQUEST_ID_HEADER.to_string()),
+            backoff: BackoffConfig::default(),
+            retry_budget: None,
+            retry_metrics: Arc::new(NoopRetryMetrics),
+            redactor: Redactor::default(),
+        }
+    }
+
+    /// Overrides the API origin (e.g. for a proxy or gateway).
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the header used to send [`CompletionRequest::request_id`], or
+    /// `None` to never send it (e.g. for a proxy that rejects unknown
+    /// headers). Defaults to [`DEFAULT_REQUEST_ID_HEADER`].
+    #[must_use]
+    pub fn with_request_id_header(mut self, header: Option<&str>) -> Self {
+        self.request_id_header = header.map(str::to_string);
+        self
+    }
+
+    /// Sets how [`LlmProvider::complete`] retries failed calls. Defaults to
+    /// [`BackoffConfig::default`]; [`BackoffConfig::disabled`] leaves
+    /// retrying to the caller.
+    #[must_use]
+    pub fn with_backoff(mut self, backoff: BackoffConfig) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Charges the retries of [`LlmProvider::complete`] to `budget`, shared
+    /// with the rest of the run.
+    #[must_use]
+    pub fn with_retry_budget(mut self, budget: Arc<RunRetryBudget>) -> Self {
+        self.retry_budget = Some(budget);
+        self
+    }
+
+    /// Records the attempts of each [`LlmProvider::complete`] call, labelled
+    /// [`COMPLETE_RETRY_OPERATION`], in `metrics`.
+    #[must_use]
+    pub fn with_retry_metrics(mut self, metrics: Arc<dyn RetryMetrics>) -> Self {
+        self.retry_metrics = metrics;
+        self
+    }
+
+    /// Sets the patterns redacted from requests and responses logged at
+    /// DEBUG, e.g. API key or token formats that may appear in repository
+    /// content. The `x-api-key` header is redacted regardless.
+    #[must_use]
+    pub fn with_redaction(mut self, patterns: Vec<Regex>) -> Self {
+        self.redactor = Redactor::new(patterns);
+        self
+    }
+}
+
+impl AnthropicProvider {
+    /// The `POST /v1/messages` request for `request`, with `stream` set when
+    /// the response is to be streamed.
+    fn messages_request(
+        &self,
+        request: &CompletionRequest,
+        stream: bool,
+    ) -> Result<HttpRequest, LlmError> {
+        let mut headers = self.headers();
+        if let (Some(header), Some(request_id)) = (&self.request_id_header, &request.request_id) {
+            headers.push((header.clone(), request_id.clone()));
+        }
+        let mut body = request_body(request)?;
+        if stream {
+            body["stream"] = JsonValue::Bool(true);
+        }
+        Ok(HttpRequest {
+            url: format!("{}/v1/messages", self.base_url),
+            headers,
+            body,
+        })
+    }
+
+    /// Headers sent with every request.
+    fn headers(&self) -> Vec<(String, String)> {
+        vec![
+            ("x-api-key".to_string(), self.api_key.clone()),
+            ("anthropic-version".to_string(), API_VERSION.to_string()),
+            ("content-type".to_string(), "application/json".to_string()),
+        ]
+    }
+
+    /// Cancel Message Batch `batch_id`.
+    ///
+    /// Cancelling a batch that has already ended is not an error; it returns
+    /// [`BatchCancellation::AlreadyEnded`].
+    ///
+    /// # Errors
+    ///
+    /// See [`parse_cancel_response`]; an unknown batch is
+    /// [`LlmError::InvalidRequest`].
+    #[instrument(skip(self))]
+    pub async fn cancel_batch(&self, batch_id: &str) -> Result<BatchCancellation, LlmError> {
+        let http_request = HttpRequest {
+            url: format!("{}/v1/messages/batches/{batch_id}/cancel", self.base_url),
+            headers: self.headers(),
+            body: JsonValue::Object(serde_json::Map::new()),
+        };
+        let response = self.transport.post_json(http_request).await?;
+        let cancellation = parse_cancel_response(&response)?;
+        tracing::info!(?cancellation, "batch cancellation requested");
+        Ok(cancellation)
+    }
+}
+
+impl std::fmt::Debug for AnthropicProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnthropicProvider")
+            .field("base_url", &self.base_url)
+            .field("request_id_header", &self.request_id_header)
+            .field("backoff", &self.backoff)
+            .field("redactor", &self.redactor)
+            .field("api_key", &"[REDACTED]")
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl LlmProvider for AnthropicProvider {
+    #[instrument(
+        skip(self, request),
+        fields(model = %request.model, request_id = ?request.request_id)
+    )]
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        let http_request = self.messages_request(&request, false)?;
+        self.redactor.log_request(PROVIDER_NAME, &http_request);
+
+        let call_budget;
+        let budget = match &self.retry_budget {
+            Some(budget) => budget.as_ref(),
+            None => {
+                call_budget = RunRetryBudget::new(self.backoff.budget());
+                &call_budget
+            }
+        };
+        let response = with_backoff(
+            COMPLETE_RETRY_OPERATION,
+            &self.backoff,
+            budget,
+            self.retry_metrics.as_ref(),
+            || async {
+                let response = self.transport.post_json(http_request.clone()).await?;
+                self.redactor.log_response(PROVIDER_NAME, &response);
+                if let Some(error) = status_error(&response) {
+                    tracing::debug!(
+                        provider_request_id = ?response.header(RESPONSE_REQUEST_ID_HEADER),
+                        "Anthropic request failed"
+                    );
+                    return Err(error);
+                }
+                Ok(response)
+            },
+        )
+        .await?;
+        let mut completion = parse_response(&response.body)?;
+        completion.provider_request_id = response
+            .header(RESPONSE_REQUEST_ID_HEADER)
+            .map(str::to_string);
+        Ok(completion)
+    }
+
+    #[instrument(
+        skip(self, request),
+        fields(model = %request.model, request_id = ?request.request_id)
+    )]
+    async fn complete_streaming(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<Box<dyn CompletionStream>, LlmError> {
+        let http_request = self.messages_request(&request, true)?;
+        self.redactor.log_request(PROVIDER_NAME, &http_request);
+
+        let response = self.transport.post_json_streaming(http_request).await?;
+        let provider_request_id = response
+            .header(RESPONSE_REQUEST_ID_HEADER)
+            .map(str::to_string);
+        if !(200..300).contains(&response.status) {
+            let response = response.into_buffered().await?;
+            self.redactor.log_response(PROVIDER_NAME, &response);
+            tracing::debug!(?provider_request_id, "Anthropic stream request failed");
+            return Err(
+                status_error(&response).unwrap_or_else(|| LlmError::Transient {
+                    message: format!("HTTP {}", response.status),
+                }),
+            );
+        }
+        Ok(Box::new(AnthropicStream::new(
+            response.body,
+            provider_request_id,
+        )))
+    }
+
+    /// Counts the prompt with the bundled [`BpeTokenizer`].
+    fn estimate_prompt_tokens(
+        &self,
+        request: &CompletionRequest,
+    ) -> Result<TokenEstimate, LlmError> {
+        Ok(TokenEstimate::exact(
+            BpeTokenizer::bundled().count_request(request),
+        ))
+    }
+}
+
+#[cfg(test)]
+#[path = "anthropic_tests.rs"]
+mod tests;
@@ -0,0 +1,846 @@
+use std::collections::VecDeque;
+
+use pipeline::retry::{InMemoryRetryMetrics, RetryBudget};
+use pipeline::{Message, RetryPolicy, SystemLayer, SystemSegment, TokenCount};
+use serde_json::json;
+
+use crate::transport::ScriptedTransport;
+
+use super::*;
+
+fn request() -> CompletionRequest {
+    CompletionRequest::new(
+        "claude-test",
+        vec![Message::user("hello")],
+        TokenCount::new(100),
+    )
+}
+
+#[test]
+fn test_request_body_system_segments_emitted_in_layer_order() {
+    let mut request = request();
+    request.system = vec![
+        SystemSegment::new(SystemLayer::Task, "task"),
+        SystemSegment::new(SystemLayer::Constitutional, "rules"),
+    ];
+
+    let body = request_body(&request).unwrap();
+
+    assert_eq!(
+        body["system"],
+        json!([
+            { "type": "text", "text": "rules" },
+            { "type": "text", "text": "task" },
+        ])
+    );
+}
+
+#[test]
+fn test_request_body_no_system_or_stop_sequences_omits_fields() {
+    let body = request_body(&request()).unwrap();
+
+    assert!(body.get("system").is_none());
+    assert!(body.get("stop_sequences").is_none());
+    assert_eq!(
+        body["messages"],
+        json!([{ "role": "user", "content": "hello" }])
+    );
+    assert_eq!(body["max_tokens"], json!(100));
+}
+
+#[test]
+fn test_request_body_stop_sequences_passed_through() {
+    let mut request = request();
+    request.stop_sequences = vec!["</answer>".to_string(), "END".to_string()];
+
+    let body = request_body(&request).unwrap();
+
+    assert_eq!(body["stop_sequences"], json!(["</answer>", "END"]));
+}
+
+#[test]
+fn test_request_body_no_messages_returns_invalid_request() {
+    let mut request = request();
+    request.messages.clear();
+
+    assert!(matches!(
+        request_body(&request),
+        Err(LlmError::InvalidRequest { .. })
+    ));
+}
+
+// ─── Cache breakpoints ──────────────────────────────────────────────────────
+
+fn cached_request(breakpoints: Vec<usize>) -> CompletionRequest {
+    let mut request = request().with_cache_breakpoints(breakpoints);
+    request.system = vec![
+        SystemSegment::new(SystemLayer::Constitutional, "rules"),
+        SystemSegment::new(SystemLayer::Task, "task"),
+    ];
+    request.messages.push(Message::user("follow-up"));
+    request
+}
+
+#[test]
+fn test_request_body_system_breakpoint_marks_that_block_only() {
+    let body = request_body(&cached_request(vec![0])).unwrap();
+
+    assert_eq!(
+        body["system"],
+        json!([
+            { "type": "text", "text": "rules", "cache_control": { "type": "ephemeral" } },
+            { "type": "text", "text": "task" },
+        ])
+    );
+    assert_eq!(body["messages"][0]["content"], json!("hello"));
+}
+
+#[test]
+fn test_request_body_message_breakpoint_sends_block_array_with_marker() {
+    let body = request_body(&cached_request(vec![2])).unwrap();
+
+    assert_eq!(
+        body["messages"],
+        json!([
+            {
+                "role": "user",
+                "content": [
+                    { "type": "text", "text": "hello", "cache_control": { "type": "ephemeral" } }
+                ]
+            },
+            { "role": "user", "content": "follow-up" },
+        ])
+    );
+    assert!(body["system"][0].get("cache_control").is_none());
+}
+
+#[test]
+fn test_request_body_breakpoint_out_of_range_returns_invalid_request() {
+    assert!(matches!(
+        request_body(&cached_request(vec![4])),
+        Err(LlmError::InvalidRequest { message }) if message.contains("out of range")
+    ));
+}
+
+#[test]
+fn test_request_body_too_many_breakpoints_returns_invalid_request() {
+    let mut request = cached_request(vec![0, 1, 2, 3, 3]);
+    request.messages.push(Message::user("third"));
+
+    assert!(matches!(
+        request_body(&request),
+        Err(LlmError::InvalidRequest { message }) if message.contains("at most")
+    ));
+}
+
+#[test]
+fn test_parse_response_cache_usage_fields_reported() {
+    let body = json!({
+        "model": "claude-test-20250101",
+        "content": [{ "type": "text", "text": "ok" }],
+        "stop_reason": "end_turn",
+        "usage": {
+            "input_tokens": 12,
+            "output_tokens": 3,
+            "cache_creation_input_tokens": 2048,
+            "cache_read_input_tokens": 4096
+        }
+    });
+
+    let response = parse_response(body.to_string().as_bytes()).unwrap();
+
+    assert_eq!(
+        response.usage,
+        TokenUsage {
+            cache_creation_input_tokens: TokenCount::new(2048),
+            cache_read_input_tokens: TokenCount::new(4096),
+            ..TokenUsage::new(TokenCount::new(12), TokenCount::new(3))
+        }
+    );
+}
+
+#[test]
+fn test_parse_response_without_cache_usage_reports_zero() {
+    let body = json!({
+        "model": "claude-test-20250101",
+        "content": [{ "type": "text", "text": "ok" }],
+        "stop_reason": "end_turn",
+        "usage": { "input_tokens": 12, "output_tokens": 3 }
+    });
+
+    let response = parse_response(body.to_string().as_bytes()).unwrap();
+
+    assert_eq!(
+        response.usage,
+        TokenUsage::new(TokenCount::new(12), TokenCount::new(3))
+    );
+}
+
+// ─── Provider over a scripted transport ─────────────────────────────────────
+
+#[test]
+fn test_finish_reason_each_stop_reason_maps_to_variant() {
+    assert_eq!(finish_reason("end_turn"), FinishReason::EndTurn);
+    assert_eq!(finish_reason("max_tokens"), FinishReason::MaxTokens);
+    assert_eq!(finish_reason("stop_sequence"), FinishReason::StopSequence);
+    assert_eq!(finish_reason("refusal"), FinishReason::Refusal);
+    assert_eq!(
+        finish_reason("tool_use"),
+        FinishReason::Other("tool_use".to_string())
+    );
+}
+
+#[test]
+fn test_parse_response_max_tokens_stop_reason_is_truncated() {
+    let body = json!({
+        "model": "claude-test-20250101",
+        "content": [{ "type": "text", "text": "Partial" }],
+        "stop_reason": "max_tokens",
+        "usage": { "input_tokens": 12, "output_tokens": 256 }
+    });
+
+    let response = parse_response(body.to_string().as_bytes()).unwrap();
+
+    assert_eq!(response.finish_reason, FinishReason::MaxTokens);
+    assert!(response.finish_reason.is_truncated());
+}
+
+#[test]
+fn test_parse_response_missing_stop_reason_returns_empty_other() {
+    let body = json!({
+        "model": "claude-test-20250101",
+        "content": [],
+        "usage": { "input_tokens": 1, "output_tokens": 0 }
+    });
+
+    let response = parse_response(body.to_string().as_bytes()).unwrap();
+
+    assert_eq!(response.finish_reason, FinishReason::Other(String::new()));
+}
+
+fn provider(transport: &Arc<ScriptedTransport>) -> AnthropicProvider {
+    AnthropicProvider::new(Arc::clone(transport) as _, "sk-test")
+        .with_base_url("https://llm.example/")
+        .with_backoff(BackoffConfig::disabled())
+}
+
+fn success_body() -> JsonValue {
+    json!({
+        "model": "claude-test-20250101",
+        "content": [
+            { "type": "text", "text": "Hello" },
+            { "type": "tool_use", "id": "t1", "name": "x", "input": {} },
+            { "type": "text", "text": ", world" }
+        ],
+        "stop_reason": "end_turn",
+        "usage": { "input_tokens": 12, "output_tokens": 3 }
+    })
+}
+
+#[tokio::test]
+async fn test_complete_success_response_parsed() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push(Ok(HttpResponse {
+        status: 200,
+        headers: vec![("request-id".to_string(), "req_abc".to_string())],
+        body: success_body().to_string().into_bytes(),
+    }));
+
+    let response = provider(&transport).complete(request()).await.unwrap();
+
+    assert_eq!(response.content, "Hello, world");
+    assert_eq!(response.model, "claude-test-20250101");
+    assert_eq!(response.finish_reason, FinishReason::EndTurn);
+    assert_eq!(response.usage.input_tokens, TokenCount::new(12));
+    assert_eq!(response.usage.output_tokens, TokenCount::new(3));
+    assert_eq!(response.provider_request_id.as_deref(), Some("req_abc"));
+}
+
+#[tokio::test]
+async fn test_complete_sends_messages_request_with_headers() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, &success_body());
+
+    provider(&transport)
+        .complete(request().with_request_id("run-1/plan/1"))
+        .await
+        .unwrap();
+
+    let sent = &transport.requests()[0];
+    assert_eq!(sent.url, "https://llm.example/v1/messages");
+    let header = |name: &str| {
+        sent.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+    assert_eq!(header("x-api-key"), Some("sk-test"));
+    assert_eq!(header("anthropic-version"), Some(API_VERSION));
+    assert_eq!(header(DEFAULT_REQUEST_ID_HEADER), Some("run-1/plan/1"));
+    assert_eq!(sent.body["model"], "claude-test");
+}
+
+#[tokio::test]
+async fn test_complete_request_id_header_disabled_not_sent() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, &success_body());
+
+    provider(&transport)
+        .with_request_id_header(None)
+        .complete(request().with_request_id("run-1/plan/1"))
+        .await
+        .unwrap();
+
+    assert!(transport.requests()[0]
+        .headers
+        .iter()
+        .all(|(_, value)| value != "run-1/plan/1"));
+}
+
+#[tokio::test]
+async fn test_complete_without_request_id_sends_no_request_id_header() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, &success_body());
+
+    provider(&transport).complete(request()).await.unwrap();
+
+    assert!(transport.requests()[0]
+        .headers
+        .iter()
+        .all(|(key, _)| key != DEFAULT_REQUEST_ID_HEADER));
+}
+
+#[tokio::test]
+async fn test_complete_custom_request_id_header_sends_id_under_that_name() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, &success_body());
+
+    provider(&transport)
+        .with_request_id_header(Some("x-trace-id"))
+        .complete(request().with_request_id("run-1/plan/1"))
+        .await
+        .unwrap();
+
+    let headers = &transport.requests()[0].headers;
+    assert!(headers.contains(&("x-trace-id".to_string(), "run-1/plan/1".to_string())));
+    assert!(headers
+        .iter()
+        .all(|(key, _)| key != DEFAULT_REQUEST_ID_HEADER));
+}
+
+#[tokio::test]
+async fn test_complete_response_without_request_id_header_records_none() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, &success_body());
+
+    let response = provider(&transport).complete(request()).await.unwrap();
+
+    assert_eq!(response.provider_request_id, None);
+}
+
+#[tokio::test]
+async fn test_complete_unauthorized_returns_authentication() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(401, &json!({ "type": "error" }));
+
+    let error = provider(&transport).complete(request()).await.unwrap_err();
+
+    assert!(matches!(error, LlmError::Authentication { .. }));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+// ─── Retries ────────────────────────────────────────────────────────────────
+
+fn retrying_provider(transport: &Arc<ScriptedTransport>, max_retries: u32) -> AnthropicProvider {
+    provider(transport).with_backoff(BackoffConfig {
+        max_retries,
+        base_delay_ms: 1,
+        max_delay_ms: 2,
+        deadline_secs: 60,
+    })
+}
+
+fn push_overloaded(transport: &ScriptedTransport) {
+    transport.push_json(
+        529,
+        &json!({ "type": "error", "error": { "type": "overloaded_error" } }),
+    );
+}
+
+#[tokio::test]
+async fn test_complete_two_overloaded_then_success_returns_response() {
+    let transport = Arc::new(ScriptedTransport::new());
+    push_overloaded(&transport);
+    push_overloaded(&transport);
+    transport.push_json(200, &success_body());
+    let metrics = Arc::new(InMemoryRetryMetrics::new());
+
+    let response = retrying_provider(&transport, 3)
+        .with_retry_metrics(Arc::clone(&metrics) as _)
+        .complete(request())
+        .await
+        .unwrap();
+
+    assert_eq!(response.content, "Hello, world");
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests.iter().all(|r| r.body == requests[0].body));
+    let samples = metrics.samples();
+    assert_eq!(samples[0].operation, COMPLETE_RETRY_OPERATION);
+    assert_eq!(samples[0].attempts, 3);
+}
+
+#[tokio::test]
+async fn test_complete_overloaded_past_max_retries_returns_exhausted_retries() {
+    let transport = Arc::new(ScriptedTransport::new());
+    for _ in 0..3 {
+        push_overloaded(&transport);
+    }
+
+    let error = retrying_provider(&transport, 2)
+        .complete(request())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        LlmError::ExhaustedRetries { attempts: 3, .. }
+    ));
+    assert_eq!(error.retry_policy(), RetryPolicy::NonRetryable);
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_complete_bad_request_with_retries_fails_fast() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(400, &json!({ "type": "error" }));
+
+    let error = retrying_provider(&transport, 3)
+        .complete(request())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, LlmError::InvalidRequest { .. }));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_complete_shared_run_budget_charged_for_retries() {
+    let transport = Arc::new(ScriptedTransport::new());
+    push_overloaded(&transport);
+    transport.push_json(200, &success_body());
+    let budget = Arc::new(RunRetryBudget::new(RetryBudget::default()));
+
+    retrying_provider(&transport, 3)
+        .with_retry_budget(Arc::clone(&budget))
+        .complete(request())
+        .await
+        .unwrap();
+
+    assert_eq!(budget.usage().retries, 1);
+}
+
+#[tokio::test]
+async fn test_complete_shared_run_budget_spent_returns_first_error_without_retry() {
+    let transport = Arc::new(ScriptedTransport::new());
+    push_overloaded(&transport);
+    let budget = Arc::new(RunRetryBudget::new(RetryBudget {
+        max_retries: 0,
+        ..RetryBudget::default()
+    }));
+
+    let error = retrying_provider(&transport, 3)
+        .with_retry_budget(budget)
+        .complete(request())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, LlmError::Transient { .. }));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_complete_malformed_body_returns_response_parse() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, &json!({ "unexpected": true }));
+
+    let error = provider(&transport).complete(request()).await.unwrap_err();
+
+    assert!(matches!(error, LlmError::ResponseParse { .. }));
+}
+
+#[tokio::test]
+async fn test_complete_streaming_error_status_returns_mapped_error() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(400, &json!({ "type": "error" }));
+
+    let Err(error) = provider(&transport).complete_streaming(request()).await else {
+        panic!("expected the stream request to fail");
+    };
+
+    assert!(matches!(error, LlmError::InvalidRequest { .. }));
+    assert_eq!(transport.requests()[0].body["stream"], true);
+}
+
+// ─── Streaming ──────────────────────────────────────────────────────────────
+
+/// A body replaying queued reads, then reporting its end.
+struct ChunkedBody(VecDeque<Result<Vec<u8>, LlmError>>);
+
+#[async_trait]
+impl ResponseBody for ChunkedBody {
+    async fn next_bytes(&mut self) -> Result<Option<Vec<u8>>, LlmError> {
+        self.0.pop_front().transpose()
+    }
+}
+
+fn sse(events: &[(&str, JsonValue)]) -> String {
+    events
+        .iter()
+        .map(|(name, data)| format!("event: {name}\ndata: {data}\n\n"))
+        .collect()
+}
+
+/// A recorded stream for "Hello, world" with 12 prompt and 5 output tokens.
+fn stream_events() -> String {
+    sse(&[
+        (
+            "message_start",
+            json!({
+                "type": "message_start",
+                "message": {
+                    "model": "claude-test-20250101",
+                    "usage": { "input_tokens": 12, "output_tokens": 1 }
+                }
+            }),
+        ),
+        (
+            "content_block_start",
+            json!({ "type": "content_block_start", "index": 0 }),
+        ),
+        ("ping", json!({ "type": "ping" })),
+        (
+            "content_block_delta",
+            json!({
+                "type": "content_block_delta",
+                "index": 0,
+                "delta": { "type": "text_delta", "text": "Hello" }
+            }),
+        ),
+        (
+            "content_block_delta",
+            json!({
+                "type": "content_block_delta",
+                "index": 0,
+                "delta": { "type": "text_delta", "text": ", world" }
+            }),
+        ),
+        (
+            "message_delta",
+            json!({
+                "type": "message_delta",
+                "delta": { "stop_reason": "end_turn" },
+                "usage": { "output_tokens": 5 }
+            }),
+        ),
+        ("message_stop", json!({ "type": "message_stop" })),
+    ])
+}
+
+/// Splits `body` into reads of `size` bytes, so frames and JSON objects
+/// straddle read boundaries.
+fn reads(body: &str, size: usize) -> VecDeque<Result<Vec<u8>, LlmError>> {
+    body.as_bytes()
+        .chunks(size)
+        .map(|chunk| Ok(chunk.to_vec()))
+        .collect()
+}
+
+fn stream(reads: VecDeque<Result<Vec<u8>, LlmError>>) -> AnthropicStream {
+    AnthropicStream::new(Box::new(ChunkedBody(reads)), Some("req_1".to_string()))
+}
+
+fn usage(input: u64, output: u64) -> TokenUsage {
+    TokenUsage {
+        input_tokens: TokenCount::new(input),
+        output_tokens: TokenCount::new(output),
+        ..TokenUsage::zero()
+    }
+}
+
+#[tokio::test]
+async fn test_into_response_split_frames_reconstructs_text_and_usage() {
+    let response = stream(reads(&stream_events(), 7))
+        .into_response()
+        .await
+        .unwrap();
+
+    assert_eq!(response.content, "Hello, world");
+    assert_eq!(response.model, "claude-test-20250101");
+    assert_eq!(response.usage, usage(12, 5));
+    assert_eq!(response.finish_reason, FinishReason::EndTurn);
+    assert_eq!(response.provider_request_id.as_deref(), Some("req_1"));
+}
+
+#[tokio::test]
+async fn test_next_chunk_recorded_stream_yields_text_then_finished() {
+    let mut stream = stream(reads(&stream_events(), 1024));
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next_chunk().await.unwrap() {
+        chunks.push(chunk);
+    }
+
+    assert_eq!(
+        chunks,
+        vec![
+            CompletionChunk::Text("Hello".to_string()),
+            CompletionChunk::Text(", world".to_string()),
+            CompletionChunk::Finished {
+                finish_reason: FinishReason::EndTurn,
+                usage: usage(12, 5),
+            },
+        ]
+    );
+    assert_eq!(stream.next_chunk().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_next_chunk_body_ends_before_message_stop_returns_interrupted_with_partial() {
+    let events = stream_events();
+    let cut = events.find("event: message_delta").unwrap();
+    let mut stream = stream(reads(&events[..cut], 16));
+
+    let mut text = String::new();
+    let error = loop {
+        match stream.next_chunk().await {
+            Ok(Some(CompletionChunk::Text(delta))) => text.push_str(&delta),
+            Ok(other) => panic!("unexpected chunk {other:?}"),
+            Err(error) => break error,
+        }
+    };
+
+    assert_eq!(text, "Hello, world");
+    assert!(matches!(
+        error,
+        LlmError::Interrupted { partial, usage: reported, .. }
+            if partial == "Hello, world" && reported == usage(12, 1)
+    ));
+}
+
+#[tokio::test]
+async fn test_next_chunk_connection_drop_returns_interrupted() {
+    let events = stream_events();
+    let cut = events.find("event: content_block_delta").unwrap();
+    let mut reads = reads(&events[..cut], 64);
+    reads.push_back(Err(LlmError::Transient {
+        message: "connection reset".to_string(),
+    }));
+
+    let result = stream(reads).into_response().await;
+
+    assert!(matches!(
+        result,
+        Err(LlmError::Interrupted { message, partial, .. })
+            if message.contains("connection reset") && partial.is_empty()
+    ));
+}
+
+#[tokio::test]
+async fn test_next_chunk_overloaded_error_event_returns_interrupted() {
+    let body = sse(&[(
+        "error",
+        json!({
+            "type": "error",
+            "error": { "type": "overloaded_error", "message": "Overloaded" }
+        }),
+    )]);
+
+    let result = stream(reads(&body, 1024)).into_response().await;
+
+    assert!(matches!(result, Err(LlmError::Interrupted { .. })));
+}
+
+#[tokio::test]
+async fn test_next_chunk_rate_limit_error_event_returns_rate_limited() {
+    let body = sse(&[(
+        "error",
+        json!({
+            "type": "error",
+            "error": { "type": "rate_limit_error", "message": "Too many requests" }
+        }),
+    )]);
+
+    let result = stream(reads(&body, 1024)).into_response().await;
+
+    assert!(matches!(
+        result,
+        Err(LlmError::RateLimited { retry_after: None })
+    ));
+}
+
+#[tokio::test]
+async fn test_next_chunk_malformed_event_data_returns_response_parse() {
+    let result = stream(reads("event: message_start\ndata: {not json\n\n", 1024))
+        .into_response()
+        .await;
+
+    assert!(matches!(result, Err(LlmError::ResponseParse { .. })));
+}
+
+#[tokio::test]
+async fn test_complete_streaming_success_returns_stream_with_request_id() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push(Ok(HttpResponse {
+        status: 200,
+        headers: vec![(RESPONSE_REQUEST_ID_HEADER.to_string(), "req_9".to_string())],
+        body: stream_events().into_bytes(),
+    }));
+
+    let mut stream = provider(&transport)
+        .complete_streaming(request())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        stream.next_chunk().await.unwrap(),
+        Some(CompletionChunk::Text("Hello".to_string()))
+    );
+    assert_eq!(transport.requests()[0].body["stream"], true);
+}
+
+// ─── Message batches ────────────────────────────────────────────────────────
+
+fn batch_body(processing_status: &str) -> JsonValue {
+    json!({
+        "id": "msgbatch_013Zva2CMHLNnXjNJJKqJ2EF",
+        "type": "message_batch",
+        "processing_status": processing_status,
+        "request_counts": { "processing": 7, "succeeded": 3, "errored": 0, "canceled": 0, "expired": 0 }
+    })
+}
+
+#[test]
+fn test_parse_cancel_response_in_progress_returns_canceling() {
+    let response = HttpResponse {
+        status: 200,
+        headers: Vec::new(),
+        body: batch_body("canceling").to_string().into_bytes(),
+    };
+
+    assert_eq!(
+        parse_cancel_response(&response).unwrap(),
+        BatchCancellation::Canceling
+    );
+}
+
+#[test]
+fn test_parse_cancel_response_ended_batch_returns_already_ended() {
+    let response = HttpResponse {
+        status: 200,
+        headers: Vec::new(),
+        body: batch_body("ended").to_string().into_bytes(),
+    };
+
+    assert_eq!(
+        parse_cancel_response(&response).unwrap(),
+        BatchCancellation::AlreadyEnded
+    );
+}
+
+#[test]
+fn test_parse_cancel_response_conflict_returns_already_ended() {
+    let response = HttpResponse {
+        status: 409,
+        headers: Vec::new(),
+        body: Vec::new(),
+    };
+
+    assert_eq!(
+        parse_cancel_response(&response).unwrap(),
+        BatchCancellation::AlreadyEnded
+    );
+}
+
+#[test]
+fn test_parse_cancel_response_missing_status_returns_response_parse() {
+    let response = HttpResponse {
+        status: 200,
+        headers: Vec::new(),
+        body: b"{}".to_vec(),
+    };
+
+    assert!(matches!(
+        parse_cancel_response(&response),
+        Err(LlmError::ResponseParse { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_cancel_batch_in_progress_posts_cancel_and_returns_canceling() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(200, &batch_body("canceling"));
+
+    let cancellation = provider(&transport)
+        .cancel_batch("msgbatch_013Zva2CMHLNnXjNJJKqJ2EF")
+        .await
+        .unwrap();
+
+    assert_eq!(cancellation, BatchCancellation::Canceling);
+    let sent = &transport.requests()[0];
+    assert_eq!(
+        sent.url,
+        "https://llm.example/v1/messages/batches/msgbatch_013Zva2CMHLNnXjNJJKqJ2EF/cancel"
+    );
+    assert!(sent
+        .headers
+        .iter()
+        .any(|(name, value)| name == "x-api-key" && value == "sk-test"));
+}
+
+#[tokio::test]
+async fn test_cancel_batch_already_finished_is_a_no_op() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(409, &json!({ "type": "error" }));
+
+    let cancellation = provider(&transport)
+        .cancel_batch("msgbatch_done")
+        .await
+        .unwrap();
+
+    assert_eq!(cancellation, BatchCancellation::AlreadyEnded);
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_cancel_batch_unknown_batch_returns_error() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(404, &json!({ "type": "error" }));
+
+    let result = provider(&transport).cancel_batch("msgbatch_missing").await;
+
+    assert!(result.is_err());
+}
+
+// ─── Token estimate ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_estimate_prompt_tokens_counts_with_bundled_tokenizer() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let mut request = request();
+    request.system = vec![SystemSegment::new(
+        SystemLayer::Task,
+        "Review the pull request and report any change that does not match the design.",
+    )];
+
+    let estimate = provider(&transport)
+        .estimate_prompt_tokens(&request)
+        .unwrap();
+
+    assert!(!estimate.approximate);
+    assert_eq!(
+        estimate.tokens,
+        crate::tokenizer::BpeTokenizer::bundled().count_request(&request)
+    );
+    assert!(transport.requests().is_empty());
+}
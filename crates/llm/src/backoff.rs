@@ -0,0 +1,197 @@
+//! Provider-level retries with exponential back-off and full jitter.
+//!
+//! [`with_backoff`] re-sends a call that failed with a retryable
+//! [`LlmError`] (429, 408, 5xx including Anthropic's 529 overloaded, or a
+//! connection failure) through [`pipeline::retry::retry_within_budget`].
+//! [`BackoffConfig`] becomes the loop's [`RetrySchedule`], so each wait is
+//! [`RetryPolicy::backoff_for_attempt_jittered`]: a random delay up to
+//! `base × 2^retry`, capped at `max_delay`, or exactly the provider's
+//! `retry-after` when it sent one. Non-retryable errors (400, 401, 403, parse
+//! failures) are returned at once.
+//!
+//! Every retry and its wait are also charged to a [`RunRetryBudget`]: the
+//! run's shared budget when the caller has one, otherwise
+//! [`BackoffConfig::budget`], which allows [`BackoffConfig::max_retries`]
+//! retries and [`BackoffConfig::deadline_secs`] of back-off. When the
+//! schedule or the budget refuses a further retry, the last error is returned
+//! as [`LlmError::ExhaustedRetries`]; a call never retried at all returns its
+//! error unchanged, so the caller may still retry it.
+//!
+//! [`RetryPolicy::backoff_for_attempt_jittered`]: pipeline::RetryPolicy::backoff_for_attempt_jittered
+//!
+//! ```toml
+//! [llm.retry]
+//! max_retries = 3        # default; 0 disables retries
+//! base_delay_ms = 1000   # default
+//! max_delay_ms = 60000   # default
+//! deadline_secs = 300    # default
+//! ```
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §LlmError.
+
+use std::{
+    future::Future,
+    num::NonZeroU32,
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use pipeline::retry::{
+    retry_within_budget, RetryBudget, RetryBudgetError, RetryMetrics, RetrySchedule, RunRetryBudget,
+};
+use pipeline::{LlmError, RetryPolicy};
+
+/// Retries after the first attempt when not configured.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Delay ceiling before the first retry when not configured, in milliseconds.
+pub const DEFAULT_BASE_DELAY_MS: u64 = 1_000;
+
+/// Longest single wait when not configured, in milliseconds.
+pub const DEFAULT_MAX_DELAY_MS: u64 = 60_000;
+
+/// Total back-off allowed across all retries when not configured, in seconds.
+pub const DEFAULT_DEADLINE_SECS: u64 = 300;
+
+/// The `[llm.retry]` configuration table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackoffConfig {
+    /// Retries after the first attempt; zero disables retrying.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Delay ceiling before the first retry, doubled for each later one.
+    #[serde(default = "default_base_delay_ms")]
+    pub base_delay_ms: u64,
+    /// Longest single wait, including a provider's `retry-after`.
+    #[serde(default = "default_max_delay_ms")]
+    pub max_delay_ms: u64,
+    /// Total back-off waited across all retries; a retry whose wait would
+    /// go past it is not started.
+    #[serde(default = "default_deadline_secs")]
+    pub deadline_secs: u64,
+}
+
+fn default_max_retries() -> u32 {
+    DEFAULT_MAX_RETRIES
+}
+
+fn default_base_delay_ms() -> u64 {
+    DEFAULT_BASE_DELAY_MS
+}
+
+fn default_max_delay_ms() -> u64 {
+    DEFAULT_MAX_DELAY_MS
+}
+
+fn default_deadline_secs() -> u64 {
+    DEFAULT_DEADLINE_SECS
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_max_retries(),
+            base_delay_ms: default_base_delay_ms(),
+            max_delay_ms: default_max_delay_ms(),
+            deadline_secs: default_deadline_secs(),
+        }
+    }
+}
+
+impl BackoffConfig {
+    /// A configuration that never retries.
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// The per-operation schedule: `max_retries + 1` attempts, waits
+    /// starting at `base_delay_ms` and capped at `max_delay_ms`.
+    pub fn schedule(&self) -> RetrySchedule {
+        RetrySchedule {
+            max_attempts: NonZeroU32::new(self.max_retries.saturating_add(1))
+                .unwrap_or(NonZeroU32::MIN),
+            backoff: Duration::from_millis(self.base_delay_ms),
+            max_backoff: Duration::from_millis(self.max_delay_ms),
+        }
+    }
+
+    /// A budget of `max_retries` retries and `deadline_secs` of back-off,
+    /// for calls made outside a run's shared [`RunRetryBudget`].
+    pub fn budget(&self) -> RetryBudget {
+        RetryBudget {
+            max_retries: self.max_retries,
+            max_retry_time: Duration::from_secs(self.deadline_secs),
+        }
+    }
+}
+
+/// Runs `call` through [`retry_within_budget`] as `operation`, retrying
+/// retryable failures on `config`'s schedule and charging each retry to
+/// `budget`. Records one sample in `metrics`.
+///
+/// # Errors
+///
+/// - A non-retryable error from `call`, unchanged, on the attempt it occurs.
+/// - A retryable error from `call`, unchanged, when no retry was allowed at
+///   all (`max_retries = 0`), so the caller may retry it.
+/// - [`LlmError::ExhaustedRetries`] — a retryable error after at least one
+///   retry, when the schedule or `budget` refuses another.
+pub async fn with_backoff<T, F, Fut>(
+    operation: &str,
+    config: &BackoffConfig,
+    budget: &RunRetryBudget,
+    metrics: &dyn RetryMetrics,
+    mut call: F,
+) -> Result<T, LlmError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, LlmError>>,
+{
+    let attempts = AtomicU32::new(0);
+    let result = retry_within_budget(
+        operation,
+        config.schedule(),
+        budget,
+        metrics,
+        || {
+            attempts.fetch_add(1, Ordering::Relaxed);
+            call()
+        },
+        tokio::time::sleep,
+    )
+    .await;
+    let attempts = attempts.into_inner();
+    let last = match result {
+        Ok(value) => return Ok(value),
+        Err(RetryBudgetError::Failed(error))
+            if matches!(error.retry_policy(), RetryPolicy::NonRetryable) =>
+        {
+            return Err(error);
+        }
+        Err(
+            RetryBudgetError::Failed(error)
+            | RetryBudgetError::Exhausted {
+                last_error: error, ..
+            },
+        ) => error,
+    };
+    if attempts <= 1 {
+        return Err(last);
+    }
+    tracing::warn!(operation, attempts, error = %last, "LLM call failed; retries exhausted");
+    Err(LlmError::ExhaustedRetries {
+        attempts,
+        last: Box::new(last),
+    })
+}
+
+#[cfg(test)]
+#[path = "backoff_tests.rs"]
+mod tests;
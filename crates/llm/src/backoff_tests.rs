@@ -0,0 +1,200 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use pipeline::retry::{InMemoryRetryMetrics, NoopRetryMetrics, RetryOutcome};
+
+use super::*;
+
+/// Retries with millisecond waits, so tests do not sleep for long.
+fn fast(max_retries: u32) -> BackoffConfig {
+    BackoffConfig {
+        max_retries,
+        base_delay_ms: 1,
+        max_delay_ms: 2,
+        deadline_secs: 60,
+    }
+}
+
+fn overloaded() -> LlmError {
+    LlmError::Transient {
+        message: "HTTP 529: overloaded_error".to_string(),
+    }
+}
+
+/// Runs `with_backoff` over `results`, one per attempt, returning the
+/// outcome and the number of results left unused.
+async fn run(
+    config: &BackoffConfig,
+    budget: &RunRetryBudget,
+    results: Vec<Result<&'static str, LlmError>>,
+) -> (Result<&'static str, LlmError>, usize) {
+    let results = Mutex::new(VecDeque::from(results));
+    let result = with_backoff("llm.test", config, budget, &NoopRetryMetrics, || {
+        let next = results
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("unexpected attempt");
+        async move { next }
+    })
+    .await;
+    let remaining = results.lock().unwrap().len();
+    (result, remaining)
+}
+
+// ─── BackoffConfig ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_schedule_allows_one_attempt_per_retry_plus_the_first() {
+    let schedule = BackoffConfig::default().schedule();
+
+    assert_eq!(schedule.max_attempts.get(), DEFAULT_MAX_RETRIES + 1);
+    assert_eq!(
+        schedule.backoff,
+        Duration::from_millis(DEFAULT_BASE_DELAY_MS)
+    );
+    assert_eq!(
+        schedule.max_backoff,
+        Duration::from_millis(DEFAULT_MAX_DELAY_MS)
+    );
+}
+
+#[test]
+fn test_schedule_disabled_allows_single_attempt() {
+    assert_eq!(BackoffConfig::disabled().schedule().max_attempts.get(), 1);
+}
+
+#[test]
+fn test_budget_limits_retries_and_total_back_off() {
+    let budget = BackoffConfig::default().budget();
+
+    assert_eq!(budget.max_retries, DEFAULT_MAX_RETRIES);
+    assert_eq!(
+        budget.max_retry_time,
+        Duration::from_secs(DEFAULT_DEADLINE_SECS)
+    );
+}
+
+// ─── with_backoff ───────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_with_backoff_two_overloaded_then_success_returns_value() {
+    let config = fast(3);
+    let budget = RunRetryBudget::new(config.budget());
+
+    let (result, remaining) = run(
+        &config,
+        &budget,
+        vec![Err(overloaded()), Err(overloaded()), Ok("done")],
+    )
+    .await;
+
+    assert_eq!(result.unwrap(), "done");
+    assert_eq!(remaining, 0);
+    assert_eq!(budget.usage().retries, 2);
+}
+
+#[tokio::test]
+async fn test_with_backoff_non_retryable_error_returns_it_without_retry() {
+    let config = fast(3);
+    let budget = RunRetryBudget::new(config.budget());
+
+    let (result, remaining) = run(
+        &config,
+        &budget,
+        vec![
+            Err(LlmError::Authentication {
+                message: "HTTP 401".to_string(),
+            }),
+            Ok("unreachable"),
+        ],
+    )
+    .await;
+
+    assert!(matches!(result, Err(LlmError::Authentication { .. })));
+    assert_eq!(remaining, 1);
+    assert_eq!(budget.usage().retries, 0);
+}
+
+#[tokio::test]
+async fn test_with_backoff_retries_exhausted_returns_exhausted_retries() {
+    let config = fast(2);
+    let budget = RunRetryBudget::new(config.budget());
+
+    let (result, remaining) = run(
+        &config,
+        &budget,
+        vec![Err(overloaded()), Err(overloaded()), Err(overloaded())],
+    )
+    .await;
+
+    assert_eq!(remaining, 0);
+    match result {
+        Err(LlmError::ExhaustedRetries { attempts, last }) => {
+            assert_eq!(attempts, 3);
+            assert!(matches!(*last, LlmError::Transient { .. }));
+        }
+        other => panic!("expected ExhaustedRetries, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_with_backoff_disabled_returns_first_error_unwrapped() {
+    let config = BackoffConfig::disabled();
+    let budget = RunRetryBudget::new(config.budget());
+
+    let (result, _) = run(
+        &config,
+        &budget,
+        vec![Err(LlmError::RateLimited { retry_after: None })],
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(LlmError::RateLimited { retry_after: None })
+    ));
+}
+
+#[tokio::test]
+async fn test_with_backoff_shared_budget_spent_returns_exhausted_retries() {
+    let config = fast(5);
+    let budget = RunRetryBudget::new(RetryBudget {
+        max_retries: 1,
+        max_retry_time: Duration::from_secs(60),
+    });
+
+    let (result, remaining) = run(
+        &config,
+        &budget,
+        vec![Err(overloaded()), Err(overloaded()), Ok("unreachable")],
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(LlmError::ExhaustedRetries { attempts: 2, .. })
+    ));
+    assert_eq!(remaining, 1);
+    assert_eq!(budget.usage().retries, 1);
+}
+
+#[tokio::test]
+async fn test_with_backoff_records_one_sample_per_call() {
+    let config = fast(3);
+    let budget = RunRetryBudget::new(config.budget());
+    let metrics = InMemoryRetryMetrics::new();
+    let mut results = VecDeque::from([Err(overloaded()), Ok(())]);
+
+    with_backoff("llm.test", &config, &budget, &metrics, || {
+        let next = results.pop_front().unwrap();
+        async move { next }
+    })
+    .await
+    .unwrap();
+
+    let samples = metrics.samples();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].operation, "llm.test");
+    assert_eq!(samples[0].outcome, RetryOutcome::Success);
+    assert_eq!(samples[0].attempts, 2);
+}
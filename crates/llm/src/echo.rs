@@ -0,0 +1,92 @@
+//! Deterministic echo provider for pipeline-structure testing.
+//!
+//! [`EchoProvider`] implements [`LlmProvider`] without any network access: it
+//! answers every request with text derived only from the request, and reports
+//! zero token usage so the run accrues no cost. Selecting it
+//! (`[llm] provider = "echo"`, see [`crate::provider::ProviderKind`]) lets a
+//! pipeline be exercised end to end — graph traversal, checkpointing, comment
+//! rendering — without paying for completions.
+//!
+//! The same request always produces the same response, so runs against the
+//! echo provider can be snapshot-tested.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §Echo provider.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use pipeline::{
+    CompletionRequest, CompletionResponse, FinishReason, LlmError, LlmProvider, MessageRole,
+    TokenUsage,
+};
+
+/// What [`EchoProvider`] answers with.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum EchoResponse {
+    /// The content of the last user message, or an empty string if there is
+    /// none.
+    #[default]
+    LastUserMessage,
+    /// A fixed text, whatever the request.
+    Fixed {
+        /// The text returned.
+        text: String,
+    },
+}
+
+/// An [`LlmProvider`] that echoes the request instead of calling a model.
+///
+/// Responses report the requested model, zero usage, and
+/// [`FinishReason::EndTurn`]. The provider never fails.
+#[derive(Debug, Clone, Default)]
+pub struct EchoProvider {
+    response: EchoResponse,
+}
+
+impl EchoProvider {
+    /// Creates a provider that echoes the last user message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a provider answering as configured by `response`.
+    pub fn with_response(response: EchoResponse) -> Self {
+        Self { response }
+    }
+
+    /// Returns the text answered for `request`.
+    pub fn reply(&self, request: &CompletionRequest) -> String {
+        match &self.response {
+            EchoResponse::LastUserMessage => request
+                .messages
+                .iter()
+                .rev()
+                .find(|message| message.role == MessageRole::User)
+                .map(|message| message.content.clone())
+                .unwrap_or_default(),
+            EchoResponse::Fixed { text } => text.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for EchoProvider {
+    #[instrument(skip(self, request), fields(model = %request.model))]
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        Ok(CompletionResponse {
+            content: self.reply(&request),
+            model: request.model,
+            usage: TokenUsage::zero(),
+            finish_reason: FinishReason::EndTurn,
+            provider_request_id: None,
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "echo_tests.rs"]
+mod tests;
@@ -0,0 +1,84 @@
+use pipeline::{Message, TokenCount};
+
+use crate::provider::{LlmConfig, ProviderKind};
+
+use super::*;
+
+fn request(messages: Vec<Message>) -> CompletionRequest {
+    CompletionRequest::new("claude-sonnet", messages, TokenCount::new(256))
+}
+
+#[test]
+fn test_reply_last_user_message_returns_latest_user_turn() {
+    let provider = EchoProvider::new();
+    let request = request(vec![
+        Message::user("first question"),
+        Message::assistant("an answer"),
+        Message::user("follow-up"),
+    ]);
+
+    assert_eq!(provider.reply(&request), "follow-up");
+}
+
+#[test]
+fn test_reply_without_user_message_returns_empty_string() {
+    let provider = EchoProvider::new();
+
+    assert_eq!(
+        provider.reply(&request(vec![Message::assistant("only me")])),
+        ""
+    );
+}
+
+#[test]
+fn test_reply_fixed_response_ignores_request() {
+    let provider = EchoProvider::with_response(EchoResponse::Fixed {
+        text: "LGTM".to_string(),
+    });
+
+    assert_eq!(
+        provider.reply(&request(vec![Message::user("review this")])),
+        "LGTM"
+    );
+}
+
+#[tokio::test]
+async fn test_complete_echo_reports_requested_model_and_zero_usage() {
+    let provider = EchoProvider::new();
+
+    let response = provider
+        .complete(request(vec![Message::user("plan the work")]))
+        .await
+        .unwrap();
+
+    assert_eq!(response.content, "plan the work");
+    assert_eq!(response.model, "claude-sonnet");
+    assert_eq!(response.usage, TokenUsage::zero());
+    assert_eq!(response.finish_reason, FinishReason::EndTurn);
+    assert_eq!(response.provider_request_id, None);
+}
+
+#[tokio::test]
+async fn test_complete_same_request_returns_same_response() {
+    let provider = EchoProvider::new();
+    let request = request(vec![Message::user("deterministic")]);
+
+    let first = provider.complete(request.clone()).await.unwrap();
+    let second = provider.complete(request).await.unwrap();
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_is_billable_echo_provider_is_not_billed() {
+    assert!(ProviderKind::Anthropic.is_billable());
+    assert!(!ProviderKind::Echo.is_billable());
+}
+
+#[test]
+fn test_llm_config_default_uses_anthropic_and_echoes_last_user_message() {
+    let config = LlmConfig::default();
+
+    assert_eq!(config.provider, ProviderKind::Anthropic);
+    assert_eq!(config.echo, EchoResponse::LastUserMessage);
+}
@@ -1,8 +1,10 @@
 //! CogWorks LLM provider infrastructure adapter.
 //!
-//! Implements the [`pipeline::LlmProvider`] trait for Anthropic's API.
-//! Additional providers (e.g. OpenAI) are added as new `impl` blocks in this
-//! crate without any changes to the `pipeline` crate.
+//! Implements the [`pipeline::LlmProvider`] trait for Anthropic's Messages API
+//! ([`anthropic::AnthropicProvider`]) and OpenAI's Chat Completions API
+//! ([`openai::OpenAiProvider`]). `[llm] provider` ([`provider::ProviderKind`])
+//! selects which one the CLI constructs. Additional providers are added as new
+//! `impl` blocks in this crate without any changes to the `pipeline` crate.
 //!
 //! ## Architectural Layer
 //!
@@ -10,8 +12,92 @@
 //! rate-limit header tracking, and exponential back-off live here. The
 //! [`pipeline`] crate sees only [`pipeline::LlmProvider`].
 //!
+//! ## Transport
+//!
+//! Providers never open connections themselves. Each is constructed over an
+//! `Arc<dyn` [`transport::LlmTransport`]`>`: production wiring passes a
+//! [`transport::ReqwestTransport`], tests pass a scripted transport that
+//! replays canned responses.
+//!
+//! ## Retries
+//!
+//! Retried provider calls go through [`pipeline::retry::retry`], which derives
+//! the retry decision from [`pipeline::LlmError::retry_policy`] and records
+//! attempt counts and total duration per operation.
+//!
+//! ## Provider Wire Formats
+//!
+//! | Module | API | System prompt placement |
+//! |--------|-----|-------------------------|
+//! | [`anthropic`] | Messages API | Top-level `system` array, one text block per segment |
+//! | [`openai`] | Chat Completions API | Single leading `system` message, segments joined by a blank line |
+//!
+//! Both formatters compose [`pipeline::SystemSegment`]s in
+//! [`pipeline::SystemLayer`] order and forward stop sequences.
+//!
+//! ## Streaming
+//!
+//! [`pipeline::LlmProvider::complete_streaming`] returns output as it is
+//! generated. [`anthropic::AnthropicStream`] reads the Messages API's
+//! server-sent events through [`sse::SseDecoder`], which reassembles events
+//! split across network reads; the transport supplies the body through
+//! [`transport::LlmTransport::post_json_streaming`]. Providers without a
+//! streaming implementation return their full response as a single chunk.
+//!
+//! ## Logging
+//!
+//! At DEBUG, [`anthropic::AnthropicProvider`] logs each request and response
+//! through a [`redaction::Redactor`], which replaces matches of the patterns
+//! passed to `with_redaction` with `***` and always hides the `x-api-key` and
+//! `authorization` headers.
+//!
+//! ## Sampling Parameters
+//!
+//! [`sampling::LlmSamplingConfig`] validates temperature (`0..=2`), `top_p`
+//! (`0..=1`), and `max_tokens` (`> 0`) when it is built and copies them onto
+//! a [`pipeline::CompletionRequest`]; both formatters forward `temperature`
+//! and `top_p` and omit them when unset.
+//!
+//! ## Echo Provider
+//!
+//! [`echo::EchoProvider`] answers every request with text derived from the
+//! request itself and zero usage. Selecting it with `[llm] provider = "echo"`
+//! ([`provider::ProviderKind`]) runs a pipeline's structure without LLM cost.
+//!
+//! ## Connectivity Probe
+//!
+//! [`probe::probe_connectivity`] sends a one-token request through any
+//! [`pipeline::LlmProvider`] and reports the latency and serving model. The
+//! CLI `doctor` check uses it to verify credentials and model access.
+//!
+//! ## Prompt Token Estimates
+//!
+//! [`tokenizer::BpeTokenizer`] counts prompt tokens locally with a
+//! byte-pair-encoding table compiled into the crate
+//! ([`tokenizer::BUNDLED_TABLE`]). Both providers override
+//! [`pipeline::LlmProvider::estimate_prompt_tokens`] with it and report
+//! [`pipeline::TokenEstimate::exact`] instead of the character heuristic.
+//!
+//! ## Cargo Features
+//!
+//! | Feature | Enables |
+//! |---------|---------|
+//! | `mock-transport` | `transport::ScriptedTransport`, a test-only transport replaying queued responses |
+//!
 //! ## Specification
 //!
 //! See `docs/spec/interfaces/infrastructure.md` §llm for the full contract.
 //!
 //! *This crate is a skeleton. Method bodies are added in PR 10.*
+
+pub mod anthropic;
+pub mod backoff;
+pub mod echo;
+pub mod openai;
+pub mod probe;
+pub mod provider;
+pub mod redaction;
+pub mod sampling;
+pub mod sse;
+pub mod tokenizer;
+pub mod transport;
@@ -0,0 +1,337 @@
+//! OpenAI Chat Completions API request formatting.
+//!
+//! OpenAI has no top-level system field: the system prompt is a single
+//! `system`-role message placed first in `messages`. The layered
+//! [`pipeline::SystemSegment`]s are joined with a blank line, in
+//! [`pipeline::SystemLayer`] order, to form that message.
+//!
+//! [`OpenAiProvider`] sends the formatted body through an [`LlmTransport`]
+//! with a bearer token and parses the first choice of the response. Status
+//! codes map as for Anthropic (see [`status_error`]), so a 429 becomes
+//! [`LlmError::RateLimited`] carrying `Retry-After` and is retried.
+//!
+//! [`LlmProvider::estimate_prompt_tokens`] counts the prompt locally with the
+//! bundled [`BpeTokenizer`].
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §Provider wire formats.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tracing::instrument;
+
+use pipeline::llm::{
+    CompletionRequest, CompletionResponse, FinishReason, LlmError, LlmProvider, MessageRole,
+    TokenEstimate, TokenUsage,
+};
+use pipeline::TokenCount;
+
+use crate::tokenizer::BpeTokenizer;
+use crate::transport::{status_error, HttpRequest, LlmTransport};
+
+/// Default OpenAI API origin.
+pub const DEFAULT_BASE_URL: &str = "https://api.openai.com";
+
+/// Maximum number of stop sequences accepted by the Chat Completions API.
+pub const MAX_STOP_SEQUENCES: usize = 4;
+
+/// Request header OpenAI accepts for a client-generated request ID.
+pub const CLIENT_REQUEST_ID_HEADER: &str = "X-Client-Request-Id";
+
+/// Response header carrying the ID OpenAI assigned to the request.
+pub const RESPONSE_REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Separator placed between system segments in the composed system message.
+const SEGMENT_SEPARATOR: &str = "\n\n";
+
+/// One entry of the Chat Completions `messages` array.
+#[derive(Debug, Serialize)]
+struct WireMessage<'a> {
+    role: &'static str,
+    content: std::borrow::Cow<'a, str>,
+}
+
+/// Body of `POST /v1/chat/completions`.
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest<'a> {
+    model: &'a str,
+    messages: Vec<WireMessage<'a>>,
+    max_tokens: u64,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    stop: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f64>,
+}
+
+fn role_name(role: MessageRole) -> &'static str {
+    match role {
+        MessageRole::User => "user",
+        MessageRole::Assistant => "assistant",
+    }
+}
+
+/// Build the JSON body for an OpenAI Chat Completions call.
+///
+/// - System segments are joined in layer order into one leading `system`
+///   message. No system message is emitted when there are no segments.
+/// - `stop_sequences` maps to the `stop` field and is omitted when empty.
+///
+/// # Errors
+///
+/// - [`LlmError::InvalidRequest`] — the request has no messages, has more than
+///   [`MAX_STOP_SEQUENCES`] stop sequences, or could not be serialised.
+pub fn request_body(request: &CompletionRequest) -> Result<JsonValue, LlmError> {
+    if request.messages.is_empty() {
+        return Err(LlmError::InvalidRequest {
+            message: "OpenAI requests require at least one message".to_string(),
+        });
+    }
+    if request.stop_sequences.len() > MAX_STOP_SEQUENCES {
+        return Err(LlmError::InvalidRequest {
+            message: format!(
+                "OpenAI accepts at most {MAX_STOP_SEQUENCES} stop sequences; got {}",
+                request.stop_sequences.len()
+            ),
+        });
+    }
+
+    let mut messages = Vec::with_capacity(request.messages.len() + 1);
+    let system = request.layered_system();
+    if !system.is_empty() {
+        let composed = system
+            .iter()
+            .map(|segment| segment.content.as_str())
+            .collect::<Vec<_>>()
+            .join(SEGMENT_SEPARATOR);
+        messages.push(WireMessage {
+            role: "system",
+            content: composed.into(),
+        });
+    }
+    messages.extend(request.messages.iter().map(|message| WireMessage {
+        role: role_name(message.role),
+        content: message.content.as_str().into(),
+    }));
+
+    let body = ChatCompletionRequest {
+        model: &request.model,
+        messages,
+        max_tokens: request.max_tokens.as_u64(),
+        stop: &request.stop_sequences,
+        temperature: request.temperature,
+        top_p: request.top_p,
+    };
+
+    serde_json::to_value(body).map_err(|e| LlmError::InvalidRequest {
+        message: format!("failed to serialise OpenAI request: {e}"),
+    })
+}
+
+/// Map a Chat Completions `finish_reason` to a [`FinishReason`].
+///
+/// OpenAI reports `stop` both for a natural end and for a matched stop
+/// sequence, so both map to [`FinishReason::EndTurn`].
+///
+/// | `finish_reason` | [`FinishReason`] |
+/// |-----------------|------------------|
+/// | `stop` | `EndTurn` |
+/// | `length` | `MaxTokens` |
+/// | `content_filter` | `Refusal` |
+/// | anything else (e.g. `tool_calls`) | `Other` |
+pub fn finish_reason(finish_reason: &str) -> FinishReason {
+    match finish_reason {
+        "stop" => FinishReason::EndTurn,
+        "length" => FinishReason::MaxTokens,
+        "content_filter" => FinishReason::Refusal,
+        other => FinishReason::Other(other.to_string()),
+    }
+}
+
+// ─── Response ───────────────────────────────────────────────────────────────
+
+/// The `message` of a response choice.
+#[derive(Debug, Deserialize)]
+struct ChoiceMessage {
+    /// `null` when the model answered only with tool calls or refused.
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// One entry of the response `choices` array.
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+/// `usage.prompt_tokens_details`.
+#[derive(Debug, Default, Deserialize)]
+struct PromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: u64,
+}
+
+/// The `usage` object of a Chat Completions response.
+#[derive(Debug, Deserialize)]
+struct ResponseUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    #[serde(default)]
+    prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+/// Body of a successful `POST /v1/chat/completions` response.
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    model: String,
+    choices: Vec<Choice>,
+    usage: ResponseUsage,
+}
+
+/// Parse a Chat Completions response body into a [`CompletionResponse`].
+///
+/// Only the first choice is read; a `null` content is an empty string and a
+/// missing `finish_reason` is [`FinishReason::Other`] with an empty string.
+/// OpenAI counts cached prompt tokens within `prompt_tokens`; they are moved
+/// to [`TokenUsage::cache_read_input_tokens`] so that `input_tokens` means the
+/// same as for Anthropic.
+///
+/// # Errors
+///
+/// - [`LlmError::ResponseParse`] — the body is not a Chat Completions
+///   response, or it has no choices.
+pub fn parse_response(body: &[u8]) -> Result<CompletionResponse, LlmError> {
+    let response: ChatCompletionResponse =
+        serde_json::from_slice(body).map_err(|e| LlmError::ResponseParse {
+            message: format!("failed to parse OpenAI response: {e}"),
+        })?;
+    let choice = response
+        .choices
+        .into_iter()
+        .next()
+        .ok_or_else(|| LlmError::ResponseParse {
+            message: "OpenAI response has no choices".to_string(),
+        })?;
+
+    let cached = response
+        .usage
+        .prompt_tokens_details
+        .map_or(0, |details| details.cached_tokens);
+    let mut usage = TokenUsage::new(
+        TokenCount::new(response.usage.prompt_tokens.saturating_sub(cached)),
+        TokenCount::new(response.usage.completion_tokens),
+    );
+    usage.cache_read_input_tokens = TokenCount::new(cached);
+
+    Ok(CompletionResponse {
+        content: choice.message.content.unwrap_or_default(),
+        model: response.model,
+        usage,
+        finish_reason: finish_reason(choice.finish_reason.as_deref().unwrap_or_default()),
+        provider_request_id: None,
+    })
+}
+
+// ─── Provider ───────────────────────────────────────────────────────────────
+
+/// [`LlmProvider`] for the OpenAI Chat Completions API.
+///
+/// Streaming uses the provided [`LlmProvider::complete_streaming`], which
+/// returns the whole response as one chunk.
+pub struct OpenAiProvider {
+    transport: Arc<dyn LlmTransport>,
+    api_key: String,
+    base_url: String,
+}
+
+impl OpenAiProvider {
+    /// Creates a provider sending requests to [`DEFAULT_BASE_URL`] through
+    /// `transport`.
+    pub fn new(transport: Arc<dyn LlmTransport>, api_key: impl Into<String>) -> Self {
+        Self {
+            transport,
+            api_key: api_key.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+        }
+    }
+
+    /// Overrides the API origin (e.g. for Azure OpenAI or a compatible
+    /// gateway).
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// The `POST /v1/chat/completions` request for `request`.
+    fn chat_request(&self, request: &CompletionRequest) -> Result<HttpRequest, LlmError> {
+        let mut headers = vec![
+            (
+                "authorization".to_string(),
+                format!("Bearer {}", self.api_key),
+            ),
+            ("content-type".to_string(), "application/json".to_string()),
+        ];
+        if let Some(request_id) = &request.request_id {
+            headers.push((CLIENT_REQUEST_ID_HEADER.to_string(), request_id.clone()));
+        }
+        Ok(HttpRequest {
+            url: format!("{}/v1/chat/completions", self.base_url),
+            headers,
+            body: request_body(request)?,
+        })
+    }
+}
+
+impl std::fmt::Debug for OpenAiProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpenAiProvider")
+            .field("base_url", &self.base_url)
+            .field("api_key", &"[REDACTED]")
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl LlmProvider for OpenAiProvider {
+    #[instrument(
+        skip(self, request),
+        fields(model = %request.model, request_id = ?request.request_id)
+    )]
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        let http_request = self.chat_request(&request)?;
+
+        let response = self.transport.post_json(http_request).await?;
+        let provider_request_id = response
+            .header(RESPONSE_REQUEST_ID_HEADER)
+            .map(str::to_string);
+        if let Some(error) = status_error(&response) {
+            tracing::debug!(?provider_request_id, "OpenAI request failed");
+            return Err(error);
+        }
+        let mut completion = parse_response(&response.body)?;
+        completion.provider_request_id = provider_request_id;
+        Ok(completion)
+    }
+
+    /// Counts the prompt with the bundled [`BpeTokenizer`].
+    fn estimate_prompt_tokens(
+        &self,
+        request: &CompletionRequest,
+    ) -> Result<TokenEstimate, LlmError> {
+        Ok(TokenEstimate::exact(
+            BpeTokenizer::bundled().count_request(request),
+        ))
+    }
+}
+
+#[cfg(test)]
+#[path = "openai_tests.rs"]
+mod tests;
@@ -0,0 +1,296 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use pipeline::{Message, RetryPolicy, SystemLayer, SystemSegment, TokenCount};
+use serde_json::json;
+
+use crate::provider::{LlmConfig, ProviderKind};
+use crate::transport::{HttpResponse, ScriptedTransport};
+
+use super::*;
+
+fn request() -> CompletionRequest {
+    CompletionRequest::new(
+        "gpt-test",
+        vec![Message::user("hello")],
+        TokenCount::new(100),
+    )
+}
+
+#[test]
+fn test_request_body_system_segments_joined_into_leading_message() {
+    let mut request = request();
+    request.system = vec![
+        SystemSegment::new(SystemLayer::Task, "task"),
+        SystemSegment::new(SystemLayer::Constitutional, "rules"),
+    ];
+
+    let body = request_body(&request).unwrap();
+
+    assert_eq!(
+        body["messages"],
+        json!([
+            { "role": "system", "content": "rules\n\ntask" },
+            { "role": "user", "content": "hello" },
+        ])
+    );
+}
+
+#[test]
+fn test_request_body_no_system_or_stop_sequences_omits_them() {
+    let body = request_body(&request()).unwrap();
+
+    assert_eq!(
+        body["messages"],
+        json!([{ "role": "user", "content": "hello" }])
+    );
+    assert!(body.get("stop").is_none());
+}
+
+#[test]
+fn test_request_body_stop_sequences_mapped_to_stop() {
+    let mut request = request();
+    request.stop_sequences = vec!["END".to_string()];
+
+    let body = request_body(&request).unwrap();
+
+    assert_eq!(body["stop"], json!(["END"]));
+}
+
+#[test]
+fn test_request_body_too_many_stop_sequences_returns_invalid_request() {
+    let mut request = request();
+    request.stop_sequences = (0..=MAX_STOP_SEQUENCES)
+        .map(|i| format!("stop{i}"))
+        .collect();
+
+    assert!(matches!(
+        request_body(&request),
+        Err(LlmError::InvalidRequest { .. })
+    ));
+}
+
+#[test]
+fn test_request_body_no_messages_returns_invalid_request() {
+    let mut request = request();
+    request.messages.clear();
+
+    assert!(matches!(
+        request_body(&request),
+        Err(LlmError::InvalidRequest { .. })
+    ));
+}
+
+#[test]
+fn test_finish_reason_each_finish_reason_maps_to_variant() {
+    assert_eq!(finish_reason("stop"), FinishReason::EndTurn);
+    assert_eq!(finish_reason("length"), FinishReason::MaxTokens);
+    assert_eq!(finish_reason("content_filter"), FinishReason::Refusal);
+    assert_eq!(
+        finish_reason("tool_calls"),
+        FinishReason::Other("tool_calls".to_string())
+    );
+}
+
+#[test]
+fn test_request_body_all_fields_serialised_in_chat_completions_shape() {
+    let mut request = request();
+    request.system = vec![SystemSegment::new(SystemLayer::Constitutional, "rules")];
+    request.messages.push(Message::assistant("hi"));
+    request.stop_sequences = vec!["END".to_string()];
+    request.temperature = Some(0.5);
+    request.top_p = Some(0.9);
+
+    let body = request_body(&request).unwrap();
+
+    assert_eq!(
+        body,
+        json!({
+            "model": "gpt-test",
+            "messages": [
+                { "role": "system", "content": "rules" },
+                { "role": "user", "content": "hello" },
+                { "role": "assistant", "content": "hi" },
+            ],
+            "max_tokens": 100,
+            "stop": ["END"],
+            "temperature": 0.5,
+            "top_p": 0.9,
+        })
+    );
+}
+
+// ─── Response ───────────────────────────────────────────────────────────────
+
+fn success_body() -> JsonValue {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "model": "gpt-test-2025-01-01",
+        "choices": [
+            {
+                "index": 0,
+                "message": { "role": "assistant", "content": "Hello, world" },
+                "finish_reason": "stop"
+            }
+        ],
+        "usage": {
+            "prompt_tokens": 120,
+            "completion_tokens": 3,
+            "total_tokens": 123,
+            "prompt_tokens_details": { "cached_tokens": 100 }
+        }
+    })
+}
+
+#[test]
+fn test_parse_response_recorded_response_maps_first_choice_and_usage() {
+    let response = parse_response(success_body().to_string().as_bytes()).unwrap();
+
+    assert_eq!(response.content, "Hello, world");
+    assert_eq!(response.model, "gpt-test-2025-01-01");
+    assert_eq!(response.finish_reason, FinishReason::EndTurn);
+    assert_eq!(
+        response.usage,
+        TokenUsage {
+            cache_read_input_tokens: TokenCount::new(100),
+            ..TokenUsage::new(TokenCount::new(20), TokenCount::new(3))
+        }
+    );
+}
+
+#[test]
+fn test_parse_response_null_content_returns_empty_text() {
+    let body = json!({
+        "model": "gpt-test",
+        "choices": [{ "message": { "content": null }, "finish_reason": "content_filter" }],
+        "usage": { "prompt_tokens": 5, "completion_tokens": 0 }
+    });
+
+    let response = parse_response(body.to_string().as_bytes()).unwrap();
+
+    assert_eq!(response.content, "");
+    assert_eq!(response.finish_reason, FinishReason::Refusal);
+}
+
+#[test]
+fn test_parse_response_no_choices_returns_response_parse() {
+    let body = json!({
+        "model": "gpt-test",
+        "choices": [],
+        "usage": { "prompt_tokens": 5, "completion_tokens": 0 }
+    });
+
+    assert!(matches!(
+        parse_response(body.to_string().as_bytes()),
+        Err(LlmError::ResponseParse { .. })
+    ));
+}
+
+// ─── Provider over a scripted transport ─────────────────────────────────────
+
+fn provider(transport: &Arc<ScriptedTransport>) -> OpenAiProvider {
+    OpenAiProvider::new(Arc::clone(transport) as _, "sk-test").with_base_url("https://llm.example/")
+}
+
+#[tokio::test]
+async fn test_complete_success_sends_bearer_request_and_parses_response() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push(Ok(HttpResponse {
+        status: 200,
+        headers: vec![(RESPONSE_REQUEST_ID_HEADER.to_string(), "req_7".to_string())],
+        body: success_body().to_string().into_bytes(),
+    }));
+
+    let response = provider(&transport)
+        .complete(request().with_request_id("run-1/plan/1"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.content, "Hello, world");
+    assert_eq!(response.provider_request_id.as_deref(), Some("req_7"));
+    let sent = &transport.requests()[0];
+    assert_eq!(sent.url, "https://llm.example/v1/chat/completions");
+    assert!(sent
+        .headers
+        .contains(&("authorization".to_string(), "Bearer sk-test".to_string())));
+    assert!(sent.headers.contains(&(
+        CLIENT_REQUEST_ID_HEADER.to_string(),
+        "run-1/plan/1".to_string()
+    )));
+    assert_eq!(sent.body, request_body(&request()).unwrap());
+}
+
+#[tokio::test]
+async fn test_complete_rate_limited_with_retry_after_returns_retryable() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push(Ok(HttpResponse {
+        status: 429,
+        headers: vec![("retry-after".to_string(), "7".to_string())],
+        body: br#"{"error":{"type":"rate_limit_exceeded"}}"#.to_vec(),
+    }));
+
+    let error = provider(&transport).complete(request()).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        LlmError::RateLimited { retry_after: Some(delay) } if delay == Duration::from_secs(7)
+    ));
+    assert_eq!(
+        error.retry_policy(),
+        RetryPolicy::Retryable {
+            after: Some(Duration::from_secs(7))
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_complete_unauthorized_returns_authentication() {
+    let transport = Arc::new(ScriptedTransport::new());
+    transport.push_json(401, &json!({ "error": { "type": "invalid_api_key" } }));
+
+    let error = provider(&transport).complete(request()).await.unwrap_err();
+
+    assert!(matches!(error, LlmError::Authentication { .. }));
+}
+
+#[test]
+fn test_debug_redacts_api_key() {
+    let transport = Arc::new(ScriptedTransport::new());
+
+    let debug = format!("{:?}", provider(&transport));
+
+    assert!(!debug.contains("sk-test"));
+    assert!(debug.contains("[REDACTED]"));
+}
+
+#[test]
+fn test_llm_config_openai_provider_selected_by_name() {
+    let config: LlmConfig = serde_json::from_value(json!({ "provider": "openai" })).unwrap();
+
+    assert_eq!(config.provider, ProviderKind::OpenAi);
+    assert!(config.provider.is_billable());
+}
+
+// ─── Token estimate ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_estimate_prompt_tokens_counts_with_bundled_tokenizer() {
+    let transport = Arc::new(ScriptedTransport::new());
+    let mut request = request();
+    request.system = vec![SystemSegment::new(
+        SystemLayer::Task,
+        "Review the pull request and report any change that does not match the design.",
+    )];
+
+    let estimate = provider(&transport)
+        .estimate_prompt_tokens(&request)
+        .unwrap();
+
+    assert!(!estimate.approximate);
+    assert_eq!(
+        estimate.tokens,
+        crate::tokenizer::BpeTokenizer::bundled().count_request(&request)
+    );
+    assert!(transport.requests().is_empty());
+}
@@ -0,0 +1,81 @@
+//! Minimal-cost LLM connectivity probe.
+//!
+//! Used by the CLI `doctor` check to confirm that credentials are accepted and
+//! the configured model is reachable. The probe sends a real request capped at
+//! one output token, so it exercises authentication, model access, and the
+//! network path while costing a fraction of a cent.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §Connectivity probe.
+
+use std::time::{Duration, Instant};
+
+use tracing::instrument;
+
+use pipeline::{CompletionRequest, LlmError, LlmProvider, Message, TokenCount, TokenUsage};
+
+/// Prompt sent by [`probe_connectivity`]. Kept short to minimise input tokens.
+pub const PROBE_PROMPT: &str = "ping";
+
+/// Output-token cap for the probe request.
+pub const PROBE_MAX_TOKENS: u64 = 1;
+
+/// Result of a successful connectivity probe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectivityReport {
+    /// The model that was requested.
+    pub requested_model: String,
+    /// The model that served the request, as reported by the provider.
+    ///
+    /// May differ from `requested_model` when the provider resolves an alias
+    /// (e.g. a `-latest` name) to a dated version.
+    pub served_model: String,
+    /// Wall-clock time from sending the request to receiving the response.
+    pub latency: Duration,
+    /// Tokens consumed by the probe.
+    pub usage: TokenUsage,
+}
+
+/// Sends a one-token completion to `provider` for `model` and reports latency
+/// and the serving model.
+///
+/// # Errors
+///
+/// Returns the provider's [`LlmError`] unchanged, so the caller can tell an
+/// authentication failure ([`LlmError::Authentication`]) from an inaccessible
+/// model ([`LlmError::InvalidRequest`]) or a network problem
+/// ([`LlmError::Transient`]).
+#[instrument(skip(provider))]
+pub async fn probe_connectivity(
+    provider: &dyn LlmProvider,
+    model: &str,
+) -> Result<ConnectivityReport, LlmError> {
+    let request = CompletionRequest::new(
+        model,
+        vec![Message::user(PROBE_PROMPT)],
+        TokenCount::new(PROBE_MAX_TOKENS),
+    );
+
+    let started = Instant::now();
+    let response = provider.complete(request).await?;
+    let latency = started.elapsed();
+
+    tracing::info!(
+        requested_model = model,
+        served_model = %response.model,
+        latency_ms = latency.as_millis(),
+        "LLM connectivity probe succeeded"
+    );
+
+    Ok(ConnectivityReport {
+        requested_model: model.to_string(),
+        served_model: response.model,
+        latency,
+        usage: response.usage,
+    })
+}
+
+#[cfg(test)]
+#[path = "probe_tests.rs"]
+mod tests;
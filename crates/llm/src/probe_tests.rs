@@ -0,0 +1,109 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use pipeline::{CompletionResponse, FinishReason, MessageRole};
+
+use super::*;
+
+/// Simulated network round trip of [`TinyProvider`].
+const ROUND_TRIP: Duration = Duration::from_millis(20);
+
+/// Provider answering every request with a one-token response from
+/// `served_model` after [`ROUND_TRIP`], or rejecting it as unauthenticated.
+struct TinyProvider {
+    served_model: &'static str,
+    reject: bool,
+    requests: Mutex<Vec<CompletionRequest>>,
+}
+
+impl TinyProvider {
+    fn serving(served_model: &'static str) -> Self {
+        Self {
+            served_model,
+            reject: false,
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn rejecting() -> Self {
+        Self {
+            reject: true,
+            ..Self::serving("unused")
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for TinyProvider {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        self.requests.lock().unwrap().push(request);
+        if self.reject {
+            return Err(LlmError::Authentication {
+                message: "invalid x-api-key".to_string(),
+            });
+        }
+        tokio::time::sleep(ROUND_TRIP).await;
+        Ok(CompletionResponse {
+            content: "p".to_string(),
+            model: self.served_model.to_string(),
+            usage: TokenUsage::new(TokenCount::new(8), TokenCount::new(1)),
+            finish_reason: FinishReason::MaxTokens,
+            provider_request_id: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_probe_connectivity_tiny_response_reports_served_model_and_usage() {
+    let provider = TinyProvider::serving("claude-sonnet-4-20250514");
+
+    let report = probe_connectivity(&provider, "claude-sonnet-4-latest")
+        .await
+        .unwrap();
+
+    assert_eq!(report.requested_model, "claude-sonnet-4-latest");
+    assert_eq!(report.served_model, "claude-sonnet-4-20250514");
+    assert_eq!(
+        report.usage,
+        TokenUsage::new(TokenCount::new(8), TokenCount::new(1))
+    );
+}
+
+#[tokio::test]
+async fn test_probe_connectivity_tiny_response_reports_measured_latency() {
+    let provider = TinyProvider::serving("claude-sonnet-4-20250514");
+
+    let report = probe_connectivity(&provider, "claude-sonnet-4-20250514")
+        .await
+        .unwrap();
+
+    assert!(report.latency >= ROUND_TRIP, "latency {:?}", report.latency);
+}
+
+#[tokio::test]
+async fn test_probe_connectivity_sends_one_token_ping() {
+    let provider = TinyProvider::serving("claude-sonnet-4-20250514");
+
+    probe_connectivity(&provider, "claude-sonnet-4-20250514")
+        .await
+        .unwrap();
+
+    let requests = provider.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].model, "claude-sonnet-4-20250514");
+    assert_eq!(requests[0].max_tokens, TokenCount::new(PROBE_MAX_TOKENS));
+    assert_eq!(requests[0].messages.len(), 1);
+    assert_eq!(requests[0].messages[0].role, MessageRole::User);
+    assert_eq!(requests[0].messages[0].content, PROBE_PROMPT);
+}
+
+#[tokio::test]
+async fn test_probe_connectivity_rejected_credentials_returns_authentication() {
+    let provider = TinyProvider::rejecting();
+
+    let error = probe_connectivity(&provider, "claude-sonnet-4-20250514")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, LlmError::Authentication { .. }));
+}
@@ -0,0 +1,49 @@
+//! Provider selection.
+//!
+//! [`LlmConfig`] is the `[llm]` table of `.cogworks/config.toml`. Its
+//! [`ProviderKind`] tells the CLI which [`pipeline::LlmProvider`] to
+//! construct.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/operations.md` §Budget Configuration.
+
+use serde::{Deserialize, Serialize};
+
+use crate::backoff::BackoffConfig;
+use crate::echo::EchoResponse;
+
+/// Which LLM provider a run uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderKind {
+    /// [`crate::anthropic::AnthropicProvider`].
+    #[default]
+    Anthropic,
+    /// [`crate::openai::OpenAiProvider`].
+    #[serde(rename = "openai")]
+    OpenAi,
+    /// [`crate::echo::EchoProvider`]; no network calls and no cost.
+    Echo,
+}
+
+impl ProviderKind {
+    /// Returns `true` if calls through this provider are billed.
+    pub fn is_billable(self) -> bool {
+        !matches!(self, ProviderKind::Echo)
+    }
+}
+
+/// The `[llm]` configuration table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LlmConfig {
+    /// Provider used for completions.
+    #[serde(default)]
+    pub provider: ProviderKind,
+    /// Reply of the echo provider; ignored by the others.
+    #[serde(default)]
+    pub echo: EchoResponse,
+    /// Retries of failed provider calls (`[llm.retry]`).
+    #[serde(default)]
+    pub retry: BackoffConfig,
+}
@@ -0,0 +1,103 @@
+//! Redacted DEBUG logging of provider requests and responses.
+//!
+//! Prompts are assembled from repository content and may carry secrets that
+//! were committed there. When DEBUG tracing is enabled, providers log each
+//! request and response through a [`Redactor`]: every match of its patterns
+//! in header values and bodies is replaced with [`REDACTED`] before the event
+//! is emitted. Headers named in [`ALWAYS_REDACTED_HEADERS`] are replaced
+//! whole whatever the patterns, so the provider's own API key never reaches
+//! a log.
+//!
+//! Nothing is rendered when DEBUG is off, and redaction applies only to what
+//! is logged: the request sent to the provider is unchanged.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §Request logging.
+
+use regex::Regex;
+
+use crate::transport::{HttpRequest, HttpResponse};
+
+/// Replacement for each redacted value.
+pub const REDACTED: &str = "***";
+
+/// Headers whose values are never logged, compared case-insensitively.
+pub const ALWAYS_REDACTED_HEADERS: &[&str] = &["x-api-key", "authorization"];
+
+/// Rewrites logged requests and responses so configured secrets do not
+/// appear in them.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    patterns: Vec<Regex>,
+}
+
+impl Redactor {
+    /// Creates a redactor replacing every match of `patterns`.
+    ///
+    /// With no patterns only [`ALWAYS_REDACTED_HEADERS`] are redacted.
+    pub fn new(patterns: Vec<Regex>) -> Self {
+        Self { patterns }
+    }
+
+    /// Returns `text` with every pattern match replaced by [`REDACTED`].
+    pub fn redact(&self, text: &str) -> String {
+        self.patterns
+            .iter()
+            .fold(text.to_string(), |text, pattern| {
+                pattern.replace_all(&text, REDACTED).into_owned()
+            })
+    }
+
+    /// Renders `headers` as `name: value` lines, replacing
+    /// [`ALWAYS_REDACTED_HEADERS`] whole and redacting the rest.
+    fn headers(&self, headers: &[(String, String)]) -> String {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                let always = ALWAYS_REDACTED_HEADERS
+                    .iter()
+                    .any(|header| header.eq_ignore_ascii_case(name));
+                let value = if always {
+                    REDACTED.to_string()
+                } else {
+                    self.redact(value)
+                };
+                format!("{name}: {value}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Emits `request` at DEBUG with its headers and body redacted.
+    pub fn log_request(&self, provider: &str, request: &HttpRequest) {
+        if !tracing::enabled!(tracing::Level::DEBUG) {
+            return;
+        }
+        tracing::debug!(
+            provider,
+            url = %request.url,
+            headers = %self.headers(&request.headers),
+            body = %self.redact(&request.body.to_string()),
+            "LLM provider request"
+        );
+    }
+
+    /// Emits `response` at DEBUG with its headers and body redacted.
+    pub fn log_response(&self, provider: &str, response: &HttpResponse) {
+        if !tracing::enabled!(tracing::Level::DEBUG) {
+            return;
+        }
+        tracing::debug!(
+            provider,
+            status = response.status,
+            headers = %self.headers(&response.headers),
+            body = %self.redact(&String::from_utf8_lossy(&response.body)),
+            "LLM provider response"
+        );
+    }
+}
+
+#[cfg(test)]
+#[path = "redaction_tests.rs"]
+mod tests;
@@ -0,0 +1,157 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use serde_json::json;
+use tracing::Level;
+
+use super::*;
+
+const SECRET: &str = "sk-live-4f9a8b7c6d5e";
+
+const API_KEY: &str = "sk-ant-test-key";
+
+fn secret_pattern() -> Regex {
+    Regex::new(r"sk-live-[A-Za-z0-9]+").unwrap()
+}
+
+/// Collects formatted tracing output in memory.
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Captured {
+    fn output(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+}
+
+impl io::Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runs `emit` with a subscriber enabled up to `level`, returning what it
+/// logged.
+fn capture(level: Level, emit: impl FnOnce()) -> String {
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_max_level(level)
+        .with_ansi(false)
+        .finish();
+    tracing::subscriber::with_default(subscriber, emit);
+    captured.output()
+}
+
+fn request() -> HttpRequest {
+    HttpRequest {
+        url: "https://llm.example/v1/messages".to_string(),
+        headers: vec![
+            ("x-api-key".to_string(), API_KEY.to_string()),
+            ("content-type".to_string(), "application/json".to_string()),
+        ],
+        body: json!({
+            "messages": [{
+                "role": "user",
+                "content": format!("The config file sets TOKEN={SECRET}; fix the loader.")
+            }]
+        }),
+    }
+}
+
+// ─── redact ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_redact_every_match_replaced() {
+    let redactor = Redactor::new(vec![secret_pattern()]);
+
+    let redacted = redactor.redact(&format!("a={SECRET} b={SECRET}"));
+
+    assert_eq!(redacted, "a=*** b=***");
+}
+
+#[test]
+fn test_redact_no_patterns_returns_text_unchanged() {
+    let text = format!("TOKEN={SECRET}");
+
+    assert_eq!(Redactor::default().redact(&text), text);
+}
+
+#[test]
+fn test_headers_api_key_redacted_without_patterns() {
+    let headers = vec![
+        ("X-Api-Key".to_string(), API_KEY.to_string()),
+        ("Authorization".to_string(), "Bearer abc".to_string()),
+        ("anthropic-version".to_string(), "2023-06-01".to_string()),
+    ];
+
+    let rendered = Redactor::default().headers(&headers);
+
+    assert_eq!(
+        rendered,
+        "X-Api-Key: ***\nAuthorization: ***\nanthropic-version: 2023-06-01"
+    );
+}
+
+// ─── log_request / log_response ─────────────────────────────────────────────
+
+#[test]
+fn test_log_request_prompt_with_secret_emits_event_without_it() {
+    let redactor = Redactor::new(vec![secret_pattern()]);
+
+    let output = capture(Level::DEBUG, || {
+        redactor.log_request("anthropic", &request())
+    });
+
+    assert!(output.contains("LLM provider request"));
+    assert!(output.contains("TOKEN=***"));
+    assert!(!output.contains(SECRET));
+    assert!(!output.contains(API_KEY));
+}
+
+#[test]
+fn test_log_request_without_patterns_still_redacts_api_key() {
+    let output = capture(Level::DEBUG, || {
+        Redactor::default().log_request("anthropic", &request())
+    });
+
+    assert!(output.contains("x-api-key: ***"));
+    assert!(!output.contains(API_KEY));
+}
+
+#[test]
+fn test_log_response_secret_in_body_redacted() {
+    let redactor = Redactor::new(vec![secret_pattern()]);
+    let response = HttpResponse {
+        status: 200,
+        headers: vec![("request-id".to_string(), "req_1".to_string())],
+        body: format!(r#"{{"content":[{{"type":"text","text":"Use {SECRET}"}}]}}"#).into_bytes(),
+    };
+
+    let output = capture(Level::DEBUG, || {
+        redactor.log_response("anthropic", &response)
+    });
+
+    assert!(output.contains("LLM provider response"));
+    assert!(output.contains("Use ***"));
+    assert!(!output.contains(SECRET));
+}
+
+#[test]
+fn test_log_request_debug_disabled_emits_nothing() {
+    let redactor = Redactor::new(vec![secret_pattern()]);
+
+    let output = capture(Level::INFO, || {
+        redactor.log_request("anthropic", &request())
+    });
+
+    assert!(output.is_empty());
+}
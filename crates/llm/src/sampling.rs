@@ -0,0 +1,185 @@
+//! Validated sampling parameters.
+//!
+//! [`LlmSamplingConfig`] holds the temperature, nucleus-sampling `top_p`, and
+//! generation cap used for a call. Ranges are checked when the config is built
+//! (or deserialised), so an out-of-range value is reported where it was
+//! written rather than as a provider `400` mid-run. [`LlmSamplingConfig::apply`]
+//! copies the values onto a [`CompletionRequest`], and each provider's
+//! `request_body` forwards them in its own wire format.
+//!
+//! | Parameter | Valid range | Unset |
+//! |-----------|-------------|-------|
+//! | `temperature` | `0.0..=2.0` | Provider default |
+//! | `top_p` | `0.0..=1.0` | Provider default |
+//! | `max_tokens` | `> 0` | — (required) |
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §Sampling parameters.
+
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use pipeline::{CompletionRequest, TokenCount};
+
+/// Accepted `temperature` values.
+pub const TEMPERATURE_RANGE: RangeInclusive<f64> = 0.0..=2.0;
+
+/// Accepted `top_p` values.
+pub const TOP_P_RANGE: RangeInclusive<f64> = 0.0..=1.0;
+
+/// A sampling parameter was outside its accepted range.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum SamplingConfigError {
+    /// `temperature` was outside [`TEMPERATURE_RANGE`] or not a number.
+    #[error("temperature must be between 0 and 2; got {value}")]
+    Temperature {
+        /// The rejected value.
+        value: f64,
+    },
+
+    /// `top_p` was outside [`TOP_P_RANGE`] or not a number.
+    #[error("top_p must be between 0 and 1; got {value}")]
+    TopP {
+        /// The rejected value.
+        value: f64,
+    },
+
+    /// `max_tokens` was zero.
+    #[error("max_tokens must be greater than 0")]
+    MaxTokens,
+}
+
+/// Sampling parameters whose ranges have been checked.
+///
+/// Fields are private so every value has passed validation; deserialising
+/// runs the same checks as the constructors.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "WireSampling", into = "WireSampling")]
+pub struct LlmSamplingConfig {
+    temperature: Option<f64>,
+    top_p: Option<f64>,
+    max_tokens: TokenCount,
+}
+
+/// Unvalidated form used for (de)serialisation.
+#[derive(Serialize, Deserialize)]
+struct WireSampling {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    top_p: Option<f64>,
+    max_tokens: TokenCount,
+}
+
+impl TryFrom<WireSampling> for LlmSamplingConfig {
+    type Error = SamplingConfigError;
+
+    fn try_from(wire: WireSampling) -> Result<Self, Self::Error> {
+        Self::new(wire.max_tokens)?.with_optional(wire.temperature, wire.top_p)
+    }
+}
+
+impl From<LlmSamplingConfig> for WireSampling {
+    fn from(config: LlmSamplingConfig) -> Self {
+        Self {
+            temperature: config.temperature,
+            top_p: config.top_p,
+            max_tokens: config.max_tokens,
+        }
+    }
+}
+
+impl LlmSamplingConfig {
+    /// Creates a config capping generation at `max_tokens`, with the
+    /// provider's default temperature and `top_p`.
+    ///
+    /// # Errors
+    ///
+    /// [`SamplingConfigError::MaxTokens`] — `max_tokens` is zero.
+    pub fn new(max_tokens: TokenCount) -> Result<Self, SamplingConfigError> {
+        if max_tokens.as_u64() == 0 {
+            return Err(SamplingConfigError::MaxTokens);
+        }
+        Ok(Self {
+            temperature: None,
+            top_p: None,
+            max_tokens,
+        })
+    }
+
+    /// Sets the sampling temperature.
+    ///
+    /// # Errors
+    ///
+    /// [`SamplingConfigError::Temperature`] — `temperature` is outside
+    /// [`TEMPERATURE_RANGE`].
+    pub fn with_temperature(mut self, temperature: f64) -> Result<Self, SamplingConfigError> {
+        if !TEMPERATURE_RANGE.contains(&temperature) {
+            return Err(SamplingConfigError::Temperature { value: temperature });
+        }
+        self.temperature = Some(temperature);
+        Ok(self)
+    }
+
+    /// Sets the nucleus-sampling probability mass.
+    ///
+    /// # Errors
+    ///
+    /// [`SamplingConfigError::TopP`] — `top_p` is outside [`TOP_P_RANGE`].
+    pub fn with_top_p(mut self, top_p: f64) -> Result<Self, SamplingConfigError> {
+        if !TOP_P_RANGE.contains(&top_p) {
+            return Err(SamplingConfigError::TopP { value: top_p });
+        }
+        self.top_p = Some(top_p);
+        Ok(self)
+    }
+
+    fn with_optional(
+        self,
+        temperature: Option<f64>,
+        top_p: Option<f64>,
+    ) -> Result<Self, SamplingConfigError> {
+        let config = match temperature {
+            Some(temperature) => self.with_temperature(temperature)?,
+            None => self,
+        };
+        match top_p {
+            Some(top_p) => config.with_top_p(top_p),
+            None => Ok(config),
+        }
+    }
+
+    /// The sampling temperature, or `None` for the provider default.
+    pub fn temperature(&self) -> Option<f64> {
+        self.temperature
+    }
+
+    /// The nucleus-sampling probability mass, or `None` for the provider
+    /// default.
+    pub fn top_p(&self) -> Option<f64> {
+        self.top_p
+    }
+
+    /// The generation cap.
+    pub fn max_tokens(&self) -> TokenCount {
+        self.max_tokens
+    }
+
+    /// Returns `request` with its `max_tokens`, `temperature`, and `top_p`
+    /// replaced by this config's values.
+    #[must_use]
+    pub fn apply(&self, mut request: CompletionRequest) -> CompletionRequest {
+        request.max_tokens = self.max_tokens;
+        request.temperature = self.temperature;
+        request.top_p = self.top_p;
+        request
+    }
+}
+
+#[cfg(test)]
+#[path = "sampling_tests.rs"]
+mod tests;
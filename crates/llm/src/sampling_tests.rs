@@ -0,0 +1,151 @@
+use pipeline::Message;
+use serde_json::json;
+
+use super::*;
+
+fn config() -> LlmSamplingConfig {
+    LlmSamplingConfig::new(TokenCount::new(512)).unwrap()
+}
+
+fn request() -> CompletionRequest {
+    CompletionRequest::new("model", vec![Message::user("hello")], TokenCount::new(100))
+}
+
+// ─── Construction ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_new_positive_max_tokens_uses_provider_defaults() {
+    let config = config();
+
+    assert_eq!(config.max_tokens(), TokenCount::new(512));
+    assert_eq!(config.temperature(), None);
+    assert_eq!(config.top_p(), None);
+}
+
+#[test]
+fn test_new_zero_max_tokens_returns_max_tokens_error() {
+    assert_eq!(
+        LlmSamplingConfig::new(TokenCount::new(0)),
+        Err(SamplingConfigError::MaxTokens)
+    );
+}
+
+#[test]
+fn test_with_temperature_range_bounds_are_accepted() {
+    for temperature in [0.0, 0.7, 2.0] {
+        let config = config().with_temperature(temperature).unwrap();
+
+        assert_eq!(config.temperature(), Some(temperature));
+    }
+}
+
+#[test]
+fn test_with_temperature_out_of_range_returns_temperature_error() {
+    for temperature in [-0.1, 2.01, f64::NAN, f64::INFINITY] {
+        let error = config().with_temperature(temperature).unwrap_err();
+
+        assert!(
+            matches!(error, SamplingConfigError::Temperature { .. }),
+            "{temperature}"
+        );
+    }
+}
+
+#[test]
+fn test_with_top_p_range_bounds_are_accepted() {
+    for top_p in [0.0, 0.9, 1.0] {
+        assert_eq!(config().with_top_p(top_p).unwrap().top_p(), Some(top_p));
+    }
+}
+
+#[test]
+fn test_with_top_p_out_of_range_returns_top_p_error() {
+    for top_p in [-0.5, 1.5, f64::NAN] {
+        let error = config().with_top_p(top_p).unwrap_err();
+
+        assert!(matches!(error, SamplingConfigError::TopP { .. }), "{top_p}");
+    }
+}
+
+#[test]
+fn test_sampling_config_error_display_names_parameter_and_value() {
+    assert_eq!(
+        SamplingConfigError::Temperature { value: 3.0 }.to_string(),
+        "temperature must be between 0 and 2; got 3"
+    );
+    assert_eq!(
+        SamplingConfigError::TopP { value: 1.5 }.to_string(),
+        "top_p must be between 0 and 1; got 1.5"
+    );
+}
+
+// ─── Deserialisation ────────────────────────────────────────────────────────
+
+#[test]
+fn test_deserialize_valid_config_returns_config() {
+    let config: LlmSamplingConfig = serde_json::from_value(json!({
+        "temperature": 0.2,
+        "top_p": 0.95,
+        "max_tokens": 1024
+    }))
+    .unwrap();
+
+    assert_eq!(config.temperature(), Some(0.2));
+    assert_eq!(config.top_p(), Some(0.95));
+    assert_eq!(config.max_tokens(), TokenCount::new(1024));
+}
+
+#[test]
+fn test_deserialize_out_of_range_temperature_is_rejected() {
+    let result: Result<LlmSamplingConfig, _> =
+        serde_json::from_value(json!({ "temperature": 5.0, "max_tokens": 1024 }));
+
+    assert!(result.is_err());
+}
+
+// ─── apply ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_apply_overwrites_request_sampling_fields() {
+    let config = config()
+        .with_temperature(0.3)
+        .unwrap()
+        .with_top_p(0.8)
+        .unwrap();
+
+    let request = config.apply(request());
+
+    assert_eq!(request.max_tokens, TokenCount::new(512));
+    assert_eq!(request.temperature, Some(0.3));
+    assert_eq!(request.top_p, Some(0.8));
+}
+
+#[test]
+fn test_apply_provider_defaults_clear_request_sampling_fields() {
+    let mut request = request();
+    request.temperature = Some(1.0);
+    request.top_p = Some(0.5);
+
+    let request = config().apply(request);
+
+    assert_eq!(request.temperature, None);
+    assert_eq!(request.top_p, None);
+}
+
+#[test]
+fn test_apply_each_provider_body_forwards_sampling_values() {
+    let request = config()
+        .with_temperature(0.3)
+        .unwrap()
+        .with_top_p(0.8)
+        .unwrap()
+        .apply(request());
+
+    for body in [
+        crate::anthropic::request_body(&request).unwrap(),
+        crate::openai::request_body(&request).unwrap(),
+    ] {
+        assert_eq!(body["temperature"], json!(0.3));
+        assert_eq!(body["top_p"], json!(0.8));
+    }
+}
@@ -0,0 +1,104 @@
+//! Server-sent event framing for streamed provider responses.
+//!
+//! A streamed body is a sequence of events, each a block of `field: value`
+//! lines terminated by a blank line. Network reads do not respect those
+//! boundaries: one read may carry several events, or stop in the middle of a
+//! line or of a multi-byte UTF-8 character. [`SseDecoder`] buffers raw bytes
+//! and only hands out complete events.
+//!
+//! Only the `event` and `data` fields are kept; multiple `data` lines are
+//! joined with `\n`, and comment lines (starting with `:`) are ignored.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §Streaming completions.
+
+/// One decoded server-sent event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    /// The `event` field; empty when the event has none.
+    pub event: String,
+    /// The `data` lines, joined with `\n`.
+    pub data: String,
+}
+
+/// Reassembles [`SseEvent`]s from arbitrarily split body bytes.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: Vec<u8>,
+}
+
+impl SseDecoder {
+    /// Creates a decoder with an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends bytes read from the body.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Returns the next complete event, or `None` if the buffer does not hold
+    /// one yet.
+    ///
+    /// Events without an `event` or `data` field (e.g. keep-alive comments)
+    /// are skipped.
+    pub fn next_event(&mut self) -> Option<SseEvent> {
+        loop {
+            let (end, separator) = frame_end(&self.buffer)?;
+            let frame: Vec<u8> = self.buffer.drain(..end + separator).take(end).collect();
+            if let Some(event) = parse_frame(&String::from_utf8_lossy(&frame)) {
+                return Some(event);
+            }
+        }
+    }
+
+    /// Returns `true` if bytes remain that do not yet form a complete event.
+    pub fn has_pending(&self) -> bool {
+        self.buffer.iter().any(|b| !b.is_ascii_whitespace())
+    }
+}
+
+/// Finds the first blank-line separator, returning the frame length and the
+/// separator length.
+fn frame_end(buffer: &[u8]) -> Option<(usize, usize)> {
+    (0..buffer.len()).find_map(|i| {
+        let rest = &buffer[i..];
+        if rest.starts_with(b"\r\n\r\n") {
+            Some((i, 4))
+        } else if rest.starts_with(b"\n\n") || rest.starts_with(b"\r\r") {
+            Some((i, 2))
+        } else {
+            None
+        }
+    })
+}
+
+fn parse_frame(frame: &str) -> Option<SseEvent> {
+    let mut event = String::new();
+    let mut data: Vec<&str> = Vec::new();
+    for line in frame.lines() {
+        if line.starts_with(':') {
+            continue;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => event = value.to_string(),
+            "data" => data.push(value),
+            _ => {}
+        }
+    }
+    if event.is_empty() && data.is_empty() {
+        return None;
+    }
+    Some(SseEvent {
+        event,
+        data: data.join("\n"),
+    })
+}
+
+#[cfg(test)]
+#[path = "sse_tests.rs"]
+mod tests;
@@ -0,0 +1,86 @@
+use super::*;
+
+fn event(event: &str, data: &str) -> SseEvent {
+    SseEvent {
+        event: event.to_string(),
+        data: data.to_string(),
+    }
+}
+
+#[test]
+fn test_next_event_empty_buffer_returns_none() {
+    let mut decoder = SseDecoder::new();
+
+    assert_eq!(decoder.next_event(), None);
+    assert!(!decoder.has_pending());
+}
+
+#[test]
+fn test_next_event_frame_split_across_pushes_waits_for_blank_line() {
+    let mut decoder = SseDecoder::new();
+    decoder.push(b"event: content_block_delta\ndata: {\"delta\":");
+
+    assert_eq!(decoder.next_event(), None);
+    assert!(decoder.has_pending());
+
+    decoder.push(b"{\"text\":\"Hi\"}}\n\n");
+
+    assert_eq!(
+        decoder.next_event(),
+        Some(event("content_block_delta", r#"{"delta":{"text":"Hi"}}"#))
+    );
+    assert!(!decoder.has_pending());
+}
+
+#[test]
+fn test_next_event_several_frames_in_one_push_returned_in_order() {
+    let mut decoder = SseDecoder::new();
+    decoder.push(
+        b"event: ping\ndata: {}\n\nevent: message_stop\ndata: {\"type\":\"message_stop\"}\n\n",
+    );
+
+    assert_eq!(decoder.next_event(), Some(event("ping", "{}")));
+    assert_eq!(
+        decoder.next_event(),
+        Some(event("message_stop", r#"{"type":"message_stop"}"#))
+    );
+    assert_eq!(decoder.next_event(), None);
+}
+
+#[test]
+fn test_next_event_crlf_separators_recognised() {
+    let mut decoder = SseDecoder::new();
+    decoder.push(b"event: ping\r\ndata: {}\r\n\r\n");
+
+    assert_eq!(decoder.next_event(), Some(event("ping", "{}")));
+}
+
+#[test]
+fn test_next_event_comment_only_frame_skipped() {
+    let mut decoder = SseDecoder::new();
+    decoder.push(b": keep-alive\n\nevent: ping\ndata: {}\n\n");
+
+    assert_eq!(decoder.next_event(), Some(event("ping", "{}")));
+}
+
+#[test]
+fn test_next_event_multiple_data_lines_joined_with_newline() {
+    let mut decoder = SseDecoder::new();
+    decoder.push(b"data: first\ndata: second\nid: 7\n\n");
+
+    assert_eq!(decoder.next_event(), Some(event("", "first\nsecond")));
+}
+
+#[test]
+fn test_next_event_multibyte_character_split_across_pushes_kept_intact() {
+    let bytes = "data: caf\u{e9}\n\n".as_bytes();
+    let split = bytes.iter().position(|b| *b == 0xC3).unwrap() + 1;
+    let mut decoder = SseDecoder::new();
+    decoder.push(&bytes[..split]);
+
+    assert_eq!(decoder.next_event(), None);
+
+    decoder.push(&bytes[split..]);
+
+    assert_eq!(decoder.next_event(), Some(event("", "caf\u{e9}")));
+}
@@ -0,0 +1,223 @@
+//! Local prompt token counting with a bundled byte-pair-encoding table.
+//!
+//! [`BpeTokenizer`] counts tokens the way tiktoken-style encoders do: the text
+//! is split into pieces (words with their leading space, runs of up to three
+//! digits, punctuation runs, whitespace), and each piece is encoded by
+//! repeatedly merging the adjacent pair of parts whose concatenation has the
+//! lowest rank in the table. Every single byte has a rank, so any text
+//! encodes.
+//!
+//! Tables are read in tiktoken's text format: one `<base64 token> <rank>` per
+//! line. [`BpeTokenizer::bundled`] loads [`BUNDLED_TABLE`], a byte-level table
+//! compiled into the crate, so counting never touches the network.
+//! [`AnthropicProvider`](crate::anthropic::AnthropicProvider) and
+//! [`OpenAiProvider`](crate::openai::OpenAiProvider) use it for
+//! [`LlmProvider::estimate_prompt_tokens`](pipeline::LlmProvider::estimate_prompt_tokens).
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §LlmProvider.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use regex::Regex;
+use thiserror::Error;
+
+use pipeline::{llm::CompletionRequest, TokenCount};
+
+/// The bundled table, in tiktoken's `<base64 token> <rank>` format.
+pub const BUNDLED_TABLE: &str = include_str!("tokenizer/cogworks_base.tiktoken");
+
+/// Splits text into pieces before merging.
+///
+/// The tiktoken pattern ends with `\s+(?!\S)|\s+`; `regex` has no
+/// look-ahead, so [`BpeTokenizer::pieces`] gives a whitespace run followed by
+/// a non-space character back its last character instead.
+const PIECE_PATTERN: &str = r"(?i:'s|'t|'re|'ve|'m|'ll|'d)|[^\r\n\p{L}\p{N}]?\p{L}+|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+";
+
+/// Errors loading a table.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TokenizerError {
+    /// A line is not `<base64 token> <rank>`.
+    #[error("invalid tokenizer table line {line}")]
+    InvalidLine {
+        /// 1-based line number.
+        line: usize,
+    },
+
+    /// A byte has no rank, so some text could not be encoded.
+    #[error("tokenizer table has no rank for byte {byte:#04x}")]
+    MissingByte {
+        /// The byte without a rank.
+        byte: u8,
+    },
+}
+
+/// Counts tokens with a byte-pair-encoding table.
+#[derive(Debug, Clone)]
+pub struct BpeTokenizer {
+    ranks: HashMap<Vec<u8>, u32>,
+    pattern: Regex,
+}
+
+impl BpeTokenizer {
+    /// Parses a table in tiktoken's `<base64 token> <rank>` format.
+    ///
+    /// Blank lines are ignored.
+    ///
+    /// # Errors
+    ///
+    /// - [`TokenizerError::InvalidLine`] — a line does not parse.
+    /// - [`TokenizerError::MissingByte`] — one of the 256 bytes has no rank.
+    pub fn from_tiktoken(table: &str) -> Result<Self, TokenizerError> {
+        let mut ranks = HashMap::new();
+        for (index, line) in table.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || TokenizerError::InvalidLine { line: index + 1 };
+            let (token, rank) = line.split_once(' ').ok_or_else(invalid)?;
+            let token = decode_base64(token).ok_or_else(invalid)?;
+            let rank = rank.parse::<u32>().map_err(|_| invalid())?;
+            ranks.insert(token, rank);
+        }
+        if let Some(byte) = (0..=u8::MAX).find(|byte| !ranks.contains_key(&vec![*byte])) {
+            return Err(TokenizerError::MissingByte { byte });
+        }
+        Ok(Self {
+            ranks,
+            pattern: Regex::new(PIECE_PATTERN).expect("piece pattern is valid"),
+        })
+    }
+
+    /// The tokenizer over [`BUNDLED_TABLE`], parsed on first use.
+    pub fn bundled() -> &'static Self {
+        static BUNDLED: OnceLock<BpeTokenizer> = OnceLock::new();
+        BUNDLED.get_or_init(|| {
+            Self::from_tiktoken(BUNDLED_TABLE).expect("bundled tokenizer table is valid")
+        })
+    }
+
+    /// Number of tokens in the table.
+    pub fn vocabulary_size(&self) -> usize {
+        self.ranks.len()
+    }
+
+    /// Number of tokens `text` encodes to.
+    pub fn count(&self, text: &str) -> usize {
+        self.pieces(text)
+            .map(|piece| self.count_piece(piece.as_bytes()))
+            .sum()
+    }
+
+    /// Number of prompt tokens in `request`: its system segments and message
+    /// contents, each counted separately.
+    pub fn count_request(&self, request: &CompletionRequest) -> TokenCount {
+        let tokens: usize = request
+            .system
+            .iter()
+            .map(|segment| self.count(&segment.content))
+            .chain(
+                request
+                    .messages
+                    .iter()
+                    .map(|message| self.count(&message.content)),
+            )
+            .sum();
+        TokenCount::new(u64::try_from(tokens).unwrap_or(u64::MAX))
+    }
+
+    /// Splits `text` into the pieces encoded independently.
+    fn pieces<'t>(&'t self, text: &'t str) -> impl Iterator<Item = &'t str> + 't {
+        let mut start = 0;
+        std::iter::from_fn(move || {
+            let found = self.pattern.find_at(text, start)?;
+            let mut end = found.end();
+            let piece = found.as_str();
+            // `\s+(?!\S)`: leave the last whitespace character to prefix the
+            // word that follows.
+            if end < text.len()
+                && piece.chars().count() > 1
+                && piece.chars().all(char::is_whitespace)
+            {
+                let next_is_space = text[end..].chars().next().is_some_and(char::is_whitespace);
+                if !next_is_space {
+                    end -= piece.chars().last().map_or(0, char::len_utf8);
+                }
+            }
+            start = end;
+            Some(&text[found.start()..end])
+        })
+    }
+
+    /// Number of tokens `piece` merges into.
+    fn count_piece(&self, piece: &[u8]) -> usize {
+        if piece.is_empty() {
+            return 0;
+        }
+        if self.ranks.contains_key(piece) {
+            return 1;
+        }
+        // Part boundaries: part `i` is `piece[bounds[i]..bounds[i + 1]]`.
+        let mut bounds: Vec<usize> = (0..=piece.len()).collect();
+        loop {
+            let best = (0..bounds.len().saturating_sub(2))
+                .filter_map(|i| {
+                    self.ranks
+                        .get(&piece[bounds[i]..bounds[i + 2]])
+                        .map(|rank| (*rank, i))
+                })
+                .min();
+            match best {
+                Some((_, i)) => {
+                    bounds.remove(i + 1);
+                }
+                None => return bounds.len() - 1,
+            }
+        }
+    }
+}
+
+/// Decodes standard, padded base64; `None` if `text` is not valid.
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    fn value(symbol: u8) -> Option<u32> {
+        match symbol {
+            b'A'..=b'Z' => Some(u32::from(symbol - b'A')),
+            b'a'..=b'z' => Some(u32::from(symbol - b'a') + 26),
+            b'0'..=b'9' => Some(u32::from(symbol - b'0') + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    let symbols = text.as_bytes();
+    if symbols.is_empty() || symbols.len() % 4 != 0 {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(symbols.len() / 4 * 3);
+    for chunk in symbols.chunks(4) {
+        let padding = chunk
+            .iter()
+            .rev()
+            .take_while(|symbol| **symbol == b'=')
+            .count();
+        if padding > 2 {
+            return None;
+        }
+        let mut group = 0;
+        for symbol in &chunk[..4 - padding] {
+            group = (group << 6) | value(*symbol)?;
+        }
+        group <<= 6 * padding;
+        let decoded = [(group >> 16) as u8, (group >> 8) as u8, group as u8];
+        bytes.extend_from_slice(&decoded[..3 - padding]);
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+#[path = "tokenizer_tests.rs"]
+mod tests;
@@ -0,0 +1,157 @@
+use pipeline::{Message, SystemLayer, SystemSegment};
+
+use super::*;
+
+/// Prompt-style text with its token count under OpenAI's `cl100k_base`
+/// encoding: one token per word (with its leading space) and per punctuation
+/// mark.
+const FIXTURES: &[(&str, usize)] = &[
+    ("hello world", 2),
+    (
+        "You are a careful software engineer. Read the issue below and write a short plan \
+         that lists the files you will change and the tests you will add.",
+        30,
+    ),
+    (
+        "Review the pull request and report any change that does not match the design. \
+         List each problem with the file name and line number.",
+        26,
+    ),
+    (
+        "If the tests fail, explain why and suggest a fix. Keep the answer short and do not \
+         repeat the question.",
+        23,
+    ),
+];
+
+/// Allowed difference from a fixture's count, as a fraction of it.
+const TOLERANCE: f64 = 0.1;
+
+fn table(lines: &[&str]) -> String {
+    let mut table: Vec<String> = (0..=u8::MAX)
+        .map(|byte| {
+            let token = [byte];
+            format!("{} {byte}", encode_base64(&token))
+        })
+        .collect();
+    table.extend(lines.iter().map(|line| (*line).to_string()));
+    table.join("\n")
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    const SYMBOLS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut text = String::new();
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | u32::from(*byte) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(char::from(SYMBOLS[(group >> (18 - 6 * i) & 0x3f) as usize]));
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+// ─── Table loading ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_bundled_table_parses_with_every_byte_ranked() {
+    let tokenizer = BpeTokenizer::bundled();
+
+    assert!(tokenizer.vocabulary_size() > 256);
+}
+
+#[test]
+fn test_from_tiktoken_missing_byte_returns_missing_byte() {
+    let result = BpeTokenizer::from_tiktoken("AA== 0");
+
+    assert!(matches!(
+        result,
+        Err(TokenizerError::MissingByte { byte: 1 })
+    ));
+}
+
+#[test]
+fn test_from_tiktoken_malformed_line_returns_its_line_number() {
+    let result = BpeTokenizer::from_tiktoken(&table(&["not-base64! 256"]));
+
+    assert_eq!(
+        result.unwrap_err(),
+        TokenizerError::InvalidLine { line: 257 }
+    );
+}
+
+#[test]
+fn test_decode_base64_padded_token_round_trips() {
+    assert_eq!(decode_base64("aGk="), Some(b"hi".to_vec()));
+    assert_eq!(decode_base64("IHRoZQ=="), Some(b" the".to_vec()));
+    assert_eq!(decode_base64("abc"), None);
+}
+
+// ─── Counting ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_count_merges_by_lowest_rank() {
+    // "ab" ranks before "bc", so "abc" is "ab" + "c" and no "abc" token forms.
+    let tokenizer =
+        BpeTokenizer::from_tiktoken(&table(&["YWI= 256", "YmM= 257", "YmNk 258"])).unwrap();
+
+    assert_eq!(tokenizer.count("abc"), 2);
+    assert_eq!(tokenizer.count("bcd"), 1);
+}
+
+#[test]
+fn test_count_single_bytes_without_merges_counts_each_byte() {
+    let tokenizer = BpeTokenizer::from_tiktoken(&table(&[])).unwrap();
+
+    assert_eq!(tokenizer.count("héllo"), 6);
+    assert_eq!(tokenizer.count(""), 0);
+}
+
+#[test]
+fn test_pieces_whitespace_run_leaves_last_space_to_next_word() {
+    let tokenizer = BpeTokenizer::from_tiktoken(&table(&[])).unwrap();
+
+    let pieces: Vec<&str> = tokenizer.pieces("a   b\n").collect();
+
+    assert_eq!(pieces, vec!["a", "  ", " b", "\n"]);
+}
+
+#[test]
+fn test_count_fixtures_within_tolerance_of_reference_counts() {
+    let tokenizer = BpeTokenizer::bundled();
+
+    for (text, expected) in FIXTURES {
+        let counted = tokenizer.count(text);
+        let allowed = (*expected as f64 * TOLERANCE).ceil() as usize;
+        assert!(
+            counted.abs_diff(*expected) <= allowed,
+            "{counted} tokens for {text:?}, expected {expected} ± {allowed}"
+        );
+    }
+}
+
+#[test]
+fn test_count_request_sums_system_segments_and_messages() {
+    let tokenizer = BpeTokenizer::bundled();
+    let mut request = CompletionRequest::new(
+        "model",
+        vec![Message::user(FIXTURES[1].0), Message::user(FIXTURES[2].0)],
+        TokenCount::new(100),
+    );
+    request.system = vec![SystemSegment::new(SystemLayer::Task, FIXTURES[3].0)];
+
+    let expected = [1, 2, 3]
+        .iter()
+        .map(|i| tokenizer.count(FIXTURES[*i].0))
+        .sum::<usize>();
+
+    assert_eq!(
+        tokenizer.count_request(&request),
+        TokenCount::new(expected as u64)
+    );
+}
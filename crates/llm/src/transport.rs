@@ -0,0 +1,394 @@
+//! HTTP transport seam shared by the LLM providers.
+//!
+//! Providers format requests and parse responses; they never talk to the
+//! network directly. Every call goes through an [`LlmTransport`] held as
+//! `Arc<dyn LlmTransport>`, so the HTTP client can be swapped without touching
+//! provider code:
+//!
+//! | Transport | Use |
+//! |-----------|-----|
+//! | [`ReqwestTransport`] | Production; a shared `reqwest::Client` |
+//! | `ScriptedTransport` | Tests; replays queued responses (feature `mock-transport`) |
+//!
+//! Transports only move bytes. Mapping HTTP status codes to [`LlmError`]
+//! variants is shared by all providers through [`status_error`].
+//!
+//! A [`CompletionRequest::request_id`](pipeline::CompletionRequest::request_id)
+//! is sent in the header named by the provider's request-ID setting (default
+//! [`DEFAULT_REQUEST_ID_HEADER`]); the ID the provider returns is copied into
+//! [`CompletionResponse::provider_request_id`](pipeline::CompletionResponse::provider_request_id)
+//! so audit records can name both.
+//!
+//! Streamed completions use [`LlmTransport::post_json_streaming`], which hands
+//! back the body as a [`ResponseBody`] read chunk by chunk. Transports that
+//! cannot stream inherit a default that reads the whole body and returns it
+//! as a single chunk.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §LLM transport.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use tracing::instrument;
+
+use pipeline::llm::LlmError;
+
+/// Header carrying the client-generated request ID unless a provider is
+/// configured otherwise.
+pub const DEFAULT_REQUEST_ID_HEADER: &str = "x-client-request-id";
+
+// ─── Request / response ─────────────────────────────────────────────────────
+
+/// A JSON `POST` request to a provider endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    /// Absolute endpoint URL.
+    pub url: String,
+    /// Request headers as `(name, value)` pairs.
+    pub headers: Vec<(String, String)>,
+    /// JSON request body.
+    pub body: JsonValue,
+}
+
+/// A raw provider response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers as `(name, value)` pairs.
+    pub headers: Vec<(String, String)>,
+    /// Response body bytes.
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Returns the first header named `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns `true` for a 2xx status.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Maps a non-success response to the matching [`LlmError`].
+///
+/// Returns `None` for a 2xx response.
+///
+/// | Status | Error |
+/// |--------|-------|
+/// | 401, 403 | [`LlmError::Authentication`] |
+/// | 429 | [`LlmError::RateLimited`] with `retry-after` seconds, if present |
+/// | 408, 5xx (including Anthropic's 529 overloaded) | [`LlmError::Transient`] |
+/// | Other 4xx | [`LlmError::InvalidRequest`] |
+pub fn status_error(response: &HttpResponse) -> Option<LlmError> {
+    if response.is_success() {
+        return None;
+    }
+    let message = format!(
+        "HTTP {}: {}",
+        response.status,
+        String::from_utf8_lossy(&response.body)
+    );
+    Some(match response.status {
+        401 | 403 => LlmError::Authentication { message },
+        429 => LlmError::RateLimited {
+            retry_after: response
+                .header("retry-after")
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(Duration::from_secs),
+        },
+        400..=499 if response.status != 408 => LlmError::InvalidRequest { message },
+        _ => LlmError::Transient { message },
+    })
+}
+
+// ─── Streaming response ─────────────────────────────────────────────────────
+
+/// A response body read incrementally.
+#[async_trait]
+pub trait ResponseBody: Send {
+    /// Returns the next chunk of bytes, or `Ok(None)` at the end of the body.
+    ///
+    /// Chunk boundaries are arbitrary; they need not align with any framing.
+    ///
+    /// # Errors
+    ///
+    /// - [`LlmError::Transient`] — the connection failed mid-body.
+    async fn next_bytes(&mut self) -> Result<Option<Vec<u8>>, LlmError>;
+}
+
+/// A provider response whose body has not been read yet.
+pub struct StreamingResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers as `(name, value)` pairs.
+    pub headers: Vec<(String, String)>,
+    /// The body, read on demand.
+    pub body: Box<dyn ResponseBody>,
+}
+
+impl StreamingResponse {
+    /// Returns the first header named `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Reads the rest of the body into an [`HttpResponse`], e.g. to map an
+    /// error status with [`status_error`].
+    ///
+    /// # Errors
+    ///
+    /// - [`LlmError::Transient`] — the body could not be read.
+    pub async fn into_buffered(mut self) -> Result<HttpResponse, LlmError> {
+        let mut body = Vec::new();
+        while let Some(chunk) = self.body.next_bytes().await? {
+            body.extend_from_slice(&chunk);
+        }
+        Ok(HttpResponse {
+            status: self.status,
+            headers: self.headers,
+            body,
+        })
+    }
+}
+
+impl std::fmt::Debug for StreamingResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A body already held in memory, returned as one chunk.
+struct BufferedBody(Option<Vec<u8>>);
+
+#[async_trait]
+impl ResponseBody for BufferedBody {
+    async fn next_bytes(&mut self) -> Result<Option<Vec<u8>>, LlmError> {
+        Ok(self.0.take())
+    }
+}
+
+// ─── Trait ──────────────────────────────────────────────────────────────────
+
+/// Sends provider requests over HTTP.
+#[async_trait]
+pub trait LlmTransport: Send + Sync {
+    /// Sends `request` and returns the response, whatever its status.
+    ///
+    /// # Errors
+    ///
+    /// - [`LlmError::Transient`] — the request could not be sent or the
+    ///   response could not be read (connection, TLS, or timeout failure).
+    async fn post_json(&self, request: HttpRequest) -> Result<HttpResponse, LlmError>;
+
+    /// Sends `request` and returns the response as soon as its headers
+    /// arrive, whatever its status; the body is read through
+    /// [`StreamingResponse::body`].
+    ///
+    /// The default calls [`LlmTransport::post_json`] and returns the whole
+    /// body as a single chunk.
+    ///
+    /// # Errors
+    ///
+    /// - [`LlmError::Transient`] — the request could not be sent.
+    async fn post_json_streaming(
+        &self,
+        request: HttpRequest,
+    ) -> Result<StreamingResponse, LlmError> {
+        let response = self.post_json(request).await?;
+        Ok(StreamingResponse {
+            status: response.status,
+            headers: response.headers,
+            body: Box::new(BufferedBody(Some(response.body))),
+        })
+    }
+}
+
+// ─── reqwest ────────────────────────────────────────────────────────────────
+
+/// [`LlmTransport`] backed by a shared `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a transport with a default `reqwest::Client`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a transport over an existing client (e.g. one with custom
+    /// timeouts or proxy settings).
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl LlmTransport for ReqwestTransport {
+    #[instrument(skip(self, request), fields(url = %request.url))]
+    async fn post_json(&self, request: HttpRequest) -> Result<HttpResponse, LlmError> {
+        let transient = |e: reqwest::Error| LlmError::Transient {
+            message: e.to_string(),
+        };
+        let body = serde_json::to_vec(&request.body).map_err(|e| LlmError::InvalidRequest {
+            message: format!("failed to serialise request body: {e}"),
+        })?;
+
+        let mut builder = self.client.post(&request.url).body(body);
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        let response = builder.send().await.map_err(transient)?;
+
+        let status = response.status().as_u16();
+        let headers = response_headers(&response);
+        let body = response.bytes().await.map_err(transient)?.to_vec();
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    #[instrument(skip(self, request), fields(url = %request.url))]
+    async fn post_json_streaming(
+        &self,
+        request: HttpRequest,
+    ) -> Result<StreamingResponse, LlmError> {
+        let body = serde_json::to_vec(&request.body).map_err(|e| LlmError::InvalidRequest {
+            message: format!("failed to serialise request body: {e}"),
+        })?;
+
+        let mut builder = self.client.post(&request.url).body(body);
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        let response = builder.send().await.map_err(|e| LlmError::Transient {
+            message: e.to_string(),
+        })?;
+
+        Ok(StreamingResponse {
+            status: response.status().as_u16(),
+            headers: response_headers(&response),
+            body: Box::new(ReqwestBody(response)),
+        })
+    }
+}
+
+fn response_headers(response: &reqwest::Response) -> Vec<(String, String)> {
+    response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.as_str().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// A `reqwest` response body read chunk by chunk as it arrives.
+struct ReqwestBody(reqwest::Response);
+
+#[async_trait]
+impl ResponseBody for ReqwestBody {
+    async fn next_bytes(&mut self) -> Result<Option<Vec<u8>>, LlmError> {
+        self.0
+            .chunk()
+            .await
+            .map(|chunk| chunk.map(|bytes| bytes.to_vec()))
+            .map_err(|e| LlmError::Transient {
+                message: e.to_string(),
+            })
+    }
+}
+
+// ─── Scripted ───────────────────────────────────────────────────────────────
+
+/// [`LlmTransport`] that replays queued responses instead of sending requests.
+///
+/// Every request is recorded so tests can assert on the URL, headers, and body
+/// a provider produced. Once the queue is empty, further calls return
+/// [`LlmError::Transient`].
+#[cfg(any(test, feature = "mock-transport"))]
+#[derive(Debug, Default)]
+pub struct ScriptedTransport {
+    responses: std::sync::Mutex<std::collections::VecDeque<Result<HttpResponse, LlmError>>>,
+    requests: std::sync::Mutex<Vec<HttpRequest>>,
+}
+
+#[cfg(any(test, feature = "mock-transport"))]
+impl ScriptedTransport {
+    /// Creates a transport with no queued responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues `response` to be returned by the next unanswered call.
+    pub fn push(&self, response: Result<HttpResponse, LlmError>) {
+        self.responses
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push_back(response);
+    }
+
+    /// Queues a response with `status` and a JSON `body`.
+    pub fn push_json(&self, status: u16, body: &JsonValue) {
+        self.push(Ok(HttpResponse {
+            status,
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: body.to_string().into_bytes(),
+        }));
+    }
+
+    /// Returns every request received so far, oldest first.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[cfg(any(test, feature = "mock-transport"))]
+#[async_trait]
+impl LlmTransport for ScriptedTransport {
+    async fn post_json(&self, request: HttpRequest) -> Result<HttpResponse, LlmError> {
+        self.requests
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(request);
+        self.responses
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .pop_front()
+            .unwrap_or_else(|| {
+                Err(LlmError::Transient {
+                    message: "scripted transport has no queued response".to_string(),
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+#[path = "transport_tests.rs"]
+mod tests;
@@ -0,0 +1,153 @@
+use serde_json::json;
+
+use super::*;
+
+fn response(status: u16, headers: &[(&str, &str)], body: &str) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+        body: body.as_bytes().to_vec(),
+    }
+}
+
+fn request() -> HttpRequest {
+    HttpRequest {
+        url: "https://llm.example/v1/messages".to_string(),
+        headers: Vec::new(),
+        body: json!({ "model": "claude-test" }),
+    }
+}
+
+// ─── HttpResponse ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_header_mixed_case_name_found() {
+    let response = response(200, &[("Request-Id", "req_123")], "");
+
+    assert_eq!(response.header("request-id"), Some("req_123"));
+    assert_eq!(response.header("x-missing"), None);
+}
+
+// ─── status_error ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_status_error_success_returns_none() {
+    assert!(status_error(&response(200, &[], "{}")).is_none());
+    assert!(status_error(&response(204, &[], "")).is_none());
+}
+
+#[test]
+fn test_status_error_unauthorized_returns_authentication() {
+    let error = status_error(&response(401, &[], "bad key")).unwrap();
+
+    assert!(matches!(
+        error,
+        LlmError::Authentication { message } if message == "HTTP 401: bad key"
+    ));
+}
+
+#[test]
+fn test_status_error_rate_limited_reads_retry_after() {
+    let error = status_error(&response(429, &[("retry-after", "7")], "")).unwrap();
+
+    assert!(matches!(
+        error,
+        LlmError::RateLimited { retry_after: Some(after) } if after == Duration::from_secs(7)
+    ));
+}
+
+#[test]
+fn test_status_error_rate_limited_without_header_has_no_delay() {
+    let error = status_error(&response(429, &[], "")).unwrap();
+
+    assert!(matches!(error, LlmError::RateLimited { retry_after: None }));
+}
+
+#[test]
+fn test_status_error_timeout_and_overloaded_are_transient() {
+    for status in [408, 500, 529] {
+        let error = status_error(&response(status, &[], "")).unwrap();
+
+        assert!(matches!(error, LlmError::Transient { .. }), "{status}");
+    }
+}
+
+#[test]
+fn test_status_error_bad_request_returns_invalid_request() {
+    let error = status_error(&response(400, &[], "max_tokens required")).unwrap();
+
+    assert!(matches!(error, LlmError::InvalidRequest { .. }));
+}
+
+// ─── Streaming ──────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_post_json_streaming_default_returns_whole_body_as_one_chunk() {
+    let transport = ScriptedTransport::new();
+    transport.push(Ok(response(
+        200,
+        &[("request-id", "req_1")],
+        "data: hi\n\n",
+    )));
+
+    let mut streaming = transport.post_json_streaming(request()).await.unwrap();
+
+    assert_eq!(streaming.status, 200);
+    assert_eq!(streaming.header("Request-ID"), Some("req_1"));
+    assert_eq!(
+        streaming.body.next_bytes().await.unwrap(),
+        Some(b"data: hi\n\n".to_vec())
+    );
+    assert_eq!(streaming.body.next_bytes().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_into_buffered_reads_remaining_body() {
+    let transport = ScriptedTransport::new();
+    transport.push(Ok(response(503, &[], "overloaded")));
+
+    let buffered = transport
+        .post_json_streaming(request())
+        .await
+        .unwrap()
+        .into_buffered()
+        .await
+        .unwrap();
+
+    assert_eq!(buffered, response(503, &[], "overloaded"));
+    assert!(matches!(
+        status_error(&buffered),
+        Some(LlmError::Transient { .. })
+    ));
+}
+
+// ─── ScriptedTransport ──────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_scripted_transport_records_requests_and_replays_in_order() {
+    let transport = ScriptedTransport::new();
+    transport.push_json(200, &json!({ "n": 1 }));
+    transport.push(Err(LlmError::Transient {
+        message: "reset".to_string(),
+    }));
+
+    let first = transport.post_json(request()).await.unwrap();
+    let second = transport.post_json(request()).await;
+
+    assert_eq!(first.status, 200);
+    assert_eq!(first.header("content-type"), Some("application/json"));
+    assert!(matches!(second, Err(LlmError::Transient { message }) if message == "reset"));
+    assert_eq!(transport.requests(), vec![request(), request()]);
+}
+
+#[tokio::test]
+async fn test_scripted_transport_empty_queue_returns_transient() {
+    let transport = ScriptedTransport::new();
+
+    let result = transport.post_json(request()).await;
+
+    assert!(matches!(result, Err(LlmError::Transient { .. })));
+}
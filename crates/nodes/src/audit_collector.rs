@@ -0,0 +1,240 @@
+//! Streaming audit events to an external collector.
+//!
+//! The audit trail on GitHub is written per step and read after the fact. For
+//! real-time monitoring, [`CollectorAuditStore`] wraps the [`AuditStore`] in
+//! use and also hands every event to a bounded queue. A
+//! [`CollectorForwarder`], spawned by the caller, drains the queue and POSTs
+//! each event to the configured collector through a [`CollectorTransport`].
+//!
+//! Delivery is fire-and-forget. Recording an event never waits for the
+//! collector: a full queue drops the event with a warning. A failed POST is
+//! retried up to [`AuditCollectorConfig::max_attempts`] times with a doubling
+//! delay, then dropped with a warning. Nothing the collector does can fail
+//! the run or delay the write to the wrapped store.
+//!
+//! ```toml
+//! [audit.collector]
+//! endpoint = "https://collector.example.com/cogworks/audit"
+//! max_attempts = 3        # default
+//! queue_capacity = 256    # default
+//! ```
+//!
+//! ## Specification
+//!
+//! See `docs/spec/operations.md` §Audit Trail on GitHub.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tracing::instrument;
+
+use pipeline::{
+    audit::{AuditEvent, AuditStore, AuditStoreError, PipelineSummary},
+    PipelineRunId, WorkItemId,
+};
+
+/// POST attempts per event when not configured.
+pub const DEFAULT_COLLECTOR_MAX_ATTEMPTS: u32 = 3;
+
+/// Events buffered for the collector when not configured.
+pub const DEFAULT_COLLECTOR_QUEUE_CAPACITY: usize = 256;
+
+/// Wait before the first retry of a failed POST; doubled for each later one.
+pub const COLLECTOR_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// The `[audit.collector]` configuration table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditCollectorConfig {
+    /// URL each event is POSTed to.
+    pub endpoint: String,
+    /// POST attempts per event, including the first.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Events held while the collector is slow; more are dropped.
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+}
+
+fn default_max_attempts() -> u32 {
+    DEFAULT_COLLECTOR_MAX_ATTEMPTS
+}
+
+fn default_queue_capacity() -> usize {
+    DEFAULT_COLLECTOR_QUEUE_CAPACITY
+}
+
+/// One audit event as sent to the collector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectedAuditEvent {
+    /// The run that produced the event.
+    pub run_id: PipelineRunId,
+    /// The work item the run is acting on.
+    pub work_item_id: WorkItemId,
+    /// The event itself, tagged by `kind`.
+    pub event: AuditEvent,
+}
+
+/// Errors returned by a [`CollectorTransport`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum CollectorError {
+    /// The collector could not be reached.
+    #[error("audit collector unreachable: {message}")]
+    Unreachable {
+        /// Human-readable description of the failure.
+        message: String,
+    },
+
+    /// The collector answered with a non-success status.
+    #[error("audit collector rejected the event with HTTP {status}")]
+    Rejected {
+        /// HTTP status code.
+        status: u16,
+    },
+}
+
+impl CollectorError {
+    /// Returns `true` if a later attempt may succeed: the collector was
+    /// unreachable, overloaded (429), or failed (5xx).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Unreachable { .. } => true,
+            Self::Rejected { status } => *status == 429 || *status >= 500,
+        }
+    }
+}
+
+/// Sends one event to the collector.
+///
+/// The `cli` crate provides the HTTP implementation; tests supply their own.
+#[async_trait]
+pub trait CollectorTransport: Send + Sync {
+    /// POST `event` as JSON to `endpoint`.
+    ///
+    /// # Errors
+    ///
+    /// - [`CollectorError::Unreachable`] — the request could not be sent.
+    /// - [`CollectorError::Rejected`] — the collector returned a non-2xx
+    ///   status.
+    async fn post(&self, endpoint: &str, event: &CollectedAuditEvent)
+        -> Result<(), CollectorError>;
+}
+
+// ─── Store ──────────────────────────────────────────────────────────────────
+
+/// An [`AuditStore`] that also queues every event for the collector.
+pub struct CollectorAuditStore {
+    inner: Arc<dyn AuditStore>,
+    queue: mpsc::Sender<CollectedAuditEvent>,
+}
+
+impl CollectorAuditStore {
+    /// Wraps `inner`, returning the store and the forwarder that delivers
+    /// queued events through `transport`.
+    ///
+    /// The caller spawns [`CollectorForwarder::run`]; it ends once the store
+    /// is dropped and the queue is drained.
+    pub fn new(
+        inner: Arc<dyn AuditStore>,
+        transport: Arc<dyn CollectorTransport>,
+        config: AuditCollectorConfig,
+    ) -> (Self, CollectorForwarder) {
+        let (queue, events) = mpsc::channel(config.queue_capacity.max(1));
+        (
+            Self { inner, queue },
+            CollectorForwarder {
+                events,
+                transport,
+                config,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl AuditStore for CollectorAuditStore {
+    async fn record_event(
+        &self,
+        run_id: PipelineRunId,
+        work_item_id: WorkItemId,
+        event: AuditEvent,
+    ) -> Result<(), AuditStoreError> {
+        let collected = CollectedAuditEvent {
+            run_id,
+            work_item_id,
+            event: event.clone(),
+        };
+        if let Err(error) = self.queue.try_send(collected) {
+            tracing::warn!(
+                kind = ?event.kind(),
+                %error,
+                "audit collector queue unavailable; event not streamed"
+            );
+        }
+        self.inner.record_event(run_id, work_item_id, event).await
+    }
+
+    async fn write_summary(&self, summary: &PipelineSummary) -> Result<(), AuditStoreError> {
+        self.inner.write_summary(summary).await
+    }
+}
+
+// ─── Forwarder ──────────────────────────────────────────────────────────────
+
+/// Drains the [`CollectorAuditStore`] queue into the collector.
+pub struct CollectorForwarder {
+    events: mpsc::Receiver<CollectedAuditEvent>,
+    transport: Arc<dyn CollectorTransport>,
+    config: AuditCollectorConfig,
+}
+
+impl CollectorForwarder {
+    /// Delivers queued events in order until the store is dropped.
+    pub async fn run(mut self) {
+        while let Some(event) = self.events.recv().await {
+            deliver(
+                self.transport.as_ref(),
+                &self.config.endpoint,
+                &event,
+                self.config.max_attempts,
+            )
+            .await;
+        }
+        tracing::debug!("audit collector forwarder stopped");
+    }
+}
+
+/// POSTs `event`, retrying retryable failures up to `max_attempts` times in
+/// total. Returns `true` if the collector accepted it; a dropped event is
+/// logged, never returned as an error.
+#[instrument(skip(transport, event), fields(kind = ?event.event.kind(), run_id = %event.run_id))]
+pub async fn deliver(
+    transport: &dyn CollectorTransport,
+    endpoint: &str,
+    event: &CollectedAuditEvent,
+    max_attempts: u32,
+) -> bool {
+    let mut delay = COLLECTOR_RETRY_BASE_DELAY;
+    for attempt in 1..=max_attempts.max(1) {
+        match transport.post(endpoint, event).await {
+            Ok(()) => return true,
+            Err(error) if error.is_retryable() && attempt < max_attempts => {
+                tracing::debug!(attempt, %error, ?delay, "audit collector POST failed; retrying");
+                tokio::time::sleep(delay).await;
+                delay = delay.saturating_mul(2);
+            }
+            Err(error) => {
+                tracing::warn!(attempt, %error, "audit event dropped by collector delivery");
+                return false;
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+#[path = "audit_collector_tests.rs"]
+mod tests;
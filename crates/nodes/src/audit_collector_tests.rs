@@ -0,0 +1,326 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use pipeline::{
+    audit::{AuditEventKind, PipelineOutcome, StateTransitionRecord},
+    NodeId, NodeStatus, Timestamp, TokenCost,
+};
+
+use super::*;
+
+const ENDPOINT: &str = "https://collector.example.com/cogworks/audit";
+
+/// A collector that answers from a script (accepting once it runs out) and
+/// records every POST.
+#[derive(Default)]
+struct FakeCollector {
+    results: Mutex<VecDeque<Result<(), CollectorError>>>,
+    posts: Mutex<Vec<(String, CollectedAuditEvent)>>,
+}
+
+impl FakeCollector {
+    fn answering(results: Vec<Result<(), CollectorError>>) -> Self {
+        Self {
+            results: Mutex::new(results.into()),
+            ..Self::default()
+        }
+    }
+
+    fn posted_kinds(&self) -> Vec<AuditEventKind> {
+        self.posts
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, event)| event.event.kind())
+            .collect()
+    }
+
+    fn post_count(&self) -> usize {
+        self.posts.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl CollectorTransport for FakeCollector {
+    async fn post(
+        &self,
+        endpoint: &str,
+        event: &CollectedAuditEvent,
+    ) -> Result<(), CollectorError> {
+        self.posts
+            .lock()
+            .unwrap()
+            .push((endpoint.to_string(), event.clone()));
+        self.results.lock().unwrap().pop_front().unwrap_or(Ok(()))
+    }
+}
+
+/// The wrapped store: records events and summaries.
+#[derive(Default)]
+struct RecordingStore {
+    events: Mutex<Vec<AuditEventKind>>,
+    summaries: Mutex<Vec<PipelineRunId>>,
+}
+
+#[async_trait]
+impl AuditStore for RecordingStore {
+    async fn record_event(
+        &self,
+        _run_id: PipelineRunId,
+        _work_item_id: WorkItemId,
+        event: AuditEvent,
+    ) -> Result<(), AuditStoreError> {
+        self.events.lock().unwrap().push(event.kind());
+        Ok(())
+    }
+
+    async fn write_summary(&self, summary: &PipelineSummary) -> Result<(), AuditStoreError> {
+        self.summaries.lock().unwrap().push(summary.run_id);
+        Ok(())
+    }
+}
+
+fn config(max_attempts: u32, queue_capacity: usize) -> AuditCollectorConfig {
+    AuditCollectorConfig {
+        endpoint: ENDPOINT.to_string(),
+        max_attempts,
+        queue_capacity,
+    }
+}
+
+fn transition(to_status: NodeStatus) -> AuditEvent {
+    AuditEvent::StateTransition(StateTransitionRecord {
+        node_id: NodeId::new("plan").unwrap(),
+        from_status: NodeStatus::Pending,
+        to_status,
+        reason: None,
+        timestamp: Timestamp::now().as_datetime(),
+    })
+}
+
+fn collected() -> CollectedAuditEvent {
+    CollectedAuditEvent {
+        run_id: PipelineRunId::new_random(),
+        work_item_id: WorkItemId::new(42),
+        event: transition(NodeStatus::Active),
+    }
+}
+
+fn unavailable() -> Result<(), CollectorError> {
+    Err(CollectorError::Rejected { status: 503 })
+}
+
+// ─── CollectorError ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_is_retryable_unreachable_overloaded_and_server_errors_return_true() {
+    let unreachable = CollectorError::Unreachable {
+        message: "connection refused".to_string(),
+    };
+
+    assert!(unreachable.is_retryable());
+    assert!(CollectorError::Rejected { status: 429 }.is_retryable());
+    assert!(CollectorError::Rejected { status: 502 }.is_retryable());
+    assert!(!CollectorError::Rejected { status: 400 }.is_retryable());
+    assert!(!CollectorError::Rejected { status: 401 }.is_retryable());
+}
+
+// ─── deliver ────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_deliver_accepted_posts_event_to_endpoint_once() {
+    let collector = FakeCollector::default();
+    let event = collected();
+
+    assert!(deliver(&collector, ENDPOINT, &event, 3).await);
+
+    let posts = collector.posts.lock().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].0, ENDPOINT);
+    assert_eq!(posts[0].1.run_id, event.run_id);
+}
+
+#[tokio::test]
+async fn test_deliver_retryable_failure_then_success_returns_true() {
+    let collector = FakeCollector::answering(vec![unavailable()]);
+
+    assert!(deliver(&collector, ENDPOINT, &collected(), 3).await);
+    assert_eq!(collector.post_count(), 2);
+}
+
+#[tokio::test]
+async fn test_deliver_retryable_failures_past_max_attempts_returns_false() {
+    let collector = FakeCollector::answering(vec![unavailable(), unavailable(), unavailable()]);
+
+    assert!(!deliver(&collector, ENDPOINT, &collected(), 2).await);
+    assert_eq!(collector.post_count(), 2);
+}
+
+#[tokio::test]
+async fn test_deliver_non_retryable_rejection_returns_false_without_retry() {
+    let collector = FakeCollector::answering(vec![Err(CollectorError::Rejected { status: 400 })]);
+
+    assert!(!deliver(&collector, ENDPOINT, &collected(), 3).await);
+    assert_eq!(collector.post_count(), 1);
+}
+
+#[tokio::test]
+async fn test_deliver_zero_max_attempts_still_posts_once() {
+    let collector = FakeCollector::default();
+
+    assert!(deliver(&collector, ENDPOINT, &collected(), 0).await);
+    assert_eq!(collector.post_count(), 1);
+}
+
+// ─── CollectorAuditStore ────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_record_event_writes_inner_store_and_forwarder_posts_in_order() {
+    let inner = Arc::new(RecordingStore::default());
+    let collector = Arc::new(FakeCollector::default());
+    let (store, forwarder) = CollectorAuditStore::new(
+        Arc::clone(&inner) as _,
+        Arc::clone(&collector) as _,
+        config(3, 8),
+    );
+    let forwarding = tokio::spawn(forwarder.run());
+    let run_id = PipelineRunId::new_random();
+
+    store
+        .record_event(run_id, WorkItemId::new(42), transition(NodeStatus::Active))
+        .await
+        .unwrap();
+    store
+        .record_event(
+            run_id,
+            WorkItemId::new(42),
+            transition(NodeStatus::Completed),
+        )
+        .await
+        .unwrap();
+    drop(store);
+    forwarding.await.unwrap();
+
+    assert_eq!(inner.events.lock().unwrap().len(), 2);
+    let posts = collector.posts.lock().unwrap();
+    let statuses: Vec<_> = posts
+        .iter()
+        .map(|(_, posted)| match &posted.event {
+            AuditEvent::StateTransition(record) => record.to_status,
+            other => panic!("unexpected event {:?}", other.kind()),
+        })
+        .collect();
+    assert_eq!(statuses, vec![NodeStatus::Active, NodeStatus::Completed]);
+    assert!(posts.iter().all(|(endpoint, posted)| endpoint == ENDPOINT
+        && posted.run_id == run_id
+        && posted.work_item_id == WorkItemId::new(42)));
+}
+
+#[tokio::test]
+async fn test_record_event_collector_rejects_event_returns_ok() {
+    let inner = Arc::new(RecordingStore::default());
+    let collector = Arc::new(FakeCollector::answering(vec![Err(
+        CollectorError::Rejected { status: 400 },
+    )]));
+    let (store, forwarder) = CollectorAuditStore::new(
+        Arc::clone(&inner) as _,
+        Arc::clone(&collector) as _,
+        config(3, 8),
+    );
+    let forwarding = tokio::spawn(forwarder.run());
+
+    let result = store
+        .record_event(
+            PipelineRunId::new_random(),
+            WorkItemId::new(42),
+            transition(NodeStatus::Failed),
+        )
+        .await;
+    drop(store);
+    forwarding.await.unwrap();
+
+    assert!(result.is_ok());
+    assert_eq!(
+        *inner.events.lock().unwrap(),
+        vec![AuditEventKind::StateTransition]
+    );
+    assert_eq!(collector.post_count(), 1);
+}
+
+#[tokio::test]
+async fn test_record_event_full_queue_drops_event_and_returns_ok() {
+    let inner = Arc::new(RecordingStore::default());
+    let collector = Arc::new(FakeCollector::default());
+    let (store, forwarder) = CollectorAuditStore::new(
+        Arc::clone(&inner) as _,
+        Arc::clone(&collector) as _,
+        config(3, 1),
+    );
+    let run_id = PipelineRunId::new_random();
+
+    for status in [NodeStatus::Active, NodeStatus::Completed] {
+        store
+            .record_event(run_id, WorkItemId::new(42), transition(status))
+            .await
+            .unwrap();
+    }
+    drop(store);
+    forwarder.run().await;
+
+    assert_eq!(inner.events.lock().unwrap().len(), 2);
+    assert_eq!(
+        collector.posted_kinds(),
+        vec![AuditEventKind::StateTransition]
+    );
+}
+
+#[tokio::test]
+async fn test_record_event_forwarder_stopped_returns_ok() {
+    let inner = Arc::new(RecordingStore::default());
+    let (store, forwarder) = CollectorAuditStore::new(
+        Arc::clone(&inner) as _,
+        Arc::new(FakeCollector::default()),
+        config(3, 8),
+    );
+    drop(forwarder);
+
+    let result = store
+        .record_event(
+            PipelineRunId::new_random(),
+            WorkItemId::new(42),
+            transition(NodeStatus::Active),
+        )
+        .await;
+
+    assert!(result.is_ok());
+    assert_eq!(inner.events.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_write_summary_delegates_to_inner_store_without_streaming() {
+    let inner = Arc::new(RecordingStore::default());
+    let collector = Arc::new(FakeCollector::default());
+    let (store, forwarder) = CollectorAuditStore::new(
+        Arc::clone(&inner) as _,
+        Arc::clone(&collector) as _,
+        config(3, 8),
+    );
+    let summary = PipelineSummary {
+        run_id: PipelineRunId::new_random(),
+        work_item_id: WorkItemId::new(42),
+        outcome: PipelineOutcome::Completed,
+        total_cost: TokenCost::zero(),
+        duration: Duration::from_secs(60),
+        nodes_completed: 3,
+        nodes_failed: 0,
+        total_rework_count: 0,
+        terminal_message: "opened #7".to_string(),
+        completed_at: Timestamp::now().as_datetime(),
+    };
+
+    store.write_summary(&summary).await.unwrap();
+    drop(store);
+    forwarder.run().await;
+
+    assert_eq!(*inner.summaries.lock().unwrap(), vec![summary.run_id]);
+    assert_eq!(collector.post_count(), 0);
+}
@@ -0,0 +1,127 @@
+//! Run budget enforcement with an optional overshoot grace.
+//!
+//! A run halts once its cost reaches the budget. When the call that crosses
+//! the limit only overshoots it slightly, halting throws away a nearly
+//! finished node. An [`OvershootGrace`] lets the current node finish when the
+//! overshoot is within the grace; the run then halts after that node, and the
+//! overshoot is reported in [`BudgetDecision::FinishNode`]. An overshoot past
+//! the grace halts immediately, exactly as without one.
+//!
+//! The grace is off by default and never more than
+//! [`MAX_OVERSHOOT_GRACE_FRACTION`] of the budget, however it is configured.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/operations.md` §Budget Configuration.
+
+use serde::{Deserialize, Serialize};
+
+use pipeline::{CogWorksError, CostBudget, HaltReason, TokenCost};
+
+/// Largest grace allowed, as a fraction of the budget.
+pub const MAX_OVERSHOOT_GRACE_FRACTION: f64 = 0.25;
+
+/// How far past the budget the current node may run before halting.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OvershootGrace {
+    /// A fixed amount in USD.
+    Dollars(f64),
+    /// A percentage of the budget (e.g. `5.0` for 5 %).
+    Percent(f64),
+}
+
+impl OvershootGrace {
+    /// The grace in USD for `budget`, clamped to
+    /// [`MAX_OVERSHOOT_GRACE_FRACTION`] of the budget.
+    ///
+    /// Negative, infinite, and NaN settings give no grace.
+    pub fn amount(self, budget: CostBudget) -> TokenCost {
+        let requested = match self {
+            Self::Dollars(dollars) => dollars,
+            Self::Percent(percent) => budget.as_f64() * percent / 100.0,
+        };
+        let cap = budget.as_f64() * MAX_OVERSHOOT_GRACE_FRACTION;
+        // Validate before clamping: `f64::min` would turn a NaN into the cap.
+        TokenCost::new(requested).map_or_else(TokenCost::zero, |grace| {
+            TokenCost::new(grace.as_f64().min(cap)).unwrap_or_else(TokenCost::zero)
+        })
+    }
+}
+
+/// What the executor does after checking the accumulated cost.
+#[derive(Debug)]
+pub enum BudgetDecision {
+    /// The run is under budget.
+    Continue,
+    /// The budget is reached but the overshoot is within grace: finish the
+    /// current node, then halt with the carried error
+    /// ([`HaltReason::CostBudget`]).
+    FinishNode {
+        /// Spend past the budget.
+        overshoot: TokenCost,
+        /// The error to halt with once the node finishes.
+        halt: CogWorksError,
+    },
+    /// The budget is reached and the overshoot exceeds any grace; halt now
+    /// with [`HaltReason::CostBudget`].
+    Halt(CogWorksError),
+}
+
+/// Checks a run's accumulated cost against its budget and grace.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BudgetEnforcer {
+    /// The run budget.
+    pub budget: CostBudget,
+    /// Optional overshoot grace; `None` halts as soon as the budget is reached.
+    #[serde(default)]
+    pub overshoot_grace: Option<OvershootGrace>,
+}
+
+impl BudgetEnforcer {
+    /// Creates an enforcer for `budget` with no grace.
+    pub fn new(budget: CostBudget) -> Self {
+        Self {
+            budget,
+            overshoot_grace: None,
+        }
+    }
+
+    /// Sets the overshoot grace.
+    #[must_use]
+    pub fn with_overshoot_grace(mut self, grace: OvershootGrace) -> Self {
+        self.overshoot_grace = Some(grace);
+        self
+    }
+
+    /// The grace in USD; zero when none is configured.
+    pub fn grace_amount(&self) -> TokenCost {
+        self.overshoot_grace
+            .map_or_else(TokenCost::zero, |grace| grace.amount(self.budget))
+    }
+
+    /// Decides whether the run may continue after spending `accumulated`.
+    pub fn check(&self, accumulated: TokenCost) -> BudgetDecision {
+        if !self.budget.is_exceeded_by(accumulated) {
+            return BudgetDecision::Continue;
+        }
+        let halt = CogWorksError::halt(
+            HaltReason::CostBudget,
+            format!("accumulated {accumulated}, limit {}", self.budget),
+        );
+        let overshoot = TokenCost::new(accumulated.as_f64() - self.budget.as_f64())
+            .unwrap_or_else(TokenCost::zero);
+        let grace = self.grace_amount();
+        if !grace.is_zero() && overshoot <= grace {
+            tracing::warn!(%overshoot, %grace, "budget overshoot within grace; finishing node");
+            BudgetDecision::FinishNode { overshoot, halt }
+        } else {
+            tracing::error!(%overshoot, %grace, "budget exceeded; halting");
+            BudgetDecision::Halt(halt)
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "budget_tests.rs"]
+mod tests;
@@ -0,0 +1,132 @@
+use super::*;
+
+fn budget(limit: f64) -> CostBudget {
+    CostBudget::new(limit).unwrap()
+}
+
+fn cost(value: f64) -> TokenCost {
+    TokenCost::new(value).unwrap()
+}
+
+// ─── OvershootGrace ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_amount_dollars_returns_fixed_grace() {
+    assert_eq!(OvershootGrace::Dollars(0.5).amount(budget(10.0)), cost(0.5));
+}
+
+#[test]
+fn test_amount_percent_returns_share_of_budget() {
+    assert_eq!(OvershootGrace::Percent(5.0).amount(budget(10.0)), cost(0.5));
+}
+
+#[test]
+fn test_amount_above_cap_clamps_to_maximum_fraction() {
+    let limit = budget(10.0);
+    let cap = cost(10.0 * MAX_OVERSHOOT_GRACE_FRACTION);
+
+    assert_eq!(OvershootGrace::Dollars(100.0).amount(limit), cap);
+    assert_eq!(OvershootGrace::Percent(90.0).amount(limit), cap);
+}
+
+#[test]
+fn test_amount_invalid_setting_returns_zero() {
+    let limit = budget(10.0);
+
+    assert!(OvershootGrace::Dollars(-1.0).amount(limit).is_zero());
+    assert!(OvershootGrace::Percent(f64::NAN).amount(limit).is_zero());
+}
+
+// ─── BudgetEnforcer::check ──────────────────────────────────────────────────
+
+#[test]
+fn test_grace_amount_default_is_zero() {
+    let enforcer = BudgetEnforcer::new(budget(10.0));
+
+    assert_eq!(enforcer.overshoot_grace, None);
+    assert!(enforcer.grace_amount().is_zero());
+}
+
+#[test]
+fn test_check_under_budget_continues() {
+    let enforcer =
+        BudgetEnforcer::new(budget(10.0)).with_overshoot_grace(OvershootGrace::Dollars(1.0));
+
+    assert!(matches!(
+        enforcer.check(cost(9.99)),
+        BudgetDecision::Continue
+    ));
+}
+
+#[test]
+fn test_check_no_grace_reaching_budget_halts_immediately() {
+    let enforcer = BudgetEnforcer::new(budget(10.0));
+
+    let decision = enforcer.check(cost(10.0));
+
+    assert!(matches!(
+        decision,
+        BudgetDecision::Halt(CogWorksError::PipelineHalt {
+            reason: HaltReason::CostBudget,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_check_within_grace_finishes_node_and_records_overshoot() {
+    let enforcer =
+        BudgetEnforcer::new(budget(10.0)).with_overshoot_grace(OvershootGrace::Percent(5.0));
+
+    let decision = enforcer.check(cost(10.25));
+
+    match decision {
+        BudgetDecision::FinishNode { overshoot, halt } => {
+            assert_eq!(overshoot, cost(0.25));
+            assert!(matches!(
+                halt,
+                CogWorksError::PipelineHalt {
+                    reason: HaltReason::CostBudget,
+                    detail: Some(_),
+                }
+            ));
+        }
+        other => panic!("expected FinishNode, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_check_exactly_at_grace_finishes_node() {
+    let enforcer =
+        BudgetEnforcer::new(budget(10.0)).with_overshoot_grace(OvershootGrace::Dollars(0.5));
+
+    assert!(matches!(
+        enforcer.check(cost(10.5)),
+        BudgetDecision::FinishNode { .. }
+    ));
+}
+
+#[test]
+fn test_check_beyond_grace_halts_immediately() {
+    let enforcer =
+        BudgetEnforcer::new(budget(10.0)).with_overshoot_grace(OvershootGrace::Dollars(0.5));
+
+    assert!(matches!(
+        enforcer.check(cost(10.75)),
+        BudgetDecision::Halt(CogWorksError::PipelineHalt {
+            reason: HaltReason::CostBudget,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_check_overshoot_past_cap_halts_despite_large_grace() {
+    let enforcer =
+        BudgetEnforcer::new(budget(10.0)).with_overshoot_grace(OvershootGrace::Dollars(50.0));
+
+    assert!(matches!(
+        enforcer.check(cost(13.0)),
+        BudgetDecision::Halt(_)
+    ));
+}
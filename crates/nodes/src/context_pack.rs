@@ -0,0 +1,132 @@
+//! Context Pack loading from the target repository.
+//!
+//! [`ContextPackLoader`] reads one pack directory under
+//! [`CONTEXT_PACKS_DIR`] at a fixed git ref. Only files chosen by the caller's
+//! [`PackFileSelection`] are read, so a node that needs a pack's
+//! anti-patterns does not pay prompt tokens for the rest of it. Required
+//! files from the [`ContextPackManifest`] are checked first and regardless of
+//! the selection.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/architecture.md` §Context Pack Selection.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use tracing::instrument;
+
+use pipeline::{
+    context::CONTEXT_PACKS_DIR, CodeRepository, ContextPack, ContextPackError, ContextPackManifest,
+    DirectoryEntryKind, GitHubOperationError, PackFileSelection, RepositoryId,
+};
+
+/// Loads Context Packs from one repository at one ref.
+pub struct ContextPackLoader {
+    repository: Arc<dyn CodeRepository>,
+    repository_id: RepositoryId,
+    git_ref: String,
+}
+
+impl ContextPackLoader {
+    /// Creates a loader reading `repository_id` at `git_ref`.
+    pub fn new(
+        repository: Arc<dyn CodeRepository>,
+        repository_id: RepositoryId,
+        git_ref: impl Into<String>,
+    ) -> Self {
+        Self {
+            repository,
+            repository_id,
+            git_ref: git_ref.into(),
+        }
+    }
+
+    /// Loads the files of the pack described by `manifest` that `selection`
+    /// selects.
+    ///
+    /// # Errors
+    ///
+    /// - [`ContextPackError::MissingRequiredFile`] — a required file does not
+    ///   exist, whether or not `selection` selects it.
+    /// - [`ContextPackError::Repository`] — the pack could not be read.
+    #[instrument(skip(self, manifest, selection), fields(pack = %manifest.id))]
+    pub async fn load(
+        &self,
+        manifest: &ContextPackManifest,
+        selection: &PackFileSelection,
+    ) -> Result<ContextPack, ContextPackError> {
+        let pack = &manifest.id;
+        let root = format!("{CONTEXT_PACKS_DIR}/{pack}");
+        let repository_error = |source| ContextPackError::Repository {
+            pack: pack.clone(),
+            source,
+        };
+
+        for required in &manifest.required_files {
+            let exists = self
+                .repository
+                .file_exists(
+                    &self.repository_id,
+                    &format!("{root}/{required}"),
+                    &self.git_ref,
+                )
+                .await
+                .map_err(repository_error)?;
+            if !exists {
+                return Err(ContextPackError::MissingRequiredFile {
+                    pack: pack.clone(),
+                    path: required.clone(),
+                });
+            }
+        }
+
+        let mut files = BTreeMap::new();
+        for path in self.list_files(&root).await.map_err(repository_error)? {
+            let Some(relative) = path.strip_prefix(&root).map(|p| p.trim_start_matches('/')) else {
+                continue;
+            };
+            if !selection.selects(relative) {
+                continue;
+            }
+            let file = self
+                .repository
+                .read_file(&self.repository_id, &path, &self.git_ref)
+                .await
+                .map_err(repository_error)?;
+            files.insert(
+                relative.to_string(),
+                String::from_utf8_lossy(&file.content).into_owned(),
+            );
+        }
+
+        tracing::debug!(files = files.len(), "loaded context pack");
+        Ok(ContextPack {
+            id: pack.clone(),
+            files,
+        })
+    }
+
+    /// Lists every file below `root`, descending into subdirectories.
+    async fn list_files(&self, root: &str) -> Result<Vec<String>, GitHubOperationError> {
+        let mut pending = vec![root.to_string()];
+        let mut files = Vec::new();
+        while let Some(directory) = pending.pop() {
+            let entries = self
+                .repository
+                .list_directory(&self.repository_id, &directory, &self.git_ref)
+                .await?;
+            for entry in entries {
+                match entry.kind {
+                    DirectoryEntryKind::File => files.push(entry.path),
+                    DirectoryEntryKind::Directory => pending.push(entry.path),
+                    DirectoryEntryKind::Symlink | DirectoryEntryKind::Submodule => {}
+                }
+            }
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+#[path = "context_pack_tests.rs"]
+mod tests;
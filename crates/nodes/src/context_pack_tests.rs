@@ -0,0 +1,144 @@
+use pipeline::{ContextPackId, GlobPattern};
+
+use crate::test_support::FakeCodeRepository;
+
+use super::*;
+
+const PACK: &str = ".cogworks/context-packs/rust-async";
+
+fn repository() -> Arc<FakeCodeRepository> {
+    Arc::new(FakeCodeRepository::with_files([
+        (".cogworks/context-packs/rust-async/README.md", "overview"),
+        (
+            ".cogworks/context-packs/rust-async/safe-patterns/select.md",
+            "select!",
+        ),
+        (
+            ".cogworks/context-packs/rust-async/anti-patterns/blocking.md",
+            "no block_on",
+        ),
+        (
+            ".cogworks/context-packs/rust-async/anti-patterns/locks.md",
+            "no std locks",
+        ),
+        (".cogworks/context-packs/other/README.md", "other pack"),
+    ]))
+}
+
+fn loader(repository: &Arc<FakeCodeRepository>) -> ContextPackLoader {
+    ContextPackLoader::new(
+        Arc::clone(repository) as _,
+        RepositoryId::parse("octo/widgets").unwrap(),
+        "main",
+    )
+}
+
+fn manifest(required: &[&str]) -> ContextPackManifest {
+    ContextPackManifest {
+        id: ContextPackId::new("rust-async").unwrap(),
+        required_files: required.iter().map(|path| path.to_string()).collect(),
+    }
+}
+
+fn globs(patterns: &[&str]) -> Vec<GlobPattern> {
+    patterns
+        .iter()
+        .map(|pattern| GlobPattern::new(*pattern).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_load_default_selection_loads_every_pack_file() {
+    let repository = repository();
+
+    let pack = loader(&repository)
+        .load(&manifest(&[]), &PackFileSelection::default())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        pack.files.keys().collect::<Vec<_>>(),
+        vec![
+            "README.md",
+            "anti-patterns/blocking.md",
+            "anti-patterns/locks.md",
+            "safe-patterns/select.md",
+        ]
+    );
+    assert_eq!(pack.files["anti-patterns/locks.md"], "no std locks");
+}
+
+#[tokio::test]
+async fn test_load_include_glob_loads_only_matching_files() {
+    let repository = repository();
+    let selection = PackFileSelection {
+        include: globs(&["anti-patterns/*.md"]),
+        exclude: Vec::new(),
+    };
+
+    let pack = loader(&repository)
+        .load(&manifest(&[]), &selection)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        pack.files.keys().collect::<Vec<_>>(),
+        vec!["anti-patterns/blocking.md", "anti-patterns/locks.md"]
+    );
+    assert!(!repository.reads().contains(&format!("{PACK}/README.md")));
+}
+
+#[tokio::test]
+async fn test_load_exclude_glob_omits_excluded_files_without_reading_them() {
+    let repository = repository();
+    let selection = PackFileSelection {
+        include: Vec::new(),
+        exclude: globs(&["**/locks.md", "README.md"]),
+    };
+
+    let pack = loader(&repository)
+        .load(&manifest(&[]), &selection)
+        .await
+        .unwrap();
+
+    assert!(!pack.files.contains_key("anti-patterns/locks.md"));
+    assert!(!pack.files.contains_key("README.md"));
+    assert_eq!(pack.files.len(), 2);
+    assert_eq!(repository.reads().len(), 2);
+}
+
+#[tokio::test]
+async fn test_load_missing_required_file_returns_error_even_if_not_selected() {
+    let repository = repository();
+    let selection = PackFileSelection {
+        include: globs(&["anti-patterns/**"]),
+        exclude: Vec::new(),
+    };
+
+    let error = loader(&repository)
+        .load(&manifest(&["README.md", "domain.md"]), &selection)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        ContextPackError::MissingRequiredFile { path, .. } if path == "domain.md"
+    ));
+    assert!(repository.reads().is_empty());
+}
+
+#[tokio::test]
+async fn test_load_required_file_excluded_by_selection_is_not_loaded() {
+    let repository = repository();
+    let selection = PackFileSelection {
+        include: Vec::new(),
+        exclude: globs(&["README.md"]),
+    };
+
+    let pack = loader(&repository)
+        .load(&manifest(&["README.md"]), &selection)
+        .await
+        .unwrap();
+
+    assert!(!pack.files.contains_key("README.md"));
+}
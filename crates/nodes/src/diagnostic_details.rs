@@ -0,0 +1,115 @@
+//! Collapsible rendering of diagnostic findings for issue comments.
+//!
+//! A run can report dozens of findings; listed inline they push everything
+//! else in the comment out of view. [`render_diagnostic_details`] puts them in
+//! a `<details>` block whose `<summary>` line, always visible, gives the count
+//! per severity. Expanded, the findings are grouped under one heading per
+//! severity, blocking first. Headings and counts come from a
+//! [`MessageCatalog`].
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Run summary.
+
+use pipeline::{Diagnostic, DiagnosticSeverity};
+
+use crate::messages::MessageCatalog;
+
+/// Severities in the order their groups are rendered.
+const SEVERITY_ORDER: [DiagnosticSeverity; 3] = [
+    DiagnosticSeverity::Blocking,
+    DiagnosticSeverity::Warning,
+    DiagnosticSeverity::Informational,
+];
+
+/// Returns the message ID suffix for `severity`.
+fn severity_key(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::Blocking => "blocking",
+        DiagnosticSeverity::Warning => "warning",
+        DiagnosticSeverity::Informational => "informational",
+    }
+}
+
+/// Formats one finding as a Markdown list item.
+///
+/// The category leads, followed by the artifact and location when present.
+/// Line breaks in the message are collapsed so the item stays on one line.
+fn finding_line(diagnostic: &Diagnostic) -> String {
+    let mut line = format!("- **{}**", diagnostic.category);
+    if let Some(artifact) = &diagnostic.artifact {
+        line.push_str(&format!(" `{artifact}`"));
+    }
+    if let Some(location) = &diagnostic.location {
+        line.push_str(&format!(" ({location})"));
+    }
+    let message = diagnostic.message.split_whitespace().collect::<Vec<_>>();
+    line.push_str(&format!(": {}", message.join(" ")));
+    line
+}
+
+/// Renders `diagnostics` as a collapsible Markdown block grouped by severity.
+///
+/// The summary line reads e.g. `Findings: 2 blocking, 1 warning`; severities
+/// without findings are omitted from both the summary and the groups. Findings
+/// keep their input order within a group. Returns `None` if there are no
+/// findings.
+#[must_use]
+pub fn render_diagnostic_details(
+    diagnostics: &[Diagnostic],
+    messages: &MessageCatalog,
+) -> Option<String> {
+    if diagnostics.is_empty() {
+        return None;
+    }
+
+    let groups: Vec<(DiagnosticSeverity, Vec<&Diagnostic>)> = SEVERITY_ORDER
+        .into_iter()
+        .map(|severity| {
+            let findings = diagnostics
+                .iter()
+                .filter(|d| d.severity == severity)
+                .collect::<Vec<_>>();
+            (severity, findings)
+        })
+        .filter(|(_, findings)| !findings.is_empty())
+        .collect();
+
+    let counts = groups
+        .iter()
+        .map(|(severity, findings)| {
+            let number = if findings.len() == 1 { "one" } else { "other" };
+            messages.format(
+                &format!("findings.count.{}.{number}", severity_key(*severity)),
+                &[("count", &findings.len().to_string())],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    // GitHub only renders Markdown inside `<details>` after a blank line.
+    let mut lines = vec![
+        "<details>".to_string(),
+        format!(
+            "<summary>{}</summary>",
+            messages.format("findings.summary", &[("counts", &counts)])
+        ),
+    ];
+    for (severity, findings) in &groups {
+        lines.push(String::new());
+        lines.push(format!(
+            "#### {} ({})",
+            messages.message(&format!("findings.heading.{}", severity_key(*severity))),
+            findings.len()
+        ));
+        lines.push(String::new());
+        lines.extend(findings.iter().map(|d| finding_line(d)));
+    }
+    lines.push(String::new());
+    lines.push("</details>".to_string());
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+#[path = "diagnostic_details_tests.rs"]
+mod tests;
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use pipeline::{ArtifactPath, DiagnosticCategory};
+
+use super::*;
+
+fn diagnostic(severity: DiagnosticSeverity, category: &str, message: &str) -> Diagnostic {
+    Diagnostic {
+        artifact: None,
+        location: None,
+        severity,
+        category: DiagnosticCategory::new(category).unwrap(),
+        message: message.to_string(),
+    }
+}
+
+fn render(diagnostics: &[Diagnostic]) -> String {
+    render_diagnostic_details(diagnostics, &MessageCatalog::english()).unwrap()
+}
+
+#[test]
+fn test_render_diagnostic_details_no_findings_returns_none() {
+    assert_eq!(
+        render_diagnostic_details(&[], &MessageCatalog::english()),
+        None
+    );
+}
+
+#[test]
+fn test_render_diagnostic_details_findings_wrapped_in_details_and_summary() {
+    let rendered = render(&[
+        diagnostic(
+            DiagnosticSeverity::Blocking,
+            "alignment",
+            "missing endpoint",
+        ),
+        diagnostic(DiagnosticSeverity::Warning, "style", "long function"),
+    ]);
+
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines[0], "<details>");
+    assert_eq!(
+        lines[1],
+        "<summary>Findings: 1 blocking, 1 warning</summary>"
+    );
+    assert_eq!(lines[2], "", "Markdown needs a blank line after <summary>");
+    assert_eq!(lines.last(), Some(&"</details>"));
+}
+
+#[test]
+fn test_render_diagnostic_details_groups_by_severity_blocking_first() {
+    let rendered = render(&[
+        diagnostic(
+            DiagnosticSeverity::Informational,
+            "notes",
+            "consider caching",
+        ),
+        diagnostic(DiagnosticSeverity::Warning, "style", "long function"),
+        diagnostic(
+            DiagnosticSeverity::Blocking,
+            "alignment",
+            "missing endpoint",
+        ),
+        diagnostic(DiagnosticSeverity::Warning, "style", "unused import"),
+    ]);
+
+    let blocking = rendered.find("#### 🛑 Blocking (1)").unwrap();
+    let warnings = rendered.find("#### ⚠️ Warnings (2)").unwrap();
+    let informational = rendered.find("#### ℹ️ Informational (1)").unwrap();
+    assert!(blocking < warnings && warnings < informational);
+
+    let missing = rendered.find("missing endpoint").unwrap();
+    let long = rendered.find("long function").unwrap();
+    let unused = rendered.find("unused import").unwrap();
+    let caching = rendered.find("consider caching").unwrap();
+    assert!(blocking < missing && missing < warnings);
+    assert!(warnings < long && long < unused && unused < informational);
+    assert!(informational < caching);
+}
+
+#[test]
+fn test_render_diagnostic_details_empty_severity_is_omitted() {
+    let rendered = render(&[
+        diagnostic(DiagnosticSeverity::Warning, "style", "long function"),
+        diagnostic(DiagnosticSeverity::Warning, "style", "unused import"),
+    ]);
+
+    assert!(rendered.contains("<summary>Findings: 2 warnings</summary>"));
+    assert!(!rendered.contains("Blocking"));
+    assert!(!rendered.contains("Informational"));
+}
+
+#[test]
+fn test_render_diagnostic_details_finding_line_includes_artifact_and_location() {
+    let mut finding = diagnostic(
+        DiagnosticSeverity::Blocking,
+        "alignment",
+        "handler does not\nmatch   the design",
+    );
+    finding.artifact = ArtifactPath::new("src/api.rs");
+    finding.location = Some("line 12".to_string());
+
+    let rendered = render(&[finding]);
+
+    assert!(rendered
+        .lines()
+        .any(|line| line
+            == "- **alignment** `src/api.rs` (line 12): handler does not match the design"));
+}
+
+#[test]
+fn test_render_diagnostic_details_catalog_overrides_headings() {
+    let messages = MessageCatalog::with_overrides(HashMap::from([(
+        "findings.heading.blocking".to_string(),
+        "Must fix".to_string(),
+    )]));
+
+    let rendered = render_diagnostic_details(
+        &[diagnostic(
+            DiagnosticSeverity::Blocking,
+            "alignment",
+            "missing endpoint",
+        )],
+        &messages,
+    )
+    .unwrap();
+
+    assert!(rendered.contains("#### Must fix (1)"));
+}
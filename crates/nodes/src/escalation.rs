@@ -0,0 +1,316 @@
+//! Notifications sent when a run stops for human attention.
+//!
+//! A halt or a human gate is recorded in the run summary comment, which
+//! nobody sees until they open the issue. [`Escalator`]s push a notification
+//! out instead. Two are provided:
+//!
+//! - [`IssueMentionEscalator`] posts a comment on the work item mentioning the
+//!   configured users or teams, so GitHub notifies them.
+//! - [`WebhookEscalator`] POSTs an [`EscalationPayload`] as JSON to a
+//!   configured URL (chat bridge, paging system), through a
+//!   [`WebhookTransport`].
+//!
+//! [`Escalation::from_step`] decides whether a step needs escalating (outcome
+//! `HumanGated`, `Escalated`, or `Failed`) and [`escalate_all`] notifies every
+//! configured escalator. [`PipelineExecutor::halt`](crate::executor::PipelineExecutor::halt)
+//! calls both whenever a run halts. One escalator failing does not stop the others, and
+//! never fails the step: escalation is best effort.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Escalation.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::instrument;
+
+use pipeline::{
+    GitHubOperationError, HaltReason, IssueTracker, PipelineOutcome, PipelineRunId, WorkItemId,
+};
+
+use crate::{executor::StepResult, messages::MessageCatalog};
+
+// ─── Escalation ─────────────────────────────────────────────────────────────
+
+/// A run that stopped and needs a human.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Escalation {
+    /// The run that stopped.
+    pub run_id: PipelineRunId,
+    /// The work item the run is processing.
+    pub work_item_id: WorkItemId,
+    /// How the step ended.
+    pub outcome: PipelineOutcome,
+    /// Why the run halted; `None` when it is waiting at a human gate or
+    /// failed for a reason without a [`HaltReason`].
+    pub reason: Option<HaltReason>,
+    /// Free-text detail (e.g. the halting error message).
+    pub detail: Option<String>,
+}
+
+impl Escalation {
+    /// Builds the escalation for `step`, or `None` if the step does not need
+    /// one (still running, or completed).
+    pub fn from_step(
+        step: &StepResult,
+        reason: Option<HaltReason>,
+        detail: Option<String>,
+    ) -> Option<Self> {
+        let outcome = step.outcome?;
+        if !needs_escalation(outcome) {
+            return None;
+        }
+        Some(Self {
+            run_id: step.run_id,
+            work_item_id: step.work_item_id,
+            outcome,
+            reason,
+            detail,
+        })
+    }
+
+    /// One-line description used by the built-in escalators, with its text
+    /// taken from `messages`.
+    pub fn summary(&self, messages: &MessageCatalog) -> String {
+        let id = match self.outcome {
+            PipelineOutcome::HumanGated => "escalation.human_gated",
+            PipelineOutcome::Escalated => "escalation.escalated",
+            PipelineOutcome::Failed => "escalation.failed",
+            PipelineOutcome::Completed => "escalation.completed",
+        };
+        let mut summary = messages.format(
+            id,
+            &[
+                ("run", &self.run_id.to_string()),
+                ("work_item", &self.work_item_id.to_string()),
+            ],
+        );
+        if let Some(reason) = self.reason {
+            summary.push_str(&format!(" ({reason})"));
+        }
+        if let Some(detail) = &self.detail {
+            summary.push_str(&format!(": {detail}"));
+        }
+        summary
+    }
+}
+
+/// Returns `true` for outcomes that need a human: held at a gate,
+/// escalated, or halted.
+pub fn needs_escalation(outcome: PipelineOutcome) -> bool {
+    matches!(
+        outcome,
+        PipelineOutcome::HumanGated | PipelineOutcome::Escalated | PipelineOutcome::Failed
+    )
+}
+
+/// Errors returned by an [`Escalator`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum EscalationError {
+    /// The mention comment could not be posted.
+    #[error("failed to post escalation comment")]
+    Comment {
+        /// The GitHub error.
+        #[source]
+        source: GitHubOperationError,
+    },
+
+    /// The webhook could not be reached or did not accept the notification.
+    #[error("escalation webhook {url} failed: {message}")]
+    Webhook {
+        /// The configured URL.
+        url: String,
+        /// Transport error or rejected status.
+        message: String,
+    },
+}
+
+/// Sends a notification for an [`Escalation`].
+#[async_trait]
+pub trait Escalator: Send + Sync {
+    /// Short name used in logs (e.g. `"issue-mention"`).
+    fn name(&self) -> &str;
+
+    /// Notifies about `escalation`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EscalationError`] if the notification was not delivered.
+    async fn escalate(&self, escalation: &Escalation) -> Result<(), EscalationError>;
+}
+
+/// Notifies every escalator about `escalation`, in order.
+///
+/// Failures are logged and collected; every escalator is tried. Returns the
+/// failures with the name of the escalator that produced each.
+#[instrument(skip_all, fields(run_id = %escalation.run_id, outcome = ?escalation.outcome))]
+pub async fn escalate_all(
+    escalators: &[Arc<dyn Escalator>],
+    escalation: &Escalation,
+) -> Vec<(String, EscalationError)> {
+    let mut failures = Vec::new();
+    for escalator in escalators {
+        match escalator.escalate(escalation).await {
+            Ok(()) => tracing::info!(escalator = escalator.name(), "escalation sent"),
+            Err(error) => {
+                tracing::warn!(escalator = escalator.name(), %error, "escalation failed");
+                failures.push((escalator.name().to_string(), error));
+            }
+        }
+    }
+    failures
+}
+
+// ─── Issue mention ──────────────────────────────────────────────────────────
+
+/// Posts a comment on the work item mentioning the configured handles.
+pub struct IssueMentionEscalator {
+    tracker: Arc<dyn IssueTracker>,
+    mentions: Vec<String>,
+    messages: MessageCatalog,
+}
+
+impl IssueMentionEscalator {
+    /// Creates an escalator mentioning `mentions` (user logins or
+    /// `org/team` names, with or without the leading `@`).
+    pub fn new(tracker: Arc<dyn IssueTracker>, mentions: Vec<String>) -> Self {
+        Self {
+            tracker,
+            mentions,
+            messages: MessageCatalog::english(),
+        }
+    }
+
+    /// Uses `messages` for the comment text instead of English.
+    #[must_use]
+    pub fn with_messages(mut self, messages: MessageCatalog) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Renders the comment body for `escalation`.
+    pub fn comment_body(&self, escalation: &Escalation) -> String {
+        let mentions: Vec<String> = self
+            .mentions
+            .iter()
+            .map(|handle| format!("@{}", handle.trim_start_matches('@')))
+            .collect();
+        let summary = escalation.summary(&self.messages);
+        if mentions.is_empty() {
+            summary
+        } else {
+            format!("{} {summary}", mentions.join(" "))
+        }
+    }
+}
+
+#[async_trait]
+impl Escalator for IssueMentionEscalator {
+    fn name(&self) -> &str {
+        "issue-mention"
+    }
+
+    async fn escalate(&self, escalation: &Escalation) -> Result<(), EscalationError> {
+        self.tracker
+            .post_comment(escalation.work_item_id, &self.comment_body(escalation))
+            .await
+            .map_err(|source| EscalationError::Comment { source })
+    }
+}
+
+// ─── Webhook ────────────────────────────────────────────────────────────────
+
+/// JSON body POSTed by [`WebhookEscalator`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscalationPayload {
+    /// The run that stopped.
+    pub run_id: PipelineRunId,
+    /// The work item the run is processing.
+    pub work_item_id: WorkItemId,
+    /// How the step ended.
+    pub outcome: PipelineOutcome,
+    /// Why the run halted, if known.
+    pub reason: Option<HaltReason>,
+    /// Free-text detail.
+    pub detail: Option<String>,
+    /// One-line description ([`Escalation::summary`]), in English.
+    pub summary: String,
+}
+
+impl From<&Escalation> for EscalationPayload {
+    fn from(escalation: &Escalation) -> Self {
+        Self {
+            run_id: escalation.run_id,
+            work_item_id: escalation.work_item_id,
+            outcome: escalation.outcome,
+            reason: escalation.reason,
+            detail: escalation.detail.clone(),
+            summary: escalation.summary(&MessageCatalog::english()),
+        }
+    }
+}
+
+/// Sends an HTTP POST for [`WebhookEscalator`].
+///
+/// The production implementation is `cli::escalation_webhook::HttpWebhookTransport`;
+/// tests record the requests.
+#[async_trait]
+pub trait WebhookTransport: Send + Sync {
+    /// POSTs `payload` as JSON to `url` and returns the response status.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the failure if no response was received.
+    async fn post_json(&self, url: &str, payload: &EscalationPayload) -> Result<u16, String>;
+}
+
+/// POSTs an [`EscalationPayload`] to a configured URL.
+pub struct WebhookEscalator {
+    url: String,
+    transport: Arc<dyn WebhookTransport>,
+}
+
+impl WebhookEscalator {
+    /// Creates an escalator POSTing to `url` through `transport`.
+    pub fn new(url: impl Into<String>, transport: Arc<dyn WebhookTransport>) -> Self {
+        Self {
+            url: url.into(),
+            transport,
+        }
+    }
+}
+
+#[async_trait]
+impl Escalator for WebhookEscalator {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn escalate(&self, escalation: &Escalation) -> Result<(), EscalationError> {
+        let payload = EscalationPayload::from(escalation);
+        let status = self
+            .transport
+            .post_json(&self.url, &payload)
+            .await
+            .map_err(|message| EscalationError::Webhook {
+                url: self.url.clone(),
+                message,
+            })?;
+        if (200..300).contains(&status) {
+            Ok(())
+        } else {
+            Err(EscalationError::Webhook {
+                url: self.url.clone(),
+                message: format!("responded with status {status}"),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "escalation_tests.rs"]
+mod tests;
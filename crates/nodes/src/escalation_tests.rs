@@ -0,0 +1,333 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use pipeline::{PipelineRunId, WorkItemId};
+
+use crate::test_support::FakeIssueTracker;
+
+use super::*;
+
+/// [`WebhookTransport`] recording every POST and answering with `status`, or
+/// failing with `error` when set.
+struct RecordingTransport {
+    status: u16,
+    error: Option<String>,
+    posts: Mutex<Vec<(String, EscalationPayload)>>,
+}
+
+impl RecordingTransport {
+    fn responding(status: u16) -> Self {
+        Self {
+            status,
+            error: None,
+            posts: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn unreachable(error: &str) -> Self {
+        Self {
+            error: Some(error.to_string()),
+            ..Self::responding(0)
+        }
+    }
+
+    fn posts(&self) -> Vec<(String, EscalationPayload)> {
+        self.posts.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl WebhookTransport for RecordingTransport {
+    async fn post_json(&self, url: &str, payload: &EscalationPayload) -> Result<u16, String> {
+        self.posts
+            .lock()
+            .unwrap()
+            .push((url.to_string(), payload.clone()));
+        match &self.error {
+            Some(error) => Err(error.clone()),
+            None => Ok(self.status),
+        }
+    }
+}
+
+/// [`Escalator`] counting its calls and optionally failing.
+struct CountingEscalator {
+    name: &'static str,
+    fail: bool,
+    calls: Mutex<u32>,
+}
+
+impl CountingEscalator {
+    fn new(name: &'static str, fail: bool) -> Self {
+        Self {
+            name,
+            fail,
+            calls: Mutex::new(0),
+        }
+    }
+
+    fn calls(&self) -> u32 {
+        *self.calls.lock().unwrap()
+    }
+}
+
+#[async_trait]
+impl Escalator for CountingEscalator {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    async fn escalate(&self, _escalation: &Escalation) -> Result<(), EscalationError> {
+        *self.calls.lock().unwrap() += 1;
+        if self.fail {
+            Err(EscalationError::Webhook {
+                url: "https://hooks.example.com".to_string(),
+                message: "refused".to_string(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn work_item() -> WorkItemId {
+    WorkItemId::new(42)
+}
+
+fn step(outcome: Option<PipelineOutcome>) -> StepResult {
+    let mut step = StepResult::new(PipelineRunId::new_random(), work_item());
+    step.outcome = outcome;
+    step
+}
+
+fn halt() -> Escalation {
+    Escalation::from_step(
+        &step(Some(PipelineOutcome::Failed)),
+        Some(HaltReason::ScopeEnforcer),
+        Some("edited src/unrelated.rs".to_string()),
+    )
+    .unwrap()
+}
+
+// ─── Escalation ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_needs_escalation_each_outcome_matches_human_attention() {
+    assert!(needs_escalation(PipelineOutcome::HumanGated));
+    assert!(needs_escalation(PipelineOutcome::Escalated));
+    assert!(needs_escalation(PipelineOutcome::Failed));
+    assert!(!needs_escalation(PipelineOutcome::Completed));
+}
+
+#[test]
+fn test_from_step_halted_step_returns_escalation() {
+    let step = step(Some(PipelineOutcome::Failed));
+
+    let escalation = Escalation::from_step(&step, Some(HaltReason::RetryBudget), None).unwrap();
+
+    assert_eq!(escalation.run_id, step.run_id);
+    assert_eq!(escalation.work_item_id, work_item());
+    assert_eq!(escalation.outcome, PipelineOutcome::Failed);
+    assert_eq!(escalation.reason, Some(HaltReason::RetryBudget));
+}
+
+#[test]
+fn test_from_step_completed_step_returns_none() {
+    assert_eq!(
+        Escalation::from_step(&step(Some(PipelineOutcome::Completed)), None, None),
+        None
+    );
+}
+
+#[test]
+fn test_from_step_running_step_returns_none() {
+    assert_eq!(Escalation::from_step(&step(None), None, None), None);
+}
+
+#[test]
+fn test_summary_halt_includes_reason_and_detail() {
+    let escalation = halt();
+
+    let summary = escalation.summary(&MessageCatalog::english());
+
+    assert_eq!(
+        summary,
+        format!(
+            "CogWorks run {} on #42 halted (scope_enforcer): edited src/unrelated.rs",
+            escalation.run_id
+        )
+    );
+}
+
+#[test]
+fn test_summary_human_gate_without_reason_is_message_only() {
+    let escalation =
+        Escalation::from_step(&step(Some(PipelineOutcome::HumanGated)), None, None).unwrap();
+
+    let summary = escalation.summary(&MessageCatalog::english());
+
+    assert_eq!(
+        summary,
+        format!(
+            "CogWorks run {} on #42 is waiting for human review",
+            escalation.run_id
+        )
+    );
+}
+
+// ─── Issue mention ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_comment_body_mentions_normalise_leading_at() {
+    let tracker = Arc::new(FakeIssueTracker::default());
+    let escalator = IssueMentionEscalator::new(
+        tracker,
+        vec!["@octocat".to_string(), "acme/oncall".to_string()],
+    );
+
+    let body = escalator.comment_body(&halt());
+
+    assert!(body.starts_with("@octocat @acme/oncall CogWorks run "));
+}
+
+#[test]
+fn test_comment_body_no_mentions_is_summary_only() {
+    let tracker = Arc::new(FakeIssueTracker::default());
+    let escalator = IssueMentionEscalator::new(tracker, Vec::new());
+    let escalation = halt();
+
+    let body = escalator.comment_body(&escalation);
+
+    assert_eq!(body, escalation.summary(&MessageCatalog::english()));
+}
+
+#[test]
+fn test_comment_body_override_catalog_uses_translated_summary() {
+    let tracker = Arc::new(FakeIssueTracker::default());
+    let messages = MessageCatalog::with_overrides(HashMap::from([(
+        "escalation.failed".to_string(),
+        "L'exécution CogWorks {run} sur #{work_item} est arrêtée".to_string(),
+    )]));
+    let escalator =
+        IssueMentionEscalator::new(tracker, vec!["octocat".to_string()]).with_messages(messages);
+    let escalation = halt();
+
+    let body = escalator.comment_body(&escalation);
+
+    assert!(body.starts_with(&format!(
+        "@octocat L'exécution CogWorks {} sur #42 est arrêtée",
+        escalation.run_id
+    )));
+}
+
+#[tokio::test]
+async fn test_issue_mention_escalate_halt_posts_comment_on_work_item() {
+    let tracker = Arc::new(FakeIssueTracker::default());
+    let escalator = IssueMentionEscalator::new(tracker.clone(), vec!["octocat".to_string()]);
+
+    escalator.escalate(&halt()).await.unwrap();
+
+    let comments = tracker.comment_bodies(work_item());
+    assert_eq!(comments.len(), 1);
+    assert!(comments[0].starts_with("@octocat "));
+    assert!(comments[0].contains("(scope_enforcer)"));
+}
+
+// ─── Webhook ────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_payload_from_escalation_copies_fields_and_summary() {
+    let escalation = halt();
+
+    let payload = EscalationPayload::from(&escalation);
+
+    assert_eq!(payload.run_id, escalation.run_id);
+    assert_eq!(payload.work_item_id, work_item());
+    assert_eq!(payload.outcome, PipelineOutcome::Failed);
+    assert_eq!(payload.reason, Some(HaltReason::ScopeEnforcer));
+    assert_eq!(payload.detail.as_deref(), Some("edited src/unrelated.rs"));
+    assert_eq!(
+        payload.summary,
+        escalation.summary(&MessageCatalog::english())
+    );
+}
+
+#[tokio::test]
+async fn test_webhook_escalate_halt_posts_payload_to_url() {
+    let transport = Arc::new(RecordingTransport::responding(204));
+    let escalator = WebhookEscalator::new("https://hooks.example.com/cogworks", transport.clone());
+    let escalation = halt();
+
+    escalator.escalate(&escalation).await.unwrap();
+
+    let posts = transport.posts();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].0, "https://hooks.example.com/cogworks");
+    assert_eq!(posts[0].1, EscalationPayload::from(&escalation));
+}
+
+#[tokio::test]
+async fn test_webhook_escalate_rejected_status_returns_webhook_error() {
+    let transport = Arc::new(RecordingTransport::responding(500));
+    let escalator = WebhookEscalator::new("https://hooks.example.com/cogworks", transport);
+
+    let error = escalator.escalate(&halt()).await.unwrap_err();
+
+    match error {
+        EscalationError::Webhook { url, message } => {
+            assert_eq!(url, "https://hooks.example.com/cogworks");
+            assert!(message.contains("500"));
+        }
+        other => panic!("expected a webhook error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_webhook_escalate_unreachable_returns_transport_message() {
+    let transport = Arc::new(RecordingTransport::unreachable("connection refused"));
+    let escalator = WebhookEscalator::new("https://hooks.example.com/cogworks", transport);
+
+    let error = escalator.escalate(&halt()).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        EscalationError::Webhook { message, .. } if message == "connection refused"
+    ));
+}
+
+// ─── escalate_all ───────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_escalate_all_halt_fires_mention_and_webhook() {
+    let tracker = Arc::new(FakeIssueTracker::default());
+    let transport = Arc::new(RecordingTransport::responding(200));
+    let escalators: Vec<Arc<dyn Escalator>> = vec![
+        Arc::new(IssueMentionEscalator::new(
+            tracker.clone(),
+            vec!["octocat".to_string()],
+        )),
+        Arc::new(WebhookEscalator::new(
+            "https://hooks.example.com/cogworks",
+            transport.clone(),
+        )),
+    ];
+
+    let failures = escalate_all(&escalators, &halt()).await;
+
+    assert!(failures.is_empty());
+    assert_eq!(tracker.comment_bodies(work_item()).len(), 1);
+    assert_eq!(transport.posts().len(), 1);
+}
+
+#[tokio::test]
+async fn test_escalate_all_failing_escalator_still_runs_the_rest() {
+    let failing = Arc::new(CountingEscalator::new("first", true));
+    let succeeding = Arc::new(CountingEscalator::new("second", false));
+    let escalators: Vec<Arc<dyn Escalator>> = vec![failing.clone(), succeeding.clone()];
+
+    let failures = escalate_all(&escalators, &halt()).await;
+
+    assert_eq!(failing.calls(), 1);
+    assert_eq!(succeeding.calls(), 1);
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, "first");
+}
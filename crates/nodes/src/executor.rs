@@ -0,0 +1,799 @@
+//! Pipeline executor and its result types.
+//!
+//! [`PipelineExecutor`] holds the validated [`PipelineGraph`] and the [`Node`]
+//! implementation for each node in it. [`StepResult`] summarises one
+//! step-function invocation: which nodes ran, which edges were evaluated, and
+//! what it cost.
+//!
+//! ## Single-Node Execution
+//!
+//! [`PipelineExecutor::run_node`] runs one node in isolation for debugging
+//! (`cogworks run-node`). It bypasses eligibility checks and edge evaluation
+//! and does not modify the [`PipelineState`]; the caller gets the
+//! [`NodeOutcome`] and nothing else changes.
+//!
+//! ## Ready-Batch Ordering
+//!
+//! When several nodes are ready at once, [`PipelineExecutor::prioritize`]
+//! orders them by
+//! [`NodeDefinition::priority`](pipeline::NodeDefinition::priority), highest
+//! first, so cheap nodes declared with a high priority run (and fail) before
+//! expensive ones.
+//! [`PipelineExecutor::run_ready_batch`] runs a batch in that order.
+//!
+//! [`PipelineExecutor::run_parallel_batch`] runs a batch concurrently instead.
+//! Its nodes finish in whatever order the scheduler and the LLM providers
+//! produce, so their results are collected and applied in
+//! [`prioritize`](PipelineExecutor::prioritize) order, not completion order.
+//! Two runs of the same batch therefore produce the same [`StepResult`] and
+//! state, which keeps step summaries and snapshots stable.
+//!
+//! ## Checkpointing and Resume
+//!
+//! [`PipelineExecutor::run_nodes`] persists the [`PipelineState`] through a
+//! [`CheckpointStore`] after every node finishes. If GitHub becomes
+//! unreachable part-way through a step, the nodes completed before the failure
+//! are already recorded in the persisted state. Running the same sequence
+//! again against that state skips them instead of re-executing them.
+//!
+//! ## Alignment Re-check Loop
+//!
+//! [`PipelineExecutor::run_alignment_loop`] handles an alignment check that
+//! fails with blocking findings. It runs a configured fix node and then runs
+//! alignment again, stopping when alignment passes or the fix node has been
+//! re-run [`AlignmentLoop::max_iterations`] times. Each fix counts as a rework
+//! of the fix node ([`NodeState::rework_count`]), so the bound holds across
+//! steps and resumes.
+//!
+//! ## Budget Enforcement
+//!
+//! With a [`BudgetEnforcer`] set through [`PipelineExecutor::with_budget`],
+//! [`PipelineExecutor::run_nodes`] checks the run's accumulated cost after
+//! each node. Once the budget is reached the sequence halts and the halt is
+//! recorded in the [`StepResult`]. An overshoot within the grace
+//! ([`BudgetDecision::FinishNode`]) keeps the node's outcome and records the
+//! overshoot in [`StepResult::budget_overshoot`]. An overshoot past the
+//! grace ([`BudgetDecision::Halt`]) halts the node itself: it is recorded as
+//! failed, so a resume after the budget is raised runs it again.
+//!
+//! Every halt goes through [`PipelineExecutor::halt`], which also notifies the
+//! escalators set with [`PipelineExecutor::with_escalators`]. Callers halting
+//! for other reasons (e.g. [`AlignmentLoopOutcome::halt`]) use it too.
+//!
+//! ## Cost Attribution
+//!
+//! Node cost (LLM calls made by nodes) and edge cost (LLM-evaluated edge
+//! conditions) are accumulated separately so that reports can show how much
+//! of a run was spent deciding *where to go* versus *doing the work*.
+//! [`StepResult::total_cost`] is their sum and is what counts against the
+//! pipeline budget.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §PipelineExecutor.
+
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::instrument;
+
+use pipeline::{
+    CogWorksError, Diagnostic, EdgeEvaluationRecord, EdgeId, GitHubOperationError, HaltReason,
+    NodeId, NodeState, NodeStatus, PipelineGraph, PipelineOutcome, PipelineRunId, PipelineState,
+    PullRequestId, TokenCost, WorkItemId,
+};
+
+use crate::{
+    budget::{BudgetDecision, BudgetEnforcer},
+    escalation::{escalate_all, Escalation, Escalator},
+    review::{review, DiagnosticSource, ReviewVerdict},
+};
+
+/// Default number of fix iterations in an alignment re-check loop.
+pub const DEFAULT_ALIGNMENT_MAX_ITERATIONS: u32 = 3;
+
+// ─── Node contract ──────────────────────────────────────────────────────────
+
+/// Result of executing one node once.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeOutcome {
+    /// The node finished and its outputs are available.
+    Completed {
+        /// Cost of the node's LLM calls.
+        cost: TokenCost,
+    },
+    /// The node produced output that must be reviewed by a human before the
+    /// pipeline can continue.
+    AwaitingHumanReview {
+        /// Cost of the node's LLM calls.
+        cost: TokenCost,
+    },
+    /// The node failed.
+    Failed {
+        /// Description of the failure.
+        error: String,
+        /// Cost incurred before the failure.
+        cost: TokenCost,
+    },
+}
+
+impl NodeOutcome {
+    /// Returns the cost incurred by the execution.
+    pub fn cost(&self) -> TokenCost {
+        match self {
+            Self::Completed { cost }
+            | Self::AwaitingHumanReview { cost }
+            | Self::Failed { cost, .. } => *cost,
+        }
+    }
+}
+
+/// A pipeline node implementation.
+#[async_trait]
+pub trait Node: Send + Sync {
+    /// Execute the node once against the current run state.
+    ///
+    /// Implementations read `state` but never modify it; state transitions are
+    /// applied by the executor from the returned [`NodeOutcome`].
+    async fn execute(&self, state: &PipelineState) -> NodeOutcome;
+}
+
+/// Persists run state at node boundaries.
+///
+/// The production implementation writes the pipeline state comment on the
+/// work item via `IssueTracker::upsert_comment`.
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// Persist `state` so that a later run can resume from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the GitHub error if the state could not be written.
+    async fn save(&self, state: &PipelineState) -> Result<(), GitHubOperationError>;
+}
+
+// ─── Executor ───────────────────────────────────────────────────────────────
+
+/// Errors returned by [`PipelineExecutor`] operations.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ExecutorError {
+    /// The node is not declared in the pipeline graph.
+    #[error("node '{node}' is not declared in the pipeline graph")]
+    UnknownNode {
+        /// The requested node.
+        node: NodeId,
+    },
+
+    /// The node is declared in the graph but no implementation is registered.
+    #[error("no implementation registered for node '{node}'")]
+    MissingImplementation {
+        /// The node without an implementation.
+        node: NodeId,
+    },
+
+    /// The node ran but the state recording its outcome could not be
+    /// persisted. The in-memory state is up to date; the persisted state is
+    /// not, so a resume from persisted state runs the node again.
+    #[error("failed to checkpoint state after node '{node}'")]
+    CheckpointFailed {
+        /// The node whose outcome was not persisted.
+        node: NodeId,
+        /// The underlying GitHub error.
+        #[source]
+        source: GitHubOperationError,
+    },
+
+    /// The alignment findings could not be collected.
+    #[error("alignment check failed to run")]
+    AlignmentCheckFailed {
+        /// The error reported by the diagnostic source.
+        #[source]
+        source: CogWorksError,
+    },
+}
+
+// ─── Alignment loop ─────────────────────────────────────────────────────────
+
+/// Configuration of the alignment re-check loop.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlignmentLoop {
+    /// Node run to fix blocking alignment findings.
+    pub fix_node: NodeId,
+    /// Maximum number of times `fix_node` may be re-run for alignment.
+    #[serde(default = "default_alignment_max_iterations")]
+    pub max_iterations: u32,
+}
+
+fn default_alignment_max_iterations() -> u32 {
+    DEFAULT_ALIGNMENT_MAX_ITERATIONS
+}
+
+impl AlignmentLoop {
+    /// Creates a loop fixing findings with `fix_node`, bounded by
+    /// [`DEFAULT_ALIGNMENT_MAX_ITERATIONS`].
+    pub fn new(fix_node: NodeId) -> Self {
+        Self {
+            fix_node,
+            max_iterations: DEFAULT_ALIGNMENT_MAX_ITERATIONS,
+        }
+    }
+}
+
+/// How an alignment re-check loop ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlignmentLoopOutcome {
+    /// Alignment passed.
+    Passed {
+        /// Fix iterations run in this call before the pass.
+        fixes: u32,
+        /// Warnings reported by the passing check.
+        warnings: Vec<Diagnostic>,
+    },
+    /// Alignment still fails and the iteration limit has been reached; the
+    /// run halts with [`AlignmentLoopOutcome::halt`].
+    LimitReached {
+        /// Fix iterations run in this call.
+        fixes: u32,
+        /// Blocking findings of the last check.
+        blocking: Vec<Diagnostic>,
+    },
+    /// The fix node failed or is awaiting human review.
+    FixIncomplete {
+        /// Fix iterations run in this call, including the incomplete one.
+        fixes: u32,
+        /// Outcome of the incomplete fix.
+        outcome: NodeOutcome,
+    },
+}
+
+impl AlignmentLoopOutcome {
+    /// The error the run halts with: a [`CogWorksError::PipelineHalt`] with
+    /// [`HaltReason::ReworkLimit`] for [`AlignmentLoopOutcome::LimitReached`],
+    /// `None` for every other outcome.
+    pub fn halt(&self) -> Option<CogWorksError> {
+        match self {
+            Self::LimitReached { fixes, blocking } => Some(CogWorksError::halt(
+                HaltReason::ReworkLimit,
+                format!(
+                    "alignment still failing after {fixes} fix iteration(s) with {} blocking finding(s)",
+                    blocking.len()
+                ),
+            )),
+            Self::Passed { .. } | Self::FixIncomplete { .. } => None,
+        }
+    }
+}
+
+/// Drives a pipeline graph by executing its nodes.
+pub struct PipelineExecutor {
+    graph: PipelineGraph,
+    nodes: HashMap<NodeId, Arc<dyn Node>>,
+    budget: Option<BudgetEnforcer>,
+    escalators: Vec<Arc<dyn Escalator>>,
+}
+
+impl PipelineExecutor {
+    /// Creates an executor for a validated `graph`.
+    ///
+    /// `nodes` maps each [`NodeId`] in the graph to its implementation. The
+    /// executor enforces no budget until [`PipelineExecutor::with_budget`].
+    pub fn new(graph: PipelineGraph, nodes: HashMap<NodeId, Arc<dyn Node>>) -> Self {
+        Self {
+            graph,
+            nodes,
+            budget: None,
+            escalators: Vec::new(),
+        }
+    }
+
+    /// Notifies `escalators` whenever the run halts
+    /// ([`PipelineExecutor::halt`]).
+    #[must_use]
+    pub fn with_escalators(mut self, escalators: Vec<Arc<dyn Escalator>>) -> Self {
+        self.escalators = escalators;
+        self
+    }
+
+    /// Checks the run's cost against `budget` after every node run by
+    /// [`PipelineExecutor::run_nodes`].
+    #[must_use]
+    pub fn with_budget(mut self, budget: BudgetEnforcer) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Returns the graph this executor drives.
+    pub fn graph(&self) -> &PipelineGraph {
+        &self.graph
+    }
+
+    /// Run a single node regardless of its position in the graph.
+    ///
+    /// Upstream completion, gates, and outgoing edges are not evaluated, and
+    /// `state` is not modified: the graph does not advance. Intended for
+    /// debugging a node (`cogworks run-node`).
+    ///
+    /// # Errors
+    ///
+    /// - [`ExecutorError::UnknownNode`] — `node` is not in the graph.
+    /// - [`ExecutorError::MissingImplementation`] — no [`Node`] registered for it.
+    #[instrument(skip(self, state), fields(run_id = %state.run_id))]
+    pub async fn run_node(
+        &self,
+        state: &PipelineState,
+        node: &NodeId,
+    ) -> Result<NodeOutcome, ExecutorError> {
+        if !self
+            .graph
+            .nodes
+            .iter()
+            .any(|definition| &definition.id == node)
+        {
+            return Err(ExecutorError::UnknownNode { node: node.clone() });
+        }
+        let implementation = self
+            .nodes
+            .get(node)
+            .ok_or_else(|| ExecutorError::MissingImplementation { node: node.clone() })?;
+
+        let outcome = implementation.execute(state).await;
+        tracing::info!(%node, ?outcome, "single node run finished");
+        Ok(outcome)
+    }
+
+    /// Run `nodes` in order, checkpointing `state` after each one.
+    ///
+    /// Nodes already [`NodeStatus::Completed`] in `state` are skipped, which
+    /// makes the call resumable: after a failure, calling it again with the
+    /// persisted state picks up at the first unfinished node. The sequence
+    /// stops early, without error, at the first node that does not complete
+    /// (failed or awaiting human review); its status is recorded in `state`.
+    ///
+    /// Every executed node and its cost are recorded in `step`, including the
+    /// node the sequence stopped at.
+    ///
+    /// With a budget set, the sequence also stops after the node whose cost
+    /// reaches it, and the halt is recorded in `step` (see
+    /// [Budget Enforcement](self#budget-enforcement)).
+    ///
+    /// # Errors
+    ///
+    /// - [`ExecutorError::UnknownNode`] / [`ExecutorError::MissingImplementation`]
+    ///   — as for [`PipelineExecutor::run_node`].
+    /// - [`ExecutorError::CheckpointFailed`] — the state could not be persisted
+    ///   after a node finished. Nodes checkpointed before it stay skipped on
+    ///   resume.
+    #[instrument(skip_all, fields(run_id = %state.run_id))]
+    pub async fn run_nodes(
+        &self,
+        state: &mut PipelineState,
+        nodes: &[NodeId],
+        checkpoints: &dyn CheckpointStore,
+        step: &mut StepResult,
+    ) -> Result<(), ExecutorError> {
+        for node in nodes {
+            let already_completed = state
+                .node_states
+                .get(node)
+                .is_some_and(|node_state| node_state.status == NodeStatus::Completed);
+            if already_completed {
+                tracing::debug!(%node, "skipping node completed before resume");
+                continue;
+            }
+
+            let outcome = self.run_node(state, node).await?;
+            apply_outcome(state, node, &outcome);
+            step.record_node(node.clone(), outcome.cost());
+            let halt = self.enforce_budget(state, node, step);
+
+            checkpoints
+                .save(state)
+                .await
+                .map_err(|source| ExecutorError::CheckpointFailed {
+                    node: node.clone(),
+                    source,
+                })?;
+
+            if let Some(halt) = halt {
+                self.halt(step, &halt).await;
+                break;
+            }
+            if !matches!(outcome, NodeOutcome::Completed { .. }) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Halts the run: records `error` in `step` with
+    /// [`StepResult::record_halt`] and notifies every escalator set through
+    /// [`PipelineExecutor::with_escalators`].
+    ///
+    /// Escalation is best effort; failures are logged by [`escalate_all`] and
+    /// do not change the step.
+    pub async fn halt(&self, step: &mut StepResult, error: &CogWorksError) {
+        step.record_halt(error);
+        let escalation = Escalation::from_step(step, step.halt_reason, step.halt_detail.clone());
+        if let Some(escalation) = escalation {
+            escalate_all(&self.escalators, &escalation).await;
+        }
+    }
+
+    /// Checks the run's accumulated cost after `node` ran. Returns the error
+    /// to halt with, having recorded any overshoot in `step` and, past the
+    /// grace, marked `node` failed.
+    fn enforce_budget(
+        &self,
+        state: &mut PipelineState,
+        node: &NodeId,
+        step: &mut StepResult,
+    ) -> Option<CogWorksError> {
+        let budget = self.budget?;
+        match budget.check(state.cost_accumulator) {
+            BudgetDecision::Continue => None,
+            BudgetDecision::FinishNode { overshoot, halt } => {
+                tracing::warn!(%node, %overshoot, "halting after node within budget grace");
+                step.budget_overshoot = Some(overshoot);
+                Some(halt)
+            }
+            BudgetDecision::Halt(halt) => {
+                tracing::error!(%node, "halting node past budget grace");
+                if let Some(node_state) = state.node_states.get_mut(node) {
+                    node_state.status = NodeStatus::Failed;
+                    node_state.current_error = Some(halt.to_string());
+                }
+                Some(halt)
+            }
+        }
+    }
+
+    /// Orders a batch of ready nodes for execution.
+    ///
+    /// Sorts by
+    /// [`NodeDefinition::priority`](pipeline::NodeDefinition::priority),
+    /// highest first. The sort is stable, so nodes of equal priority keep
+    /// their order in `ready`. Nodes not declared in the graph are treated as
+    /// priority `0`.
+    #[must_use]
+    pub fn prioritize(&self, ready: &[NodeId]) -> Vec<NodeId> {
+        let priority = |node: &NodeId| {
+            self.graph
+                .nodes
+                .iter()
+                .find(|definition| &definition.id == node)
+                .map_or(0, |definition| definition.priority)
+        };
+        let mut ordered = ready.to_vec();
+        ordered.sort_by_key(|node| std::cmp::Reverse(priority(node)));
+        ordered
+    }
+
+    /// Run a batch of ready nodes in [`prioritize`](Self::prioritize) order.
+    ///
+    /// Equivalent to [`PipelineExecutor::run_nodes`] on the ordered batch: the
+    /// batch stops at the first node that does not complete.
+    ///
+    /// # Errors
+    ///
+    /// As for [`PipelineExecutor::run_nodes`].
+    pub async fn run_ready_batch(
+        &self,
+        state: &mut PipelineState,
+        ready: &[NodeId],
+        checkpoints: &dyn CheckpointStore,
+        step: &mut StepResult,
+    ) -> Result<(), ExecutorError> {
+        let ordered = self.prioritize(ready);
+        tracing::debug!(order = ?ordered, "running ready batch");
+        self.run_nodes(state, &ordered, checkpoints, step).await
+    }
+
+    /// Run a batch of ready nodes concurrently.
+    ///
+    /// Every node not already [`NodeStatus::Completed`] executes against the
+    /// same snapshot of `state`. Their outcomes are applied to `state`,
+    /// recorded in `step`, and checkpointed one node at a time in
+    /// [`prioritize`](Self::prioritize) order, regardless of which finished
+    /// first. Unlike
+    /// [`PipelineExecutor::run_ready_batch`], a failing node does not stop the
+    /// others. A node that panics is recorded as failed.
+    ///
+    /// # Errors
+    ///
+    /// - [`ExecutorError::UnknownNode`] / [`ExecutorError::MissingImplementation`]
+    ///   — as for [`PipelineExecutor::run_node`]; checked before any node runs.
+    /// - [`ExecutorError::CheckpointFailed`] — the state could not be persisted
+    ///   after applying the named node's outcome. Outcomes of nodes after it
+    ///   are discarded; they run again on resume.
+    #[instrument(skip_all, fields(run_id = %state.run_id))]
+    pub async fn run_parallel_batch(
+        &self,
+        state: &mut PipelineState,
+        ready: &[NodeId],
+        checkpoints: &dyn CheckpointStore,
+        step: &mut StepResult,
+    ) -> Result<(), ExecutorError> {
+        let mut batch = Vec::new();
+        for node in self.prioritize(ready) {
+            let already_completed = state
+                .node_states
+                .get(&node)
+                .is_some_and(|node_state| node_state.status == NodeStatus::Completed);
+            if already_completed {
+                tracing::debug!(%node, "skipping node completed before resume");
+                continue;
+            }
+            if !self
+                .graph
+                .nodes
+                .iter()
+                .any(|definition| definition.id == node)
+            {
+                return Err(ExecutorError::UnknownNode { node });
+            }
+            let implementation = self
+                .nodes
+                .get(&node)
+                .cloned()
+                .ok_or_else(|| ExecutorError::MissingImplementation { node: node.clone() })?;
+            batch.push((node, implementation));
+        }
+        if batch.is_empty() {
+            return Ok(());
+        }
+        tracing::debug!(order = ?batch.iter().map(|(node, _)| node).collect::<Vec<_>>(), "running parallel batch");
+
+        let snapshot = Arc::new(state.clone());
+        let handles: Vec<_> = batch
+            .into_iter()
+            .map(|(node, implementation)| {
+                let snapshot = Arc::clone(&snapshot);
+                let handle = tokio::spawn(async move { implementation.execute(&snapshot).await });
+                (node, handle)
+            })
+            .collect();
+
+        // Awaiting the handles in batch order collects the outcomes in
+        // priority order, whatever order the nodes actually finished in.
+        for (node, handle) in handles {
+            let outcome = handle.await.unwrap_or_else(|error| NodeOutcome::Failed {
+                error: format!("node task did not finish: {error}"),
+                cost: TokenCost::zero(),
+            });
+            tracing::info!(%node, ?outcome, "parallel node finished");
+            apply_outcome(state, &node, &outcome);
+            step.record_node(node.clone(), outcome.cost());
+
+            checkpoints
+                .save(state)
+                .await
+                .map_err(|source| ExecutorError::CheckpointFailed { node, source })?;
+        }
+        Ok(())
+    }
+
+    /// Check alignment, and while it reports blocking findings, run the fix
+    /// node and check again.
+    ///
+    /// Stops when alignment passes, when the fix node does not complete, or
+    /// when `config.fix_node` has reached `config.max_iterations` reworks.
+    /// Every fix run increments the fix node's `rework_count`, is recorded in
+    /// `step`, and is checkpointed like a node in
+    /// [`PipelineExecutor::run_nodes`].
+    ///
+    /// # Errors
+    ///
+    /// - [`ExecutorError::AlignmentCheckFailed`] — `alignment` could not
+    ///   collect findings.
+    /// - [`ExecutorError::UnknownNode`] / [`ExecutorError::MissingImplementation`]
+    ///   — as for [`PipelineExecutor::run_node`], for the fix node.
+    /// - [`ExecutorError::CheckpointFailed`] — the state could not be persisted
+    ///   after a fix.
+    #[instrument(skip_all, fields(run_id = %state.run_id, fix_node = %config.fix_node))]
+    pub async fn run_alignment_loop(
+        &self,
+        state: &mut PipelineState,
+        alignment: &dyn DiagnosticSource,
+        config: &AlignmentLoop,
+        checkpoints: &dyn CheckpointStore,
+        step: &mut StepResult,
+    ) -> Result<AlignmentLoopOutcome, ExecutorError> {
+        let fix_node = &config.fix_node;
+        let mut fixes = 0;
+        loop {
+            let blocking = match review(alignment, state)
+                .await
+                .map_err(|source| ExecutorError::AlignmentCheckFailed { source })?
+            {
+                ReviewVerdict::Continue { warnings } => {
+                    return Ok(AlignmentLoopOutcome::Passed { fixes, warnings });
+                }
+                ReviewVerdict::Halt { blocking } => blocking,
+            };
+
+            let reworks = state
+                .node_states
+                .get(fix_node)
+                .map_or(0, |node_state| node_state.rework_count);
+            if reworks >= config.max_iterations {
+                tracing::warn!(
+                    reworks,
+                    findings = blocking.len(),
+                    "alignment still failing at iteration limit; halting"
+                );
+                return Ok(AlignmentLoopOutcome::LimitReached { fixes, blocking });
+            }
+
+            tracing::info!(
+                iteration = reworks + 1,
+                findings = blocking.len(),
+                "alignment failed; running fix node"
+            );
+            let outcome = self.run_node(state, fix_node).await?;
+            apply_outcome(state, fix_node, &outcome);
+            if let Some(node_state) = state.node_states.get_mut(fix_node) {
+                node_state.rework_count += 1;
+            }
+            fixes += 1;
+            step.record_node(fix_node.clone(), outcome.cost());
+
+            checkpoints
+                .save(state)
+                .await
+                .map_err(|source| ExecutorError::CheckpointFailed {
+                    node: fix_node.clone(),
+                    source,
+                })?;
+
+            if !matches!(outcome, NodeOutcome::Completed { .. }) {
+                return Ok(AlignmentLoopOutcome::FixIncomplete { fixes, outcome });
+            }
+        }
+    }
+}
+
+/// Records `outcome` for `node` in `state`.
+fn apply_outcome(state: &mut PipelineState, node: &NodeId, outcome: &NodeOutcome) {
+    let node_state = state
+        .node_states
+        .entry(node.clone())
+        .or_insert_with(|| NodeState {
+            status: NodeStatus::Pending,
+            attempt_count: 0,
+            rework_count: 0,
+            current_error: None,
+            rework_edge_traversals: HashMap::new(),
+        });
+    node_state.attempt_count += 1;
+    match outcome {
+        NodeOutcome::Completed { .. } => {
+            node_state.status = NodeStatus::Completed;
+            node_state.current_error = None;
+        }
+        NodeOutcome::AwaitingHumanReview { .. } => node_state.status = NodeStatus::HumanGated,
+        NodeOutcome::Failed { error, .. } => {
+            node_state.status = NodeStatus::Failed;
+            node_state.current_error = Some(error.clone());
+        }
+    }
+    state.cost_accumulator += outcome.cost();
+}
+
+// ─── Step result ────────────────────────────────────────────────────────────
+
+/// Outcome of one step-function invocation.
+#[derive(Debug, Clone)]
+pub struct StepResult {
+    /// The run this step belongs to.
+    pub run_id: PipelineRunId,
+    /// The work item the run is processing.
+    pub work_item_id: WorkItemId,
+    /// How the run ended, or `None` if the run continues in a later step.
+    pub outcome: Option<PipelineOutcome>,
+    /// The pull request opened for the work item, once one exists.
+    pub pull_request: Option<PullRequestId>,
+    /// Nodes executed during this step, in completion order, including nodes
+    /// that failed or stopped for human review; nodes of a parallel batch
+    /// appear in priority order.
+    pub executed_nodes: Vec<NodeId>,
+    /// Every edge evaluation performed during this step, in evaluation order.
+    pub edge_evaluations: Vec<EdgeEvaluationRecord>,
+    /// Cost of LLM calls made by nodes.
+    pub node_cost: TokenCost,
+    /// Cost of LLM-evaluated edge conditions.
+    ///
+    /// Always equals the sum of `edge_evaluations[..].cost`; maintained by
+    /// [`StepResult::record_edge_evaluation`].
+    pub edge_cost: TokenCost,
+    /// Review and validation findings reported during this step, in the order
+    /// they were reported.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Why the run halted, when it halted with a
+    /// [`CogWorksError::PipelineHalt`].
+    pub halt_reason: Option<HaltReason>,
+    /// Detail of the halt, if any.
+    pub halt_detail: Option<String>,
+    /// Spend past the budget allowed by the overshoot grace, when the run
+    /// halted after finishing a node within it.
+    pub budget_overshoot: Option<TokenCost>,
+}
+
+impl StepResult {
+    /// Creates an empty, still-running result for `run_id` on `work_item_id`.
+    pub fn new(run_id: PipelineRunId, work_item_id: WorkItemId) -> Self {
+        Self {
+            run_id,
+            work_item_id,
+            outcome: None,
+            pull_request: None,
+            executed_nodes: Vec::new(),
+            edge_evaluations: Vec::new(),
+            node_cost: TokenCost::zero(),
+            edge_cost: TokenCost::zero(),
+            diagnostics: Vec::new(),
+            halt_reason: None,
+            halt_detail: None,
+            budget_overshoot: None,
+        }
+    }
+
+    /// Records that the run halted with `error`: the outcome becomes
+    /// [`PipelineOutcome::Escalated`] and the halt reason and detail are kept.
+    pub fn record_halt(&mut self, error: &CogWorksError) {
+        self.outcome = Some(PipelineOutcome::Escalated);
+        match error {
+            CogWorksError::PipelineHalt { reason, detail } => {
+                self.halt_reason = Some(*reason);
+                self.halt_detail = detail.clone();
+            }
+            other => self.halt_detail = Some(other.to_string()),
+        }
+    }
+
+    /// Appends findings to [`StepResult::diagnostics`].
+    pub fn record_diagnostics(&mut self, diagnostics: impl IntoIterator<Item = Diagnostic>) {
+        self.diagnostics.extend(diagnostics);
+    }
+
+    /// Records an executed node, whatever its outcome, and the cost of its
+    /// LLM calls.
+    pub fn record_node(&mut self, node: NodeId, cost: TokenCost) {
+        self.executed_nodes.push(node);
+        self.node_cost += cost;
+    }
+
+    /// Records an edge evaluation and adds its cost to [`StepResult::edge_cost`].
+    pub fn record_edge_evaluation(&mut self, record: EdgeEvaluationRecord) {
+        self.edge_cost += record.cost;
+        self.edge_evaluations.push(record);
+    }
+
+    /// Returns the combined node and edge cost of this step.
+    #[must_use]
+    pub fn total_cost(&self) -> TokenCost {
+        self.node_cost + self.edge_cost
+    }
+
+    /// Returns the evaluation cost per edge.
+    ///
+    /// An edge evaluated more than once in the step (e.g. a rework loop) has
+    /// its costs summed. Edges that were evaluated only deterministically map
+    /// to zero.
+    #[must_use]
+    pub fn edge_costs(&self) -> HashMap<EdgeId, TokenCost> {
+        let mut costs: HashMap<EdgeId, TokenCost> = HashMap::new();
+        for record in &self.edge_evaluations {
+            *costs
+                .entry(record.edge_id.clone())
+                .or_insert_with(TokenCost::zero) += record.cost;
+        }
+        costs
+    }
+}
+
+#[cfg(test)]
+#[path = "executor_tests.rs"]
+mod tests;
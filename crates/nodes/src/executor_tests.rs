@@ -0,0 +1,1328 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use pipeline::{
+    CostBudget, DiagnosticCategory, DiagnosticSeverity, EdgeConditionKind, EvaluatorKind,
+    Expression, HaltReason, NaturalLanguageCondition, NodeDefinition, NodeGate, NodeType,
+    PipelineSettings, PipelineToolProfileConfig, ProfileName, Timestamp, ValidationKind,
+};
+
+use crate::{
+    budget::{BudgetEnforcer, OvershootGrace},
+    escalation::{Escalation, Escalator},
+    test_support::pipeline_state,
+};
+
+use super::*;
+
+fn node_id(id: &str) -> NodeId {
+    NodeId::new(id).unwrap()
+}
+
+fn cost(usd: f64) -> TokenCost {
+    TokenCost::new(usd).unwrap()
+}
+
+fn step() -> StepResult {
+    StepResult::new(PipelineRunId::new_random(), WorkItemId::new(42))
+}
+
+fn llm_evaluation(edge: &str, usd: f64) -> EdgeEvaluationRecord {
+    EdgeEvaluationRecord {
+        edge_id: EdgeId::new(edge).unwrap(),
+        condition: EdgeConditionKind::LlmEvaluated(
+            NaturalLanguageCondition::new("the review found no blocking issues").unwrap(),
+        ),
+        input_snapshot: Default::default(),
+        result: true,
+        evaluator: EvaluatorKind::LlmModel {
+            model_id: "claude-sonnet".to_string(),
+        },
+        cost: cost(usd),
+        timestamp: Timestamp::now(),
+    }
+}
+
+fn deterministic_evaluation(edge: &str) -> EdgeEvaluationRecord {
+    EdgeEvaluationRecord {
+        edge_id: EdgeId::new(edge).unwrap(),
+        condition: EdgeConditionKind::Deterministic(
+            Expression::new("review.blocking_count == 0").unwrap(),
+        ),
+        input_snapshot: Default::default(),
+        result: false,
+        evaluator: EvaluatorKind::Deterministic,
+        cost: TokenCost::zero(),
+        timestamp: Timestamp::now(),
+    }
+}
+
+/// Node returning a fixed outcome and counting its executions.
+struct FixedNode {
+    outcome: NodeOutcome,
+    runs: AtomicU32,
+}
+
+impl FixedNode {
+    fn new(outcome: NodeOutcome) -> Arc<Self> {
+        Arc::new(Self {
+            outcome,
+            runs: AtomicU32::new(0),
+        })
+    }
+
+    fn runs(&self) -> u32 {
+        self.runs.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl Node for FixedNode {
+    async fn execute(&self, _state: &PipelineState) -> NodeOutcome {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        self.outcome.clone()
+    }
+}
+
+fn definition(id: &str) -> NodeDefinition {
+    NodeDefinition {
+        id: node_id(id),
+        node_type: NodeType::Llm,
+        declared_inputs: Vec::new(),
+        declared_outputs: Vec::new(),
+        timeout: None,
+        cost_budget: None,
+        gate: NodeGate::AutoProceed,
+        validation_kind: ValidationKind::None,
+        abort_siblings_on_failure: false,
+        priority: 0,
+        model: None,
+    }
+}
+
+fn graph(ids: &[&str]) -> PipelineGraph {
+    PipelineGraph {
+        nodes: ids.iter().map(|id| definition(id)).collect(),
+        edges: Vec::new(),
+        evaluation_modes: HashMap::new(),
+        explicit_edge_lists: HashMap::new(),
+        settings: PipelineSettings {
+            default_timeout: None,
+            default_cost_budget: None,
+            max_node_retries: 3,
+            default_model: None,
+        },
+        tool_profiles: PipelineToolProfileConfig {
+            default_profile: ProfileName::new("default").unwrap(),
+            node_overrides: HashMap::new(),
+        },
+    }
+}
+
+/// [`CheckpointStore`] keeping every saved state, optionally failing one save.
+#[derive(Default)]
+struct FakeCheckpoints {
+    saved: Mutex<Vec<PipelineState>>,
+    /// 1-based index of the save that fails with a transient error.
+    fail_on: Option<usize>,
+}
+
+impl FakeCheckpoints {
+    fn failing_on(save: usize) -> Self {
+        Self {
+            fail_on: Some(save),
+            ..Self::default()
+        }
+    }
+
+    fn saves(&self) -> usize {
+        self.saved.lock().unwrap().len()
+    }
+
+    /// The state most recently persisted.
+    fn last_saved(&self) -> PipelineState {
+        self.saved.lock().unwrap().last().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FakeCheckpoints {
+    async fn save(&self, state: &PipelineState) -> Result<(), GitHubOperationError> {
+        let mut saved = self.saved.lock().unwrap();
+        if self.fail_on == Some(saved.len() + 1) {
+            return Err(GitHubOperationError::Transient {
+                message: "GitHub unreachable".to_string(),
+            });
+        }
+        saved.push(state.clone());
+        Ok(())
+    }
+}
+
+fn status(state: &PipelineState, node: &str) -> Option<NodeStatus> {
+    state
+        .node_states
+        .get(&node_id(node))
+        .map(|node_state| node_state.status)
+}
+
+fn executor(graph_ids: &[&str], nodes: Vec<(&str, Arc<dyn Node>)>) -> PipelineExecutor {
+    let nodes = nodes
+        .into_iter()
+        .map(|(id, node)| (node_id(id), node))
+        .collect();
+    PipelineExecutor::new(graph(graph_ids), nodes)
+}
+
+// ─── StepResult ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_record_edge_evaluation_llm_edge_adds_to_edge_cost() {
+    let mut step = step();
+
+    step.record_edge_evaluation(llm_evaluation("review-to-merge", 0.25));
+
+    assert_eq!(step.edge_cost, cost(0.25));
+    assert_eq!(step.node_cost, TokenCost::zero());
+    assert_eq!(step.edge_evaluations.len(), 1);
+}
+
+#[test]
+fn test_record_edge_evaluation_deterministic_edge_records_zero() {
+    let mut step = step();
+
+    step.record_edge_evaluation(deterministic_evaluation("review-to-fix"));
+
+    assert_eq!(step.edge_cost, TokenCost::zero());
+    assert_eq!(
+        step.edge_costs()
+            .get(&EdgeId::new("review-to-fix").unwrap()),
+        Some(&TokenCost::zero())
+    );
+}
+
+#[test]
+fn test_edge_costs_repeated_edge_sums_evaluations() {
+    let mut step = step();
+    step.record_edge_evaluation(llm_evaluation("review-to-merge", 0.25));
+    step.record_edge_evaluation(llm_evaluation("review-to-merge", 0.5));
+    step.record_edge_evaluation(deterministic_evaluation("review-to-fix"));
+
+    let costs = step.edge_costs();
+
+    assert_eq!(costs.len(), 2);
+    assert_eq!(
+        costs.get(&EdgeId::new("review-to-merge").unwrap()),
+        Some(&cost(0.75))
+    );
+}
+
+#[test]
+fn test_total_cost_node_and_edge_cost_returns_sum() {
+    let mut step = step();
+    step.record_node(node_id("plan"), cost(1.0));
+    step.record_edge_evaluation(llm_evaluation("plan-to-code", 0.5));
+
+    assert_eq!(step.node_cost, cost(1.0));
+    assert_eq!(step.edge_cost, cost(0.5));
+    assert_eq!(step.total_cost(), cost(1.5));
+}
+
+// ─── run_node ───────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_run_node_registered_node_returns_its_outcome() {
+    let code = FixedNode::new(NodeOutcome::Failed {
+        error: "tests failed".to_string(),
+        cost: cost(0.4),
+    });
+    let executor = executor(&["plan", "code"], vec![("code", code.clone() as _)]);
+
+    let outcome = executor
+        .run_node(&pipeline_state(), &node_id("code"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        outcome,
+        NodeOutcome::Failed {
+            error: "tests failed".to_string(),
+            cost: cost(0.4),
+        }
+    );
+    assert_eq!(code.runs(), 1);
+}
+
+#[tokio::test]
+async fn test_run_node_completed_node_does_not_advance_state() {
+    let plan = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let executor = executor(&["plan", "code"], vec![("plan", plan as _)]);
+    let state = pipeline_state();
+
+    executor.run_node(&state, &node_id("plan")).await.unwrap();
+
+    assert!(state.node_states.is_empty());
+    assert_eq!(state.cost_accumulator, TokenCost::zero());
+}
+
+#[tokio::test]
+async fn test_run_node_node_not_in_graph_returns_unknown_node() {
+    let plan = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let executor = executor(&["plan"], vec![("plan", plan.clone() as _)]);
+
+    let error = executor
+        .run_node(&pipeline_state(), &node_id("deploy"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ExecutorError::UnknownNode { node } if node == node_id("deploy")));
+    assert_eq!(plan.runs(), 0);
+}
+
+#[tokio::test]
+async fn test_run_node_node_without_implementation_returns_missing_implementation() {
+    let executor = executor(&["plan"], Vec::new());
+
+    let error = executor
+        .run_node(&pipeline_state(), &node_id("plan"))
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, ExecutorError::MissingImplementation { node } if node == node_id("plan"))
+    );
+}
+
+// ─── run_nodes ──────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_run_nodes_all_complete_checkpoints_after_each_node() {
+    let plan = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let code = FixedNode::new(NodeOutcome::Completed { cost: cost(2.0) });
+    let executor = executor(
+        &["plan", "code"],
+        vec![("plan", plan as _), ("code", code as _)],
+    );
+    let checkpoints = FakeCheckpoints::default();
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    executor
+        .run_nodes(
+            &mut state,
+            &[node_id("plan"), node_id("code")],
+            &checkpoints,
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(checkpoints.saves(), 2);
+    assert_eq!(step.executed_nodes, vec![node_id("plan"), node_id("code")]);
+    assert_eq!(step.node_cost, cost(3.0));
+    assert_eq!(status(&state, "code"), Some(NodeStatus::Completed));
+    assert_eq!(state.cost_accumulator, cost(3.0));
+}
+
+#[tokio::test]
+async fn test_run_nodes_checkpoint_fails_returns_checkpoint_failed_for_that_node() {
+    let plan = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let code = FixedNode::new(NodeOutcome::Completed { cost: cost(2.0) });
+    let review = FixedNode::new(NodeOutcome::Completed { cost: cost(0.5) });
+    let executor = executor(
+        &["plan", "code", "review"],
+        vec![
+            ("plan", plan as _),
+            ("code", code as _),
+            ("review", review.clone() as _),
+        ],
+    );
+    let checkpoints = FakeCheckpoints::failing_on(2);
+    let mut state = pipeline_state();
+
+    let error = executor
+        .run_nodes(
+            &mut state,
+            &[node_id("plan"), node_id("code"), node_id("review")],
+            &checkpoints,
+            &mut step(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        ExecutorError::CheckpointFailed { node, source: GitHubOperationError::Transient { .. } }
+            if node == node_id("code")
+    ));
+    assert_eq!(review.runs(), 0);
+    assert_eq!(
+        status(&checkpoints.last_saved(), "plan"),
+        Some(NodeStatus::Completed)
+    );
+    assert_eq!(status(&checkpoints.last_saved(), "code"), None);
+}
+
+#[tokio::test]
+async fn test_run_nodes_resume_from_persisted_state_skips_completed_node() {
+    let plan = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let code = FixedNode::new(NodeOutcome::Completed { cost: cost(2.0) });
+    let executor = executor(
+        &["plan", "code"],
+        vec![("plan", plan.clone() as _), ("code", code.clone() as _)],
+    );
+    let sequence = [node_id("plan"), node_id("code")];
+    let failing = FakeCheckpoints::failing_on(2);
+    let mut state = pipeline_state();
+    let _ = executor
+        .run_nodes(&mut state, &sequence, &failing, &mut step())
+        .await;
+
+    let mut resumed = failing.last_saved();
+    let mut step = step();
+    executor
+        .run_nodes(
+            &mut resumed,
+            &sequence,
+            &FakeCheckpoints::default(),
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(plan.runs(), 1);
+    assert_eq!(code.runs(), 2);
+    assert_eq!(step.executed_nodes, vec![node_id("code")]);
+    assert_eq!(status(&resumed, "code"), Some(NodeStatus::Completed));
+}
+
+#[tokio::test]
+async fn test_run_nodes_failed_node_stops_and_is_recorded() {
+    let plan = FixedNode::new(NodeOutcome::Failed {
+        error: "timeout".to_string(),
+        cost: cost(0.25),
+    });
+    let code = FixedNode::new(NodeOutcome::Completed { cost: cost(2.0) });
+    let executor = executor(
+        &["plan", "code"],
+        vec![("plan", plan as _), ("code", code.clone() as _)],
+    );
+    let checkpoints = FakeCheckpoints::default();
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    executor
+        .run_nodes(
+            &mut state,
+            &[node_id("plan"), node_id("code")],
+            &checkpoints,
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(step.executed_nodes, vec![node_id("plan")]);
+    assert_eq!(step.node_cost, cost(0.25));
+    assert_eq!(code.runs(), 0);
+    assert_eq!(status(&state, "plan"), Some(NodeStatus::Failed));
+    assert_eq!(
+        state.node_states[&node_id("plan")].current_error.as_deref(),
+        Some("timeout")
+    );
+    assert_eq!(checkpoints.saves(), 1);
+}
+
+#[tokio::test]
+async fn test_run_nodes_awaiting_review_stops_and_is_recorded() {
+    let plan = FixedNode::new(NodeOutcome::AwaitingHumanReview { cost: cost(0.5) });
+    let code = FixedNode::new(NodeOutcome::Completed { cost: cost(2.0) });
+    let executor = executor(
+        &["plan", "code"],
+        vec![("plan", plan as _), ("code", code.clone() as _)],
+    );
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    executor
+        .run_nodes(
+            &mut state,
+            &[node_id("plan"), node_id("code")],
+            &FakeCheckpoints::default(),
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(step.executed_nodes, vec![node_id("plan")]);
+    assert_eq!(code.runs(), 0);
+    assert_eq!(status(&state, "plan"), Some(NodeStatus::HumanGated));
+}
+
+// ─── Budget enforcement ─────────────────────────────────────────────────────
+
+/// Runs `plan` ($1.00), `code` (`code_usd`), and `review` ($0.50) against a
+/// $2.00 budget with a $0.50 overshoot grace.
+async fn run_with_budget(
+    code_usd: f64,
+) -> (StepResult, PipelineState, FakeCheckpoints, Arc<FixedNode>) {
+    let plan = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let code = FixedNode::new(NodeOutcome::Completed {
+        cost: cost(code_usd),
+    });
+    let review = FixedNode::new(NodeOutcome::Completed { cost: cost(0.5) });
+    let executor = executor(
+        &["plan", "code", "review"],
+        vec![
+            ("plan", plan as _),
+            ("code", code as _),
+            ("review", review.clone() as _),
+        ],
+    )
+    .with_budget(
+        BudgetEnforcer::new(CostBudget::new(2.0).unwrap())
+            .with_overshoot_grace(OvershootGrace::Dollars(0.5)),
+    );
+    let checkpoints = FakeCheckpoints::default();
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    executor
+        .run_nodes(
+            &mut state,
+            &[node_id("plan"), node_id("code"), node_id("review")],
+            &checkpoints,
+            &mut step,
+        )
+        .await
+        .unwrap();
+    (step, state, checkpoints, review)
+}
+
+#[tokio::test]
+async fn test_run_nodes_under_budget_runs_every_node() {
+    let (step, _, _, review) = run_with_budget(0.25).await;
+
+    assert_eq!(review.runs(), 1);
+    assert_eq!(step.outcome, None);
+    assert_eq!(step.halt_reason, None);
+    assert_eq!(step.budget_overshoot, None);
+}
+
+#[tokio::test]
+async fn test_run_nodes_overshoot_within_grace_finishes_node_and_halts() {
+    let (step, state, checkpoints, review) = run_with_budget(1.25).await;
+
+    assert_eq!(step.executed_nodes, vec![node_id("plan"), node_id("code")]);
+    assert_eq!(review.runs(), 0);
+    assert_eq!(step.outcome, Some(PipelineOutcome::Escalated));
+    assert_eq!(step.halt_reason, Some(HaltReason::CostBudget));
+    assert_eq!(step.budget_overshoot, Some(cost(0.25)));
+    assert_eq!(status(&state, "code"), Some(NodeStatus::Completed));
+    assert_eq!(
+        status(&checkpoints.last_saved(), "code"),
+        Some(NodeStatus::Completed)
+    );
+}
+
+#[tokio::test]
+async fn test_run_nodes_overshoot_beyond_grace_halts_node_as_failed() {
+    let (step, state, checkpoints, review) = run_with_budget(2.0).await;
+
+    assert_eq!(step.executed_nodes, vec![node_id("plan"), node_id("code")]);
+    assert_eq!(review.runs(), 0);
+    assert_eq!(step.outcome, Some(PipelineOutcome::Escalated));
+    assert_eq!(step.halt_reason, Some(HaltReason::CostBudget));
+    assert_eq!(step.budget_overshoot, None);
+    assert_eq!(step.node_cost, cost(3.0));
+    assert_eq!(status(&state, "code"), Some(NodeStatus::Failed));
+    assert_eq!(
+        status(&checkpoints.last_saved(), "code"),
+        Some(NodeStatus::Failed)
+    );
+}
+
+#[test]
+fn test_record_halt_pipeline_halt_keeps_reason_and_detail() {
+    let mut step = step();
+
+    step.record_halt(&CogWorksError::halt(
+        HaltReason::ReworkLimit,
+        "alignment still failing".to_string(),
+    ));
+
+    assert_eq!(step.outcome, Some(PipelineOutcome::Escalated));
+    assert_eq!(step.halt_reason, Some(HaltReason::ReworkLimit));
+    assert_eq!(step.halt_detail.as_deref(), Some("alignment still failing"));
+}
+
+/// [`Escalator`] recording every escalation it receives.
+#[derive(Default)]
+struct RecordingEscalator {
+    received: Mutex<Vec<Escalation>>,
+}
+
+#[async_trait]
+impl Escalator for RecordingEscalator {
+    fn name(&self) -> &str {
+        "recording"
+    }
+
+    async fn escalate(
+        &self,
+        escalation: &Escalation,
+    ) -> Result<(), crate::escalation::EscalationError> {
+        self.received.lock().unwrap().push(escalation.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_run_nodes_budget_halt_notifies_escalators() {
+    let plan = FixedNode::new(NodeOutcome::Completed { cost: cost(3.0) });
+    let escalator = Arc::new(RecordingEscalator::default());
+    let executor = executor(&["plan"], vec![("plan", plan as _)])
+        .with_budget(BudgetEnforcer::new(CostBudget::new(2.0).unwrap()))
+        .with_escalators(vec![escalator.clone() as _]);
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    executor
+        .run_nodes(
+            &mut state,
+            &[node_id("plan")],
+            &FakeCheckpoints::default(),
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    let received = escalator.received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].outcome, PipelineOutcome::Escalated);
+    assert_eq!(received[0].reason, Some(HaltReason::CostBudget));
+}
+
+#[tokio::test]
+async fn test_halt_alignment_limit_notifies_escalators_with_rework_limit() {
+    let escalator = Arc::new(RecordingEscalator::default());
+    let executor = executor(&[], Vec::new()).with_escalators(vec![escalator.clone() as _]);
+    let mut step = step();
+
+    executor
+        .halt(
+            &mut step,
+            &CogWorksError::halt(HaltReason::ReworkLimit, "alignment".to_string()),
+        )
+        .await;
+
+    assert_eq!(step.halt_reason, Some(HaltReason::ReworkLimit));
+    let received = escalator.received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].reason, Some(HaltReason::ReworkLimit));
+    assert_eq!(received[0].detail.as_deref(), Some("alignment"));
+}
+
+// ─── Ready batch ────────────────────────────────────────────────────────────
+
+/// Node appending its id to a shared log when it runs.
+struct LoggingNode {
+    id: &'static str,
+    log: Arc<Mutex<Vec<&'static str>>>,
+}
+
+#[async_trait]
+impl Node for LoggingNode {
+    async fn execute(&self, _state: &PipelineState) -> NodeOutcome {
+        self.log.lock().unwrap().push(self.id);
+        NodeOutcome::Completed { cost: cost(0.1) }
+    }
+}
+
+/// Executor over nodes declared with the given priorities, each logging its
+/// runs to the returned log.
+fn prioritized_executor(
+    nodes: &[(&'static str, i32)],
+) -> (PipelineExecutor, Arc<Mutex<Vec<&'static str>>>) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut graph = graph(&nodes.iter().map(|(id, _)| *id).collect::<Vec<_>>());
+    for (definition, (_, priority)) in graph.nodes.iter_mut().zip(nodes) {
+        definition.priority = *priority;
+    }
+    let implementations = nodes
+        .iter()
+        .map(|(id, _)| {
+            let node: Arc<dyn Node> = Arc::new(LoggingNode {
+                id,
+                log: Arc::clone(&log),
+            });
+            (node_id(id), node)
+        })
+        .collect();
+    (PipelineExecutor::new(graph, implementations), log)
+}
+
+fn ids(names: &[&str]) -> Vec<NodeId> {
+    names.iter().map(|name| node_id(name)).collect()
+}
+
+#[test]
+fn test_prioritize_mixed_priorities_orders_highest_first() {
+    let (executor, _) = prioritized_executor(&[("review", -1), ("lint", 10), ("code", 0)]);
+
+    let ordered = executor.prioritize(&ids(&["review", "lint", "code"]));
+
+    assert_eq!(ordered, ids(&["lint", "code", "review"]));
+}
+
+#[test]
+fn test_prioritize_equal_priorities_keeps_ready_order() {
+    let (executor, _) = prioritized_executor(&[("a", 1), ("b", 1), ("c", 1)]);
+
+    let ordered = executor.prioritize(&ids(&["c", "a", "b"]));
+
+    assert_eq!(ordered, ids(&["c", "a", "b"]));
+}
+
+#[test]
+fn test_prioritize_undeclared_node_is_treated_as_priority_zero() {
+    let (executor, _) = prioritized_executor(&[("lint", 5), ("review", -5)]);
+
+    let ordered = executor.prioritize(&ids(&["review", "unknown", "lint"]));
+
+    assert_eq!(ordered, ids(&["lint", "unknown", "review"]));
+}
+
+#[tokio::test]
+async fn test_run_ready_batch_executes_nodes_in_priority_order() {
+    let (executor, log) = prioritized_executor(&[("review", 0), ("lint", 20), ("test", 10)]);
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    executor
+        .run_ready_batch(
+            &mut state,
+            &ids(&["review", "lint", "test"]),
+            &FakeCheckpoints::default(),
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(*log.lock().unwrap(), vec!["lint", "test", "review"]);
+    assert_eq!(step.executed_nodes, ids(&["lint", "test", "review"]));
+}
+
+#[tokio::test]
+async fn test_run_ready_batch_failing_high_priority_node_stops_batch_early() {
+    let lint = FixedNode::new(NodeOutcome::Failed {
+        error: "clippy found errors".to_string(),
+        cost: cost(0.1),
+    });
+    let review = FixedNode::new(NodeOutcome::Completed { cost: cost(2.0) });
+    let mut graph = graph(&["review", "lint"]);
+    graph.nodes[1].priority = 10;
+    let executor = PipelineExecutor::new(
+        graph,
+        [
+            (node_id("review"), review.clone() as Arc<dyn Node>),
+            (node_id("lint"), lint.clone() as Arc<dyn Node>),
+        ]
+        .into_iter()
+        .collect(),
+    );
+    let mut state = pipeline_state();
+
+    executor
+        .run_ready_batch(
+            &mut state,
+            &ids(&["review", "lint"]),
+            &FakeCheckpoints::default(),
+            &mut step(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(lint.runs(), 1);
+    assert_eq!(review.runs(), 0);
+    assert_eq!(status(&state, "lint"), Some(NodeStatus::Failed));
+}
+
+// ─── Parallel batch ─────────────────────────────────────────────────────────
+
+/// Node that waits `delay` before completing, so batch members finish out of
+/// priority order.
+struct DelayedNode {
+    id: &'static str,
+    delay: Duration,
+    finished: Arc<Mutex<Vec<&'static str>>>,
+}
+
+#[async_trait]
+impl Node for DelayedNode {
+    async fn execute(&self, _state: &PipelineState) -> NodeOutcome {
+        tokio::time::sleep(self.delay).await;
+        self.finished.lock().unwrap().push(self.id);
+        NodeOutcome::Completed { cost: cost(0.5) }
+    }
+}
+
+fn parallel_executor(nodes: Vec<(&str, i32, Arc<dyn Node>)>) -> PipelineExecutor {
+    let mut graph = graph(&nodes.iter().map(|(id, _, _)| *id).collect::<Vec<_>>());
+    for (definition, (_, priority, _)) in graph.nodes.iter_mut().zip(&nodes) {
+        definition.priority = *priority;
+    }
+    PipelineExecutor::new(
+        graph,
+        nodes
+            .into_iter()
+            .map(|(id, _, node)| (node_id(id), node))
+            .collect(),
+    )
+}
+
+#[tokio::test]
+async fn test_run_parallel_batch_out_of_order_finish_records_priority_order() {
+    let finished = Arc::new(Mutex::new(Vec::new()));
+    let delayed = |id, millis| -> Arc<dyn Node> {
+        Arc::new(DelayedNode {
+            id,
+            delay: Duration::from_millis(millis),
+            finished: Arc::clone(&finished),
+        })
+    };
+    let executor = parallel_executor(vec![
+        ("review", 0, delayed("review", 0)),
+        ("lint", 20, delayed("lint", 40)),
+        ("test", 10, delayed("test", 20)),
+    ]);
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    executor
+        .run_parallel_batch(
+            &mut state,
+            &ids(&["review", "lint", "test"]),
+            &FakeCheckpoints::default(),
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(*finished.lock().unwrap(), vec!["review", "test", "lint"]);
+    assert_eq!(step.executed_nodes, ids(&["lint", "test", "review"]));
+    assert_eq!(step.node_cost, cost(1.5));
+}
+
+#[tokio::test]
+async fn test_run_parallel_batch_failing_node_does_not_stop_others() {
+    let lint = FixedNode::new(NodeOutcome::Failed {
+        error: "clippy found errors".to_string(),
+        cost: cost(0.25),
+    });
+    let review = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let executor = parallel_executor(vec![
+        ("lint", 10, lint.clone() as _),
+        ("review", 0, review.clone() as _),
+    ]);
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    executor
+        .run_parallel_batch(
+            &mut state,
+            &ids(&["lint", "review"]),
+            &FakeCheckpoints::default(),
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(review.runs(), 1);
+    assert_eq!(status(&state, "lint"), Some(NodeStatus::Failed));
+    assert_eq!(status(&state, "review"), Some(NodeStatus::Completed));
+    assert_eq!(step.executed_nodes, ids(&["lint", "review"]));
+    assert_eq!(step.node_cost, cost(1.25));
+}
+
+#[tokio::test]
+async fn test_run_parallel_batch_checkpoints_after_each_node() {
+    let executor = parallel_executor(vec![
+        (
+            "lint",
+            10,
+            FixedNode::new(NodeOutcome::Completed { cost: cost(0.1) }) as _,
+        ),
+        (
+            "test",
+            0,
+            FixedNode::new(NodeOutcome::Completed { cost: cost(0.1) }) as _,
+        ),
+    ]);
+    let checkpoints = FakeCheckpoints::default();
+    let mut state = pipeline_state();
+
+    executor
+        .run_parallel_batch(
+            &mut state,
+            &ids(&["test", "lint"]),
+            &checkpoints,
+            &mut step(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(checkpoints.saves(), 2);
+    let saved = checkpoints.last_saved();
+    assert_eq!(status(&saved, "lint"), Some(NodeStatus::Completed));
+    assert_eq!(status(&saved, "test"), Some(NodeStatus::Completed));
+}
+
+#[tokio::test]
+async fn test_run_parallel_batch_checkpoint_fails_returns_checkpoint_failed_for_that_node() {
+    let executor = parallel_executor(vec![
+        (
+            "lint",
+            20,
+            FixedNode::new(NodeOutcome::Completed { cost: cost(0.1) }) as _,
+        ),
+        (
+            "test",
+            10,
+            FixedNode::new(NodeOutcome::Completed { cost: cost(0.1) }) as _,
+        ),
+        (
+            "review",
+            0,
+            FixedNode::new(NodeOutcome::Completed { cost: cost(0.1) }) as _,
+        ),
+    ]);
+    let checkpoints = FakeCheckpoints::failing_on(2);
+    let mut state = pipeline_state();
+
+    let error = executor
+        .run_parallel_batch(
+            &mut state,
+            &ids(&["review", "test", "lint"]),
+            &checkpoints,
+            &mut step(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        ExecutorError::CheckpointFailed { ref node, .. } if *node == node_id("test")
+    ));
+    assert_eq!(checkpoints.saves(), 1);
+    assert_eq!(
+        status(&checkpoints.last_saved(), "lint"),
+        Some(NodeStatus::Completed)
+    );
+    assert_eq!(status(&checkpoints.last_saved(), "test"), None);
+}
+
+#[tokio::test]
+async fn test_run_parallel_batch_completed_node_skipped_on_resume() {
+    let lint = FixedNode::new(NodeOutcome::Completed { cost: cost(0.1) });
+    let test = FixedNode::new(NodeOutcome::Completed { cost: cost(0.1) });
+    let executor = parallel_executor(vec![
+        ("lint", 10, lint.clone() as _),
+        ("test", 0, test.clone() as _),
+    ]);
+    let checkpoints = FakeCheckpoints::default();
+    let mut state = pipeline_state();
+    executor
+        .run_parallel_batch(&mut state, &ids(&["lint"]), &checkpoints, &mut step())
+        .await
+        .unwrap();
+    let mut step = step();
+
+    executor
+        .run_parallel_batch(&mut state, &ids(&["lint", "test"]), &checkpoints, &mut step)
+        .await
+        .unwrap();
+
+    assert_eq!(lint.runs(), 1);
+    assert_eq!(test.runs(), 1);
+    assert_eq!(step.executed_nodes, ids(&["test"]));
+}
+
+#[tokio::test]
+async fn test_run_parallel_batch_unknown_node_returns_error_before_running_any() {
+    let lint = FixedNode::new(NodeOutcome::Completed { cost: cost(0.1) });
+    let executor = parallel_executor(vec![("lint", 0, lint.clone() as _)]);
+    let checkpoints = FakeCheckpoints::default();
+
+    let error = executor
+        .run_parallel_batch(
+            &mut pipeline_state(),
+            &ids(&["lint", "ghost"]),
+            &checkpoints,
+            &mut step(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ExecutorError::UnknownNode { ref node } if *node == node_id("ghost")));
+    assert_eq!(lint.runs(), 0);
+    assert_eq!(checkpoints.saves(), 0);
+}
+
+// ─── Alignment loop ─────────────────────────────────────────────────────────
+
+fn finding(severity: DiagnosticSeverity, message: &str) -> Diagnostic {
+    Diagnostic {
+        artifact: None,
+        location: None,
+        severity,
+        category: DiagnosticCategory::new("alignment").unwrap(),
+        message: message.to_string(),
+    }
+}
+
+fn blocking() -> Vec<Diagnostic> {
+    vec![finding(
+        DiagnosticSeverity::Blocking,
+        "interface does not match the design",
+    )]
+}
+
+/// [`DiagnosticSource`] returning scripted findings in order, then a fallback
+/// for every further check.
+struct ScriptedAlignment {
+    script: Mutex<VecDeque<Result<Vec<Diagnostic>, CogWorksError>>>,
+    fallback: Vec<Diagnostic>,
+    checks: AtomicU32,
+}
+
+impl ScriptedAlignment {
+    fn new(
+        script: impl IntoIterator<Item = Result<Vec<Diagnostic>, CogWorksError>>,
+        fallback: Vec<Diagnostic>,
+    ) -> Self {
+        Self {
+            script: Mutex::new(script.into_iter().collect()),
+            fallback,
+            checks: AtomicU32::new(0),
+        }
+    }
+
+    fn checks(&self) -> u32 {
+        self.checks.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl DiagnosticSource for ScriptedAlignment {
+    async fn collect(&self, _state: &PipelineState) -> Result<Vec<Diagnostic>, CogWorksError> {
+        self.checks.fetch_add(1, Ordering::SeqCst);
+        self.script
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| Ok(self.fallback.clone()))
+    }
+}
+
+fn rework_count(state: &PipelineState, node: &str) -> u32 {
+    state
+        .node_states
+        .get(&node_id(node))
+        .map_or(0, |node_state| node_state.rework_count)
+}
+
+fn alignment_loop(max_iterations: u32) -> AlignmentLoop {
+    AlignmentLoop {
+        max_iterations,
+        ..AlignmentLoop::new(node_id("fix"))
+    }
+}
+
+#[test]
+fn test_alignment_loop_new_uses_default_iteration_limit() {
+    let config = AlignmentLoop::new(node_id("fix"));
+
+    assert_eq!(config.fix_node, node_id("fix"));
+    assert_eq!(config.max_iterations, DEFAULT_ALIGNMENT_MAX_ITERATIONS);
+}
+
+#[tokio::test]
+async fn test_run_alignment_loop_passing_check_returns_passed_without_fixing() {
+    let fix = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let executor = executor(&["fix"], vec![("fix", fix.clone() as _)]);
+    let warning = finding(
+        DiagnosticSeverity::Warning,
+        "naming differs from the design",
+    );
+    let alignment = ScriptedAlignment::new([], vec![warning.clone()]);
+    let checkpoints = FakeCheckpoints::default();
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    let outcome = executor
+        .run_alignment_loop(
+            &mut state,
+            &alignment,
+            &alignment_loop(3),
+            &checkpoints,
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        outcome,
+        AlignmentLoopOutcome::Passed {
+            fixes: 0,
+            warnings: vec![warning]
+        }
+    );
+    assert_eq!(fix.runs(), 0);
+    assert_eq!(checkpoints.saves(), 0);
+}
+
+#[tokio::test]
+async fn test_run_alignment_loop_fix_then_pass_returns_passed_after_one_fix() {
+    let fix = FixedNode::new(NodeOutcome::Completed { cost: cost(1.5) });
+    let executor = executor(&["fix"], vec![("fix", fix.clone() as _)]);
+    let alignment = ScriptedAlignment::new([Ok(blocking())], Vec::new());
+    let checkpoints = FakeCheckpoints::default();
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    let outcome = executor
+        .run_alignment_loop(
+            &mut state,
+            &alignment,
+            &alignment_loop(3),
+            &checkpoints,
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        outcome,
+        AlignmentLoopOutcome::Passed {
+            fixes: 1,
+            warnings: Vec::new()
+        }
+    );
+    assert_eq!(fix.runs(), 1);
+    assert_eq!(alignment.checks(), 2);
+    assert_eq!(rework_count(&state, "fix"), 1);
+    assert_eq!(step.executed_nodes, vec![node_id("fix")]);
+    assert!((step.node_cost.as_f64() - 1.5).abs() < 1e-9);
+    assert_eq!(checkpoints.saves(), 1);
+    assert_eq!(rework_count(&checkpoints.last_saved(), "fix"), 1);
+}
+
+#[tokio::test]
+async fn test_run_alignment_loop_never_passing_halts_at_iteration_limit() {
+    let fix = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let executor = executor(&["fix"], vec![("fix", fix.clone() as _)]);
+    let alignment = ScriptedAlignment::new([], blocking());
+    let checkpoints = FakeCheckpoints::default();
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    let outcome = executor
+        .run_alignment_loop(
+            &mut state,
+            &alignment,
+            &alignment_loop(2),
+            &checkpoints,
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        outcome,
+        AlignmentLoopOutcome::LimitReached {
+            fixes: 2,
+            blocking: blocking()
+        }
+    );
+    assert_eq!(fix.runs(), 2);
+    assert_eq!(alignment.checks(), 3);
+    assert_eq!(rework_count(&state, "fix"), 2);
+    assert_eq!(checkpoints.saves(), 2);
+}
+
+#[tokio::test]
+async fn test_run_alignment_loop_limit_reached_halts_with_rework_limit() {
+    let fix = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let executor = executor(&["fix"], vec![("fix", fix as _)]);
+    let alignment = ScriptedAlignment::new([], blocking());
+    let mut state = pipeline_state();
+
+    let outcome = executor
+        .run_alignment_loop(
+            &mut state,
+            &alignment,
+            &alignment_loop(1),
+            &FakeCheckpoints::default(),
+            &mut step(),
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        outcome.halt(),
+        Some(CogWorksError::PipelineHalt {
+            reason: HaltReason::ReworkLimit,
+            detail: Some(_),
+        })
+    ));
+}
+
+#[test]
+fn test_alignment_loop_outcome_halt_passed_or_fix_incomplete_returns_none() {
+    let passed = AlignmentLoopOutcome::Passed {
+        fixes: 1,
+        warnings: Vec::new(),
+    };
+    let incomplete = AlignmentLoopOutcome::FixIncomplete {
+        fixes: 1,
+        outcome: NodeOutcome::Completed { cost: cost(1.0) },
+    };
+
+    assert!(passed.halt().is_none());
+    assert!(incomplete.halt().is_none());
+}
+
+#[tokio::test]
+async fn test_run_alignment_loop_limit_reached_in_earlier_step_does_not_fix_again() {
+    let fix = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let executor = executor(&["fix"], vec![("fix", fix.clone() as _)]);
+    let alignment = ScriptedAlignment::new([], blocking());
+    let checkpoints = FakeCheckpoints::default();
+    let mut state = pipeline_state();
+    let mut step = step();
+    executor
+        .run_alignment_loop(
+            &mut state,
+            &alignment,
+            &alignment_loop(1),
+            &checkpoints,
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    let outcome = executor
+        .run_alignment_loop(
+            &mut state,
+            &alignment,
+            &alignment_loop(1),
+            &checkpoints,
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        outcome,
+        AlignmentLoopOutcome::LimitReached { fixes: 0, .. }
+    ));
+    assert_eq!(fix.runs(), 1);
+}
+
+#[tokio::test]
+async fn test_run_alignment_loop_failed_fix_returns_fix_incomplete() {
+    let failure = NodeOutcome::Failed {
+        error: "compilation failed".to_string(),
+        cost: cost(0.5),
+    };
+    let fix = FixedNode::new(failure.clone());
+    let executor = executor(&["fix"], vec![("fix", fix.clone() as _)]);
+    let alignment = ScriptedAlignment::new([], blocking());
+    let checkpoints = FakeCheckpoints::default();
+    let mut state = pipeline_state();
+    let mut step = step();
+
+    let outcome = executor
+        .run_alignment_loop(
+            &mut state,
+            &alignment,
+            &alignment_loop(3),
+            &checkpoints,
+            &mut step,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        outcome,
+        AlignmentLoopOutcome::FixIncomplete {
+            fixes: 1,
+            outcome: failure
+        }
+    );
+    assert_eq!(fix.runs(), 1);
+    assert_eq!(alignment.checks(), 1);
+    assert_eq!(status(&state, "fix"), Some(NodeStatus::Failed));
+    assert_eq!(checkpoints.saves(), 1);
+    assert_eq!(step.executed_nodes, vec![node_id("fix")]);
+    assert_eq!(step.node_cost, cost(0.5));
+}
+
+#[tokio::test]
+async fn test_run_alignment_loop_source_error_returns_alignment_check_failed() {
+    let fix = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let executor = executor(&["fix"], vec![("fix", fix.clone() as _)]);
+    let alignment = ScriptedAlignment::new(
+        [Err(CogWorksError::PipelineHalt {
+            reason: HaltReason::Cancelled,
+            detail: None,
+        })],
+        Vec::new(),
+    );
+    let mut state = pipeline_state();
+
+    let result = executor
+        .run_alignment_loop(
+            &mut state,
+            &alignment,
+            &alignment_loop(3),
+            &FakeCheckpoints::default(),
+            &mut step(),
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ExecutorError::AlignmentCheckFailed { .. })
+    ));
+    assert_eq!(fix.runs(), 0);
+}
+
+#[tokio::test]
+async fn test_run_alignment_loop_checkpoint_fails_returns_checkpoint_failed_for_fix_node() {
+    let fix = FixedNode::new(NodeOutcome::Completed { cost: cost(1.0) });
+    let executor = executor(&["fix"], vec![("fix", fix.clone() as _)]);
+    let alignment = ScriptedAlignment::new([Ok(blocking())], Vec::new());
+    let mut state = pipeline_state();
+
+    let result = executor
+        .run_alignment_loop(
+            &mut state,
+            &alignment,
+            &alignment_loop(3),
+            &FakeCheckpoints::failing_on(1),
+            &mut step(),
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ExecutorError::CheckpointFailed { node, .. }) if node == node_id("fix")
+    ));
+}
@@ -0,0 +1,497 @@
+//! LLM gateway: the single path from nodes to the [`LlmProvider`].
+//!
+//! Every LLM call a node makes goes through [`LlmGateway::complete`]. The
+//! gateway enforces a concurrency limit per model: providers grant different
+//! allowances to different models, so one global limit would either starve a
+//! generous model or overrun a strict one. Each model name gets its own
+//! semaphore, sized from [`ModelConcurrencyLimits`]; calls to a model at its
+//! limit wait without blocking calls to any other model. Fallback and
+//! mixed-model runs therefore respect each model's limit independently.
+//!
+//! Responses cut off by `max_tokens` ([`pipeline::FinishReason::MaxTokens`]) are logged
+//! as warnings; callers inspect `finish_reason` to decide whether to continue
+//! the response.
+//!
+//! [`LlmGateway::complete_for_node`] applies the node's model override from
+//! `pipeline.toml` ([`PipelineGraph::model_for`]) before sending, so each
+//! node can run on its own model while unconfigured nodes keep the run's
+//! default.
+//!
+//! [`LlmGateway::complete_until`] streams the call
+//! ([`LlmProvider::complete_streaming`]) and stops reading when a
+//! cancellation future resolves (budget exhausted, pipeline halted). It
+//! returns [`LlmError::Cancelled`] with the text deltas received so far, so
+//! the node can record them before halting.
+//!
+//! [`LlmGateway::complete_with_continuation`] streams the call the same way
+//! and recovers from a stream cut off by a transient failure
+//! ([`LlmError::Interrupted`]): instead of
+//! regenerating from scratch, it sends the partial output back as an
+//! assistant turn followed by [`CONTINUATION_PROMPT`], up to a bounded number
+//! of continuations, and joins the pieces into one response whose usage is
+//! the sum over every call.
+//!
+//! Prompts over the configured [`PromptLimit`] lose Context Pack sections,
+//! oldest first, before they are sent (see [`crate::prompt_limit`]). Nodes
+//! call [`LlmGateway::fit_prompt`] themselves to record the resulting
+//! warning; [`LlmGateway::complete`] applies the limit regardless, so no
+//! oversized prompt is sent.
+//!
+//! With a [`ResponseCache`] configured, [`LlmGateway::complete_for_node`]
+//! answers a repeated request from the node's earlier response until the
+//! node's TTL expires (see [`crate::response_cache`]).
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §LLM gateway.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::instrument;
+
+use pipeline::{
+    CompletionChunk, CompletionRequest, CompletionResponse, Diagnostic, LlmError, LlmProvider,
+    Message, NodeId, PartialCompletion, PipelineGraph, TokenEstimate, TokenUsage,
+};
+
+use crate::prompt_limit::{fit_prompt, PromptLimit};
+use crate::response_cache::{ResponseCache, ResponseCacheConfig};
+
+/// Concurrency limit applied to models without an explicit entry.
+pub const DEFAULT_MODEL_CONCURRENCY: NonZeroUsize = match NonZeroUsize::new(4) {
+    Some(limit) => limit,
+    None => unreachable!(),
+};
+
+/// Continuations [`LlmGateway::complete_with_continuation`] attempts after
+/// interruptions before giving up.
+pub const DEFAULT_MAX_CONTINUATIONS: u32 = 2;
+
+/// User turn sent after the partial output to ask the model to resume.
+pub const CONTINUATION_PROMPT: &str = "Your previous response was cut off. Continue exactly \
+where it stopped, without repeating any text.";
+
+// ─── Limits ─────────────────────────────────────────────────────────────────
+
+/// Maximum number of in-flight calls per model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelConcurrencyLimits {
+    /// Limit for models not listed in `per_model`.
+    #[serde(default = "default_model_concurrency")]
+    pub default: NonZeroUsize,
+    /// Limits keyed by model name as sent to the provider.
+    #[serde(default)]
+    pub per_model: HashMap<String, NonZeroUsize>,
+}
+
+fn default_model_concurrency() -> NonZeroUsize {
+    DEFAULT_MODEL_CONCURRENCY
+}
+
+impl Default for ModelConcurrencyLimits {
+    fn default() -> Self {
+        Self {
+            default: DEFAULT_MODEL_CONCURRENCY,
+            per_model: HashMap::new(),
+        }
+    }
+}
+
+impl ModelConcurrencyLimits {
+    /// Sets the limit for `model`, replacing any previous entry.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>, limit: NonZeroUsize) -> Self {
+        self.per_model.insert(model.into(), limit);
+        self
+    }
+
+    /// Returns the limit that applies to `model`.
+    pub fn limit_for(&self, model: &str) -> NonZeroUsize {
+        self.per_model.get(model).copied().unwrap_or(self.default)
+    }
+}
+
+// ─── Gateway ────────────────────────────────────────────────────────────────
+
+/// Wraps an [`LlmProvider`] with per-model concurrency limits.
+pub struct LlmGateway {
+    provider: Arc<dyn LlmProvider>,
+    limits: ModelConcurrencyLimits,
+    /// One semaphore per model seen so far, created on first use.
+    slots: Mutex<HashMap<String, Arc<Semaphore>>>,
+    /// Continuations attempted by [`LlmGateway::complete_with_continuation`].
+    max_continuations: u32,
+    /// Largest prompt sent; see [`LlmGateway::fit_prompt`].
+    prompt_limit: PromptLimit,
+    /// Per-node responses reused by [`LlmGateway::complete_for_node`].
+    response_cache: Option<ResponseCache>,
+}
+
+impl LlmGateway {
+    /// Creates a gateway sending calls to `provider` under `limits`.
+    pub fn new(provider: Arc<dyn LlmProvider>, limits: ModelConcurrencyLimits) -> Self {
+        Self {
+            provider,
+            limits,
+            slots: Mutex::new(HashMap::new()),
+            max_continuations: DEFAULT_MAX_CONTINUATIONS,
+            prompt_limit: PromptLimit::default(),
+            response_cache: None,
+        }
+    }
+
+    /// Sets how many continuations
+    /// [`complete_with_continuation`](Self::complete_with_continuation)
+    /// attempts. Zero disables continuation.
+    #[must_use]
+    pub fn with_max_continuations(mut self, max_continuations: u32) -> Self {
+        self.max_continuations = max_continuations;
+        self
+    }
+
+    /// Sets the largest prompt sent. The default is no limit.
+    #[must_use]
+    pub fn with_prompt_limit(mut self, prompt_limit: PromptLimit) -> Self {
+        self.prompt_limit = prompt_limit;
+        self
+    }
+
+    /// Caches [`complete_for_node`](Self::complete_for_node) responses per
+    /// node under `config`. The default is no cache.
+    #[must_use]
+    pub fn with_response_cache(mut self, config: ResponseCacheConfig) -> Self {
+        self.response_cache = Some(ResponseCache::new(config));
+        self
+    }
+
+    /// Returns the configured limits.
+    pub fn limits(&self) -> &ModelConcurrencyLimits {
+        &self.limits
+    }
+
+    /// Drops Context Pack sections from `request` until it is within the
+    /// prompt limit, returning the warning to record if any were dropped.
+    ///
+    /// See [`crate::prompt_limit::fit_prompt`].
+    pub fn fit_prompt(&self, request: &mut CompletionRequest) -> Option<Diagnostic> {
+        fit_prompt(request, &self.prompt_limit)
+    }
+
+    /// Estimates the prompt tokens of `request` without sending it, so the
+    /// caller can check the run budget first. Apply
+    /// [`fit_prompt`](Self::fit_prompt) before estimating.
+    ///
+    /// # Errors
+    ///
+    /// As for [`LlmProvider::estimate_prompt_tokens`].
+    pub fn estimate_prompt_tokens(
+        &self,
+        request: &CompletionRequest,
+    ) -> Result<TokenEstimate, LlmError> {
+        let estimate = self.provider.estimate_prompt_tokens(request)?;
+        tracing::debug!(
+            model = %request.model,
+            tokens = %estimate.tokens,
+            approximate = estimate.approximate,
+            "estimated prompt tokens"
+        );
+        Ok(estimate)
+    }
+
+    /// Returns the number of calls to `model` that may start without waiting.
+    pub fn available(&self, model: &str) -> usize {
+        self.slots_for(model).available_permits()
+    }
+
+    /// Sends `request`, first waiting for a free slot for `request.model`.
+    ///
+    /// A prompt over the prompt limit is truncated first, as by
+    /// [`LlmGateway::fit_prompt`]; the warning is logged but not returned.
+    /// The slot is held until the provider returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns the provider's [`LlmError`] unchanged.
+    #[instrument(skip(self, request), fields(model = %request.model))]
+    pub async fn complete(
+        &self,
+        mut request: CompletionRequest,
+    ) -> Result<CompletionResponse, LlmError> {
+        self.fit_prompt(&mut request);
+        let _permit = self.acquire_slot(&request.model).await?;
+        let response = self.provider.complete(request).await?;
+        warn_if_truncated(&response);
+        Ok(response)
+    }
+
+    /// Sends `request` on behalf of `node`, using the node's configured model.
+    ///
+    /// `request.model` is replaced by [`PipelineGraph::model_for`] when the
+    /// node or the pipeline configures a model; otherwise it is sent as built
+    /// (the run's default model). Concurrency is limited per resolved model.
+    ///
+    /// With a response cache, an unexpired response the node received for an
+    /// identical request is returned without calling the provider, with zero
+    /// usage. Fresh responses are stored with the node's TTL.
+    ///
+    /// # Errors
+    ///
+    /// As for [`LlmGateway::complete`].
+    #[instrument(skip(self, graph, request), fields(%node))]
+    pub async fn complete_for_node(
+        &self,
+        graph: &PipelineGraph,
+        node: &NodeId,
+        mut request: CompletionRequest,
+    ) -> Result<CompletionResponse, LlmError> {
+        if let Some(model) = graph.model_for(node) {
+            if model != request.model {
+                tracing::debug!(default = %request.model, model, "using node model override");
+            }
+            request.model = model.to_string();
+        }
+        let Some(cache) = &self.response_cache else {
+            return self.complete(request).await;
+        };
+        if let Some(response) = cache.get(node, &request) {
+            tracing::debug!(model = %request.model, "LLM response served from cache");
+            return Ok(response);
+        }
+        let response = self.complete(request.clone()).await?;
+        cache.insert(node, &request, &response);
+        Ok(response)
+    }
+
+    /// Streams `request` like [`LlmGateway::complete`], abandoning the call
+    /// if `cancel` resolves first.
+    ///
+    /// The response is read through [`LlmProvider::complete_streaming`], so
+    /// text deltas are collected as they arrive. A cancelled call is dropped,
+    /// which closes the provider connection, and its concurrency slot is
+    /// released at once. A call still waiting for a slot is cancelled the
+    /// same way. The returned response names the requested model and carries
+    /// no `provider_request_id`, which streams do not report.
+    ///
+    /// # Errors
+    ///
+    /// - [`LlmError::Cancelled`] — `cancel` resolved first. `partial` holds
+    ///   the text deltas received before cancellation and `usage` the last
+    ///   usage reported (zero until the stream finishes).
+    /// - [`LlmError::Interrupted`] — the stream ended without finishing.
+    /// - Otherwise as for [`LlmGateway::complete`].
+    #[instrument(skip(self, request, cancel), fields(model = %request.model))]
+    pub async fn complete_until<C>(
+        &self,
+        request: CompletionRequest,
+        cancel: C,
+    ) -> Result<CompletionResponse, LlmError>
+    where
+        C: Future<Output = ()>,
+    {
+        let mut partial = PartialCompletion::new();
+        let finished = tokio::select! {
+            result = self.stream(request, &mut partial) => Some(result),
+            () = cancel => None,
+        };
+        finished.unwrap_or_else(|| {
+            tracing::warn!(
+                received = partial.text().len(),
+                "LLM call cancelled before the provider finished"
+            );
+            Err(partial.cancelled())
+        })
+    }
+
+    /// Streams `request` like [`LlmGateway::complete_until`], continuing from
+    /// the partial output when the response stream is interrupted.
+    ///
+    /// Each call is read through [`LlmProvider::complete_streaming`], so the
+    /// text received before a dropped connection or an early end of stream is
+    /// kept. On [`LlmError::Interrupted`] with non-empty output, the output so far
+    /// is appended to the original messages as an assistant turn, followed by
+    /// [`CONTINUATION_PROMPT`], and the request is sent again (see
+    /// [`continuation_request`]). The final response's `content` is every
+    /// piece joined in order and its `usage` the sum over all calls; `model`
+    /// and `finish_reason` come from the last call. Streams report no
+    /// `provider_request_id`.
+    ///
+    /// # Errors
+    ///
+    /// - [`LlmError::Interrupted`] — the continuation limit was reached, or
+    ///   the stream was cut off before any output. `partial` and `usage`
+    ///   cover every call made, so the caller can audit or retry.
+    /// - Otherwise as for [`LlmGateway::complete`]. Usage from earlier
+    ///   interrupted calls is not carried by other errors; it is logged.
+    #[instrument(skip(self, request), fields(model = %request.model))]
+    pub async fn complete_with_continuation(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse, LlmError> {
+        let mut output = String::new();
+        let mut usage = TokenUsage::zero();
+        let mut continuations = 0;
+        let mut next = request.clone();
+        loop {
+            match self.stream(next, &mut PartialCompletion::new()).await {
+                Ok(mut response) => {
+                    if continuations > 0 {
+                        output.push_str(&response.content);
+                        response.content = output;
+                        response.usage = usage + response.usage;
+                        tracing::info!(continuations, "LLM response completed by continuation");
+                    }
+                    return Ok(response);
+                }
+                Err(LlmError::Interrupted {
+                    message,
+                    partial,
+                    usage: call_usage,
+                }) => {
+                    output.push_str(&partial);
+                    usage = usage + call_usage;
+                    if partial.is_empty() || continuations >= self.max_continuations {
+                        return Err(LlmError::Interrupted {
+                            message,
+                            partial: output,
+                            usage,
+                        });
+                    }
+                    continuations += 1;
+                    tracing::warn!(
+                        continuations,
+                        received = output.len(),
+                        %message,
+                        "LLM stream interrupted; continuing from partial output"
+                    );
+                    next = continuation_request(&request, &output);
+                }
+                Err(error) => {
+                    if continuations > 0 {
+                        tracing::warn!(
+                            input_tokens = %usage.input_tokens,
+                            output_tokens = %usage.output_tokens,
+                            "LLM continuation failed; usage of interrupted calls not returned"
+                        );
+                    }
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    /// Streams `request` under its model's concurrency slot, appending each
+    /// text delta to `partial`, and assembles the finished response.
+    ///
+    /// # Errors
+    ///
+    /// - [`LlmError::Interrupted`] — the stream ended, or failed with
+    ///   [`LlmError::Transient`] (e.g. a dropped connection), before
+    ///   [`CompletionChunk::Finished`]; carries the text received.
+    /// - Otherwise the provider's [`LlmError`] unchanged.
+    async fn stream(
+        &self,
+        mut request: CompletionRequest,
+        partial: &mut PartialCompletion,
+    ) -> Result<CompletionResponse, LlmError> {
+        self.fit_prompt(&mut request);
+        let _permit = self.acquire_slot(&request.model).await?;
+        let model = request.model.clone();
+        let mut stream = self.provider.complete_streaming(request).await?;
+        loop {
+            let chunk = match stream.next_chunk().await {
+                Ok(chunk) => chunk,
+                Err(LlmError::Transient { message }) => {
+                    return Err(std::mem::take(partial).interrupted(message))
+                }
+                Err(error) => return Err(error),
+            };
+            match chunk {
+                Some(CompletionChunk::Text(delta)) => partial.push_text(&delta),
+                Some(CompletionChunk::Finished {
+                    finish_reason,
+                    usage,
+                }) => {
+                    partial.set_usage(usage);
+                    let response = CompletionResponse {
+                        content: partial.text().to_string(),
+                        model,
+                        usage,
+                        finish_reason,
+                        provider_request_id: None,
+                    };
+                    warn_if_truncated(&response);
+                    return Ok(response);
+                }
+                None => {
+                    return Err(std::mem::take(partial)
+                        .interrupted("response stream ended before the response finished"))
+                }
+            }
+        }
+    }
+
+    /// Waits for a free concurrency slot for `model`.
+    async fn acquire_slot(&self, model: &str) -> Result<OwnedSemaphorePermit, LlmError> {
+        let slots = self.slots_for(model);
+        if slots.available_permits() == 0 {
+            tracing::info!(
+                limit = self.limits.limit_for(model).get(),
+                "LLM call queued; model concurrency limit reached"
+            );
+        }
+        // The semaphores are never closed, so acquisition only fails if that
+        // invariant is broken.
+        slots
+            .acquire_owned()
+            .await
+            .map_err(|_| LlmError::Transient {
+                message: format!("concurrency slots for model '{model}' closed"),
+            })
+    }
+
+    /// Returns the semaphore for `model`, creating it at the configured limit.
+    fn slots_for(&self, model: &str) -> Arc<Semaphore> {
+        let mut slots = self
+            .slots
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        Arc::clone(
+            slots
+                .entry(model.to_string())
+                .or_insert_with(|| Arc::new(Semaphore::new(self.limits.limit_for(model).get()))),
+        )
+    }
+}
+
+/// Logs a warning when `response` was cut off at `max_tokens`.
+fn warn_if_truncated(response: &CompletionResponse) {
+    if response.finish_reason.is_truncated() {
+        tracing::warn!(
+            output_tokens = %response.usage.output_tokens,
+            "LLM response truncated at max_tokens"
+        );
+    }
+}
+
+/// Builds the request that asks the model to resume after `partial`.
+///
+/// `request`'s messages are kept, followed by `partial` as an assistant turn
+/// and [`CONTINUATION_PROMPT`] as a user turn; every other field is
+/// unchanged.
+pub fn continuation_request(request: &CompletionRequest, partial: &str) -> CompletionRequest {
+    let mut continued = request.clone();
+    continued.messages.push(Message::assistant(partial));
+    continued.messages.push(Message::user(CONTINUATION_PROMPT));
+    continued
+}
+
+#[cfg(test)]
+#[path = "gateway_tests.rs"]
+mod tests;
@@ -0,0 +1,695 @@
+use std::time::Duration;
+
+use pipeline::{
+    FinishReason, MessageRole, NodeDefinition, NodeGate, NodeType, PipelineSettings,
+    PipelineToolProfileConfig, ProfileName, SystemLayer, SystemSegment, TokenCount, ValidationKind,
+};
+
+use crate::test_support::{completion_request, completion_response, FakeLlmProvider};
+
+use super::*;
+
+/// Long enough for every spawned call to reach the provider or its slot.
+const SETTLE: Duration = Duration::from_millis(50);
+
+fn limit(n: usize) -> NonZeroUsize {
+    NonZeroUsize::new(n).unwrap()
+}
+
+fn gateway(provider: &Arc<FakeLlmProvider>, limits: ModelConcurrencyLimits) -> Arc<LlmGateway> {
+    Arc::new(LlmGateway::new(Arc::clone(provider) as _, limits))
+}
+
+fn text(delta: &str) -> Result<CompletionChunk, LlmError> {
+    Ok(CompletionChunk::Text(delta.to_string()))
+}
+
+fn finished(finish_reason: FinishReason) -> Result<CompletionChunk, LlmError> {
+    Ok(CompletionChunk::Finished {
+        finish_reason,
+        usage: TokenUsage::new(TokenCount::new(12), TokenCount::new(3)),
+    })
+}
+
+#[test]
+fn test_limit_for_listed_model_returns_model_limit() {
+    let limits = ModelConcurrencyLimits::default().with_model("model-a", limit(1));
+
+    assert_eq!(limits.limit_for("model-a"), limit(1));
+    assert_eq!(limits.limit_for("model-b"), DEFAULT_MODEL_CONCURRENCY);
+}
+
+#[test]
+fn test_with_model_repeated_model_replaces_limit() {
+    let limits = ModelConcurrencyLimits::default()
+        .with_model("model-a", limit(1))
+        .with_model("model-a", limit(3));
+
+    assert_eq!(limits.limit_for("model-a"), limit(3));
+}
+
+#[test]
+fn test_available_unused_model_returns_configured_limit() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let gateway = gateway(
+        &provider,
+        ModelConcurrencyLimits::default().with_model("model-a", limit(2)),
+    );
+
+    assert_eq!(gateway.available("model-a"), 2);
+    assert_eq!(
+        gateway.available("model-b"),
+        DEFAULT_MODEL_CONCURRENCY.get()
+    );
+}
+
+#[tokio::test]
+async fn test_complete_two_models_enforces_each_limit_separately() {
+    let provider = Arc::new(FakeLlmProvider::gated());
+    let gateway = gateway(
+        &provider,
+        ModelConcurrencyLimits::default()
+            .with_model("model-a", limit(1))
+            .with_model("model-b", limit(2)),
+    );
+    let calls: Vec<_> = ["model-a", "model-b"]
+        .into_iter()
+        .flat_map(|model| std::iter::repeat(model).take(3))
+        .map(|model| {
+            let gateway = Arc::clone(&gateway);
+            tokio::spawn(async move { gateway.complete(completion_request(model, "hi")).await })
+        })
+        .collect();
+    tokio::time::sleep(SETTLE).await;
+
+    assert_eq!(provider.in_flight("model-a"), 1);
+    assert_eq!(provider.in_flight("model-b"), 2);
+    assert_eq!(gateway.available("model-a"), 0);
+    assert_eq!(gateway.available("model-b"), 0);
+
+    provider.release(6);
+    for call in calls {
+        call.await.unwrap().unwrap();
+    }
+    assert_eq!(provider.peak("model-a"), 1);
+    assert_eq!(provider.peak("model-b"), 2);
+    assert_eq!(provider.requests().len(), 6);
+    assert_eq!(gateway.available("model-a"), 1);
+    assert_eq!(gateway.available("model-b"), 2);
+}
+
+#[tokio::test]
+async fn test_complete_model_at_limit_does_not_delay_other_model() {
+    let provider = Arc::new(FakeLlmProvider::gated());
+    let gateway = gateway(
+        &provider,
+        ModelConcurrencyLimits::default().with_model("model-a", limit(1)),
+    );
+    let held = tokio::spawn({
+        let gateway = Arc::clone(&gateway);
+        async move {
+            gateway
+                .complete(completion_request("model-a", "first"))
+                .await
+        }
+    });
+    let queued = tokio::spawn({
+        let gateway = Arc::clone(&gateway);
+        async move {
+            gateway
+                .complete(completion_request("model-a", "second"))
+                .await
+        }
+    });
+    let other = tokio::spawn({
+        let gateway = Arc::clone(&gateway);
+        async move {
+            gateway
+                .complete(completion_request("model-b", "other"))
+                .await
+        }
+    });
+    tokio::time::sleep(SETTLE).await;
+
+    assert_eq!(provider.in_flight("model-a"), 1);
+    assert_eq!(provider.in_flight("model-b"), 1);
+
+    provider.release(3);
+    for call in [held, queued, other] {
+        call.await.unwrap().unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_complete_provider_error_releases_slot() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push(Err(LlmError::Transient {
+        message: "overloaded".to_string(),
+    }));
+    let gateway = gateway(
+        &provider,
+        ModelConcurrencyLimits::default().with_model("model-a", limit(1)),
+    );
+
+    let error = gateway
+        .complete(completion_request("model-a", "hi"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, LlmError::Transient { .. }));
+    assert_eq!(gateway.available("model-a"), 1);
+}
+
+#[tokio::test]
+async fn test_complete_truncated_response_returned_with_max_tokens_reason() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push(Ok(CompletionResponse {
+        finish_reason: FinishReason::MaxTokens,
+        ..completion_response("model-a", "cut off mid")
+    }));
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    let response = gateway
+        .complete(completion_request("model-a", "hi"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.finish_reason, FinishReason::MaxTokens);
+    assert_eq!(response.content, "cut off mid");
+}
+
+#[tokio::test]
+async fn test_complete_over_prompt_limit_sends_request_without_context() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push(Ok(completion_response("model-a", "ok")));
+    let gateway = LlmGateway::new(
+        Arc::clone(&provider) as _,
+        ModelConcurrencyLimits::default(),
+    )
+    .with_prompt_limit(PromptLimit {
+        max_chars: Some(10),
+    });
+    let mut request = completion_request("model-a", "hi");
+    request.system = vec![
+        SystemSegment::new(SystemLayer::Context, "## Pack\n\nbackground"),
+        SystemSegment::new(SystemLayer::Task, "task"),
+    ];
+
+    gateway.complete(request).await.unwrap();
+
+    let sent = &provider.requests()[0];
+    assert_eq!(
+        sent.system,
+        vec![SystemSegment::new(SystemLayer::Task, "task")]
+    );
+    assert_eq!(sent.messages, vec![Message::user("hi")]);
+}
+
+#[test]
+fn test_estimate_prompt_tokens_returns_provider_estimate_without_calling() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+    let request = completion_request("model-a", "twelve chars");
+
+    let estimate = gateway.estimate_prompt_tokens(&request).unwrap();
+
+    assert_eq!(estimate, TokenEstimate::heuristic(&request));
+    assert_eq!(estimate.tokens, TokenCount::new(3));
+    assert!(estimate.approximate);
+    assert!(provider.requests().is_empty());
+}
+
+#[test]
+fn test_fit_prompt_no_limit_configured_returns_none() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let gateway = LlmGateway::new(
+        Arc::clone(&provider) as _,
+        ModelConcurrencyLimits::default(),
+    );
+    let mut request = completion_request("model-a", "hi");
+    request.system = vec![SystemSegment::new(SystemLayer::Context, "background")];
+
+    assert!(gateway.fit_prompt(&mut request).is_none());
+    assert_eq!(request.system.len(), 1);
+}
+
+#[test]
+fn test_continuation_request_appends_partial_and_prompt() {
+    let request = completion_request("model-a", "write a poem");
+
+    let continued = continuation_request(&request, "Roses are");
+
+    let turns: Vec<_> = continued
+        .messages
+        .iter()
+        .map(|message| (message.role, message.content.as_str()))
+        .collect();
+    assert_eq!(
+        turns,
+        vec![
+            (MessageRole::User, "write a poem"),
+            (MessageRole::Assistant, "Roses are"),
+            (MessageRole::User, CONTINUATION_PROMPT),
+        ]
+    );
+    assert_eq!(continued.model, request.model);
+    assert_eq!(continued.max_tokens, request.max_tokens);
+}
+
+fn dropped() -> Result<CompletionChunk, LlmError> {
+    Err(LlmError::Transient {
+        message: "connection reset".to_string(),
+    })
+}
+
+#[tokio::test]
+async fn test_complete_with_continuation_interrupted_stream_joins_output_and_usage() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push_stream(vec![text("Roses "), text("are")], false);
+    provider.push_stream(vec![text(" red"), finished(FinishReason::EndTurn)], false);
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    let response = gateway
+        .complete_with_continuation(completion_request("model-a", "write a poem"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.content, "Roses are red");
+    assert_eq!(response.finish_reason, FinishReason::EndTurn);
+    assert_eq!(
+        response.usage,
+        TokenUsage::new(TokenCount::new(12), TokenCount::new(3))
+    );
+    let requests = provider.requests();
+    assert_eq!(requests.len(), 2);
+    let last = requests[1].messages.last().unwrap();
+    assert_eq!(last.content, CONTINUATION_PROMPT);
+    assert_eq!(requests[1].messages[1].content, "Roses are");
+}
+
+#[tokio::test]
+async fn test_complete_with_continuation_dropped_connection_continues_from_partial() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push_stream(vec![text("Roses are"), dropped()], false);
+    provider.push_stream(vec![text(" red"), finished(FinishReason::EndTurn)], false);
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    let response = gateway
+        .complete_with_continuation(completion_request("model-a", "write a poem"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.content, "Roses are red");
+    assert_eq!(provider.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_complete_with_continuation_empty_partial_returns_interrupted() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push_stream(vec![dropped()], false);
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    let error = gateway
+        .complete_with_continuation(completion_request("model-a", "hi"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, LlmError::Interrupted { .. }));
+    assert_eq!(error.partial_output(), Some(""));
+    assert_eq!(provider.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_complete_with_continuation_limit_reached_returns_joined_partial() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push_stream(vec![text("one ")], false);
+    provider.push_stream(vec![text("two")], false);
+    let gateway = Arc::new(
+        LlmGateway::new(
+            Arc::clone(&provider) as _,
+            ModelConcurrencyLimits::default(),
+        )
+        .with_max_continuations(1),
+    );
+
+    let error = gateway
+        .complete_with_continuation(completion_request("model-a", "count"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, LlmError::Interrupted { .. }));
+    assert_eq!(error.partial_output(), Some("one two"));
+    assert_eq!(provider.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_complete_with_continuation_zero_continuations_returns_interrupted() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push_stream(vec![text("half")], false);
+    let gateway = Arc::new(
+        LlmGateway::new(
+            Arc::clone(&provider) as _,
+            ModelConcurrencyLimits::default(),
+        )
+        .with_max_continuations(0),
+    );
+
+    let error = gateway
+        .complete_with_continuation(completion_request("model-a", "hi"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.partial_output(), Some("half"));
+    assert_eq!(provider.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_complete_with_continuation_non_transient_error_returned_unchanged() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push_stream(
+        vec![
+            text("partial"),
+            Err(LlmError::Authentication {
+                message: "revoked".to_string(),
+            }),
+        ],
+        false,
+    );
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    let error = gateway
+        .complete_with_continuation(completion_request("model-a", "hi"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, LlmError::Authentication { .. }));
+    assert_eq!(provider.requests().len(), 1);
+}
+
+// ─── Cancellation ───────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_complete_until_stream_finishes_returns_joined_deltas() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push_stream(
+        vec![text("Hel"), text("lo"), finished(FinishReason::EndTurn)],
+        false,
+    );
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    let response = gateway
+        .complete_until(completion_request("model-a", "hi"), std::future::pending())
+        .await
+        .unwrap();
+
+    assert_eq!(response.content, "Hello");
+    assert_eq!(response.model, "model-a");
+    assert_eq!(response.finish_reason, FinishReason::EndTurn);
+    assert_eq!(
+        response.usage,
+        TokenUsage::new(TokenCount::new(12), TokenCount::new(3))
+    );
+}
+
+#[tokio::test]
+async fn test_complete_until_cancel_mid_stream_returns_partial_text() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push_stream(vec![text("Roses "), text("are")], true);
+    let gateway = gateway(
+        &provider,
+        ModelConcurrencyLimits::default().with_model("model-a", limit(1)),
+    );
+
+    let error = gateway
+        .complete_until(
+            completion_request("model-a", "write a poem"),
+            tokio::time::sleep(SETTLE),
+        )
+        .await
+        .unwrap_err();
+
+    match error {
+        LlmError::Cancelled { partial, usage } => {
+            assert_eq!(partial, "Roses are");
+            assert_eq!(usage, TokenUsage::zero());
+        }
+        other => panic!("expected cancellation, got {other:?}"),
+    }
+    assert_eq!(gateway.available("model-a"), 1);
+}
+
+#[tokio::test]
+async fn test_complete_until_cancel_before_output_returns_empty_partial() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push_stream(Vec::new(), true);
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    let error = gateway
+        .complete_until(
+            completion_request("model-a", "hi"),
+            tokio::time::sleep(SETTLE),
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.partial_output(), Some(""));
+    assert!(matches!(error, LlmError::Cancelled { .. }));
+}
+
+#[tokio::test]
+async fn test_complete_until_stream_ends_without_finish_returns_interrupted() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push_stream(vec![text("half an ans")], false);
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    let error = gateway
+        .complete_until(completion_request("model-a", "hi"), std::future::pending())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, LlmError::Interrupted { .. }));
+    assert_eq!(error.partial_output(), Some("half an ans"));
+}
+
+#[tokio::test]
+async fn test_complete_until_mid_stream_error_returned_unchanged() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push_stream(
+        vec![
+            text("partial"),
+            Err(LlmError::Authentication {
+                message: "revoked".to_string(),
+            }),
+        ],
+        false,
+    );
+    let gateway = gateway(
+        &provider,
+        ModelConcurrencyLimits::default().with_model("model-a", limit(1)),
+    );
+
+    let error = gateway
+        .complete_until(completion_request("model-a", "hi"), std::future::pending())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, LlmError::Authentication { .. }));
+    assert_eq!(gateway.available("model-a"), 1);
+}
+
+#[tokio::test]
+async fn test_complete_until_non_streaming_provider_returns_whole_response() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push(Ok(completion_response("model-a", "in one piece")));
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    let response = gateway
+        .complete_until(completion_request("model-a", "hi"), std::future::pending())
+        .await
+        .unwrap();
+
+    assert_eq!(response.content, "in one piece");
+    assert_eq!(provider.requests().len(), 1);
+}
+
+// ─── Node model overrides ───────────────────────────────────────────────────
+
+fn node_id(id: &str) -> NodeId {
+    NodeId::new(id).unwrap()
+}
+
+/// Graph with a `triage` node on `claude-haiku` and a `plan` node without an
+/// override, under the pipeline default `default_model`.
+fn graph_with_models(default_model: Option<&str>) -> PipelineGraph {
+    let node = |id: &str, model: Option<&str>| NodeDefinition {
+        id: node_id(id),
+        node_type: NodeType::Llm,
+        declared_inputs: Vec::new(),
+        declared_outputs: Vec::new(),
+        timeout: None,
+        cost_budget: None,
+        gate: NodeGate::AutoProceed,
+        validation_kind: ValidationKind::None,
+        abort_siblings_on_failure: false,
+        priority: 0,
+        model: model.map(str::to_string),
+    };
+    PipelineGraph {
+        nodes: vec![node("triage", Some("claude-haiku")), node("plan", None)],
+        edges: Vec::new(),
+        evaluation_modes: HashMap::new(),
+        explicit_edge_lists: HashMap::new(),
+        settings: PipelineSettings {
+            default_timeout: None,
+            default_cost_budget: None,
+            max_node_retries: 3,
+            default_model: default_model.map(str::to_string),
+        },
+        tool_profiles: PipelineToolProfileConfig {
+            default_profile: ProfileName::new("default").unwrap(),
+            node_overrides: HashMap::new(),
+        },
+    }
+}
+
+#[tokio::test]
+async fn test_complete_for_node_with_override_sends_node_model() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    gateway
+        .complete_for_node(
+            &graph_with_models(Some("claude-sonnet")),
+            &node_id("triage"),
+            completion_request("run-default", "triage this"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(provider.requests()[0].model, "claude-haiku");
+}
+
+#[tokio::test]
+async fn test_complete_for_node_without_override_sends_pipeline_default() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    gateway
+        .complete_for_node(
+            &graph_with_models(Some("claude-sonnet")),
+            &node_id("plan"),
+            completion_request("run-default", "plan this"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(provider.requests()[0].model, "claude-sonnet");
+}
+
+#[tokio::test]
+async fn test_complete_for_node_nothing_configured_keeps_request_model() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+
+    gateway
+        .complete_for_node(
+            &graph_with_models(None),
+            &node_id("plan"),
+            completion_request("run-default", "plan this"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(provider.requests()[0].model, "run-default");
+}
+
+#[tokio::test]
+async fn test_complete_for_node_override_limited_by_node_model_slots() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push(Err(LlmError::Transient {
+        message: "overloaded".to_string(),
+    }));
+    let gateway = gateway(
+        &provider,
+        ModelConcurrencyLimits::default().with_model("claude-haiku", limit(1)),
+    );
+
+    let result = gateway
+        .complete_for_node(
+            &graph_with_models(None),
+            &node_id("triage"),
+            completion_request("run-default", "triage this"),
+        )
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(gateway.available("claude-haiku"), 1);
+    assert_eq!(provider.requests()[0].model, "claude-haiku");
+}
+
+// ─── Response cache ─────────────────────────────────────────────────────────
+
+fn cached_gateway(provider: &Arc<FakeLlmProvider>, default_ttl_secs: Option<u64>) -> LlmGateway {
+    LlmGateway::new(Arc::clone(provider) as _, ModelConcurrencyLimits::default())
+        .with_response_cache(ResponseCacheConfig {
+            enabled: true,
+            default_ttl_secs,
+            ..ResponseCacheConfig::default()
+        })
+}
+
+#[tokio::test]
+async fn test_complete_for_node_repeat_within_ttl_answers_from_cache() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let gateway = cached_gateway(&provider, Some(3600));
+    let graph = graph_with_models(None);
+
+    let first = gateway
+        .complete_for_node(&graph, &node_id("plan"), completion_request("m", "plan"))
+        .await
+        .unwrap();
+    let second = gateway
+        .complete_for_node(&graph, &node_id("plan"), completion_request("m", "plan"))
+        .await
+        .unwrap();
+
+    assert_eq!(provider.requests().len(), 1);
+    assert_eq!(second.content, first.content);
+    assert_eq!(second.usage, TokenUsage::zero());
+}
+
+#[tokio::test]
+async fn test_complete_for_node_repeat_after_ttl_calls_provider_again() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let gateway = cached_gateway(&provider, Some(1));
+    let graph = graph_with_models(None);
+
+    gateway
+        .complete_for_node(&graph, &node_id("plan"), completion_request("m", "plan"))
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(1_100)).await;
+    let second = gateway
+        .complete_for_node(&graph, &node_id("plan"), completion_request("m", "plan"))
+        .await
+        .unwrap();
+
+    assert_eq!(provider.requests().len(), 2);
+    assert_ne!(second.usage, TokenUsage::zero());
+}
+
+#[tokio::test]
+async fn test_complete_for_node_cache_disabled_calls_provider_each_time() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let gateway = gateway(&provider, ModelConcurrencyLimits::default());
+    let graph = graph_with_models(None);
+
+    for _ in 0..2 {
+        gateway
+            .complete_for_node(&graph, &node_id("plan"), completion_request("m", "plan"))
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(provider.requests().len(), 2);
+}
@@ -0,0 +1,74 @@
+//! Skipping events that are already reflected in the run state.
+//!
+//! After a crash the event queue may redeliver events whose effects were
+//! persisted before the process died. Each step records the event it handled
+//! in [`PipelineState::last_processed_event`]. An incoming event is treated as
+//! already applied when:
+//!
+//! - its delivery GUID equals the recorded one (a redelivery), or
+//! - it was delivered strictly before the recorded event.
+//!
+//! An event with neither a delivery GUID nor a delivery time can't be
+//! compared, so it is always processed.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Event idempotency.
+
+use pipeline::{GitHubEvent, PipelineState, ProcessedEventMarker, WorkItemId};
+
+use crate::executor::StepResult;
+
+/// Returns `true` if `event` is already reflected in `state`.
+pub fn is_already_applied(state: &PipelineState, event: &GitHubEvent) -> bool {
+    let Some(marker) = &state.last_processed_event else {
+        return false;
+    };
+    let context = event.context();
+
+    if let (Some(seen), Some(incoming)) = (&marker.delivery_id, &context.delivery_id) {
+        if seen == incoming {
+            return true;
+        }
+    }
+    matches!(
+        (marker.delivered_at, context.delivered_at),
+        (Some(seen), Some(incoming)) if incoming < seen
+    )
+}
+
+/// Records `event` as the most recent event applied to `state`.
+///
+/// Call before persisting the state produced by handling the event.
+pub fn record_processed(state: &mut PipelineState, event: &GitHubEvent) {
+    let context = event.context();
+    state.last_processed_event = Some(ProcessedEventMarker {
+        delivery_id: context.delivery_id.clone(),
+        delivered_at: context.delivered_at,
+    });
+}
+
+/// Returns a no-op [`StepResult`] if `event` was already applied to `state`.
+///
+/// The result has no executed nodes, no edge evaluations, zero cost, and no
+/// outcome. Returns `None` when the event is new and should be processed.
+pub fn skip_if_applied(
+    state: &PipelineState,
+    event: &GitHubEvent,
+    work_item_id: WorkItemId,
+) -> Option<StepResult> {
+    if is_already_applied(state, event) {
+        tracing::info!(
+            run_id = %state.run_id,
+            delivery_id = ?event.context().delivery_id,
+            "skipping event already applied to pipeline state"
+        );
+        Some(StepResult::new(state.run_id, work_item_id))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+#[path = "idempotency_tests.rs"]
+mod tests;
@@ -0,0 +1,113 @@
+use pipeline::{EventContext, Timestamp};
+
+use crate::test_support::pipeline_state;
+
+use super::*;
+
+fn at(time: &str) -> Timestamp {
+    Timestamp::parse_rfc3339(&format!("2026-03-01T{time}Z")).unwrap()
+}
+
+fn event(delivery_id: Option<&str>, delivered_at: Option<Timestamp>) -> GitHubEvent {
+    GitHubEvent::LabelApplied {
+        work_item_id: WorkItemId::new(42),
+        label: "cogworks:run".to_string(),
+        context: EventContext {
+            delivery_id: delivery_id.map(str::to_string),
+            delivered_at,
+            ..EventContext::default()
+        },
+    }
+}
+
+fn state_after(event: &GitHubEvent) -> PipelineState {
+    let mut state = pipeline_state();
+    record_processed(&mut state, event);
+    state
+}
+
+#[test]
+fn test_is_already_applied_fresh_state_returns_false() {
+    let incoming = event(Some("delivery-1"), Some(at("12:00:00")));
+
+    assert!(!is_already_applied(&pipeline_state(), &incoming));
+}
+
+#[test]
+fn test_is_already_applied_same_delivery_id_returns_true() {
+    let state = state_after(&event(Some("delivery-1"), Some(at("12:00:00"))));
+
+    let redelivery = event(Some("delivery-1"), Some(at("12:05:00")));
+
+    assert!(is_already_applied(&state, &redelivery));
+}
+
+#[test]
+fn test_is_already_applied_earlier_delivery_returns_true() {
+    let state = state_after(&event(Some("delivery-2"), Some(at("12:00:00"))));
+
+    let older = event(Some("delivery-1"), Some(at("11:59:59")));
+
+    assert!(is_already_applied(&state, &older));
+}
+
+#[test]
+fn test_is_already_applied_later_delivery_returns_false() {
+    let state = state_after(&event(Some("delivery-1"), Some(at("12:00:00"))));
+
+    let newer = event(Some("delivery-2"), Some(at("12:00:01")));
+
+    assert!(!is_already_applied(&state, &newer));
+}
+
+#[test]
+fn test_is_already_applied_event_without_markers_returns_false() {
+    let state = state_after(&event(Some("delivery-1"), Some(at("12:00:00"))));
+
+    assert!(!is_already_applied(&state, &event(None, None)));
+}
+
+#[test]
+fn test_record_processed_copies_delivery_fields() {
+    let state = state_after(&event(Some("delivery-7"), Some(at("08:30:00"))));
+
+    assert_eq!(
+        state.last_processed_event,
+        Some(ProcessedEventMarker {
+            delivery_id: Some("delivery-7".to_string()),
+            delivered_at: Some(at("08:30:00")),
+        })
+    );
+}
+
+#[test]
+fn test_skip_if_applied_fresh_event_returns_none() {
+    let state = state_after(&event(Some("delivery-1"), Some(at("12:00:00"))));
+
+    let result = skip_if_applied(
+        &state,
+        &event(Some("delivery-2"), Some(at("12:01:00"))),
+        WorkItemId::new(42),
+    );
+
+    assert!(result.is_none());
+}
+
+#[test]
+fn test_skip_if_applied_applied_event_returns_no_op_step() {
+    let state = state_after(&event(Some("delivery-1"), Some(at("12:00:00"))));
+
+    let step = skip_if_applied(
+        &state,
+        &event(Some("delivery-1"), Some(at("12:00:00"))),
+        WorkItemId::new(42),
+    )
+    .unwrap();
+
+    assert_eq!(step.run_id, state.run_id);
+    assert_eq!(step.work_item_id, WorkItemId::new(42));
+    assert!(step.executed_nodes.is_empty());
+    assert!(step.edge_evaluations.is_empty());
+    assert!(step.outcome.is_none());
+    assert_eq!(step.total_cost(), pipeline::TokenCost::zero());
+}
@@ -0,0 +1,136 @@
+//! What a node does when injection detection fires.
+//!
+//! By default a detected injection halts the run with a
+//! [`CogWorksError::PipelineHalt`] ([`HaltReason::InjectionGuard`]) and the
+//! work item goes on hold
+//! ([`InjectionPolicy::Halt`]). Some organisations prefer to keep the run
+//! going under review instead: with [`InjectionPolicy::WarnAndContinue`] the
+//! offending text is stripped from the content, a diagnostic at the
+//! configured severity is recorded, and the node proceeds. Either way the
+//! detection itself is audited (`AuditEvent::InjectionDetected`) by the
+//! caller.
+//!
+//! ```toml
+//! [injection]
+//! mode = "warn_and_continue"
+//! severity = "blocking"   # or "warning" (default)
+//! ```
+//!
+//! A `blocking` diagnostic lets the run continue but fails the review gate,
+//! so a human still has to look at the result before it merges.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/operations.md` §Injection Detected (Hold State).
+
+use serde::{Deserialize, Serialize};
+
+use pipeline::{
+    audit::InjectionDetectionRecord, CogWorksError, Diagnostic, DiagnosticCategory,
+    DiagnosticSeverity, HaltReason,
+};
+
+/// Category of the diagnostic recorded under
+/// [`InjectionPolicy::WarnAndContinue`].
+pub const INJECTION_DIAGNOSTIC_CATEGORY: &str = "injection";
+
+/// Text put in place of each stripped occurrence of the offending text.
+pub const STRIPPED_INJECTION_PLACEHOLDER: &str = "[removed: suspected prompt injection]";
+
+/// How a detected injection is handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum InjectionPolicy {
+    /// Halt with [`HaltReason::InjectionGuard`]; the work item is held.
+    #[default]
+    Halt,
+    /// Strip the offending text, record a diagnostic, and continue.
+    WarnAndContinue {
+        /// Severity of the recorded diagnostic.
+        #[serde(default = "default_warn_severity")]
+        severity: DiagnosticSeverity,
+    },
+}
+
+fn default_warn_severity() -> DiagnosticSeverity {
+    DiagnosticSeverity::Warning
+}
+
+/// The outcome of applying an [`InjectionPolicy`] to a detection.
+#[derive(Debug)]
+pub enum InjectionResolution {
+    /// The node must stop and return this error.
+    Halt(CogWorksError),
+    /// The node proceeds with `content` and records `diagnostic`.
+    Continue {
+        /// The content with every occurrence of the offending text replaced
+        /// by [`STRIPPED_INJECTION_PLACEHOLDER`].
+        content: String,
+        /// The finding to report.
+        diagnostic: Diagnostic,
+    },
+}
+
+impl InjectionPolicy {
+    /// Decides what to do about `detection`, found in `content`.
+    pub fn resolve(
+        &self,
+        detection: &InjectionDetectionRecord,
+        content: &str,
+    ) -> InjectionResolution {
+        match *self {
+            InjectionPolicy::Halt => {
+                tracing::error!(
+                    node = %detection.node_id,
+                    source = %detection.source_label,
+                    pattern = %detection.pattern,
+                    "injection detected; halting"
+                );
+                InjectionResolution::Halt(CogWorksError::halt(
+                    HaltReason::InjectionGuard,
+                    format!(
+                        "injection detected in '{}': {}",
+                        detection.source_label, detection.offending_text
+                    ),
+                ))
+            }
+            InjectionPolicy::WarnAndContinue { severity } => {
+                tracing::warn!(
+                    node = %detection.node_id,
+                    source = %detection.source_label,
+                    pattern = %detection.pattern,
+                    ?severity,
+                    "injection detected; stripping and continuing"
+                );
+                InjectionResolution::Continue {
+                    content: strip_injection(content, &detection.offending_text),
+                    diagnostic: Diagnostic {
+                        artifact: None,
+                        location: Some(detection.source_label.clone()),
+                        severity,
+                        category: DiagnosticCategory::from_static(INJECTION_DIAGNOSTIC_CATEGORY),
+                        message: format!(
+                            "Suspected prompt injection ({}) removed from {}: {}",
+                            detection.pattern, detection.source_label, detection.offending_text
+                        ),
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// Replaces every occurrence of `offending_text` in `content` with
+/// [`STRIPPED_INJECTION_PLACEHOLDER`].
+///
+/// Empty `offending_text` leaves `content` unchanged.
+pub fn strip_injection(content: &str, offending_text: &str) -> String {
+    if offending_text.is_empty() {
+        return content.to_string();
+    }
+    content.replace(offending_text, STRIPPED_INJECTION_PLACEHOLDER)
+}
+
+#[cfg(test)]
+#[path = "injection_tests.rs"]
+mod tests;
@@ -0,0 +1,95 @@
+//! Labels applied when the Intake node picks up a work item.
+//!
+//! Teams mark picked-up work with labels of their own (e.g.
+//! `cogworks:triaged`) so their boards and filters show it. [`IntakeLabels`]
+//! lists them; [`apply_intake_labels`] adds the ones the issue does not
+//! already carry and records them in [`PipelineState::expected_labels`], so
+//! the label drift check does not report them as human changes.
+//!
+//! Applying is idempotent: labels already on the issue are not re-added, and
+//! a redelivered trigger event applies nothing.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Intake labels.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use pipeline::{GitHubOperationError, IssueTracker, Label, PipelineState, WorkItemId};
+
+/// Label applied at intake when none are configured.
+pub const DEFAULT_INTAKE_LABEL: &str = "cogworks:triaged";
+
+/// Labels the Intake node applies to every work item it picks up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntakeLabels {
+    /// Label names; an empty list applies nothing.
+    #[serde(default = "default_intake_labels")]
+    pub labels: Vec<String>,
+}
+
+fn default_intake_labels() -> Vec<String> {
+    vec![DEFAULT_INTAKE_LABEL.to_string()]
+}
+
+impl Default for IntakeLabels {
+    fn default() -> Self {
+        Self {
+            labels: default_intake_labels(),
+        }
+    }
+}
+
+/// Adds the configured intake labels missing from `work_item_id`.
+///
+/// Reads the issue's labels once, then adds each configured label that is not
+/// present, in configuration order (duplicates in the configuration are
+/// applied once). Every configured label is added to
+/// `state.expected_labels`. Returns the names that were added.
+///
+/// # Errors
+///
+/// Returns the first [`GitHubOperationError`] from reading or adding labels.
+/// Labels added before the failure stay on the issue and in `state`; calling
+/// again adds only the rest.
+#[instrument(skip(state, tracker, config), fields(run_id = %state.run_id))]
+pub async fn apply_intake_labels(
+    state: &mut PipelineState,
+    tracker: &dyn IssueTracker,
+    work_item_id: WorkItemId,
+    config: &IntakeLabels,
+) -> Result<Vec<String>, GitHubOperationError> {
+    if config.labels.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut present: BTreeSet<String> = tracker
+        .get_labels(work_item_id)
+        .await?
+        .into_iter()
+        .map(|label| label.name)
+        .collect();
+
+    let mut applied = Vec::new();
+    for name in &config.labels {
+        if present.insert(name.clone()) {
+            let label = Label {
+                name: name.clone(),
+                color: None,
+            };
+            tracker.add_label(work_item_id, &label).await?;
+            applied.push(name.clone());
+        }
+        state.expected_labels.insert(name.clone());
+    }
+    if !applied.is_empty() {
+        tracing::info!(labels = ?applied, "applied intake labels");
+    }
+    Ok(applied)
+}
+
+#[cfg(test)]
+#[path = "intake_labels_tests.rs"]
+mod tests;
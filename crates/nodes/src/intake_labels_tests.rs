@@ -0,0 +1,142 @@
+use pipeline::IssueTracker;
+
+use crate::test_support::{pipeline_state, FakeIssueTracker};
+
+use super::*;
+
+fn work_item() -> WorkItemId {
+    WorkItemId::new(7)
+}
+
+fn labels(names: &[&str]) -> IntakeLabels {
+    IntakeLabels {
+        labels: names.iter().map(|name| name.to_string()).collect(),
+    }
+}
+
+async fn label_names(tracker: &FakeIssueTracker) -> Vec<String> {
+    tracker
+        .get_labels(work_item())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|label| label.name)
+        .collect()
+}
+
+#[test]
+fn test_intake_labels_default_applies_triaged_label() {
+    assert_eq!(
+        IntakeLabels::default().labels,
+        vec![DEFAULT_INTAKE_LABEL.to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_apply_intake_labels_missing_labels_added_in_config_order() {
+    let tracker = FakeIssueTracker::default();
+    let mut state = pipeline_state();
+
+    let applied = apply_intake_labels(
+        &mut state,
+        &tracker,
+        work_item(),
+        &labels(&["cogworks:triaged", "team:platform"]),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(applied, vec!["cogworks:triaged", "team:platform"]);
+    assert_eq!(
+        label_names(&tracker).await,
+        vec!["cogworks:triaged", "team:platform"]
+    );
+}
+
+#[tokio::test]
+async fn test_apply_intake_labels_existing_label_not_re_added() {
+    let tracker = FakeIssueTracker::with_labels(work_item(), ["cogworks:triaged", "bug"]);
+    let mut state = pipeline_state();
+
+    let applied = apply_intake_labels(
+        &mut state,
+        &tracker,
+        work_item(),
+        &labels(&["cogworks:triaged", "team:platform"]),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(applied, vec!["team:platform"]);
+    assert_eq!(
+        label_names(&tracker).await,
+        vec!["cogworks:triaged", "bug", "team:platform"]
+    );
+}
+
+#[tokio::test]
+async fn test_apply_intake_labels_every_configured_label_expected() {
+    let tracker = FakeIssueTracker::with_labels(work_item(), ["cogworks:triaged"]);
+    let mut state = pipeline_state();
+
+    apply_intake_labels(
+        &mut state,
+        &tracker,
+        work_item(),
+        &labels(&["cogworks:triaged", "team:platform"]),
+    )
+    .await
+    .unwrap();
+
+    assert!(state.expected_labels.contains("cogworks:triaged"));
+    assert!(state.expected_labels.contains("team:platform"));
+    assert!(!state.expected_labels.contains("bug"));
+}
+
+#[tokio::test]
+async fn test_apply_intake_labels_second_call_applies_nothing() {
+    let tracker = FakeIssueTracker::default();
+    let mut state = pipeline_state();
+    let config = labels(&["cogworks:triaged"]);
+    apply_intake_labels(&mut state, &tracker, work_item(), &config)
+        .await
+        .unwrap();
+
+    let applied = apply_intake_labels(&mut state, &tracker, work_item(), &config)
+        .await
+        .unwrap();
+
+    assert!(applied.is_empty());
+    assert_eq!(label_names(&tracker).await, vec!["cogworks:triaged"]);
+}
+
+#[tokio::test]
+async fn test_apply_intake_labels_duplicate_config_entry_applied_once() {
+    let tracker = FakeIssueTracker::default();
+    let mut state = pipeline_state();
+
+    let applied = apply_intake_labels(
+        &mut state,
+        &tracker,
+        work_item(),
+        &labels(&["team:platform", "team:platform"]),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(applied, vec!["team:platform"]);
+    assert_eq!(label_names(&tracker).await, vec!["team:platform"]);
+}
+
+#[tokio::test]
+async fn test_apply_intake_labels_empty_config_applies_nothing() {
+    let tracker = FakeIssueTracker::default();
+    let mut state = pipeline_state();
+
+    let applied = apply_intake_labels(&mut state, &tracker, work_item(), &labels(&[]))
+        .await
+        .unwrap();
+
+    assert!(applied.is_empty());
+    assert!(state.expected_labels.is_empty());
+}
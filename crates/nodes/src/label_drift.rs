@@ -0,0 +1,121 @@
+//! Reconciling the run state's labels with the labels on the issue.
+//!
+//! [`PipelineState::expected_labels`] records the labels the pipeline last
+//! saw or applied. A human may add or remove labels between steps, after which
+//! the reconstructed state and GitHub disagree. Before a step acts on labels,
+//! [`reconcile_labels`] compares the two. GitHub is the source of truth: on a
+//! mismatch the state is overwritten with the actual labels and a
+//! [`DiagnosticSeverity::Warning`] describing the drift is returned for the
+//! step's report.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Label drift.
+
+use std::collections::BTreeSet;
+
+use tracing::instrument;
+
+use pipeline::{
+    Diagnostic, DiagnosticCategory, DiagnosticSeverity, GitHubOperationError, IssueTracker, Label,
+    PipelineState, WorkItemId,
+};
+
+/// Category of the diagnostic reported when labels have drifted.
+pub const LABEL_DRIFT_CATEGORY: &str = "label_drift";
+
+/// Difference between the expected and actual labels of an issue.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelDrift {
+    /// Labels the state expects that are no longer on the issue.
+    pub removed: BTreeSet<String>,
+    /// Labels on the issue that the state does not expect.
+    pub added: BTreeSet<String>,
+}
+
+impl LabelDrift {
+    /// Compares `expected` with `actual` label names.
+    pub fn between(expected: &BTreeSet<String>, actual: &BTreeSet<String>) -> Self {
+        Self {
+            removed: expected.difference(actual).cloned().collect(),
+            added: actual.difference(expected).cloned().collect(),
+        }
+    }
+
+    /// Returns `true` if the label sets match.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty()
+    }
+
+    /// Builds the warning reported for this drift, or `None` if there is no
+    /// drift.
+    pub fn to_diagnostic(&self) -> Option<Diagnostic> {
+        if self.is_empty() {
+            return None;
+        }
+        let list = |labels: &BTreeSet<String>| {
+            if labels.is_empty() {
+                "none".to_string()
+            } else {
+                labels
+                    .iter()
+                    .map(|l| format!("`{l}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        };
+        Some(Diagnostic {
+            artifact: None,
+            location: None,
+            severity: DiagnosticSeverity::Warning,
+            category: DiagnosticCategory::new(LABEL_DRIFT_CATEGORY)?,
+            message: format!(
+                "Issue labels changed outside CogWorks (added: {}; removed: {}); \
+                 using the labels on the issue",
+                list(&self.added),
+                list(&self.removed)
+            ),
+        })
+    }
+}
+
+/// Reconciles `state.expected_labels` with `actual`.
+///
+/// Returns `None` when they match, leaving `state` untouched. Otherwise sets
+/// the expected labels to `actual` and returns a warning describing the
+/// drift.
+pub fn reconcile_labels(state: &mut PipelineState, actual: &[Label]) -> Option<Diagnostic> {
+    let actual: BTreeSet<String> = actual.iter().map(|label| label.name.clone()).collect();
+    let drift = LabelDrift::between(&state.expected_labels, &actual);
+    if drift.is_empty() {
+        return None;
+    }
+    tracing::warn!(
+        run_id = %state.run_id,
+        added = ?drift.added,
+        removed = ?drift.removed,
+        "issue labels drifted from pipeline state; reconciling to GitHub"
+    );
+    state.expected_labels = actual;
+    drift.to_diagnostic()
+}
+
+/// Fetches the labels of `work_item_id` and reconciles `state` with them.
+///
+/// # Errors
+///
+/// Returns the [`GitHubOperationError`] from reading the labels; `state` is
+/// unchanged in that case.
+#[instrument(skip(state, tracker))]
+pub async fn reconcile_with_issue(
+    state: &mut PipelineState,
+    tracker: &dyn IssueTracker,
+    work_item_id: WorkItemId,
+) -> Result<Option<Diagnostic>, GitHubOperationError> {
+    let actual = tracker.get_labels(work_item_id).await?;
+    Ok(reconcile_labels(state, &actual))
+}
+
+#[cfg(test)]
+#[path = "label_drift_tests.rs"]
+mod tests;
@@ -0,0 +1,126 @@
+use crate::test_support::{pipeline_state, FakeIssueTracker};
+
+use super::*;
+
+fn names(labels: &[&str]) -> BTreeSet<String> {
+    labels.iter().map(|name| name.to_string()).collect()
+}
+
+fn labels(names: &[&str]) -> Vec<Label> {
+    names
+        .iter()
+        .map(|name| Label {
+            name: name.to_string(),
+            color: None,
+        })
+        .collect()
+}
+
+fn state_expecting(expected: &[&str]) -> PipelineState {
+    let mut state = pipeline_state();
+    state.expected_labels = names(expected);
+    state
+}
+
+#[test]
+fn test_between_differing_sets_returns_added_and_removed() {
+    let drift = LabelDrift::between(
+        &names(&["cogworks:run", "cogworks:planning"]),
+        &names(&["cogworks:run", "priority:high"]),
+    );
+
+    assert_eq!(drift.removed, names(&["cogworks:planning"]));
+    assert_eq!(drift.added, names(&["priority:high"]));
+    assert!(!drift.is_empty());
+}
+
+#[test]
+fn test_to_diagnostic_no_drift_returns_none() {
+    assert!(LabelDrift::default().to_diagnostic().is_none());
+}
+
+#[test]
+fn test_to_diagnostic_drift_returns_warning_listing_labels() {
+    let drift = LabelDrift::between(&names(&["a"]), &names(&["b"]));
+
+    let diagnostic = drift.to_diagnostic().unwrap();
+
+    assert_eq!(diagnostic.severity, DiagnosticSeverity::Warning);
+    assert_eq!(diagnostic.category.as_str(), LABEL_DRIFT_CATEGORY);
+    assert!(
+        diagnostic.message.contains("added: `b`"),
+        "{}",
+        diagnostic.message
+    );
+    assert!(
+        diagnostic.message.contains("removed: `a`"),
+        "{}",
+        diagnostic.message
+    );
+}
+
+#[test]
+fn test_to_diagnostic_only_added_reports_none_removed() {
+    let drift = LabelDrift::between(&names(&[]), &names(&["b"]));
+
+    let diagnostic = drift.to_diagnostic().unwrap();
+
+    assert!(
+        diagnostic.message.contains("removed: none"),
+        "{}",
+        diagnostic.message
+    );
+}
+
+#[test]
+fn test_reconcile_labels_matching_state_is_noop() {
+    let mut state = state_expecting(&["cogworks:run"]);
+
+    let warning = reconcile_labels(&mut state, &labels(&["cogworks:run"]));
+
+    assert!(warning.is_none());
+    assert_eq!(state.expected_labels, names(&["cogworks:run"]));
+}
+
+#[test]
+fn test_reconcile_labels_drifted_state_adopts_actual_labels_with_warning() {
+    let mut state = state_expecting(&["cogworks:run", "cogworks:planning"]);
+
+    let warning = reconcile_labels(&mut state, &labels(&["cogworks:run", "needs-info"]));
+
+    assert_eq!(warning.unwrap().severity, DiagnosticSeverity::Warning);
+    assert_eq!(
+        state.expected_labels,
+        names(&["cogworks:run", "needs-info"])
+    );
+}
+
+#[tokio::test]
+async fn test_reconcile_with_issue_drifted_labels_reconciles_to_issue() {
+    let work_item = WorkItemId::new(42);
+    let tracker = FakeIssueTracker::with_labels(work_item, ["cogworks:run", "needs-info"]);
+    let mut state = state_expecting(&["cogworks:run"]);
+
+    let warning = reconcile_with_issue(&mut state, &tracker, work_item)
+        .await
+        .unwrap();
+
+    assert!(warning.is_some());
+    assert_eq!(
+        state.expected_labels,
+        names(&["cogworks:run", "needs-info"])
+    );
+}
+
+#[tokio::test]
+async fn test_reconcile_with_issue_matching_labels_returns_none() {
+    let work_item = WorkItemId::new(42);
+    let tracker = FakeIssueTracker::with_labels(work_item, ["cogworks:run"]);
+    let mut state = state_expecting(&["cogworks:run"]);
+
+    let warning = reconcile_with_issue(&mut state, &tracker, work_item)
+        .await
+        .unwrap();
+
+    assert!(warning.is_none());
+}
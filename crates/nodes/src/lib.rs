@@ -5,6 +5,39 @@
 //! calls with constitutional rules and rate-limit tracking, and the
 //! `PipelineExecutor` that drives the step-function loop.
 //!
+//! ## Module Overview
+//!
+//! | Module | Contents |
+//! |--------|----------|
+//! | [`audit_collector`] | [`CollectorAuditStore`](audit_collector::CollectorAuditStore) — streams each audit event to an external collector, fire-and-forget with bounded retry |
+//! | [`budget`] | [`BudgetEnforcer`](budget::BudgetEnforcer) — run budget check with an optional bounded overshoot grace |
+//! | [`context_pack`] | [`ContextPackLoader`](context_pack::ContextPackLoader) — selective Context Pack loading by glob |
+//! | [`diagnostic_details`] | Collapsible `<details>` rendering of findings grouped by severity |
+//! | [`escalation`] | [`Escalator`](escalation::Escalator) — issue-mention and webhook notifications when a run stops for a human |
+//! | [`executor`] | [`PipelineExecutor`](executor::PipelineExecutor), the [`Node`](executor::Node) trait, and [`StepResult`](executor::StepResult) — per-step outcome and cost attribution; bounded alignment re-check loop |
+//! | [`gateway`] | [`LlmGateway`](gateway::LlmGateway) — the path from nodes to the LLM provider, with per-model concurrency limits |
+//! | [`idempotency`] | Skip events already reflected in the run state |
+//! | [`injection`] | [`InjectionPolicy`](injection::InjectionPolicy) — halt, or strip and warn, when injection detection fires |
+//! | [`intake_labels`] | Configurable labels applied once when Intake picks up a work item |
+//! | [`label_drift`] | Reconcile the run state's expected labels with the issue's actual labels |
+//! | [`messages`] | [`MessageCatalog`](messages::MessageCatalog) — comment text by message ID, English by default, with an override catalog |
+//! | [`markers`] | [`CommentMarkers`](markers::CommentMarkers) — configurable hidden comment markers |
+//! | [`prompt_limit`] | [`PromptLimit`](prompt_limit::PromptLimit) — maximum prompt size; oldest Context Pack sections dropped first, with a warning |
+//! | [`read_only`] | Refuse code changes and pull request creation in review-only runs |
+//! | [`response_cache`] | [`ResponseCache`](response_cache::ResponseCache) — per-node LLM response reuse with a configurable TTL |
+//! | [`review`] | [`DiagnosticSource`](review::DiagnosticSource) and [`ReviewVerdict`](review::ReviewVerdict) — halt/continue decision on review findings |
+//! | [`sub_work_items`] | Per-run cap on sub-work-item creation |
+//! | [`summary`] | Run summary comment rendering and upsert |
+//! | [`tool_loop`] | [`run_tool_loop`](tool_loop::run_tool_loop) — model call → tool execution → result loop for agentic nodes, with an iteration cap |
+//! | [`usage_export`] | [`UsageCsvExporter`](usage_export::UsageCsvExporter) — per-run token usage and cost rows appended to a CSV file |
+//! | [`work_lock`] | Per-work-item processing lock (`cogworks:processing` label and lock comment) with stale-lock reclaim |
+//!
+//! ## Cargo Features
+//!
+//! | Feature | Effect |
+//! |---------|--------|
+//! | `synthetic-diagnostics` | Enables `review::ScriptedDiagnostics` for tests that drive node gates without real domain services |
+//!
 //! ## Architectural Layer
 //!
 //! **Orchestration layer.** Nodes sequence calls between business logic in the
@@ -16,3 +49,76 @@
 //! See `docs/spec/interfaces/nodes.md` for the full contract.
 //!
 //! *This crate is a skeleton. Implementation is added in PR 9.*
+
+pub mod audit_collector;
+pub mod budget;
+pub mod context_pack;
+pub mod diagnostic_details;
+pub mod escalation;
+pub mod executor;
+pub mod gateway;
+pub mod idempotency;
+pub mod injection;
+pub mod intake_labels;
+pub mod label_drift;
+pub mod markers;
+pub mod messages;
+pub mod prompt_limit;
+pub mod read_only;
+pub mod response_cache;
+pub mod review;
+pub mod sub_work_items;
+pub mod summary;
+pub mod tool_loop;
+pub mod usage_export;
+pub mod work_lock;
+
+#[cfg(test)]
+mod test_support;
+
+pub use audit_collector::{
+    AuditCollectorConfig, CollectedAuditEvent, CollectorAuditStore, CollectorError,
+    CollectorForwarder, CollectorTransport,
+};
+pub use budget::{BudgetDecision, BudgetEnforcer, OvershootGrace, MAX_OVERSHOOT_GRACE_FRACTION};
+pub use context_pack::ContextPackLoader;
+pub use diagnostic_details::render_diagnostic_details;
+pub use escalation::{
+    escalate_all, needs_escalation, Escalation, EscalationError, EscalationPayload, Escalator,
+    IssueMentionEscalator, WebhookEscalator, WebhookTransport,
+};
+pub use executor::{
+    AlignmentLoop, AlignmentLoopOutcome, CheckpointStore, ExecutorError, Node, NodeOutcome,
+    PipelineExecutor, StepResult, DEFAULT_ALIGNMENT_MAX_ITERATIONS,
+};
+pub use gateway::{LlmGateway, ModelConcurrencyLimits, DEFAULT_MODEL_CONCURRENCY};
+pub use idempotency::{is_already_applied, record_processed, skip_if_applied};
+pub use injection::{
+    strip_injection, InjectionPolicy, InjectionResolution, INJECTION_DIAGNOSTIC_CATEGORY,
+    STRIPPED_INJECTION_PLACEHOLDER,
+};
+pub use intake_labels::{apply_intake_labels, IntakeLabels, DEFAULT_INTAKE_LABEL};
+pub use label_drift::{reconcile_labels, reconcile_with_issue, LabelDrift};
+pub use markers::{CommentMarkers, DEFAULT_MARKER_NAMESPACE};
+pub use messages::{MessageCatalog, DEFAULT_MESSAGES};
+pub use prompt_limit::{
+    fit_prompt, prompt_chars, PromptLimit, PROMPT_TRUNCATION_DIAGNOSTIC_CATEGORY,
+};
+pub use read_only::{check_repository_write, create_pull_request, ReadOnlyError, RepositoryWrite};
+pub use response_cache::{ResponseCache, ResponseCacheConfig, DEFAULT_RESPONSE_CACHE_CAPACITY};
+pub use review::{review, DiagnosticSource, ReviewVerdict};
+pub use sub_work_items::{
+    create_sub_work_item, SubWorkItemCap, SubWorkItemError, DEFAULT_MAX_SUB_WORK_ITEMS_PER_RUN,
+};
+pub use summary::{post_run_summary, summary_comment};
+pub use tool_loop::{
+    run_tool_loop, tool_results_message, ToolCall, ToolFailure, ToolLoopConfig, ToolLoopError,
+    ToolLoopOutcome, ToolResult, ToolRunner, DEFAULT_MAX_TOOL_ITERATIONS,
+};
+pub use usage_export::{
+    render_usage_csv, usage_rows, UsageCsvExporter, UsageExportError, UsageRow, USAGE_CSV_HEADER,
+};
+pub use work_lock::{
+    acquire_work_lock, release_work_lock, LockAcquisition, LockHolder, WorkLockConfig,
+    DEFAULT_STALE_LOCK_MINUTES, PROCESSING_LABEL,
+};
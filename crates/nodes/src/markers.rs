@@ -0,0 +1,73 @@
+//! Hidden HTML markers identifying CogWorks-managed comments.
+//!
+//! CogWorks edits its own comments in place (see
+//! [`pipeline::IssueTracker::upsert_comment`]), locating them by a marker
+//! embedded in the comment body. Repositories that already run bots with
+//! similar markers can namespace CogWorks' markers to avoid one bot editing
+//! another's comment.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Comment markers.
+
+use serde::{Deserialize, Serialize};
+
+/// Namespace used by [`CommentMarkers::default`].
+pub const DEFAULT_MARKER_NAMESPACE: &str = "cogworks";
+
+/// The marker used for each kind of CogWorks-managed comment.
+///
+/// Every marker is an HTML comment, invisible when the Markdown is rendered.
+/// Fields missing from configuration take their default value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommentMarkers {
+    /// Marker on the persisted pipeline state comment.
+    pub state: String,
+    /// Marker on the run summary comment.
+    pub summary: String,
+    /// Marker on the audit log comment.
+    pub audit: String,
+    /// Marker on the processing lock comment (see [`crate::work_lock`]).
+    pub lock: String,
+}
+
+impl CommentMarkers {
+    /// Creates markers of the form `<!-- {namespace}:{kind} -->`.
+    ///
+    /// Returns `None` if `namespace` is empty, contains whitespace, or
+    /// contains `--` or `>` (which would end the HTML comment early).
+    #[must_use]
+    pub fn namespaced(namespace: &str) -> Option<Self> {
+        let valid = !namespace.is_empty()
+            && !namespace.contains("--")
+            && !namespace.contains('>')
+            && !namespace.chars().any(char::is_whitespace);
+        if !valid {
+            return None;
+        }
+
+        let marker = |kind: &str| format!("<!-- {namespace}:{kind} -->");
+        Some(Self {
+            state: marker("pipeline-state"),
+            summary: marker("run-summary"),
+            audit: marker("audit"),
+            lock: marker("processing-lock"),
+        })
+    }
+}
+
+impl Default for CommentMarkers {
+    fn default() -> Self {
+        Self {
+            state: "<!-- cogworks:pipeline-state -->".to_string(),
+            summary: "<!-- cogworks:run-summary -->".to_string(),
+            audit: "<!-- cogworks:audit -->".to_string(),
+            lock: "<!-- cogworks:processing-lock -->".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "markers_tests.rs"]
+mod tests;
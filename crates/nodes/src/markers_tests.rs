@@ -0,0 +1,103 @@
+use pipeline::{IssueTracker, PipelineRunId, WorkItemId};
+
+use crate::{
+    executor::StepResult, messages::MessageCatalog, summary::post_run_summary,
+    test_support::FakeIssueTracker,
+};
+
+use super::*;
+
+const FOREIGN_SUMMARY: &str = "<!-- otherbot:run-summary -->\nOther bot's summary";
+
+fn step() -> StepResult {
+    StepResult::new(PipelineRunId::new_random(), WorkItemId::new(42))
+}
+
+#[test]
+fn test_namespaced_valid_namespace_builds_every_marker() {
+    let markers = CommentMarkers::namespaced("acme-cogworks").unwrap();
+
+    assert_eq!(markers.state, "<!-- acme-cogworks:pipeline-state -->");
+    assert_eq!(markers.summary, "<!-- acme-cogworks:run-summary -->");
+    assert_eq!(markers.audit, "<!-- acme-cogworks:audit -->");
+    assert_eq!(markers.lock, "<!-- acme-cogworks:processing-lock -->");
+}
+
+#[test]
+fn test_namespaced_default_namespace_matches_default() {
+    assert_eq!(
+        CommentMarkers::namespaced(DEFAULT_MARKER_NAMESPACE),
+        Some(CommentMarkers::default())
+    );
+}
+
+#[test]
+fn test_namespaced_invalid_namespace_returns_none() {
+    for namespace in ["", "acme cogworks", "acme--cogworks", "acme>"] {
+        assert_eq!(
+            CommentMarkers::namespaced(namespace),
+            None,
+            "{namespace:?} should be rejected"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_upsert_configured_marker_targets_namespaced_comment() {
+    let issues = FakeIssueTracker::default();
+    let markers = CommentMarkers::namespaced("acme").unwrap();
+
+    post_run_summary(&issues, &markers, &MessageCatalog::english(), &step())
+        .await
+        .unwrap();
+
+    let bodies = issues.comment_bodies(WorkItemId::new(42));
+    assert_eq!(bodies.len(), 1);
+    assert!(bodies[0].starts_with("<!-- acme:run-summary -->"));
+}
+
+#[tokio::test]
+async fn test_upsert_foreign_bot_comment_is_left_untouched() {
+    let issues = FakeIssueTracker::default();
+    issues
+        .post_comment(WorkItemId::new(42), FOREIGN_SUMMARY)
+        .await
+        .unwrap();
+
+    post_run_summary(
+        &issues,
+        &CommentMarkers::default(),
+        &MessageCatalog::english(),
+        &step(),
+    )
+    .await
+    .unwrap();
+
+    let bodies = issues.comment_bodies(WorkItemId::new(42));
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies[0], FOREIGN_SUMMARY);
+    assert!(bodies[1].starts_with("<!-- cogworks:run-summary -->"));
+}
+
+#[tokio::test]
+async fn test_upsert_longer_marker_with_same_prefix_is_not_matched() {
+    let issues = FakeIssueTracker::default();
+    issues
+        .post_comment(
+            WorkItemId::new(42),
+            "<!-- cogworks:run-summary-v2 -->\nNewer format",
+        )
+        .await
+        .unwrap();
+
+    post_run_summary(
+        &issues,
+        &CommentMarkers::default(),
+        &MessageCatalog::english(),
+        &step(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(issues.comment_bodies(WorkItemId::new(42)).len(), 2);
+}
@@ -0,0 +1,161 @@
+//! Message catalog for the text of CogWorks comments.
+//!
+//! Every user-facing string in the comments CogWorks writes (run summary,
+//! findings block, escalation mention) is looked up by message ID in a
+//! [`MessageCatalog`] rather than hard-coded, so teams working in another
+//! language can translate it. The built-in catalog is English
+//! ([`DEFAULT_MESSAGES`]); an override catalog replaces any subset of it:
+//!
+//! ```toml
+//! [messages]
+//! "summary.title" = "Résumé de l'exécution CogWorks"
+//! "outcome.completed" = "✅ Terminé"
+//! ```
+//!
+//! Messages may contain `{name}` placeholders, filled in by
+//! [`MessageCatalog::format`]. A message missing from the override catalog
+//! falls back to English with a warning.
+//!
+//! Only prose is translated. Markdown structure, hidden markers, and values
+//! such as costs, run IDs, and node names are rendered as-is.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Message catalog.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The built-in English messages, as `(message ID, text)`.
+pub const DEFAULT_MESSAGES: &[(&str, &str)] = &[
+    ("summary.title", "CogWorks run summary"),
+    ("summary.outcome", "Outcome"),
+    ("summary.pull_request", "Pull request"),
+    ("summary.cost", "Cost"),
+    ("summary.cost_breakdown", "Cost breakdown"),
+    (
+        "summary.cost_breakdown_value",
+        "nodes {nodes} · edge evaluations {edges}",
+    ),
+    ("summary.run", "Run"),
+    ("summary.nodes_run", "Nodes run:"),
+    ("summary.no_nodes", "No nodes ran in this step."),
+    ("outcome.completed", "✅ Completed"),
+    ("outcome.failed", "❌ Failed"),
+    ("outcome.human_gated", "⏸️ Awaiting human review"),
+    ("outcome.escalated", "⚠️ Escalated"),
+    ("outcome.in_progress", "🔄 In progress"),
+    ("findings.summary", "Findings: {counts}"),
+    ("findings.heading.blocking", "🛑 Blocking"),
+    ("findings.heading.warning", "⚠️ Warnings"),
+    ("findings.heading.informational", "ℹ️ Informational"),
+    ("findings.count.blocking.one", "{count} blocking"),
+    ("findings.count.blocking.other", "{count} blocking"),
+    ("findings.count.warning.one", "{count} warning"),
+    ("findings.count.warning.other", "{count} warnings"),
+    ("findings.count.informational.one", "{count} informational"),
+    (
+        "findings.count.informational.other",
+        "{count} informational",
+    ),
+    (
+        "escalation.human_gated",
+        "CogWorks run {run} on #{work_item} is waiting for human review",
+    ),
+    (
+        "escalation.escalated",
+        "CogWorks run {run} on #{work_item} was escalated",
+    ),
+    (
+        "escalation.failed",
+        "CogWorks run {run} on #{work_item} halted",
+    ),
+    (
+        "escalation.completed",
+        "CogWorks run {run} on #{work_item} completed",
+    ),
+];
+
+/// Returns the built-in English text for `id`.
+fn english(id: &str) -> Option<&'static str> {
+    DEFAULT_MESSAGES
+        .iter()
+        .find(|(key, _)| *key == id)
+        .map(|(_, text)| *text)
+}
+
+/// Comment text by message ID: an optional override catalog over the built-in
+/// English messages.
+///
+/// Deserialises from a table of message ID to text, which becomes the
+/// override catalog.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MessageCatalog {
+    overrides: HashMap<String, String>,
+}
+
+impl MessageCatalog {
+    /// The built-in English catalog with no overrides.
+    pub fn english() -> Self {
+        Self::default()
+    }
+
+    /// A catalog using `overrides` where they have an entry and English
+    /// everywhere else.
+    pub fn with_overrides(overrides: HashMap<String, String>) -> Self {
+        Self { overrides }
+    }
+
+    /// Returns the text for `id`.
+    ///
+    /// Falls back to English, with a warning, when an override catalog is
+    /// loaded but lacks `id`. An ID unknown to the English catalog too is a
+    /// programming error; it is logged and the ID itself is returned.
+    pub fn message<'a>(&'a self, id: &'a str) -> &'a str {
+        if let Some(text) = self.overrides.get(id) {
+            return text;
+        }
+        match english(id) {
+            Some(text) => {
+                if !self.overrides.is_empty() {
+                    tracing::warn!(id, "message missing from catalog; using English");
+                }
+                text
+            }
+            None => {
+                tracing::error!(id, "unknown message id");
+                id
+            }
+        }
+    }
+
+    /// Returns the text for `id` with each `{name}` placeholder replaced by
+    /// the matching value in `args`.
+    ///
+    /// Placeholders without a value are left as they are.
+    pub fn format(&self, id: &str, args: &[(&str, &str)]) -> String {
+        args.iter()
+            .fold(self.message(id).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), value)
+            })
+    }
+
+    /// Returns the override IDs that name no built-in message, e.g. typos in
+    /// a catalog file, sorted.
+    pub fn unknown_ids(&self) -> Vec<&str> {
+        let mut unknown: Vec<&str> = self
+            .overrides
+            .keys()
+            .map(String::as_str)
+            .filter(|id| english(id).is_none())
+            .collect();
+        unknown.sort_unstable();
+        unknown
+    }
+}
+
+#[cfg(test)]
+#[path = "messages_tests.rs"]
+mod tests;
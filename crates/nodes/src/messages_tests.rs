@@ -0,0 +1,97 @@
+use std::collections::{HashMap, HashSet};
+
+use super::*;
+
+fn french() -> MessageCatalog {
+    MessageCatalog::with_overrides(HashMap::from([
+        (
+            "summary.title".to_string(),
+            "Résumé de l'exécution CogWorks".to_string(),
+        ),
+        (
+            "findings.count.warning.other".to_string(),
+            "{count} avertissements".to_string(),
+        ),
+    ]))
+}
+
+#[test]
+fn test_default_messages_ids_are_unique() {
+    let ids: HashSet<&str> = DEFAULT_MESSAGES.iter().map(|(id, _)| *id).collect();
+
+    assert_eq!(ids.len(), DEFAULT_MESSAGES.len());
+}
+
+#[test]
+fn test_message_english_catalog_returns_built_in_text() {
+    assert_eq!(
+        MessageCatalog::english().message("summary.title"),
+        "CogWorks run summary"
+    );
+}
+
+#[test]
+fn test_message_override_present_returns_override() {
+    assert_eq!(
+        french().message("summary.title"),
+        "Résumé de l'exécution CogWorks"
+    );
+}
+
+#[test]
+fn test_message_override_missing_id_falls_back_to_english() {
+    assert_eq!(french().message("outcome.completed"), "✅ Completed");
+}
+
+#[test]
+fn test_message_unknown_id_returns_id() {
+    assert_eq!(
+        MessageCatalog::english().message("summary.no_such_message"),
+        "summary.no_such_message"
+    );
+}
+
+#[test]
+fn test_format_placeholders_replaced_from_args() {
+    let text = MessageCatalog::english().format(
+        "escalation.failed",
+        &[("run", "run-7"), ("work_item", "42")],
+    );
+
+    assert_eq!(text, "CogWorks run run-7 on #42 halted");
+}
+
+#[test]
+fn test_format_override_text_filled_in() {
+    assert_eq!(
+        french().format("findings.count.warning.other", &[("count", "3")]),
+        "3 avertissements"
+    );
+}
+
+#[test]
+fn test_format_missing_arg_leaves_placeholder() {
+    assert_eq!(
+        MessageCatalog::english().format("findings.summary", &[]),
+        "Findings: {counts}"
+    );
+}
+
+#[test]
+fn test_unknown_ids_typo_in_overrides_listed_sorted() {
+    let catalog = MessageCatalog::with_overrides(HashMap::from([
+        ("summary.titel".to_string(), "x".to_string()),
+        ("outcome.completed".to_string(), "fait".to_string()),
+        ("outcome.faild".to_string(), "x".to_string()),
+    ]));
+
+    assert_eq!(
+        catalog.unknown_ids(),
+        vec!["outcome.faild", "summary.titel"]
+    );
+}
+
+#[test]
+fn test_unknown_ids_english_catalog_returns_empty() {
+    assert!(MessageCatalog::english().unknown_ids().is_empty());
+}
@@ -0,0 +1,187 @@
+//! Maximum prompt size and the truncation applied above it.
+//!
+//! Context Packs can make an assembled prompt larger than the model accepts
+//! or than a run should pay for. When a request is over
+//! [`PromptLimit::max_chars`], [`fit_prompt`] drops whole
+//! [`SystemLayer::Context`] segments, oldest first, until it fits. The
+//! constitutional rules, the role, the task, and the conversation are never
+//! touched. Each truncation is reported as a [`DiagnosticSeverity::Warning`]
+//! naming the sections dropped, so the run summary shows what the model did
+//! not see.
+//!
+//! ```toml
+//! [prompt]
+//! max_chars = 400000   # unset (default): no limit
+//! ```
+//!
+//! If the prompt is still over the limit once every Context segment is gone,
+//! it is sent as is and the diagnostic says so; the provider then rejects it
+//! or accepts it on its own terms.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §System prompt layering.
+
+use serde::{Deserialize, Serialize};
+
+use pipeline::{
+    CompletionRequest, Diagnostic, DiagnosticCategory, DiagnosticSeverity, SystemLayer,
+};
+
+/// Category of the diagnostic recorded when a prompt is truncated.
+pub const PROMPT_TRUNCATION_DIAGNOSTIC_CATEGORY: &str = "prompt_truncation";
+
+/// Longest section label quoted in the truncation diagnostic, in characters.
+const MAX_SECTION_LABEL_CHARS: usize = 80;
+
+/// The `[prompt]` configuration table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptLimit {
+    /// Largest prompt sent, counted in characters over every system segment
+    /// and message. `None` sends prompts of any size.
+    #[serde(default)]
+    pub max_chars: Option<usize>,
+}
+
+/// Returns the size of `request`'s prompt in characters: every system
+/// segment and every message.
+pub fn prompt_chars(request: &CompletionRequest) -> usize {
+    request
+        .system
+        .iter()
+        .map(|segment| segment.content.chars().count())
+        .chain(
+            request
+                .messages
+                .iter()
+                .map(|message| message.content.chars().count()),
+        )
+        .sum()
+}
+
+/// One Context segment removed by [`fit_prompt`].
+struct DroppedSection {
+    /// The segment's first non-empty line, without leading `#`s, shortened
+    /// to a readable length.
+    label: String,
+    /// Characters removed.
+    chars: usize,
+}
+
+/// Drops Context segments from `request`, oldest first, until its prompt is
+/// within `limit`.
+///
+/// Segments are dropped in insertion order among [`SystemLayer::Context`]
+/// segments; no other segment or message is changed. Cache breakpoints on
+/// later blocks are shifted so they still mark the same content, and a
+/// breakpoint on a dropped segment is removed.
+///
+/// Returns `None` if nothing was dropped, otherwise the
+/// [`DiagnosticSeverity::Warning`] to record.
+pub fn fit_prompt(request: &mut CompletionRequest, limit: &PromptLimit) -> Option<Diagnostic> {
+    let max_chars = limit.max_chars?;
+    let original_chars = prompt_chars(request);
+    let mut chars = original_chars;
+    let mut dropped = Vec::new();
+    while chars > max_chars {
+        let Some(position) = request
+            .system
+            .iter()
+            .position(|segment| segment.layer == SystemLayer::Context)
+        else {
+            break;
+        };
+        remove_cache_breakpoint(request, position);
+        let segment = request.system.remove(position);
+        let section = DroppedSection {
+            label: section_label(&segment.content),
+            chars: segment.content.chars().count(),
+        };
+        chars -= section.chars;
+        dropped.push(section);
+    }
+    if dropped.is_empty() {
+        if chars > max_chars {
+            tracing::warn!(
+                chars,
+                max_chars,
+                "prompt over size limit with no Context Pack sections to drop"
+            );
+        }
+        return None;
+    }
+
+    tracing::warn!(
+        original_chars,
+        chars,
+        max_chars,
+        dropped = dropped.len(),
+        "prompt over size limit; dropped Context Pack sections"
+    );
+    Some(truncation_diagnostic(&dropped, chars, max_chars))
+}
+
+/// Removes the cache breakpoint on the system segment at `position` (in
+/// `request.system`) and shifts the breakpoints after it down by one.
+fn remove_cache_breakpoint(request: &mut CompletionRequest, position: usize) {
+    if request.cache_breakpoints.is_empty() {
+        return;
+    }
+    let target = &request.system[position];
+    let Some(index) = request
+        .layered_system()
+        .iter()
+        .position(|segment| std::ptr::eq(*segment, target))
+    else {
+        return;
+    };
+    request
+        .cache_breakpoints
+        .retain(|&breakpoint| breakpoint != index);
+    for breakpoint in &mut request.cache_breakpoints {
+        if *breakpoint > index {
+            *breakpoint -= 1;
+        }
+    }
+}
+
+/// The label naming a dropped segment in the diagnostic.
+fn section_label(content: &str) -> String {
+    let line = content
+        .lines()
+        .map(|line| line.trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or("(empty section)");
+    match line.char_indices().nth(MAX_SECTION_LABEL_CHARS) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+fn truncation_diagnostic(dropped: &[DroppedSection], chars: usize, max_chars: usize) -> Diagnostic {
+    let sections = dropped
+        .iter()
+        .map(|section| format!("{} ({} chars)", section.label, section.chars))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let still_over = if chars > max_chars {
+        format!(" The prompt is still {chars} chars, over the limit.")
+    } else {
+        String::new()
+    };
+    Diagnostic {
+        artifact: None,
+        location: None,
+        severity: DiagnosticSeverity::Warning,
+        category: DiagnosticCategory::from_static(PROMPT_TRUNCATION_DIAGNOSTIC_CATEGORY),
+        message: format!(
+            "Prompt exceeded the {max_chars}-char limit; dropped {} Context Pack section(s): \
+             {sections}.{still_over}",
+            dropped.len()
+        ),
+    }
+}
+
+#[cfg(test)]
+#[path = "prompt_limit_tests.rs"]
+mod tests;
@@ -0,0 +1,158 @@
+use pipeline::{Message, SystemSegment, TokenCount};
+
+use super::*;
+
+fn context(title: &str, body_chars: usize) -> SystemSegment {
+    SystemSegment::new(
+        SystemLayer::Context,
+        format!("## {title}\n\n{}", "x".repeat(body_chars)),
+    )
+}
+
+/// A request with rules, a task, a user message, and two Context sections of
+/// roughly 100 characters each, `oldest.md` first.
+fn request() -> CompletionRequest {
+    let mut request = CompletionRequest::new(
+        "model-a",
+        vec![Message::user("hello")],
+        TokenCount::new(256),
+    );
+    request.system = vec![
+        SystemSegment::new(SystemLayer::Constitutional, "rules"),
+        context("Context Pack docs: oldest.md", 100),
+        context("Context Pack docs: newest.md", 100),
+        SystemSegment::new(SystemLayer::Task, "task"),
+    ];
+    request
+}
+
+fn limit(max_chars: usize) -> PromptLimit {
+    PromptLimit {
+        max_chars: Some(max_chars),
+    }
+}
+
+fn contents(request: &CompletionRequest) -> Vec<&str> {
+    request
+        .system
+        .iter()
+        .map(|segment| segment.content.as_str())
+        .collect()
+}
+
+// ─── prompt_chars ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_prompt_chars_counts_system_segments_and_messages() {
+    let mut request = request();
+    request.system.retain(|s| s.layer != SystemLayer::Context);
+
+    assert_eq!(
+        prompt_chars(&request),
+        "rules".len() + "task".len() + "hello".len()
+    );
+}
+
+// ─── fit_prompt ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_fit_prompt_no_limit_leaves_request_unchanged() {
+    let mut request = request();
+
+    assert!(fit_prompt(&mut request, &PromptLimit::default()).is_none());
+    assert_eq!(request, self::request());
+}
+
+#[test]
+fn test_fit_prompt_within_limit_leaves_request_unchanged() {
+    let mut request = request();
+    let size = prompt_chars(&request);
+
+    assert!(fit_prompt(&mut request, &limit(size)).is_none());
+    assert_eq!(request, self::request());
+}
+
+#[test]
+fn test_fit_prompt_over_limit_drops_oldest_context_and_keeps_rules_and_task() {
+    let mut request = request();
+    let size = prompt_chars(&request);
+
+    let diagnostic = fit_prompt(&mut request, &limit(size - 1)).unwrap();
+
+    assert_eq!(request.system.len(), 3);
+    assert_eq!(request.system[0].content, "rules");
+    assert!(request.system[1].content.contains("newest.md"));
+    assert_eq!(request.system[2].content, "task");
+    assert_eq!(request.messages, vec![Message::user("hello")]);
+    assert_eq!(diagnostic.severity, DiagnosticSeverity::Warning);
+    assert_eq!(
+        diagnostic.category.as_str(),
+        PROMPT_TRUNCATION_DIAGNOSTIC_CATEGORY
+    );
+    assert!(diagnostic
+        .message
+        .contains("dropped 1 Context Pack section(s): Context Pack docs: oldest.md"));
+    assert!(!diagnostic.message.contains("newest.md"));
+    assert!(!diagnostic.message.contains("still"));
+}
+
+#[test]
+fn test_fit_prompt_still_over_after_every_section_dropped_reports_overflow() {
+    let mut request = request();
+
+    let diagnostic = fit_prompt(&mut request, &limit(5)).unwrap();
+
+    assert_eq!(contents(&request), vec!["rules", "task"]);
+    assert!(diagnostic
+        .message
+        .contains("dropped 2 Context Pack section(s)"));
+    assert!(diagnostic.message.contains("oldest.md"));
+    assert!(diagnostic.message.contains("newest.md"));
+    assert!(diagnostic
+        .message
+        .contains("The prompt is still 14 chars, over the limit."));
+}
+
+#[test]
+fn test_fit_prompt_over_limit_with_no_context_returns_none() {
+    let mut request = request();
+    request.system.retain(|s| s.layer != SystemLayer::Context);
+    let unchanged = request.clone();
+
+    assert!(fit_prompt(&mut request, &limit(1)).is_none());
+    assert_eq!(request, unchanged);
+}
+
+#[test]
+fn test_fit_prompt_dropped_section_shifts_later_cache_breakpoints() {
+    // Layered order: rules (0), oldest.md (1), newest.md (2), task (3).
+    let mut request = request().with_cache_breakpoints(vec![1, 3]);
+    let size = prompt_chars(&request);
+
+    fit_prompt(&mut request, &limit(size - 1)).unwrap();
+
+    assert_eq!(request.cache_breakpoints, vec![2]);
+}
+
+// ─── section_label ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_section_label_skips_blank_lines_and_heading_markers() {
+    assert_eq!(
+        section_label("\n\n## Context Pack docs: a.md\nbody"),
+        "Context Pack docs: a.md"
+    );
+}
+
+#[test]
+fn test_section_label_long_line_is_shortened() {
+    let label = section_label(&"é".repeat(MAX_SECTION_LABEL_CHARS + 10));
+
+    assert_eq!(label.chars().count(), MAX_SECTION_LABEL_CHARS + 1);
+    assert!(label.ends_with('…'));
+}
+
+#[test]
+fn test_section_label_empty_content_returns_placeholder() {
+    assert_eq!(section_label("  \n#\n"), "(empty section)");
+}
@@ -0,0 +1,126 @@
+//! Read-only runs: comments and labels allowed, repository writes refused.
+//!
+//! A review-only run must never change the repository, but it still needs to
+//! post its findings and move labels. Dry-run suppresses every mutation, so it
+//! cannot be used for this. Instead a run is started with
+//! [`PipelineState::read_only`] set, and every repository write goes through
+//! [`check_repository_write`] first. In a read-only run the check fails with
+//! a [`CogWorksError::PipelineHalt`] ([`HaltReason::ScopeEnforcer`]) naming
+//! the node and the write it attempted; issue comments and labels are not checked and go ahead as
+//! usual.
+//!
+//! Pull requests are created through [`create_pull_request`], which performs
+//! the check before calling GitHub. Code changes are checked by the node that
+//! commits them, with [`RepositoryWrite::CodeChange`].
+//!
+//! The flag lives in the persisted state, so the restriction holds across
+//! steps and resumes.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Read-only runs.
+
+use thiserror::Error;
+use tracing::instrument;
+
+use pipeline::{
+    BranchName, CogWorksError, GitHubOperationError, HaltReason, NodeId, PipelineState,
+    PullRequest, PullRequestManager, RepositoryId,
+};
+
+/// A repository write refused in a read-only run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepositoryWrite {
+    /// Committing or pushing changes to repository files.
+    CodeChange,
+    /// Opening a pull request.
+    PullRequestCreation,
+}
+
+impl RepositoryWrite {
+    /// Short description used in the scope-violation message.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CodeChange => "code change",
+            Self::PullRequestCreation => "pull request creation",
+        }
+    }
+}
+
+impl std::fmt::Display for RepositoryWrite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Checks that `node` may perform `write` in the run described by `state`.
+///
+/// # Errors
+///
+/// [`CogWorksError::PipelineHalt`] ([`HaltReason::ScopeEnforcer`]) if the run
+/// is read-only.
+pub fn check_repository_write(
+    state: &PipelineState,
+    node: &NodeId,
+    write: RepositoryWrite,
+) -> Result<(), CogWorksError> {
+    if !state.read_only {
+        return Ok(());
+    }
+    Err(CogWorksError::halt(
+        HaltReason::ScopeEnforcer,
+        format!("node '{node}' attempted a {write} in a read-only run"),
+    ))
+}
+
+/// Errors returned by [`create_pull_request`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ReadOnlyError {
+    /// The run is read-only; the run must halt.
+    #[error(transparent)]
+    WriteRefused(CogWorksError),
+
+    /// The pull request could not be created.
+    #[error("failed to create pull request: {source}")]
+    Create {
+        /// The underlying GitHub error.
+        #[source]
+        source: GitHubOperationError,
+    },
+}
+
+/// Opens a pull request on behalf of `node` unless the run is read-only.
+///
+/// # Errors
+///
+/// - [`ReadOnlyError::WriteRefused`] — the run is read-only; nothing is
+///   created.
+/// - [`ReadOnlyError::Create`] — the GitHub call failed.
+#[instrument(skip(state, prs, body), fields(run_id = %state.run_id))]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_pull_request(
+    state: &PipelineState,
+    prs: &dyn PullRequestManager,
+    node: &NodeId,
+    repository: &RepositoryId,
+    title: &str,
+    body: &str,
+    head: &BranchName,
+    base: &BranchName,
+    draft: bool,
+) -> Result<PullRequest, ReadOnlyError> {
+    if let Err(violation) =
+        check_repository_write(state, node, RepositoryWrite::PullRequestCreation)
+    {
+        tracing::error!(%node, "pull request creation refused in read-only run; halting");
+        return Err(ReadOnlyError::WriteRefused(violation));
+    }
+    prs.create_pull_request(repository, title, body, head, base, draft)
+        .await
+        .map_err(|source| ReadOnlyError::Create { source })
+}
+
+#[cfg(test)]
+#[path = "read_only_tests.rs"]
+mod tests;
@@ -0,0 +1,127 @@
+use pipeline::{IssueTracker, WorkItemId};
+
+use crate::test_support::{pipeline_state, FakeIssueTracker, FakePullRequestManager};
+
+use super::*;
+
+fn node() -> NodeId {
+    NodeId::new("review").unwrap()
+}
+
+fn read_only_state() -> PipelineState {
+    PipelineState {
+        read_only: true,
+        ..pipeline_state()
+    }
+}
+
+fn branch(name: &str) -> BranchName {
+    BranchName::new(name).unwrap()
+}
+
+async fn open_pull_request(
+    state: &PipelineState,
+    prs: &FakePullRequestManager,
+) -> Result<PullRequest, ReadOnlyError> {
+    create_pull_request(
+        state,
+        prs,
+        &node(),
+        &RepositoryId::parse("octo/widgets").unwrap(),
+        "Fix the widget",
+        "Closes #42",
+        &branch("cogworks/42-fix-widget"),
+        &branch("main"),
+        false,
+    )
+    .await
+}
+
+// ─── check_repository_write ─────────────────────────────────────────────────
+
+#[test]
+fn test_check_repository_write_normal_run_allows_code_change() {
+    assert!(
+        check_repository_write(&pipeline_state(), &node(), RepositoryWrite::CodeChange).is_ok()
+    );
+}
+
+#[test]
+fn test_check_repository_write_read_only_run_blocks_code_change() {
+    let result = check_repository_write(&read_only_state(), &node(), RepositoryWrite::CodeChange);
+
+    match result {
+        Err(CogWorksError::PipelineHalt {
+            reason: HaltReason::ScopeEnforcer,
+            detail: Some(description),
+        }) => {
+            assert_eq!(
+                description,
+                "node 'review' attempted a code change in a read-only run"
+            );
+        }
+        other => panic!("expected a scope violation, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_check_repository_write_read_only_run_blocks_pull_request_creation() {
+    let result = check_repository_write(
+        &read_only_state(),
+        &node(),
+        RepositoryWrite::PullRequestCreation,
+    );
+
+    assert!(matches!(
+        result,
+        Err(CogWorksError::PipelineHalt { reason: HaltReason::ScopeEnforcer, detail: Some(description) })
+            if description.contains("pull request creation")
+    ));
+}
+
+// ─── create_pull_request ────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_create_pull_request_normal_run_creates_pull_request() {
+    let prs = FakePullRequestManager::default();
+
+    let pull_request = open_pull_request(&pipeline_state(), &prs).await.unwrap();
+
+    assert_eq!(pull_request.title, "Fix the widget");
+    assert_eq!(prs.created().len(), 1);
+}
+
+#[tokio::test]
+async fn test_create_pull_request_read_only_run_refuses_without_calling_github() {
+    let prs = FakePullRequestManager::default();
+
+    let result = open_pull_request(&read_only_state(), &prs).await;
+
+    assert!(matches!(
+        result,
+        Err(ReadOnlyError::WriteRefused(CogWorksError::PipelineHalt {
+            reason: HaltReason::ScopeEnforcer,
+            ..
+        }))
+    ));
+    assert!(prs.created().is_empty());
+}
+
+#[tokio::test]
+async fn test_read_only_run_comments_still_allowed() {
+    let state = read_only_state();
+    let tracker = FakeIssueTracker::default();
+    let work_item = WorkItemId::new(42);
+
+    let write = check_repository_write(&state, &node(), RepositoryWrite::CodeChange);
+    tracker
+        .post_comment(work_item, "Review findings: 2 warnings")
+        .await
+        .unwrap();
+
+    assert!(write.is_err());
+    assert_eq!(
+        tracker.comment_bodies(work_item),
+        vec!["Review findings: 2 warnings".to_string()]
+    );
+}
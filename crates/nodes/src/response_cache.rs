@@ -0,0 +1,218 @@
+//! Per-node caching of LLM responses with an expiry.
+//!
+//! Re-running a node with the same prompt, e.g. when a run resumes or a
+//! rework loop repeats an unchanged step, need not pay for the same call
+//! twice. When the cache is enabled, [`LlmGateway::complete_for_node`]
+//! answers a request identical to one the node already sent from the stored
+//! response. Requests compare equal on every field except
+//! [`CompletionRequest::request_id`], so the cache keys on the resolved
+//! model, the whole prompt, and the sampling parameters.
+//!
+//! Entries expire after the node's TTL, so nodes whose inputs drift outside
+//! the prompt (repository state, external services) call the provider
+//! again. A node listed in `node_ttl_secs` uses its own TTL; other nodes use
+//! `default_ttl_secs`, and entries never expire when neither is set. A TTL
+//! of zero turns caching off for that node.
+//!
+//! ```toml
+//! [llm_cache]
+//! enabled = true            # default: false
+//! default_ttl_secs = 3600   # unset (default): entries never expire
+//! capacity = 256            # default
+//!
+//! [llm_cache.node_ttl_secs]
+//! architecture = 600
+//! review = 0                # never cached
+//! ```
+//!
+//! A cached response reports zero usage and no provider request ID, because
+//! no call was made. Responses cut off by `max_tokens` are not cached.
+//!
+//! [`LlmGateway::complete_for_node`]: crate::gateway::LlmGateway::complete_for_node
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §Response cache.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Mutex, PoisonError},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+use pipeline::{CompletionRequest, CompletionResponse, NodeId, TokenUsage};
+
+/// Entries held when not configured.
+pub const DEFAULT_RESPONSE_CACHE_CAPACITY: usize = 256;
+
+/// The `[llm_cache]` configuration table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseCacheConfig {
+    /// Whether responses are cached at all.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Lifetime of entries for nodes not in `node_ttl_secs`; `None` keeps
+    /// them until evicted.
+    #[serde(default)]
+    pub default_ttl_secs: Option<u64>,
+    /// Lifetime of entries per node; zero disables caching for the node.
+    #[serde(default)]
+    pub node_ttl_secs: HashMap<NodeId, u64>,
+    /// Entries held across all nodes; the oldest is evicted beyond this.
+    #[serde(default = "default_capacity")]
+    pub capacity: usize,
+}
+
+fn default_capacity() -> usize {
+    DEFAULT_RESPONSE_CACHE_CAPACITY
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_ttl_secs: None,
+            node_ttl_secs: HashMap::new(),
+            capacity: DEFAULT_RESPONSE_CACHE_CAPACITY,
+        }
+    }
+}
+
+impl ResponseCacheConfig {
+    /// Returns the lifetime of `node`'s entries: its own TTL, then the
+    /// default. `None` means entries do not expire.
+    pub fn ttl_for(&self, node: &NodeId) -> Option<Duration> {
+        self.node_ttl_secs
+            .get(node)
+            .copied()
+            .or(self.default_ttl_secs)
+            .map(Duration::from_secs)
+    }
+
+    /// Returns `true` if responses to `node` are cached.
+    pub fn caches(&self, node: &NodeId) -> bool {
+        self.enabled && self.capacity > 0 && self.ttl_for(node) != Some(Duration::ZERO)
+    }
+}
+
+/// One stored response.
+struct CacheEntry {
+    node: NodeId,
+    /// The request as sent, with `request_id` cleared.
+    request: CompletionRequest,
+    response: CompletionResponse,
+    /// `None` for entries that do not expire.
+    expires_at: Option<Instant>,
+}
+
+impl CacheEntry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+/// Responses keyed by node and request, expiring per
+/// [`ResponseCacheConfig::ttl_for`].
+pub struct ResponseCache {
+    config: ResponseCacheConfig,
+    /// Oldest first.
+    entries: Mutex<VecDeque<CacheEntry>>,
+}
+
+impl ResponseCache {
+    /// Creates an empty cache.
+    pub fn new(config: ResponseCacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Returns the configuration in use.
+    pub fn config(&self) -> &ResponseCacheConfig {
+        &self.config
+    }
+
+    /// Number of entries held, including expired ones not yet removed.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Returns `true` if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the unexpired response stored for `request` from `node`, with
+    /// zero usage and no provider request ID. Expired entries are removed.
+    pub fn get(&self, node: &NodeId, request: &CompletionRequest) -> Option<CompletionResponse> {
+        if !self.config.caches(node) {
+            return None;
+        }
+        let key = cache_key(request);
+        let now = Instant::now();
+        let mut entries = self.entries();
+        entries.retain(|entry| !entry.is_expired(now));
+        let entry = entries
+            .iter()
+            .find(|entry| &entry.node == node && entry.request == key)?;
+        let mut response = entry.response.clone();
+        response.usage = TokenUsage::zero();
+        response.provider_request_id = None;
+        Some(response)
+    }
+
+    /// Stores `response` to `request` from `node`, replacing any entry for
+    /// the same request and evicting the oldest entry when full. Responses
+    /// truncated at `max_tokens` and nodes with caching off are ignored.
+    pub fn insert(
+        &self,
+        node: &NodeId,
+        request: &CompletionRequest,
+        response: &CompletionResponse,
+    ) {
+        if !self.config.caches(node) || response.finish_reason.is_truncated() {
+            return;
+        }
+        let key = cache_key(request);
+        let mut entries = self.entries();
+        entries.retain(|entry| !(&entry.node == node && entry.request == key));
+        while entries.len() >= self.config.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(CacheEntry {
+            node: node.clone(),
+            request: key,
+            response: response.clone(),
+            expires_at: self.config.ttl_for(node).map(|ttl| Instant::now() + ttl),
+        });
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, VecDeque<CacheEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("config", &self.config)
+            .field("entries", &self.len())
+            .finish()
+    }
+}
+
+/// `request` without its `request_id`, which differs on every call.
+fn cache_key(request: &CompletionRequest) -> CompletionRequest {
+    CompletionRequest {
+        request_id: None,
+        ..request.clone()
+    }
+}
+
+#[cfg(test)]
+#[path = "response_cache_tests.rs"]
+mod tests;
@@ -0,0 +1,216 @@
+use pipeline::{FinishReason, TokenCount};
+
+use crate::test_support::{completion_request, completion_response};
+
+use super::*;
+
+fn node(id: &str) -> NodeId {
+    NodeId::new(id).unwrap()
+}
+
+fn config(default_ttl_secs: Option<u64>, node_ttl_secs: &[(&str, u64)]) -> ResponseCacheConfig {
+    ResponseCacheConfig {
+        enabled: true,
+        default_ttl_secs,
+        node_ttl_secs: node_ttl_secs
+            .iter()
+            .map(|(id, ttl)| (node(id), *ttl))
+            .collect(),
+        ..ResponseCacheConfig::default()
+    }
+}
+
+fn response() -> CompletionResponse {
+    CompletionResponse {
+        provider_request_id: Some("req_1".to_string()),
+        ..completion_response("model-a", "the plan")
+    }
+}
+
+/// Moves every entry's expiry to now, as if its TTL had elapsed.
+fn expire_all(cache: &ResponseCache) {
+    let now = Instant::now();
+    for entry in cache.entries().iter_mut() {
+        entry.expires_at = Some(now);
+    }
+}
+
+// ─── ResponseCacheConfig ────────────────────────────────────────────────────
+
+#[test]
+fn test_ttl_for_node_override_wins_over_default() {
+    let config = config(Some(3600), &[("architecture", 600)]);
+
+    assert_eq!(
+        config.ttl_for(&node("architecture")),
+        Some(Duration::from_secs(600))
+    );
+    assert_eq!(
+        config.ttl_for(&node("plan")),
+        Some(Duration::from_secs(3600))
+    );
+    assert_eq!(ResponseCacheConfig::default().ttl_for(&node("plan")), None);
+}
+
+#[test]
+fn test_caches_disabled_zero_ttl_or_zero_capacity_returns_false() {
+    let config = config(None, &[("review", 0)]);
+
+    assert!(config.caches(&node("plan")));
+    assert!(!config.caches(&node("review")));
+    assert!(!ResponseCacheConfig::default().caches(&node("plan")));
+    let no_room = ResponseCacheConfig {
+        capacity: 0,
+        ..config
+    };
+    assert!(!no_room.caches(&node("plan")));
+}
+
+// ─── ResponseCache ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_get_within_ttl_returns_response_with_zero_usage() {
+    let cache = ResponseCache::new(config(Some(3600), &[]));
+    let request = completion_request("model-a", "plan this");
+    cache.insert(&node("plan"), &request, &response());
+
+    let cached = cache.get(&node("plan"), &request).unwrap();
+
+    assert_eq!(cached.content, "the plan");
+    assert_eq!(cached.usage, TokenUsage::zero());
+    assert_eq!(cached.provider_request_id, None);
+}
+
+#[test]
+fn test_get_request_differing_only_in_request_id_hits() {
+    let cache = ResponseCache::new(config(None, &[]));
+    let request = completion_request("model-a", "plan this");
+    cache.insert(
+        &node("plan"),
+        &request.clone().with_request_id("run-1/plan/1"),
+        &response(),
+    );
+
+    let cached = cache.get(&node("plan"), &request.with_request_id("run-2/plan/1"));
+
+    assert!(cached.is_some());
+}
+
+#[test]
+fn test_get_other_node_or_prompt_misses() {
+    let cache = ResponseCache::new(config(None, &[]));
+    let request = completion_request("model-a", "plan this");
+    cache.insert(&node("plan"), &request, &response());
+
+    assert!(cache.get(&node("review"), &request).is_none());
+    assert!(cache
+        .get(&node("plan"), &completion_request("model-a", "plan that"))
+        .is_none());
+    assert!(cache
+        .get(&node("plan"), &completion_request("model-b", "plan this"))
+        .is_none());
+}
+
+#[test]
+fn test_get_after_ttl_misses_and_removes_entry() {
+    let cache = ResponseCache::new(config(Some(60), &[]));
+    let request = completion_request("model-a", "plan this");
+    cache.insert(&node("plan"), &request, &response());
+    expire_all(&cache);
+
+    assert!(cache.get(&node("plan"), &request).is_none());
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_insert_without_ttl_never_expires() {
+    let cache = ResponseCache::new(config(None, &[]));
+    cache.insert(
+        &node("plan"),
+        &completion_request("model-a", "plan this"),
+        &response(),
+    );
+
+    assert!(cache.entries()[0].expires_at.is_none());
+}
+
+#[test]
+fn test_insert_truncated_response_not_cached() {
+    let cache = ResponseCache::new(config(None, &[]));
+    let truncated = CompletionResponse {
+        finish_reason: FinishReason::MaxTokens,
+        ..response()
+    };
+
+    cache.insert(
+        &node("plan"),
+        &completion_request("model-a", "plan this"),
+        &truncated,
+    );
+
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_insert_node_with_zero_ttl_not_cached() {
+    let cache = ResponseCache::new(config(Some(60), &[("review", 0)]));
+    let request = completion_request("model-a", "review this");
+
+    cache.insert(&node("review"), &request, &response());
+
+    assert!(cache.is_empty());
+    assert!(cache.get(&node("review"), &request).is_none());
+}
+
+#[test]
+fn test_insert_same_request_replaces_entry() {
+    let cache = ResponseCache::new(config(None, &[]));
+    let request = completion_request("model-a", "plan this");
+    cache.insert(&node("plan"), &request, &response());
+
+    cache.insert(
+        &node("plan"),
+        &request,
+        &completion_response("model-a", "a better plan"),
+    );
+
+    assert_eq!(cache.len(), 1);
+    assert_eq!(
+        cache.get(&node("plan"), &request).unwrap().content,
+        "a better plan"
+    );
+}
+
+#[test]
+fn test_insert_at_capacity_evicts_oldest() {
+    let cache = ResponseCache::new(ResponseCacheConfig {
+        capacity: 2,
+        ..config(None, &[])
+    });
+    let requests: Vec<_> = ["one", "two", "three"]
+        .into_iter()
+        .map(|prompt| completion_request("model-a", prompt))
+        .collect();
+
+    for request in &requests {
+        cache.insert(&node("plan"), request, &response());
+    }
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&node("plan"), &requests[0]).is_none());
+    assert!(cache.get(&node("plan"), &requests[2]).is_some());
+}
+
+#[test]
+fn test_usage_of_fresh_response_is_unchanged_in_cache() {
+    let cache = ResponseCache::new(config(None, &[]));
+    let request = completion_request("model-a", "plan this");
+    let fresh = response();
+
+    cache.insert(&node("plan"), &request, &fresh);
+
+    assert_eq!(
+        cache.entries()[0].response.usage,
+        TokenUsage::new(TokenCount::new(10), TokenCount::new(5))
+    );
+}
@@ -0,0 +1,157 @@
+//! Review and alignment gating on diagnostic findings.
+//!
+//! Review-style nodes collect [`Diagnostic`]s from a [`DiagnosticSource`]
+//! (domain services, the alignment checker) and turn them into a
+//! [`ReviewVerdict`]: any [`DiagnosticSeverity::Blocking`] finding halts the
+//! node; warnings are carried forward and the pipeline continues;
+//! informational findings do not affect the verdict.
+//!
+//! ## Synthetic Diagnostics
+//!
+//! With the `synthetic-diagnostics` feature enabled, `ScriptedDiagnostics`
+//! replays a fixed list of findings in place of a real domain service, so the
+//! halt and continue paths can be exercised deterministically. The feature
+//! is intended for tests only and must not be enabled in release builds.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Review.
+
+use async_trait::async_trait;
+
+use pipeline::{CogWorksError, Diagnostic, DiagnosticSeverity, PipelineState};
+
+// ─── Source ─────────────────────────────────────────────────────────────────
+
+/// Supplies the diagnostic findings for a review or alignment pass.
+#[async_trait]
+pub trait DiagnosticSource: Send + Sync {
+    /// Collects the findings for the current state of the work item.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the findings could not be obtained (e.g. the
+    /// domain service was unreachable).
+    async fn collect(&self, state: &PipelineState) -> Result<Vec<Diagnostic>, CogWorksError>;
+}
+
+// ─── Verdict ────────────────────────────────────────────────────────────────
+
+/// Decision reached from a set of diagnostic findings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReviewVerdict {
+    /// At least one blocking finding was reported; the node must halt.
+    Halt {
+        /// The blocking findings, in the order they were reported.
+        blocking: Vec<Diagnostic>,
+    },
+    /// No blocking findings; the pipeline may continue.
+    Continue {
+        /// Warnings to carry forward into the node output.
+        warnings: Vec<Diagnostic>,
+    },
+}
+
+impl ReviewVerdict {
+    /// Classifies `diagnostics` by severity.
+    pub fn from_diagnostics(diagnostics: Vec<Diagnostic>) -> Self {
+        let (blocking, rest): (Vec<_>, Vec<_>) = diagnostics
+            .into_iter()
+            .partition(|d| d.severity == DiagnosticSeverity::Blocking);
+
+        if blocking.is_empty() {
+            let warnings = rest
+                .into_iter()
+                .filter(|d| d.severity == DiagnosticSeverity::Warning)
+                .collect();
+            Self::Continue { warnings }
+        } else {
+            Self::Halt { blocking }
+        }
+    }
+
+    /// Returns `true` if the verdict halts the node.
+    pub fn is_halt(&self) -> bool {
+        matches!(self, Self::Halt { .. })
+    }
+}
+
+/// Collects findings from `source` and classifies them.
+///
+/// # Errors
+///
+/// Propagates any error from [`DiagnosticSource::collect`].
+pub async fn review(
+    source: &dyn DiagnosticSource,
+    state: &PipelineState,
+) -> Result<ReviewVerdict, CogWorksError> {
+    let diagnostics = source.collect(state).await?;
+    Ok(ReviewVerdict::from_diagnostics(diagnostics))
+}
+
+// ─── Synthetic source ───────────────────────────────────────────────────────
+
+#[cfg(feature = "synthetic-diagnostics")]
+pub use synthetic::ScriptedDiagnostics;
+
+#[cfg(feature = "synthetic-diagnostics")]
+mod synthetic {
+    use std::{collections::VecDeque, sync::Mutex};
+
+    use async_trait::async_trait;
+
+    use pipeline::{CogWorksError, Diagnostic, PipelineState};
+
+    use super::DiagnosticSource;
+
+    /// A [`DiagnosticSource`] that replays a scripted sequence of findings.
+    ///
+    /// Each call to [`collect`](DiagnosticSource::collect) returns the next
+    /// scripted batch. Once the script is exhausted every call returns no
+    /// findings.
+    #[derive(Debug, Default)]
+    pub struct ScriptedDiagnostics {
+        script: Mutex<VecDeque<Vec<Diagnostic>>>,
+    }
+
+    impl ScriptedDiagnostics {
+        /// Creates a source that returns `batches` in order.
+        pub fn new(batches: impl IntoIterator<Item = Vec<Diagnostic>>) -> Self {
+            Self {
+                script: Mutex::new(batches.into_iter().collect()),
+            }
+        }
+
+        /// Appends a batch to the end of the script.
+        pub fn push(&self, batch: Vec<Diagnostic>) {
+            self.script
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push_back(batch);
+        }
+
+        /// Number of batches not yet returned.
+        pub fn remaining(&self) -> usize {
+            self.script
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .len()
+        }
+    }
+
+    #[async_trait]
+    impl DiagnosticSource for ScriptedDiagnostics {
+        async fn collect(&self, _state: &PipelineState) -> Result<Vec<Diagnostic>, CogWorksError> {
+            Ok(self
+                .script
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .pop_front()
+                .unwrap_or_default())
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "review_tests.rs"]
+mod tests;
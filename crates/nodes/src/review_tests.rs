@@ -0,0 +1,122 @@
+use pipeline::DiagnosticCategory;
+
+#[cfg(feature = "synthetic-diagnostics")]
+use crate::test_support::pipeline_state;
+
+use super::*;
+
+fn diagnostic(severity: DiagnosticSeverity, message: &str) -> Diagnostic {
+    Diagnostic {
+        artifact: None,
+        location: None,
+        severity,
+        category: DiagnosticCategory::new("alignment").unwrap(),
+        message: message.to_string(),
+    }
+}
+
+// ─── ReviewVerdict ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_from_diagnostics_blocking_finding_halts_with_blocking_only() {
+    let verdict = ReviewVerdict::from_diagnostics(vec![
+        diagnostic(DiagnosticSeverity::Warning, "naming"),
+        diagnostic(DiagnosticSeverity::Blocking, "missing requirement"),
+    ]);
+
+    assert_eq!(
+        verdict,
+        ReviewVerdict::Halt {
+            blocking: vec![diagnostic(
+                DiagnosticSeverity::Blocking,
+                "missing requirement"
+            )],
+        }
+    );
+    assert!(verdict.is_halt());
+}
+
+#[test]
+fn test_from_diagnostics_warnings_and_info_continues_with_warnings() {
+    let verdict = ReviewVerdict::from_diagnostics(vec![
+        diagnostic(DiagnosticSeverity::Informational, "style note"),
+        diagnostic(DiagnosticSeverity::Warning, "naming"),
+    ]);
+
+    assert_eq!(
+        verdict,
+        ReviewVerdict::Continue {
+            warnings: vec![diagnostic(DiagnosticSeverity::Warning, "naming")],
+        }
+    );
+    assert!(!verdict.is_halt());
+}
+
+#[test]
+fn test_from_diagnostics_no_findings_continues() {
+    let verdict = ReviewVerdict::from_diagnostics(Vec::new());
+
+    assert_eq!(
+        verdict,
+        ReviewVerdict::Continue {
+            warnings: Vec::new()
+        }
+    );
+}
+
+// ─── Synthetic diagnostics ──────────────────────────────────────────────────
+
+#[cfg(feature = "synthetic-diagnostics")]
+#[tokio::test]
+async fn test_review_injected_blocking_diagnostic_halts() {
+    let source = ScriptedDiagnostics::new([vec![diagnostic(
+        DiagnosticSeverity::Blocking,
+        "interface does not match the design",
+    )]]);
+
+    let verdict = review(&source, &pipeline_state()).await.unwrap();
+
+    assert!(verdict.is_halt());
+    assert_eq!(source.remaining(), 0);
+}
+
+#[cfg(feature = "synthetic-diagnostics")]
+#[tokio::test]
+async fn test_review_injected_warning_continues() {
+    let source = ScriptedDiagnostics::new([vec![diagnostic(
+        DiagnosticSeverity::Warning,
+        "test coverage below target",
+    )]]);
+
+    let verdict = review(&source, &pipeline_state()).await.unwrap();
+
+    assert_eq!(
+        verdict,
+        ReviewVerdict::Continue {
+            warnings: vec![diagnostic(
+                DiagnosticSeverity::Warning,
+                "test coverage below target"
+            )],
+        }
+    );
+}
+
+#[cfg(feature = "synthetic-diagnostics")]
+#[tokio::test]
+async fn test_review_script_exhausted_continues_without_findings() {
+    let source = ScriptedDiagnostics::new([vec![diagnostic(
+        DiagnosticSeverity::Blocking,
+        "missing requirement",
+    )]]);
+    source.push(Vec::new());
+    let state = pipeline_state();
+
+    assert!(review(&source, &state).await.unwrap().is_halt());
+    assert!(!review(&source, &state).await.unwrap().is_halt());
+    assert_eq!(
+        review(&source, &state).await.unwrap(),
+        ReviewVerdict::Continue {
+            warnings: Vec::new()
+        }
+    );
+}
@@ -0,0 +1,136 @@
+//! Per-run cap on sub-work-item creation.
+//!
+//! A Planning node that misreads its input could create dozens of sub-issues
+//! before anyone notices. Every sub-work-item is created through
+//! [`create_sub_work_item`], which counts creations in
+//! [`PipelineState::sub_work_items_created`] and refuses to exceed
+//! [`SubWorkItemCap::max_per_run`]. Reaching the cap halts the run with a
+//! [`CogWorksError::PipelineHalt`] ([`HaltReason::SubWorkItemLimit`]) naming
+//! how many were created.
+//!
+//! The count lives in the persisted state, so the cap holds across steps and
+//! resumes.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Sub-work-item cap.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::instrument;
+
+use pipeline::{
+    CogWorksError, GitHubOperationError, HaltReason, IssueTracker, PipelineState, SubIssue,
+    WorkItemId,
+};
+
+/// Sub-work-items a run may create when no cap is configured.
+pub const DEFAULT_MAX_SUB_WORK_ITEMS_PER_RUN: u32 = 20;
+
+/// Configured limit on sub-work-items created by one run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubWorkItemCap {
+    /// Maximum number of sub-work-items created per run.
+    #[serde(default = "default_max_sub_work_items_per_run")]
+    pub max_per_run: u32,
+}
+
+fn default_max_sub_work_items_per_run() -> u32 {
+    DEFAULT_MAX_SUB_WORK_ITEMS_PER_RUN
+}
+
+impl Default for SubWorkItemCap {
+    fn default() -> Self {
+        Self {
+            max_per_run: DEFAULT_MAX_SUB_WORK_ITEMS_PER_RUN,
+        }
+    }
+}
+
+impl SubWorkItemCap {
+    /// Creates a cap of `max_per_run` sub-work-items.
+    pub fn new(max_per_run: u32) -> Self {
+        Self { max_per_run }
+    }
+
+    /// Number of sub-work-items `state` may still create.
+    pub fn remaining(&self, state: &PipelineState) -> u32 {
+        self.max_per_run
+            .saturating_sub(state.sub_work_items_created)
+    }
+
+    /// Checks that `state` may create one more sub-work-item.
+    ///
+    /// # Errors
+    ///
+    /// [`CogWorksError::PipelineHalt`] ([`HaltReason::SubWorkItemLimit`]) if
+    /// the run has already created `max_per_run` sub-work-items.
+    pub fn check(&self, state: &PipelineState) -> Result<(), CogWorksError> {
+        if self.remaining(state) > 0 {
+            return Ok(());
+        }
+        Err(CogWorksError::halt(
+            HaltReason::SubWorkItemLimit,
+            format!(
+                "sub-work-item cap reached: {} created, limit {} per run",
+                state.sub_work_items_created, self.max_per_run
+            ),
+        ))
+    }
+}
+
+/// Errors returned by [`create_sub_work_item`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SubWorkItemError {
+    /// The cap is reached; the run must halt.
+    #[error(transparent)]
+    CapExceeded(CogWorksError),
+
+    /// The sub-issue could not be created.
+    #[error("failed to create sub-work-item: {source}")]
+    Create {
+        /// The underlying GitHub error.
+        #[source]
+        source: GitHubOperationError,
+    },
+}
+
+/// Creates a sub-issue under `parent` if the run is below `cap`.
+///
+/// On success the creation is counted in `state`. The count is not changed
+/// when creation fails.
+///
+/// # Errors
+///
+/// - [`SubWorkItemError::CapExceeded`] — the run has already created
+///   `cap.max_per_run` sub-work-items; nothing is created.
+/// - [`SubWorkItemError::Create`] — the GitHub call failed.
+#[instrument(skip(state, tracker, body), fields(run_id = %state.run_id))]
+pub async fn create_sub_work_item(
+    state: &mut PipelineState,
+    tracker: &dyn IssueTracker,
+    cap: SubWorkItemCap,
+    parent: WorkItemId,
+    title: &str,
+    body: &str,
+) -> Result<SubIssue, SubWorkItemError> {
+    if let Err(violation) = cap.check(state) {
+        tracing::error!(
+            created = state.sub_work_items_created,
+            limit = cap.max_per_run,
+            "sub-work-item cap reached; halting"
+        );
+        return Err(SubWorkItemError::CapExceeded(violation));
+    }
+    let sub_issue = tracker
+        .create_sub_issue(parent, title, body)
+        .await
+        .map_err(|source| SubWorkItemError::Create { source })?;
+    state.sub_work_items_created += 1;
+    Ok(sub_issue)
+}
+
+#[cfg(test)]
+#[path = "sub_work_items_tests.rs"]
+mod tests;
@@ -0,0 +1,131 @@
+use pipeline::IssueTracker;
+
+use crate::test_support::{pipeline_state, FakeIssueTracker};
+
+use super::*;
+
+fn parent() -> WorkItemId {
+    WorkItemId::new(42)
+}
+
+async fn create(
+    state: &mut PipelineState,
+    tracker: &dyn IssueTracker,
+    cap: SubWorkItemCap,
+    title: &str,
+) -> Result<SubIssue, SubWorkItemError> {
+    create_sub_work_item(state, tracker, cap, parent(), title, "Split from the plan.").await
+}
+
+// ─── SubWorkItemCap ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_sub_work_item_cap_default_uses_default_limit() {
+    assert_eq!(
+        SubWorkItemCap::default().max_per_run,
+        DEFAULT_MAX_SUB_WORK_ITEMS_PER_RUN
+    );
+}
+
+#[test]
+fn test_remaining_partially_used_cap_returns_difference() {
+    let mut state = pipeline_state();
+    state.sub_work_items_created = 3;
+
+    assert_eq!(SubWorkItemCap::new(5).remaining(&state), 2);
+    assert_eq!(SubWorkItemCap::new(2).remaining(&state), 0);
+}
+
+#[test]
+fn test_check_cap_reached_halts_with_sub_work_item_limit_and_count() {
+    let mut state = pipeline_state();
+    state.sub_work_items_created = 4;
+
+    let result = SubWorkItemCap::new(4).check(&state);
+
+    let Err(CogWorksError::PipelineHalt {
+        reason: HaltReason::SubWorkItemLimit,
+        detail: Some(description),
+    }) = result
+    else {
+        panic!("expected a SubWorkItemLimit halt, got {result:?}");
+    };
+    assert!(description.contains("4 created"), "{description}");
+    assert!(description.contains("limit 4"), "{description}");
+}
+
+#[test]
+fn test_check_below_cap_returns_ok() {
+    let mut state = pipeline_state();
+    state.sub_work_items_created = 1;
+
+    assert!(SubWorkItemCap::new(2).check(&state).is_ok());
+}
+
+// ─── create_sub_work_item ───────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_create_sub_work_item_under_cap_creates_and_counts() {
+    let tracker = FakeIssueTracker::default();
+    let mut state = pipeline_state();
+
+    for title in ["API", "Storage"] {
+        create(&mut state, &tracker, SubWorkItemCap::new(2), title)
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(state.sub_work_items_created, 2);
+    let titles: Vec<String> = tracker.sub_issues().into_iter().map(|s| s.title).collect();
+    assert_eq!(titles, vec!["API".to_string(), "Storage".to_string()]);
+    assert!(tracker.sub_issues().iter().all(|s| s.parent_id == parent()));
+}
+
+#[tokio::test]
+async fn test_create_sub_work_item_cap_exceeded_halts_without_creating() {
+    let tracker = FakeIssueTracker::default();
+    let mut state = pipeline_state();
+    create(&mut state, &tracker, SubWorkItemCap::new(1), "API")
+        .await
+        .unwrap();
+
+    let result = create(&mut state, &tracker, SubWorkItemCap::new(1), "Storage").await;
+
+    assert!(matches!(
+        result,
+        Err(SubWorkItemError::CapExceeded(CogWorksError::PipelineHalt {
+            reason: HaltReason::SubWorkItemLimit,
+            ..
+        }))
+    ));
+    assert_eq!(state.sub_work_items_created, 1);
+    assert_eq!(tracker.sub_issues().len(), 1);
+}
+
+#[tokio::test]
+async fn test_create_sub_work_item_count_from_earlier_step_applies() {
+    let tracker = FakeIssueTracker::default();
+    let mut state = pipeline_state();
+    state.sub_work_items_created = 3;
+
+    let result = create(&mut state, &tracker, SubWorkItemCap::new(3), "API").await;
+
+    assert!(matches!(result, Err(SubWorkItemError::CapExceeded(_))));
+    assert!(tracker.sub_issues().is_empty());
+}
+
+#[tokio::test]
+async fn test_create_sub_work_item_github_failure_does_not_count() {
+    let tracker = FakeIssueTracker::failing_sub_issues();
+    let mut state = pipeline_state();
+
+    let result = create(&mut state, &tracker, SubWorkItemCap::new(2), "API").await;
+
+    assert!(matches!(
+        result,
+        Err(SubWorkItemError::Create {
+            source: GitHubOperationError::Transient { .. }
+        })
+    ));
+    assert_eq!(state.sub_work_items_created, 0);
+}
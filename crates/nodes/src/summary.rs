@@ -0,0 +1,122 @@
+//! Human-readable run summary comment.
+//!
+//! At the end of every step the executor upserts one summary comment on the
+//! work-item issue. The comment is identified by the configured
+//! [`CommentMarkers::summary`] marker, so re-runs and later steps edit the
+//! same comment rather than adding new ones. Its text comes from a
+//! [`MessageCatalog`].
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Run summary.
+
+use tracing::instrument;
+
+use pipeline::{GitHubOperationError, IssueTracker, PipelineOutcome};
+
+use crate::{
+    diagnostic_details::render_diagnostic_details, executor::StepResult, markers::CommentMarkers,
+    messages::MessageCatalog,
+};
+
+/// Returns the message ID of the label for an outcome (`None` = still
+/// running).
+fn outcome_label(outcome: Option<PipelineOutcome>) -> &'static str {
+    match outcome {
+        Some(PipelineOutcome::Completed) => "outcome.completed",
+        Some(PipelineOutcome::Failed) => "outcome.failed",
+        Some(PipelineOutcome::HumanGated) => "outcome.human_gated",
+        Some(PipelineOutcome::Escalated) => "outcome.escalated",
+        None => "outcome.in_progress",
+    }
+}
+
+/// Renders the Markdown run summary for `step_result`.
+///
+/// Lists the outcome, pull request (as a `#N` reference, which GitHub links
+/// automatically), the node and edge cost split, and the nodes run in this
+/// step. Findings recorded in the step follow in a collapsed block (see
+/// [`render_diagnostic_details`]). The comment marker is not included;
+/// [`post_run_summary`] adds it.
+#[must_use]
+pub fn summary_comment(step_result: &StepResult, messages: &MessageCatalog) -> String {
+    let row = |id: &str, value: &str| format!("| **{}** | {value} |", messages.message(id));
+    let mut lines = vec![
+        format!("### {}", messages.message("summary.title")),
+        String::new(),
+        "| | |".to_string(),
+        "|---|---|".to_string(),
+        row(
+            "summary.outcome",
+            messages.message(outcome_label(step_result.outcome)),
+        ),
+    ];
+    if let Some(pr) = step_result.pull_request {
+        lines.push(row("summary.pull_request", &format!("#{pr}")));
+    }
+    lines.push(row("summary.cost", &step_result.total_cost().to_string()));
+    lines.push(row(
+        "summary.cost_breakdown",
+        &messages.format(
+            "summary.cost_breakdown_value",
+            &[
+                ("nodes", &step_result.node_cost.to_string()),
+                ("edges", &step_result.edge_cost.to_string()),
+            ],
+        ),
+    ));
+    lines.push(row("summary.run", &format!("`{}`", step_result.run_id)));
+    lines.push(String::new());
+
+    if step_result.executed_nodes.is_empty() {
+        lines.push(messages.message("summary.no_nodes").to_string());
+    } else {
+        let nodes = step_result
+            .executed_nodes
+            .iter()
+            .map(|node| format!("`{node}`"))
+            .collect::<Vec<_>>()
+            .join(" → ");
+        lines.push(format!(
+            "**{}** {nodes}",
+            messages.message("summary.nodes_run")
+        ));
+    }
+
+    if let Some(details) = render_diagnostic_details(&step_result.diagnostics, messages) {
+        lines.push(String::new());
+        lines.push(details);
+    }
+
+    lines.join("\n")
+}
+
+/// Creates or updates the run summary comment on the work item.
+///
+/// The comment is located by `markers.summary`.
+///
+/// # Errors
+///
+/// Any [`GitHubOperationError`] from [`IssueTracker::upsert_comment`].
+#[instrument(
+    skip(issues, markers, messages, step_result),
+    fields(work_item = %step_result.work_item_id)
+)]
+pub async fn post_run_summary(
+    issues: &dyn IssueTracker,
+    markers: &CommentMarkers,
+    messages: &MessageCatalog,
+    step_result: &StepResult,
+) -> Result<(), GitHubOperationError> {
+    issues
+        .upsert_comment(
+            step_result.work_item_id,
+            &markers.summary,
+            &summary_comment(step_result, messages),
+        )
+        .await
+}
+
+#[cfg(test)]
+#[path = "summary_tests.rs"]
+mod tests;
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use pipeline::{PipelineRunId, PullRequestId, TokenCost, WorkItemId};
+
+use crate::test_support::FakeIssueTracker;
+
+use super::*;
+
+fn cost(usd: f64) -> TokenCost {
+    TokenCost::new(usd).unwrap()
+}
+
+fn completed_step() -> StepResult {
+    let mut step = StepResult::new(PipelineRunId::new_random(), WorkItemId::new(42));
+    step.outcome = Some(PipelineOutcome::Completed);
+    step.pull_request = Some(PullRequestId::new(57));
+    step.record_node(pipeline::NodeId::new("plan").unwrap(), cost(1.5));
+    step.record_node(pipeline::NodeId::new("code").unwrap(), cost(2.0));
+    step
+}
+
+#[test]
+fn test_summary_comment_completed_run_lists_outcome_cost_and_pr() {
+    let step = completed_step();
+
+    let summary = summary_comment(&step, &MessageCatalog::english());
+
+    assert!(summary.contains("| **Outcome** | ✅ Completed |"));
+    assert!(summary.contains("| **Pull request** | #57 |"));
+    assert!(summary.contains("| **Cost** | $3.500000 |"));
+    assert!(summary.contains("nodes $3.500000 · edge evaluations $0.000000"));
+    assert!(summary.contains("**Nodes run:** `plan` → `code`"));
+    assert!(summary.contains(&format!("`{}`", step.run_id)));
+}
+
+#[test]
+fn test_summary_comment_running_without_pr_omits_pr_row() {
+    let step = StepResult::new(PipelineRunId::new_random(), WorkItemId::new(42));
+
+    let summary = summary_comment(&step, &MessageCatalog::english());
+
+    assert!(summary.contains("🔄 In progress"));
+    assert!(!summary.contains("Pull request"));
+    assert!(summary.contains("No nodes ran in this step."));
+}
+
+#[test]
+fn test_summary_comment_each_outcome_uses_its_label() {
+    for (outcome, label) in [
+        (PipelineOutcome::Failed, "❌ Failed"),
+        (PipelineOutcome::HumanGated, "⏸️ Awaiting human review"),
+        (PipelineOutcome::Escalated, "⚠️ Escalated"),
+    ] {
+        let mut step = StepResult::new(PipelineRunId::new_random(), WorkItemId::new(42));
+        step.outcome = Some(outcome);
+        assert!(summary_comment(&step, &MessageCatalog::english()).contains(label));
+    }
+}
+
+#[test]
+fn test_summary_comment_recorded_findings_appended_in_details_block() {
+    let mut step = completed_step();
+    step.record_diagnostics([pipeline::Diagnostic {
+        artifact: None,
+        location: None,
+        severity: pipeline::DiagnosticSeverity::Warning,
+        category: pipeline::DiagnosticCategory::new("style").unwrap(),
+        message: "long function".to_string(),
+    }]);
+
+    let summary = summary_comment(&step, &MessageCatalog::english());
+
+    let details = summary.find("<details>").unwrap();
+    assert!(summary.find("**Nodes run:**").unwrap() < details);
+    assert!(summary.contains("<summary>Findings: 1 warning</summary>"));
+    assert!(summary.contains("- **style**: long function"));
+}
+
+#[test]
+fn test_summary_comment_no_findings_has_no_details_block() {
+    let summary = summary_comment(&completed_step(), &MessageCatalog::english());
+
+    assert!(!summary.contains("<details>"));
+}
+
+#[test]
+fn test_summary_comment_override_catalog_translates_and_falls_back_to_english() {
+    let messages = MessageCatalog::with_overrides(HashMap::from([
+        (
+            "summary.title".to_string(),
+            "Résumé de l'exécution CogWorks".to_string(),
+        ),
+        ("summary.outcome".to_string(), "Résultat".to_string()),
+        ("outcome.completed".to_string(), "✅ Terminé".to_string()),
+    ]));
+
+    let summary = summary_comment(&completed_step(), &messages);
+
+    assert!(summary.starts_with("### Résumé de l'exécution CogWorks"));
+    assert!(summary.contains("| **Résultat** | ✅ Terminé |"));
+    assert!(summary.contains("| **Pull request** | #57 |"));
+}
+
+#[tokio::test]
+async fn test_post_run_summary_first_run_posts_marked_comment() {
+    let issues = FakeIssueTracker::default();
+    let markers = CommentMarkers::default();
+
+    post_run_summary(
+        &issues,
+        &markers,
+        &MessageCatalog::english(),
+        &completed_step(),
+    )
+    .await
+    .unwrap();
+
+    let bodies = issues.comment_bodies(WorkItemId::new(42));
+    assert_eq!(bodies.len(), 1);
+    assert!(bodies[0].starts_with(&markers.summary));
+    assert!(bodies[0].contains("#57"));
+}
+
+#[tokio::test]
+async fn test_post_run_summary_rerun_updates_same_comment() {
+    let issues = FakeIssueTracker::default();
+    let markers = CommentMarkers::default();
+    let messages = MessageCatalog::english();
+    let mut step = StepResult::new(PipelineRunId::new_random(), WorkItemId::new(42));
+    post_run_summary(&issues, &markers, &messages, &step)
+        .await
+        .unwrap();
+
+    step.outcome = Some(PipelineOutcome::Completed);
+    post_run_summary(&issues, &markers, &messages, &step)
+        .await
+        .unwrap();
+
+    let bodies = issues.comment_bodies(WorkItemId::new(42));
+    assert_eq!(bodies.len(), 1);
+    assert!(bodies[0].contains("✅ Completed"));
+    assert_eq!(issues.comment_writes(), 2);
+}
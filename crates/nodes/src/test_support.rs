@@ -0,0 +1,635 @@
+//! In-memory fakes shared by the crate's unit tests.
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use tokio::sync::Semaphore;
+
+use pipeline::{
+    BranchName, ChangedFile, CodeRepository, CommentId, CommitSha, CompletionChunk,
+    CompletionRequest, CompletionResponse, CompletionStream, DirectoryEntry, DirectoryEntryKind,
+    FileContent, FinishReason, GitHubOperationError, GitObjectSha, Issue, IssueComment,
+    IssueFilter, IssueState, IssueStateReason, IssueTracker, Label, LlmError, LlmProvider, Message,
+    Milestone, MilestoneId, PipelineRunId, PipelineState, PullRequest, PullRequestFilter,
+    PullRequestId, PullRequestManager, RepositoryId, ReviewStatus, SubIssue, SubWorkItemId,
+    Timestamp, TokenCost, TokenCount, TokenUsage, TypedLink, TypedLinkKind, WorkItemId,
+};
+
+/// State of a run that has not executed any node yet.
+pub(crate) fn pipeline_state() -> PipelineState {
+    PipelineState {
+        run_id: PipelineRunId::new_random(),
+        node_states: HashMap::new(),
+        active_parallel_branches: Vec::new(),
+        cost_accumulator: TokenCost::zero(),
+        last_processed_event: None,
+        expected_labels: Default::default(),
+        sub_work_items_created: 0,
+        read_only: false,
+    }
+}
+
+/// Error returned by the fake GitHub methods tests do not use.
+fn unsupported(operation: &str) -> GitHubOperationError {
+    GitHubOperationError::SdkCapabilityMissing {
+        capability: format!("fake github: {operation}"),
+    }
+}
+
+/// [`IssueTracker`] keeping comments, labels, and sub-issues in memory.
+#[derive(Default)]
+pub(crate) struct FakeIssueTracker {
+    comments: Mutex<Vec<(WorkItemId, IssueComment)>>,
+    writes: Mutex<u32>,
+    /// Labels of each issue; issues without an entry have none.
+    labels: Mutex<HashMap<WorkItemId, Vec<Label>>>,
+    sub_issues: Mutex<Vec<SubIssue>>,
+    /// When set, `create_sub_issue` fails with a transient error.
+    fail_sub_issues: bool,
+}
+
+impl FakeIssueTracker {
+    /// Creates a tracker where `work_item` carries the `labels` named.
+    pub(crate) fn with_labels<'a>(
+        work_item: WorkItemId,
+        labels: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let tracker = Self::default();
+        tracker.labels.lock().unwrap().insert(
+            work_item,
+            labels
+                .into_iter()
+                .map(|name| Label {
+                    name: name.to_string(),
+                    color: None,
+                })
+                .collect(),
+        );
+        tracker
+    }
+
+    /// Creates a tracker whose `create_sub_issue` always fails.
+    pub(crate) fn failing_sub_issues() -> Self {
+        Self {
+            fail_sub_issues: true,
+            ..Self::default()
+        }
+    }
+
+    /// Sub-issues created so far, oldest first.
+    pub(crate) fn sub_issues(&self) -> Vec<SubIssue> {
+        self.sub_issues.lock().unwrap().clone()
+    }
+
+    /// Bodies of the comments on `work_item`, oldest first.
+    pub(crate) fn comment_bodies(&self, work_item: WorkItemId) -> Vec<String> {
+        self.comments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(id, _)| *id == work_item)
+            .map(|(_, comment)| comment.body.clone())
+            .collect()
+    }
+
+    /// Number of comments posted or updated.
+    pub(crate) fn comment_writes(&self) -> u32 {
+        *self.writes.lock().unwrap()
+    }
+}
+
+#[async_trait]
+impl IssueTracker for FakeIssueTracker {
+    async fn get_issue(&self, _id: WorkItemId) -> Result<Issue, GitHubOperationError> {
+        Err(unsupported("get_issue"))
+    }
+
+    async fn list_sub_issues(
+        &self,
+        _parent: WorkItemId,
+    ) -> Result<Vec<SubIssue>, GitHubOperationError> {
+        Err(unsupported("list_sub_issues"))
+    }
+
+    async fn create_sub_issue(
+        &self,
+        parent: WorkItemId,
+        title: &str,
+        _body: &str,
+    ) -> Result<SubIssue, GitHubOperationError> {
+        if self.fail_sub_issues {
+            return Err(GitHubOperationError::Transient {
+                message: "GitHub unreachable".to_string(),
+            });
+        }
+        let mut sub_issues = self.sub_issues.lock().unwrap();
+        let sub_issue = SubIssue {
+            id: SubWorkItemId::new(1000 + sub_issues.len() as u64),
+            parent_id: parent,
+            title: title.to_string(),
+            state: IssueState::Open,
+            created_at: Timestamp::now().as_datetime(),
+        };
+        sub_issues.push(sub_issue.clone());
+        Ok(sub_issue)
+    }
+
+    async fn add_typed_link(
+        &self,
+        _source: WorkItemId,
+        _target: WorkItemId,
+        _kind: TypedLinkKind,
+    ) -> Result<TypedLink, GitHubOperationError> {
+        Err(unsupported("add_typed_link"))
+    }
+
+    async fn get_typed_links(
+        &self,
+        _id: WorkItemId,
+    ) -> Result<Vec<TypedLink>, GitHubOperationError> {
+        Err(unsupported("get_typed_links"))
+    }
+
+    async fn get_labels(&self, id: WorkItemId) -> Result<Vec<Label>, GitHubOperationError> {
+        Ok(self
+            .labels
+            .lock()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn add_label(&self, id: WorkItemId, label: &Label) -> Result<(), GitHubOperationError> {
+        let mut labels = self.labels.lock().unwrap();
+        let labels = labels.entry(id).or_default();
+        if !labels.iter().any(|existing| existing.name == label.name) {
+            labels.push(label.clone());
+        }
+        Ok(())
+    }
+
+    async fn remove_label(
+        &self,
+        id: WorkItemId,
+        label: &Label,
+    ) -> Result<(), GitHubOperationError> {
+        if let Some(labels) = self.labels.lock().unwrap().get_mut(&id) {
+            labels.retain(|existing| existing.name != label.name);
+        }
+        Ok(())
+    }
+
+    async fn post_comment(&self, id: WorkItemId, body: &str) -> Result<(), GitHubOperationError> {
+        let mut comments = self.comments.lock().unwrap();
+        let comment_id = CommentId::new(comments.len() as u64 + 1);
+        comments.push((
+            id,
+            IssueComment {
+                id: comment_id,
+                author: "cogworks[bot]".to_string(),
+                body: body.to_string(),
+                created_at: Timestamp::now().as_datetime(),
+            },
+        ));
+        *self.writes.lock().unwrap() += 1;
+        Ok(())
+    }
+
+    async fn list_comments(
+        &self,
+        id: WorkItemId,
+    ) -> Result<Vec<IssueComment>, GitHubOperationError> {
+        Ok(self
+            .comments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(work_item, _)| *work_item == id)
+            .map(|(_, comment)| comment.clone())
+            .collect())
+    }
+
+    async fn update_comment(
+        &self,
+        comment: CommentId,
+        body: &str,
+    ) -> Result<(), GitHubOperationError> {
+        let mut comments = self.comments.lock().unwrap();
+        let (_, existing) = comments
+            .iter_mut()
+            .find(|(_, existing)| existing.id == comment)
+            .ok_or_else(|| GitHubOperationError::NotFound {
+                resource: format!("comment {comment}"),
+            })?;
+        existing.body = body.to_string();
+        *self.writes.lock().unwrap() += 1;
+        Ok(())
+    }
+
+    async fn get_issue_state(&self, _id: WorkItemId) -> Result<IssueState, GitHubOperationError> {
+        Err(unsupported("get_issue_state"))
+    }
+
+    async fn set_issue_state(
+        &self,
+        _id: WorkItemId,
+        _state: IssueState,
+        _reason: IssueStateReason,
+    ) -> Result<(), GitHubOperationError> {
+        Err(unsupported("set_issue_state"))
+    }
+
+    async fn list_issues(
+        &self,
+        _repository: &RepositoryId,
+        _filter: &IssueFilter,
+    ) -> Result<Vec<Issue>, GitHubOperationError> {
+        Err(unsupported("list_issues"))
+    }
+
+    async fn get_milestone(&self, _id: MilestoneId) -> Result<Milestone, GitHubOperationError> {
+        Err(unsupported("get_milestone"))
+    }
+
+    async fn set_milestone(
+        &self,
+        _id: WorkItemId,
+        _milestone: Option<MilestoneId>,
+    ) -> Result<(), GitHubOperationError> {
+        Err(unsupported("set_milestone"))
+    }
+}
+
+/// [`CodeRepository`] serving files from memory, whatever the repository or
+/// ref.
+#[derive(Default)]
+pub(crate) struct FakeCodeRepository {
+    /// File contents keyed by repository-root-relative path.
+    files: BTreeMap<String, String>,
+    reads: Mutex<Vec<String>>,
+}
+
+impl FakeCodeRepository {
+    /// Creates a repository holding `files` as `(path, content)` pairs.
+    pub(crate) fn with_files<'a>(files: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        Self {
+            files: files
+                .into_iter()
+                .map(|(path, content)| (path.to_string(), content.to_string()))
+                .collect(),
+            reads: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Paths passed to `read_file`, in call order.
+    pub(crate) fn reads(&self) -> Vec<String> {
+        self.reads.lock().unwrap().clone()
+    }
+
+    fn sha() -> GitObjectSha {
+        GitObjectSha::new("0000000000000000000000000000000000000000").unwrap()
+    }
+}
+
+#[async_trait]
+impl CodeRepository for FakeCodeRepository {
+    async fn read_file(
+        &self,
+        _repository: &RepositoryId,
+        path: &str,
+        _git_ref: &str,
+    ) -> Result<FileContent, GitHubOperationError> {
+        self.reads.lock().unwrap().push(path.to_string());
+        let content = self
+            .files
+            .get(path)
+            .ok_or_else(|| GitHubOperationError::NotFound {
+                resource: path.to_string(),
+            })?;
+        Ok(FileContent {
+            path: path.to_string(),
+            content: content.as_bytes().to_vec(),
+            sha: Self::sha(),
+            content_type: None,
+        })
+    }
+
+    async fn list_directory(
+        &self,
+        _repository: &RepositoryId,
+        path: &str,
+        _git_ref: &str,
+    ) -> Result<Vec<DirectoryEntry>, GitHubOperationError> {
+        let prefix = format!("{path}/");
+        let mut entries: BTreeMap<String, DirectoryEntryKind> = BTreeMap::new();
+        for file in self.files.keys() {
+            let Some(rest) = file.strip_prefix(&prefix) else {
+                continue;
+            };
+            match rest.split_once('/') {
+                Some((directory, _)) => {
+                    entries.insert(directory.to_string(), DirectoryEntryKind::Directory);
+                }
+                None => {
+                    entries.insert(rest.to_string(), DirectoryEntryKind::File);
+                }
+            }
+        }
+        if entries.is_empty() {
+            return Err(GitHubOperationError::NotFound {
+                resource: path.to_string(),
+            });
+        }
+        Ok(entries
+            .into_iter()
+            .map(|(name, kind)| DirectoryEntry {
+                path: format!("{prefix}{name}"),
+                name,
+                kind,
+                sha: Self::sha(),
+            })
+            .collect())
+    }
+
+    async fn file_exists(
+        &self,
+        _repository: &RepositoryId,
+        path: &str,
+        _git_ref: &str,
+    ) -> Result<bool, GitHubOperationError> {
+        let prefix = format!("{path}/");
+        Ok(self
+            .files
+            .keys()
+            .any(|file| file == path || file.starts_with(&prefix)))
+    }
+
+    async fn read_tree(
+        &self,
+        _repository: &RepositoryId,
+        _git_ref: &str,
+    ) -> Result<Vec<DirectoryEntry>, GitHubOperationError> {
+        Err(GitHubOperationError::SdkCapabilityMissing {
+            capability: "fake code repository: read_tree".to_string(),
+        })
+    }
+}
+
+// ─── Pull requests ───────────────────────────────────────────────────────────
+
+/// [`PullRequestManager`] recording the pull requests it is asked to create.
+#[derive(Default)]
+pub(crate) struct FakePullRequestManager {
+    created: Mutex<Vec<PullRequest>>,
+}
+
+impl FakePullRequestManager {
+    /// Pull requests created so far, oldest first.
+    pub(crate) fn created(&self) -> Vec<PullRequest> {
+        self.created.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl PullRequestManager for FakePullRequestManager {
+    async fn create_pull_request(
+        &self,
+        repository: &RepositoryId,
+        title: &str,
+        body: &str,
+        head: &BranchName,
+        base: &BranchName,
+        _draft: bool,
+    ) -> Result<PullRequest, GitHubOperationError> {
+        let mut created = self.created.lock().unwrap();
+        let pull_request = PullRequest {
+            id: PullRequestId::new(created.len() as u64 + 1),
+            repository: repository.clone(),
+            title: title.to_string(),
+            body: body.to_string(),
+            author: "cogworks[bot]".to_string(),
+            head_branch: head.clone(),
+            base_branch: base.clone(),
+            head_sha: CommitSha::new("0000000000000000000000000000000000000000").unwrap(),
+            is_open: true,
+            is_merged: false,
+            review_status: ReviewStatus {
+                approvals: 0,
+                changes_requested: false,
+                approved: false,
+            },
+            created_at: Timestamp::now().as_datetime(),
+        };
+        created.push(pull_request.clone());
+        Ok(pull_request)
+    }
+
+    async fn get_pull_request(
+        &self,
+        _repository: &RepositoryId,
+        _id: PullRequestId,
+    ) -> Result<PullRequest, GitHubOperationError> {
+        Err(unsupported("get_pull_request"))
+    }
+
+    async fn find_pull_requests(
+        &self,
+        _repository: &RepositoryId,
+        _filter: &PullRequestFilter,
+    ) -> Result<Vec<PullRequest>, GitHubOperationError> {
+        Ok(self.created())
+    }
+
+    async fn post_review_comment(
+        &self,
+        _repository: &RepositoryId,
+        _id: PullRequestId,
+        _commit_sha: &CommitSha,
+        _path: &str,
+        _line: u32,
+        _body: &str,
+    ) -> Result<(), GitHubOperationError> {
+        Err(unsupported("post_review_comment"))
+    }
+
+    async fn get_review_status(
+        &self,
+        _repository: &RepositoryId,
+        _id: PullRequestId,
+    ) -> Result<ReviewStatus, GitHubOperationError> {
+        Err(unsupported("get_review_status"))
+    }
+
+    async fn update_pull_request_body(
+        &self,
+        _repository: &RepositoryId,
+        _id: PullRequestId,
+        _body: &str,
+    ) -> Result<(), GitHubOperationError> {
+        Err(unsupported("update_pull_request_body"))
+    }
+
+    async fn list_pr_files(
+        &self,
+        _repository: &RepositoryId,
+        _id: PullRequestId,
+    ) -> Result<Vec<ChangedFile>, GitHubOperationError> {
+        Ok(Vec::new())
+    }
+}
+
+// ─── LLM ─────────────────────────────────────────────────────────────────────
+
+/// A request for `model` with one user message.
+pub(crate) fn completion_request(model: &str, prompt: &str) -> CompletionRequest {
+    CompletionRequest::new(model, vec![Message::user(prompt)], TokenCount::new(256))
+}
+
+/// A finished response from `model` with `content`.
+pub(crate) fn completion_response(model: &str, content: &str) -> CompletionResponse {
+    CompletionResponse {
+        content: content.to_string(),
+        model: model.to_string(),
+        usage: TokenUsage::new(TokenCount::new(10), TokenCount::new(5)),
+        finish_reason: FinishReason::EndTurn,
+        provider_request_id: None,
+    }
+}
+
+/// [`LlmProvider`] replaying queued results and recording requests.
+///
+/// Once the queue is empty every call answers `"ok"` from the requested
+/// model. A gated provider holds each call until [`FakeLlmProvider::release`]
+/// admits it, so tests can observe how many calls are in flight.
+///
+/// Streaming calls replay streams queued with
+/// [`FakeLlmProvider::push_stream`]; without one they stream the next
+/// [`FakeLlmProvider::complete`] result as a single chunk.
+#[derive(Default)]
+pub(crate) struct FakeLlmProvider {
+    results: Mutex<VecDeque<Result<CompletionResponse, LlmError>>>,
+    streams: Mutex<VecDeque<ScriptedStream>>,
+    requests: Mutex<Vec<CompletionRequest>>,
+    gate: Option<Arc<Semaphore>>,
+    /// Calls currently in flight, and the most seen at once, per model.
+    in_flight: Mutex<HashMap<String, (usize, usize)>>,
+}
+
+impl FakeLlmProvider {
+    /// Creates a provider whose calls wait for [`FakeLlmProvider::release`].
+    pub(crate) fn gated() -> Self {
+        Self {
+            gate: Some(Arc::new(Semaphore::new(0))),
+            ..Self::default()
+        }
+    }
+
+    /// Queues the result of the next call.
+    pub(crate) fn push(&self, result: Result<CompletionResponse, LlmError>) {
+        self.results.lock().unwrap().push_back(result);
+    }
+
+    /// Queues the chunks of the next streaming call. With `stall`, the
+    /// stream never ends after its last chunk, as if the provider hung.
+    pub(crate) fn push_stream(&self, chunks: Vec<Result<CompletionChunk, LlmError>>, stall: bool) {
+        self.streams.lock().unwrap().push_back(ScriptedStream {
+            chunks: chunks.into(),
+            stall,
+        });
+    }
+
+    /// Lets `calls` waiting calls complete.
+    pub(crate) fn release(&self, calls: usize) {
+        if let Some(gate) = &self.gate {
+            gate.add_permits(calls);
+        }
+    }
+
+    /// Requests received, in call order.
+    pub(crate) fn requests(&self) -> Vec<CompletionRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Calls to `model` currently in flight.
+    pub(crate) fn in_flight(&self, model: &str) -> usize {
+        self.in_flight.lock().unwrap().get(model).map_or(0, |c| c.0)
+    }
+
+    /// Most calls to `model` that were in flight at once.
+    pub(crate) fn peak(&self, model: &str) -> usize {
+        self.in_flight.lock().unwrap().get(model).map_or(0, |c| c.1)
+    }
+}
+
+#[async_trait]
+impl LlmProvider for FakeLlmProvider {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        let model = request.model.clone();
+        self.requests.lock().unwrap().push(request);
+        {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            let counts = in_flight.entry(model.clone()).or_default();
+            counts.0 += 1;
+            counts.1 = counts.1.max(counts.0);
+        }
+        if let Some(gate) = &self.gate {
+            gate.acquire().await.unwrap().forget();
+        }
+        if let Some(counts) = self.in_flight.lock().unwrap().get_mut(&model) {
+            counts.0 -= 1;
+        }
+        self.results
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| Ok(completion_response(&model, "ok")))
+    }
+
+    async fn complete_streaming(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<Box<dyn CompletionStream>, LlmError> {
+        let scripted = self.streams.lock().unwrap().pop_front();
+        let stream = match scripted {
+            Some(stream) => {
+                self.requests.lock().unwrap().push(request);
+                stream
+            }
+            None => {
+                let response = self.complete(request).await?;
+                ScriptedStream {
+                    chunks: VecDeque::from([
+                        Ok(CompletionChunk::Text(response.content)),
+                        Ok(CompletionChunk::Finished {
+                            finish_reason: response.finish_reason,
+                            usage: response.usage,
+                        }),
+                    ]),
+                    stall: false,
+                }
+            }
+        };
+        Ok(Box::new(stream))
+    }
+}
+
+/// A [`CompletionStream`] replaying queued chunks.
+struct ScriptedStream {
+    chunks: VecDeque<Result<CompletionChunk, LlmError>>,
+    stall: bool,
+}
+
+#[async_trait]
+impl CompletionStream for ScriptedStream {
+    async fn next_chunk(&mut self) -> Result<Option<CompletionChunk>, LlmError> {
+        match self.chunks.pop_front() {
+            Some(chunk) => chunk.map(Some),
+            None if self.stall => std::future::pending().await,
+            None => Ok(None),
+        }
+    }
+}
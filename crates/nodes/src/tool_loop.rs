@@ -0,0 +1,310 @@
+//! Multi-turn tool-use loops for agentic nodes.
+//!
+//! An agentic node asks the model for the next step, runs the tools it calls
+//! for, and sends the results back, until the model answers without calling
+//! a tool. [`run_tool_loop`] drives that loop through the
+//! [`LlmGateway`]. A [`ToolRunner`] supplied by the node reads the tool calls
+//! out of each response and executes them.
+//!
+//! The loop stops when:
+//!
+//! - the model's response calls no tools — the loop succeeds with that
+//!   response;
+//! - [`ToolLoopConfig::max_iterations`] model calls have been made and the
+//!   last one still calls tools — [`ToolLoopError::IterationLimit`];
+//! - a tool fails in a way the model cannot recover from —
+//!   [`ToolLoopError::ToolFailed`]. A failure the model can act on (bad
+//!   arguments, file not found) is instead a [`ToolResult`] with
+//!   `is_error: true`, sent back like any other result.
+//!
+//! Usage is summed over every call and reported on success and on every
+//! error, so the run budget is charged for calls made before the loop
+//! stopped.
+//!
+//! ```toml
+//! [tool_loop]
+//! max_iterations = 10   # default
+//! ```
+//!
+//! Results are sent back as a user turn containing one `<tool_result>`
+//! element per call (see [`tool_results_message`]).
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §Tool-use loop.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::instrument;
+
+use pipeline::{
+    CompletionRequest, CompletionResponse, LlmError, Message, NodeId, PipelineGraph, TokenUsage,
+    ToolName,
+};
+
+use crate::gateway::LlmGateway;
+
+/// Model calls per loop when not configured.
+pub const DEFAULT_MAX_TOOL_ITERATIONS: u32 = 10;
+
+/// The `[tool_loop]` configuration table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolLoopConfig {
+    /// Model calls allowed per loop, including the first.
+    #[serde(default = "default_max_iterations")]
+    pub max_iterations: u32,
+}
+
+fn default_max_iterations() -> u32 {
+    DEFAULT_MAX_TOOL_ITERATIONS
+}
+
+impl Default for ToolLoopConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: DEFAULT_MAX_TOOL_ITERATIONS,
+        }
+    }
+}
+
+/// A tool invocation requested by the model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolCall {
+    /// Identifier pairing the call with its [`ToolResult`].
+    pub call_id: String,
+    /// The tool to run.
+    pub name: ToolName,
+    /// Arguments as sent by the model, normally a JSON object.
+    pub input: String,
+}
+
+/// The outcome of one [`ToolCall`], sent back to the model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolResult {
+    /// The [`ToolCall::call_id`] this answers.
+    pub call_id: String,
+    /// The tool that ran.
+    pub name: ToolName,
+    /// Tool output, or the error message when `is_error` is set.
+    pub content: String,
+    /// `true` if the tool failed in a way the model may correct.
+    pub is_error: bool,
+}
+
+impl ToolResult {
+    /// A successful result for `call`.
+    pub fn success(call: &ToolCall, content: impl Into<String>) -> Self {
+        Self {
+            call_id: call.call_id.clone(),
+            name: call.name.clone(),
+            content: content.into(),
+            is_error: false,
+        }
+    }
+
+    /// A failed result for `call` that the model is told about.
+    pub fn error(call: &ToolCall, message: impl Into<String>) -> Self {
+        Self {
+            call_id: call.call_id.clone(),
+            name: call.name.clone(),
+            content: message.into(),
+            is_error: true,
+        }
+    }
+}
+
+/// A tool failure that ends the loop.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("tool '{name}' failed: {message}")]
+pub struct ToolFailure {
+    /// The tool that failed.
+    pub name: ToolName,
+    /// Human-readable description of the failure.
+    pub message: String,
+}
+
+/// Reads tool calls from model responses and executes them.
+#[async_trait]
+pub trait ToolRunner: Send + Sync {
+    /// Returns the tool calls in `response`, in the order the model made
+    /// them. An empty list ends the loop.
+    fn tool_calls(&self, response: &CompletionResponse) -> Vec<ToolCall>;
+
+    /// Runs `call`.
+    ///
+    /// # Errors
+    ///
+    /// A [`ToolFailure`] ends the loop. Failures the model may correct are
+    /// returned as `Ok` with [`ToolResult::error`].
+    async fn execute(&self, call: &ToolCall) -> Result<ToolResult, ToolFailure>;
+}
+
+/// A loop that ended with a response calling no tools.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolLoopOutcome {
+    /// The final response.
+    pub response: CompletionResponse,
+    /// Model calls made.
+    pub iterations: u32,
+    /// Usage summed over every model call.
+    pub usage: TokenUsage,
+    /// Every tool result, in the order they were sent back.
+    pub results: Vec<ToolResult>,
+}
+
+/// Errors returned by [`run_tool_loop`]. Each carries the usage of the model
+/// calls made before the loop stopped.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ToolLoopError {
+    /// A model call failed.
+    #[error("LLM call {iteration} of the tool loop failed: {source}")]
+    Llm {
+        /// The failing call, counting from 1.
+        iteration: u32,
+        /// The gateway's error.
+        source: LlmError,
+        /// Usage of the calls that completed.
+        usage: TokenUsage,
+    },
+
+    /// The last allowed model call still requested tools.
+    #[error("tool loop reached its limit of {iterations} LLM calls")]
+    IterationLimit {
+        /// Model calls made.
+        iterations: u32,
+        /// Usage summed over every call.
+        usage: TokenUsage,
+    },
+
+    /// A tool failed in a way the model cannot recover from.
+    #[error("tool loop halted after {iterations} LLM calls: {failure}")]
+    ToolFailed {
+        /// Model calls made.
+        iterations: u32,
+        /// The failing tool call's ID.
+        call_id: String,
+        /// The failure.
+        failure: ToolFailure,
+        /// Usage summed over every call.
+        usage: TokenUsage,
+    },
+}
+
+impl ToolLoopError {
+    /// Usage of the model calls made before the loop stopped.
+    pub fn usage(&self) -> TokenUsage {
+        match self {
+            Self::Llm { usage, .. }
+            | Self::IterationLimit { usage, .. }
+            | Self::ToolFailed { usage, .. } => *usage,
+        }
+    }
+}
+
+/// Runs the tool-use loop for `node`, starting from `request`.
+///
+/// Each iteration sends the conversation through
+/// [`LlmGateway::complete_for_node`]. If the response calls tools, each call
+/// is executed in order, and the response and a [`tool_results_message`] are
+/// appended to the conversation for the next iteration.
+///
+/// # Errors
+///
+/// - [`ToolLoopError::Llm`] — a model call failed.
+/// - [`ToolLoopError::IterationLimit`] — `config.max_iterations` calls were
+///   made and the last still requested tools; its calls are not executed.
+/// - [`ToolLoopError::ToolFailed`] — a tool returned a [`ToolFailure`]; the
+///   remaining calls of that iteration are not executed.
+#[instrument(skip(gateway, graph, request, runner), fields(%node))]
+pub async fn run_tool_loop(
+    gateway: &LlmGateway,
+    graph: &PipelineGraph,
+    node: &NodeId,
+    mut request: CompletionRequest,
+    runner: &dyn ToolRunner,
+    config: &ToolLoopConfig,
+) -> Result<ToolLoopOutcome, ToolLoopError> {
+    let max_iterations = config.max_iterations.max(1);
+    let mut usage = TokenUsage::zero();
+    let mut results = Vec::new();
+    let mut iteration = 0;
+    loop {
+        iteration += 1;
+        let response = gateway
+            .complete_for_node(graph, node, request.clone())
+            .await
+            .map_err(|source| ToolLoopError::Llm {
+                iteration,
+                source,
+                usage,
+            })?;
+        usage = usage + response.usage;
+
+        let calls = runner.tool_calls(&response);
+        if calls.is_empty() {
+            tracing::debug!(iterations = iteration, "tool loop finished");
+            return Ok(ToolLoopOutcome {
+                response,
+                iterations: iteration,
+                usage,
+                results,
+            });
+        }
+        if iteration >= max_iterations {
+            tracing::warn!(
+                iterations = iteration,
+                pending_calls = calls.len(),
+                "tool loop reached its iteration limit"
+            );
+            return Err(ToolLoopError::IterationLimit {
+                iterations: iteration,
+                usage,
+            });
+        }
+
+        let mut turn_results = Vec::with_capacity(calls.len());
+        for call in &calls {
+            let result =
+                runner
+                    .execute(call)
+                    .await
+                    .map_err(|failure| ToolLoopError::ToolFailed {
+                        iterations: iteration,
+                        call_id: call.call_id.clone(),
+                        failure,
+                        usage,
+                    })?;
+            if result.is_error {
+                tracing::debug!(tool = %call.name, call_id = %call.call_id, "tool reported an error");
+            }
+            turn_results.push(result);
+        }
+        request.messages.push(Message::assistant(response.content));
+        request
+            .messages
+            .push(Message::user(tool_results_message(&turn_results)));
+        results.extend(turn_results);
+    }
+}
+
+/// Renders `results` as the user turn sent back to the model: one
+/// `<tool_result call_id=".." name=".." is_error="..">` element per result,
+/// in order.
+pub fn tool_results_message(results: &[ToolResult]) -> String {
+    results
+        .iter()
+        .map(|result| {
+            format!(
+                "<tool_result call_id=\"{}\" name=\"{}\" is_error=\"{}\">\n{}\n</tool_result>",
+                result.call_id, result.name, result.is_error, result.content
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+#[path = "tool_loop_tests.rs"]
+mod tests;
@@ -0,0 +1,364 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use pipeline::{
+    NodeDefinition, NodeGate, NodeType, PipelineSettings, PipelineToolProfileConfig, ProfileName,
+    TokenCount, ValidationKind,
+};
+
+use crate::gateway::ModelConcurrencyLimits;
+use crate::test_support::{completion_request, completion_response, FakeLlmProvider};
+
+use super::*;
+
+fn node_id() -> NodeId {
+    NodeId::new("implement").unwrap()
+}
+
+fn graph() -> PipelineGraph {
+    PipelineGraph {
+        nodes: vec![NodeDefinition {
+            id: node_id(),
+            node_type: NodeType::Llm,
+            declared_inputs: Vec::new(),
+            declared_outputs: Vec::new(),
+            timeout: None,
+            cost_budget: None,
+            gate: NodeGate::AutoProceed,
+            validation_kind: ValidationKind::None,
+            abort_siblings_on_failure: false,
+            priority: 0,
+            model: None,
+        }],
+        edges: Vec::new(),
+        evaluation_modes: HashMap::new(),
+        explicit_edge_lists: HashMap::new(),
+        settings: PipelineSettings {
+            default_timeout: None,
+            default_cost_budget: None,
+            max_node_retries: 3,
+            default_model: None,
+        },
+        tool_profiles: PipelineToolProfileConfig {
+            default_profile: ProfileName::new("default").unwrap(),
+            node_overrides: HashMap::new(),
+        },
+    }
+}
+
+fn gateway(provider: &Arc<FakeLlmProvider>) -> LlmGateway {
+    LlmGateway::new(Arc::clone(provider) as _, ModelConcurrencyLimits::default())
+}
+
+fn call(call_id: &str, name: &str) -> ToolCall {
+    ToolCall {
+        call_id: call_id.to_string(),
+        name: ToolName::new(name).unwrap(),
+        input: "{}".to_string(),
+    }
+}
+
+/// Usage of `calls` responses from [`completion_response`].
+fn usage_of(calls: u64) -> TokenUsage {
+    TokenUsage::new(TokenCount::new(10 * calls), TokenCount::new(5 * calls))
+}
+
+/// Returns scripted tool calls for each response (none once the script runs
+/// out) and records every executed call.
+#[derive(Default)]
+struct ScriptedRunner {
+    turns: Mutex<VecDeque<Vec<ToolCall>>>,
+    /// Calls that always request another tool.
+    endless: Option<ToolCall>,
+    /// Tool answering with a [`ToolResult::error`].
+    reports_error: Option<&'static str>,
+    /// Tool failing with a [`ToolFailure`].
+    fails: Option<&'static str>,
+    executed: Mutex<Vec<String>>,
+}
+
+impl ScriptedRunner {
+    fn with_turns(turns: Vec<Vec<ToolCall>>) -> Self {
+        Self {
+            turns: Mutex::new(turns.into()),
+            ..Self::default()
+        }
+    }
+
+    fn executed(&self) -> Vec<String> {
+        self.executed.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl ToolRunner for ScriptedRunner {
+    fn tool_calls(&self, _response: &CompletionResponse) -> Vec<ToolCall> {
+        self.turns
+            .lock()
+            .unwrap()
+            .pop_front()
+            .or_else(|| self.endless.clone().map(|call| vec![call]))
+            .unwrap_or_default()
+    }
+
+    async fn execute(&self, call: &ToolCall) -> Result<ToolResult, ToolFailure> {
+        self.executed.lock().unwrap().push(call.call_id.clone());
+        if self.fails == Some(call.name.as_str()) {
+            return Err(ToolFailure {
+                name: call.name.clone(),
+                message: "sandbox crashed".to_string(),
+            });
+        }
+        if self.reports_error == Some(call.name.as_str()) {
+            return Ok(ToolResult::error(call, "no such file"));
+        }
+        Ok(ToolResult::success(
+            call,
+            format!("output of {}", call.call_id),
+        ))
+    }
+}
+
+// ─── run_tool_loop ──────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_run_tool_loop_two_iterations_returns_final_response() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push(Ok(completion_response("m", "reading the file")));
+    provider.push(Ok(completion_response("m", "all done")));
+    let runner = ScriptedRunner::with_turns(vec![vec![call("c1", "read_file")]]);
+
+    let outcome = run_tool_loop(
+        &gateway(&provider),
+        &graph(),
+        &node_id(),
+        completion_request("m", "fix the bug"),
+        &runner,
+        &ToolLoopConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(outcome.response.content, "all done");
+    assert_eq!(outcome.iterations, 2);
+    assert_eq!(outcome.usage, usage_of(2));
+    assert_eq!(
+        outcome.results,
+        vec![ToolResult::success(
+            &call("c1", "read_file"),
+            "output of c1"
+        )]
+    );
+    assert_eq!(runner.executed(), vec!["c1"]);
+}
+
+#[tokio::test]
+async fn test_run_tool_loop_tool_results_sent_back_in_next_request() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push(Ok(completion_response("m", "reading the file")));
+    let runner = ScriptedRunner::with_turns(vec![vec![call("c1", "read_file")]]);
+
+    run_tool_loop(
+        &gateway(&provider),
+        &graph(),
+        &node_id(),
+        completion_request("m", "fix the bug"),
+        &runner,
+        &ToolLoopConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    let requests = provider.requests();
+    assert_eq!(requests.len(), 2);
+    let messages = &requests[1].messages;
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[1], Message::assistant("reading the file"));
+    assert_eq!(
+        messages[2],
+        Message::user(tool_results_message(&[ToolResult::success(
+            &call("c1", "read_file"),
+            "output of c1"
+        )]))
+    );
+}
+
+#[tokio::test]
+async fn test_run_tool_loop_hits_iteration_cap_returns_iteration_limit() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let runner = ScriptedRunner {
+        endless: Some(call("again", "read_file")),
+        ..ScriptedRunner::default()
+    };
+
+    let error = run_tool_loop(
+        &gateway(&provider),
+        &graph(),
+        &node_id(),
+        completion_request("m", "fix the bug"),
+        &runner,
+        &ToolLoopConfig { max_iterations: 3 },
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(
+        error,
+        ToolLoopError::IterationLimit { iterations: 3, .. }
+    ));
+    assert_eq!(error.usage(), usage_of(3));
+    assert_eq!(provider.requests().len(), 3);
+    // The last call's tool requests are not executed.
+    assert_eq!(runner.executed().len(), 2);
+}
+
+#[tokio::test]
+async fn test_run_tool_loop_zero_max_iterations_makes_one_call() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let runner = ScriptedRunner::with_turns(vec![vec![call("c1", "read_file")]]);
+
+    let error = run_tool_loop(
+        &gateway(&provider),
+        &graph(),
+        &node_id(),
+        completion_request("m", "fix the bug"),
+        &runner,
+        &ToolLoopConfig { max_iterations: 0 },
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(
+        error,
+        ToolLoopError::IterationLimit { iterations: 1, .. }
+    ));
+    assert_eq!(provider.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_run_tool_loop_unrecoverable_tool_error_returns_tool_failed() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let runner = ScriptedRunner {
+        fails: Some("run_tests"),
+        ..ScriptedRunner::with_turns(vec![vec![
+            call("c1", "read_file"),
+            call("c2", "run_tests"),
+            call("c3", "write_file"),
+        ]])
+    };
+
+    let error = run_tool_loop(
+        &gateway(&provider),
+        &graph(),
+        &node_id(),
+        completion_request("m", "fix the bug"),
+        &runner,
+        &ToolLoopConfig::default(),
+    )
+    .await
+    .unwrap_err();
+
+    match &error {
+        ToolLoopError::ToolFailed {
+            iterations,
+            call_id,
+            failure,
+            ..
+        } => {
+            assert_eq!(*iterations, 1);
+            assert_eq!(call_id, "c2");
+            assert_eq!(failure.name.as_str(), "run_tests");
+        }
+        other => panic!("expected ToolFailed, got {other:?}"),
+    }
+    assert_eq!(error.usage(), usage_of(1));
+    assert_eq!(runner.executed(), vec!["c1", "c2"]);
+    assert_eq!(provider.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_run_tool_loop_recoverable_tool_error_is_sent_back_and_loop_continues() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    let runner = ScriptedRunner {
+        reports_error: Some("read_file"),
+        ..ScriptedRunner::with_turns(vec![vec![call("c1", "read_file")]])
+    };
+
+    let outcome = run_tool_loop(
+        &gateway(&provider),
+        &graph(),
+        &node_id(),
+        completion_request("m", "fix the bug"),
+        &runner,
+        &ToolLoopConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(outcome.iterations, 2);
+    assert!(outcome.results[0].is_error);
+    assert!(provider.requests()[1].messages[2]
+        .content
+        .contains("is_error=\"true\">\nno such file\n"));
+}
+
+#[tokio::test]
+async fn test_run_tool_loop_llm_error_returns_llm_error_with_prior_usage() {
+    let provider = Arc::new(FakeLlmProvider::default());
+    provider.push(Ok(completion_response("m", "reading the file")));
+    provider.push(Err(LlmError::Authentication {
+        message: "HTTP 401".to_string(),
+    }));
+    let runner = ScriptedRunner::with_turns(vec![vec![call("c1", "read_file")]]);
+
+    let error = run_tool_loop(
+        &gateway(&provider),
+        &graph(),
+        &node_id(),
+        completion_request("m", "fix the bug"),
+        &runner,
+        &ToolLoopConfig::default(),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(
+        error,
+        ToolLoopError::Llm {
+            iteration: 2,
+            source: LlmError::Authentication { .. },
+            ..
+        }
+    ));
+    assert_eq!(error.usage(), usage_of(1));
+}
+
+// ─── tool_results_message ───────────────────────────────────────────────────
+
+#[test]
+fn test_tool_results_message_one_element_per_result_in_order() {
+    let results = [
+        ToolResult::success(&call("c1", "read_file"), "fn main() {}"),
+        ToolResult::error(&call("c2", "write_file"), "permission denied"),
+    ];
+
+    assert_eq!(
+        tool_results_message(&results),
+        "<tool_result call_id=\"c1\" name=\"read_file\" is_error=\"false\">\n\
+         fn main() {}\n</tool_result>\n\
+         <tool_result call_id=\"c2\" name=\"write_file\" is_error=\"true\">\n\
+         permission denied\n</tool_result>"
+    );
+}
+
+// ─── ToolLoopConfig ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_tool_loop_config_default_allows_default_iterations() {
+    assert_eq!(
+        ToolLoopConfig::default().max_iterations,
+        DEFAULT_MAX_TOOL_ITERATIONS
+    );
+}
@@ -0,0 +1,202 @@
+//! Per-run LLM usage and cost export as CSV.
+//!
+//! Finance reporting needs the spend of each run broken down by node and
+//! model. [`usage_rows`] aggregates a run's [`LlmCallRecord`]s into one
+//! [`UsageRow`] per node and model, limited to the nodes the step executed and
+//! in execution order. [`UsageCsvExporter`] appends those rows to a CSV file
+//! at a configured path, writing the [`USAGE_CSV_HEADER`] when the file is new
+//! so exports from many runs accumulate in one file.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/nodes.md` §Usage export.
+
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+use tracing::instrument;
+
+use pipeline::{LlmCallRecord, NodeId, PipelineRunId, Timestamp, TokenCost, TokenCount};
+
+use crate::executor::StepResult;
+
+/// Header line of the usage CSV.
+pub const USAGE_CSV_HEADER: &str =
+    "run_id,node,model,input_tokens,output_tokens,cost_usd,timestamp";
+
+/// Usage of one model by one node during a run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageRow {
+    /// Run the usage belongs to.
+    pub run_id: PipelineRunId,
+    /// Node that made the calls.
+    pub node: NodeId,
+    /// Model the calls were sent to.
+    pub model: String,
+    /// Prompt tokens summed over the calls.
+    pub input_tokens: TokenCount,
+    /// Completion tokens summed over the calls.
+    pub output_tokens: TokenCount,
+    /// Cost summed over the calls.
+    pub cost: TokenCost,
+    /// Time of the node's first call to the model.
+    pub timestamp: Timestamp,
+}
+
+impl UsageRow {
+    /// Formats the row as one CSV line, without a line terminator.
+    pub fn to_csv_line(&self) -> String {
+        [
+            csv_field(&self.run_id.to_string()),
+            csv_field(&self.node.to_string()),
+            csv_field(&self.model),
+            self.input_tokens.to_string(),
+            self.output_tokens.to_string(),
+            format!("{:.6}", self.cost.as_f64()),
+            self.timestamp.to_string(),
+        ]
+        .join(",")
+    }
+}
+
+/// Quotes `value` if it contains a comma, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Aggregates `calls` into one row per executed node and model.
+///
+/// Calls from nodes not in `step_result.executed_nodes` are ignored. Rows are
+/// ordered by the node's position in `executed_nodes`, then by the model's
+/// first call.
+pub fn usage_rows(step_result: &StepResult, calls: &[LlmCallRecord]) -> Vec<UsageRow> {
+    let mut rows: Vec<UsageRow> = Vec::new();
+    for node in &step_result.executed_nodes {
+        if rows.iter().any(|row| &row.node == node) {
+            continue;
+        }
+        let start = rows.len();
+        for call in calls.iter().filter(|call| &call.node_id == node) {
+            let timestamp = Timestamp::from_utc(call.timestamp);
+            match rows[start..]
+                .iter_mut()
+                .find(|row| row.model == call.model_id)
+            {
+                Some(row) => {
+                    row.input_tokens += call.prompt_tokens;
+                    row.output_tokens += call.completion_tokens;
+                    row.cost += call.cost;
+                    row.timestamp = row.timestamp.min(timestamp);
+                }
+                None => rows.push(UsageRow {
+                    run_id: step_result.run_id,
+                    node: node.clone(),
+                    model: call.model_id.clone(),
+                    input_tokens: call.prompt_tokens,
+                    output_tokens: call.completion_tokens,
+                    cost: call.cost,
+                    timestamp,
+                }),
+            }
+        }
+    }
+    rows
+}
+
+/// Renders `rows` as a CSV document with a header line.
+#[must_use]
+pub fn render_usage_csv(rows: &[UsageRow]) -> String {
+    let mut csv = String::from(USAGE_CSV_HEADER);
+    csv.push('\n');
+    for row in rows {
+        csv.push_str(&row.to_csv_line());
+        csv.push('\n');
+    }
+    csv
+}
+
+// ─── Exporter ───────────────────────────────────────────────────────────────
+
+/// Errors returned by [`UsageCsvExporter::export`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum UsageExportError {
+    /// The CSV file could not be opened or written.
+    #[error("failed to write usage CSV '{}': {message}", path.display())]
+    Write {
+        /// The configured export path.
+        path: PathBuf,
+        /// The underlying I/O error.
+        message: String,
+    },
+}
+
+/// Appends per-run usage rows to a CSV file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageCsvExporter {
+    path: PathBuf,
+}
+
+impl UsageCsvExporter {
+    /// Creates an exporter writing to `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the configured export path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends the usage rows of `step_result` to the CSV file.
+    ///
+    /// Creates the file if needed and writes [`USAGE_CSV_HEADER`] first when
+    /// it is empty. Returns the number of rows written.
+    ///
+    /// # Errors
+    ///
+    /// - [`UsageExportError::Write`] — the file could not be opened or
+    ///   written.
+    #[instrument(skip(self, step_result, calls), fields(path = %self.path.display(), run_id = %step_result.run_id))]
+    pub fn export(
+        &self,
+        step_result: &StepResult,
+        calls: &[LlmCallRecord],
+    ) -> Result<usize, UsageExportError> {
+        let rows = usage_rows(step_result, calls);
+        let write_err = |e: std::io::Error| UsageExportError::Write {
+            path: self.path.clone(),
+            message: e.to_string(),
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(write_err)?;
+        let mut out = String::new();
+        if file.metadata().map_err(write_err)?.len() == 0 {
+            out.push_str(USAGE_CSV_HEADER);
+            out.push('\n');
+        }
+        for row in &rows {
+            out.push_str(&row.to_csv_line());
+            out.push('\n');
+        }
+        file.write_all(out.as_bytes()).map_err(write_err)?;
+        tracing::debug!(rows = rows.len(), "usage exported");
+        Ok(rows.len())
+    }
+}
+
+#[cfg(test)]
+#[path = "usage_export_tests.rs"]
+mod tests;
@@ -0,0 +1,274 @@
+use std::time::Duration;
+
+use pipeline::{
+    LlmCallRecord, NodeId, PipelineRunId, Timestamp, TokenCost, TokenCount, WorkItemId,
+};
+
+use super::*;
+
+fn node(name: &str) -> NodeId {
+    NodeId::new(name).unwrap()
+}
+
+fn at(rfc3339: &str) -> Timestamp {
+    Timestamp::parse_rfc3339(rfc3339).unwrap()
+}
+
+fn call(
+    node_id: &str,
+    model: &str,
+    prompt: u64,
+    completion: u64,
+    usd: f64,
+    time: &str,
+) -> LlmCallRecord {
+    LlmCallRecord {
+        node_id: node(node_id),
+        model_id: model.to_string(),
+        prompt_tokens: TokenCount::new(prompt),
+        completion_tokens: TokenCount::new(completion),
+        cost: TokenCost::new(usd).unwrap(),
+        latency: Duration::from_millis(250),
+        schema_validated: true,
+        request_id: None,
+        provider_request_id: None,
+        timestamp: at(time).as_datetime(),
+    }
+}
+
+fn step(executed: &[&str]) -> StepResult {
+    let mut step = StepResult::new(PipelineRunId::new_random(), WorkItemId::new(42));
+    step.executed_nodes = executed.iter().map(|name| node(name)).collect();
+    step
+}
+
+fn temp_csv_path() -> PathBuf {
+    std::env::temp_dir().join(format!("usage-{}.csv", PipelineRunId::new_random()))
+}
+
+// ─── usage_rows ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_usage_rows_calls_from_one_node_and_model_are_summed() {
+    let step = step(&["plan"]);
+    let calls = [
+        call(
+            "plan",
+            "claude-sonnet",
+            100,
+            20,
+            0.5,
+            "2026-03-01T10:00:05Z",
+        ),
+        call(
+            "plan",
+            "claude-sonnet",
+            50,
+            10,
+            0.25,
+            "2026-03-01T10:00:01Z",
+        ),
+    ];
+
+    let rows = usage_rows(&step, &calls);
+
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(row.run_id, step.run_id);
+    assert_eq!(row.node, node("plan"));
+    assert_eq!(row.model, "claude-sonnet");
+    assert_eq!(row.input_tokens, TokenCount::new(150));
+    assert_eq!(row.output_tokens, TokenCount::new(30));
+    assert!((row.cost.as_f64() - 0.75).abs() < 1e-9);
+    assert_eq!(row.timestamp, at("2026-03-01T10:00:01Z"));
+}
+
+#[test]
+fn test_usage_rows_models_and_nodes_split_in_execution_order() {
+    let step = step(&["review", "plan"]);
+    let calls = [
+        call("plan", "claude-sonnet", 10, 1, 0.1, "2026-03-01T10:00:00Z"),
+        call("review", "claude-opus", 20, 2, 0.2, "2026-03-01T10:01:00Z"),
+        call("review", "claude-haiku", 30, 3, 0.3, "2026-03-01T10:02:00Z"),
+    ];
+
+    let rows = usage_rows(&step, &calls);
+
+    let keys: Vec<(String, String)> = rows
+        .iter()
+        .map(|row| (row.node.to_string(), row.model.clone()))
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            ("review".to_string(), "claude-opus".to_string()),
+            ("review".to_string(), "claude-haiku".to_string()),
+            ("plan".to_string(), "claude-sonnet".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_usage_rows_node_not_executed_is_ignored() {
+    let step = step(&["plan"]);
+    let calls = [
+        call("plan", "claude-sonnet", 10, 1, 0.1, "2026-03-01T10:00:00Z"),
+        call(
+            "review",
+            "claude-sonnet",
+            20,
+            2,
+            0.2,
+            "2026-03-01T10:01:00Z",
+        ),
+    ];
+
+    let rows = usage_rows(&step, &calls);
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].node, node("plan"));
+}
+
+#[test]
+fn test_usage_rows_node_executed_twice_is_reported_once() {
+    let step = step(&["plan", "review", "plan"]);
+    let calls = [call(
+        "plan",
+        "claude-sonnet",
+        10,
+        1,
+        0.1,
+        "2026-03-01T10:00:00Z",
+    )];
+
+    let rows = usage_rows(&step, &calls);
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].input_tokens, TokenCount::new(10));
+}
+
+// ─── CSV formatting ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_to_csv_line_plain_row_matches_header_columns() {
+    let step = step(&["plan"]);
+    let calls = [call(
+        "plan",
+        "claude-sonnet",
+        100,
+        20,
+        0.5,
+        "2026-03-01T10:00:00Z",
+    )];
+    let row = &usage_rows(&step, &calls)[0];
+
+    let line = row.to_csv_line();
+
+    assert_eq!(
+        line,
+        format!(
+            "{},plan,claude-sonnet,100,20,0.500000,{}",
+            step.run_id,
+            at("2026-03-01T10:00:00Z")
+        )
+    );
+    assert_eq!(line.split(',').count(), USAGE_CSV_HEADER.split(',').count());
+}
+
+#[test]
+fn test_to_csv_line_model_with_comma_and_quote_is_quoted() {
+    let step = step(&["plan"]);
+    let calls = [call(
+        "plan",
+        "vendor,\"big\"",
+        1,
+        1,
+        0.0,
+        "2026-03-01T10:00:00Z",
+    )];
+    let row = &usage_rows(&step, &calls)[0];
+
+    let line = row.to_csv_line();
+
+    assert!(line.contains(",\"vendor,\"\"big\"\"\","), "line: {line}");
+}
+
+#[test]
+fn test_render_usage_csv_rows_follow_header() {
+    let step = step(&["plan", "review"]);
+    let calls = [
+        call("plan", "claude-sonnet", 1, 1, 0.1, "2026-03-01T10:00:00Z"),
+        call("review", "claude-sonnet", 2, 2, 0.2, "2026-03-01T10:01:00Z"),
+    ];
+    let rows = usage_rows(&step, &calls);
+
+    let csv = render_usage_csv(&rows);
+
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], USAGE_CSV_HEADER);
+    assert_eq!(lines[1], rows[0].to_csv_line());
+    assert_eq!(lines[2], rows[1].to_csv_line());
+}
+
+#[test]
+fn test_render_usage_csv_no_rows_is_header_only() {
+    assert_eq!(render_usage_csv(&[]), format!("{USAGE_CSV_HEADER}\n"));
+}
+
+// ─── UsageCsvExporter ───────────────────────────────────────────────────────
+
+#[test]
+fn test_export_new_file_writes_header_and_rows() {
+    let path = temp_csv_path();
+    let exporter = UsageCsvExporter::new(&path);
+    let step = step(&["plan"]);
+    let calls = [call(
+        "plan",
+        "claude-sonnet",
+        1,
+        1,
+        0.1,
+        "2026-03-01T10:00:00Z",
+    )];
+
+    let written = exporter.export(&step, &calls).unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(written, 1);
+    assert_eq!(contents, render_usage_csv(&usage_rows(&step, &calls)));
+}
+
+#[test]
+fn test_export_existing_file_appends_without_repeating_header() {
+    let path = temp_csv_path();
+    let exporter = UsageCsvExporter::new(&path);
+    let first = step(&["plan"]);
+    let second = step(&["review"]);
+    let calls = [
+        call("plan", "claude-sonnet", 1, 1, 0.1, "2026-03-01T10:00:00Z"),
+        call("review", "claude-opus", 2, 2, 0.2, "2026-03-01T10:01:00Z"),
+    ];
+
+    exporter.export(&first, &calls).unwrap();
+    exporter.export(&second, &calls).unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], USAGE_CSV_HEADER);
+    assert!(lines[1].starts_with(&first.run_id.to_string()));
+    assert!(lines[2].starts_with(&second.run_id.to_string()));
+}
+
+#[test]
+fn test_export_unwritable_path_returns_write_error() {
+    let path = temp_csv_path().join("missing-dir").join("usage.csv");
+    let exporter = UsageCsvExporter::new(&path);
+
+    let result = exporter.export(&step(&[]), &[]);
+
+    assert!(matches!(result, Err(UsageExportError::Write { path: p, .. }) if p == path));
+}
@@ -0,0 +1,249 @@
+//! Per-work-item processing lock.
+//!
+//! Two invocations processing the same work item at once would race on its
+//! labels, comments, and branches. [`acquire_work_lock`] takes the lock: it
+//! adds the [`PROCESSING_LABEL`] and records the holding run and the time in a
+//! lock comment (identified by [`CommentMarkers::lock`]). Another invocation
+//! that finds the label backs off. [`release_work_lock`] removes the label.
+//!
+//! The step function (`run_step`) these are meant to bracket does not exist
+//! yet, so nothing calls [`acquire_work_lock`] or [`release_work_lock`] and
+//! the lock is not enforced today. Until a caller takes the lock around each
+//! step, concurrent invocations on one work item are not prevented.
+//!
+//! An invocation that crashes never releases its lock. A lock whose comment
+//! is older than [`WorkLockConfig::stale_after_minutes`] (default 30) is
+//! treated as abandoned and reclaimed, with a warning. A label without a
+//! readable lock comment names no holder that could come back for it (the
+//! holder crashed between adding the label and writing the comment, or the
+//! comment was deleted), so it is reclaimed at once.
+//!
+//! The lock is advisory: labels and comments cannot be updated atomically,
+//! so two invocations starting within the same instant can both acquire it.
+//! Trigger deduplication upstream keeps that window small.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/security.md` §THREAT-007 and `docs/spec/operations.md`
+//! §Pipeline is Stuck (Processing Lock).
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use pipeline::{GitHubOperationError, IssueTracker, Label, PipelineRunId, Timestamp, WorkItemId};
+
+use crate::markers::CommentMarkers;
+
+/// Label marking a work item as being processed. Pipeline-internal; not
+/// configurable.
+pub const PROCESSING_LABEL: &str = "cogworks:processing";
+
+/// Age after which a lock is considered abandoned, in minutes, when not
+/// configured.
+pub const DEFAULT_STALE_LOCK_MINUTES: u64 = 30;
+
+/// Processing lock settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkLockConfig {
+    /// Minutes after which a held lock is reclaimed.
+    #[serde(default = "default_stale_after_minutes")]
+    pub stale_after_minutes: u64,
+}
+
+fn default_stale_after_minutes() -> u64 {
+    DEFAULT_STALE_LOCK_MINUTES
+}
+
+impl Default for WorkLockConfig {
+    fn default() -> Self {
+        Self {
+            stale_after_minutes: default_stale_after_minutes(),
+        }
+    }
+}
+
+impl WorkLockConfig {
+    /// Returns `true` if a lock taken at `acquired_at` is abandoned at `now`.
+    pub fn is_stale(&self, acquired_at: Timestamp, now: Timestamp) -> bool {
+        let stale_after = Duration::from_secs(self.stale_after_minutes.saturating_mul(60));
+        now.duration_since(acquired_at)
+            .is_some_and(|age| age > stale_after)
+    }
+}
+
+/// Who holds a lock, as recorded in the lock comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockHolder {
+    /// ID of the run holding the lock, as written in the comment.
+    pub run_id: String,
+    /// When the lock was taken.
+    pub acquired_at: Timestamp,
+}
+
+/// Prefix of the hidden line recording the holder in the lock comment.
+const HOLDER_PREFIX: &str = "<!-- lock-holder:";
+
+/// Result of [`acquire_work_lock`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockAcquisition {
+    /// The work item was free; the caller now holds the lock.
+    Acquired,
+    /// An abandoned lock was taken over; the caller now holds the lock.
+    Reclaimed {
+        /// The holder whose lock went stale; `None` if the label was present
+        /// but the lock comment was missing or unreadable.
+        previous: Option<LockHolder>,
+    },
+    /// Another invocation holds the lock; the caller must back off.
+    Held {
+        /// The current holder.
+        holder: LockHolder,
+    },
+}
+
+impl LockAcquisition {
+    /// Returns `true` if the caller holds the lock.
+    pub fn is_acquired(&self) -> bool {
+        !matches!(self, Self::Held { .. })
+    }
+}
+
+fn processing_label() -> Label {
+    Label {
+        name: PROCESSING_LABEL.to_string(),
+        color: None,
+    }
+}
+
+/// Renders the lock comment body (without the marker).
+fn lock_comment(run_id: PipelineRunId, acquired_at: Timestamp) -> String {
+    format!(
+        "🔒 CogWorks run `{run_id}` is processing this work item (since {acquired_at}).\n\
+         {HOLDER_PREFIX} {run_id} {acquired_at} -->"
+    )
+}
+
+/// Extracts the holder recorded in a lock comment body.
+fn parse_lock_comment(body: &str) -> Option<LockHolder> {
+    let start = body.find(HOLDER_PREFIX)? + HOLDER_PREFIX.len();
+    let mut fields = body[start..].split_whitespace();
+    let run_id = fields.next()?.to_string();
+    let acquired_at = Timestamp::parse_rfc3339(fields.next()?)?;
+    Some(LockHolder {
+        run_id,
+        acquired_at,
+    })
+}
+
+/// Reads the holder from the lock comment on `work_item_id`, if any.
+async fn current_holder(
+    tracker: &dyn IssueTracker,
+    work_item_id: WorkItemId,
+    markers: &CommentMarkers,
+) -> Result<Option<LockHolder>, GitHubOperationError> {
+    Ok(tracker
+        .list_comments(work_item_id)
+        .await?
+        .into_iter()
+        .rev()
+        .find(|comment| comment.body.contains(&markers.lock))
+        .and_then(|comment| parse_lock_comment(&comment.body)))
+}
+
+/// Takes the processing lock on `work_item_id` for `run_id`.
+///
+/// If [`PROCESSING_LABEL`] is absent, adds it and writes the lock comment.
+/// If it is present with a lock comment older than
+/// `config.stale_after_minutes`, or with no readable lock comment at all,
+/// takes the lock over. Otherwise returns [`LockAcquisition::Held`] and
+/// changes nothing.
+///
+/// # Errors
+///
+/// Any [`GitHubOperationError`] from reading labels or comments, adding the
+/// label, or writing the comment. If writing the comment fails after the
+/// label was added, the lock is held without a comment; release it.
+#[instrument(skip(tracker, markers, config))]
+pub async fn acquire_work_lock(
+    tracker: &dyn IssueTracker,
+    work_item_id: WorkItemId,
+    run_id: PipelineRunId,
+    markers: &CommentMarkers,
+    config: &WorkLockConfig,
+    now: Timestamp,
+) -> Result<LockAcquisition, GitHubOperationError> {
+    let labelled = tracker
+        .get_labels(work_item_id)
+        .await?
+        .iter()
+        .any(|label| label.name == PROCESSING_LABEL);
+
+    let acquisition = if labelled {
+        match current_holder(tracker, work_item_id, markers).await? {
+            Some(holder) if config.is_stale(holder.acquired_at, now) => {
+                tracing::warn!(
+                    previous_run = %holder.run_id,
+                    acquired_at = %holder.acquired_at,
+                    "reclaiming stale processing lock"
+                );
+                LockAcquisition::Reclaimed {
+                    previous: Some(holder),
+                }
+            }
+            Some(holder) => {
+                tracing::info!(holder = %holder.run_id, "work item is locked by another run; backing off");
+                return Ok(LockAcquisition::Held { holder });
+            }
+            None => {
+                tracing::warn!("processing label has no lock comment; reclaiming lock");
+                LockAcquisition::Reclaimed { previous: None }
+            }
+        }
+    } else {
+        tracker.add_label(work_item_id, &processing_label()).await?;
+        LockAcquisition::Acquired
+    };
+
+    tracker
+        .upsert_comment(work_item_id, &markers.lock, &lock_comment(run_id, now))
+        .await?;
+    Ok(acquisition)
+}
+
+/// Releases the processing lock on `work_item_id` held by `run_id`.
+///
+/// Removes [`PROCESSING_LABEL`] unless the lock comment names a different
+/// run, which means the lock went stale and was reclaimed; that lock is left
+/// alone. Returns `true` if the label was removed.
+///
+/// # Errors
+///
+/// Any [`GitHubOperationError`] from reading comments or removing the label.
+#[instrument(skip(tracker, markers))]
+pub async fn release_work_lock(
+    tracker: &dyn IssueTracker,
+    work_item_id: WorkItemId,
+    run_id: PipelineRunId,
+    markers: &CommentMarkers,
+) -> Result<bool, GitHubOperationError> {
+    if let Some(holder) = current_holder(tracker, work_item_id, markers).await? {
+        if holder.run_id != run_id.to_string() {
+            tracing::warn!(
+                holder = %holder.run_id,
+                "processing lock was reclaimed by another run; not releasing"
+            );
+            return Ok(false);
+        }
+    }
+    tracker
+        .remove_label(work_item_id, &processing_label())
+        .await?;
+    tracing::debug!("released processing lock");
+    Ok(true)
+}
+
+#[cfg(test)]
+#[path = "work_lock_tests.rs"]
+mod tests;
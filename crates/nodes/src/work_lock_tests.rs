@@ -0,0 +1,221 @@
+use crate::test_support::FakeIssueTracker;
+
+use super::*;
+
+fn work_item() -> WorkItemId {
+    WorkItemId::new(42)
+}
+
+fn minutes(count: u64) -> Duration {
+    Duration::from_secs(count * 60)
+}
+
+async fn labels(tracker: &FakeIssueTracker) -> Vec<String> {
+    tracker
+        .get_labels(work_item())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|label| label.name)
+        .collect()
+}
+
+async fn acquire(
+    tracker: &FakeIssueTracker,
+    run_id: PipelineRunId,
+    now: Timestamp,
+) -> LockAcquisition {
+    acquire_work_lock(
+        tracker,
+        work_item(),
+        run_id,
+        &CommentMarkers::default(),
+        &WorkLockConfig::default(),
+        now,
+    )
+    .await
+    .unwrap()
+}
+
+// ─── Configuration ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_work_lock_config_default_stale_after_thirty_minutes() {
+    assert_eq!(
+        WorkLockConfig::default().stale_after_minutes,
+        DEFAULT_STALE_LOCK_MINUTES
+    );
+}
+
+#[test]
+fn test_is_stale_older_than_threshold_returns_true() {
+    let acquired_at = Timestamp::now();
+
+    assert!(WorkLockConfig::default().is_stale(acquired_at, acquired_at.add_duration(minutes(31))));
+}
+
+#[test]
+fn test_is_stale_younger_than_threshold_returns_false() {
+    let acquired_at = Timestamp::now();
+
+    assert!(!WorkLockConfig::default().is_stale(acquired_at, acquired_at.add_duration(minutes(5))));
+}
+
+#[test]
+fn test_is_stale_acquired_in_future_returns_false() {
+    let now = Timestamp::now();
+
+    assert!(!WorkLockConfig::default().is_stale(now.add_duration(minutes(60)), now));
+}
+
+// ─── Lock comment ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_lock_comment_rendered_comment_round_trips_holder() {
+    let run_id = PipelineRunId::new_random();
+    let acquired_at = Timestamp::now();
+
+    let holder = parse_lock_comment(&lock_comment(run_id, acquired_at)).unwrap();
+
+    assert_eq!(holder.run_id, run_id.to_string());
+    assert_eq!(holder.acquired_at, acquired_at);
+}
+
+#[test]
+fn test_parse_lock_comment_without_holder_line_returns_none() {
+    assert_eq!(parse_lock_comment("🔒 CogWorks is processing"), None);
+}
+
+// ─── acquire_work_lock ──────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_acquire_work_lock_free_item_adds_label_and_comment() {
+    let tracker = FakeIssueTracker::default();
+    let run_id = PipelineRunId::new_random();
+
+    let acquisition = acquire(&tracker, run_id, Timestamp::now()).await;
+
+    assert_eq!(acquisition, LockAcquisition::Acquired);
+    assert!(acquisition.is_acquired());
+    assert_eq!(labels(&tracker).await, vec![PROCESSING_LABEL]);
+    let comments = tracker.comment_bodies(work_item());
+    assert_eq!(comments.len(), 1);
+    assert!(comments[0].contains(&CommentMarkers::default().lock));
+    assert!(comments[0].contains(&run_id.to_string()));
+}
+
+#[tokio::test]
+async fn test_acquire_work_lock_held_by_live_run_returns_held_and_changes_nothing() {
+    let tracker = FakeIssueTracker::default();
+    let holder = PipelineRunId::new_random();
+    let acquired_at = Timestamp::now();
+    acquire(&tracker, holder, acquired_at).await;
+
+    let acquisition = acquire(
+        &tracker,
+        PipelineRunId::new_random(),
+        acquired_at.add_duration(minutes(5)),
+    )
+    .await;
+
+    assert_eq!(
+        acquisition,
+        LockAcquisition::Held {
+            holder: LockHolder {
+                run_id: holder.to_string(),
+                acquired_at,
+            },
+        }
+    );
+    assert!(!acquisition.is_acquired());
+    assert_eq!(tracker.comment_writes(), 1);
+}
+
+#[tokio::test]
+async fn test_acquire_work_lock_label_without_comment_reclaims_lock() {
+    let tracker = FakeIssueTracker::with_labels(work_item(), [PROCESSING_LABEL]);
+    let run_id = PipelineRunId::new_random();
+
+    let acquisition = acquire(&tracker, run_id, Timestamp::now()).await;
+
+    assert_eq!(acquisition, LockAcquisition::Reclaimed { previous: None });
+    assert!(acquisition.is_acquired());
+    let comments = tracker.comment_bodies(work_item());
+    assert_eq!(comments.len(), 1);
+    assert!(comments[0].contains(&run_id.to_string()));
+    assert_eq!(labels(&tracker).await, vec![PROCESSING_LABEL]);
+}
+
+#[tokio::test]
+async fn test_acquire_work_lock_stale_lock_reclaimed_for_new_run() {
+    let tracker = FakeIssueTracker::default();
+    let abandoned = PipelineRunId::new_random();
+    let acquired_at = Timestamp::now();
+    acquire(&tracker, abandoned, acquired_at).await;
+    let run_id = PipelineRunId::new_random();
+
+    let acquisition = acquire(&tracker, run_id, acquired_at.add_duration(minutes(31))).await;
+
+    assert!(matches!(
+        &acquisition,
+        LockAcquisition::Reclaimed { previous: Some(previous) }
+            if previous.run_id == abandoned.to_string()
+    ));
+    assert!(acquisition.is_acquired());
+    let comments = tracker.comment_bodies(work_item());
+    assert_eq!(comments.len(), 1);
+    assert!(comments[0].contains(&run_id.to_string()));
+    assert_eq!(labels(&tracker).await, vec![PROCESSING_LABEL]);
+}
+
+// ─── release_work_lock ──────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_release_work_lock_holder_removes_label() {
+    let tracker = FakeIssueTracker::default();
+    let run_id = PipelineRunId::new_random();
+    acquire(&tracker, run_id, Timestamp::now()).await;
+
+    let released = release_work_lock(&tracker, work_item(), run_id, &CommentMarkers::default())
+        .await
+        .unwrap();
+
+    assert!(released);
+    assert!(labels(&tracker).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_release_work_lock_after_reclaim_leaves_new_holders_lock() {
+    let tracker = FakeIssueTracker::default();
+    let abandoned = PipelineRunId::new_random();
+    let acquired_at = Timestamp::now();
+    acquire(&tracker, abandoned, acquired_at).await;
+    acquire(
+        &tracker,
+        PipelineRunId::new_random(),
+        acquired_at.add_duration(minutes(31)),
+    )
+    .await;
+
+    let released = release_work_lock(&tracker, work_item(), abandoned, &CommentMarkers::default())
+        .await
+        .unwrap();
+
+    assert!(!released);
+    assert_eq!(labels(&tracker).await, vec![PROCESSING_LABEL]);
+}
+
+#[tokio::test]
+async fn test_release_work_lock_then_acquire_succeeds() {
+    let tracker = FakeIssueTracker::default();
+    let first = PipelineRunId::new_random();
+    let now = Timestamp::now();
+    acquire(&tracker, first, now).await;
+    release_work_lock(&tracker, work_item(), first, &CommentMarkers::default())
+        .await
+        .unwrap();
+
+    let acquisition = acquire(&tracker, PipelineRunId::new_random(), now).await;
+
+    assert_eq!(acquisition, LockAcquisition::Acquired);
+}
@@ -56,6 +56,12 @@ pub struct LlmCallRecord {
     pub latency: Duration,
     /// Whether the completion was validated against the output schema.
     pub schema_validated: bool,
+    /// Client-generated request ID sent with the call, if any.
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// Request ID the provider assigned to the call, if reported.
+    #[serde(default)]
+    pub provider_request_id: Option<String>,
     /// When the call was made (UTC).
     pub timestamp: DateTime<Utc>,
 }
@@ -195,6 +201,43 @@ pub enum AuditEvent {
     ScopeViolation(ScopeViolationRecord),
 }
 
+impl AuditEvent {
+    /// Returns the kind of this event, matching its serialised `kind` tag.
+    pub fn kind(&self) -> AuditEventKind {
+        match self {
+            AuditEvent::LlmCall(_) => AuditEventKind::LlmCall,
+            AuditEvent::Validation(_) => AuditEventKind::Validation,
+            AuditEvent::StateTransition(_) => AuditEventKind::StateTransition,
+            AuditEvent::CostSnapshot(_) => AuditEventKind::CostSnapshot,
+            AuditEvent::EdgeEvaluation(_) => AuditEventKind::EdgeEvaluation,
+            AuditEvent::InjectionDetected(_) => AuditEventKind::InjectionDetected,
+            AuditEvent::ScopeViolation(_) => AuditEventKind::ScopeViolation,
+        }
+    }
+}
+
+/// The variant of an [`AuditEvent`] without its payload.
+///
+/// Used to select events by kind, e.g. when replaying only LLM calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventKind {
+    /// [`AuditEvent::LlmCall`].
+    LlmCall,
+    /// [`AuditEvent::Validation`].
+    Validation,
+    /// [`AuditEvent::StateTransition`].
+    StateTransition,
+    /// [`AuditEvent::CostSnapshot`].
+    CostSnapshot,
+    /// [`AuditEvent::EdgeEvaluation`].
+    EdgeEvaluation,
+    /// [`AuditEvent::InjectionDetected`].
+    InjectionDetected,
+    /// [`AuditEvent::ScopeViolation`].
+    ScopeViolation,
+}
+
 // ─── Pipeline summary ────────────────────────────────────────────────────────
 
 /// Overall outcome of a completed (or halted) pipeline run.
@@ -0,0 +1,213 @@
+//! Context Pack content types and selective file loading.
+//!
+//! A Context Pack is a directory under [`CONTEXT_PACKS_DIR`] holding domain
+//! knowledge, safe patterns, and anti-patterns for one area of the codebase.
+//! Large packs need not be loaded whole: a [`PackFileSelection`] of include
+//! and exclude [`GlobPattern`]s restricts which files a node pulls into its
+//! prompt. Files the pack's [`ContextPackManifest`] declares as required are
+//! checked regardless of the selection; a missing required file is always an
+//! error.
+//!
+//! ## Glob Syntax
+//!
+//! Patterns match pack-relative paths using `/` as the separator:
+//!
+//! | Token | Matches |
+//! |-------|---------|
+//! | `*` | Any run of characters within one path segment |
+//! | `?` | Exactly one character within one path segment |
+//! | `**` | Zero or more whole path segments (must be a segment on its own) |
+//!
+//! ## Specification
+//!
+//! See `docs/spec/architecture.md` §Context Pack Selection.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    github::GitHubOperationError,
+    llm::{SystemLayer, SystemSegment},
+    ContextPackId,
+};
+
+/// Repository-relative directory containing all Context Packs.
+pub const CONTEXT_PACKS_DIR: &str = ".cogworks/context-packs";
+
+// ─── Globs ──────────────────────────────────────────────────────────────────
+
+/// A file glob matched against pack-relative paths.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GlobPattern(String);
+
+impl GlobPattern {
+    /// Creates a [`GlobPattern`], returning `None` if `pattern` is empty.
+    #[must_use]
+    pub fn new(pattern: impl Into<String>) -> Option<Self> {
+        let pattern = pattern.into();
+        if pattern.is_empty() {
+            None
+        } else {
+            Some(Self(pattern))
+        }
+    }
+
+    /// Returns the pattern text.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns `true` if `path` (pack-relative, `/`-separated) matches.
+    pub fn matches(&self, path: &str) -> bool {
+        let pattern: Vec<&str> = self.0.split('/').collect();
+        let path: Vec<&str> = path.split('/').collect();
+        match_segments(&pattern, &path)
+    }
+}
+
+impl std::fmt::Display for GlobPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Matches path segments against pattern segments, expanding `**`.
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(rest, &path[skip..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((name, path_rest)) => {
+                match_segment(
+                    &segment.chars().collect::<Vec<_>>(),
+                    &name.chars().collect::<Vec<_>>(),
+                ) && match_segments(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Matches one path segment against a pattern segment with `*` and `?`.
+fn match_segment(pattern: &[char], name: &[char]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern and the name position it was
+    // tried at, for backtracking.
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some('?') => {
+                p += 1;
+                n += 1;
+            }
+            Some(c) if *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Which files of a pack to load.
+///
+/// A file is selected when it matches at least one `include` pattern (or
+/// `include` is empty) and matches no `exclude` pattern. The default selects
+/// every file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackFileSelection {
+    /// Patterns a file must match to be loaded. Empty means all files.
+    #[serde(default)]
+    pub include: Vec<GlobPattern>,
+    /// Patterns that exclude a file even if it matches `include`.
+    #[serde(default)]
+    pub exclude: Vec<GlobPattern>,
+}
+
+impl PackFileSelection {
+    /// Returns `true` if the file at pack-relative `path` should be loaded.
+    pub fn selects(&self, path: &str) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|g| g.matches(path));
+        included && !self.exclude.iter().any(|g| g.matches(path))
+    }
+}
+
+// ─── Packs ──────────────────────────────────────────────────────────────────
+
+/// The parts of a pack's trigger definition that govern loading.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextPackManifest {
+    /// The pack this manifest belongs to.
+    pub id: ContextPackId,
+    /// Pack-relative paths that must exist for the pack to be valid.
+    #[serde(default)]
+    pub required_files: Vec<String>,
+}
+
+/// The loaded content of one Context Pack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextPack {
+    /// The pack that was loaded.
+    pub id: ContextPackId,
+    /// Loaded files keyed by pack-relative path.
+    pub files: BTreeMap<String, String>,
+}
+
+impl ContextPack {
+    /// Returns one [`SystemLayer::Context`] segment per loaded file, in path
+    /// order, each headed `## Context Pack {id}: {path}` so the file can be
+    /// named if the segment is later dropped.
+    pub fn system_segments(&self) -> Vec<SystemSegment> {
+        self.files
+            .iter()
+            .map(|(path, content)| {
+                SystemSegment::new(
+                    SystemLayer::Context,
+                    format!("## Context Pack {}: {path}\n\n{content}", self.id),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Errors returned while loading a Context Pack.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ContextPackError {
+    /// A file the manifest declares as required does not exist.
+    #[error("context pack '{pack}' is missing required file '{path}'")]
+    MissingRequiredFile {
+        /// The pack with the missing file.
+        pack: ContextPackId,
+        /// Pack-relative path of the missing file.
+        path: String,
+    },
+
+    /// Reading the pack from the repository failed.
+    #[error("failed to read context pack '{pack}'")]
+    Repository {
+        /// The pack being read.
+        pack: ContextPackId,
+        /// The underlying GitHub error.
+        #[source]
+        source: GitHubOperationError,
+    },
+}
+
+#[cfg(test)]
+#[path = "context_tests.rs"]
+mod tests;
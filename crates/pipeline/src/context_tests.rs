@@ -0,0 +1,122 @@
+use super::*;
+
+fn glob(pattern: &str) -> GlobPattern {
+    GlobPattern::new(pattern).unwrap()
+}
+
+// ─── GlobPattern ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_new_empty_pattern_returns_none() {
+    assert_eq!(GlobPattern::new(""), None);
+}
+
+#[test]
+fn test_matches_star_stays_within_one_segment() {
+    let pattern = glob("anti-patterns/*.md");
+
+    assert!(pattern.matches("anti-patterns/locks.md"));
+    assert!(pattern.matches("anti-patterns/.md"));
+    assert!(!pattern.matches("anti-patterns/deep/locks.md"));
+    assert!(!pattern.matches("anti-patterns/locks.txt"));
+}
+
+#[test]
+fn test_matches_question_mark_matches_one_character() {
+    let pattern = glob("v?.md");
+
+    assert!(pattern.matches("v1.md"));
+    assert!(!pattern.matches("v10.md"));
+    assert!(!pattern.matches("v.md"));
+}
+
+#[test]
+fn test_matches_double_star_matches_zero_or_more_segments() {
+    let pattern = glob("**/locks.md");
+
+    assert!(pattern.matches("locks.md"));
+    assert!(pattern.matches("anti-patterns/locks.md"));
+    assert!(pattern.matches("a/b/c/locks.md"));
+    assert!(!pattern.matches("a/b/c/locks.mdx"));
+}
+
+#[test]
+fn test_matches_trailing_double_star_matches_directory_contents() {
+    let pattern = glob("safe-patterns/**");
+
+    assert!(pattern.matches("safe-patterns/select.md"));
+    assert!(pattern.matches("safe-patterns/nested/join.md"));
+    assert!(!pattern.matches("anti-patterns/select.md"));
+}
+
+#[test]
+fn test_matches_star_backtracks_over_repeated_characters() {
+    let pattern = glob("*ab*ab");
+
+    assert!(pattern.matches("xabyabab"));
+    assert!(!pattern.matches("xabyaba"));
+}
+
+#[test]
+fn test_matches_literal_pattern_requires_exact_path() {
+    let pattern = glob("README.md");
+
+    assert!(pattern.matches("README.md"));
+    assert!(!pattern.matches("docs/README.md"));
+}
+
+// ─── PackFileSelection ──────────────────────────────────────────────────────
+
+#[test]
+fn test_selects_default_selects_everything() {
+    assert!(PackFileSelection::default().selects("any/file.md"));
+}
+
+#[test]
+fn test_selects_include_restricts_to_matching_files() {
+    let selection = PackFileSelection {
+        include: vec![glob("anti-patterns/**")],
+        exclude: Vec::new(),
+    };
+
+    assert!(selection.selects("anti-patterns/locks.md"));
+    assert!(!selection.selects("README.md"));
+}
+
+#[test]
+fn test_selects_exclude_wins_over_include() {
+    let selection = PackFileSelection {
+        include: vec![glob("**/*.md")],
+        exclude: vec![glob("anti-patterns/locks.md")],
+    };
+
+    assert!(selection.selects("anti-patterns/blocking.md"));
+    assert!(!selection.selects("anti-patterns/locks.md"));
+}
+
+#[test]
+fn test_system_segments_one_context_segment_per_file_in_path_order() {
+    let pack = ContextPack {
+        id: ContextPackId::new("rust-async").unwrap(),
+        files: BTreeMap::from([
+            ("b.md".to_string(), "second".to_string()),
+            ("a.md".to_string(), "first".to_string()),
+        ]),
+    };
+
+    let segments = pack.system_segments();
+
+    assert_eq!(
+        segments,
+        vec![
+            SystemSegment::new(
+                SystemLayer::Context,
+                "## Context Pack rust-async: a.md\n\nfirst"
+            ),
+            SystemSegment::new(
+                SystemLayer::Context,
+                "## Context Pack rust-async: b.md\n\nsecond"
+            ),
+        ]
+    );
+}
@@ -0,0 +1,149 @@
+//! Run cost accounting broken down by spending category.
+//!
+//! [`CostLedger`] accumulates every [`TokenCost`] incurred during a run,
+//! tagged with the [`CostCategory`] that caused it. Reports can then show how
+//! the run's spend splits between doing the work (node execution), deciding
+//! where to go next (edge evaluation), and guarding the pipeline (injection
+//! checks).
+//!
+//! ## Budget Enforcement
+//!
+//! The run budget applies to the overall total, never to a single category.
+//! Costs are always recorded — the money has been spent — and
+//! [`CostLedger::record`] reports [`CogWorksError::BudgetExceeded`] once the
+//! total reaches the budget, matching [`CostBudget::is_exceeded_by`].
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/shared-types.md` §CostLedger.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CogWorksError, CostBudget, TokenCost};
+
+// ─── Category ───────────────────────────────────────────────────────────────
+
+/// What a unit of spend was for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum CostCategory {
+    /// LLM calls made by nodes while producing their outputs.
+    NodeExecution,
+    /// LLM calls made to evaluate natural-language edge conditions.
+    EdgeEvaluation,
+    /// LLM calls made by the injection guard on external content.
+    InjectionCheck,
+}
+
+impl CostCategory {
+    /// Every category, in report order.
+    pub const ALL: [CostCategory; 3] = [
+        CostCategory::NodeExecution,
+        CostCategory::EdgeEvaluation,
+        CostCategory::InjectionCheck,
+    ];
+
+    /// Human-readable label used in summaries.
+    pub fn label(self) -> &'static str {
+        match self {
+            CostCategory::NodeExecution => "Node execution",
+            CostCategory::EdgeEvaluation => "Edge evaluation",
+            CostCategory::InjectionCheck => "Injection checks",
+        }
+    }
+}
+
+// ─── Ledger ─────────────────────────────────────────────────────────────────
+
+/// Accumulated run cost with a per-category breakdown.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostLedger {
+    budget: CostBudget,
+    node_execution: TokenCost,
+    edge_evaluation: TokenCost,
+    injection_check: TokenCost,
+}
+
+impl CostLedger {
+    /// Creates an empty ledger enforcing `budget` on the overall total.
+    pub fn new(budget: CostBudget) -> Self {
+        Self {
+            budget,
+            node_execution: TokenCost::zero(),
+            edge_evaluation: TokenCost::zero(),
+            injection_check: TokenCost::zero(),
+        }
+    }
+
+    /// The budget enforced on the overall total.
+    pub fn budget(&self) -> CostBudget {
+        self.budget
+    }
+
+    /// Adds `cost` to `category` and checks the overall total.
+    ///
+    /// The cost is recorded even when the budget is exceeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CogWorksError::BudgetExceeded`] if the overall total now
+    /// equals or exceeds the budget.
+    pub fn record(&mut self, category: CostCategory, cost: TokenCost) -> Result<(), CogWorksError> {
+        *self.slot_mut(category) += cost;
+        self.check_budget()
+    }
+
+    /// Checks the overall total against the budget without recording
+    /// anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CogWorksError::BudgetExceeded`] if the overall total equals
+    /// or exceeds the budget.
+    pub fn check_budget(&self) -> Result<(), CogWorksError> {
+        let accumulated = self.total();
+        if self.budget.is_exceeded_by(accumulated) {
+            Err(CogWorksError::BudgetExceeded {
+                accumulated,
+                limit: self.budget,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Total recorded against `category`.
+    pub fn category_total(&self, category: CostCategory) -> TokenCost {
+        match category {
+            CostCategory::NodeExecution => self.node_execution,
+            CostCategory::EdgeEvaluation => self.edge_evaluation,
+            CostCategory::InjectionCheck => self.injection_check,
+        }
+    }
+
+    /// Per-category totals in [`CostCategory::ALL`] order, including zero
+    /// entries.
+    pub fn breakdown(&self) -> impl Iterator<Item = (CostCategory, TokenCost)> + '_ {
+        CostCategory::ALL
+            .into_iter()
+            .map(|category| (category, self.category_total(category)))
+    }
+
+    /// Overall total across every category.
+    pub fn total(&self) -> TokenCost {
+        self.node_execution + self.edge_evaluation + self.injection_check
+    }
+
+    fn slot_mut(&mut self, category: CostCategory) -> &mut TokenCost {
+        match category {
+            CostCategory::NodeExecution => &mut self.node_execution,
+            CostCategory::EdgeEvaluation => &mut self.edge_evaluation,
+            CostCategory::InjectionCheck => &mut self.injection_check,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "cost_tests.rs"]
+mod tests;
@@ -0,0 +1,116 @@
+use super::*;
+
+fn cost(usd: f64) -> TokenCost {
+    TokenCost::new(usd).unwrap()
+}
+
+fn ledger(limit: f64) -> CostLedger {
+    CostLedger::new(CostBudget::new(limit).unwrap())
+}
+
+#[test]
+fn test_record_costs_across_categories_reports_breakdown_and_total() {
+    let mut ledger = ledger(10.0);
+
+    ledger
+        .record(CostCategory::NodeExecution, cost(2.0))
+        .unwrap();
+    ledger
+        .record(CostCategory::EdgeEvaluation, cost(0.5))
+        .unwrap();
+    ledger
+        .record(CostCategory::NodeExecution, cost(1.0))
+        .unwrap();
+    ledger
+        .record(CostCategory::InjectionCheck, cost(0.25))
+        .unwrap();
+
+    assert_eq!(
+        ledger.breakdown().collect::<Vec<_>>(),
+        vec![
+            (CostCategory::NodeExecution, cost(3.0)),
+            (CostCategory::EdgeEvaluation, cost(0.5)),
+            (CostCategory::InjectionCheck, cost(0.25)),
+        ]
+    );
+    assert_eq!(ledger.total(), cost(3.75));
+}
+
+#[test]
+fn test_breakdown_empty_ledger_lists_every_category_at_zero() {
+    let ledger = ledger(1.0);
+
+    let breakdown: Vec<_> = ledger.breakdown().collect();
+
+    assert_eq!(breakdown.len(), CostCategory::ALL.len());
+    assert!(breakdown
+        .iter()
+        .all(|(_, total)| *total == TokenCost::zero()));
+    assert_eq!(ledger.total(), TokenCost::zero());
+}
+
+#[test]
+fn test_record_total_reaches_budget_returns_budget_exceeded() {
+    let mut ledger = ledger(1.0);
+    ledger
+        .record(CostCategory::NodeExecution, cost(0.75))
+        .unwrap();
+
+    let error = ledger
+        .record(CostCategory::EdgeEvaluation, cost(0.25))
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        CogWorksError::BudgetExceeded { accumulated, limit }
+            if accumulated == cost(1.0) && limit == CostBudget::new(1.0).unwrap()
+    ));
+}
+
+#[test]
+fn test_record_over_budget_still_records_cost() {
+    let mut ledger = ledger(1.0);
+
+    assert!(ledger
+        .record(CostCategory::InjectionCheck, cost(1.5))
+        .is_err());
+
+    assert_eq!(
+        ledger.category_total(CostCategory::InjectionCheck),
+        cost(1.5)
+    );
+    assert!(ledger.check_budget().is_err());
+}
+
+#[test]
+fn test_record_no_single_category_over_budget_enforces_overall_total() {
+    let mut ledger = ledger(1.0);
+    ledger
+        .record(CostCategory::NodeExecution, cost(0.5))
+        .unwrap();
+    ledger
+        .record(CostCategory::EdgeEvaluation, cost(0.25))
+        .unwrap();
+
+    let result = ledger.record(CostCategory::InjectionCheck, cost(0.5));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_check_budget_under_budget_returns_ok() {
+    let mut ledger = ledger(5.0);
+    ledger
+        .record(CostCategory::NodeExecution, cost(4.0))
+        .unwrap();
+
+    assert!(ledger.check_budget().is_ok());
+    assert_eq!(ledger.budget(), CostBudget::new(5.0).unwrap());
+}
+
+#[test]
+fn test_label_every_category_has_label() {
+    assert_eq!(CostCategory::NodeExecution.label(), "Node execution");
+    assert_eq!(CostCategory::EdgeEvaluation.label(), "Edge evaluation");
+    assert_eq!(CostCategory::InjectionCheck.label(), "Injection checks");
+}
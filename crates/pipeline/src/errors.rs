@@ -47,6 +47,120 @@ pub enum RetryPolicy {
     NonRetryable,
 }
 
+impl RetryPolicy {
+    /// Delay before retry number `attempt` (0 for the first retry).
+    ///
+    /// Returns `None` for [`RetryPolicy::NonRetryable`]. An explicit `after`
+    /// is used as given, clamped to `max`; otherwise the delay is
+    /// `base × 2^attempt`, capped at `max`.
+    pub fn backoff_for_attempt(
+        &self,
+        attempt: u32,
+        base: Duration,
+        max: Duration,
+    ) -> Option<Duration> {
+        match self {
+            RetryPolicy::NonRetryable => None,
+            RetryPolicy::Retryable { after: Some(after) } => Some((*after).min(max)),
+            RetryPolicy::Retryable { after: None } => {
+                let factor = 2u32.checked_pow(attempt).unwrap_or(u32::MAX);
+                Some(base.saturating_mul(factor).min(max))
+            }
+        }
+    }
+
+    /// [`backoff_for_attempt`](Self::backoff_for_attempt) with full jitter.
+    ///
+    /// The exponential delay is replaced by a uniformly chosen delay between
+    /// zero and that delay, using `rng` as the source of randomness, so many
+    /// callers failing together do not retry together. Pass a seeded
+    /// generator to get a repeatable sequence. An explicit `after` is not
+    /// jittered: the server asked for at least that long.
+    pub fn backoff_for_attempt_jittered(
+        &self,
+        attempt: u32,
+        base: Duration,
+        max: Duration,
+        rng: &mut dyn FnMut() -> u64,
+    ) -> Option<Duration> {
+        let delay = self.backoff_for_attempt(attempt, base, max)?;
+        if matches!(self, RetryPolicy::Retryable { after: Some(_) }) {
+            return Some(delay);
+        }
+        let ceiling = u64::try_from(delay.as_nanos()).unwrap_or(u64::MAX);
+        let jittered = match ceiling.checked_add(1) {
+            Some(span) => rng() % span,
+            None => rng(),
+        };
+        Some(Duration::from_nanos(jittered))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Halt reasons
+// ---------------------------------------------------------------------------
+
+/// Why the pipeline was halted by an explicit decision.
+///
+/// Carried by [`CogWorksError::PipelineHalt`] so halts can be counted and
+/// grouped across runs. The serialised form (`snake_case`) is part of the
+/// persisted audit format and must not change for existing variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum HaltReason {
+    /// A human reviewer rejected the output at a human-gated node.
+    HumanGateAbort,
+    /// The scope enforcer stopped work outside the approved specification.
+    ScopeEnforcer,
+    /// The injection guard found directive content in external input.
+    InjectionGuard,
+    /// A rework edge exceeded its `max_traversals` with
+    /// `OverflowBehaviour::HaltWithError`.
+    ReworkLimit,
+    /// The run or a node exceeded its wall-clock time budget.
+    TimeBudget,
+    /// The run spent its whole retry budget across its operations.
+    RetryBudget,
+    /// The run was cancelled by an operator or a `cogworks:cancel` label.
+    Cancelled,
+    /// The run's accumulated cost reached its cost budget.
+    CostBudget,
+    /// The run reached its cap on sub-work-item creation.
+    SubWorkItemLimit,
+}
+
+impl HaltReason {
+    /// Stable `snake_case` identifier, identical to the serialised form.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HaltReason::HumanGateAbort => "human_gate_abort",
+            HaltReason::ScopeEnforcer => "scope_enforcer",
+            HaltReason::InjectionGuard => "injection_guard",
+            HaltReason::ReworkLimit => "rework_limit",
+            HaltReason::TimeBudget => "time_budget",
+            HaltReason::RetryBudget => "retry_budget",
+            HaltReason::Cancelled => "cancelled",
+            HaltReason::CostBudget => "cost_budget",
+            HaltReason::SubWorkItemLimit => "sub_work_item_limit",
+        }
+    }
+}
+
+impl std::fmt::Display for HaltReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Formats an optional halt detail as a `": detail"` suffix.
+fn detail_suffix(detail: &Option<String>) -> String {
+    detail
+        .as_deref()
+        .map(|detail| format!(": {detail}"))
+        .unwrap_or_default()
+}
+
 // ---------------------------------------------------------------------------
 // Pipeline-level errors
 // ---------------------------------------------------------------------------
@@ -65,11 +179,16 @@ pub enum RetryPolicy {
 pub enum CogWorksError {
     /// The pipeline has been halted by an explicit decision (not a transient failure).
     ///
-    /// Produced by: scope enforcer, injection guard, human-gate abort.
-    #[error("Pipeline halted: {reason}")]
+    /// Produced by: scope enforcer, injection guard, human-gate abort, rework
+    /// limit overflow, time, retry and cost budgets, the sub-work-item cap,
+    /// cancellation. See [`HaltReason`].
+    #[error("Pipeline halted ({reason}){}", detail_suffix(.detail))]
     PipelineHalt {
-        /// Human-readable description of why the pipeline was halted.
-        reason: String,
+        /// Which component or rule halted the pipeline.
+        reason: HaltReason,
+        /// Optional human-readable context (e.g. the rejected node, the edge
+        /// that overflowed).
+        detail: Option<String>,
     },
 
     /// Accumulated token cost exceeded the configured budget before completion.
@@ -132,3 +251,39 @@ pub enum CogWorksError {
         message: String,
     },
 }
+
+impl CogWorksError {
+    /// Creates a [`CogWorksError::PipelineHalt`].
+    pub fn halt(reason: HaltReason, detail: impl Into<Option<String>>) -> Self {
+        Self::PipelineHalt {
+            reason,
+            detail: detail.into(),
+        }
+    }
+
+    /// Returns whether the condition may be retried.
+    ///
+    /// Always [`RetryPolicy::NonRetryable`]: every variant needs a human
+    /// before the pipeline can continue. The match lists each variant so a
+    /// new one must state its policy explicitly.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        match self {
+            Self::PipelineHalt { .. }
+            | Self::BudgetExceeded { .. }
+            | Self::InjectionDetected { .. }
+            | Self::ConstitutionalRulesMissing
+            | Self::ProtectedPathViolation { .. }
+            | Self::ScopeViolation { .. }
+            | Self::ConfigurationError { .. } => RetryPolicy::NonRetryable,
+        }
+    }
+
+    /// Returns `true` if the error ends the run rather than being retried.
+    pub fn is_terminal(&self) -> bool {
+        self.retry_policy() == RetryPolicy::NonRetryable
+    }
+}
+
+#[cfg(test)]
+#[path = "errors_tests.rs"]
+mod tests;
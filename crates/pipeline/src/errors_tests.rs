@@ -0,0 +1,292 @@
+use super::*;
+
+// ─── HaltReason ─────────────────────────────────────────────────────────────
+
+const ALL_HALT_REASONS: [HaltReason; 9] = [
+    HaltReason::HumanGateAbort,
+    HaltReason::ScopeEnforcer,
+    HaltReason::InjectionGuard,
+    HaltReason::ReworkLimit,
+    HaltReason::TimeBudget,
+    HaltReason::RetryBudget,
+    HaltReason::Cancelled,
+    HaltReason::CostBudget,
+    HaltReason::SubWorkItemLimit,
+];
+
+#[test]
+fn test_halt_reason_serialize_uses_stable_snake_case_names() {
+    let serialized: Vec<String> = ALL_HALT_REASONS
+        .iter()
+        .map(|reason| serde_json::to_string(reason).unwrap())
+        .collect();
+
+    assert_eq!(
+        serialized,
+        vec![
+            "\"human_gate_abort\"",
+            "\"scope_enforcer\"",
+            "\"injection_guard\"",
+            "\"rework_limit\"",
+            "\"time_budget\"",
+            "\"retry_budget\"",
+            "\"cancelled\"",
+            "\"cost_budget\"",
+            "\"sub_work_item_limit\"",
+        ]
+    );
+}
+
+#[test]
+fn test_halt_reason_as_str_matches_serialized_form() {
+    for reason in ALL_HALT_REASONS {
+        assert_eq!(
+            serde_json::to_value(reason).unwrap(),
+            serde_json::Value::String(reason.as_str().to_string())
+        );
+        assert_eq!(reason.to_string(), reason.as_str());
+    }
+}
+
+#[test]
+fn test_halt_reason_deserialize_round_trips() {
+    for reason in ALL_HALT_REASONS {
+        let json = serde_json::to_string(&reason).unwrap();
+
+        assert_eq!(serde_json::from_str::<HaltReason>(&json).unwrap(), reason);
+    }
+}
+
+// ─── CogWorksError::PipelineHalt ────────────────────────────────────────────
+
+#[test]
+fn test_halt_with_detail_carries_reason_and_detail() {
+    let error = CogWorksError::halt(
+        HaltReason::ReworkLimit,
+        "edge 'review-to-code' exceeded 3 traversals".to_string(),
+    );
+
+    assert!(matches!(
+        &error,
+        CogWorksError::PipelineHalt { reason: HaltReason::ReworkLimit, detail: Some(detail) }
+            if detail == "edge 'review-to-code' exceeded 3 traversals"
+    ));
+    assert_eq!(
+        error.to_string(),
+        "Pipeline halted (rework_limit): edge 'review-to-code' exceeded 3 traversals"
+    );
+}
+
+#[test]
+fn test_halt_without_detail_displays_reason_only() {
+    let error = CogWorksError::halt(HaltReason::Cancelled, None);
+
+    assert_eq!(error.to_string(), "Pipeline halted (cancelled)");
+}
+
+#[test]
+fn test_halt_any_reason_is_terminal() {
+    for reason in ALL_HALT_REASONS {
+        let error = CogWorksError::halt(reason, None);
+
+        assert_eq!(error.retry_policy(), RetryPolicy::NonRetryable);
+        assert!(error.is_terminal());
+    }
+}
+
+// ─── RetryPolicy back-off ───────────────────────────────────────────────────
+
+const BASE: Duration = Duration::from_millis(100);
+const MAX: Duration = Duration::from_secs(1);
+
+#[test]
+fn test_backoff_for_attempt_non_retryable_returns_none() {
+    assert_eq!(
+        RetryPolicy::NonRetryable.backoff_for_attempt(0, BASE, MAX),
+        None
+    );
+}
+
+#[test]
+fn test_backoff_for_attempt_without_after_doubles_per_attempt() {
+    let policy = RetryPolicy::Retryable { after: None };
+
+    let delays: Vec<_> = (0..3)
+        .map(|attempt| policy.backoff_for_attempt(attempt, BASE, MAX))
+        .collect();
+
+    assert_eq!(
+        delays,
+        vec![
+            Some(Duration::from_millis(100)),
+            Some(Duration::from_millis(200)),
+            Some(Duration::from_millis(400)),
+        ]
+    );
+}
+
+#[test]
+fn test_backoff_for_attempt_large_attempt_caps_at_max() {
+    let policy = RetryPolicy::Retryable { after: None };
+
+    assert_eq!(policy.backoff_for_attempt(40, BASE, MAX), Some(MAX));
+}
+
+#[test]
+fn test_backoff_for_attempt_explicit_after_used_as_given() {
+    let policy = RetryPolicy::Retryable {
+        after: Some(Duration::from_millis(50)),
+    };
+
+    assert_eq!(
+        policy.backoff_for_attempt(3, BASE, MAX),
+        Some(Duration::from_millis(50))
+    );
+}
+
+#[test]
+fn test_backoff_for_attempt_explicit_after_clamped_to_max() {
+    let policy = RetryPolicy::Retryable {
+        after: Some(Duration::from_secs(60)),
+    };
+
+    assert_eq!(policy.backoff_for_attempt(0, BASE, MAX), Some(MAX));
+}
+
+#[test]
+fn test_backoff_for_attempt_jittered_stays_within_exponential_delay() {
+    let policy = RetryPolicy::Retryable { after: None };
+    let mut state = 7u64;
+    let mut rng = move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1);
+        state
+    };
+
+    for attempt in 0..5 {
+        let ceiling = policy.backoff_for_attempt(attempt, BASE, MAX).unwrap();
+        let jittered = policy
+            .backoff_for_attempt_jittered(attempt, BASE, MAX, &mut rng)
+            .unwrap();
+        assert!(jittered <= ceiling, "{jittered:?} > {ceiling:?}");
+    }
+}
+
+#[test]
+fn test_backoff_for_attempt_jittered_uses_rng_modulo_delay() {
+    let policy = RetryPolicy::Retryable { after: None };
+    let span = u64::try_from(BASE.as_nanos()).unwrap() + 1;
+
+    let delay = policy.backoff_for_attempt_jittered(0, BASE, MAX, &mut || span + 5);
+
+    assert_eq!(delay, Some(Duration::from_nanos(5)));
+}
+
+#[test]
+fn test_backoff_for_attempt_jittered_same_seed_same_sequence() {
+    let policy = RetryPolicy::Retryable { after: None };
+    let sequence = || {
+        let mut next = 0u64;
+        let mut rng = move || {
+            next += 12_345_678;
+            next
+        };
+        (0..4)
+            .map(|attempt| policy.backoff_for_attempt_jittered(attempt, BASE, MAX, &mut rng))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(sequence(), sequence());
+}
+
+#[test]
+fn test_backoff_for_attempt_jittered_explicit_after_not_jittered() {
+    let after = Duration::from_millis(300);
+    let policy = RetryPolicy::Retryable { after: Some(after) };
+
+    let delay = policy.backoff_for_attempt_jittered(0, BASE, MAX, &mut || 1);
+
+    assert_eq!(delay, Some(after));
+}
+
+#[test]
+fn test_backoff_for_attempt_jittered_non_retryable_returns_none() {
+    let delay = RetryPolicy::NonRetryable.backoff_for_attempt_jittered(0, BASE, MAX, &mut || 0);
+
+    assert_eq!(delay, None);
+}
+
+#[test]
+fn test_backoff_for_attempt_first_retry_returns_base() {
+    let policy = RetryPolicy::Retryable { after: None };
+
+    assert_eq!(policy.backoff_for_attempt(0, BASE, MAX), Some(BASE));
+}
+
+#[test]
+fn test_backoff_for_attempt_max_below_base_returns_max() {
+    let policy = RetryPolicy::Retryable { after: None };
+    let max = Duration::from_millis(50);
+
+    assert_eq!(policy.backoff_for_attempt(0, BASE, max), Some(max));
+}
+
+#[test]
+fn test_backoff_for_attempt_jittered_zero_delay_returns_zero() {
+    let policy = RetryPolicy::Retryable { after: None };
+
+    let delay = policy.backoff_for_attempt_jittered(3, Duration::ZERO, MAX, &mut || u64::MAX);
+
+    assert_eq!(delay, Some(Duration::ZERO));
+}
+
+// ─── CogWorksError::retry_policy ────────────────────────────────────────────
+
+fn every_error_kind() -> Vec<CogWorksError> {
+    vec![
+        CogWorksError::halt(HaltReason::ScopeEnforcer, None),
+        CogWorksError::BudgetExceeded {
+            accumulated: TokenCost::new(12.0).unwrap(),
+            limit: CostBudget::new(10.0).unwrap(),
+        },
+        CogWorksError::InjectionDetected {
+            source_document: "issue body".to_string(),
+            offending_text: "ignore previous instructions".to_string(),
+        },
+        CogWorksError::ConstitutionalRulesMissing,
+        CogWorksError::ProtectedPathViolation {
+            path: ArtifactPath::new(".github/workflows/ci.yml").unwrap(),
+        },
+        CogWorksError::ScopeViolation {
+            description: "edited an unrelated module".to_string(),
+        },
+        CogWorksError::ConfigurationError {
+            message: "missing [llm] section".to_string(),
+        },
+    ]
+}
+
+#[test]
+fn test_retry_policy_every_variant_returns_non_retryable() {
+    for error in every_error_kind() {
+        assert_eq!(error.retry_policy(), RetryPolicy::NonRetryable, "{error:?}");
+    }
+}
+
+#[test]
+fn test_is_terminal_every_variant_returns_true() {
+    for error in every_error_kind() {
+        assert!(error.is_terminal(), "{error:?}");
+    }
+}
+
+#[test]
+fn test_retryable_error_impl_matches_inherent_retry_policy() {
+    for error in every_error_kind() {
+        assert_eq!(
+            crate::RetryableError::retry_policy(&error),
+            error.retry_policy()
+        );
+    }
+}
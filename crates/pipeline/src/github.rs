@@ -29,17 +29,18 @@
 //! [listener]: ../../listener/index.html
 
 use std::net::SocketAddr;
+use std::num::NonZeroUsize;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use thiserror::Error;
 
 use crate::{
-    BranchName, CommitSha, GitObjectSha, MilestoneId, PullRequestId, RepositoryId, SubWorkItemId,
-    WorkItemId,
+    ArtifactPath, BranchName, CommentId, CommitSha, GitObjectSha, InstallationId, MilestoneId,
+    PullRequestId, RepositoryId, RetryPolicy, SubWorkItemId, Timestamp, WorkItemId,
 };
 
 // ─── Event trigger abstraction ─────────────────────────────────────────────
@@ -65,6 +66,9 @@ pub enum GitHubEvent {
         work_item_id: WorkItemId,
         /// The exact label string that was applied (e.g. `"cogworks:run"`).
         label: String,
+        /// Installation and repository the event was delivered for.
+        #[serde(default)]
+        context: EventContext,
     },
 
     /// A comment was posted on a work-item issue.
@@ -78,6 +82,9 @@ pub enum GitHubEvent {
         author: String,
         /// Full text of the comment body.
         body: String,
+        /// Installation and repository the event was delivered for.
+        #[serde(default)]
+        context: EventContext,
     },
 
     /// The state of a sub-issue changed (open → closed or closed → reopened).
@@ -89,6 +96,9 @@ pub enum GitHubEvent {
         sub_work_item_id: SubWorkItemId,
         /// New state of the sub-issue.
         new_state: IssueState,
+        /// Installation and repository the event was delivered for.
+        #[serde(default)]
+        context: EventContext,
     },
 
     /// A pull-request review was submitted.
@@ -100,9 +110,56 @@ pub enum GitHubEvent {
         pr_id: PullRequestId,
         /// The reviewer's decision.
         decision: ReviewDecision,
+        /// Installation and repository the event was delivered for.
+        #[serde(default)]
+        context: EventContext,
     },
 }
 
+impl GitHubEvent {
+    /// Returns the installation and repository context of the event.
+    pub fn context(&self) -> &EventContext {
+        match self {
+            Self::LabelApplied { context, .. }
+            | Self::CommentPosted { context, .. }
+            | Self::SubIssueStateChanged { context, .. }
+            | Self::PullRequestReviewed { context, .. } => context,
+        }
+    }
+
+    /// Returns the GitHub App installation the event was delivered for, if known.
+    pub fn installation_id(&self) -> Option<InstallationId> {
+        self.context().installation_id
+    }
+
+    /// Returns the repository the event originated in, if known.
+    pub fn repository(&self) -> Option<&RepositoryId> {
+        self.context().repository.as_ref()
+    }
+}
+
+/// Delivery context shared by every [`GitHubEvent`] variant.
+///
+/// Extracted from the `installation` and `repository` objects of the webhook
+/// payload and from the delivery headers. Used to select the installation access token and to build the
+/// [`RepositoryId`] for API calls. Both fields are `None` for events that were
+/// not delivered by a GitHub App (e.g. synthesised by the single-shot CLI
+/// before it resolves the repository).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventContext {
+    /// The `installation.id` of the delivering GitHub App installation.
+    pub installation_id: Option<InstallationId>,
+    /// The `repository.full_name` of the source repository.
+    pub repository: Option<RepositoryId>,
+    /// The `X-GitHub-Delivery` GUID identifying this delivery.
+    ///
+    /// Redeliveries of the same event keep the same GUID, which makes it the
+    /// primary key for skipping events that were already applied.
+    pub delivery_id: Option<String>,
+    /// When the event source received the delivery.
+    pub delivered_at: Option<Timestamp>,
+}
+
 /// Errors that can be returned by an [`EventSource`] implementation.
 ///
 /// All variants except [`EventSourceError::Timeout`] indicate that the source
@@ -154,6 +211,30 @@ pub enum EventSourceError {
     },
 }
 
+/// Default number of received webhook events buffered ahead of the executor.
+pub const DEFAULT_EVENT_BUFFER_CAPACITY: NonZeroUsize = match NonZeroUsize::new(32) {
+    Some(capacity) => capacity,
+    None => unreachable!(),
+};
+
+fn default_event_buffer_capacity() -> NonZeroUsize {
+    DEFAULT_EVENT_BUFFER_CAPACITY
+}
+
+/// Path GitHub deliveries are accepted on when none is configured.
+pub const DEFAULT_WEBHOOK_PATH: &str = "/";
+
+/// Path of the liveness endpoint when none is configured.
+pub const DEFAULT_HEALTH_PATH: &str = "/healthz";
+
+fn default_webhook_path() -> String {
+    DEFAULT_WEBHOOK_PATH.to_string()
+}
+
+fn default_health_path() -> String {
+    DEFAULT_HEALTH_PATH.to_string()
+}
+
 /// Configuration for a GitHub-webhook-based [`EventSource`] implementation.
 ///
 /// Passed to `GitHubWebhookEventSource::new` in the `listener` crate.
@@ -167,9 +248,16 @@ pub struct WebhookConfig {
     /// (e.g. `"0.0.0.0:3000"`).
     pub bind_address: SocketAddr,
 
-    /// URL path prefix for the webhook endpoint (e.g. `"/hooks"`).
-    /// The full endpoint is `{bind_address}{path_prefix}/github`.
-    pub path_prefix: String,
+    /// Path on which webhook deliveries are accepted (e.g.
+    /// `"/github/webhook"` when mounted behind a path prefix). `POST`s to any
+    /// other path, except `health_path`, are answered with `404`. Also read
+    /// from `path_prefix`.
+    #[serde(default = "default_webhook_path", alias = "path_prefix")]
+    pub webhook_path: String,
+
+    /// Path of the liveness endpoint, independent of `webhook_path`.
+    #[serde(default = "default_health_path")]
+    pub health_path: String,
 
     /// HMAC-SHA256 secret used to verify the `X-Hub-Signature-256` header on
     /// every incoming webhook. Must match the secret configured in the GitHub
@@ -180,14 +268,32 @@ pub struct WebhookConfig {
     /// This field is intentionally excluded from the `Debug` impl to prevent
     /// accidental exposure in logs or tracing spans.
     pub secret: String,
+
+    /// Earlier secrets still accepted while a rotation is in progress. A
+    /// delivery signed with `secret` or any of these passes verification.
+    /// Excluded from the `Debug` impl like `secret`.
+    #[serde(default)]
+    pub previous_secrets: Vec<String>,
+
+    /// Maximum number of verified events held between the HTTP server and
+    /// the executor. When the buffer is full, further deliveries are answered
+    /// with `503 Service Unavailable` instead of being queued.
+    #[serde(default = "default_event_buffer_capacity")]
+    pub event_buffer_capacity: NonZeroUsize,
 }
 
 impl std::fmt::Debug for WebhookConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("WebhookConfig")
             .field("bind_address", &self.bind_address)
-            .field("path_prefix", &self.path_prefix)
+            .field("webhook_path", &self.webhook_path)
+            .field("health_path", &self.health_path)
+            .field("event_buffer_capacity", &self.event_buffer_capacity)
             .field("secret", &"[REDACTED]")
+            .field(
+                "previous_secrets",
+                &format_args!("[{} REDACTED]", self.previous_secrets.len()),
+            )
             .finish()
     }
 }
@@ -272,6 +378,26 @@ pub enum IssueState {
     Closed,
 }
 
+/// Why an issue was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueStateReason {
+    /// The work was done (the work item completed).
+    Completed,
+    /// The work will not be done (the work item was abandoned).
+    NotPlanned,
+}
+
+impl IssueStateReason {
+    /// The issues API `state_reason` value: `completed` or `not_planned`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IssueStateReason::Completed => "completed",
+            IssueStateReason::NotPlanned => "not_planned",
+        }
+    }
+}
+
 /// A GitHub label as seen by the pipeline domain.
 ///
 /// The pipeline only reads and applies labels; it never creates or deletes
@@ -297,6 +423,44 @@ pub struct Milestone {
     pub due_on: Option<DateTime<Utc>>,
 }
 
+/// A milestone with its due date and issue progress, for scheduling.
+///
+/// Read by `GithubClient::get_milestone_info` in the `github` crate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MilestoneInfo {
+    /// The repository-scoped milestone number.
+    pub id: MilestoneId,
+    /// The milestone title string.
+    pub title: String,
+    /// Due date, if one is set.
+    pub due_on: Option<Timestamp>,
+    /// Whether the milestone itself is open.
+    pub is_open: bool,
+    /// Open issues and pull requests in the milestone.
+    pub open_issues: u32,
+    /// Closed issues and pull requests in the milestone.
+    pub closed_issues: u32,
+}
+
+impl MilestoneInfo {
+    /// Fraction of the milestone's items that are closed, in `[0.0, 1.0]`.
+    ///
+    /// `0.0` for a milestone with no items.
+    pub fn progress(&self) -> f64 {
+        let total = u64::from(self.open_issues) + u64::from(self.closed_issues);
+        if total == 0 {
+            return 0.0;
+        }
+        self.closed_issues as f64 / total as f64
+    }
+
+    /// Returns `true` if the milestone is open and its due date is before
+    /// `now`.
+    pub fn is_overdue(&self, now: Timestamp) -> bool {
+        self.is_open && self.due_on.is_some_and(|due_on| due_on < now)
+    }
+}
+
 /// The kind of typed link between two work items.
 ///
 /// Mapped to/from the GitHub GraphQL `issueLink` type. CogWorks uses
@@ -350,6 +514,67 @@ pub struct Issue {
     pub updated_at: DateTime<Utc>,
 }
 
+/// State selector for [`IssueFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IssueStateFilter {
+    /// Include only open issues.
+    Open,
+    /// Include only closed issues.
+    Closed,
+    /// Include issues in all states.
+    All,
+}
+
+impl IssueStateFilter {
+    /// The issues API `state` query value: `open`, `closed`, or `all`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Closed => "closed",
+            Self::All => "all",
+        }
+    }
+}
+
+/// Parameters for [`IssueTracker::list_issues`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueFilter {
+    /// Lifecycle state filter. `None` is equivalent to
+    /// [`IssueStateFilter::Open`], the API default.
+    pub state: Option<IssueStateFilter>,
+    /// Restrict to issues carrying all of these labels.
+    pub labels: Vec<String>,
+    /// Restrict to issues updated at or after this time.
+    pub since: Option<Timestamp>,
+}
+
+/// The fields of a work-item issue needed for state reconstruction, read in
+/// one request.
+///
+/// Unlike [`Issue`], the milestone is carried by ID only and the assignees are
+/// included.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueSnapshot {
+    /// GitHub issue number.
+    pub id: WorkItemId,
+    /// Repository that contains the issue.
+    pub repository: RepositoryId,
+    /// Issue title.
+    pub title: String,
+    /// Issue body (Markdown); empty when the issue has no body.
+    pub body: String,
+    /// Current lifecycle state.
+    pub state: IssueState,
+    /// Labels currently applied to the issue.
+    pub labels: Vec<Label>,
+    /// Milestone the issue is assigned to, if any.
+    pub milestone: Option<MilestoneId>,
+    /// Logins of the assigned users.
+    pub assignees: Vec<String>,
+    /// When the issue was last updated (UTC).
+    pub updated_at: DateTime<Utc>,
+}
+
 /// A GitHub Issue that was created as a sub-task of a parent work item.
 ///
 /// Sub-issues are created by the Planning node; their state is monitored by the
@@ -368,6 +593,34 @@ pub struct SubIssue {
     pub created_at: DateTime<Utc>,
 }
 
+/// A comment on a work-item issue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueComment {
+    /// GitHub comment ID.
+    pub id: CommentId,
+    /// Login of the comment author.
+    pub author: String,
+    /// Comment body (Markdown).
+    pub body: String,
+    /// When the comment was created (UTC).
+    pub created_at: DateTime<Utc>,
+}
+
+/// Returns `body` with `marker` on its first line, unless `body` already
+/// contains `marker`.
+///
+/// Markers are HTML comments (e.g. `<!-- cogworks:run-summary -->`) that are
+/// invisible when rendered and let [`IssueTracker::upsert_comment`] find the
+/// comment again on a later run.
+#[must_use]
+pub fn with_comment_marker(marker: &str, body: &str) -> String {
+    if body.contains(marker) {
+        body.to_string()
+    } else {
+        format!("{marker}\n{body}")
+    }
+}
+
 /// Errors returned by [`IssueTracker`] operations.
 ///
 /// ## Specification
@@ -390,6 +643,20 @@ pub enum GitHubOperationError {
         action: String,
     },
 
+    /// The GitHub App installation lacks a permission the request needs.
+    ///
+    /// Distinguished from [`PermissionDenied`](Self::PermissionDenied) when
+    /// GitHub names the permission it expected. Fixed by granting the
+    /// permission to the App, not by retrying.
+    #[error("GitHub App lacks the '{permission}' permission needed to {action}")]
+    MissingAppPermission {
+        /// Description of the action that was denied.
+        action: String,
+        /// The permission GitHub expected, as reported in the
+        /// `x-accepted-github-permissions` header (e.g. `checks=write`).
+        permission: String,
+    },
+
     /// The GitHub API rate limit was exhausted.
     ///
     /// The `reset_at` field gives the UTC time when the limit resets; callers
@@ -416,6 +683,63 @@ pub enum GitHubOperationError {
         message: String,
     },
 
+    /// A response body exceeded the size cap set by the caller.
+    ///
+    /// Returned by the `github` crate's streaming readers, which stop reading
+    /// as soon as the cap is passed instead of buffering the whole body.
+    #[error("GitHub API response exceeded the {limit_bytes}-byte size cap")]
+    ResponseTooLarge {
+        /// The cap that was exceeded, in bytes.
+        limit_bytes: u64,
+    },
+
+    /// GitHub refused to open a pull request because `head` has no commits
+    /// that `base` does not already contain.
+    #[error("no commits between {base} and {head}")]
+    EmptyDiff {
+        /// The branch that would have been merged.
+        head: BranchName,
+        /// The branch it would have been merged into.
+        base: BranchName,
+    },
+
+    /// GitHub refused to open a pull request because one is already open for
+    /// the same head branch.
+    #[error("pull request #{existing} is already open for this branch")]
+    PullRequestAlreadyExists {
+        /// The open pull request.
+        existing: PullRequestId,
+    },
+
+    /// A signed commit was requested, or the branch requires one, and no
+    /// signed commit could be produced. Nothing is written to the branch.
+    /// Not retryable: configure a signing method or fix the signing key.
+    #[error("cannot produce a signed commit on {branch}: {reason}")]
+    CommitSigningUnavailable {
+        /// The branch the commit was for.
+        branch: BranchName,
+        /// Why no signed commit was produced.
+        reason: String,
+    },
+
+    /// Auto-merge was requested on a repository whose settings do not allow
+    /// it. Not retryable: a repository admin must enable "Allow auto-merge".
+    #[error("auto-merge is not allowed in {repository}")]
+    AutoMergeDisabled {
+        /// The repository whose settings disallow auto-merge.
+        repository: RepositoryId,
+    },
+
+    /// A paginated listing linked more pages than the caller's cap.
+    ///
+    /// Guards against runaway pagination; narrow the listing's filter or
+    /// raise the cap.
+    #[error("GitHub listing exceeded the {max_pages}-page cap")]
+    PaginationLimitExceeded {
+        /// The page cap that was reached.
+        max_pages: u32,
+    },
+
     /// An operation that requires a pending SDK addition was called.
     ///
     /// Produced by `todo!()` stubs until `github-bot-sdk` gains the required
@@ -427,6 +751,33 @@ pub enum GitHubOperationError {
     },
 }
 
+impl GitHubOperationError {
+    /// Returns whether the failed operation may be retried.
+    ///
+    /// Transient errors may be retried at once; an exhausted rate limit only
+    /// after `reset_at`.
+    #[must_use]
+    pub fn retry_policy(&self) -> RetryPolicy {
+        match self {
+            Self::Transient { .. } => RetryPolicy::Retryable { after: None },
+            Self::RateLimitExhausted { reset_at } => RetryPolicy::Retryable {
+                after: Some((*reset_at - Utc::now()).to_std().unwrap_or_default()),
+            },
+            Self::NotFound { .. }
+            | Self::PermissionDenied { .. }
+            | Self::MissingAppPermission { .. }
+            | Self::ParseFailure { .. }
+            | Self::ResponseTooLarge { .. }
+            | Self::PaginationLimitExceeded { .. }
+            | Self::EmptyDiff { .. }
+            | Self::PullRequestAlreadyExists { .. }
+            | Self::CommitSigningUnavailable { .. }
+            | Self::AutoMergeDisabled { .. }
+            | Self::SdkCapabilityMissing { .. } => RetryPolicy::NonRetryable,
+        }
+    }
+}
+
 /// GitHub Issues API — the operations the pipeline domain needs to read and
 /// update work items, sub-issues, labels, comments, and milestones.
 ///
@@ -569,6 +920,63 @@ pub trait IssueTracker: Send + Sync {
     /// - [`GitHubOperationError::PermissionDenied`] — insufficient write access.
     async fn post_comment(&self, id: WorkItemId, body: &str) -> Result<(), GitHubOperationError>;
 
+    /// List all comments on an issue, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — issue does not exist.
+    /// - [`GitHubOperationError::Transient`] — transient network failure.
+    async fn list_comments(
+        &self,
+        id: WorkItemId,
+    ) -> Result<Vec<IssueComment>, GitHubOperationError>;
+
+    /// Replace the body of an existing comment.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — comment does not exist.
+    /// - [`GitHubOperationError::PermissionDenied`] — insufficient write access.
+    async fn update_comment(
+        &self,
+        comment: CommentId,
+        body: &str,
+    ) -> Result<(), GitHubOperationError>;
+
+    /// Create or update the comment on `id` identified by `marker`.
+    ///
+    /// The first comment whose body contains `marker` is updated in place;
+    /// if none exists a new comment is posted. The written body always
+    /// carries the marker (see [`with_comment_marker`]), so repeated calls
+    /// keep editing the same comment instead of adding new ones.
+    ///
+    /// The default implementation is built on [`IssueTracker::list_comments`],
+    /// [`IssueTracker::update_comment`], and [`IssueTracker::post_comment`].
+    /// No write is made when the existing comment already has the same body.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the underlying list, update, or post call.
+    async fn upsert_comment(
+        &self,
+        id: WorkItemId,
+        marker: &str,
+        body: &str,
+    ) -> Result<(), GitHubOperationError> {
+        let body = with_comment_marker(marker, body);
+        let existing = self
+            .list_comments(id)
+            .await?
+            .into_iter()
+            .find(|comment| comment.body.contains(marker));
+
+        match existing {
+            Some(comment) if comment.body == body => Ok(()),
+            Some(comment) => self.update_comment(comment.id, &body).await,
+            None => self.post_comment(id, &body).await,
+        }
+    }
+
     /// Return the current lifecycle state of an issue without fetching all fields.
     ///
     /// Cheaper than [`IssueTracker::get_issue`] when only the open/closed state
@@ -579,6 +987,41 @@ pub trait IssueTracker: Send + Sync {
     /// - [`GitHubOperationError::NotFound`] — issue does not exist.
     async fn get_issue_state(&self, id: WorkItemId) -> Result<IssueState, GitHubOperationError>;
 
+    /// Close or reopen an issue.
+    ///
+    /// Closing records `reason` (completed or abandoned) on the issue;
+    /// `reason` is ignored when reopening, which GitHub records as
+    /// `reopened`. Setting the state the issue is already in is a no-op apart
+    /// from updating the close reason.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — issue does not exist.
+    /// - [`GitHubOperationError::PermissionDenied`] — insufficient write access.
+    async fn set_issue_state(
+        &self,
+        id: WorkItemId,
+        state: IssueState,
+        reason: IssueStateReason,
+    ) -> Result<(), GitHubOperationError>;
+
+    /// List the issues of `repository` that match `filter`.
+    ///
+    /// Every page of the listing is read; pull requests, which the issues
+    /// endpoint also returns, are left out.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — repository does not exist.
+    /// - [`GitHubOperationError::PaginationLimitExceeded`] — the listing has
+    ///   more pages than the client's cap.
+    /// - [`GitHubOperationError::RateLimitExhausted`] — retry after `reset_at`.
+    async fn list_issues(
+        &self,
+        repository: &RepositoryId,
+        filter: &IssueFilter,
+    ) -> Result<Vec<Issue>, GitHubOperationError>;
+
     /// Fetch a milestone by its numeric ID.
     ///
     /// # Errors
@@ -654,6 +1097,8 @@ pub struct PullRequest {
     pub title: String,
     /// PR body in Markdown.
     pub body: String,
+    /// Login of the account that opened the PR.
+    pub author: String,
     /// The branch being merged.
     pub head_branch: BranchName,
     /// The target branch (base).
@@ -692,6 +1137,108 @@ pub struct PullRequestFilter {
     pub state: Option<PullRequestStateFilter>,
 }
 
+/// How a file was changed by a pull request.
+///
+/// Serialised with GitHub's `status` values (`added`, `removed`, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChangeStatus {
+    /// The file is new.
+    Added,
+    /// The file was deleted.
+    Removed,
+    /// The file's contents changed.
+    Modified,
+    /// The file was moved; see [`ChangedFile::previous_path`].
+    Renamed,
+    /// The file was copied from another path.
+    Copied,
+    /// The file's mode or type changed without a content change.
+    Changed,
+    /// The file is listed but not changed.
+    Unchanged,
+}
+
+/// One file changed by a pull request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangedFile {
+    /// Path of the file after the change.
+    pub path: ArtifactPath,
+    /// How the file was changed.
+    pub status: FileChangeStatus,
+    /// Lines added.
+    pub additions: u32,
+    /// Lines deleted.
+    pub deletions: u32,
+    /// Path before the change, for [`FileChangeStatus::Renamed`] and
+    /// [`FileChangeStatus::Copied`].
+    pub previous_path: Option<ArtifactPath>,
+}
+
+/// State of a commit status, for one context or combined over all of them.
+///
+/// Serialised with GitHub's `state` values (`pending`, `success`, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitState {
+    /// Checks are still running, or none have reported yet.
+    Pending,
+    /// Every check passed.
+    Success,
+    /// A check failed.
+    Failure,
+    /// A check could not run.
+    Error,
+}
+
+impl CommitState {
+    /// Returns `true` for a final state (anything but [`CommitState::Pending`]).
+    pub fn is_final(self) -> bool {
+        !matches!(self, Self::Pending)
+    }
+}
+
+/// The status one CI context reported for a commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextStatus {
+    /// Context name (e.g. `"ci/build"`).
+    pub context: String,
+    /// State the context reported.
+    pub state: CommitState,
+    /// Short description from the reporter, if any.
+    pub description: Option<String>,
+    /// Link to the run's details, if any.
+    pub target_url: Option<String>,
+}
+
+/// Combined and per-context CI status of a commit.
+///
+/// Read by `GithubClient::get_commit_status` in the `github` crate; the
+/// executor waits on it before merging CogWorks' commits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusSummary {
+    /// The commit the statuses were reported for.
+    pub sha: CommitSha,
+    /// GitHub's combined state over all contexts.
+    pub state: CommitState,
+    /// The latest status of each context.
+    pub contexts: Vec<ContextStatus>,
+}
+
+impl StatusSummary {
+    /// Returns `true` if CI passed.
+    pub fn is_success(&self) -> bool {
+        self.state == CommitState::Success
+    }
+
+    /// Returns the contexts that failed or errored.
+    pub fn failed_contexts(&self) -> impl Iterator<Item = &ContextStatus> {
+        self.contexts
+            .iter()
+            .filter(|context| matches!(context.state, CommitState::Failure | CommitState::Error))
+    }
+}
+
 /// GitHub Pull Request API — operations the pipeline domain needs for PR
 /// lifecycle management and review gating.
 ///
@@ -716,9 +1263,15 @@ pub trait PullRequestManager: Send + Sync {
     /// * `body` — pull request body in Markdown.
     /// * `head` — the branch containing the changes.
     /// * `base` — the target branch to merge into.
+    /// * `draft` — open the PR as a draft, which cannot be merged until it is
+    ///   marked ready for review.
     ///
     /// # Errors
     ///
+    /// - [`GitHubOperationError::EmptyDiff`] — `head` has no commits over
+    ///   `base`; there is nothing to open a PR for.
+    /// - [`GitHubOperationError::PullRequestAlreadyExists`] — an open PR from
+    ///   `head` already exists; carries its number.
     /// - [`GitHubOperationError::PermissionDenied`] — insufficient write access.
     /// - [`GitHubOperationError::Transient`] — transient network failure.
     async fn create_pull_request(
@@ -728,6 +1281,7 @@ pub trait PullRequestManager: Send + Sync {
         body: &str,
         head: &BranchName,
         base: &BranchName,
+        draft: bool,
     ) -> Result<PullRequest, GitHubOperationError>;
 
     /// Fetch a pull request by its numeric ID.
@@ -792,6 +1346,119 @@ pub trait PullRequestManager: Send + Sync {
         repository: &RepositoryId,
         id: PullRequestId,
     ) -> Result<ReviewStatus, GitHubOperationError>;
+
+    /// Replace the body of an existing pull request.
+    ///
+    /// Used to record cross-references (e.g. closing keywords for the work
+    /// item) after the PR has been created.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` — the repository containing the PR.
+    /// * `id` — the pull request to update.
+    /// * `body` — the new PR body in Markdown. Replaces the existing body.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — pull request does not exist.
+    /// - [`GitHubOperationError::PermissionDenied`] — insufficient write access.
+    async fn update_pull_request_body(
+        &self,
+        repository: &RepositoryId,
+        id: PullRequestId,
+        body: &str,
+    ) -> Result<(), GitHubOperationError>;
+
+    /// List every file changed by a pull request, across all result pages.
+    ///
+    /// Files are returned in the order GitHub lists them. GitHub lists at
+    /// most 3000 files per pull request.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — pull request does not exist.
+    /// - [`GitHubOperationError::ParseFailure`] — a page has an unexpected
+    ///   shape or an unknown change status.
+    async fn list_pr_files(
+        &self,
+        repository: &RepositoryId,
+        id: PullRequestId,
+    ) -> Result<Vec<ChangedFile>, GitHubOperationError>;
+}
+
+// ─── Deployment environment data types ─────────────────────────────────────
+
+/// Deployment protection rules configured on a repository environment.
+///
+/// A merge that deploys into a protected environment waits for reviewers or a
+/// timer outside CogWorks' control. The Integration node checks
+/// [`EnvironmentProtection::is_protected`] and holds the work item for a human
+/// instead of merging into a gated flow.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentProtection {
+    /// Users (login) and teams (slug) that must approve a deployment.
+    pub required_reviewers: Vec<String>,
+    /// Delay applied before a deployment may proceed.
+    pub wait_timer: Option<Duration>,
+}
+
+impl EnvironmentProtection {
+    /// Returns `true` if a deployment must wait for a reviewer or a timer.
+    pub fn is_protected(&self) -> bool {
+        !self.required_reviewers.is_empty() || self.wait_timer.is_some_and(|t| !t.is_zero())
+    }
+}
+
+// ─── Merge readiness data types ────────────────────────────────────────────
+
+/// GitHub's `mergeable_state` for a pull request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum MergeableState {
+    /// No conflicts and every requirement is met.
+    Clean,
+    /// Mergeable; non-required status checks are failing.
+    Unstable,
+    /// Mergeable; pre-receive hooks will run on merge.
+    HasHooks,
+    /// The head branch is behind the base branch.
+    Behind,
+    /// Blocked by branch protection (reviews or required checks).
+    Blocked,
+    /// The merge has conflicts.
+    Dirty,
+    /// The pull request is a draft.
+    Draft,
+    /// GitHub has not finished computing mergeability.
+    Unknown,
+}
+
+/// Whether a pull request can be merged, as computed by GitHub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mergeability {
+    /// GitHub's `mergeable` flag; `false` while unresolved.
+    pub mergeable: bool,
+    /// GitHub's `mergeable_state`.
+    pub state: MergeableState,
+}
+
+impl Mergeability {
+    /// Returns `true` if GitHub has finished computing mergeability.
+    pub fn is_resolved(&self) -> bool {
+        self.state != MergeableState::Unknown
+    }
+
+    /// Returns `true` if the Integration node may merge now: the PR is
+    /// mergeable and its state is [`MergeableState::Clean`],
+    /// [`MergeableState::Unstable`], or [`MergeableState::HasHooks`].
+    pub fn is_ready(&self) -> bool {
+        self.mergeable
+            && matches!(
+                self.state,
+                MergeableState::Clean | MergeableState::Unstable | MergeableState::HasHooks
+            )
+    }
 }
 
 // ─── Code repository data types ────────────────────────────────────────────
@@ -937,6 +1604,40 @@ pub trait CodeRepository: Send + Sync {
 
 // ─── Project board synchronisation ─────────────────────────────────────────
 
+/// A typed value for a Projects V2 custom field.
+///
+/// Single-select and iteration values are given by the option or iteration
+/// name as shown on the board; the implementation resolves them to IDs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum ProjectFieldValue {
+    /// A text field.
+    Text(String),
+    /// A number field.
+    Number(f64),
+    /// A date field.
+    Date(NaiveDate),
+    /// A single-select field, by option name.
+    SingleSelect(String),
+    /// An iteration field, by iteration title.
+    Iteration(String),
+}
+
+impl ProjectFieldValue {
+    /// Returns the value as the JSON scalar accepted by
+    /// [`ProjectBoard::sync_custom_field`].
+    #[must_use]
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            Self::Text(text) | Self::SingleSelect(text) | Self::Iteration(text) => {
+                JsonValue::from(text.as_str())
+            }
+            Self::Number(number) => JsonValue::from(*number),
+            Self::Date(date) => JsonValue::from(date.to_string()),
+        }
+    }
+}
+
 /// GitHub Projects V2 — status and custom-field synchronisation.
 ///
 /// All methods are best-effort and non-blocking with respect to pipeline
@@ -985,4 +1686,38 @@ pub trait ProjectBoard: Send + Sync {
         field_name: &str,
         value: &JsonValue,
     ) -> Result<(), GitHubOperationError>;
+
+    /// Update several custom Projects V2 fields of a work-item card at once.
+    ///
+    /// The default implementation calls
+    /// [`ProjectBoard::sync_custom_field`] once per field and stops at the
+    /// first failure. Implementations that can batch (e.g. one GraphQL
+    /// mutation) should override it.
+    ///
+    /// # Arguments
+    ///
+    /// * `work_item_id` — the issue to update.
+    /// * `fields` — `(field name, value)` pairs, applied in order.
+    ///
+    /// # Errors
+    ///
+    /// - [`GitHubOperationError::NotFound`] — item, field, or option not found.
+    /// - [`GitHubOperationError::RateLimitExhausted`] — the GraphQL budget is
+    ///   spent; retry after the reset.
+    /// - [`GitHubOperationError::Transient`] — transient network failure.
+    async fn set_item_fields(
+        &self,
+        work_item_id: WorkItemId,
+        fields: &[(String, ProjectFieldValue)],
+    ) -> Result<(), GitHubOperationError> {
+        for (field_name, value) in fields {
+            self.sync_custom_field(work_item_id, field_name, &value.to_json())
+                .await?;
+        }
+        Ok(())
+    }
 }
+
+#[cfg(test)]
+#[path = "github_tests.rs"]
+mod tests;
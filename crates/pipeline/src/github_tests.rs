@@ -0,0 +1,197 @@
+use super::*;
+
+fn context() -> EventContext {
+    EventContext {
+        installation_id: Some(InstallationId::new(12345678)),
+        repository: RepositoryId::parse("octo/widgets"),
+        delivery_id: Some("72d3162e-cc78-11e3-81ab-4c9367dc0958".to_string()),
+        delivered_at: None,
+    }
+}
+
+// ─── GitHubEvent ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_github_event_accessors_each_variant_return_context() {
+    let events = [
+        GitHubEvent::LabelApplied {
+            work_item_id: WorkItemId::new(42),
+            label: "cogworks:run".to_string(),
+            context: context(),
+        },
+        GitHubEvent::CommentPosted {
+            work_item_id: WorkItemId::new(42),
+            author: "octocat".to_string(),
+            body: "/cogworks approve".to_string(),
+            context: context(),
+        },
+        GitHubEvent::SubIssueStateChanged {
+            sub_work_item_id: SubWorkItemId::new(43),
+            new_state: IssueState::Closed,
+            context: context(),
+        },
+        GitHubEvent::PullRequestReviewed {
+            pr_id: PullRequestId::new(57),
+            decision: ReviewDecision::Approved,
+            context: context(),
+        },
+    ];
+
+    for event in events {
+        assert_eq!(event.context(), &context());
+        assert_eq!(event.installation_id(), Some(InstallationId::new(12345678)));
+        assert_eq!(
+            event.repository(),
+            RepositoryId::parse("octo/widgets").as_ref()
+        );
+    }
+}
+
+#[test]
+fn test_github_event_deserialize_without_context_defaults_to_empty() {
+    let event: GitHubEvent =
+        serde_json::from_str(r#"{"LabelApplied":{"work_item_id":42,"label":"cogworks:run"}}"#)
+            .unwrap();
+
+    assert_eq!(event.context(), &EventContext::default());
+    assert_eq!(event.installation_id(), None);
+    assert_eq!(event.repository(), None);
+}
+
+#[test]
+fn test_github_event_serde_round_trip_keeps_context() {
+    let event = GitHubEvent::LabelApplied {
+        work_item_id: WorkItemId::new(42),
+        label: "cogworks:run".to_string(),
+        context: context(),
+    };
+
+    let json = serde_json::to_string(&event).unwrap();
+    let parsed: GitHubEvent = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(parsed, event);
+}
+
+// ─── MilestoneInfo ──────────────────────────────────────────────────────────
+
+fn milestone(open_issues: u32, closed_issues: u32, due_on: Option<&str>) -> MilestoneInfo {
+    MilestoneInfo {
+        id: MilestoneId::new(3),
+        title: "v1.0".to_string(),
+        due_on: due_on.and_then(Timestamp::parse_rfc3339),
+        is_open: true,
+        open_issues,
+        closed_issues,
+    }
+}
+
+#[test]
+fn test_milestone_progress_partly_closed_returns_closed_fraction() {
+    assert_eq!(milestone(1, 3, None).progress(), 0.75);
+}
+
+#[test]
+fn test_milestone_progress_no_items_returns_zero() {
+    assert_eq!(milestone(0, 0, None).progress(), 0.0);
+}
+
+#[test]
+fn test_milestone_is_overdue_past_due_date_returns_true() {
+    let now = Timestamp::parse_rfc3339("2026-10-10T00:00:00Z").unwrap();
+
+    assert!(milestone(2, 0, Some("2026-10-09T07:00:00Z")).is_overdue(now));
+}
+
+#[test]
+fn test_milestone_is_overdue_closed_or_undated_returns_false() {
+    let now = Timestamp::parse_rfc3339("2026-10-10T00:00:00Z").unwrap();
+    let mut closed = milestone(0, 2, Some("2026-10-09T07:00:00Z"));
+    closed.is_open = false;
+
+    assert!(!closed.is_overdue(now));
+    assert!(!milestone(2, 0, None).is_overdue(now));
+    assert!(!milestone(2, 0, Some("2026-10-11T00:00:00Z")).is_overdue(now));
+}
+
+// ─── StatusSummary ──────────────────────────────────────────────────────────
+
+fn context_status(context: &str, state: CommitState) -> ContextStatus {
+    ContextStatus {
+        context: context.to_string(),
+        state,
+        description: None,
+        target_url: None,
+    }
+}
+
+fn status_summary(state: CommitState, contexts: Vec<ContextStatus>) -> StatusSummary {
+    StatusSummary {
+        sha: CommitSha::parse("6dcb09b5b57875f334f61aebed695e2e4193db5e").unwrap(),
+        state,
+        contexts,
+    }
+}
+
+#[test]
+fn test_commit_state_is_final_pending_returns_false() {
+    assert!(!CommitState::Pending.is_final());
+    assert!(CommitState::Success.is_final());
+    assert!(CommitState::Failure.is_final());
+    assert!(CommitState::Error.is_final());
+}
+
+#[test]
+fn test_status_summary_is_success_only_for_success_state() {
+    assert!(status_summary(CommitState::Success, Vec::new()).is_success());
+    assert!(!status_summary(CommitState::Pending, Vec::new()).is_success());
+}
+
+#[test]
+fn test_status_summary_failed_contexts_returns_failure_and_error_only() {
+    let summary = status_summary(
+        CommitState::Failure,
+        vec![
+            context_status("ci/build", CommitState::Success),
+            context_status("ci/test", CommitState::Failure),
+            context_status("ci/lint", CommitState::Error),
+            context_status("ci/deploy", CommitState::Pending),
+        ],
+    );
+
+    let failed: Vec<_> = summary
+        .failed_contexts()
+        .map(|context| context.context.as_str())
+        .collect();
+    assert_eq!(failed, vec!["ci/test", "ci/lint"]);
+}
+
+// ─── WebhookConfig ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_webhook_config_deserialize_webhook_path_sets_path() {
+    let config: WebhookConfig = serde_json::from_str(
+        r#"{"bind_address":"127.0.0.1:3000","secret":"s","webhook_path":"/github/webhook"}"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.webhook_path, "/github/webhook");
+    assert_eq!(config.health_path, DEFAULT_HEALTH_PATH);
+}
+
+#[test]
+fn test_webhook_config_deserialize_path_prefix_alias_sets_webhook_path() {
+    let config: WebhookConfig = serde_json::from_str(
+        r#"{"bind_address":"127.0.0.1:3000","secret":"s","path_prefix":"/github/webhook"}"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.webhook_path, "/github/webhook");
+}
+
+#[test]
+fn test_webhook_config_deserialize_without_path_uses_default() {
+    let config: WebhookConfig =
+        serde_json::from_str(r#"{"bind_address":"127.0.0.1:3000","secret":"s"}"#).unwrap();
+
+    assert_eq!(config.webhook_path, DEFAULT_WEBHOOK_PATH);
+}
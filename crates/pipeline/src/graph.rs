@@ -14,7 +14,7 @@
 //!
 //! See `docs/spec/interfaces/pipeline-graph.md` for the full contract.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
@@ -168,6 +168,20 @@ pub struct NodeDefinition {
     pub validation_kind: ValidationKind,
     /// When `true`, failure of this node cancels all concurrently active siblings.
     pub abort_siblings_on_failure: bool,
+    /// Execution priority among nodes that become ready at the same time.
+    ///
+    /// Higher values run first; nodes with equal priority keep their ready
+    /// order. Give cheap, fast nodes a higher priority so failures surface
+    /// early. Defaults to `0`.
+    #[serde(default)]
+    pub priority: i32,
+    /// Model used for this node's LLM calls (e.g. a cheap model for triage,
+    /// a strong one for planning).
+    ///
+    /// `None` uses [`PipelineSettings::default_model`]; see
+    /// [`PipelineGraph::model_for`].
+    #[serde(default)]
+    pub model: Option<String>,
 }
 
 /// A composite edge condition combining inner conditions with boolean logic.
@@ -264,6 +278,11 @@ pub struct PipelineSettings {
     pub default_cost_budget: Option<CostBudget>,
     /// Maximum retries for any node before the pipeline escalates.
     pub max_node_retries: u32,
+    /// Model used by nodes without their own `model`.
+    ///
+    /// `None` leaves the model chosen by the run's LLM configuration.
+    #[serde(default)]
+    pub default_model: Option<String>,
 }
 
 /// A complete, validated pipeline graph with all structural metadata.
@@ -303,6 +322,163 @@ pub struct PipelineGraph {
     pub tool_profiles: PipelineToolProfileConfig,
 }
 
+impl PipelineGraph {
+    /// Returns the model configured for `node`.
+    ///
+    /// The node's own [`NodeDefinition::model`] wins, then
+    /// [`PipelineSettings::default_model`]. `None` means neither is set and
+    /// the run's default model applies. Nodes not in the graph get the
+    /// pipeline default.
+    pub fn model_for(&self, node: &NodeId) -> Option<&str> {
+        self.nodes
+            .iter()
+            .find(|definition| &definition.id == node)
+            .and_then(|definition| definition.model.as_deref())
+            .or(self.settings.default_model.as_deref())
+    }
+
+    /// Compares this graph with `other`, a replacement for it, and reports
+    /// whether runs started under this graph can continue under `other`.
+    ///
+    /// Runs record node state by [`NodeId`] and rework traversals by
+    /// [`EdgeId`], so a replacement is compatible when every node and edge a
+    /// run may reference still exists and every edge still joins the same
+    /// nodes with no lower traversal limit. Added nodes and edges are always
+    /// compatible. Lists in the report follow declaration order.
+    pub fn is_compatible_with(&self, other: &PipelineGraph) -> CompatibilityReport {
+        let node_ids = |graph: &PipelineGraph| -> HashSet<NodeId> {
+            graph.nodes.iter().map(|node| node.id.clone()).collect()
+        };
+        let (ours, theirs) = (node_ids(self), node_ids(other));
+        fn find_edge<'a>(graph: &'a PipelineGraph, id: &EdgeId) -> Option<&'a EdgeDefinition> {
+            graph.edges.iter().find(|edge| &edge.id == id)
+        }
+
+        let mut report = CompatibilityReport {
+            added_nodes: other
+                .nodes
+                .iter()
+                .filter(|node| !ours.contains(&node.id))
+                .map(|node| node.id.clone())
+                .collect(),
+            removed_nodes: self
+                .nodes
+                .iter()
+                .filter(|node| !theirs.contains(&node.id))
+                .map(|node| node.id.clone())
+                .collect(),
+            added_edges: other
+                .edges
+                .iter()
+                .filter(|edge| find_edge(self, &edge.id).is_none())
+                .map(|edge| edge.id.clone())
+                .collect(),
+            ..CompatibilityReport::default()
+        };
+
+        for edge in &self.edges {
+            let Some(replacement) = find_edge(other, &edge.id) else {
+                report.removed_edges.push(edge.id.clone());
+                continue;
+            };
+            if replacement.source != edge.source || replacement.target != edge.target {
+                report.rerouted_edges.push(EdgeRerouting {
+                    edge: edge.id.clone(),
+                    from: (edge.source.clone(), edge.target.clone()),
+                    to: (replacement.source.clone(), replacement.target.clone()),
+                });
+            }
+            let limit = |edge: &EdgeDefinition| edge.rework_edge.as_ref().map(|r| r.max_traversals);
+            let (before, after) = (limit(edge), limit(replacement));
+            if before != after {
+                report.rework_limit_changes.push(ReworkLimitChange {
+                    edge: edge.id.clone(),
+                    before,
+                    after,
+                });
+            }
+        }
+
+        report
+    }
+}
+
+// ─── Compatibility ─────────────────────────────────────────────────────────
+
+/// The differences between a pipeline graph and its replacement, from
+/// [`PipelineGraph::is_compatible_with`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    /// Nodes declared only by the replacement.
+    pub added_nodes: Vec<NodeId>,
+    /// Nodes missing from the replacement. Runs may hold state for them.
+    pub removed_nodes: Vec<NodeId>,
+    /// Edges declared only by the replacement.
+    pub added_edges: Vec<EdgeId>,
+    /// Edges missing from the replacement.
+    pub removed_edges: Vec<EdgeId>,
+    /// Edges kept by ID whose source or target changed.
+    pub rerouted_edges: Vec<EdgeRerouting>,
+    /// Edges whose rework traversal limit changed, was added, or was removed.
+    pub rework_limit_changes: Vec<ReworkLimitChange>,
+}
+
+impl CompatibilityReport {
+    /// Returns `true` if in-flight runs can continue under the replacement:
+    /// nothing was removed or rerouted and no rework limit was lowered or
+    /// removed.
+    pub fn is_compatible(&self) -> bool {
+        self.removed_nodes.is_empty()
+            && self.removed_edges.is_empty()
+            && self.rerouted_edges.is_empty()
+            && self
+                .rework_limit_changes
+                .iter()
+                .all(ReworkLimitChange::is_compatible)
+    }
+
+    /// Returns `true` if the two graphs have the same nodes and edges.
+    pub fn is_unchanged(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// An edge whose endpoints differ between a graph and its replacement.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeRerouting {
+    /// The edge, identified by its unchanged ID.
+    pub edge: EdgeId,
+    /// `(source, target)` in the current graph.
+    pub from: (NodeId, NodeId),
+    /// `(source, target)` in the replacement.
+    pub to: (NodeId, NodeId),
+}
+
+/// A change to an edge's [`ReworkEdge::max_traversals`]. `None` means the
+/// edge is not a rework edge on that side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReworkLimitChange {
+    /// The edge whose limit changed.
+    pub edge: EdgeId,
+    /// Limit in the current graph.
+    pub before: Option<u32>,
+    /// Limit in the replacement.
+    pub after: Option<u32>,
+}
+
+impl ReworkLimitChange {
+    /// Returns `true` if runs that already traversed the edge up to `before`
+    /// times remain within the new limit: the limit was raised, or the edge
+    /// became a rework edge.
+    pub fn is_compatible(&self) -> bool {
+        match (self.before, self.after) {
+            (Some(before), Some(after)) => after >= before,
+            (None, _) => true,
+            (Some(_), None) => false,
+        }
+    }
+}
+
 /// Tool-profile overrides declared in a pipeline configuration file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PipelineToolProfileConfig {
@@ -331,6 +507,39 @@ pub struct PipelineConfiguration {
     pub pipelines: HashMap<PipelineName, PipelineGraph>,
 }
 
+/// Name of the pipeline selected when the caller does not name one.
+pub const DEFAULT_PIPELINE_NAME: &str = "default";
+
+impl PipelineConfiguration {
+    /// Selects the pipeline named `name` and validates its graph.
+    ///
+    /// Only the selected graph is validated; other pipelines declared in the
+    /// same file are not inspected.
+    ///
+    /// # Errors
+    ///
+    /// - [`PipelineSelectionError::UndefinedPipeline`] — no pipeline named `name`.
+    /// - [`PipelineSelectionError::InvalidGraph`] — the selected graph failed
+    ///   [`validate_pipeline_graph`].
+    pub fn select(&self, name: &PipelineName) -> Result<&PipelineGraph, PipelineSelectionError> {
+        let Some(graph) = self.pipelines.get(name) else {
+            let mut available: Vec<PipelineName> = self.pipelines.keys().cloned().collect();
+            available.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+            return Err(PipelineSelectionError::UndefinedPipeline {
+                name: name.clone(),
+                available,
+            });
+        };
+
+        validate_pipeline_graph(graph).map_err(|errors| PipelineSelectionError::InvalidGraph {
+            name: name.clone(),
+            errors,
+        })?;
+
+        Ok(graph)
+    }
+}
+
 // ─── Runtime state ──────────────────────────────────────────────────────────
 
 /// Execution phase of a single node within a pipeline run.
@@ -386,6 +595,43 @@ pub struct PipelineState {
     /// Starts at [`TokenCost::zero()`] when a run begins. Compare against
     /// the configured [`CostBudget`] using [`CostBudget::is_exceeded_by`].
     pub cost_accumulator: TokenCost,
+    /// The most recent event whose effects are reflected in this state.
+    ///
+    /// Compared against incoming events so that a queue replayed after a crash
+    /// does not apply the same event twice. `None` for state persisted before
+    /// this field existed.
+    #[serde(default)]
+    pub last_processed_event: Option<ProcessedEventMarker>,
+    /// Labels the pipeline believes are applied to the work-item issue.
+    ///
+    /// Humans may change labels between steps, so this is checked against the
+    /// issue before each step; GitHub is the source of truth on mismatch.
+    #[serde(default)]
+    pub expected_labels: BTreeSet<String>,
+    /// Number of sub-work-items this run has created.
+    ///
+    /// Checked against the configured per-run cap before each creation.
+    #[serde(default)]
+    pub sub_work_items_created: u32,
+    /// Whether this run may only comment and label.
+    ///
+    /// Set when a review-only run starts. Repository writes (code changes and
+    /// pull request creation) are refused with a scope violation; issue
+    /// comments and labels are unaffected.
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+/// Identifies an event that has already been applied to a [`PipelineState`].
+///
+/// Copied from the event's [`EventContext`](crate::EventContext) when the
+/// step that handled it persists its state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessedEventMarker {
+    /// Delivery GUID of the applied event, if it had one.
+    pub delivery_id: Option<String>,
+    /// When the applied event was delivered, if known.
+    pub delivered_at: Option<Timestamp>,
 }
 
 /// Which component performed a given edge-condition evaluation.
@@ -423,6 +669,15 @@ pub struct EdgeEvaluationRecord {
     pub result: bool,
     /// The component that performed the evaluation.
     pub evaluator: EvaluatorKind,
+    /// Token cost spent evaluating this edge (USD).
+    ///
+    /// Non-zero only for [`EvaluatorKind::LlmModel`] evaluations (and
+    /// composites containing one); deterministic evaluations record
+    /// [`TokenCost::zero()`]. Attributed to the edge rather than to either
+    /// adjacent node. Defaults to zero when reading state persisted before this
+    /// field existed.
+    #[serde(default = "TokenCost::zero")]
+    pub cost: TokenCost,
     /// Wall-clock time at which the evaluation was performed.
     pub timestamp: Timestamp,
 }
@@ -585,6 +840,31 @@ pub enum GraphValidationError {
     },
 }
 
+/// Returned by [`PipelineConfiguration::select`].
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum PipelineSelectionError {
+    /// The configuration does not declare a pipeline with the requested name.
+    #[error(
+        "Pipeline '{name}' is not defined; available pipelines: [{}]",
+        available.iter().map(PipelineName::as_str).collect::<Vec<_>>().join(", ")
+    )]
+    UndefinedPipeline {
+        /// The requested pipeline name.
+        name: PipelineName,
+        /// Names declared in the configuration, sorted.
+        available: Vec<PipelineName>,
+    },
+
+    /// The selected pipeline's graph failed validation.
+    #[error("Pipeline '{name}' is invalid: {} validation error(s)", errors.len())]
+    InvalidGraph {
+        /// The selected pipeline name.
+        name: PipelineName,
+        /// Every violation reported by [`validate_pipeline_graph`].
+        errors: Vec<GraphValidationError>,
+    },
+}
+
 // ─── Pure business logic functions ──────────────────────────────────────────
 
 /// Returns the forward-edge topological ordering of node IDs (sources first).
@@ -657,3 +937,7 @@ pub fn validate_pipeline_graph(_graph: &PipelineGraph) -> Result<(), Vec<GraphVa
 pub fn compute_eligible_nodes(_state: &PipelineState, _graph: &PipelineGraph) -> Vec<NodeId> {
     todo!("See docs/spec/interfaces/pipeline-graph.md §compute_eligible_nodes")
 }
+
+#[cfg(test)]
+#[path = "graph_tests.rs"]
+mod tests;
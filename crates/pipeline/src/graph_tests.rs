@@ -0,0 +1,278 @@
+use super::*;
+
+fn node_id(id: &str) -> NodeId {
+    NodeId::new(id).unwrap()
+}
+
+fn node(id: &str) -> NodeDefinition {
+    NodeDefinition {
+        id: node_id(id),
+        node_type: NodeType::Llm,
+        declared_inputs: Vec::new(),
+        declared_outputs: Vec::new(),
+        timeout: None,
+        cost_budget: None,
+        gate: NodeGate::AutoProceed,
+        validation_kind: ValidationKind::None,
+        abort_siblings_on_failure: false,
+        priority: 0,
+        model: None,
+    }
+}
+
+fn graph(nodes: Vec<NodeDefinition>, edges: Vec<EdgeDefinition>) -> PipelineGraph {
+    PipelineGraph {
+        nodes,
+        edges,
+        evaluation_modes: HashMap::new(),
+        explicit_edge_lists: HashMap::new(),
+        settings: PipelineSettings {
+            default_timeout: None,
+            default_cost_budget: None,
+            max_node_retries: 3,
+            default_model: None,
+        },
+        tool_profiles: PipelineToolProfileConfig {
+            default_profile: ProfileName::new("default").unwrap(),
+            node_overrides: HashMap::new(),
+        },
+    }
+}
+
+// ─── Pipeline selection ──────────────────────────────────────────────────────
+
+#[test]
+fn test_select_undefined_pipeline_lists_available_names_sorted() {
+    let configuration = PipelineConfiguration {
+        pipelines: HashMap::from([
+            (
+                PipelineName::new("release").unwrap(),
+                graph(vec![node("a")], Vec::new()),
+            ),
+            (
+                PipelineName::new("default").unwrap(),
+                graph(vec![node("a")], Vec::new()),
+            ),
+        ]),
+    };
+
+    let error = configuration
+        .select(&PipelineName::new("hotfix").unwrap())
+        .unwrap_err();
+
+    match &error {
+        PipelineSelectionError::UndefinedPipeline { name, available } => {
+            assert_eq!(name.as_str(), "hotfix");
+            let available: Vec<&str> = available.iter().map(PipelineName::as_str).collect();
+            assert_eq!(available, ["default", "release"]);
+        }
+        other => panic!("expected UndefinedPipeline, got {other:?}"),
+    }
+    assert_eq!(
+        error.to_string(),
+        "Pipeline 'hotfix' is not defined; available pipelines: [default, release]"
+    );
+}
+
+#[test]
+fn test_select_empty_configuration_reports_no_available_pipelines() {
+    let configuration = PipelineConfiguration {
+        pipelines: HashMap::new(),
+    };
+
+    let error = configuration
+        .select(&PipelineName::new(DEFAULT_PIPELINE_NAME).unwrap())
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        PipelineSelectionError::UndefinedPipeline { ref available, .. } if available.is_empty()
+    ));
+}
+
+// ─── Model overrides ────────────────────────────────────────────────────────
+
+fn with_model(id: &str, model: &str) -> NodeDefinition {
+    NodeDefinition {
+        model: Some(model.to_string()),
+        ..node(id)
+    }
+}
+
+#[test]
+fn test_model_for_node_with_override_returns_node_model() {
+    let mut graph = graph(
+        vec![with_model("triage", "claude-haiku"), node("plan")],
+        Vec::new(),
+    );
+    graph.settings.default_model = Some("claude-sonnet".to_string());
+
+    assert_eq!(graph.model_for(&node_id("triage")), Some("claude-haiku"));
+}
+
+#[test]
+fn test_model_for_node_without_override_returns_pipeline_default() {
+    let mut graph = graph(
+        vec![with_model("triage", "claude-haiku"), node("plan")],
+        Vec::new(),
+    );
+    graph.settings.default_model = Some("claude-sonnet".to_string());
+
+    assert_eq!(graph.model_for(&node_id("plan")), Some("claude-sonnet"));
+}
+
+#[test]
+fn test_model_for_no_override_and_no_default_returns_none() {
+    let graph = graph(vec![node("plan")], Vec::new());
+
+    assert_eq!(graph.model_for(&node_id("plan")), None);
+}
+
+#[test]
+fn test_model_for_node_not_in_graph_returns_pipeline_default() {
+    let mut graph = graph(vec![with_model("triage", "claude-haiku")], Vec::new());
+    graph.settings.default_model = Some("claude-sonnet".to_string());
+
+    assert_eq!(graph.model_for(&node_id("unknown")), Some("claude-sonnet"));
+}
+
+// ─── Compatibility ──────────────────────────────────────────────────────────
+
+fn edge_id(id: &str) -> EdgeId {
+    EdgeId::new(id).unwrap()
+}
+
+fn edge(id: &str, source: &str, target: &str) -> EdgeDefinition {
+    EdgeDefinition {
+        id: edge_id(id),
+        source: node_id(source),
+        target: node_id(target),
+        condition: EdgeConditionKind::Deterministic(Expression::new("true").unwrap()),
+        rework_edge: None,
+    }
+}
+
+fn rework(id: &str, source: &str, target: &str, max_traversals: u32) -> EdgeDefinition {
+    EdgeDefinition {
+        rework_edge: Some(ReworkEdge {
+            max_traversals,
+            preserved_outputs: Vec::new(),
+            overflow_behaviour: OverflowBehaviour::Escalate,
+            semantics: ReworkSemantics::Rework,
+        }),
+        ..edge(id, source, target)
+    }
+}
+
+/// plan → code → review, with review → code as a rework edge.
+fn review_loop(max_traversals: u32) -> PipelineGraph {
+    graph(
+        vec![node("plan"), node("code"), node("review")],
+        vec![
+            edge("plan-code", "plan", "code"),
+            edge("code-review", "code", "review"),
+            rework("review-code", "review", "code", max_traversals),
+        ],
+    )
+}
+
+#[test]
+fn test_is_compatible_with_identical_graph_reports_unchanged() {
+    let report = review_loop(3).is_compatible_with(&review_loop(3));
+
+    assert!(report.is_unchanged());
+    assert!(report.is_compatible());
+}
+
+#[test]
+fn test_is_compatible_with_added_node_and_edge_is_compatible() {
+    let mut replacement = review_loop(3);
+    replacement.nodes.push(node("docs"));
+    replacement
+        .edges
+        .push(edge("review-docs", "review", "docs"));
+
+    let report = review_loop(3).is_compatible_with(&replacement);
+
+    assert!(report.is_compatible());
+    assert!(!report.is_unchanged());
+    assert_eq!(report.added_nodes, vec![node_id("docs")]);
+    assert_eq!(report.added_edges, vec![edge_id("review-docs")]);
+    assert!(report.removed_nodes.is_empty());
+    assert!(report.removed_edges.is_empty());
+}
+
+#[test]
+fn test_is_compatible_with_removed_node_is_incompatible() {
+    let replacement = graph(
+        vec![node("plan"), node("code")],
+        vec![edge("plan-code", "plan", "code")],
+    );
+
+    let report = review_loop(3).is_compatible_with(&replacement);
+
+    assert!(!report.is_compatible());
+    assert_eq!(report.removed_nodes, vec![node_id("review")]);
+    assert_eq!(
+        report.removed_edges,
+        vec![edge_id("code-review"), edge_id("review-code")]
+    );
+    assert!(report.added_nodes.is_empty());
+}
+
+#[test]
+fn test_is_compatible_with_rerouted_edge_is_incompatible() {
+    let mut replacement = review_loop(3);
+    replacement.nodes.push(node("lint"));
+    replacement.edges[1] = edge("code-review", "lint", "review");
+
+    let report = review_loop(3).is_compatible_with(&replacement);
+
+    assert!(!report.is_compatible());
+    assert_eq!(
+        report.rerouted_edges,
+        vec![EdgeRerouting {
+            edge: edge_id("code-review"),
+            from: (node_id("code"), node_id("review")),
+            to: (node_id("lint"), node_id("review")),
+        }]
+    );
+}
+
+#[test]
+fn test_is_compatible_with_raised_rework_limit_is_compatible() {
+    let report = review_loop(3).is_compatible_with(&review_loop(5));
+
+    assert!(report.is_compatible());
+    assert_eq!(
+        report.rework_limit_changes,
+        vec![ReworkLimitChange {
+            edge: edge_id("review-code"),
+            before: Some(3),
+            after: Some(5),
+        }]
+    );
+}
+
+#[test]
+fn test_is_compatible_with_lowered_rework_limit_is_incompatible() {
+    let report = review_loop(3).is_compatible_with(&review_loop(2));
+
+    assert!(!report.is_compatible());
+    assert_eq!(report.rework_limit_changes.len(), 1);
+}
+
+#[test]
+fn test_rework_limit_change_is_compatible_each_transition() {
+    let change = |before, after| ReworkLimitChange {
+        edge: edge_id("review-code"),
+        before,
+        after,
+    };
+
+    assert!(change(Some(2), Some(2)).is_compatible());
+    assert!(change(Some(2), Some(4)).is_compatible());
+    assert!(!change(Some(4), Some(2)).is_compatible());
+    assert!(change(None, Some(1)).is_compatible());
+    assert!(!change(Some(2), None).is_compatible());
+}
@@ -10,6 +10,7 @@
 //! See `docs/spec/interfaces/shared-types.md` §Identifiers for the full contract.
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use uuid::Uuid;
 
 // ---------------------------------------------------------------------------
@@ -109,6 +110,21 @@ u64_id! {
     PullRequestId
 }
 
+u64_id! {
+    /// Identifies a GitHub App installation.
+    ///
+    /// Selects which installation access token authenticates API calls for the
+    /// repository an event came from.
+    InstallationId
+}
+
+u64_id! {
+    /// Identifies a comment on a GitHub Issue or Pull Request.
+    ///
+    /// Assigned by GitHub; unique across the repository (not per issue).
+    CommentId
+}
+
 // ---------------------------------------------------------------------------
 // Identifiers — UUID-backed (internally generated)
 // ---------------------------------------------------------------------------
@@ -177,19 +193,173 @@ string_id! {
 string_id! {
     /// A Git commit SHA (expected format: 40-character lowercase hex string).
     ///
-    /// The constructor only rejects empty strings; format conformance is the
-    /// responsibility of the caller (values typically originate from GitHub API
-    /// responses or validated config files).
+    /// [`CommitSha::new`] only rejects empty strings. Values from user input
+    /// or configuration go through [`CommitSha::parse`] (or
+    /// [`CommitSha::parse_abbrev`]), which check the format.
     CommitSha
 }
 
-string_id! {
-    /// Identifies a GitHub repository (expected format: `"owner/repo"`).
+/// Length of a full Git commit SHA in hex characters.
+pub const COMMIT_SHA_LEN: usize = 40;
+
+/// Shortest abbreviated SHA accepted by [`CommitSha::parse_abbrev`].
+pub const MIN_ABBREV_COMMIT_SHA_LEN: usize = 7;
+
+/// Returned when a string is not a valid commit SHA.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum CommitShaError {
+    /// The value has the wrong number of characters.
+    #[error("commit SHA '{value}' has {len} characters; expected {expected}")]
+    InvalidLength {
+        /// The rejected value.
+        value: String,
+        /// Its length in characters.
+        len: usize,
+        /// The accepted length or range (e.g. `"40"`, `"7-40"`).
+        expected: &'static str,
+    },
+
+    /// The value contains a character that is not a hex digit.
+    #[error("commit SHA '{value}' contains non-hex character '{character}'")]
+    NonHex {
+        /// The rejected value.
+        value: String,
+        /// The first offending character.
+        character: char,
+    },
+}
+
+impl CommitSha {
+    /// Parses a full 40-character hex SHA.
+    ///
+    /// Uppercase hex digits are accepted and normalised to lowercase.
+    ///
+    /// # Errors
+    ///
+    /// - [`CommitShaError::InvalidLength`] — not exactly 40 characters.
+    /// - [`CommitShaError::NonHex`] — a character is not a hex digit.
+    pub fn parse(value: &str) -> Result<Self, CommitShaError> {
+        Self::parse_within(value, COMMIT_SHA_LEN, "40")
+    }
+
+    /// Parses a full or abbreviated SHA of 7 to 40 hex characters.
+    ///
+    /// Uppercase hex digits are accepted and normalised to lowercase.
+    ///
+    /// # Errors
+    ///
+    /// - [`CommitShaError::InvalidLength`] — fewer than 7 or more than 40
+    ///   characters.
+    /// - [`CommitShaError::NonHex`] — a character is not a hex digit.
+    pub fn parse_abbrev(value: &str) -> Result<Self, CommitShaError> {
+        Self::parse_within(value, MIN_ABBREV_COMMIT_SHA_LEN, "7-40")
+    }
+
+    /// Returns `true` if this is a full 40-character SHA rather than an
+    /// abbreviation.
+    pub fn is_full(&self) -> bool {
+        self.0.len() == COMMIT_SHA_LEN
+    }
+
+    fn parse_within(
+        value: &str,
+        min_len: usize,
+        expected: &'static str,
+    ) -> Result<Self, CommitShaError> {
+        if let Some(character) = value.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(CommitShaError::NonHex {
+                value: value.to_string(),
+                character,
+            });
+        }
+        // All characters are ASCII, so the byte length is the character count.
+        let len = value.len();
+        if !(min_len..=COMMIT_SHA_LEN).contains(&len) {
+            return Err(CommitShaError::InvalidLength {
+                value: value.to_string(),
+                len,
+                expected,
+            });
+        }
+        Ok(Self(value.to_ascii_lowercase()))
+    }
+}
+
+impl std::str::FromStr for CommitSha {
+    type Err = CommitShaError;
+
+    /// Parses a full SHA; see [`CommitSha::parse`].
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+/// Identifies a GitHub repository (format: `"owner/repo"`).
+///
+/// Construction and deserialisation both validate the format, so
+/// [`RepositoryId::owner`] and [`RepositoryId::repo`] never need to handle a
+/// malformed value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String")]
+pub struct RepositoryId(String);
+
+impl RepositoryId {
+    /// Creates a repository identifier; equivalent to [`RepositoryId::parse`].
+    #[must_use]
+    pub fn new(value: impl Into<String>) -> Option<Self> {
+        Self::parse(&value.into())
+    }
+
+    /// Parses `"owner/repo"`.
     ///
-    /// The constructor only rejects empty strings; two-segment format conformance
-    /// is the responsibility of the caller (values typically originate from
-    /// validated config files or GitHub API responses).
-    RepositoryId
+    /// Returns `None` unless `value` contains exactly one `/` with a
+    /// non-empty owner and repo on either side, and no whitespace (e.g.
+    /// `"owner/"`, `"/repo"`, `"a/b/c"`, and `"owner repo"` are rejected).
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let (owner, repo) = value.split_once('/')?;
+        let valid = !owner.is_empty()
+            && !repo.is_empty()
+            && !repo.contains('/')
+            && !value.contains(char::is_whitespace);
+        valid.then(|| Self(value.to_string()))
+    }
+
+    /// Returns the identifier as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the owner (user or organisation) segment.
+    pub fn owner(&self) -> &str {
+        self.split().0
+    }
+
+    /// Returns the repository name segment.
+    pub fn repo(&self) -> &str {
+        self.split().1
+    }
+
+    fn split(&self) -> (&str, &str) {
+        // Every constructor validated the single '/'.
+        self.0.split_once('/').unwrap_or((&self.0, ""))
+    }
+}
+
+impl TryFrom<String> for RepositoryId {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+            .ok_or_else(|| format!("invalid repository '{value}': expected 'owner/repo'"))
+    }
+}
+
+impl std::fmt::Display for RepositoryId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
 
 string_id! {
@@ -242,3 +412,7 @@ string_id! {
     /// must not be passed to APIs that expect a commit ref.
     GitObjectSha
 }
+
+#[cfg(test)]
+#[path = "identifiers_tests.rs"]
+mod tests;
@@ -0,0 +1,164 @@
+use super::*;
+
+const FULL_SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+// ─── CommitSha::parse ───────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_full_lowercase_sha_returns_full_sha() {
+    let sha = CommitSha::parse(FULL_SHA).unwrap();
+
+    assert_eq!(sha.as_str(), FULL_SHA);
+    assert!(sha.is_full());
+}
+
+#[test]
+fn test_parse_uppercase_sha_normalises_to_lowercase() {
+    let sha = CommitSha::parse(&FULL_SHA.to_ascii_uppercase()).unwrap();
+
+    assert_eq!(sha.as_str(), FULL_SHA);
+}
+
+#[test]
+fn test_parse_abbreviated_sha_returns_invalid_length() {
+    assert_eq!(
+        CommitSha::parse("0123456"),
+        Err(CommitShaError::InvalidLength {
+            value: "0123456".to_string(),
+            len: 7,
+            expected: "40",
+        })
+    );
+}
+
+#[test]
+fn test_parse_too_long_sha_returns_invalid_length() {
+    let value = format!("{FULL_SHA}8");
+
+    assert!(matches!(
+        CommitSha::parse(&value),
+        Err(CommitShaError::InvalidLength { len: 41, .. })
+    ));
+}
+
+#[test]
+fn test_parse_non_hex_character_returns_non_hex() {
+    let value = FULL_SHA.replace('a', "g");
+
+    assert_eq!(
+        CommitSha::parse(&value),
+        Err(CommitShaError::NonHex {
+            value: value.clone(),
+            character: 'g',
+        })
+    );
+}
+
+#[test]
+fn test_parse_empty_returns_invalid_length() {
+    assert!(matches!(
+        CommitSha::parse(""),
+        Err(CommitShaError::InvalidLength { len: 0, .. })
+    ));
+}
+
+#[test]
+fn test_from_str_full_sha_matches_parse() {
+    let sha: CommitSha = FULL_SHA.parse().unwrap();
+
+    assert_eq!(sha, CommitSha::parse(FULL_SHA).unwrap());
+}
+
+// ─── CommitSha::parse_abbrev ────────────────────────────────────────────────
+
+#[test]
+fn test_parse_abbrev_minimum_length_returns_abbreviated_sha() {
+    let sha = CommitSha::parse_abbrev("ABCDEF0").unwrap();
+
+    assert_eq!(sha.as_str(), "abcdef0");
+    assert!(!sha.is_full());
+}
+
+#[test]
+fn test_parse_abbrev_full_sha_returns_full_sha() {
+    assert!(CommitSha::parse_abbrev(FULL_SHA).unwrap().is_full());
+}
+
+#[test]
+fn test_parse_abbrev_too_short_returns_invalid_length() {
+    assert_eq!(
+        CommitSha::parse_abbrev("abc123"),
+        Err(CommitShaError::InvalidLength {
+            value: "abc123".to_string(),
+            len: 6,
+            expected: "7-40",
+        })
+    );
+}
+
+#[test]
+fn test_parse_abbrev_non_hex_returns_non_hex() {
+    assert!(matches!(
+        CommitSha::parse_abbrev("abc-1234"),
+        Err(CommitShaError::NonHex { character: '-', .. })
+    ));
+}
+
+// ─── RepositoryId ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_repository_id_parse_owner_and_repo_returns_segments() {
+    let repository = RepositoryId::parse("octo-org/widgets.rs").unwrap();
+
+    assert_eq!(repository.owner(), "octo-org");
+    assert_eq!(repository.repo(), "widgets.rs");
+    assert_eq!(repository.as_str(), "octo-org/widgets.rs");
+}
+
+#[test]
+fn test_repository_id_parse_malformed_values_return_none() {
+    for value in ["", "widgets", "octo/", "/widgets", "a/b/c", "octo /widgets"] {
+        assert_eq!(RepositoryId::parse(value), None, "{value:?}");
+    }
+}
+
+#[test]
+fn test_repository_id_new_matches_parse() {
+    assert_eq!(
+        RepositoryId::new("octo/widgets"),
+        RepositoryId::parse("octo/widgets")
+    );
+    assert_eq!(RepositoryId::new("octo"), None);
+}
+
+#[test]
+fn test_repository_id_display_returns_owner_slash_repo() {
+    let repository = RepositoryId::parse("octo/widgets").unwrap();
+
+    assert_eq!(repository.to_string(), "octo/widgets");
+}
+
+#[test]
+fn test_repository_id_deserialize_valid_value_round_trips() {
+    let repository = RepositoryId::parse("octo/widgets").unwrap();
+
+    let json = serde_json::to_string(&repository).unwrap();
+
+    assert_eq!(json, r#""octo/widgets""#);
+    assert_eq!(
+        serde_json::from_str::<RepositoryId>(&json).unwrap(),
+        repository
+    );
+}
+
+#[test]
+fn test_repository_id_deserialize_malformed_value_fails() {
+    assert!(serde_json::from_str::<RepositoryId>(r#""octo/widgets/extra""#).is_err());
+}
+
+#[test]
+fn test_repository_id_try_from_malformed_value_names_expected_format() {
+    let error = RepositoryId::try_from("widgets".to_string()).unwrap_err();
+
+    assert!(error.contains("expected 'owner/repo'"));
+}
@@ -15,10 +15,14 @@
 //! |--------|----------|
 //! | [`identifiers`] | Newtype domain identifiers (`WorkItemId`, `NodeId`, etc.) |
 //! | [`types`] | Shared value types (`TokenCount`, `CostBudget`, `Diagnostic`, etc.) |
+//! | [`context`] | Context Pack content, `GlobPattern`, `PackFileSelection` |
+//! | [`cost`] | `CostLedger` — run cost accumulated per `CostCategory` |
 //! | [`errors`] | Top-level error and retry-policy types |
 //! | [`graph`] | Pipeline graph model and runtime state types |
 //! | [`github`] | GitHub traits: `EventSource`, `IssueTracker`, `PullRequestManager`, `CodeRepository`, `ProjectBoard` and their data types |
 //! | [`templates`] | `TemplateEngine` trait |
+//! | [`llm`] | `LlmProvider` trait, `CompletionRequest`, `CompletionResponse`, `LlmError` |
+//! | [`retry`] | Shared retry loop recording attempt metrics (`retry`, `RetryMetrics`) and the per-run retry budget (`RunRetryBudget`) |
 //! | [`audit`] | `AuditStore` trait, `AuditEvent` enum, `PipelineSummary` |
 //!
 //! ## Specification
@@ -26,44 +30,72 @@
 //! See [`docs/spec/interfaces/shared-types.md`] for shared types.
 //! See [`docs/spec/interfaces/pipeline-graph.md`] for graph model types.
 //! See [`docs/spec/interfaces/github-traits.md`] for GitHub trait contracts.
+//! See [`docs/spec/interfaces/domain-traits.md`] for the LLM provider contract.
 
 pub mod audit;
+pub mod context;
+pub mod cost;
 pub mod errors;
 pub mod github;
 pub mod graph;
 pub mod identifiers;
+pub mod llm;
+pub mod retry;
 pub mod templates;
 pub mod types;
 
 // Re-export everything at the crate root for ergonomic usage by downstream crates.
 pub use audit::{
-    AuditEvent, AuditStore, AuditStoreError, CostSnapshot, InjectionDetectionRecord, LlmCallRecord,
-    PipelineOutcome, PipelineSummary, ScopeViolationRecord, StateTransitionRecord,
-    ValidationRecord,
+    AuditEvent, AuditEventKind, AuditStore, AuditStoreError, CostSnapshot,
+    InjectionDetectionRecord, LlmCallRecord, PipelineOutcome, PipelineSummary,
+    ScopeViolationRecord, StateTransitionRecord, ValidationRecord,
 };
-pub use errors::{CogWorksError, RetryPolicy};
+pub use context::{
+    ContextPack, ContextPackError, ContextPackManifest, GlobPattern, PackFileSelection,
+};
+pub use cost::{CostCategory, CostLedger};
+pub use errors::{CogWorksError, HaltReason, RetryPolicy};
 pub use github::{
-    CodeRepository, DirectoryEntry, DirectoryEntryKind, EventSource, EventSourceError, FileContent,
-    GitHubEvent, GitHubOperationError, Issue, IssueState, IssueTracker, Label, Milestone,
-    ProjectBoard, PullRequest, PullRequestFilter, PullRequestManager, QueueEventConfig,
-    ReviewDecision, ReviewStatus, SubIssue, TypedLink, TypedLinkKind, WebhookConfig,
+    with_comment_marker, ChangedFile, CodeRepository, CommitState, ContextStatus, DirectoryEntry,
+    DirectoryEntryKind, EnvironmentProtection, EventContext, EventSource, EventSourceError,
+    FileChangeStatus, FileContent, GitHubEvent, GitHubOperationError, Issue, IssueComment,
+    IssueFilter, IssueSnapshot, IssueState, IssueStateFilter, IssueStateReason, IssueTracker,
+    Label, Mergeability, MergeableState, Milestone, MilestoneInfo, ProjectBoard, ProjectFieldValue,
+    PullRequest, PullRequestFilter, PullRequestManager, QueueEventConfig, ReviewDecision,
+    ReviewStatus, StatusSummary, SubIssue, TypedLink, TypedLinkKind, WebhookConfig,
+    DEFAULT_EVENT_BUFFER_CAPACITY, DEFAULT_HEALTH_PATH, DEFAULT_WEBHOOK_PATH,
 };
 pub use graph::{
     compute_eligible_nodes, evaluate_deterministic_condition, topological_sort,
-    validate_pipeline_graph, CompositeCondition, CycleError, EdgeConditionKind, EdgeDefinition,
-    EdgeEvaluationRecord, EvaluationMode, EvaluatorKind, Expression, GraphValidationError,
-    NaturalLanguageCondition, NodeDefinition, NodeGate, NodeState, NodeStatus, NodeType,
-    OverflowBehaviour, PipelineConfiguration, PipelineGraph, PipelineSettings, PipelineState,
-    PipelineStateComment, PipelineToolProfileConfig, ReworkEdge, ReworkSemantics, SchemaVersion,
-    TimeoutSeconds, ValidationKind,
+    validate_pipeline_graph, CompatibilityReport, CompositeCondition, CycleError,
+    EdgeConditionKind, EdgeDefinition, EdgeEvaluationRecord, EdgeRerouting, EvaluationMode,
+    EvaluatorKind, Expression, GraphValidationError, NaturalLanguageCondition, NodeDefinition,
+    NodeGate, NodeState, NodeStatus, NodeType, OverflowBehaviour, PipelineConfiguration,
+    PipelineGraph, PipelineSelectionError, PipelineSettings, PipelineState, PipelineStateComment,
+    PipelineToolProfileConfig, ProcessedEventMarker, ReworkEdge, ReworkLimitChange,
+    ReworkSemantics, SchemaVersion, TimeoutSeconds, ValidationKind, DEFAULT_PIPELINE_NAME,
 };
 pub use identifiers::{
-    ArtifactPath, BranchName, CommitSha, ContextPackId, DomainServiceName, EdgeId, GitObjectSha,
-    InterfaceId, MilestoneId, NodeId, PipelineName, PipelineRunId, ProfileName, PullRequestId,
-    RepositoryId, SkillName, SubWorkItemId, ToolName, WorkItemId,
+    ArtifactPath, BranchName, CommentId, CommitSha, CommitShaError, ContextPackId,
+    DomainServiceName, EdgeId, GitObjectSha, InstallationId, InterfaceId, MilestoneId, NodeId,
+    PipelineName, PipelineRunId, ProfileName, PullRequestId, RepositoryId, SkillName,
+    SubWorkItemId, ToolName, WorkItemId, COMMIT_SHA_LEN, MIN_ABBREV_COMMIT_SHA_LEN,
+};
+pub use llm::{
+    CompletionChunk, CompletionRequest, CompletionResponse, CompletionStream, FinishReason,
+    LlmError, LlmProvider, Message, MessageRole, PartialCompletion, SystemLayer, SystemSegment,
+    TokenEstimate, TokenUsage, HEURISTIC_CHARS_PER_TOKEN,
+};
+pub use retry::{
+    jitter_source, retry, retry_within_budget, InMemoryRetryMetrics, NoopRetryMetrics, RetryBudget,
+    RetryBudgetError, RetryBudgetExhausted, RetryBudgetUsage, RetryMetrics, RetryOutcome,
+    RetrySample, RetrySchedule, RetryableError, RunRetryBudget, DEFAULT_RETRY_ATTEMPTS,
+    DEFAULT_RETRY_BACKOFF, DEFAULT_RETRY_MAX_BACKOFF, DEFAULT_RUN_MAX_RETRIES,
+    DEFAULT_RUN_MAX_RETRY_TIME,
 };
 pub use templates::{TemplateEngine, TemplateError};
 pub use types::{
-    AlignmentScore, ApiVersion, CostBudget, Diagnostic, DiagnosticCategory, DiagnosticSeverity,
-    SatisfactionScore, Timestamp, TokenCost, TokenCount,
+    count_by_severity, has_blocking, highest_severity, AlignmentScore, ApiVersion, CostBudget,
+    Diagnostic, DiagnosticCategory, DiagnosticSeverity, SatisfactionScore, Timestamp, TokenCost,
+    TokenCount,
 };
@@ -0,0 +1,622 @@
+//! LLM provider port and the provider-neutral request/response types.
+//!
+//! The pipeline domain describes *what* it wants from a language model in
+//! provider-neutral terms ([`CompletionRequest`]). Infrastructure crates
+//! (specifically `llm`) translate the request into each provider's wire format
+//! and implement [`LlmProvider`].
+//!
+//! ## System Prompt Layering
+//!
+//! The system prompt is assembled from ordered [`SystemSegment`]s, each tagged
+//! with a [`SystemLayer`]. Providers compose the segments in layer order —
+//! constitutional rules first, then the node role, then the task — regardless
+//! of the order in which they were added, so the constitutional rules can never
+//! be preceded by content that attempts to override them. Within one layer the
+//! insertion order is preserved.
+//!
+//! ## Architectural Layer
+//!
+//! Port definition. No I/O lives here.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/domain-traits.md` §LLM Provider for the full
+//! contract.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{RetryPolicy, TokenCount};
+
+// ─── Request types ──────────────────────────────────────────────────────────
+
+/// The layer a [`SystemSegment`] belongs to.
+///
+/// Variants are declared in composition order: the derived `Ord` is the order
+/// in which providers emit the segments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemLayer {
+    /// Constitutional rules loaded from the protected rules file. Always first.
+    Constitutional,
+    /// The role the node plays (e.g. "You are the Planning node").
+    Role,
+    /// Context Pack sections, oldest first. The only layer the LLM gateway
+    /// drops from when a prompt is over its size limit.
+    Context,
+    /// Task-specific instructions for this call.
+    Task,
+}
+
+/// One layer of content in a composed system prompt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemSegment {
+    /// The layer this segment belongs to.
+    pub layer: SystemLayer,
+    /// Segment text.
+    pub content: String,
+}
+
+impl SystemSegment {
+    /// Creates a [`SystemSegment`] in the given layer.
+    pub fn new(layer: SystemLayer, content: impl Into<String>) -> Self {
+        Self {
+            layer,
+            content: content.into(),
+        }
+    }
+}
+
+/// The author of a conversational [`Message`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageRole {
+    /// Content supplied by CogWorks (prompt, tool results).
+    User,
+    /// Content previously produced by the model.
+    Assistant,
+}
+
+/// A single conversational turn sent to the model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Message {
+    /// Who authored this turn.
+    pub role: MessageRole,
+    /// Turn text.
+    pub content: String,
+}
+
+impl Message {
+    /// Creates a user-authored message.
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: MessageRole::User,
+            content: content.into(),
+        }
+    }
+
+    /// Creates an assistant-authored message.
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: MessageRole::Assistant,
+            content: content.into(),
+        }
+    }
+}
+
+/// A provider-neutral completion request.
+///
+/// Providers translate this into their own wire format; see the `llm` crate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompletionRequest {
+    /// Provider model identifier (e.g. `"claude-3-5-sonnet-20241022"`).
+    pub model: String,
+    /// System prompt segments. Composed in [`SystemLayer`] order by providers.
+    pub system: Vec<SystemSegment>,
+    /// Conversation turns, oldest first.
+    pub messages: Vec<Message>,
+    /// Maximum number of tokens the model may generate.
+    pub max_tokens: TokenCount,
+    /// Sampling temperature. `None` uses the provider default.
+    pub temperature: Option<f64>,
+    /// Nucleus-sampling probability mass. `None` uses the provider default.
+    #[serde(default)]
+    pub top_p: Option<f64>,
+    /// Sequences that end generation when produced. Empty means none.
+    pub stop_sequences: Vec<String>,
+    /// Client-generated ID sent to the provider so its logs can be matched
+    /// with CogWorks spans. `None` sends no ID.
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// Content blocks after which the provider may cache the prompt prefix.
+    /// Indices count the [`layered_system`](Self::layered_system) segments
+    /// first, then `messages`. Empty means no caching; providers without
+    /// explicit cache control ignore it.
+    #[serde(default)]
+    pub cache_breakpoints: Vec<usize>,
+}
+
+impl CompletionRequest {
+    /// Creates a request with no system prompt, no stop sequences, and the
+    /// provider's default temperature and `top_p`.
+    pub fn new(model: impl Into<String>, messages: Vec<Message>, max_tokens: TokenCount) -> Self {
+        Self {
+            model: model.into(),
+            system: Vec::new(),
+            messages,
+            max_tokens,
+            temperature: None,
+            top_p: None,
+            stop_sequences: Vec::new(),
+            request_id: None,
+            cache_breakpoints: Vec::new(),
+        }
+    }
+
+    /// Sets the client-generated request ID sent to the provider.
+    #[must_use]
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Sets the content-block indices the provider may cache up to; see
+    /// [`cache_breakpoints`](Self::cache_breakpoints).
+    #[must_use]
+    pub fn with_cache_breakpoints(mut self, breakpoints: Vec<usize>) -> Self {
+        self.cache_breakpoints = breakpoints;
+        self
+    }
+
+    /// Returns the system segments in composition order.
+    ///
+    /// Segments are sorted by [`SystemLayer`]; the sort is stable, so segments
+    /// within one layer keep their insertion order.
+    #[must_use]
+    pub fn layered_system(&self) -> Vec<&SystemSegment> {
+        let mut segments: Vec<&SystemSegment> = self.system.iter().collect();
+        segments.sort_by_key(|segment| segment.layer);
+        segments
+    }
+}
+
+// ─── Response types ─────────────────────────────────────────────────────────
+
+/// Token usage reported by the provider for one call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    /// Tokens consumed by the prompt.
+    pub input_tokens: TokenCount,
+    /// Tokens generated in the completion.
+    pub output_tokens: TokenCount,
+    /// Prompt tokens written to the provider's prompt cache, billed at a
+    /// premium. Not included in `input_tokens`.
+    #[serde(default)]
+    pub cache_creation_input_tokens: TokenCount,
+    /// Prompt tokens served from the provider's prompt cache, billed at a
+    /// discount. Not included in `input_tokens`.
+    #[serde(default)]
+    pub cache_read_input_tokens: TokenCount,
+}
+
+impl TokenUsage {
+    /// Usage of a call that consumed no tokens.
+    pub fn zero() -> Self {
+        Self::new(TokenCount::new(0), TokenCount::new(0))
+    }
+
+    /// Usage with the given input and output counts and no cache activity.
+    pub fn new(input_tokens: TokenCount, output_tokens: TokenCount) -> Self {
+        Self {
+            input_tokens,
+            output_tokens,
+            cache_creation_input_tokens: TokenCount::default(),
+            cache_read_input_tokens: TokenCount::default(),
+        }
+    }
+}
+
+impl std::ops::Add for TokenUsage {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            input_tokens: self.input_tokens + rhs.input_tokens,
+            output_tokens: self.output_tokens + rhs.output_tokens,
+            cache_creation_input_tokens: self.cache_creation_input_tokens
+                + rhs.cache_creation_input_tokens,
+            cache_read_input_tokens: self.cache_read_input_tokens + rhs.cache_read_input_tokens,
+        }
+    }
+}
+
+/// Output accumulated by a call that may be cancelled before it finishes.
+///
+/// Streaming providers append each text delta and usage update as it
+/// arrives. If the call is cancelled, [`PartialCompletion::cancelled`] turns
+/// what was received into [`LlmError::Cancelled`] so the caller can still
+/// audit it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialCompletion {
+    text: String,
+    usage: TokenUsage,
+}
+
+impl Default for PartialCompletion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialCompletion {
+    /// Creates an empty accumulator.
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+            usage: TokenUsage::zero(),
+        }
+    }
+
+    /// Appends a text delta.
+    pub fn push_text(&mut self, delta: &str) {
+        self.text.push_str(delta);
+    }
+
+    /// Replaces the usage with the provider's latest cumulative report.
+    pub fn set_usage(&mut self, usage: TokenUsage) {
+        self.usage = usage;
+    }
+
+    /// Text received so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Usage reported so far.
+    pub fn usage(&self) -> TokenUsage {
+        self.usage
+    }
+
+    /// Converts the accumulated output into [`LlmError::Cancelled`].
+    pub fn cancelled(self) -> LlmError {
+        LlmError::Cancelled {
+            partial: self.text,
+            usage: self.usage,
+        }
+    }
+
+    /// Converts the accumulated output into [`LlmError::Interrupted`], for a
+    /// stream that failed with `message` before it finished.
+    pub fn interrupted(self, message: impl Into<String>) -> LlmError {
+        LlmError::Interrupted {
+            message: message.into(),
+            partial: self.text,
+            usage: self.usage,
+        }
+    }
+}
+
+/// Why the model stopped generating.
+///
+/// Each provider's stop-reason field is mapped onto these variants by the
+/// `llm` crate. A [`FinishReason::MaxTokens`] response is truncated; callers
+/// may continue it or report a warning.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum FinishReason {
+    /// The model finished its answer naturally.
+    EndTurn,
+    /// Generation hit the request's `max_tokens` limit.
+    MaxTokens,
+    /// Generation stopped at one of the request's stop sequences.
+    StopSequence,
+    /// The model or the provider's safety system declined to answer.
+    Refusal,
+    /// A provider stop reason with no neutral equivalent, kept verbatim.
+    Other(String),
+}
+
+impl FinishReason {
+    /// Returns `true` if the response was cut off before the model finished.
+    pub fn is_truncated(&self) -> bool {
+        matches!(self, FinishReason::MaxTokens)
+    }
+}
+
+/// A provider-neutral completion response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompletionResponse {
+    /// Concatenated text content generated by the model.
+    pub content: String,
+    /// The model that actually served the request, as reported by the provider.
+    pub model: String,
+    /// Token usage for this call.
+    pub usage: TokenUsage,
+    /// Why generation stopped.
+    pub finish_reason: FinishReason,
+    /// Request ID assigned by the provider, if its response carried one.
+    #[serde(default)]
+    pub provider_request_id: Option<String>,
+}
+
+/// One increment of a streamed completion.
+///
+/// A [`CompletionStream`] yields any number of [`CompletionChunk::Text`]
+/// deltas followed by exactly one [`CompletionChunk::Finished`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompletionChunk {
+    /// Newly generated text, in order.
+    Text(String),
+    /// Generation ended; carries the final token usage.
+    Finished {
+        /// Why generation stopped.
+        finish_reason: FinishReason,
+        /// Token usage for the whole call.
+        usage: TokenUsage,
+    },
+}
+
+// ─── Error type ─────────────────────────────────────────────────────────────
+
+/// Errors returned by [`LlmProvider`] operations.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum LlmError {
+    /// The provider rejected the credentials or the model is not accessible.
+    #[error("LLM provider authentication failed: {message}")]
+    Authentication {
+        /// Human-readable description of the failure.
+        message: String,
+    },
+
+    /// The request was malformed or violates a provider limit.
+    #[error("invalid LLM request: {message}")]
+    InvalidRequest {
+        /// Human-readable description of the problem.
+        message: String,
+    },
+
+    /// The provider's rate limit was hit.
+    #[error("LLM provider rate limit reached")]
+    RateLimited {
+        /// Delay requested by the provider (e.g. from `retry-after`), if any.
+        retry_after: Option<Duration>,
+    },
+
+    /// A transient network or provider-side failure occurred.
+    #[error("LLM provider transient error: {message}")]
+    Transient {
+        /// Human-readable description of the failure.
+        message: String,
+    },
+
+    /// The provider's response could not be parsed.
+    #[error("LLM response parse failure: {message}")]
+    ResponseParse {
+        /// Human-readable description of the parse failure.
+        message: String,
+    },
+
+    /// The call was cancelled (budget exhausted or pipeline halted) before
+    /// the provider finished.
+    ///
+    /// Carries whatever was generated before cancellation so it can be
+    /// recorded in the audit trail. Both are empty/zero when the provider
+    /// does not stream.
+    #[error("LLM call cancelled after {} output tokens", usage.output_tokens)]
+    Cancelled {
+        /// Text generated before cancellation.
+        partial: String,
+        /// Tokens consumed before cancellation, as last reported.
+        usage: TokenUsage,
+    },
+
+    /// A streamed response was cut off by a transient failure after some
+    /// output had arrived.
+    ///
+    /// Retryable like [`LlmError::Transient`], but the gateway can instead
+    /// continue from `partial` rather than regenerating it.
+    #[error("LLM stream interrupted after {} output tokens: {message}", usage.output_tokens)]
+    Interrupted {
+        /// Human-readable description of the failure.
+        message: String,
+        /// Text generated before the interruption.
+        partial: String,
+        /// Tokens consumed before the interruption, as last reported.
+        usage: TokenUsage,
+    },
+
+    /// A provider's own retry loop gave up on a retryable error, either
+    /// after its maximum attempts or at its deadline.
+    ///
+    /// Not retryable again: the provider has already backed off.
+    #[error("LLM call failed after {attempts} attempts: {last}")]
+    ExhaustedRetries {
+        /// Attempts made, including the first.
+        attempts: u32,
+        /// The error of the last attempt.
+        last: Box<LlmError>,
+    },
+}
+
+impl LlmError {
+    /// Returns whether the failed call may be retried.
+    #[must_use]
+    pub fn retry_policy(&self) -> RetryPolicy {
+        match self {
+            Self::RateLimited { retry_after } => RetryPolicy::Retryable {
+                after: *retry_after,
+            },
+            Self::Transient { .. } | Self::Interrupted { .. } => {
+                RetryPolicy::Retryable { after: None }
+            }
+            Self::Authentication { .. }
+            | Self::InvalidRequest { .. }
+            | Self::ResponseParse { .. }
+            | Self::Cancelled { .. }
+            | Self::ExhaustedRetries { .. } => RetryPolicy::NonRetryable,
+        }
+    }
+
+    /// Returns the text generated before the call stopped, for
+    /// [`LlmError::Cancelled`] and [`LlmError::Interrupted`]; `None` for every
+    /// other error.
+    pub fn partial_output(&self) -> Option<&str> {
+        match self {
+            Self::Cancelled { partial, .. } | Self::Interrupted { partial, .. } => Some(partial),
+            _ => None,
+        }
+    }
+}
+
+// ─── Token estimates ────────────────────────────────────────────────────────
+
+/// Characters per token assumed by [`TokenEstimate::heuristic`].
+pub const HEURISTIC_CHARS_PER_TOKEN: usize = 4;
+
+/// Prompt tokens a request is expected to consume, computed before sending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenEstimate {
+    /// Estimated prompt tokens.
+    pub tokens: TokenCount,
+    /// `true` if the count comes from a heuristic rather than the model's
+    /// own tokenizer; expect it to be off by a margin either way.
+    pub approximate: bool,
+}
+
+impl TokenEstimate {
+    /// An estimate counted with the model's tokenizer.
+    pub fn exact(tokens: TokenCount) -> Self {
+        Self {
+            tokens,
+            approximate: false,
+        }
+    }
+
+    /// Estimates `request`'s prompt as its character count (system segments
+    /// and messages) divided by [`HEURISTIC_CHARS_PER_TOKEN`], rounded up.
+    pub fn heuristic(request: &CompletionRequest) -> Self {
+        let chars: usize = request
+            .system
+            .iter()
+            .map(|segment| segment.content.chars().count())
+            .chain(
+                request
+                    .messages
+                    .iter()
+                    .map(|message| message.content.chars().count()),
+            )
+            .sum();
+        let tokens = chars.div_ceil(HEURISTIC_CHARS_PER_TOKEN);
+        Self {
+            tokens: TokenCount::new(u64::try_from(tokens).unwrap_or(u64::MAX)),
+            approximate: true,
+        }
+    }
+}
+
+// ─── Trait ──────────────────────────────────────────────────────────────────
+
+/// A language-model provider.
+///
+/// All LLM calls go through the LLM gateway in the `nodes` crate, which wraps
+/// this trait with constitutional rules, budget enforcement, and auditing.
+/// Implementations only translate and transport.
+///
+/// ## Specification
+///
+/// See `docs/spec/interfaces/domain-traits.md` §LlmProvider.
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    /// Send a completion request and wait for the full response.
+    ///
+    /// # Errors
+    ///
+    /// - [`LlmError::Authentication`] — credentials rejected.
+    /// - [`LlmError::InvalidRequest`] — request violates a provider limit.
+    /// - [`LlmError::RateLimited`] — retry after the indicated delay.
+    /// - [`LlmError::Transient`] — transient failure; may be retried.
+    /// - [`LlmError::ResponseParse`] — unexpected response shape.
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError>;
+
+    /// Send a completion request and return its output as it is generated.
+    ///
+    /// The default calls [`LlmProvider::complete`] and yields the whole
+    /// response as one text chunk; providers that support streaming override
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// As for [`LlmProvider::complete`], for failures before any output. A
+    /// stream that breaks later returns [`LlmError::Interrupted`] from
+    /// [`CompletionStream::next_chunk`], carrying the text received so far.
+    async fn complete_streaming(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<Box<dyn CompletionStream>, LlmError> {
+        let response = self.complete(request).await?;
+        Ok(Box::new(CompletedStream {
+            chunks: vec![
+                CompletionChunk::Finished {
+                    finish_reason: response.finish_reason,
+                    usage: response.usage,
+                },
+                CompletionChunk::Text(response.content),
+            ],
+        }))
+    }
+
+    /// Estimate the prompt tokens `request` will consume, without a network
+    /// call, so a budget can be checked before spending.
+    ///
+    /// The default is [`TokenEstimate::heuristic`], flagged approximate.
+    /// Providers with a local tokenizer for the request's model family
+    /// override it and return [`TokenEstimate::exact`].
+    ///
+    /// # Errors
+    ///
+    /// - [`LlmError::InvalidRequest`] — the request cannot be tokenised for
+    ///   this provider.
+    fn estimate_prompt_tokens(
+        &self,
+        request: &CompletionRequest,
+    ) -> Result<TokenEstimate, LlmError> {
+        Ok(TokenEstimate::heuristic(request))
+    }
+}
+
+/// The output of [`LlmProvider::complete_streaming`], read chunk by chunk.
+#[async_trait]
+pub trait CompletionStream: Send {
+    /// Returns the next chunk, or `Ok(None)` after
+    /// [`CompletionChunk::Finished`].
+    ///
+    /// # Errors
+    ///
+    /// - [`LlmError::Interrupted`] — the stream broke before it finished; the
+    ///   error carries the text and usage received so far.
+    /// - Any error the provider reports mid-stream (e.g.
+    ///   [`LlmError::RateLimited`]).
+    async fn next_chunk(&mut self) -> Result<Option<CompletionChunk>, LlmError>;
+}
+
+/// A [`CompletionStream`] over a response that has already completed.
+struct CompletedStream {
+    /// Remaining chunks, last first.
+    chunks: Vec<CompletionChunk>,
+}
+
+#[async_trait]
+impl CompletionStream for CompletedStream {
+    async fn next_chunk(&mut self) -> Result<Option<CompletionChunk>, LlmError> {
+        Ok(self.chunks.pop())
+    }
+}
+
+#[cfg(test)]
+#[path = "llm_tests.rs"]
+mod tests;
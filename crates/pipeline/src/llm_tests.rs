@@ -0,0 +1,313 @@
+use super::*;
+
+fn segment_contents(request: &CompletionRequest) -> Vec<&str> {
+    request
+        .layered_system()
+        .into_iter()
+        .map(|segment| segment.content.as_str())
+        .collect()
+}
+
+#[test]
+fn test_completion_request_new_defaults_leave_optional_fields_empty() {
+    let request = CompletionRequest::new("model", vec![Message::user("hi")], TokenCount::new(10));
+
+    assert!(request.system.is_empty());
+    assert!(request.stop_sequences.is_empty());
+    assert_eq!(request.temperature, None);
+    assert_eq!(request.request_id, None);
+    assert_eq!(request.max_tokens, TokenCount::new(10));
+}
+
+#[test]
+fn test_with_request_id_sets_client_request_id() {
+    let request = CompletionRequest::new("model", vec![Message::user("hi")], TokenCount::new(10))
+        .with_request_id("run-1/plan/1");
+
+    assert_eq!(request.request_id.as_deref(), Some("run-1/plan/1"));
+}
+
+#[test]
+fn test_with_cache_breakpoints_sets_block_indices() {
+    let request = CompletionRequest::new("model", vec![Message::user("hi")], TokenCount::new(10))
+        .with_cache_breakpoints(vec![0, 2]);
+
+    assert_eq!(request.cache_breakpoints, vec![0, 2]);
+}
+
+#[test]
+fn test_completion_request_deserialize_without_cache_breakpoints_defaults_to_empty() {
+    let mut json = serde_json::to_value(CompletionRequest::new(
+        "model",
+        vec![Message::user("hi")],
+        TokenCount::new(10),
+    ))
+    .unwrap();
+    json.as_object_mut().unwrap().remove("cache_breakpoints");
+
+    let request: CompletionRequest = serde_json::from_value(json).unwrap();
+
+    assert!(request.cache_breakpoints.is_empty());
+}
+
+#[test]
+fn test_completion_request_deserialize_without_request_id_defaults_to_none() {
+    let mut json = serde_json::to_value(CompletionRequest::new(
+        "model",
+        vec![Message::user("hi")],
+        TokenCount::new(10),
+    ))
+    .unwrap();
+    json.as_object_mut().unwrap().remove("request_id");
+
+    let request: CompletionRequest = serde_json::from_value(json).unwrap();
+
+    assert_eq!(request.request_id, None);
+}
+
+#[test]
+fn test_completion_response_deserialize_without_provider_request_id_defaults_to_none() {
+    let json = serde_json::json!({
+        "content": "done",
+        "model": "model",
+        "usage": { "input_tokens": 1, "output_tokens": 1 },
+        "finish_reason": "end_turn",
+    });
+
+    let response: CompletionResponse = serde_json::from_value(json).unwrap();
+
+    assert_eq!(response.provider_request_id, None);
+}
+
+// ─── TokenUsage ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_token_usage_new_has_no_cache_activity() {
+    let usage = TokenUsage::new(TokenCount::new(20), TokenCount::new(2));
+
+    assert_eq!(usage.cache_creation_input_tokens, TokenCount::new(0));
+    assert_eq!(usage.cache_read_input_tokens, TokenCount::new(0));
+}
+
+#[test]
+fn test_token_usage_add_sums_cache_tokens() {
+    let first = TokenUsage {
+        cache_creation_input_tokens: TokenCount::new(1000),
+        ..TokenUsage::new(TokenCount::new(20), TokenCount::new(2))
+    };
+    let second = TokenUsage {
+        cache_read_input_tokens: TokenCount::new(1000),
+        ..TokenUsage::new(TokenCount::new(30), TokenCount::new(3))
+    };
+
+    let total = first + second;
+
+    assert_eq!(total.input_tokens, TokenCount::new(50));
+    assert_eq!(total.output_tokens, TokenCount::new(5));
+    assert_eq!(total.cache_creation_input_tokens, TokenCount::new(1000));
+    assert_eq!(total.cache_read_input_tokens, TokenCount::new(1000));
+}
+
+#[test]
+fn test_token_usage_deserialize_without_cache_fields_defaults_to_zero() {
+    let usage: TokenUsage =
+        serde_json::from_value(serde_json::json!({ "input_tokens": 12, "output_tokens": 3 }))
+            .unwrap();
+
+    assert_eq!(
+        usage,
+        TokenUsage::new(TokenCount::new(12), TokenCount::new(3))
+    );
+}
+
+// ─── Layered system prompt ──────────────────────────────────────────────────
+
+#[test]
+fn test_layered_system_out_of_order_segments_sorted_by_layer() {
+    let mut request =
+        CompletionRequest::new("model", vec![Message::user("hi")], TokenCount::new(10));
+    request.system = vec![
+        SystemSegment::new(SystemLayer::Task, "task"),
+        SystemSegment::new(SystemLayer::Constitutional, "rules"),
+        SystemSegment::new(SystemLayer::Role, "role"),
+    ];
+
+    assert_eq!(segment_contents(&request), ["rules", "role", "task"]);
+}
+
+#[test]
+fn test_layered_system_same_layer_keeps_insertion_order() {
+    let mut request =
+        CompletionRequest::new("model", vec![Message::user("hi")], TokenCount::new(10));
+    request.system = vec![
+        SystemSegment::new(SystemLayer::Task, "first task"),
+        SystemSegment::new(SystemLayer::Role, "role"),
+        SystemSegment::new(SystemLayer::Task, "second task"),
+    ];
+
+    assert_eq!(
+        segment_contents(&request),
+        ["role", "first task", "second task"]
+    );
+}
+
+#[test]
+fn test_layered_system_no_segments_returns_empty() {
+    let request = CompletionRequest::new("model", vec![Message::user("hi")], TokenCount::new(10));
+
+    assert!(request.layered_system().is_empty());
+}
+
+// ─── PartialCompletion ──────────────────────────────────────────────────────
+
+#[test]
+fn test_partial_completion_cancelled_carries_text_and_usage() {
+    let mut partial = PartialCompletion::new();
+    partial.push_text("Roses ");
+    partial.push_text("are");
+    let usage = TokenUsage::new(TokenCount::new(20), TokenCount::new(2));
+    partial.set_usage(usage);
+
+    let error = partial.cancelled();
+
+    match error {
+        LlmError::Cancelled {
+            partial,
+            usage: reported,
+        } => {
+            assert_eq!(partial, "Roses are");
+            assert_eq!(reported, usage);
+        }
+        other => panic!("expected Cancelled, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_partial_completion_new_is_empty_with_zero_usage() {
+    let partial = PartialCompletion::new();
+
+    assert_eq!(partial.text(), "");
+    assert_eq!(partial.usage(), TokenUsage::zero());
+}
+
+#[test]
+fn test_partial_output_cancelled_returns_partial_text() {
+    let mut partial = PartialCompletion::new();
+    partial.push_text("draft");
+
+    assert_eq!(partial.cancelled().partial_output(), Some("draft"));
+}
+
+#[test]
+fn test_partial_output_other_error_returns_none() {
+    let error = LlmError::Transient {
+        message: "overloaded".to_string(),
+    };
+
+    assert_eq!(error.partial_output(), None);
+}
+
+#[test]
+fn test_retry_policy_cancelled_is_non_retryable() {
+    let error = PartialCompletion::new().cancelled();
+
+    assert_eq!(error.retry_policy(), RetryPolicy::NonRetryable);
+}
+
+// ─── Token estimates ────────────────────────────────────────────────────────
+
+/// Relative error allowed between the heuristic and a tokenizer's count.
+const HEURISTIC_TOLERANCE: f64 = 0.25;
+
+/// English prompts with their token counts under the `cl100k_base` encoding.
+const TOKENIZER_FIXTURES: &[(&str, u64)] = &[
+    ("Hello, world!", 4),
+    ("The quick brown fox jumps over the lazy dog.", 10),
+    ("The rules are fixed.", 5),
+];
+
+/// A provider that keeps every default method.
+struct DefaultProvider;
+
+#[async_trait]
+impl LlmProvider for DefaultProvider {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        Err(LlmError::InvalidRequest {
+            message: format!("{} is not served", request.model),
+        })
+    }
+}
+
+#[test]
+fn test_heuristic_counts_system_and_messages_rounding_up() {
+    let mut request = CompletionRequest::new(
+        "model",
+        vec![Message::user("hello"), Message::assistant("hi")],
+        TokenCount::new(10),
+    );
+    request.system = vec![SystemSegment::new(SystemLayer::Task, "task")];
+
+    let estimate = TokenEstimate::heuristic(&request);
+
+    // 4 + 5 + 2 = 11 chars → 3 tokens.
+    assert_eq!(estimate.tokens, TokenCount::new(3));
+    assert!(estimate.approximate);
+}
+
+#[test]
+fn test_heuristic_counts_characters_not_bytes() {
+    let request =
+        CompletionRequest::new("model", vec![Message::user("ééééé")], TokenCount::new(10));
+
+    assert_eq!(
+        TokenEstimate::heuristic(&request).tokens,
+        TokenCount::new(2)
+    );
+}
+
+#[test]
+fn test_heuristic_empty_prompt_returns_zero() {
+    let request = CompletionRequest::new("model", Vec::new(), TokenCount::new(10));
+
+    assert_eq!(
+        TokenEstimate::heuristic(&request).tokens,
+        TokenCount::new(0)
+    );
+}
+
+#[test]
+fn test_heuristic_known_fixtures_within_tolerance() {
+    for &(text, expected) in TOKENIZER_FIXTURES {
+        let request =
+            CompletionRequest::new("model", vec![Message::user(text)], TokenCount::new(10));
+
+        let estimate = TokenEstimate::heuristic(&request).tokens.as_u64();
+
+        let error = (estimate as f64 - expected as f64).abs() / expected as f64;
+        assert!(
+            error <= HEURISTIC_TOLERANCE,
+            "{text:?}: estimated {estimate}, tokenizer counts {expected}"
+        );
+    }
+}
+
+#[test]
+fn test_exact_is_not_approximate() {
+    let estimate = TokenEstimate::exact(TokenCount::new(42));
+
+    assert_eq!(estimate.tokens, TokenCount::new(42));
+    assert!(!estimate.approximate);
+}
+
+#[test]
+fn test_estimate_prompt_tokens_default_returns_heuristic() {
+    let request = CompletionRequest::new(
+        "model",
+        vec![Message::user(TOKENIZER_FIXTURES[1].0)],
+        TokenCount::new(10),
+    );
+
+    let estimate = DefaultProvider.estimate_prompt_tokens(&request).unwrap();
+
+    assert_eq!(estimate, TokenEstimate::heuristic(&request));
+}
@@ -0,0 +1,517 @@
+//! Shared retry loop with attempt metrics.
+//!
+//! The `github` and `llm` crates retry failed calls the same way: an error's
+//! [`RetryPolicy`] decides whether another attempt is allowed, and
+//! [`RetrySchedule`] bounds the attempts and spaces them out. [`retry`] runs
+//! that loop and, when it finishes, reports one [`RetrySample`] to a
+//! [`RetryMetrics`] recorder: how many attempts were made and how long the
+//! whole operation took, labelled by operation name and [`RetryOutcome`].
+//! Comparing attempt counts across operations shows which calls are flaky.
+//!
+//! A schedule bounds one operation. [`RunRetryBudget`] bounds a whole run: it
+//! is shared by every operation the run makes, and [`retry_within_budget`]
+//! charges each retry and its back-off against it. Once the run has spent its
+//! [`RetryBudget`], further retries are refused and the run halts with
+//! [`RetryBudgetExhausted`].
+//!
+//! The loop does not sleep itself (this crate has no async runtime); callers
+//! pass the runtime's sleep, e.g. `tokio::time::sleep`.
+//!
+//! ## Specification
+//!
+//! See `docs/spec/interfaces/shared-types.md` §Retry loop and
+//! `docs/spec/operations.md` §Metrics.
+
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    num::NonZeroU32,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use thiserror::Error;
+
+use crate::{CogWorksError, GitHubOperationError, HaltReason, LlmError, RetryPolicy};
+
+/// Attempts made by [`RetrySchedule::default`], including the first.
+pub const DEFAULT_RETRY_ATTEMPTS: NonZeroU32 = match NonZeroU32::new(3) {
+    Some(attempts) => attempts,
+    None => unreachable!(),
+};
+
+/// First back-off of [`RetrySchedule::default`]; doubled after each retry.
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Longest single back-off of [`RetrySchedule::default`].
+pub const DEFAULT_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Retries a run may make across all operations under
+/// [`RetryBudget::default`].
+pub const DEFAULT_RUN_MAX_RETRIES: u32 = 20;
+
+/// Total back-off a run may wait across all operations under
+/// [`RetryBudget::default`].
+pub const DEFAULT_RUN_MAX_RETRY_TIME: Duration = Duration::from_secs(600);
+
+// ─── Schedule ───────────────────────────────────────────────────────────────
+
+/// How many attempts [`retry`] makes and how long it waits between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetrySchedule {
+    /// Maximum attempts, including the first.
+    pub max_attempts: NonZeroU32,
+    /// Base back-off before the first retry; doubled for each later one.
+    pub backoff: Duration,
+    /// Cap on any single back-off, including a
+    /// [`RetryPolicy::Retryable`] `after` delay.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetrySchedule {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_RETRY_ATTEMPTS,
+            backoff: DEFAULT_RETRY_BACKOFF,
+            max_backoff: DEFAULT_RETRY_MAX_BACKOFF,
+        }
+    }
+}
+
+impl RetrySchedule {
+    /// Wait before retry number `retry` (1 for the first retry) after a
+    /// failure with `policy`, or `None` if it must not be retried.
+    ///
+    /// Delegates to [`RetryPolicy::backoff_for_attempt_jittered`] with this
+    /// schedule's `backoff` and `max_backoff`, so the exponential delay is
+    /// fully jittered using `rng` and an explicit `after` is honoured as
+    /// given, capped at `max_backoff`.
+    pub fn delay(
+        &self,
+        retry: u32,
+        policy: &RetryPolicy,
+        rng: &mut dyn FnMut() -> u64,
+    ) -> Option<Duration> {
+        policy.backoff_for_attempt_jittered(
+            retry.saturating_sub(1),
+            self.backoff,
+            self.max_backoff,
+            rng,
+        )
+    }
+}
+
+/// Returns a pseudo-random `u64` source for back-off jitter, seeded
+/// differently on every call (xorshift64 over a [`RandomState`] seed). Not
+/// for anything security-sensitive.
+pub fn jitter_source() -> impl FnMut() -> u64 + Send {
+    let mut state = RandomState::new().build_hasher().finish() | 1;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    }
+}
+
+// ─── Metrics ────────────────────────────────────────────────────────────────
+
+/// How a retried operation ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetryOutcome {
+    /// An attempt succeeded.
+    Success,
+    /// The last attempt failed with a retryable error.
+    Exhausted,
+    /// An attempt failed with an error that must not be retried.
+    NonRetryable,
+    /// An attempt failed with a retryable error but the run's retry budget
+    /// was spent.
+    BudgetExhausted,
+}
+
+impl RetryOutcome {
+    /// Metric label value: `success`, `exhausted`, `non_retryable`, or
+    /// `budget_exhausted`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RetryOutcome::Success => "success",
+            RetryOutcome::Exhausted => "exhausted",
+            RetryOutcome::NonRetryable => "non_retryable",
+            RetryOutcome::BudgetExhausted => "budget_exhausted",
+        }
+    }
+}
+
+/// One finished call to [`retry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetrySample {
+    /// Operation label given to [`retry`] (e.g. `"github.get_issue"`).
+    pub operation: String,
+    /// How the operation ended.
+    pub outcome: RetryOutcome,
+    /// Attempts made, including the first.
+    pub attempts: u32,
+    /// Time from the first attempt starting to the last one finishing,
+    /// including back-off waits.
+    pub duration: Duration,
+}
+
+/// Receives a [`RetrySample`] for every finished [`retry`] call.
+///
+/// Implementations export `cogworks_retry_attempts_total` (counter, incremented
+/// by `attempts`) and `cogworks_retry_duration_seconds` (histogram), both
+/// labelled by `operation` and `outcome`.
+pub trait RetryMetrics: Send + Sync {
+    /// Records one finished operation.
+    fn record(&self, sample: &RetrySample);
+}
+
+/// [`RetryMetrics`] that discards every sample.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopRetryMetrics;
+
+impl RetryMetrics for NoopRetryMetrics {
+    fn record(&self, _sample: &RetrySample) {}
+}
+
+/// [`RetryMetrics`] that keeps every sample in memory, e.g. for tests.
+#[derive(Debug, Default)]
+pub struct InMemoryRetryMetrics {
+    samples: Mutex<Vec<RetrySample>>,
+}
+
+impl InMemoryRetryMetrics {
+    /// Creates an empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the recorded samples, oldest first.
+    pub fn samples(&self) -> Vec<RetrySample> {
+        self.samples
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl RetryMetrics for InMemoryRetryMetrics {
+    fn record(&self, sample: &RetrySample) {
+        self.samples
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(sample.clone());
+    }
+}
+
+// ─── Run budget ─────────────────────────────────────────────────────────────
+
+/// Retries one run may make across all of its operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryBudget {
+    /// Maximum retries, not counting each operation's first attempt.
+    pub max_retries: u32,
+    /// Maximum total back-off waited before those retries.
+    pub max_retry_time: Duration,
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_RUN_MAX_RETRIES,
+            max_retry_time: DEFAULT_RUN_MAX_RETRY_TIME,
+        }
+    }
+}
+
+/// Retries spent from a [`RunRetryBudget`] so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetryBudgetUsage {
+    /// Retries made.
+    pub retries: u32,
+    /// Back-off waited before those retries.
+    pub retry_time: Duration,
+}
+
+/// The run's retry budget is spent; the run must halt.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error(
+    "run retry budget exhausted: {} retries and {:?} of back-off used, limit {} retries and {:?}",
+    .usage.retries,
+    .usage.retry_time,
+    .budget.max_retries,
+    .budget.max_retry_time
+)]
+pub struct RetryBudgetExhausted {
+    /// The budget that was exceeded.
+    pub budget: RetryBudget,
+    /// What had been spent when the next retry was refused.
+    pub usage: RetryBudgetUsage,
+}
+
+impl From<RetryBudgetExhausted> for CogWorksError {
+    fn from(exhausted: RetryBudgetExhausted) -> Self {
+        CogWorksError::PipelineHalt {
+            reason: HaltReason::RetryBudget,
+            detail: Some(exhausted.to_string()),
+        }
+    }
+}
+
+/// Retry budget of one run, shared by all of its operations.
+///
+/// Create one per run and pass it by reference to every
+/// [`retry_within_budget`] call the run makes.
+#[derive(Debug, Default)]
+pub struct RunRetryBudget {
+    budget: RetryBudget,
+    usage: Mutex<RetryBudgetUsage>,
+}
+
+impl RunRetryBudget {
+    /// Creates an unspent budget.
+    pub fn new(budget: RetryBudget) -> Self {
+        Self {
+            budget,
+            usage: Mutex::new(RetryBudgetUsage::default()),
+        }
+    }
+
+    /// Returns the configured limits.
+    pub fn budget(&self) -> RetryBudget {
+        self.budget
+    }
+
+    /// Returns what has been spent so far.
+    pub fn usage(&self) -> RetryBudgetUsage {
+        *self
+            .usage
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Spends one retry preceded by a `delay` back-off.
+    ///
+    /// Nothing is spent when the call fails.
+    ///
+    /// # Errors
+    ///
+    /// [`RetryBudgetExhausted`] if the retry would exceed
+    /// [`RetryBudget::max_retries`] or take the total back-off past
+    /// [`RetryBudget::max_retry_time`].
+    pub fn try_spend(&self, delay: Duration) -> Result<(), RetryBudgetExhausted> {
+        let mut usage = self
+            .usage
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let retry_time = usage.retry_time.saturating_add(delay);
+        if usage.retries >= self.budget.max_retries || retry_time > self.budget.max_retry_time {
+            return Err(RetryBudgetExhausted {
+                budget: self.budget,
+                usage: *usage,
+            });
+        }
+        usage.retries += 1;
+        usage.retry_time = retry_time;
+        Ok(())
+    }
+}
+
+/// Errors returned by [`retry_within_budget`].
+#[derive(Debug, Error)]
+pub enum RetryBudgetError<E> {
+    /// The operation failed and its error ended the loop, as in [`retry`].
+    #[error(transparent)]
+    Failed(E),
+
+    /// A retryable failure was not retried because the run's budget is spent.
+    #[error("{exhausted}")]
+    Exhausted {
+        /// The spent budget.
+        exhausted: RetryBudgetExhausted,
+        /// The error of the last attempt.
+        last_error: E,
+    },
+}
+
+// ─── Retry loop ─────────────────────────────────────────────────────────────
+
+/// An error that tells the retry loop whether to try again.
+pub trait RetryableError {
+    /// Returns whether the failed operation may be retried.
+    fn retry_policy(&self) -> RetryPolicy;
+}
+
+impl RetryableError for LlmError {
+    fn retry_policy(&self) -> RetryPolicy {
+        LlmError::retry_policy(self)
+    }
+}
+
+impl RetryableError for CogWorksError {
+    fn retry_policy(&self) -> RetryPolicy {
+        CogWorksError::retry_policy(self)
+    }
+}
+
+impl RetryableError for GitHubOperationError {
+    fn retry_policy(&self) -> RetryPolicy {
+        GitHubOperationError::retry_policy(self)
+    }
+}
+
+/// Runs `attempt` until it succeeds, fails with a non-retryable error, or
+/// `schedule.max_attempts` attempts have been made.
+///
+/// Between attempts, waits [`RetrySchedule::delay`] using `sleep`, jittered
+/// by a fresh [`jitter_source`]. When the
+/// loop ends, records one [`RetrySample`] labelled `operation` in `metrics`.
+///
+/// # Errors
+///
+/// The error of the last attempt.
+pub async fn retry<T, E, Attempt, AttemptFut, Sleep, SleepFut>(
+    operation: &str,
+    schedule: RetrySchedule,
+    metrics: &dyn RetryMetrics,
+    attempt: Attempt,
+    sleep: Sleep,
+) -> Result<T, E>
+where
+    E: RetryableError,
+    Attempt: FnMut() -> AttemptFut,
+    AttemptFut: Future<Output = Result<T, E>>,
+    Sleep: FnMut(Duration) -> SleepFut,
+    SleepFut: Future<Output = ()>,
+{
+    run_loop(
+        operation,
+        schedule,
+        None,
+        metrics,
+        &mut jitter_source(),
+        attempt,
+        sleep,
+    )
+    .await
+    .map_err(|error| match error {
+        RetryBudgetError::Failed(error)
+        | RetryBudgetError::Exhausted {
+            last_error: error, ..
+        } => error,
+    })
+}
+
+/// Runs `attempt` like [`retry`], charging every retry to the run's `budget`.
+///
+/// Before each wait, one retry and its [`RetrySchedule::delay`] are spent
+/// from `budget`. If the budget cannot cover them, the loop stops without
+/// waiting and records a [`RetryOutcome::BudgetExhausted`] sample.
+///
+/// # Errors
+///
+/// - [`RetryBudgetError::Exhausted`] — a retryable failure could not be
+///   retried because the run's budget is spent; carries the last error.
+/// - [`RetryBudgetError::Failed`] — the error of the last attempt, when the
+///   loop ended as [`retry`] would have.
+pub async fn retry_within_budget<T, E, Attempt, AttemptFut, Sleep, SleepFut>(
+    operation: &str,
+    schedule: RetrySchedule,
+    budget: &RunRetryBudget,
+    metrics: &dyn RetryMetrics,
+    attempt: Attempt,
+    sleep: Sleep,
+) -> Result<T, RetryBudgetError<E>>
+where
+    E: RetryableError,
+    Attempt: FnMut() -> AttemptFut,
+    AttemptFut: Future<Output = Result<T, E>>,
+    Sleep: FnMut(Duration) -> SleepFut,
+    SleepFut: Future<Output = ()>,
+{
+    run_loop(
+        operation,
+        schedule,
+        Some(budget),
+        metrics,
+        &mut jitter_source(),
+        attempt,
+        sleep,
+    )
+    .await
+}
+
+/// The loop behind [`retry`] and [`retry_within_budget`].
+async fn run_loop<T, E, Attempt, AttemptFut, Sleep, SleepFut>(
+    operation: &str,
+    schedule: RetrySchedule,
+    budget: Option<&RunRetryBudget>,
+    metrics: &dyn RetryMetrics,
+    rng: &mut (dyn FnMut() -> u64 + Send),
+    mut attempt: Attempt,
+    mut sleep: Sleep,
+) -> Result<T, RetryBudgetError<E>>
+where
+    E: RetryableError,
+    Attempt: FnMut() -> AttemptFut,
+    AttemptFut: Future<Output = Result<T, E>>,
+    Sleep: FnMut(Duration) -> SleepFut,
+    SleepFut: Future<Output = ()>,
+{
+    let started = Instant::now();
+    let mut attempts = 0;
+    let (result, outcome) = loop {
+        attempts += 1;
+        let error = match attempt().await {
+            Ok(value) => break (Ok(value), RetryOutcome::Success),
+            Err(error) => error,
+        };
+        let Some(delay) = schedule.delay(attempts, &error.retry_policy(), rng) else {
+            break (
+                Err(RetryBudgetError::Failed(error)),
+                RetryOutcome::NonRetryable,
+            );
+        };
+        if attempts >= schedule.max_attempts.get() {
+            break (
+                Err(RetryBudgetError::Failed(error)),
+                RetryOutcome::Exhausted,
+            );
+        }
+        if let Some(Err(exhausted)) = budget.map(|budget| budget.try_spend(delay)) {
+            tracing::error!(operation, attempts, %exhausted, "run retry budget exhausted");
+            break (
+                Err(RetryBudgetError::Exhausted {
+                    exhausted,
+                    last_error: error,
+                }),
+                RetryOutcome::BudgetExhausted,
+            );
+        }
+        tracing::debug!(operation, attempts, ?delay, "retryable failure; retrying");
+        sleep(delay).await;
+    };
+
+    let sample = RetrySample {
+        operation: operation.to_string(),
+        outcome,
+        attempts,
+        duration: started.elapsed(),
+    };
+    if outcome != RetryOutcome::Success {
+        tracing::warn!(
+            operation,
+            attempts,
+            outcome = outcome.as_str(),
+            "operation failed"
+        );
+    }
+    metrics.record(&sample);
+    result
+}
+
+#[cfg(test)]
+#[path = "retry_tests.rs"]
+mod tests;
@@ -0,0 +1,478 @@
+use std::{cell::RefCell, future::ready};
+
+use super::*;
+
+// ─── Helpers ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum TestError {
+    Transient,
+    RetryAfter(Duration),
+    Fatal,
+}
+
+impl RetryableError for TestError {
+    fn retry_policy(&self) -> RetryPolicy {
+        match self {
+            TestError::Transient => RetryPolicy::Retryable { after: None },
+            TestError::RetryAfter(after) => RetryPolicy::Retryable {
+                after: Some(*after),
+            },
+            TestError::Fatal => RetryPolicy::NonRetryable,
+        }
+    }
+}
+
+fn schedule(max_attempts: u32) -> RetrySchedule {
+    RetrySchedule {
+        max_attempts: NonZeroU32::new(max_attempts).unwrap(),
+        backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(100),
+    }
+}
+
+/// Attempt closure that replays `results` in order.
+fn scripted(
+    results: Vec<Result<u32, TestError>>,
+) -> impl FnMut() -> std::future::Ready<Result<u32, TestError>> {
+    let mut results = results.into_iter();
+    move || ready(results.next().expect("more attempts than scripted"))
+}
+
+// ─── RetrySchedule ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_retry_schedule_default_uses_documented_constants() {
+    let schedule = RetrySchedule::default();
+
+    assert_eq!(schedule.max_attempts, DEFAULT_RETRY_ATTEMPTS);
+    assert_eq!(schedule.backoff, DEFAULT_RETRY_BACKOFF);
+    assert_eq!(schedule.max_backoff, DEFAULT_RETRY_MAX_BACKOFF);
+}
+
+#[test]
+fn test_retry_schedule_delay_matches_jittered_policy_backoff() {
+    let schedule = schedule(5);
+    let policy = RetryPolicy::Retryable { after: None };
+
+    for retry in 1..=4 {
+        let expected = policy.backoff_for_attempt_jittered(
+            retry - 1,
+            schedule.backoff,
+            schedule.max_backoff,
+            &mut || 3_000_017,
+        );
+        assert_eq!(
+            schedule.delay(retry, &policy, &mut || 3_000_017),
+            expected,
+            "retry {retry}"
+        );
+    }
+}
+
+#[test]
+fn test_retry_schedule_delay_max_rng_returns_capped_exponential() {
+    let schedule = schedule(5);
+    let policy = RetryPolicy::Retryable { after: None };
+    // rng() % (ceiling + 1) == ceiling when rng() returns ceiling.
+    let ceiling = |ms: u64| Duration::from_millis(ms).as_nanos() as u64;
+
+    assert_eq!(
+        schedule.delay(1, &policy, &mut || ceiling(10)),
+        Some(Duration::from_millis(10))
+    );
+    assert_eq!(
+        schedule.delay(3, &policy, &mut || ceiling(40)),
+        Some(Duration::from_millis(40))
+    );
+    assert_eq!(
+        schedule.delay(10, &policy, &mut || ceiling(100)),
+        Some(Duration::from_millis(100))
+    );
+}
+
+#[test]
+fn test_retry_schedule_delay_explicit_after_capped_at_max_backoff() {
+    let schedule = schedule(3);
+
+    let short = RetryPolicy::Retryable {
+        after: Some(Duration::from_millis(70)),
+    };
+    let long = RetryPolicy::Retryable {
+        after: Some(Duration::from_secs(5)),
+    };
+
+    assert_eq!(
+        schedule.delay(1, &short, &mut || 0),
+        Some(Duration::from_millis(70))
+    );
+    assert_eq!(
+        schedule.delay(1, &long, &mut || 0),
+        Some(Duration::from_millis(100))
+    );
+}
+
+#[test]
+fn test_retry_schedule_delay_non_retryable_returns_none() {
+    assert_eq!(
+        schedule(3).delay(1, &RetryPolicy::NonRetryable, &mut || 0),
+        None
+    );
+}
+
+#[test]
+fn test_jitter_source_successive_values_differ() {
+    let mut rng = jitter_source();
+
+    let values: Vec<u64> = (0..4).map(|_| rng()).collect();
+
+    assert!(values.windows(2).all(|pair| pair[0] != pair[1]));
+}
+
+// ─── retry ──────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_retry_two_failures_then_success_records_three_attempts() {
+    let metrics = InMemoryRetryMetrics::new();
+    let waits = RefCell::new(Vec::new());
+
+    let result = retry(
+        "github.get_issue",
+        schedule(3),
+        &metrics,
+        scripted(vec![
+            Err(TestError::Transient),
+            Err(TestError::Transient),
+            Ok(7),
+        ]),
+        |delay| {
+            waits.borrow_mut().push(delay);
+            ready(())
+        },
+    )
+    .await;
+
+    assert_eq!(result, Ok(7));
+    let samples = metrics.samples();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].operation, "github.get_issue");
+    assert_eq!(samples[0].outcome, RetryOutcome::Success);
+    assert_eq!(samples[0].attempts, 3);
+    let waits = waits.into_inner();
+    assert_eq!(waits.len(), 2);
+    assert!(waits[0] <= Duration::from_millis(10));
+    assert!(waits[1] <= Duration::from_millis(20));
+}
+
+#[tokio::test]
+async fn test_retry_first_attempt_succeeds_records_one_attempt_without_wait() {
+    let metrics = InMemoryRetryMetrics::new();
+    let mut waited = false;
+
+    let result = retry("op", schedule(3), &metrics, scripted(vec![Ok(1)]), |_| {
+        waited = true;
+        ready(())
+    })
+    .await;
+
+    assert_eq!(result, Ok(1));
+    assert!(!waited);
+    assert_eq!(metrics.samples()[0].attempts, 1);
+}
+
+#[tokio::test]
+async fn test_retry_non_retryable_error_stops_immediately() {
+    let metrics = InMemoryRetryMetrics::new();
+
+    let result = retry(
+        "op",
+        schedule(3),
+        &metrics,
+        scripted(vec![Err(TestError::Fatal)]),
+        |_| ready(()),
+    )
+    .await;
+
+    assert_eq!(result, Err(TestError::Fatal));
+    let sample = &metrics.samples()[0];
+    assert_eq!(sample.outcome, RetryOutcome::NonRetryable);
+    assert_eq!(sample.attempts, 1);
+}
+
+#[tokio::test]
+async fn test_retry_all_attempts_fail_returns_last_error_as_exhausted() {
+    let metrics = InMemoryRetryMetrics::new();
+    let last = TestError::RetryAfter(Duration::from_millis(1));
+
+    let result = retry(
+        "op",
+        schedule(2),
+        &metrics,
+        scripted(vec![Err(TestError::Transient), Err(last.clone())]),
+        |_| ready(()),
+    )
+    .await;
+
+    assert_eq!(result, Err(last));
+    let sample = &metrics.samples()[0];
+    assert_eq!(sample.outcome, RetryOutcome::Exhausted);
+    assert_eq!(sample.attempts, 2);
+}
+
+#[tokio::test]
+async fn test_retry_explicit_after_waits_requested_delay() {
+    let metrics = NoopRetryMetrics;
+    let waits = RefCell::new(Vec::new());
+
+    let result = retry(
+        "op",
+        schedule(2),
+        &metrics,
+        scripted(vec![
+            Err(TestError::RetryAfter(Duration::from_millis(60))),
+            Ok(2),
+        ]),
+        |delay| {
+            waits.borrow_mut().push(delay);
+            ready(())
+        },
+    )
+    .await;
+
+    assert_eq!(result, Ok(2));
+    assert_eq!(waits.into_inner(), vec![Duration::from_millis(60)]);
+}
+
+// ─── RunRetryBudget ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_run_retry_budget_try_spend_within_limits_records_usage() {
+    let budget = RunRetryBudget::new(RetryBudget {
+        max_retries: 2,
+        max_retry_time: Duration::from_secs(1),
+    });
+
+    budget.try_spend(Duration::from_millis(300)).unwrap();
+    budget.try_spend(Duration::from_millis(200)).unwrap();
+
+    assert_eq!(
+        budget.usage(),
+        RetryBudgetUsage {
+            retries: 2,
+            retry_time: Duration::from_millis(500),
+        }
+    );
+}
+
+#[test]
+fn test_run_retry_budget_try_spend_past_max_retries_fails_without_spending() {
+    let budget = RunRetryBudget::new(RetryBudget {
+        max_retries: 1,
+        max_retry_time: Duration::from_secs(1),
+    });
+    budget.try_spend(Duration::ZERO).unwrap();
+
+    let exhausted = budget.try_spend(Duration::ZERO).unwrap_err();
+
+    assert_eq!(exhausted.usage.retries, 1);
+    assert_eq!(budget.usage().retries, 1);
+}
+
+#[test]
+fn test_run_retry_budget_try_spend_past_max_retry_time_fails() {
+    let budget = RunRetryBudget::new(RetryBudget {
+        max_retries: 10,
+        max_retry_time: Duration::from_millis(100),
+    });
+    budget.try_spend(Duration::from_millis(80)).unwrap();
+
+    let exhausted = budget.try_spend(Duration::from_millis(30)).unwrap_err();
+
+    assert_eq!(exhausted.usage.retry_time, Duration::from_millis(80));
+    assert_eq!(budget.usage().retry_time, Duration::from_millis(80));
+}
+
+#[test]
+fn test_retry_budget_exhausted_into_cogworks_error_halts_with_retry_budget() {
+    let exhausted = RetryBudgetExhausted {
+        budget: RetryBudget::default(),
+        usage: RetryBudgetUsage::default(),
+    };
+
+    let error: CogWorksError = exhausted.into();
+
+    assert!(matches!(
+        error,
+        CogWorksError::PipelineHalt {
+            reason: HaltReason::RetryBudget,
+            detail: Some(_),
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_retry_within_budget_spent_budget_returns_exhausted_with_last_error() {
+    let metrics = InMemoryRetryMetrics::new();
+    let budget = RunRetryBudget::new(RetryBudget {
+        max_retries: 1,
+        max_retry_time: Duration::from_secs(1),
+    });
+
+    let result = retry_within_budget(
+        "op",
+        schedule(5),
+        &budget,
+        &metrics,
+        scripted(vec![Err(TestError::Transient), Err(TestError::Transient)]),
+        |_| ready(()),
+    )
+    .await;
+
+    match result {
+        Err(RetryBudgetError::Exhausted { last_error, .. }) => {
+            assert_eq!(last_error, TestError::Transient);
+        }
+        other => panic!("expected budget exhaustion, got {other:?}"),
+    }
+    let sample = &metrics.samples()[0];
+    assert_eq!(sample.outcome, RetryOutcome::BudgetExhausted);
+    assert_eq!(sample.attempts, 2);
+    assert_eq!(budget.usage().retries, 1);
+}
+
+#[tokio::test]
+async fn test_retry_within_budget_shared_budget_charged_across_operations() {
+    let metrics = NoopRetryMetrics;
+    let budget = RunRetryBudget::new(RetryBudget::default());
+
+    for _ in 0..2 {
+        let result = retry_within_budget(
+            "op",
+            schedule(3),
+            &budget,
+            &metrics,
+            scripted(vec![Err(TestError::Transient), Ok(1)]),
+            |_| ready(()),
+        )
+        .await;
+        assert!(matches!(result, Ok(1)));
+    }
+
+    assert_eq!(budget.usage().retries, 2);
+}
+
+#[tokio::test]
+async fn test_retry_within_budget_non_retryable_returns_failed() {
+    let budget = RunRetryBudget::new(RetryBudget::default());
+
+    let result = retry_within_budget(
+        "op",
+        schedule(3),
+        &budget,
+        &NoopRetryMetrics,
+        scripted(vec![Err(TestError::Fatal)]),
+        |_| ready(()),
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(RetryBudgetError::Failed(TestError::Fatal))
+    ));
+    assert_eq!(budget.usage(), RetryBudgetUsage::default());
+}
+
+#[tokio::test]
+async fn test_retry_within_budget_caps_total_retries_across_operations() {
+    let budget = RunRetryBudget::new(RetryBudget {
+        max_retries: 3,
+        max_retry_time: Duration::from_secs(60),
+    });
+
+    let first = retry_within_budget(
+        "first",
+        schedule(5),
+        &budget,
+        &NoopRetryMetrics,
+        scripted(vec![
+            Err(TestError::Transient),
+            Err(TestError::Transient),
+            Ok(1),
+        ]),
+        |_| ready(()),
+    )
+    .await;
+    let attempts = RefCell::new(0);
+    let second = retry_within_budget(
+        "second",
+        schedule(5),
+        &budget,
+        &NoopRetryMetrics,
+        || {
+            *attempts.borrow_mut() += 1;
+            ready(Err::<u32, _>(TestError::Transient))
+        },
+        |_| ready(()),
+    )
+    .await;
+
+    assert!(matches!(first, Ok(1)));
+    assert!(matches!(second, Err(RetryBudgetError::Exhausted { .. })));
+    assert_eq!(*attempts.borrow(), 2);
+    assert_eq!(budget.usage().retries, 3);
+}
+
+#[tokio::test]
+async fn test_retry_within_budget_caps_total_back_off_across_operations() {
+    let budget = RunRetryBudget::new(RetryBudget {
+        max_retries: 100,
+        max_retry_time: Duration::from_secs(5),
+    });
+    let schedule = RetrySchedule {
+        max_backoff: Duration::from_secs(10),
+        ..schedule(5)
+    };
+    let waits = RefCell::new(Vec::new());
+
+    let first = retry_within_budget(
+        "first",
+        schedule,
+        &budget,
+        &NoopRetryMetrics,
+        scripted(vec![
+            Err(TestError::RetryAfter(Duration::from_secs(4))),
+            Ok(1),
+        ]),
+        |delay| {
+            waits.borrow_mut().push(delay);
+            ready(())
+        },
+    )
+    .await;
+    let second = retry_within_budget(
+        "second",
+        schedule,
+        &budget,
+        &NoopRetryMetrics,
+        scripted(vec![Err(TestError::RetryAfter(Duration::from_secs(4)))]),
+        |delay| {
+            waits.borrow_mut().push(delay);
+            ready(())
+        },
+    )
+    .await;
+
+    assert!(matches!(first, Ok(1)));
+    match second {
+        Err(RetryBudgetError::Exhausted { exhausted, .. }) => {
+            assert_eq!(exhausted.usage.retry_time, Duration::from_secs(4));
+        }
+        other => panic!("expected budget exhaustion, got {other:?}"),
+    }
+    assert_eq!(*waits.borrow(), vec![Duration::from_secs(4)]);
+}
+
+#[test]
+fn test_retry_outcome_as_str_budget_exhausted_returns_label() {
+    assert_eq!(RetryOutcome::BudgetExhausted.as_str(), "budget_exhausted");
+}
@@ -8,7 +8,9 @@
 //!
 //! See `docs/spec/interfaces/shared-types.md` §Value Types for the full contract.
 
-use chrono::{DateTime, Utc};
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::ArtifactPath;
@@ -18,7 +20,9 @@ use crate::ArtifactPath;
 // ---------------------------------------------------------------------------
 
 /// Number of tokens consumed or budgeted in an LLM API call.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
 pub struct TokenCount(u64);
 
 impl TokenCount {
@@ -36,6 +40,19 @@ impl TokenCount {
     pub fn is_zero(self) -> bool {
         self.0 == 0
     }
+
+    /// Subtracts `rhs`, returning `None` if it exceeds `self`.
+    #[must_use]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Subtracts `rhs`, clamping at zero (e.g. the tokens left in a budget
+    /// that has been overspent).
+    #[must_use]
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
 }
 
 impl std::fmt::Display for TokenCount {
@@ -94,6 +111,27 @@ impl TokenCost {
     pub fn is_zero(self) -> bool {
         self.0 == 0.0
     }
+
+    /// Cost of `count` tokens priced at `usd_per_thousand` USD per 1000
+    /// tokens.
+    ///
+    /// Returns `None` if the price is negative, infinite, or NaN.
+    #[must_use]
+    pub fn from_tokens(count: TokenCount, usd_per_thousand: f64) -> Option<Self> {
+        Self::new(usd_per_thousand)?.checked_mul(count.as_u64() as f64 / 1000.0)
+    }
+
+    /// Multiplies the cost by `factor`.
+    ///
+    /// Returns `None` if `factor` is negative or NaN, or the product is not
+    /// finite.
+    #[must_use]
+    pub fn checked_mul(self, factor: f64) -> Option<Self> {
+        if factor.is_nan() || factor < 0.0 {
+            return None;
+        }
+        Self::new(self.0 * factor)
+    }
 }
 
 impl std::fmt::Display for TokenCost {
@@ -115,6 +153,15 @@ impl std::ops::AddAssign for TokenCost {
     }
 }
 
+/// Same as [`TokenCost::checked_mul`]; the product is `None` when it would
+/// not be a valid cost.
+impl std::ops::Mul<f64> for TokenCost {
+    type Output = Option<TokenCost>;
+    fn mul(self, factor: f64) -> Option<TokenCost> {
+        self.checked_mul(factor)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 /// Maximum token cost permitted for a pipeline run, a node, or a parallel
@@ -147,6 +194,20 @@ impl CostBudget {
     pub fn is_exceeded_by(self, accumulated: TokenCost) -> bool {
         accumulated.as_f64() >= self.0
     }
+
+    /// Returns the spend left before `accumulated` reaches this budget, or
+    /// zero once it has.
+    #[must_use]
+    pub fn remaining(self, accumulated: TokenCost) -> TokenCost {
+        TokenCost::new(self.0 - accumulated.as_f64()).unwrap_or_else(TokenCost::zero)
+    }
+
+    /// Returns `accumulated` as a fraction of this budget: `0.0` for no
+    /// spend, `1.0` at the limit, and above `1.0` once exceeded.
+    pub fn fraction_used(self, accumulated: TokenCost) -> f64 {
+        // `self.0` is strictly positive, so the ratio is always finite.
+        accumulated.as_f64() / self.0
+    }
 }
 
 impl std::fmt::Display for CostBudget {
@@ -182,6 +243,31 @@ impl SatisfactionScore {
     pub fn as_f64(self) -> f64 {
         self.0
     }
+
+    /// Weighted mean of `(score, weight)` pairs.
+    ///
+    /// Weights are normalised by their total, so only their ratios matter;
+    /// equal weights give the plain mean. Returns `None` if `pairs` is empty,
+    /// any weight is negative or not finite, or the weights sum to zero.
+    #[must_use]
+    pub fn weighted_mean(pairs: &[(SatisfactionScore, f64)]) -> Option<Self> {
+        if pairs
+            .iter()
+            .any(|(_, weight)| !weight.is_finite() || *weight < 0.0)
+        {
+            return None;
+        }
+        let total_weight: f64 = pairs.iter().map(|(_, weight)| weight).sum();
+        if !total_weight.is_finite() || total_weight <= 0.0 {
+            return None;
+        }
+        let weighted_sum: f64 = pairs
+            .iter()
+            .map(|(score, weight)| score.0 * (weight / total_weight))
+            .sum();
+        // Rounding can push the sum a hair outside the range.
+        Self::new(weighted_sum.clamp(0.0, 1.0))
+    }
 }
 
 impl std::fmt::Display for SatisfactionScore {
@@ -231,6 +317,8 @@ impl std::fmt::Display for AlignmentScore {
 ///
 /// Used consistently across domain service responses, alignment findings, and
 /// review results.
+///
+/// Ordered by how serious a finding is: `Blocking > Warning > Informational`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DiagnosticSeverity {
@@ -242,6 +330,29 @@ pub enum DiagnosticSeverity {
     Informational,
 }
 
+impl DiagnosticSeverity {
+    /// Rank used for ordering; higher is more serious.
+    fn rank(self) -> u8 {
+        match self {
+            Self::Informational => 0,
+            Self::Warning => 1,
+            Self::Blocking => 2,
+        }
+    }
+}
+
+impl PartialOrd for DiagnosticSeverity {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DiagnosticSeverity {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 /// Diagnostic category tag.
@@ -269,6 +380,15 @@ impl DiagnosticCategory {
         }
     }
 
+    /// Creates a [`DiagnosticCategory`] from a category constant.
+    ///
+    /// For the `*_CATEGORY` constants callers define, which are never empty;
+    /// use [`DiagnosticCategory::new`] for strings read at run time.
+    pub fn from_static(category: &'static str) -> Self {
+        debug_assert!(!category.is_empty(), "diagnostic category is empty");
+        Self(category.to_string())
+    }
+
     /// Returns the category tag as a string slice.
     pub fn as_str(&self) -> &str {
         &self.0
@@ -310,6 +430,31 @@ pub struct Diagnostic {
     pub message: String,
 }
 
+/// Returns the most serious severity among `diagnostics`, or `None` if there
+/// are none.
+pub fn highest_severity(diagnostics: &[Diagnostic]) -> Option<DiagnosticSeverity> {
+    diagnostics.iter().map(|d| d.severity).max()
+}
+
+/// Returns `true` if any of `diagnostics` is [`DiagnosticSeverity::Blocking`].
+pub fn has_blocking(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics
+        .iter()
+        .any(|d| d.severity == DiagnosticSeverity::Blocking)
+}
+
+/// Counts `diagnostics` per severity.
+///
+/// Severities with no findings are absent from the map. Iteration is from
+/// least to most serious.
+pub fn count_by_severity(diagnostics: &[Diagnostic]) -> BTreeMap<DiagnosticSeverity, usize> {
+    let mut counts = BTreeMap::new();
+    for diagnostic in diagnostics {
+        *counts.entry(diagnostic.severity).or_insert(0) += 1;
+    }
+    counts
+}
+
 // ---------------------------------------------------------------------------
 // Versioning
 // ---------------------------------------------------------------------------
@@ -338,6 +483,55 @@ impl ApiVersion {
     pub fn is_compatible_with(self, other: ApiVersion) -> bool {
         self.major == other.major && other.minor >= self.minor
     }
+
+    /// Parses a `"major.minor"` string such as `"1.4"`.
+    ///
+    /// Returns `None` unless both parts are non-empty runs of ASCII digits
+    /// that fit in a `u32`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (major, minor) = value.split_once('.')?;
+        let number = |part: &str| {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            part.parse().ok()
+        };
+        Some(Self::new(number(major)?, number(minor)?))
+    }
+
+    /// Picks the highest version both sides support.
+    ///
+    /// A version listed by one side is a candidate when the other side lists
+    /// a version it [is compatible with](Self::is_compatible_with): same major,
+    /// equal or higher minor. Minor bumps are additive, so a side supporting
+    /// `1.4` also speaks `1.2`. Returns the greatest candidate (by major, then
+    /// minor), or `None` if no version is usable by both sides.
+    pub fn negotiate(
+        client_supported: &[ApiVersion],
+        server_supported: &[ApiVersion],
+    ) -> Option<ApiVersion> {
+        let usable_by = |version: &ApiVersion, other: &[ApiVersion]| {
+            other
+                .iter()
+                .any(|supported| version.is_compatible_with(*supported))
+        };
+        let client_candidates = client_supported
+            .iter()
+            .filter(|version| usable_by(version, server_supported));
+        let server_candidates = server_supported
+            .iter()
+            .filter(|version| usable_by(version, client_supported));
+        client_candidates.chain(server_candidates).copied().max()
+    }
+}
+
+impl std::str::FromStr for ApiVersion {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+            .ok_or_else(|| format!("invalid API version '{value}': expected 'major.minor'"))
+    }
 }
 
 impl std::fmt::Display for ApiVersion {
@@ -373,6 +567,36 @@ impl Timestamp {
     pub fn as_datetime(self) -> DateTime<Utc> {
         self.0
     }
+
+    /// Parses an RFC 3339 timestamp (the [`Display`](std::fmt::Display)
+    /// format), converting any offset to UTC. Returns `None` if `value` is
+    /// not valid RFC 3339.
+    pub fn parse_rfc3339(value: &str) -> Option<Self> {
+        DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|dt| Self(dt.with_timezone(&Utc)))
+    }
+
+    /// Returns the time since this timestamp, or zero if it is in the future.
+    pub fn elapsed(self) -> Duration {
+        Self::now().duration_since(self).unwrap_or(Duration::ZERO)
+    }
+
+    /// Returns the time from `earlier` to this timestamp, or `None` if
+    /// `earlier` is later than this timestamp.
+    pub fn duration_since(self, earlier: Timestamp) -> Option<Duration> {
+        (self.0 - earlier.0).to_std().ok()
+    }
+
+    /// Returns this timestamp moved `duration` into the future, saturating
+    /// at the latest representable time.
+    #[must_use]
+    pub fn add_duration(self, duration: Duration) -> Timestamp {
+        TimeDelta::from_std(duration)
+            .ok()
+            .and_then(|delta| self.0.checked_add_signed(delta))
+            .map_or(Self(DateTime::<Utc>::MAX_UTC), Self)
+    }
 }
 
 impl std::fmt::Display for Timestamp {
@@ -380,3 +604,7 @@ impl std::fmt::Display for Timestamp {
         write!(f, "{}", self.0.to_rfc3339())
     }
 }
+
+#[cfg(test)]
+#[path = "types_tests.rs"]
+mod tests;
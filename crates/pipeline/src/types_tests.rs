@@ -0,0 +1,457 @@
+use super::*;
+
+fn score(value: f64) -> SatisfactionScore {
+    SatisfactionScore::new(value).unwrap()
+}
+
+// ─── SatisfactionScore::weighted_mean ───────────────────────────────────────
+
+#[test]
+fn test_weighted_mean_equal_weights_returns_plain_mean() {
+    let pairs = [(score(0.25), 2.0), (score(0.75), 2.0), (score(0.5), 2.0)];
+
+    let mean = SatisfactionScore::weighted_mean(&pairs).unwrap();
+
+    assert!((mean.as_f64() - 0.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_weighted_mean_skewed_weights_favours_heavier_score() {
+    let pairs = [(score(1.0), 3.0), (score(0.0), 1.0)];
+
+    let mean = SatisfactionScore::weighted_mean(&pairs).unwrap();
+
+    assert!((mean.as_f64() - 0.75).abs() < 1e-12);
+}
+
+#[test]
+fn test_weighted_mean_zero_weight_pair_is_ignored() {
+    let pairs = [(score(0.25), 1.0), (score(1.0), 0.0)];
+
+    let mean = SatisfactionScore::weighted_mean(&pairs).unwrap();
+
+    assert!((mean.as_f64() - 0.25).abs() < 1e-12);
+}
+
+#[test]
+fn test_weighted_mean_zero_total_weight_returns_none() {
+    let pairs = [(score(0.5), 0.0), (score(0.9), 0.0)];
+
+    assert_eq!(SatisfactionScore::weighted_mean(&pairs), None);
+}
+
+#[test]
+fn test_weighted_mean_negative_weight_returns_none() {
+    let pairs = [(score(0.5), 2.0), (score(0.9), -1.0)];
+
+    assert_eq!(SatisfactionScore::weighted_mean(&pairs), None);
+}
+
+#[test]
+fn test_weighted_mean_non_finite_weight_returns_none() {
+    assert_eq!(
+        SatisfactionScore::weighted_mean(&[(score(0.5), f64::INFINITY)]),
+        None
+    );
+    assert_eq!(
+        SatisfactionScore::weighted_mean(&[(score(0.5), f64::NAN)]),
+        None
+    );
+}
+
+#[test]
+fn test_weighted_mean_empty_returns_none() {
+    assert_eq!(SatisfactionScore::weighted_mean(&[]), None);
+}
+
+#[test]
+fn test_weighted_mean_all_perfect_scores_stays_in_range() {
+    let pairs = [(score(1.0), 0.1), (score(1.0), 0.2), (score(1.0), 0.7)];
+
+    let mean = SatisfactionScore::weighted_mean(&pairs).unwrap();
+
+    assert!(mean.as_f64() <= 1.0);
+    assert!((mean.as_f64() - 1.0).abs() < 1e-12);
+}
+
+// ─── Timestamp durations ────────────────────────────────────────────────────
+
+#[test]
+fn test_elapsed_future_timestamp_saturates_at_zero() {
+    let future = Timestamp::now().add_duration(Duration::from_secs(3600));
+
+    assert_eq!(future.elapsed(), Duration::ZERO);
+}
+
+#[test]
+fn test_elapsed_past_timestamp_returns_at_least_the_offset() {
+    let now = Timestamp::now();
+    let past = Timestamp::from_utc(now.as_datetime() - TimeDelta::seconds(90));
+
+    assert!(past.elapsed() >= Duration::from_secs(90));
+}
+
+#[test]
+fn test_add_duration_round_trip_duration_since_returns_duration() {
+    let start = Timestamp::now();
+    let duration = Duration::from_millis(1_500);
+
+    let end = start.add_duration(duration);
+
+    assert_eq!(end.duration_since(start), Some(duration));
+}
+
+#[test]
+fn test_duration_since_later_timestamp_returns_none() {
+    let start = Timestamp::now();
+    let end = start.add_duration(Duration::from_secs(1));
+
+    assert_eq!(start.duration_since(end), None);
+}
+
+#[test]
+fn test_duration_since_same_timestamp_returns_zero() {
+    let start = Timestamp::now();
+
+    assert_eq!(start.duration_since(start), Some(Duration::ZERO));
+}
+
+#[test]
+fn test_add_duration_overflow_saturates_at_latest_time() {
+    let latest = Timestamp::now().add_duration(Duration::MAX);
+
+    assert_eq!(latest.as_datetime(), DateTime::<Utc>::MAX_UTC);
+}
+
+// ─── TokenCount subtraction ─────────────────────────────────────────────────
+
+#[test]
+fn test_checked_sub_smaller_rhs_returns_difference() {
+    assert_eq!(
+        TokenCount::new(1_000).checked_sub(TokenCount::new(400)),
+        Some(TokenCount::new(600))
+    );
+}
+
+#[test]
+fn test_checked_sub_equal_rhs_returns_zero() {
+    let count = TokenCount::new(250);
+
+    assert_eq!(count.checked_sub(count), Some(TokenCount::default()));
+}
+
+#[test]
+fn test_checked_sub_larger_rhs_returns_none() {
+    assert_eq!(TokenCount::new(10).checked_sub(TokenCount::new(11)), None);
+}
+
+#[test]
+fn test_saturating_sub_smaller_rhs_returns_difference() {
+    assert_eq!(
+        TokenCount::new(1_000).saturating_sub(TokenCount::new(400)),
+        TokenCount::new(600)
+    );
+}
+
+#[test]
+fn test_saturating_sub_larger_rhs_clamps_at_zero() {
+    assert!(TokenCount::new(10)
+        .saturating_sub(TokenCount::new(11))
+        .is_zero());
+}
+
+// ─── TokenCost arithmetic ───────────────────────────────────────────────────
+
+#[test]
+fn test_from_tokens_price_per_thousand_returns_prorated_cost() {
+    let cost = TokenCost::from_tokens(TokenCount::new(2_500), 0.004).unwrap();
+
+    assert!((cost.as_f64() - 0.01).abs() < 1e-12);
+}
+
+#[test]
+fn test_from_tokens_zero_tokens_returns_zero_cost() {
+    assert!(TokenCost::from_tokens(TokenCount::new(0), 15.0)
+        .unwrap()
+        .is_zero());
+}
+
+#[test]
+fn test_from_tokens_invalid_price_returns_none() {
+    let count = TokenCount::new(1_000);
+
+    assert_eq!(TokenCost::from_tokens(count, -0.5), None);
+    assert_eq!(TokenCost::from_tokens(count, f64::NAN), None);
+    assert_eq!(TokenCost::from_tokens(count, f64::INFINITY), None);
+}
+
+#[test]
+fn test_checked_mul_positive_factor_scales_cost() {
+    let cost = TokenCost::new(1.5).unwrap();
+
+    assert_eq!(cost.checked_mul(2.0), TokenCost::new(3.0));
+    assert_eq!(cost * 0.0, Some(TokenCost::zero()));
+}
+
+#[test]
+fn test_checked_mul_negative_or_nan_factor_returns_none() {
+    let cost = TokenCost::new(1.5).unwrap();
+
+    assert_eq!(cost.checked_mul(-1.0), None);
+    assert_eq!(cost * f64::NAN, None);
+}
+
+#[test]
+fn test_checked_mul_overflowing_product_returns_none() {
+    assert_eq!(TokenCost::new(f64::MAX).unwrap().checked_mul(2.0), None);
+}
+
+// ─── CostBudget headroom ────────────────────────────────────────────────────
+
+fn budget(limit: f64) -> CostBudget {
+    CostBudget::new(limit).unwrap()
+}
+
+#[test]
+fn test_remaining_partial_spend_returns_headroom() {
+    let remaining = budget(10.0).remaining(TokenCost::new(2.5).unwrap());
+
+    assert!((remaining.as_f64() - 7.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_remaining_no_spend_returns_whole_budget() {
+    assert_eq!(budget(4.0).remaining(TokenCost::zero()).as_f64(), 4.0);
+}
+
+#[test]
+fn test_remaining_overspent_returns_zero() {
+    assert!(budget(1.0)
+        .remaining(TokenCost::new(1.5).unwrap())
+        .is_zero());
+}
+
+#[test]
+fn test_fraction_used_partial_spend_returns_ratio() {
+    let fraction = budget(8.0).fraction_used(TokenCost::new(2.0).unwrap());
+
+    assert!((fraction - 0.25).abs() < 1e-12);
+}
+
+#[test]
+fn test_fraction_used_no_spend_returns_zero() {
+    assert_eq!(budget(8.0).fraction_used(TokenCost::zero()), 0.0);
+}
+
+#[test]
+fn test_fraction_used_overspent_exceeds_one() {
+    assert!(budget(2.0).fraction_used(TokenCost::new(3.0).unwrap()) > 1.0);
+}
+
+// ─── Diagnostic severity aggregation ────────────────────────────────────────
+
+fn diagnostic(severity: DiagnosticSeverity) -> Diagnostic {
+    Diagnostic {
+        artifact: None,
+        location: None,
+        severity,
+        category: DiagnosticCategory::new("style_violation").unwrap(),
+        message: "finding".to_string(),
+    }
+}
+
+fn mixed() -> Vec<Diagnostic> {
+    vec![
+        diagnostic(DiagnosticSeverity::Warning),
+        diagnostic(DiagnosticSeverity::Informational),
+        diagnostic(DiagnosticSeverity::Blocking),
+        diagnostic(DiagnosticSeverity::Warning),
+    ]
+}
+
+#[test]
+fn test_diagnostic_severity_ordering_blocking_above_warning_above_informational() {
+    assert!(DiagnosticSeverity::Blocking > DiagnosticSeverity::Warning);
+    assert!(DiagnosticSeverity::Warning > DiagnosticSeverity::Informational);
+    assert!(DiagnosticSeverity::Blocking > DiagnosticSeverity::Informational);
+}
+
+#[test]
+fn test_highest_severity_mixed_returns_blocking() {
+    assert_eq!(
+        highest_severity(&mixed()),
+        Some(DiagnosticSeverity::Blocking)
+    );
+}
+
+#[test]
+fn test_highest_severity_no_blocking_returns_warning() {
+    let diagnostics = vec![
+        diagnostic(DiagnosticSeverity::Informational),
+        diagnostic(DiagnosticSeverity::Warning),
+    ];
+
+    assert_eq!(
+        highest_severity(&diagnostics),
+        Some(DiagnosticSeverity::Warning)
+    );
+}
+
+#[test]
+fn test_highest_severity_empty_returns_none() {
+    assert_eq!(highest_severity(&[]), None);
+}
+
+#[test]
+fn test_has_blocking_mixed_returns_true() {
+    assert!(has_blocking(&mixed()));
+}
+
+#[test]
+fn test_has_blocking_warnings_only_returns_false() {
+    assert!(!has_blocking(&[diagnostic(DiagnosticSeverity::Warning)]));
+}
+
+#[test]
+fn test_has_blocking_empty_returns_false() {
+    assert!(!has_blocking(&[]));
+}
+
+#[test]
+fn test_count_by_severity_mixed_counts_each_severity_least_serious_first() {
+    let counts: Vec<_> = count_by_severity(&mixed()).into_iter().collect();
+
+    assert_eq!(
+        counts,
+        vec![
+            (DiagnosticSeverity::Informational, 1),
+            (DiagnosticSeverity::Warning, 2),
+            (DiagnosticSeverity::Blocking, 1),
+        ]
+    );
+}
+
+#[test]
+fn test_count_by_severity_omits_absent_severities() {
+    let counts = count_by_severity(&[diagnostic(DiagnosticSeverity::Warning)]);
+
+    assert_eq!(counts.len(), 1);
+    assert_eq!(counts.get(&DiagnosticSeverity::Blocking), None);
+}
+
+#[test]
+fn test_count_by_severity_empty_returns_empty_map() {
+    assert!(count_by_severity(&[]).is_empty());
+}
+
+// ─── DiagnosticCategory ─────────────────────────────────────────────────────
+
+#[test]
+fn test_diagnostic_category_from_static_matches_checked_constructor() {
+    assert_eq!(
+        DiagnosticCategory::from_static("injection"),
+        DiagnosticCategory::new("injection").unwrap()
+    );
+    assert_eq!(DiagnosticCategory::new(""), None);
+}
+
+// ─── ApiVersion ─────────────────────────────────────────────────────────────
+
+fn versions(values: &[(u32, u32)]) -> Vec<ApiVersion> {
+    values
+        .iter()
+        .map(|&(major, minor)| ApiVersion::new(major, minor))
+        .collect()
+}
+
+#[test]
+fn test_parse_major_minor_returns_version() {
+    assert_eq!(ApiVersion::parse("1.4"), Some(ApiVersion::new(1, 4)));
+    assert_eq!("10.0".parse::<ApiVersion>(), Ok(ApiVersion::new(10, 0)));
+}
+
+#[test]
+fn test_parse_malformed_returns_none() {
+    for value in [
+        "1",
+        "1.",
+        ".4",
+        "1.4.2",
+        "v1.4",
+        "1.-4",
+        " 1.4",
+        "99999999999.0",
+    ] {
+        assert_eq!(ApiVersion::parse(value), None, "{value}");
+    }
+}
+
+#[test]
+fn test_is_compatible_with_higher_minor_same_major_returns_true() {
+    let required = ApiVersion::new(1, 2);
+
+    assert!(required.is_compatible_with(ApiVersion::new(1, 4)));
+    assert!(!required.is_compatible_with(ApiVersion::new(1, 1)));
+    assert!(!required.is_compatible_with(ApiVersion::new(2, 2)));
+}
+
+#[test]
+fn test_negotiate_common_versions_returns_highest() {
+    let client = versions(&[(1, 0), (1, 2), (2, 0)]);
+    let server = versions(&[(1, 0), (1, 2)]);
+
+    assert_eq!(
+        ApiVersion::negotiate(&client, &server),
+        Some(ApiVersion::new(1, 2))
+    );
+}
+
+#[test]
+fn test_negotiate_server_newer_minor_returns_client_version() {
+    let client = versions(&[(1, 2)]);
+    let server = versions(&[(1, 4)]);
+
+    assert_eq!(
+        ApiVersion::negotiate(&client, &server),
+        Some(ApiVersion::new(1, 2))
+    );
+}
+
+#[test]
+fn test_negotiate_client_newer_minor_returns_server_version() {
+    let client = versions(&[(1, 5)]);
+    let server = versions(&[(1, 3)]);
+
+    assert_eq!(
+        ApiVersion::negotiate(&client, &server),
+        Some(ApiVersion::new(1, 3))
+    );
+}
+
+#[test]
+fn test_negotiate_majors_differ_returns_none() {
+    let client = versions(&[(1, 0), (1, 9)]);
+    let server = versions(&[(2, 0), (3, 1)]);
+
+    assert_eq!(ApiVersion::negotiate(&client, &server), None);
+}
+
+#[test]
+fn test_negotiate_no_overlap_or_empty_returns_none() {
+    assert_eq!(
+        ApiVersion::negotiate(&versions(&[(1, 0)]), &versions(&[(2, 0)])),
+        None
+    );
+    assert_eq!(ApiVersion::negotiate(&[], &versions(&[(1, 0)])), None);
+}
+
+#[test]
+fn test_negotiate_prefers_higher_major_when_both_compatible() {
+    let client = versions(&[(1, 3), (2, 1)]);
+    let server = versions(&[(1, 5), (2, 4)]);
+
+    assert_eq!(
+        ApiVersion::negotiate(&client, &server),
+        Some(ApiVersion::new(2, 1))
+    );
+}